@@ -457,6 +457,10 @@ pub fn to_pattern2<'a>(
             malformed_pattern(env, problem, region)
         }
 
+        List(..) | ListRest(..) => todo!("list patterns"),
+
+        Tuple(..) => todo!("tuple patterns"),
+
         SpaceBefore(sub_pattern, _) | SpaceAfter(sub_pattern, _) => {
             return to_pattern2(env, scope, pattern_type, sub_pattern, region)
         }
@@ -458,6 +458,7 @@ pub fn to_type2<'a>(
 
             Type2::TagUnion(tag_types, ext_type)
         }
+        Tuple { .. } => todo!("tuple types"),
         As(
             loc_inner,
             _spaces,
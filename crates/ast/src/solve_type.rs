@@ -10,7 +10,7 @@ use roc_region::all::{Loc, Region};
 use roc_solve::module::Solved;
 use roc_types::subs::{
     self, AliasVariables, Content, Descriptor, FlatType, Mark, OptVariable, Rank, RecordFields,
-    Subs, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
+    Subs, SubsSlice, TupleElems, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
 };
 use roc_types::types::{
     gather_fields_unsorted_iter, Alias, AliasKind, Category, ErrorType, PatternCategory,
@@ -1371,6 +1371,19 @@ fn adjust_rank_content(
 
                 EmptyTagUnion => Rank::toplevel(),
 
+                EmptyTuple => group_rank,
+
+                Tuple(elems, ext_var) => {
+                    let mut rank = adjust_rank(subs, young_mark, visit_mark, group_rank, *ext_var);
+
+                    for index in elems.iter_variables() {
+                        let var = subs[index];
+                        rank = rank.max(adjust_rank(subs, young_mark, visit_mark, group_rank, var));
+                    }
+
+                    rank
+                }
+
                 Record(fields, ext_var) => {
                     let mut rank = adjust_rank(subs, young_mark, visit_mark, group_rank, *ext_var);
 
@@ -1562,7 +1575,16 @@ fn instantiate_rigids_help(
                     }
                 }
 
-                EmptyRecord | EmptyTagUnion | Erroneous(_) => {}
+                EmptyRecord | EmptyTuple | EmptyTagUnion | Erroneous(_) => {}
+
+                Tuple(elems, ext_var) => {
+                    for index in elems.iter_variables() {
+                        let var = subs[index];
+                        instantiate_rigids_help(subs, max_rank, pools, var);
+                    }
+
+                    instantiate_rigids_help(subs, max_rank, pools, ext_var);
+                }
 
                 Record(fields, ext_var) => {
                     for index in fields.iter_variables() {
@@ -1618,7 +1640,8 @@ fn instantiate_rigids_help(
 
         RigidAbleVar(name, ability) => {
             // what it's all about: convert the rigid var into a flex var
-            subs.set(copy, make_descriptor(FlexAbleVar(Some(name), ability)));
+            let abilities = subs.new_ability_set([ability]);
+            subs.set(copy, make_descriptor(FlexAbleVar(Some(name), abilities)));
         }
 
         Alias(_, args, real_type_var, _) => {
@@ -1742,7 +1765,35 @@ fn deep_copy_var_help(
                     Func(arg_vars, new_closure_var, new_ret_var)
                 }
 
-                same @ EmptyRecord | same @ EmptyTagUnion | same @ Erroneous(_) => same,
+                same @ EmptyRecord
+                | same @ EmptyTuple
+                | same @ EmptyTagUnion
+                | same @ Erroneous(_) => same,
+
+                Tuple(elems, ext_var) => {
+                    let mut new_vars = Vec::with_capacity(elems.len());
+
+                    for index in elems.iter_variables() {
+                        let var = subs[index];
+                        let copy_var = deep_copy_var_help(subs, max_rank, pools, var);
+
+                        new_vars.push(copy_var);
+                    }
+
+                    let variables_start = subs.variables.len() as u32;
+                    subs.variables.extend(new_vars);
+
+                    let tuple_elems = TupleElems {
+                        length: elems.length,
+                        elem_index_start: elems.elem_index_start,
+                        variables_start,
+                    };
+
+                    Tuple(
+                        tuple_elems,
+                        deep_copy_var_help(subs, max_rank, pools, ext_var),
+                    )
+                }
 
                 Record(fields, ext_var) => {
                     let record_fields = {
@@ -1888,7 +1939,8 @@ fn deep_copy_var_help(
         }
 
         RigidAbleVar(name, ability) => {
-            subs.set(copy, make_descriptor(FlexAbleVar(Some(name), ability)));
+            let abilities = subs.new_ability_set([ability]);
+            subs.set(copy, make_descriptor(FlexAbleVar(Some(name), abilities)));
 
             copy
         }
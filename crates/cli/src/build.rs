@@ -1,5 +1,6 @@
 use bumpalo::Bump;
 use roc_build::{
+    host_cache::HostCache,
     link::{link, preprocess_host_wasm32, rebuild_host, LinkType, LinkingStrategy},
     program::{self, Problems},
 };
@@ -139,6 +140,10 @@ pub fn build_file<'a>(
         binary_path.set_extension(extension);
     }
 
+    if link_type == LinkType::Dylib && linking_strategy == LinkingStrategy::Surgical {
+        binary_path.set_extension("so");
+    }
+
     let host_input_path = if let EntryPoint::Executable { platform_path, .. } = &loaded.entry_point
     {
         cwd.join(platform_path)
@@ -182,6 +187,7 @@ pub fn build_file<'a>(
     let rebuild_thread = spawn_rebuild_thread(
         opt_level,
         linking_strategy,
+        link_type,
         prebuilt,
         host_input_path.clone(),
         preprocessed_host_path.clone(),
@@ -253,19 +259,16 @@ pub fn build_file<'a>(
     let interns = loaded.interns.clone();
 
     enum HostRebuildTiming {
-        BeforeApp(u128),
-        ConcurrentWithApp(JoinHandle<u128>),
+        BeforeApp(HostRebuild),
+        ConcurrentWithApp(JoinHandle<HostRebuild>),
     }
 
     let rebuild_timing = if linking_strategy == LinkingStrategy::Additive {
-        let rebuild_duration = rebuild_thread.join().unwrap();
+        let rebuild = rebuild_thread.join().unwrap();
         if emit_timings && !prebuilt {
-            println!(
-                "Finished rebuilding the platform in {} ms\n",
-                rebuild_duration
-            );
+            report_host_rebuild(&rebuild);
         }
-        HostRebuildTiming::BeforeApp(rebuild_duration)
+        HostRebuildTiming::BeforeApp(rebuild)
     } else {
         HostRebuildTiming::ConcurrentWithApp(rebuild_thread)
     };
@@ -319,12 +322,9 @@ pub fn build_file<'a>(
     }
 
     if let HostRebuildTiming::ConcurrentWithApp(thread) = rebuild_timing {
-        let rebuild_duration = thread.join().unwrap();
+        let rebuild = thread.join().unwrap();
         if emit_timings && !prebuilt {
-            println!(
-                "Finished rebuilding the platform in {} ms\n",
-                rebuild_duration
-            );
+            report_host_rebuild(&rebuild);
         }
     }
 
@@ -391,10 +391,31 @@ pub fn build_file<'a>(
     })
 }
 
+struct HostRebuild {
+    duration: u128,
+    /// Whether the host was unchanged since a previous build, so it didn't need rebuilding
+    from_cache: bool,
+}
+
+fn report_host_rebuild(rebuild: &HostRebuild) {
+    if rebuild.from_cache {
+        println!(
+            "Reused the cached platform in {} ms, since it hasn't changed\n",
+            rebuild.duration
+        );
+    } else {
+        println!(
+            "Finished rebuilding the platform in {} ms\n",
+            rebuild.duration
+        );
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn spawn_rebuild_thread(
     opt_level: OptLevel,
     linking_strategy: LinkingStrategy,
+    link_type: LinkType,
     prebuilt: bool,
     host_input_path: PathBuf,
     preprocessed_host_path: PathBuf,
@@ -402,19 +423,40 @@ fn spawn_rebuild_thread(
     target: &Triple,
     exported_symbols: Vec<String>,
     exported_closure_types: Vec<String>,
-) -> std::thread::JoinHandle<u128> {
+) -> std::thread::JoinHandle<HostRebuild> {
     let thread_local_target = target.clone();
     std::thread::spawn(move || {
-        if !prebuilt {
+        let rebuild_host_start = Instant::now();
+
+        // Only the surgical linker's preprocessed host is self-contained enough to be reused as-is.
+        let host_cache = if !prebuilt && linking_strategy == LinkingStrategy::Surgical {
+            // If the host's sources can't be read, leave it to the rebuild to report why.
+            HostCache::new(
+                host_input_path.as_path(),
+                &thread_local_target,
+                opt_level,
+                link_type,
+                &exported_symbols,
+                &exported_closure_types,
+            )
+            .ok()
+        } else {
+            None
+        };
+        let metadata_path = roc_linker::metadata_path(host_input_path.as_path());
+        let cached_outputs = [preprocessed_host_path.as_path(), metadata_path.as_path()];
+
+        let from_cache = match &host_cache {
+            Some(host_cache) => host_cache.restore(&cached_outputs),
+            None => false,
+        };
+
+        if !prebuilt && !from_cache {
             // Printing to stderr because we want stdout to contain only the output of the roc program.
             // We are aware of the trade-offs.
             // `cargo run` follows the same approach
             eprintln!("🔨 Rebuilding platform...");
-        }
-
-        let rebuild_host_start = Instant::now();
 
-        if !prebuilt {
             match linking_strategy {
                 LinkingStrategy::Additive => {
                     let host_dest = rebuild_host(
@@ -434,7 +476,14 @@ fn spawn_rebuild_thread(
                         preprocessed_host_path.as_path(),
                         exported_symbols,
                         exported_closure_types,
+                        link_type,
                     );
+
+                    if let Some(host_cache) = &host_cache {
+                        if let Err(err) = host_cache.store(&cached_outputs) {
+                            eprintln!("Could not cache the rebuilt platform: {}", err);
+                        }
+                    }
                 }
                 LinkingStrategy::Legacy => {
                     rebuild_host(
@@ -452,7 +501,10 @@ fn spawn_rebuild_thread(
         }
         let rebuild_host_end = rebuild_host_start.elapsed();
 
-        rebuild_host_end.as_millis()
+        HostRebuild {
+            duration: rebuild_host_end.as_millis(),
+            from_cache,
+        }
     })
 }
 
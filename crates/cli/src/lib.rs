@@ -26,6 +26,8 @@ use tempfile::TempDir;
 
 pub mod build;
 mod format;
+#[cfg(not(windows))]
+mod test_report;
 pub use format::format;
 
 use crate::build::{BuildFileError, BuildOrdering};
@@ -57,6 +59,13 @@ pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_PREBUILT: &str = "prebuilt-platform";
 pub const FLAG_CHECK: &str = "check";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_LIST: &str = "list";
+pub const FLAG_DOC: &str = "doc";
+pub const FLAG_REPORT_FORMAT: &str = "format";
+pub const FLAG_OUTPUT: &str = "output";
+pub const FLAG_BASE_URL: &str = "base-url";
+pub const FLAG_DOCS_FORMAT: &str = "format";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_FILE: &str = "GLUE_FILE";
@@ -106,7 +115,7 @@ pub fn build_app<'a>() -> Command<'a> {
 
     let flag_prebuilt = Arg::new(FLAG_PREBUILT)
         .long(FLAG_PREBUILT)
-        .help("Assume the platform has been prebuilt and skip rebuilding the platform\n(This is enabled by default when using `roc build` with a --target other than `--target <current machine>`.)")
+        .help("Assume the platform has been prebuilt and skip rebuilding the platform\n(This is enabled by default when using `roc build` with a --target other than `--target <current machine>`. Otherwise, a platform built for the surgical linker is only rebuilt when one of its .c, .h, .cc, .cpp, .cxx, .hh, .hpp, .m, .mm, .s, .S, .asm, .zig, .rs, .swift, .toml or .lock files changes.)")
         .possible_values(["true", "false"])
         .required(false);
 
@@ -183,6 +192,33 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run the expectations that match this pattern\n(This is either a location like `Main.roc` or `Main.roc:12`, or part of the name of the top-level def an expectation follows.)")
+                    .takes_value(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_LIST)
+                    .long(FLAG_LIST)
+                    .help("List the expectations that would run, without running them")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_DOC)
+                    .long(FLAG_DOC)
+                    .help("Also run the expectations in code examples in doc comments")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_REPORT_FORMAT)
+                    .long(FLAG_REPORT_FORMAT)
+                    .help("Report the results in a machine-readable format instead of as colored text")
+                    .possible_values(TestReportFormat::OPTIONS)
+                    .takes_value(true)
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file for the main module")
@@ -194,6 +230,7 @@ pub fn build_app<'a>() -> Command<'a> {
         )
         .subcommand(Command::new(CMD_REPL)
             .about("Launch the interactive Read Eval Print Loop (REPL)")
+            .arg(flag_dev.clone())
         )
         .subcommand(Command::new(CMD_RUN)
             .about("Run a .roc file even if it has build errors")
@@ -256,9 +293,35 @@ pub fn build_app<'a>() -> Command<'a> {
                 .arg(Arg::new(DIRECTORY_OR_FILES)
                     .multiple_values(true)
                     .required(false)
-                    .help("The directory or files to build documentation for")
+                    .help("The directory or files to build documentation for\n(If one of them is a platform module, the docs include the modules it exposes and its README.)")
                     .allow_invalid_utf8(true)
                 )
+                .arg(
+                    Arg::new(FLAG_OUTPUT)
+                        .long(FLAG_OUTPUT)
+                        .help("The directory to write the documentation to")
+                        .takes_value(true)
+                        .allow_invalid_utf8(true)
+                        .default_value(roc_docs::DEFAULT_BUILD_DIR)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new(FLAG_BASE_URL)
+                        .long(FLAG_BASE_URL)
+                        .help("The path the documentation will be served from, e.g. /builtins for https://roc-lang.org/builtins")
+                        .takes_value(true)
+                        .default_value("/")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new(FLAG_DOCS_FORMAT)
+                        .long(FLAG_DOCS_FORMAT)
+                        .help("Write the documentation as html pages, or as a docs.json file for other tools to read")
+                        .possible_values(roc_docs::DocsFormat::OPTIONS)
+                        .takes_value(true)
+                        .default_value("html")
+                        .required(false),
+                )
         )
         .subcommand(Command::new(CMD_GLUE)
             .about("Generate glue code between a platform's Roc API and its host language")
@@ -270,7 +333,7 @@ pub fn build_app<'a>() -> Command<'a> {
             )
             .arg(
                 Arg::new(GLUE_FILE)
-                    .help("The filename for the generated glue code\n(Currently, this must be a .rs file because only Rust glue generation is supported so far.)")
+                    .help("The filename for the generated glue code\n(The extension picks the language: .rs for Rust, .h for C, or .zig for Zig.)")
                     .allow_invalid_utf8(true)
                     .required(true)
             )
@@ -315,6 +378,40 @@ pub enum FormatMode {
     CheckOnly,
 }
 
+/// The machine-readable formats `roc test` can report its results in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestReportFormat {
+    Json,
+    Junit,
+}
+
+impl TestReportFormat {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            TestReportFormat::Json => "json",
+            TestReportFormat::Junit => "junit",
+        }
+    }
+
+    /// NOTE keep up to date!
+    const OPTIONS: &'static [&'static str] = &[
+        TestReportFormat::Json.as_str(),
+        TestReportFormat::Junit.as_str(),
+    ];
+}
+
+impl std::str::FromStr for TestReportFormat {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "json" => Ok(TestReportFormat::Json),
+            "junit" => Ok(TestReportFormat::Junit),
+            _ => Err(format!("`roc test` can not report results as {}", string)),
+        }
+    }
+}
+
 #[cfg(windows)]
 pub fn test(_matches: &ArgMatches, _triple: Triple) -> io::Result<i32> {
     todo!("running tests does not work on windows right now")
@@ -322,10 +419,14 @@ pub fn test(_matches: &ArgMatches, _triple: Triple) -> io::Result<i32> {
 
 #[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
+    use roc_collections::MutMap;
     use roc_gen_llvm::llvm::build::LlvmBackendMode;
     use roc_load::{ExecutionMode, LoadConfig};
+    use roc_module::symbol::Symbol;
+    use roc_region::all::Region;
     use roc_target::TargetInfo;
     use std::time::Instant;
+    use test_report::ExpectLocation;
 
     let start_time = Instant::now();
     let arena = Bump::new();
@@ -372,6 +473,15 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         process::exit(1);
     }
 
+    let report_format = matches
+        .value_of(FLAG_REPORT_FORMAT)
+        .map(|format| format.parse::<TestReportFormat>().unwrap());
+
+    let render = match report_format {
+        Some(_) => roc_reporting::report::RenderTarget::Generic,
+        None => roc_reporting::report::RenderTarget::ColorTerminal,
+    };
+
     let arena = &arena;
     let target = &triple;
     let opt_level = opt_level;
@@ -382,10 +492,13 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
+        render,
         threading,
-        exec_mode: ExecutionMode::Test,
+        exec_mode: if matches.is_present(FLAG_DOC) {
+            ExecutionMode::TestWithDocs
+        } else {
+            ExecutionMode::Test
+        },
     };
     let loaded =
         roc_load::load_and_monomorphize(arena, path.to_path_buf(), subs_by_module, load_config)
@@ -393,17 +506,82 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
 
     let mut loaded = loaded;
     let mut expectations = std::mem::take(&mut loaded.expectations);
+    let preceding_defs = std::mem::take(&mut loaded.toplevel_expects.preceding_defs);
+
+    let mut locations: MutMap<_, _> = loaded
+        .toplevel_expects
+        .pure
+        .iter()
+        .chain(loaded.toplevel_expects.fx.iter())
+        .map(|(symbol, region)| {
+            let location = ExpectLocation::new(
+                *symbol,
+                *region,
+                &loaded.sources,
+                &loaded.interns,
+                &preceding_defs,
+            );
+
+            (*symbol, location)
+        })
+        .collect();
+
+    // Only the expects that match the filter are exposed by the generated code,
+    // and they run in the order they appear in the source.
+    let filter = matches.value_of(FLAG_FILTER);
+    let select = |expects: VecMap<Symbol, Region>| -> VecMap<Symbol, Region> {
+        let mut selected: Vec<_> = expects
+            .into_iter()
+            .filter(|(symbol, _)| match filter {
+                Some(pattern) => locations[symbol].matches(pattern),
+                None => true,
+            })
+            .collect();
+
+        selected.sort_by_key(|(symbol, region)| (&locations[symbol].path, region.start()));
+
+        selected.into_iter().collect()
+    };
+
+    loaded.toplevel_expects.pure = select(std::mem::take(&mut loaded.toplevel_expects.pure));
+    loaded.toplevel_expects.fx = select(std::mem::take(&mut loaded.toplevel_expects.fx));
+
+    locations.retain(|symbol, _| {
+        loaded.toplevel_expects.pure.contains_key(symbol)
+            || loaded.toplevel_expects.fx.contains_key(symbol)
+    });
+
+    if matches.is_present(FLAG_LIST) {
+        let mut listed: Vec<_> = locations.values().collect();
+        listed.sort_by_key(|location| (location.path.clone(), location.start_line));
+
+        test_report::write_list(&mut std::io::stdout(), &listed, report_format)?;
+
+        return Ok(0);
+    }
+
     let loaded = loaded;
 
     let interns = loaded.interns.clone();
 
-    let (lib, expects, layout_interner) = roc_repl_expect::run::expect_mono_module_to_dylib(
-        arena,
-        target.clone(),
-        loaded,
-        opt_level,
-        LlvmBackendMode::CliTest,
-    )
+    let (lib, expects, layout_interner) = match opt_level {
+        // The dev backend only generates code for x86_64 ELF so far, and can't look up every
+        // kind of value in an expect yet
+        OptLevel::Development
+            if matches!(target.architecture, Architecture::X86_64)
+                && matches!(target.binary_format, BinaryFormat::Elf)
+                && roc_repl_expect::run::dev_backend_supports_expects(&loaded) =>
+        {
+            roc_repl_expect::run::expect_mono_module_to_dylib_dev(arena, target.clone(), loaded)
+        }
+        _ => roc_repl_expect::run::expect_mono_module_to_dylib(
+            arena,
+            target.clone(),
+            loaded,
+            opt_level,
+            LlvmBackendMode::CliTest,
+        ),
+    }
     .unwrap();
 
     let arena = &bumpalo::Bump::new();
@@ -411,23 +589,49 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
 
     let mut writer = std::io::stdout();
 
-    let (failed, passed) = roc_repl_expect::run::run_expects(
-        &mut writer,
-        roc_reporting::report::RenderTarget::ColorTerminal,
-        arena,
-        interns,
-        &layout_interner.into_global(),
-        &lib,
-        &mut expectations,
-        expects,
-    )
-    .unwrap();
+    let (failed, passed) = match report_format {
+        Some(format) => {
+            let results = roc_repl_expect::run::run_expects_with_results(
+                render,
+                arena,
+                interns,
+                &layout_interner.into_global(),
+                &lib,
+                &mut expectations,
+                expects,
+            )?;
+
+            test_report::write_results(
+                &mut writer,
+                format,
+                &locations,
+                &results,
+                start_time.elapsed(),
+            )?;
+
+            let failed = results.iter().filter(|result| !result.passed).count();
+
+            (failed, results.len() - failed)
+        }
+        None => roc_repl_expect::run::run_expects(
+            &mut writer,
+            render,
+            arena,
+            interns,
+            &layout_interner.into_global(),
+            &lib,
+            &mut expectations,
+            expects,
+        )?,
+    };
 
     let total_time = start_time.elapsed();
 
     if failed == 0 && passed == 0 {
-        // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+        if report_format.is_none() {
+            // TODO print this in a more nicely formatted way!
+            println!("No expectations were found.");
+        }
 
         // If no tests ran, treat that as an error. This is perhaps
         // briefly annoying at the very beginning of a project when
@@ -436,16 +640,18 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         // running tests altogether!
         Ok(2)
     } else {
-        let failed_color = if failed == 0 {
-            32 // green
-        } else {
-            31 // red
-        };
-
-        println!(
-            "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
-            total_time.as_millis(),
-        );
+        if report_format.is_none() {
+            let failed_color = if failed == 0 {
+                32 // green
+            } else {
+                31 // red
+            };
+
+            println!(
+                "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
+                total_time.as_millis(),
+            );
+        }
 
         Ok((failed > 0) as i32)
     }
@@ -3,12 +3,14 @@ use roc_cli::build::check_file;
 use roc_cli::{
     build_app, format, test, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK, CMD_DEV,
     CMD_DOCS, CMD_EDIT, CMD_FORMAT, CMD_GLUE, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VERSION,
-    DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_LIB, FLAG_NO_LINK, FLAG_TARGET, FLAG_TIME, GLUE_FILE,
-    ROC_FILE,
+    DIRECTORY_OR_FILES, FLAG_BASE_URL, FLAG_CHECK, FLAG_DEV, FLAG_DOCS_FORMAT, FLAG_LIB,
+    FLAG_NO_LINK, FLAG_OUTPUT, FLAG_TARGET, FLAG_TIME, GLUE_FILE, ROC_FILE,
 };
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, generate_docs_json, DocsFormat};
 use roc_error_macros::user_error;
+use roc_glue::GlueLanguage;
 use roc_load::{LoadingProblem, Threading};
+use roc_mono::ir::OptLevel;
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -85,12 +87,13 @@ fn main() -> io::Result<()> {
             let input_path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
             let output_path = Path::new(matches.value_of_os(GLUE_FILE).unwrap());
 
-            if Some("rs") == output_path.extension().and_then(OsStr::to_str) {
-                roc_glue::generate(input_path, output_path)
-            } else {
-                eprintln!("Currently, `roc glue` only supports generating Rust glue files (with the .rs extension). In the future, the plan is to decouple `roc glue` from any particular output format, by having it accept a second .roc file which gets executed as a plugin to generate glue code for any desired language. However, this has not yet been implemented, and for now only .rs is supported.");
+            match GlueLanguage::from_path(output_path) {
+                Some(language) => roc_glue::generate(input_path, output_path, language),
+                None => {
+                    eprintln!("Currently, `roc glue` only supports generating Rust (.rs), C (.h) and Zig (.zig) glue files, and picks the language from the output file's extension. In the future, the plan is to decouple `roc glue` from any particular output format, by having it accept a second .roc file which gets executed as a plugin to generate glue code for any desired language. However, this has not yet been implemented.");
 
-                Ok(1)
+                    Ok(1)
+                }
             }
         }
         Some((CMD_BUILD, matches)) => {
@@ -171,9 +174,15 @@ fn main() -> io::Result<()> {
                 }
             }
         }
-        Some((CMD_REPL, _)) => {
+        Some((CMD_REPL, matches)) => {
             {
-                roc_repl_cli::main()?;
+                let opt_level = if matches.is_present(FLAG_DEV) {
+                    OptLevel::Development
+                } else {
+                    OptLevel::Normal
+                };
+
+                roc_repl_cli::main(opt_level)?;
 
                 // Exit 0 if the repl exited normally
                 Ok(0)
@@ -226,9 +235,23 @@ fn main() -> io::Result<()> {
                 roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
             }
 
-            generate_docs_html(roc_files);
+            let output_dir = Path::new(matches.value_of_os(FLAG_OUTPUT).unwrap());
+            let base_url = matches.value_of(FLAG_BASE_URL).unwrap();
+            let format = matches.value_of_t(FLAG_DOCS_FORMAT).unwrap();
 
-            Ok(0)
+            let result = match format {
+                DocsFormat::Html => generate_docs_html(roc_files, output_dir, base_url),
+                DocsFormat::Json => generate_docs_json(roc_files, output_dir),
+            };
+
+            match result {
+                Ok(()) => Ok(0),
+                Err(err) => {
+                    eprintln!("{}", err);
+
+                    Ok(1)
+                }
+            }
         }
         Some((CMD_FORMAT, matches)) => {
             let maybe_values = matches.values_of_os(DIRECTORY_OR_FILES);
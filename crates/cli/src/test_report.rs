@@ -0,0 +1,266 @@
+use roc_collections::{MutMap, VecMap};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_region::all::{LineInfo, Region};
+use roc_repl_expect::run::ExpectResult;
+use serde_json::json;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::TestReportFormat;
+
+/// Where a top-level expect is defined, and the name of the def it follows
+#[derive(Debug)]
+pub struct ExpectLocation {
+    pub module_name: String,
+    pub path: PathBuf,
+    /// 1-based, like the line numbers in reports
+    pub start_line: u32,
+    pub end_line: u32,
+    pub def_name: Option<String>,
+}
+
+impl ExpectLocation {
+    pub fn new(
+        symbol: Symbol,
+        region: Region,
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+        interns: &Interns,
+        preceding_defs: &VecMap<Symbol, Symbol>,
+    ) -> Self {
+        let module_id = symbol.module_id();
+        let (path, source) = &sources[&module_id];
+        let line_region = LineInfo::new(source).convert_region(region);
+
+        Self {
+            module_name: interns.module_name(module_id).as_str().to_string(),
+            path: path.clone(),
+            start_line: line_region.start().line + 1,
+            end_line: line_region.end().line + 1,
+            def_name: preceding_defs
+                .get(&symbol)
+                .map(|def_symbol| def_symbol.as_str(interns).to_string()),
+        }
+    }
+
+    /// Whether the `--filter` pattern selects this expect. The pattern is either a
+    /// location like `Main.roc` or `Main.roc:12`, or part of the name of the def the
+    /// expect follows.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let (file, line) = match pattern.rsplit_once(':') {
+            Some((file, line)) => match line.parse::<u32>() {
+                Ok(line) => (file, Some(line)),
+                Err(_) => (pattern, None),
+            },
+            None => (pattern, None),
+        };
+
+        let location_matches = file.ends_with(".roc")
+            && self.path.ends_with(file)
+            && match line {
+                Some(line) => (self.start_line..=self.end_line).contains(&line),
+                None => true,
+            };
+
+        let name_matches = match &self.def_name {
+            Some(def_name) => def_name.contains(pattern),
+            None => false,
+        };
+
+        location_matches || name_matches
+    }
+
+    /// The name of a JUnit test case. Several expects can follow the same def,
+    /// so it includes the line number too.
+    fn test_case_name(&self) -> String {
+        let name = self.def_name.as_deref().unwrap_or("expect");
+
+        format!("{} (line {})", name, self.start_line)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "name": self.def_name,
+            "module": self.module_name,
+            "file": self.path,
+            "line": self.start_line,
+        })
+    }
+}
+
+/// Prints the expects that `roc test --list` selected, without running them
+pub fn write_list(
+    writer: &mut impl Write,
+    locations: &[&ExpectLocation],
+    format: Option<TestReportFormat>,
+) -> io::Result<()> {
+    match format {
+        Some(TestReportFormat::Json) => {
+            let expectations: Vec<_> = locations.iter().map(|loc| loc.to_json()).collect();
+
+            serde_json::to_writer_pretty(&mut *writer, &json!({ "expectations": expectations }))?;
+
+            writeln!(writer)
+        }
+        Some(TestReportFormat::Junit) | None => {
+            for location in locations {
+                match &location.def_name {
+                    Some(def_name) => writeln!(
+                        writer,
+                        "{}:{} {}",
+                        location.path.display(),
+                        location.start_line,
+                        def_name
+                    )?,
+                    None => writeln!(
+                        writer,
+                        "{}:{}",
+                        location.path.display(),
+                        location.start_line
+                    )?,
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Prints the outcome of every expect that ran, in the given format
+pub fn write_results(
+    writer: &mut impl Write,
+    format: TestReportFormat,
+    locations: &MutMap<Symbol, ExpectLocation>,
+    results: &[ExpectResult],
+    total_time: Duration,
+) -> io::Result<()> {
+    match format {
+        TestReportFormat::Json => write_json(writer, locations, results, total_time),
+        TestReportFormat::Junit => write_junit(writer, locations, results, total_time),
+    }
+}
+
+fn write_json(
+    writer: &mut impl Write,
+    locations: &MutMap<Symbol, ExpectLocation>,
+    results: &[ExpectResult],
+    total_time: Duration,
+) -> io::Result<()> {
+    let failed = results.iter().filter(|result| !result.passed).count();
+    let passed = results.len() - failed;
+
+    let expectations: Vec<_> = results
+        .iter()
+        .map(|result| {
+            let mut value = locations[&result.expect.symbol].to_json();
+
+            value["status"] = json!(if result.passed { "passed" } else { "failed" });
+            value["output"] = json!(String::from_utf8_lossy(&result.output));
+
+            value
+        })
+        .collect();
+
+    let report = json!({
+        "passed": passed,
+        "failed": failed,
+        "duration_ms": total_time.as_millis() as u64,
+        "expectations": expectations,
+    });
+
+    serde_json::to_writer_pretty(&mut *writer, &report)?;
+
+    writeln!(writer)
+}
+
+fn write_junit(
+    writer: &mut impl Write,
+    locations: &MutMap<Symbol, ExpectLocation>,
+    results: &[ExpectResult],
+    total_time: Duration,
+) -> io::Result<()> {
+    // one test suite per module, in the order the modules were first run
+    let mut suites: VecMap<&str, Vec<&ExpectResult>> = VecMap::default();
+
+    for result in results {
+        let module_name = locations[&result.expect.symbol].module_name.as_str();
+
+        suites.get_or_insert(module_name, Vec::new).push(result);
+    }
+
+    let failed = results.iter().filter(|result| !result.passed).count();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="roc test" tests="{}" failures="{}" time="{:.3}">"#,
+        results.len(),
+        failed,
+        total_time.as_secs_f64()
+    )?;
+
+    for (module_name, suite) in suites.iter() {
+        let suite_failed = suite.iter().filter(|result| !result.passed).count();
+
+        writeln!(
+            writer,
+            r#"  <testsuite name="{}" tests="{}" failures="{}">"#,
+            xml_escape(module_name),
+            suite.len(),
+            suite_failed
+        )?;
+
+        for result in suite {
+            let location = &locations[&result.expect.symbol];
+            let output = String::from_utf8_lossy(&result.output);
+
+            writeln!(
+                writer,
+                r#"    <testcase name="{}" classname="{}" file="{}" line="{}">"#,
+                xml_escape(&location.test_case_name()),
+                xml_escape(module_name),
+                xml_escape(&location.path.to_string_lossy()),
+                location.start_line
+            )?;
+
+            if !result.passed {
+                writeln!(
+                    writer,
+                    r#"      <failure message="This expectation failed">{}</failure>"#,
+                    xml_escape(&output)
+                )?;
+            } else if !output.is_empty() {
+                writeln!(
+                    writer,
+                    "      <system-out>{}</system-out>",
+                    xml_escape(&output)
+                )?;
+            }
+
+            writeln!(writer, "    </testcase>")?;
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")
+}
+
+fn xml_escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+
+    for c in string.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 does not allow most control characters, even escaped
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
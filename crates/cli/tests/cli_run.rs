@@ -18,7 +18,7 @@ mod cli_run {
     use indoc::indoc;
     use once_cell::sync::Lazy;
     use parking_lot::{Mutex, RwLock};
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_RUN};
+    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_RUN, CMD_TEST};
     use roc_test_utils::assert_multiline_str_eq;
     use serial_test::serial;
     use std::iter;
@@ -30,6 +30,9 @@ mod cli_run {
     const OPTIMIZE_FLAG: &str = concatcp!("--", roc_cli::FLAG_OPTIMIZE);
     const LINKER_FLAG: &str = concatcp!("--", roc_cli::FLAG_LINKER);
     const CHECK_FLAG: &str = concatcp!("--", roc_cli::FLAG_CHECK);
+    const FILTER_FLAG: &str = concatcp!("--", roc_cli::FLAG_FILTER);
+    const LIST_FLAG: &str = concatcp!("--", roc_cli::FLAG_LIST);
+    const REPORT_FORMAT_FLAG: &str = concatcp!("--", roc_cli::FLAG_REPORT_FORMAT);
     const PREBUILT_PLATFORM: &str = concatcp!("--", roc_cli::FLAG_PREBUILT, "=true");
     #[allow(dead_code)]
     const TARGET_FLAG: &str = concatcp!("--", roc_cli::FLAG_TARGET);
@@ -1145,6 +1148,54 @@ mod cli_run {
         // This doesn't fail, since only "Formatted.roc" and non-roc files are present in this folder
         check_format_check_as_expected(&fixtures_dir("format/formatted_directory"), true);
     }
+
+    #[test]
+    fn test_list() {
+        let file = fixture_file("expects", "Expects.roc");
+        let out = run_roc([CMD_TEST, LIST_FLAG, file.to_str().unwrap()], &[]);
+
+        assert!(out.status.success());
+        assert_multiline_str_eq!(
+            out.stdout.as_str(),
+            format!("{0}:7 add\n{0}:11 double\n{0}:14 double\n", file.display()).as_str()
+        );
+    }
+
+    #[test]
+    fn test_list_filter() {
+        let file = fixture_file("expects", "Expects.roc");
+        let file_str = file.to_str().unwrap();
+
+        let out = run_roc([CMD_TEST, LIST_FLAG, FILTER_FLAG, "doub", file_str], &[]);
+        assert_multiline_str_eq!(
+            out.stdout.as_str(),
+            format!("{0}:11 double\n{0}:14 double\n", file.display()).as_str()
+        );
+
+        let out = run_roc(
+            [CMD_TEST, LIST_FLAG, FILTER_FLAG, "Expects.roc:7", file_str],
+            &[],
+        );
+        assert_multiline_str_eq!(
+            out.stdout.as_str(),
+            format!("{}:7 add\n", file.display()).as_str()
+        );
+    }
+
+    #[test]
+    fn test_json_report() {
+        let file = fixture_file("expects", "Expects.roc");
+        let out = run_roc(
+            [CMD_TEST, REPORT_FORMAT_FLAG, "json", file.to_str().unwrap()],
+            &[],
+        );
+
+        // one of the expectations fails
+        assert_eq!(out.status.code(), Some(1));
+        assert!(out.stdout.contains(r#""passed": 2"#));
+        assert!(out.stdout.contains(r#""failed": 1"#));
+        assert!(out.stdout.contains(r#""status": "failed""#));
+    }
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,14 @@
+interface Expects
+    exposes [add, double]
+    imports []
+
+add = \a, b -> a + b
+
+expect add 1 2 == 3
+
+double = \x -> x * 2
+
+expect double 2 == 4
+
+# this one fails on purpose
+expect double 3 == 5
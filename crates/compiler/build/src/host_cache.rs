@@ -0,0 +1,250 @@
+//! Reusing a preprocessed host between builds, when nothing it gets built from has changed.
+
+use crate::link::LinkType;
+use roc_builtins::bitcode;
+use roc_collections::all::WyHash;
+use roc_mono::ir::OptLevel;
+use std::cmp::Reverse;
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+use target_lexicon::Triple;
+
+/// Where the cache lives, in the platform's directory
+const HOST_CACHE_DIR: &str = "host-cache";
+
+/// Each entry is a full copy of the host, so only the most recent few are kept around.
+const MAX_ENTRIES: usize = 8;
+
+/// Extensions of the files hosts get built from. The help of `--prebuilt-platform` lists these
+/// too, since changes to any other files don't get the host rebuilt.
+const HOST_SOURCE_EXTENSIONS: &[&str] = &[
+    "c", "h", "cc", "cpp", "cxx", "hh", "hpp", "m", "mm", "s", "S", "asm", "zig", "rs", "swift",
+    "toml", "lock",
+];
+
+/// Directories of build outputs, which hosts don't get built from
+const IGNORED_DIRS: &[&str] = &[HOST_CACHE_DIR, "target", "zig-cache", "zig-out"];
+
+pub struct HostCache {
+    entry_dir: PathBuf,
+}
+
+impl HostCache {
+    /// Finds the cache entry for the host at `host_input_path`, keyed on the contents of its
+    /// sources and everything else that goes into preprocessing it.
+    pub fn new(
+        host_input_path: &Path,
+        target: &Triple,
+        opt_level: OptLevel,
+        link_type: LinkType,
+        exposed_symbols: &[String],
+        exposed_closure_types: &[String],
+    ) -> io::Result<Self> {
+        let platform_dir = host_input_path.parent().unwrap_or_else(|| Path::new("."));
+        // The key is a directory name in the platform, so it has to stay the same across
+        // compiler builds, which rules out std's hashers.
+        let mut hasher = WyHash::default();
+
+        hash_bytes(&mut hasher, target.to_string().as_bytes());
+        hash_bytes(
+            &mut hasher,
+            format!("{:?} {:?}", opt_level, link_type).as_bytes(),
+        );
+
+        // These become the symbols of the dummy app the host gets linked against.
+        let mut exposed_symbols = exposed_symbols.to_vec();
+        exposed_symbols.sort_unstable();
+        hash_strings(&mut hasher, &exposed_symbols);
+
+        let mut exposed_closure_types = exposed_closure_types.to_vec();
+        exposed_closure_types.sort_unstable();
+        hash_strings(&mut hasher, &exposed_closure_types);
+
+        // Hosts get the builtins linked in, which change along with the compiler. Without the
+        // lib directory there are none to read, so the compiler itself stands in for them.
+        let builtins = match roc_utils::get_lib_path() {
+            Some(_) => fs::read(bitcode::get_builtins_host_obj_path())?,
+            None => fs::read(std::env::current_exe()?)?,
+        };
+        hash_bytes(&mut hasher, &builtins);
+
+        hash_host_sources(platform_dir, platform_dir, &mut hasher)?;
+
+        Ok(Self {
+            entry_dir: platform_dir
+                .join(HOST_CACHE_DIR)
+                .join(format!("{:016x}", hasher.finish())),
+        })
+    }
+
+    /// Copies the cached files over `outputs`, returning whether there was an entry for all of them
+    pub fn restore(&self, outputs: &[&Path]) -> bool {
+        let cached: Vec<_> = outputs
+            .iter()
+            .map(|output| self.entry_dir.join(output.file_name().unwrap()))
+            .collect();
+
+        if !cached.iter().all(|path| path.is_file()) {
+            return false;
+        }
+
+        cached
+            .iter()
+            .zip(outputs)
+            .all(|(cached, output)| fs::copy(cached, output).is_ok())
+    }
+
+    /// Saves `outputs` for the next build with the same inputs
+    pub fn store(&self, outputs: &[&Path]) -> io::Result<()> {
+        let cache_dir = self.entry_dir.parent().unwrap();
+        fs::create_dir_all(cache_dir)?;
+
+        // Fill in a temporary directory first, so an interrupted build can't leave a partial entry behind.
+        let staging_dir = tempfile::tempdir_in(cache_dir)?;
+
+        for output in outputs {
+            fs::copy(output, staging_dir.path().join(output.file_name().unwrap()))?;
+        }
+
+        if self.entry_dir.exists() {
+            fs::remove_dir_all(&self.entry_dir)?;
+        }
+
+        // Once it's been renamed, there's nothing left for the staging directory to clean up.
+        fs::rename(staging_dir.path(), &self.entry_dir)?;
+
+        remove_old_entries(cache_dir)
+    }
+}
+
+/// Hashes the bytes along with their length, so consecutive ones can't run into each other
+fn hash_bytes(hasher: &mut WyHash, bytes: &[u8]) {
+    hasher.write(&(bytes.len() as u64).to_le_bytes());
+    hasher.write(bytes);
+}
+
+fn hash_strings(hasher: &mut WyHash, strings: &[String]) {
+    hasher.write(&(strings.len() as u64).to_le_bytes());
+
+    for string in strings {
+        hash_bytes(hasher, string.as_bytes());
+    }
+}
+
+fn hash_host_sources(root: &Path, dir: &Path, hasher: &mut WyHash) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+
+        if entry.file_type()?.is_dir() {
+            let name = name.to_string_lossy();
+
+            if !name.starts_with('.') && !IGNORED_DIRS.contains(&name.as_ref()) {
+                hash_host_sources(root, &path, hasher)?;
+            }
+        } else if matches!(path.extension(), Some(ext) if HOST_SOURCE_EXTENSIONS.iter().any(|source_ext| ext == *source_ext))
+        {
+            let relative_path = path.strip_prefix(root).unwrap().to_string_lossy();
+
+            hash_bytes(hasher, relative_path.as_bytes());
+            hash_bytes(hasher, &fs::read(&path)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_old_entries(cache_dir: &Path) -> io::Result<()> {
+    let mut entries = Vec::new();
+
+    for entry in fs::read_dir(cache_dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_dir() {
+            entries.push((metadata.modified()?, entry.path()));
+        }
+    }
+
+    if entries.len() > MAX_ENTRIES {
+        // Newest first
+        entries.sort_unstable_by_key(|(modified, _)| Reverse(*modified));
+
+        for (_, path) in entries.drain(MAX_ENTRIES..) {
+            fs::remove_dir_all(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_for(host_input_path: &Path) -> HostCache {
+        HostCache::new(
+            host_input_path,
+            &Triple::host(),
+            OptLevel::Normal,
+            LinkType::Executable,
+            &["mainForHost".to_string()],
+            &[],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn restores_stored_host() {
+        let dir = tempfile::tempdir().unwrap();
+        let host_input_path = dir.path().join("host.o");
+        let preprocessed = dir.path().join("preprocessedhost");
+        let metadata = dir.path().join("metadata");
+
+        fs::write(dir.path().join("host.c"), "int main() { return 0; }").unwrap();
+
+        let cache = cache_for(&host_input_path);
+        assert!(!cache.restore(&[&preprocessed, &metadata]));
+
+        fs::write(&preprocessed, "host").unwrap();
+        fs::write(&metadata, "metadata").unwrap();
+        cache.store(&[&preprocessed, &metadata]).unwrap();
+
+        fs::remove_file(&preprocessed).unwrap();
+        fs::remove_file(&metadata).unwrap();
+
+        let cache = cache_for(&host_input_path);
+        assert!(cache.restore(&[&preprocessed, &metadata]));
+        assert_eq!(fs::read_to_string(&preprocessed).unwrap(), "host");
+        assert_eq!(fs::read_to_string(&metadata).unwrap(), "metadata");
+    }
+
+    #[test]
+    fn host_source_changes_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let host_input_path = dir.path().join("host.o");
+        let preprocessed = dir.path().join("preprocessedhost");
+
+        fs::write(dir.path().join("host.c"), "int main() { return 0; }").unwrap();
+        fs::write(&preprocessed, "host").unwrap();
+        cache_for(&host_input_path).store(&[&preprocessed]).unwrap();
+
+        // Build outputs don't count as sources.
+        fs::write(dir.path().join("dynhost"), "dynhost").unwrap();
+        fs::create_dir(dir.path().join("zig-cache")).unwrap();
+        fs::write(dir.path().join("zig-cache").join("host.zig"), "").unwrap();
+        assert!(cache_for(&host_input_path).restore(&[&preprocessed]));
+
+        fs::write(dir.path().join("host.c"), "int main() { return 1; }").unwrap();
+        assert!(!cache_for(&host_input_path).restore(&[&preprocessed]));
+
+        cache_for(&host_input_path).store(&[&preprocessed]).unwrap();
+        fs::write(dir.path().join("roc.cpp"), "").unwrap();
+        assert!(!cache_for(&host_input_path).restore(&[&preprocessed]));
+    }
+}
@@ -775,6 +775,36 @@ pub fn rebuild_host(
     host_dest
 }
 
+/// Rebuilds the host as a shared library which dynamically links against `shared_lib_path`,
+/// for the surgical linker to turn into the app's shared library.
+pub fn rebuild_dylib_host(
+    opt_level: OptLevel,
+    target: &Triple,
+    host_input_path: &Path,
+    shared_lib_path: &Path,
+) -> PathBuf {
+    let host_obj = rebuild_host(opt_level, target, host_input_path, None);
+    let dynhost = host_input_path.with_file_name("dynhost");
+    let builtins_host_obj = bitcode::get_builtins_host_obj_path();
+
+    let inputs = [
+        host_obj.to_str().unwrap(),
+        shared_lib_path.to_str().unwrap(),
+        builtins_host_obj.as_str(),
+    ];
+
+    let (child, output_path) = link(target, dynhost.clone(), &inputs, LinkType::Dylib)
+        .unwrap_or_else(|e| internal_error!("Failed to spawn the linker: {}", e));
+    let output = child.wait_with_output().unwrap();
+
+    validate_output("dynhost", "ld", output);
+
+    // The linker names shared libraries after their version, which the preprocessed host doesn't have.
+    std::fs::rename(output_path, &dynhost).unwrap_or_else(|e| internal_error!("{}", e));
+
+    dynhost
+}
+
 fn nix_path_opt() -> Option<String> {
     env::var_os("NIX_GLIBC_PATH").map(|path| path.into_string().unwrap())
 }
@@ -1301,6 +1331,32 @@ pub fn llvm_module_to_dylib(
     unsafe { Library::new(path) }
 }
 
+pub fn dev_object_to_dylib(object: &[u8], target: &Triple) -> Result<Library, Error> {
+    let dir = tempfile::tempdir().unwrap();
+    let app_o_file = dir.path().join("app.o");
+
+    std::fs::write(&app_o_file, object).expect("Writing .o file failed");
+
+    // The dev backend does not include the builtins, so link them into the dylib too
+    let (mut child, dylib_path) = link(
+        target,
+        app_o_file.clone(),
+        &[
+            app_o_file.to_str().unwrap(),
+            &bitcode::get_builtins_host_obj_path(),
+        ],
+        LinkType::Dylib,
+    )
+    .unwrap();
+
+    child.wait().unwrap();
+
+    // Load the dylib
+    let path = dylib_path.as_path().to_str().unwrap();
+
+    unsafe { Library::new(path) }
+}
+
 pub fn preprocess_host_wasm32(host_input_path: &Path, preprocessed_host_path: &Path) {
     let host_input = host_input_path.to_str().unwrap();
     let output_file = preprocessed_host_path.to_str().unwrap();
@@ -559,6 +559,7 @@ fn gen_from_mono_module_dev_assembly(
         exposed_to_host: exposed_to_host.values.keys().copied().collect(),
         lazy_literals,
         generate_allocators,
+        runs_expects: false,
     };
 
     let module_object = roc_gen_dev::build_module(&env, &mut interns, target, procedures);
@@ -5,6 +5,7 @@
 // and substantial portions of the software.
 const std = @import("std");
 const str = @import("str.zig");
+const RocList = @import("list.zig").RocList;
 const mem = std.mem;
 
 pub fn wyhash(seed: u64, bytes: ?[*]const u8, length: usize) callconv(.C) u64 {
@@ -20,6 +21,15 @@ pub fn wyhash_rocstr(seed: u64, input: str.RocStr) callconv(.C) u64 {
     return wyhash_hash(seed, input.asSlice());
 }
 
+pub fn wyhash_roclist(input: RocList, seed: u64) callconv(.C) u64 {
+    if (input.bytes) |bytes| {
+        return wyhash_hash(seed, bytes[0..input.len()]);
+    } else {
+        // an empty list must still mix in the seed, so don't use `wyhash` here
+        return wyhash_hash(seed, &[_]u8{});
+    }
+}
+
 const primes = [_]u64{
     0xa0761d6478bd642f,
     0xe7037ed1a0b428db,
@@ -55,6 +55,13 @@ comptime {
     exportListFn(list.listIsUnique, "is_unique");
 }
 
+// Dict Module
+const hash = @import("hash.zig");
+
+comptime {
+    exportDictFn(hash.wyhash_roclist, "hash_bytes");
+}
+
 // Num Module
 const num = @import("num.zig");
 
@@ -1,8 +1,22 @@
 interface Bool
-    exposes [Bool, true, false, and, or, not, isEq, isNotEq]
+    exposes [Bool, Eq, true, false, and, or, not, isEq, isNotEq, structuralEq, structuralNotEq]
     imports []
 
-Bool := [True, False]
+## A type that can be compared for equality.
+##
+## Two values `a` and `b` are considered equal when `isEq a b` is `Bool.true`.
+## The `==` and `!=` operators are shorthand for [isEq] and [isNotEq].
+##
+## `Eq` can be derived for records, tag unions, lists, strings, and numbers,
+## as long as all the types they contain are `Eq` as well. Opaque types can
+## either derive `Eq` with `has [Eq]`, or provide their own `isEq`.
+##
+## Functions cannot be compared for equality, so no type containing a function
+## implements `Eq`.
+Eq has
+    isEq : a, a -> Bool | a has Eq
+
+Bool := [True, False] has [Eq]
 
 ## The boolean true value.
 true : Bool
@@ -67,25 +81,24 @@ or : Bool, Bool -> Bool
 ## Returns `Bool.false` when given `Bool.true`, and vice versa.
 not : Bool -> Bool
 
+## Returns `Bool.false` if the two values are equal according to [isEq], and
+## `Bool.true` otherwise.
+##
+## `a != b` is shorthand for `Bool.isNotEq a b`.
+isNotEq : a, a -> Bool | a has Eq
+isNotEq = \a, b -> not (isEq a b)
+
 ## Returns `Bool.true` if the two values are *structurally equal*, and `Bool.false` otherwise.
 ##
-## `a == b` is shorthand for `Bool.isEq a b`
+## This is the implementation of [isEq] used for types that derive [Eq].
 ##
 ## Structural equality works as follows:
 ##
 ## 1. Tags are equal if they have the same tag name, and also their contents (if any) are equal.
 ## 2. Records are equal if all their fields are equal.
 ## 3. Collections ([Str], [List], [Dict], and [Set]) are equal if they are the same length, and also all their corresponding elements are equal.
-## 4. [Num](Num#Num) values are equal if their numbers are equal, with one exception: if both arguments to `isEq` are *NaN*, then `isEq` returns `Bool.false`. See `Num.isNaN` for more about *NaN*.
-##
-## Note that `isEq` takes `'val` instead of `val`, which means `isEq` does not
-## accept arguments whose types contain functions.
-isEq : a, a -> Bool
+## 4. [Num](Num#Num) values are equal if their numbers are equal, with one exception: if both arguments to `structuralEq` are *NaN*, then `structuralEq` returns `Bool.false`. See `Num.isNaN` for more about *NaN*.
+structuralEq : a, a -> Bool
 
-## Calls [isEq] on the given values, then calls [not] on the result.
-##
-## `a != b` is shorthand for `Bool.isNotEq a b`
-##
-## Note that `isNotEq` takes `'val` instead of `val`, which means `isNotEq` does not
-## accept arguments whose types contain functions.
-isNotEq : a, a -> Bool
+## Calls [structuralEq] on the given values, then calls [not] on the result.
+structuralNotEq : a, a -> Bool
@@ -20,6 +20,7 @@ interface Dict
         Bool.{ Bool },
         Result.{ Result },
         List,
+        Hash.{ Hash, Hasher },
     ]
 
 ## A [dictionary](https://en.wikipedia.org/wiki/Associative_array) that lets you can associate keys with values.
@@ -67,114 +68,415 @@ interface Dict
 ##
 ## ### Equality
 ##
-## When comparing two dictionaries for equality, they are `==` only if their both their contents and their
-## orderings match. This preserves the property that if `dict1 == dict2`, you should be able to rely on
-## `fn dict1 == fn dict2` also being `Bool.true`, even if `fn` relies on the dictionary's ordering.
-Dict k v := List [Pair k v]
+## Two dictionaries are `==` if they have the same keys, each associated with an equal value. Their ordering
+## does not matter, so removing an element and inserting it again gives back an equal dictionary.
+Dict k v := {
+    data : List [Pair k v],
+    dataIndices : List Nat,
+    metadata : List I8,
+    deletedSlots : Nat,
+}
+     has [Eq { isEq: dictIsEq }]
+
+# Two dictionaries are equal when they have the same entries. Their order is left out, since
+# [remove] reorders entries, and so is the index, since it depends on how the dictionary was built.
+dictIsEq : Dict k v, Dict k v -> Bool | k has Hash, v has Eq
+dictIsEq = \xs, ys ->
+    if len xs != len ys then
+        Bool.false
+    else
+        @Dict { data } = xs
+
+        List.walkUntil data Bool.true \_, Pair k xVal ->
+            when get ys k is
+                Ok yVal if yVal == xVal -> Continue Bool.true
+                _ -> Break Bool.false
 
 ## An empty dictionary.
 empty : Dict k v
-empty = @Dict []
+empty = @Dict { data: [], dataIndices: [], metadata: [], deletedSlots: 0 }
 
 withCapacity : Nat -> Dict k v
-withCapacity = \n -> @Dict (List.withCapacity n)
+withCapacity = \n ->
+    slots = slotsForSize n
 
-get : Dict k v, k -> Result v [KeyNotFound]*
-get = \@Dict list, needle ->
-    when List.findFirst list (\Pair key _ -> key == needle) is
-        Ok (Pair _ v) ->
-            Ok v
+    @Dict {
+        data: List.withCapacity n,
+        dataIndices: List.repeat 0 slots,
+        metadata: List.repeat emptySlot slots,
+        deletedSlots: 0,
+    }
+
+get : Dict k v, k -> Result v [KeyNotFound]* | k has Hash
+get = \@Dict inner, key ->
+    when find (@Dict inner) key is
+        Ok found ->
+            when List.get inner.data found.dataIndex is
+                Ok (Pair _ v) ->
+                    Ok v
+
+                Err OutOfBounds ->
+                    Err KeyNotFound
 
         Err NotFound ->
             Err KeyNotFound
 
 walk : Dict k v, state, (state, k, v -> state) -> state
-walk = \@Dict list, initialState, transform ->
-    List.walk list initialState (\state, Pair k v -> transform state k v)
+walk = \@Dict { data }, initialState, transform ->
+    List.walk data initialState (\state, Pair k v -> transform state k v)
 
-insert : Dict k v, k, v -> Dict k v
-insert = \@Dict list, k, v ->
-    when List.findFirstIndex list (\Pair key _ -> key == k) is
-        Err NotFound ->
-            insertFresh (@Dict list) k v
-
-        Ok index ->
-            list
-            |> List.set index (Pair k v)
-            |> @Dict
+insert : Dict k v, k, v -> Dict k v | k has Hash
+insert = \dict, key, value ->
+    dict
+    |> reserveIndex 1
+    |> insertIndexed key value
 
 len : Dict k v -> Nat
-len = \@Dict list ->
-    List.len list
-
-remove : Dict k v, k -> Dict k v
-remove = \@Dict list, key ->
-    when List.findFirstIndex list (\Pair k _ -> k == key) is
-        Err NotFound ->
-            @Dict list
+len = \@Dict { data } ->
+    List.len data
 
-        Ok index ->
-            lastIndex = List.len list - 1
+remove : Dict k v, k -> Dict k v | k has Hash
+remove = \dict, key ->
+    dict
+    |> reserveIndex 0
+    |> removeIndexed key
 
-            list
-            |> List.swap index lastIndex
-            |> List.dropLast
-            |> @Dict
-
-contains : Dict k v, k -> Bool
-contains = \@Dict list, needle ->
-    step = \_, Pair key _val ->
-        if key == needle then
-            Break {}
-        else
-            Continue {}
+contains : Dict k v, k -> Bool | k has Hash
+contains = \dict, key ->
+    Result.isOk (find dict key)
 
-    when List.iterate list {} step is
-        Continue _ -> Bool.false
-        Break _ -> Bool.true
-
-single : k, v -> Dict k v
+single : k, v -> Dict k v | k has Hash
 single = \key, value ->
-    @Dict [Pair key value]
+    insert empty key value
 
 ## Returns a [List] of the dictionary's keys.
 keys : Dict k v -> List k
-keys = \@Dict list ->
-    List.map list (\Pair k _ -> k)
+keys = \@Dict { data } ->
+    List.map data (\Pair k _ -> k)
 
 ## Returns a [List] of the Dict's values
 values : Dict k v -> List v
-values = \@Dict list ->
-    List.map list (\Pair _ v -> v)
+values = \@Dict { data } ->
+    List.map data (\Pair _ v -> v)
 
 # union : Dict k v, Dict k v -> Dict k v
-insertAll : Dict k v, Dict k v -> Dict k v
-insertAll = \xs, @Dict ys ->
-    List.walk ys xs (\state, Pair k v -> Dict.insertIfVacant state k v)
+insertAll : Dict k v, Dict k v -> Dict k v | k has Hash
+insertAll = \xs, @Dict { data } ->
+    List.walk data xs (\state, Pair k v -> insertIfVacant state k v)
 
 # intersection : Dict k v, Dict k v -> Dict k v
-keepShared : Dict k v, Dict k v -> Dict k v
-keepShared = \@Dict xs, ys ->
-    List.keepIf xs (\Pair k _ -> Dict.contains ys k)
-    |> @Dict
+keepShared : Dict k v, Dict k v -> Dict k v | k has Hash
+keepShared = \xs, ys ->
+    walk xs empty \state, k, v ->
+        if contains ys k then
+            insert state k v
+        else
+            state
 
 # difference : Dict k v, Dict k v -> Dict k v
-removeAll : Dict k v, Dict k v -> Dict k v
-removeAll = \xs, @Dict ys ->
-    List.walk ys xs (\state, Pair k _ -> Dict.remove state k)
+removeAll : Dict k v, Dict k v -> Dict k v | k has Hash
+removeAll = \xs, @Dict { data } ->
+    List.walk data xs (\state, Pair k _ -> remove state k)
 
-## Internal helper function to insert a new association
-##
-## Precondition: `k` should not exist in the Dict yet.
-insertFresh : Dict k v, k, v -> Dict k v
-insertFresh = \@Dict list, k, v ->
-    list
-    |> List.append (Pair k v)
-    |> @Dict
-
-insertIfVacant : Dict k v, k, v -> Dict k v
+insertIfVacant : Dict k v, k, v -> Dict k v | k has Hash
 insertIfVacant = \dict, key, value ->
-    if Dict.contains dict key then
+    if contains dict key then
         dict
     else
-        Dict.insert dict key value
+        insert dict key value
+
+# A Dict is an open addressing hash table. Its entries are stored densely in `data`, in insertion order.
+#
+# `metadata` and `dataIndices` form the index into `data`; their length is always either zero or
+# a power of two. A slot of `metadata` is either `emptySlot`, `deletedSlot`, or it holds the lowest
+# 7 bits of the hash of the key that is stored in `data` at the slot's position in `dataIndices`.
+# `deletedSlots` is the number of `deletedSlot`s in `metadata`.
+#
+# A Dict with an empty `metadata` is unindexed: lookups scan `data`, and the index is built the first
+# time the Dict is modified. This is the case for `Dict.empty`, but also for dictionaries that were
+# constructed by the host, which cannot compute the hashes of their keys.
+emptySlot : I8
+emptySlot = -128
+
+deletedSlot : I8
+deletedSlot = -2
+
+## Internal helper function to find the entry of a key.
+##
+## Returns the slot of the index that points to the entry, and the position of the entry in `data`.
+## The slot is meaningless for an unindexed dictionary.
+find : Dict k v, k -> Result { slotIndex : Nat, dataIndex : Nat } [NotFound] | k has Hash
+find = \@Dict { data, dataIndices, metadata }, key ->
+    if List.isEmpty metadata then
+        List.findFirstIndex data (\Pair k _ -> k == key)
+        |> Result.map \dataIndex -> { slotIndex: 0, dataIndex }
+    else
+        hash = hashKey key
+
+        findHelp metadata dataIndices data (h2 hash) key (h1Slot hash metadata) 0
+
+findHelp : List I8, List Nat, List [Pair k v], I8, k, Nat, Nat -> Result { slotIndex : Nat, dataIndex : Nat } [NotFound] | k has Eq
+findHelp = \metadata, dataIndices, data, h2Key, key, slotIndex, probes ->
+    if probes == List.len metadata then
+        # Every slot has been checked.
+        Err NotFound
+    else
+        when List.get metadata slotIndex is
+            Ok md if md == emptySlot ->
+                Err NotFound
+
+            Ok md if md == h2Key ->
+                dataIndex = List.get dataIndices slotIndex |> Result.withDefault 0
+
+                when List.get data dataIndex is
+                    Ok (Pair k _) if k == key ->
+                        Ok { slotIndex, dataIndex }
+
+                    _ ->
+                        findHelp metadata dataIndices data h2Key key (nextSlot slotIndex metadata) (probes + 1)
+
+            Ok _ ->
+                findHelp metadata dataIndices data h2Key key (nextSlot slotIndex metadata) (probes + 1)
+
+            Err OutOfBounds ->
+                Err NotFound
+
+## Internal helper function to find the first slot, starting from the given one, that can hold a new entry.
+##
+## Precondition: the index must have at least one empty or deleted slot.
+findFreeSlot : List I8, Nat -> Nat
+findFreeSlot = \metadata, slotIndex ->
+    when List.get metadata slotIndex is
+        Ok md if md >= 0 ->
+            findFreeSlot metadata (nextSlot slotIndex metadata)
+
+        _ ->
+            slotIndex
+
+## Internal helper function to insert into a dictionary that has room in its index for one more entry.
+insertIndexed : Dict k v, k, v -> Dict k v | k has Hash
+insertIndexed = \@Dict { data, dataIndices, metadata, deletedSlots }, key, value ->
+    hash = hashKey key
+    h2Key = h2 hash
+    startSlot = h1Slot hash metadata
+
+    when findHelp metadata dataIndices data h2Key key startSlot 0 is
+        Ok { dataIndex } ->
+            @Dict {
+                data: List.set data dataIndex (Pair key value),
+                dataIndices,
+                metadata,
+                deletedSlots,
+            }
+
+        Err NotFound ->
+            slotIndex = findFreeSlot metadata startSlot
+            dataIndex = List.len data
+            newDeletedSlots =
+                when List.get metadata slotIndex is
+                    Ok md if md == deletedSlot -> deletedSlots - 1
+                    _ -> deletedSlots
+
+            @Dict {
+                data: List.append data (Pair key value),
+                dataIndices: List.set dataIndices slotIndex dataIndex,
+                metadata: List.set metadata slotIndex h2Key,
+                deletedSlots: newDeletedSlots,
+            }
+
+## Internal helper function to remove from an indexed dictionary.
+##
+## Like [List.dropAt] would, this moves the last entry of `data` into the place of the removed entry,
+## so the slot that points to the last entry is updated as well.
+removeIndexed : Dict k v, k -> Dict k v | k has Hash
+removeIndexed = \@Dict { data, dataIndices, metadata, deletedSlots }, key ->
+    hash = hashKey key
+
+    when findHelp metadata dataIndices data (h2 hash) key (h1Slot hash metadata) 0 is
+        Ok { slotIndex, dataIndex } ->
+            lastIndex = List.len data - 1
+            newDataIndices =
+                when List.get data lastIndex is
+                    Ok (Pair lastKey _) if dataIndex != lastIndex ->
+                        lastHash = hashKey lastKey
+
+                        when findHelp metadata dataIndices data (h2 lastHash) lastKey (h1Slot lastHash metadata) 0 is
+                            Ok last ->
+                                List.set dataIndices last.slotIndex dataIndex
+
+                            Err NotFound ->
+                                dataIndices
+
+                    _ ->
+                        dataIndices
+
+            @Dict {
+                data: data |> List.swap dataIndex lastIndex |> List.dropLast,
+                dataIndices: newDataIndices,
+                metadata: List.set metadata slotIndex deletedSlot,
+                deletedSlots: deletedSlots + 1,
+            }
+
+        Err NotFound ->
+            @Dict { data, dataIndices, metadata, deletedSlots }
+
+## Internal helper function to make sure the index has room for the given number of additional entries.
+##
+## The index is rebuilt when it is missing or too full, which also clears out its deleted slots.
+reserveIndex : Dict k v, Nat -> Dict k v | k has Hash
+reserveIndex = \@Dict { data, dataIndices, metadata, deletedSlots }, additional ->
+    usedSlots = List.len data + deletedSlots + additional
+
+    if List.isEmpty metadata || usedSlots > maxLoad (List.len metadata) then
+        rehash data (List.len data + additional)
+    else
+        @Dict { data, dataIndices, metadata, deletedSlots }
+
+## Internal helper function to build a fresh index for the given entries, with room for `size` entries.
+rehash : List [Pair k v], Nat -> Dict k v | k has Hash
+rehash = \data, size ->
+    slots = slotsForSize size
+
+    rehashHelp data (List.repeat emptySlot slots) (List.repeat 0 slots) 0
+
+rehashHelp : List [Pair k v], List I8, List Nat, Nat -> Dict k v | k has Hash
+rehashHelp = \data, metadata, dataIndices, dataIndex ->
+    when List.get data dataIndex is
+        Ok (Pair key _) ->
+            hash = hashKey key
+            slotIndex = findFreeSlot metadata (h1Slot hash metadata)
+
+            rehashHelp
+                data
+                (List.set metadata slotIndex (h2 hash))
+                (List.set dataIndices slotIndex dataIndex)
+                (dataIndex + 1)
+
+        Err OutOfBounds ->
+            @Dict { data, dataIndices, metadata, deletedSlots: 0 }
+
+# The number of slots of an index is always a power of two, and at least 8.
+slotsForSize : Nat -> Nat
+slotsForSize = \size ->
+    slotsForSizeHelp size 8
+
+slotsForSizeHelp : Nat, Nat -> Nat
+slotsForSizeHelp = \size, slots ->
+    if size <= maxLoad slots then
+        slots
+    else
+        slotsForSizeHelp size (slots * 2)
+
+# An index is rebuilt once more than 7/8 of its slots are in use.
+maxLoad : Nat -> Nat
+maxLoad = \slots ->
+    slots - Num.divTrunc slots 8
+
+nextSlot : Nat, List I8 -> Nat
+nextSlot = \slotIndex, metadata ->
+    Num.bitwiseAnd (slotIndex + 1) (List.len metadata - 1)
+
+# The upper bits of the hash pick the slot to start probing at.
+h1Slot : U64, List I8 -> Nat
+h1Slot = \hash, metadata ->
+    Num.bitwiseAnd (Num.toNat (Num.shiftRightZfBy hash 7)) (List.len metadata - 1)
+
+# The lower 7 bits of the hash are stored in the metadata of the slot.
+h2 : U64 -> I8
+h2 = \hash ->
+    Num.toI8 (Num.bitwiseAnd hash 0b0111_1111)
+
+hashKey : k -> U64 | k has Hash
+hashKey = \key ->
+    createLowLevelHasher {}
+    |> Hash.hash key
+    |> Hash.complete
+
+# This is the hasher that backs every Dict. Bytes are hashed with wyhash through a lowlevel,
+# and integers are mixed in with the same multiply-and-xor step that wyhash uses.
+LowLevelHasher := U64 has [Hasher { addBytes: lowLevelAddBytes, addU8: lowLevelAddU8, addU16: lowLevelAddU16, addU32: lowLevelAddU32, addU64: lowLevelAddU64, addU128: lowLevelAddU128, complete: lowLevelComplete }]
+
+# The seed is fixed, which keeps the ordering of the index the same across runs.
+createLowLevelHasher : {} -> LowLevelHasher
+createLowLevelHasher = \{} -> @LowLevelHasher wyp0
+
+hashBytesLowlevel : List U8, U64 -> U64
+
+lowLevelAddBytes : LowLevelHasher, List U8 -> LowLevelHasher
+lowLevelAddBytes = \@LowLevelHasher state, bytes ->
+    @LowLevelHasher (hashBytesLowlevel bytes state)
+
+lowLevelAddU8 : LowLevelHasher, U8 -> LowLevelHasher
+lowLevelAddU8 = \hasher, n ->
+    lowLevelAddU64 hasher (Num.toU64 n)
+
+lowLevelAddU16 : LowLevelHasher, U16 -> LowLevelHasher
+lowLevelAddU16 = \hasher, n ->
+    lowLevelAddU64 hasher (Num.toU64 n)
+
+lowLevelAddU32 : LowLevelHasher, U32 -> LowLevelHasher
+lowLevelAddU32 = \hasher, n ->
+    lowLevelAddU64 hasher (Num.toU64 n)
+
+lowLevelAddU64 : LowLevelHasher, U64 -> LowLevelHasher
+lowLevelAddU64 = \@LowLevelHasher state, n ->
+    @LowLevelHasher (wymix (Num.bitwiseXor state wyp0) (Num.bitwiseXor n wyp1))
+
+lowLevelAddU128 : LowLevelHasher, U128 -> LowLevelHasher
+lowLevelAddU128 = \hasher, n ->
+    lower = Num.toU64 n
+    upper = Num.toU64 (Num.shiftRightZfBy n 64)
+
+    hasher
+    |> lowLevelAddU64 lower
+    |> lowLevelAddU64 upper
+
+lowLevelComplete : LowLevelHasher -> U64
+lowLevelComplete = \@LowLevelHasher state -> state
+
+wyp0 : U64
+wyp0 = 0xa0761d6478bd642f
+
+wyp1 : U64
+wyp1 = 0xe7037ed1a0b428db
+
+# Multiplies the two numbers into a 128-bit result, and xors its upper and lower halves.
+# The multiplication is done in 32-bit pieces, because not every backend supports 128-bit integers.
+wymix : U64, U64 -> U64
+wymix = \a, b ->
+    aLo = Num.bitwiseAnd a 0xffff_ffff
+    aHi = Num.shiftRightZfBy a 32
+    bLo = Num.bitwiseAnd b 0xffff_ffff
+    bHi = Num.shiftRightZfBy b 32
+
+    loLo = aLo * bLo
+    hiLo = aHi * bLo
+    loHi = aLo * bHi
+    hiHi = aHi * bHi
+
+    # None of these additions can overflow.
+    cross = Num.shiftRightZfBy loLo 32 + Num.bitwiseAnd hiLo 0xffff_ffff + loHi
+    upper = Num.shiftRightZfBy hiLo 32 + Num.shiftRightZfBy cross 32 + hiHi
+    lower = Num.bitwiseOr (Num.shiftLeftBy cross 32) (Num.bitwiseAnd loLo 0xffff_ffff)
+
+    Num.bitwiseXor lower upper
+
+# Inserting an existing key replaces its value.
+expect
+    dict =
+        empty
+        |> insert "foo" 1
+        |> insert "bar" 2
+        |> insert "foo" 3
+
+    get dict "foo" == Ok 3 && len dict == 2
+
+# Removing entries keeps the index pointing at the right entries.
+expect
+    dict =
+        List.range 0 100
+        |> List.walk empty (\state, n -> insert state n n)
+        |> remove 10
+        |> remove 99
+
+    len dict == 98 && !(contains dict 10) && get dict 50 == Ok 50 && get dict 98 == Ok 98
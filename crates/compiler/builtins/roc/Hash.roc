@@ -0,0 +1,98 @@
+interface Hash
+    exposes [
+        Hash,
+        Hasher,
+        hash,
+        addBytes,
+        addU8,
+        addU16,
+        addU32,
+        addU64,
+        addU128,
+        complete,
+        hashBool,
+        hashI8,
+        hashI16,
+        hashI32,
+        hashI64,
+        hashI128,
+        hashNat,
+        hashStrBytes,
+        hashList,
+    ]
+    imports [
+        Bool.{ Bool },
+        List,
+        Str,
+    ]
+
+## A value that can be hashed.
+##
+## [hash] adds a value into a [Hasher]. Note that [hash] does not produce a
+## hash value itself; the hasher must be [complete]d in order to extract the
+## hash value.
+Hash has
+    hash : hasher, a -> hasher | a has Hash, hasher has Hasher
+
+## Describes a hashing algorithm that is fed bytes and produces an integer hash.
+##
+## The [Hasher] ability describes general-purpose hashers. It only allows
+## emission of 64-bit unsigned integer hashes. It is not suitable for
+## cryptographically-secure hashing.
+##
+## Bytes and unsigned integers are added to a hasher with [addBytes] and
+## [addU8] through [addU128]; [complete] extracts the hash value from the
+## accumulated hash state.
+Hasher has
+    addBytes : a, List U8 -> a | a has Hasher
+    addU8 : a, U8 -> a | a has Hasher
+    addU16 : a, U16 -> a | a has Hasher
+    addU32 : a, U32 -> a | a has Hasher
+    addU64 : a, U64 -> a | a has Hasher
+    addU128 : a, U128 -> a | a has Hasher
+    complete : a -> U64 | a has Hasher
+
+## Adds a [Bool] to a hasher.
+hashBool : a, Bool -> a | a has Hasher
+hashBool = \hasher, b ->
+    asU8 = if b then 1 else 0
+
+    addU8 hasher asU8
+
+## Adds a single I8 to a hasher.
+hashI8 : a, I8 -> a | a has Hasher
+hashI8 = \hasher, n -> addU8 hasher (Num.toU8 n)
+
+## Adds a single I16 to a hasher.
+hashI16 : a, I16 -> a | a has Hasher
+hashI16 = \hasher, n -> addU16 hasher (Num.toU16 n)
+
+## Adds a single I32 to a hasher.
+hashI32 : a, I32 -> a | a has Hasher
+hashI32 = \hasher, n -> addU32 hasher (Num.toU32 n)
+
+## Adds a single I64 to a hasher.
+hashI64 : a, I64 -> a | a has Hasher
+hashI64 = \hasher, n -> addU64 hasher (Num.toU64 n)
+
+## Adds a single I128 to a hasher.
+hashI128 : a, I128 -> a | a has Hasher
+hashI128 = \hasher, n -> addU128 hasher (Num.toU128 n)
+
+## Adds a single Nat to a hasher.
+##
+## A [Nat] is always hashed as a [U64], so that its hash does not depend on the
+## pointer width of the target.
+hashNat : a, Nat -> a | a has Hasher
+hashNat = \hasher, n -> addU64 hasher (Num.toU64 n)
+
+## Adds a string into a [Hasher] by hashing its UTF-8 bytes.
+hashStrBytes : a, Str -> a | a has Hasher
+hashStrBytes = \hasher, s ->
+    addBytes hasher (Str.toUtf8 s)
+
+## Adds a list of [Hash]able elements to a [Hasher] by hashing each element.
+hashList : hasher, List a -> hasher | a has Hash, hasher has Hasher
+hashList = \hasher, lst ->
+    List.walk lst hasher \accumHasher, elem ->
+        hash accumHasher elem
@@ -1,8 +1,10 @@
 interface Json
     exposes [
         Json,
+        FieldNameMapping,
         toUtf8,
         fromUtf8,
+        jsonWithOptions,
     ]
     imports [
         List,
@@ -20,7 +22,12 @@ interface Json
         },
     ]
 
-Json := {} has [
+## How record field names are written to and read from JSON object keys. Roc field names are
+## camelCase, which `Default` keeps as they are; `SnakeCase` writes `fieldName` as `field_name`,
+## `PascalCase` as `FieldName` and `KebabCase` as `field-name`, and decoding reads them back.
+FieldNameMapping : [Default, SnakeCase, PascalCase, KebabCase]
+
+Json := { fieldNameMapping : FieldNameMapping } has [
          EncoderFormatting {
              u8: encodeU8,
              u16: encodeU16,
@@ -59,43 +66,50 @@ Json := {} has [
              string: decodeString,
              list: decodeList,
              record: decodeRecord,
+             tag: decodeTag,
          },
      ]
 
-toUtf8 = @Json {}
+toUtf8 = @Json { fieldNameMapping: Default }
+
+fromUtf8 = @Json { fieldNameMapping: Default }
 
-fromUtf8 = @Json {}
+## Creates a JSON format with the given options, using the defaults of `toUtf8` and `fromUtf8` for
+## any option that isn't given.
+jsonWithOptions : { fieldNameMapping ? FieldNameMapping } -> Json
+jsonWithOptions = \{ fieldNameMapping ? Default } ->
+    @Json { fieldNameMapping }
 
 numToBytes = \n ->
     n |> Num.toStr |> Str.toUtf8
 
-encodeU8 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU8 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU16 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU16 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU32 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU32 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU64 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU64 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeU128 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeU128 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI8 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI8 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI16 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI16 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI32 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI32 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI64 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI64 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeI128 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeI128 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeF32 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeF32 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeF64 = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeF64 = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeDec = \n -> Encode.custom \bytes, @Json {} -> List.concat bytes (numToBytes n)
+encodeDec = \n -> Encode.custom \bytes, @Json _ -> List.concat bytes (numToBytes n)
 
-encodeBool = \b -> Encode.custom \bytes, @Json {} ->
+encodeBool = \b -> Encode.custom \bytes, @Json _ ->
         if
             b
         then
@@ -103,15 +117,15 @@ encodeBool = \b -> Encode.custom \bytes, @Json {} ->
         else
             List.concat bytes (Str.toUtf8 "false")
 
-encodeString = \s -> Encode.custom \bytes, @Json {} ->
+encodeString = \s -> Encode.custom \bytes, @Json _ ->
         List.append bytes (Num.toU8 '"')
         |> List.concat (Str.toUtf8 s)
         |> List.append (Num.toU8 '"')
 
 encodeList = \lst, encodeElem ->
-    Encode.custom \bytes, @Json {} ->
+    Encode.custom \bytes, @Json { fieldNameMapping } ->
         writeList = \{ buffer, elemsLeft }, elem ->
-            bufferWithElem = appendWith buffer (encodeElem elem) (@Json {})
+            bufferWithElem = appendWith buffer (encodeElem elem) (@Json { fieldNameMapping })
             bufferWithSuffix =
                 if elemsLeft > 1 then
                     List.append bufferWithElem (Num.toU8 ',')
@@ -126,14 +140,14 @@ encodeList = \lst, encodeElem ->
         List.append withList (Num.toU8 ']')
 
 encodeRecord = \fields ->
-    Encode.custom \bytes, @Json {} ->
+    Encode.custom \bytes, @Json { fieldNameMapping } ->
         writeRecord = \{ buffer, fieldsLeft }, { key, value } ->
             bufferWithKeyValue =
                 List.append buffer (Num.toU8 '"')
-                |> List.concat (Str.toUtf8 key)
+                |> List.concat (toObjectName (Str.toUtf8 key) fieldNameMapping)
                 |> List.append (Num.toU8 '"')
                 |> List.append (Num.toU8 ':')
-                |> appendWith value (@Json {})
+                |> appendWith value (@Json { fieldNameMapping })
 
             bufferWithSuffix =
                 if fieldsLeft > 1 then
@@ -149,10 +163,10 @@ encodeRecord = \fields ->
         List.append bytesWithRecord (Num.toU8 '}')
 
 encodeTag = \name, payload ->
-    Encode.custom \bytes, @Json {} ->
+    Encode.custom \bytes, @Json { fieldNameMapping } ->
         # Idea: encode `A v1 v2` as `{"A": [v1, v2]}`
         writePayload = \{ buffer, itemsLeft }, encoder ->
-            bufferWithValue = appendWith buffer encoder (@Json {})
+            bufferWithValue = appendWith buffer encoder (@Json { fieldNameMapping })
             bufferWithSuffix =
                 if itemsLeft > 1 then
                     List.append bufferWithValue (Num.toU8 ',')
@@ -174,219 +188,457 @@ encodeTag = \name, payload ->
         List.append bytesWithPayload (Num.toU8 ']')
         |> List.append (Num.toU8 '}')
 
-takeWhile = \list, predicate ->
-    helper = \{ taken, rest } ->
-        when List.first rest is
-            Ok elem ->
-                if predicate elem then
-                    helper { taken: List.append taken elem, rest: List.split rest 1 |> .others }
-                else
-                    { taken, rest }
+# Writes a record field name as an object key, following the `FieldNameMapping`.
+toObjectName : List U8, FieldNameMapping -> List U8
+toObjectName = \fieldName, fieldNameMapping ->
+    when fieldNameMapping is
+        Default -> fieldName
+        SnakeCase -> splitWords fieldName (asciiByte '_')
+        PascalCase -> mapFirst fieldName toUppercase
+        KebabCase -> splitWords fieldName (asciiByte '-')
+
+# Reads a record field name from an object key, undoing `toObjectName`.
+fromObjectName : Str, FieldNameMapping -> Str
+fromObjectName = \objectName, fieldNameMapping ->
+    fieldName =
+        when fieldNameMapping is
+            Default -> Ok objectName
+            SnakeCase -> Str.toUtf8 objectName |> joinWords (asciiByte '_') |> Str.fromUtf8
+            PascalCase -> Str.toUtf8 objectName |> mapFirst toLowercase |> Str.fromUtf8
+            KebabCase -> Str.toUtf8 objectName |> joinWords (asciiByte '-') |> Str.fromUtf8
+
+    # Only ASCII letters are changed, so the name is still valid UTF-8.
+    Result.withDefault fieldName objectName
+
+# Splits a camelCase name into lowercase words, like `fieldName` into `field_name`.
+splitWords = \name, separator ->
+    List.walk name (List.withCapacity (List.len name)) \words, b ->
+        if isUppercase b then
+            words |> List.append separator |> List.append (toLowercase b)
+        else
+            List.append words b
+
+# Joins the words of a name back into camelCase, like `field_name` into `fieldName`.
+joinWords = \name, separator ->
+    initial = { joined: List.withCapacity (List.len name), upperNext: Bool.false }
+    { joined } = List.walk name initial \state, b ->
+        if b == separator then
+            { state & upperNext: Bool.true }
+        else if state.upperNext then
+            { joined: List.append state.joined (toUppercase b), upperNext: Bool.false }
+        else
+            { state & joined: List.append state.joined b }
 
-            Err _ -> { taken, rest }
+    joined
 
-    helper { taken: [], rest: list }
+mapFirst = \bytes, f ->
+    when List.first bytes is
+        Ok b -> List.set bytes 0 (f b)
+        Err ListWasEmpty -> bytes
 
-asciiByte = \b -> Num.toU8 b
+isUppercase = \b -> b >= asciiByte 'A' && b <= asciiByte 'Z'
 
-digits = List.range (asciiByte '0') (asciiByte '9' + 1)
+isLowercase = \b -> b >= asciiByte 'a' && b <= asciiByte 'z'
 
-takeDigits = \bytes ->
-    takeWhile bytes \n -> List.contains digits n
+toUppercase = \b -> if isLowercase b then b - 32 else b
 
-takeFloat = \bytes ->
-    { taken: intPart, rest } = takeDigits bytes
+toLowercase = \b -> if isUppercase b then b + 32 else b
 
-    when List.get rest 0 is
-        Ok 46 -> # 46 = .
-            { taken: floatPart, rest: afterAll } = takeDigits (List.split rest 1).others
-            builtFloat =
-                List.concat (List.append intPart (asciiByte '.')) floatPart
+asciiByte = \b -> Num.toU8 b
 
-            { taken: builtFloat, rest: afterAll }
+# JSON only allows spaces, tabs, line feeds and carriage returns as whitespace.
+isWhitespace = \b ->
+    b == 32 || b == 9 || b == 10 || b == 13
 
-        _ ->
-            { taken: intPart, rest }
+skipWhitespace : List U8 -> List U8
+skipWhitespace = \bytes ->
+    count = List.walkUntil bytes 0 \n, b ->
+        if isWhitespace b then
+            Continue (n + 1)
+        else
+            Break n
+
+    List.drop bytes count
+
+# Splits a number, as specified in https://www.rfc-editor.org/rfc/rfc8259#section-6, off the
+# front of `bytes`, by following its grammar until a byte doesn't fit in anymore.
+takeJsonNumber : List U8 -> Result { taken : List U8, rest : List U8 } [NotANumber]*
+takeJsonNumber = \bytes ->
+    { state, length } = List.walkUntil bytes { state: Start, length: 0 } \current, b ->
+        when numberStep current.state b is
+            Ok next -> Continue { state: next, length: current.length + 1 }
+            Err Stop -> Break current
+
+    when state is
+        Zero | Integer | Fraction | ExponentDigits ->
+            { before: taken, others: rest } = List.split bytes length
+
+            Ok { taken, rest }
+
+        _ -> Err NotANumber
+
+numberStep = \state, b ->
+    isDigit = b >= asciiByte '0' && b <= asciiByte '9'
+
+    when state is
+        Start if b == asciiByte '-' -> Ok Minus
+        Start | Minus if b == asciiByte '0' -> Ok Zero
+        Start | Minus if isDigit -> Ok Integer
+        Integer if isDigit -> Ok Integer
+        Zero | Integer if b == asciiByte '.' -> Ok Point
+        Point | Fraction if isDigit -> Ok Fraction
+        Zero | Integer | Fraction if b == asciiByte 'e' || b == asciiByte 'E' -> Ok Exponent
+        Exponent if b == asciiByte '+' || b == asciiByte '-' -> Ok ExponentSign
+        Exponent | ExponentSign | ExponentDigits if isDigit -> Ok ExponentDigits
+        _ -> Err Stop
+
+# Integers and decimals can't be parsed from exponent notation, so we take the exponent out by
+# moving the decimal point instead, turning for example `-1.25e2` into `-125`. Leading zeros of
+# the integer part and trailing zeros of the fraction are dropped, so `1.0` is an integer too.
+toPlainDecimal : List U8 -> Result { negative : Bool, integer : List U8, fraction : List U8 } [OutOfRange]*
+toPlainDecimal = \number ->
+    negative =
+        when List.first number is
+            Ok 45 -> Bool.true # 45 = -
+            _ -> Bool.false
+
+    unsigned = if negative then List.dropFirst number else number
+
+    { before: mantissa, others: exponentPart } =
+        when List.findFirstIndex unsigned \b -> b == asciiByte 'e' || b == asciiByte 'E' is
+            Ok index -> List.split unsigned index
+            Err NotFound -> { before: unsigned, others: [] }
+
+    { before: integerPart, others: fractionPart } =
+        when List.findFirstIndex mantissa \b -> b == asciiByte '.' is
+            Ok index -> List.split mantissa index
+            Err NotFound -> { before: mantissa, others: [] }
+
+    exponentResult =
+        if List.isEmpty exponentPart then
+            Ok 0
+        else
+            List.dropFirst exponentPart |> Str.fromUtf8 |> Result.try Str.toI32
+
+    when exponentResult is
+        # No integer or decimal has more than 39 digits, so larger exponents can only over- or
+        # underflow, and we don't want to allocate all of those zeros to find that out.
+        Ok exponent if exponent >= -100 && exponent <= 100 ->
+            digits = List.concat integerPart (List.dropFirst fractionPart)
+            point = Num.toI32 (List.len integerPart) + exponent
+            digitCount = Num.toI32 (List.len digits)
+            zero = asciiByte '0'
+
+            { before: integer, others: fraction } =
+                if point <= 0 then
+                    { before: [], others: List.concat (List.repeat zero (Num.toNat (Num.neg point))) digits }
+                else if point >= digitCount then
+                    { before: List.concat digits (List.repeat zero (Num.toNat (point - digitCount))), others: [] }
+                else
+                    List.split digits (Num.toNat point)
 
-decodeU8 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+            trimmedInteger = dropZeros integer
+            trimmedFraction = List.reverse fraction |> dropZeros |> List.reverse
 
-    when Str.fromUtf8 taken |> Result.try Str.toU8 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+            if List.isEmpty trimmedInteger then
+                Ok { negative, integer: [zero], fraction: trimmedFraction }
+            else
+                Ok { negative, integer: trimmedInteger, fraction: trimmedFraction }
 
-decodeU16 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+        _ -> Err OutOfRange
 
-    when Str.fromUtf8 taken |> Result.try Str.toU16 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+dropZeros = \digits ->
+    count = List.walkUntil digits 0 \n, d ->
+        if d == asciiByte '0' then
+            Continue (n + 1)
+        else
+            Break n
 
-decodeU32 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+    List.drop digits count
 
-    when Str.fromUtf8 taken |> Result.try Str.toU32 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+withSign = \digits, negative ->
+    if negative then
+        List.prepend digits (asciiByte '-')
+    else
+        digits
 
-decodeU64 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+# Decodes a JSON number, using `parse` to turn its bytes into a Roc number.
+decodeNumber = \bytes, parse ->
+    when takeJsonNumber (skipWhitespace bytes) is
+        Ok { taken, rest } ->
+            when parse taken is
+                Ok n -> { result: Ok n, rest: skipWhitespace rest }
+                Err _ -> { result: Err TooShort, rest: bytes }
 
-    when Str.fromUtf8 taken |> Result.try Str.toU64 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err TooShort, rest: bytes }
 
-decodeU128 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+parseInteger = \number, toInt ->
+    { negative, integer, fraction } <- toPlainDecimal number |> Result.try
 
-    when Str.fromUtf8 taken |> Result.try Str.toU128 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+    if List.isEmpty fraction then
+        withSign integer negative |> Str.fromUtf8 |> Result.try toInt
+    else
+        Err NotAnInteger
 
-decodeI8 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+parseFloat = \number, toFloat ->
+    Str.fromUtf8 number |> Result.try toFloat
 
-    when Str.fromUtf8 taken |> Result.try Str.toI8 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+parseDec = \number ->
+    { negative, integer, fraction } <- toPlainDecimal number |> Result.try
 
-decodeI16 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+    # A `Dec` has 18 decimal places, so we round any further ones towards zero, and its integer
+    # part has at most 21 digits, only some of which fit.
+    if List.len integer > 20 then
+        Err OutOfRange
+    else if List.isEmpty fraction then
+        withSign integer negative |> Str.fromUtf8 |> Result.try Str.toDec
+    else
+        List.append integer (asciiByte '.')
+        |> List.concat (List.takeFirst fraction 18)
+        |> withSign negative
+        |> Str.fromUtf8
+        |> Result.try Str.toDec
 
-    when Str.fromUtf8 taken |> Result.try Str.toI16 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeU8 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toU8
 
-decodeI32 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeU16 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toU16
 
-    when Str.fromUtf8 taken |> Result.try Str.toI32 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeU32 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toU32
 
-decodeI64 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeU64 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toU64
 
-    when Str.fromUtf8 taken |> Result.try Str.toI64 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeU128 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toU128
 
-decodeI128 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeDigits bytes
+decodeI8 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toI8
 
-    when Str.fromUtf8 taken |> Result.try Str.toI128 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeI16 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toI16
 
-decodeF32 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeFloat bytes
+decodeI32 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toI32
 
-    when Str.fromUtf8 taken |> Result.try Str.toF32 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeI64 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toI64
 
-decodeF64 = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeFloat bytes
+decodeI128 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseInteger number Str.toI128
 
-    when Str.fromUtf8 taken |> Result.try Str.toF64 is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeF32 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseFloat number Str.toF32
 
-decodeDec = Decode.custom \bytes, @Json {} ->
-    { taken, rest } = takeFloat bytes
+decodeF64 = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes \number -> parseFloat number Str.toF64
 
-    when Str.fromUtf8 taken |> Result.try Str.toDec is
-        Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+decodeDec = Decode.custom \bytes, @Json _ ->
+    decodeNumber bytes parseDec
 
-decodeBool = Decode.custom \bytes, @Json {} ->
-    { before: maybeFalse, others: afterFalse } = List.split bytes 5
+# Matches the exact bytes of a literal like `true` or `null`.
+literal : List U8, List U8 -> DecodeResult {}
+literal = \bytes, word ->
+    afterWhitespace = skipWhitespace bytes
 
-    # Note: this could be more performant by traversing both branches char-by-char.
-    # Doing that would also make `rest` more correct in the erroring case.
-    if
-        maybeFalse == [asciiByte 'f', asciiByte 'a', asciiByte 'l', asciiByte 's', asciiByte 'e']
-    then
-        { result: Ok Bool.false, rest: afterFalse }
+    if List.startsWith afterWhitespace word then
+        { result: Ok {}, rest: skipWhitespace (List.drop afterWhitespace (List.len word)) }
     else
-        { before: maybeTrue, others: afterTrue } = List.split bytes 4
+        { result: Err TooShort, rest: bytes }
 
-        if
-            maybeTrue == [asciiByte 't', asciiByte 'r', asciiByte 'u', asciiByte 'e']
-        then
-            { result: Ok Bool.true, rest: afterTrue }
-        else
-            { result: Err TooShort, rest: bytes }
+decodeBool = Decode.custom \bytes, @Json _ ->
+    { result: trueResult, rest: afterTrue } = literal bytes (Str.toUtf8 "true")
+
+    when trueResult is
+        Ok {} -> { result: Ok Bool.true, rest: afterTrue }
+        Err _ ->
+            { result: falseResult, rest: afterFalse } = literal bytes (Str.toUtf8 "false")
+
+            when falseResult is
+                Ok {} -> { result: Ok Bool.false, rest: afterFalse }
+                Err e -> { result: Err e, rest: bytes }
 
 jsonString : List U8 -> DecodeResult Str
 jsonString = \bytes ->
-    { before, others: afterStartingQuote } = List.split bytes 1
+    afterWhitespace = skipWhitespace bytes
+
+    when List.first afterWhitespace is
+        Ok 34 -> # 34 = "
+            when unescapeString afterWhitespace 1 [] is
+                Ok { content, end } ->
+                    when Str.fromUtf8 content is
+                        Ok s -> { result: Ok s, rest: skipWhitespace (List.drop afterWhitespace end) }
+                        Err _ -> { result: Err TooShort, rest: bytes }
+
+                Err _ -> { result: Err TooShort, rest: bytes }
+
+        _ -> { result: Err TooShort, rest: bytes }
+
+# Collects the bytes of a string from `index` up to its closing quote, replacing escape sequences
+# with the characters they stand for. Returns the index just past the closing quote.
+unescapeString : List U8, Nat, List U8 -> Result { content : List U8, end : Nat } [InvalidString]*
+unescapeString = \bytes, index, content ->
+    when List.get bytes index is
+        Ok 34 -> # 34 = "
+            Ok { content, end: index + 1 }
+
+        Ok 92 -> # 92 = \
+            when List.get bytes (index + 1) is
+                Ok 117 -> # 117 = u
+                    when unicodeEscape bytes (index + 2) is
+                        Ok { codePoint, end } -> unescapeString bytes end (appendUtf8 content codePoint)
+                        Err _ -> Err InvalidString
+
+                Ok escaped ->
+                    when unescapeByte escaped is
+                        Ok b -> unescapeString bytes (index + 2) (List.append content b)
+                        Err _ -> Err InvalidString
+
+                Err _ -> Err InvalidString
+
+        Ok b ->
+            # Control characters must be escaped.
+            if b < 32 then
+                Err InvalidString
+            else
+                unescapeString bytes (index + 1) (List.append content b)
+
+        Err _ -> Err InvalidString
+
+unescapeByte = \b ->
+    when b is
+        34 | 92 | 47 -> Ok b # 34 = ", 92 = \, 47 = /
+        98 -> Ok 8 # 98 = b, backspace
+        102 -> Ok 12 # 102 = f, form feed
+        110 -> Ok 10 # 110 = n, line feed
+        114 -> Ok 13 # 114 = r, carriage return
+        116 -> Ok 9 # 116 = t, tab
+        _ -> Err InvalidEscape
+
+# Decodes the four hex digits of a `\uXXXX` escape starting at `index`. A code point outside the
+# basic multilingual plane is escaped as a UTF-16 surrogate pair, like `\uD83D\uDE00`, whose
+# halves we combine back into one code point.
+unicodeEscape = \bytes, index ->
+    when hexQuad bytes index is
+        Ok high if high >= 0xD800 && high <= 0xDBFF ->
+            # 92 = \, 117 = u
+            if List.sublist bytes { start: index + 4, len: 2 } == [92, 117] then
+                when hexQuad bytes (index + 6) is
+                    Ok low if low >= 0xDC00 && low <= 0xDFFF ->
+                        codePoint = 0x10000 + (high - 0xD800) * 0x400 + (low - 0xDC00)
+
+                        Ok { codePoint, end: index + 10 }
+
+                    _ -> Err InvalidEscape
+            else
+                Err InvalidEscape
 
-    if
-        before == [asciiByte '"']
-    then
-        # TODO: handle escape sequences
-        { taken: strSequence, rest } = takeWhile afterStartingQuote \n -> n != asciiByte '"'
+        Ok low if low >= 0xDC00 && low <= 0xDFFF ->
+            # The second half of a surrogate pair can't come first.
+            Err InvalidEscape
 
-        when Str.fromUtf8 strSequence is
-            Ok s ->
-                { others: afterEndingQuote } = List.split rest 1
+        Ok codePoint -> Ok { codePoint, end: index + 4 }
+        Err e -> Err e
 
-                { result: Ok s, rest: afterEndingQuote }
+hexQuad : List U8, Nat -> Result U32 [InvalidEscape]*
+hexQuad = \bytes, index ->
+    quad = List.sublist bytes { start: index, len: 4 }
 
-            Err _ -> { result: Err TooShort, rest }
+    if List.len quad == 4 then
+        List.walkUntil quad (Ok 0) \state, b ->
+            when state is
+                Ok n ->
+                    when hexDigit b is
+                        Ok digit -> Continue (Ok (n * 16 + digit))
+                        Err _ -> Break (Err InvalidEscape)
+
+                Err e -> Break (Err e)
     else
-        { result: Err TooShort, rest: bytes }
+        Err InvalidEscape
+
+hexDigit : U8 -> Result U32 [NotHex]*
+hexDigit = \b ->
+    if b >= asciiByte '0' && b <= asciiByte '9' then
+        Ok (Num.toU32 (b - asciiByte '0'))
+    else if b >= asciiByte 'a' && b <= asciiByte 'f' then
+        Ok (Num.toU32 (b - asciiByte 'a' + 10))
+    else if b >= asciiByte 'A' && b <= asciiByte 'F' then
+        Ok (Num.toU32 (b - asciiByte 'A' + 10))
+    else
+        Err NotHex
+
+appendUtf8 : List U8, U32 -> List U8
+appendUtf8 = \bytes, codePoint ->
+    # Each continuation byte holds the next six bits of the code point.
+    continuation = \shift ->
+        Num.toU8 (Num.bitwiseOr 0x80 (Num.bitwiseAnd (Num.shiftRightZfBy codePoint shift) 0x3F))
+
+    if codePoint < 0x80 then
+        List.append bytes (Num.toU8 codePoint)
+    else if codePoint < 0x800 then
+        bytes
+        |> List.append (Num.toU8 (Num.bitwiseOr 0xC0 (Num.shiftRightZfBy codePoint 6)))
+        |> List.append (continuation 0)
+    else if codePoint < 0x10000 then
+        bytes
+        |> List.append (Num.toU8 (Num.bitwiseOr 0xE0 (Num.shiftRightZfBy codePoint 12)))
+        |> List.append (continuation 6)
+        |> List.append (continuation 0)
+    else
+        bytes
+        |> List.append (Num.toU8 (Num.bitwiseOr 0xF0 (Num.shiftRightZfBy codePoint 18)))
+        |> List.append (continuation 12)
+        |> List.append (continuation 6)
+        |> List.append (continuation 0)
 
-decodeString = Decode.custom \bytes, @Json {} ->
+decodeString = Decode.custom \bytes, @Json _ ->
     jsonString bytes
 
-decodeList = \decodeElem -> Decode.custom \bytes, @Json {} ->
+decodeList = \decodeElem -> Decode.custom \bytes, @Json { fieldNameMapping } ->
         decodeElems = \chunk, accum ->
-            when Decode.decodeWith chunk decodeElem (@Json {}) is
+            when Decode.decodeWith chunk decodeElem (@Json { fieldNameMapping }) is
                 { result, rest } ->
                     when result is
                         Ok val ->
-                            # TODO: handle spaces before ','
-                            { before: afterElem, others } = List.split rest 1
+                            { result: commaResult, rest: afterCommaBytes } = comma rest
 
-                            if
-                                afterElem == [asciiByte ',']
-                            then
-                                decodeElems others (List.append accum val)
-                            else
-                                Done (List.append accum val) rest
+                            when commaResult is
+                                Ok {} -> decodeElems afterCommaBytes (List.append accum val)
+                                Err _ -> Done (List.append accum val) rest
 
                         Err e -> Errored e rest
 
-        { before, others: afterStartingBrace } = List.split bytes 1
+        { rest: afterBracketBytes } <- bytes |> openBracket |> tryDecode
 
-        if
-            before == [asciiByte '[']
-        then
-            # TODO: empty lists
-            when decodeElems afterStartingBrace [] is
-                Errored e rest -> { result: Err e, rest }
-                Done vals rest ->
-                    { before: maybeEndingBrace, others: afterEndingBrace } = List.split rest 1
-
-                    if
-                        maybeEndingBrace == [asciiByte ']']
-                    then
-                        { result: Ok vals, rest: afterEndingBrace }
-                    else
-                        { result: Err TooShort, rest }
-        else
-            { result: Err TooShort, rest: bytes }
+        { val: elems, rest: beforeClosingBracketBytes } <- tryDecode
+                (
+                    when List.first afterBracketBytes is
+                        Ok 93 -> # 93 = ]
+                            { result: Ok [], rest: afterBracketBytes }
+
+                        _ ->
+                            when decodeElems afterBracketBytes [] is
+                                Errored e rest -> { result: Err e, rest }
+                                Done vals rest -> { result: Ok vals, rest }
+                )
+
+        { rest: afterListBytes } <- beforeClosingBracketBytes |> closingBracket |> tryDecode
 
+        { result: Ok elems, rest: afterListBytes }
+
+# Structural characters may have whitespace on either side.
 parseExactChar : List U8, U8 -> DecodeResult {}
 parseExactChar = \bytes, char ->
-    when List.get bytes 0 is
+    afterWhitespace = skipWhitespace bytes
+
+    when List.first afterWhitespace is
         Ok c ->
             if
                 c == char
             then
-                { result: Ok {}, rest: (List.split bytes 1).others }
+                { result: Ok {}, rest: skipWhitespace (List.dropFirst afterWhitespace) }
             else
                 { result: Err TooShort, rest: bytes }
 
@@ -401,8 +653,76 @@ closingBrace = \bytes -> parseExactChar bytes (asciiByte '}')
 recordKey : List U8 -> DecodeResult Str
 recordKey = \bytes -> jsonString bytes
 
+# Skips over a single value of any kind, like the value of a field we don't decode.
 anything : List U8 -> DecodeResult {}
-anything = \bytes -> { result: Err TooShort, rest: bytes }
+anything = \bytes ->
+    afterWhitespace = skipWhitespace bytes
+
+    when List.first afterWhitespace is
+        Ok 34 -> # 34 = "
+            { rest } <- jsonString afterWhitespace |> tryDecode
+
+            { result: Ok {}, rest }
+
+        Ok 91 -> skipArray afterWhitespace # 91 = [
+        Ok 123 -> skipObject afterWhitespace # 123 = {
+        Ok 116 -> literal afterWhitespace (Str.toUtf8 "true") # 116 = t
+        Ok 102 -> literal afterWhitespace (Str.toUtf8 "false") # 102 = f
+        Ok 110 -> literal afterWhitespace (Str.toUtf8 "null") # 110 = n
+        _ ->
+            when takeJsonNumber afterWhitespace is
+                Ok { rest } -> { result: Ok {}, rest: skipWhitespace rest }
+                Err _ -> { result: Err TooShort, rest: bytes }
+
+skipArray : List U8 -> DecodeResult {}
+skipArray = \bytes ->
+    { rest: afterBracketBytes } <- bytes |> openBracket |> tryDecode
+    { result: emptyResult, rest: afterEmptyBytes } = closingBracket afterBracketBytes
+
+    when emptyResult is
+        Ok {} -> { result: Ok {}, rest: afterEmptyBytes }
+        Err _ ->
+            { rest: beforeClosingBracketBytes } <- skipElements afterBracketBytes |> tryDecode
+
+            closingBracket beforeClosingBracketBytes
+
+skipElements : List U8 -> DecodeResult {}
+skipElements = \bytes ->
+    { rest: afterElemBytes } <- anything bytes |> tryDecode
+    { result: commaResult, rest: afterCommaBytes } = comma afterElemBytes
+
+    when commaResult is
+        Ok {} -> skipElements afterCommaBytes
+        Err _ -> { result: Ok {}, rest: afterElemBytes }
+
+skipObject : List U8 -> DecodeResult {}
+skipObject = \bytes ->
+    { rest: afterBraceBytes } <- bytes |> openBrace |> tryDecode
+    { result: emptyResult, rest: afterEmptyBytes } = closingBrace afterBraceBytes
+
+    when emptyResult is
+        Ok {} -> { result: Ok {}, rest: afterEmptyBytes }
+        Err _ ->
+            { rest: beforeClosingBraceBytes } <- skipFields afterBraceBytes |> tryDecode
+
+            closingBrace beforeClosingBraceBytes
+
+skipFields : List U8 -> DecodeResult {}
+skipFields = \bytes ->
+    { rest: afterKeyBytes } <- recordKey bytes |> tryDecode
+    { rest: afterColonBytes } <- colon afterKeyBytes |> tryDecode
+    { rest: afterValueBytes } <- anything afterColonBytes |> tryDecode
+    { result: commaResult, rest: afterCommaBytes } = comma afterValueBytes
+
+    when commaResult is
+        Ok {} -> skipFields afterCommaBytes
+        Err _ -> { result: Ok {}, rest: afterValueBytes }
+
+openBracket : List U8 -> DecodeResult {}
+openBracket = \bytes -> parseExactChar bytes (asciiByte '[')
+
+closingBracket : List U8 -> DecodeResult {}
+closingBracket = \bytes -> parseExactChar bytes (asciiByte ']')
 
 colon : List U8 -> DecodeResult {}
 colon = \bytes -> parseExactChar bytes (asciiByte ':')
@@ -416,20 +736,20 @@ tryDecode = \{ result, rest }, mapper ->
         Ok val -> mapper { val, rest }
         Err e -> { result: Err e, rest }
 
-decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, @Json {} ->
+decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, @Json { fieldNameMapping } ->
         # NB: the stepper function must be passed explicitly until #2894 is resolved.
         decodeFields = \stepper, state, kvBytes ->
             { val: key, rest } <- recordKey kvBytes |> tryDecode
             { rest: afterColonBytes } <- colon rest |> tryDecode
             { val: newState, rest: beforeCommaOrBreak } <- tryDecode
                     (
-                        when stepper state key is
+                        when stepper state (fromObjectName key fieldNameMapping) is
                             Skip ->
                                 { rest: beforeCommaOrBreak } <- afterColonBytes |> anything |> tryDecode
                                 { result: Ok state, rest: beforeCommaOrBreak }
 
                             Keep decoder ->
-                                Decode.decodeWith afterColonBytes decoder (@Json {})
+                                Decode.decodeWith afterColonBytes decoder (@Json { fieldNameMapping })
                     )
 
             { result: commaResult, rest: nextBytes } = comma beforeCommaOrBreak
@@ -440,10 +760,60 @@ decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, @Jso
 
         { rest: afterBraceBytes } <- bytes |> openBrace |> tryDecode
 
-        { val: endStateResult, rest: beforeClosingBraceBytes } <- decodeFields stepField initialState afterBraceBytes |> tryDecode
+        { val: endStateResult, rest: beforeClosingBraceBytes } <- tryDecode
+                (
+                    when List.first afterBraceBytes is
+                        Ok 125 -> # 125 = }
+                            { result: Ok initialState, rest: afterBraceBytes }
+
+                        _ ->
+                            decodeFields stepField initialState afterBraceBytes
+                )
 
         { rest: afterRecordBytes } <- beforeClosingBraceBytes |> closingBrace |> tryDecode
 
         when finalizer endStateResult is
             Ok val -> { result: Ok val, rest: afterRecordBytes }
             Err e -> { result: Err e, rest: afterRecordBytes }
+
+decodeTag = \stepName, stepPayload, finalizer -> Decode.custom \bytes, @Json { fieldNameMapping } ->
+        # Idea: decode `{"A": [v1, v2]}` as `A v1 v2`, mirroring `encodeTag`
+        # NB: the stepper function must be passed explicitly until #2894 is resolved.
+        decodePayload = \stepper, state, payloadBytes ->
+            when stepper state is
+                Skip ->
+                    # The tag was given more payloads than it has.
+                    { result: Err TooShort, rest: payloadBytes }
+
+                Keep decoder ->
+                    { val: newState, rest: beforeCommaOrBreak } <- Decode.decodeWith payloadBytes decoder (@Json { fieldNameMapping }) |> tryDecode
+                    { result: commaResult, rest: nextBytes } = comma beforeCommaOrBreak
+
+                    when commaResult is
+                        Ok {} -> decodePayload stepPayload newState nextBytes
+                        Err _ -> { result: Ok newState, rest: nextBytes }
+
+        { rest: afterBraceBytes } <- bytes |> openBrace |> tryDecode
+        { val: name, rest: afterNameBytes } <- recordKey afterBraceBytes |> tryDecode
+        { rest: afterColonBytes } <- colon afterNameBytes |> tryDecode
+        { rest: afterBracketBytes } <- openBracket afterColonBytes |> tryDecode
+
+        when stepName name is
+            Skip -> { result: Err TooShort, rest: afterBracketBytes }
+            Keep initialState ->
+                { val: endState, rest: beforeClosingBracketBytes } <- tryDecode
+                        (
+                            when List.first afterBracketBytes is
+                                Ok 93 -> # 93 = ]
+                                    { result: Ok initialState, rest: afterBracketBytes }
+
+                                _ ->
+                                    decodePayload stepPayload initialState afterBracketBytes
+                        )
+
+                { rest: afterBracketsBytes } <- beforeClosingBracketBytes |> closingBracket |> tryDecode
+                { rest: afterTagBytes } <- afterBracketsBytes |> closingBrace |> tryDecode
+
+                when finalizer endState is
+                    Ok val -> { result: Ok val, rest: afterTagBytes }
+                    Err e -> { result: Err e, rest: afterTagBytes }
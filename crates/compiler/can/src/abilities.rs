@@ -5,7 +5,7 @@ use roc_error_macros::internal_error;
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::Region;
 use roc_types::{
-    subs::Variable,
+    subs::{Subs, Variable},
     types::{MemberImpl, Type},
 };
 
@@ -469,6 +469,202 @@ impl IAbilitiesStore<Resolved> {
     pub fn get_resolved(&self, id: SpecializationId) -> Option<Symbol> {
         self.resolved_specializations.get(&id).copied()
     }
+
+    /// Writes out the store after solving, so that a module's abilities can be cached along with
+    /// its [Subs](roc_types::subs::Subs). `written` is the number of bytes already written, which
+    /// the slices are aligned relative to.
+    pub fn serialize(
+        &self,
+        writer: &mut impl std::io::Write,
+        written: usize,
+    ) -> std::io::Result<usize> {
+        let mut members_of_ability = Vec::with_capacity(self.members_of_ability.len());
+        let mut ability_member_symbols = Vec::new();
+        for (ability, members) in self.members_of_ability.iter() {
+            members_of_ability.push((*ability, members.len() as u64));
+            ability_member_symbols.extend(members.iter().copied());
+        }
+
+        let specialization_to_root: Vec<(Symbol, ImplKey)> = self
+            .specialization_to_root
+            .iter()
+            .map(|(symbol, impl_key)| (*symbol, *impl_key))
+            .collect();
+
+        let ability_members: Vec<SerializedMemberData> = self
+            .ability_members
+            .iter()
+            .map(|(member, data)| SerializedMemberData {
+                member: *member,
+                parent_ability: data.parent_ability,
+                region: data.region,
+                signature_var: data.signature_var(),
+            })
+            .collect();
+
+        let declared_implementations: Vec<(ImplKey, MemberImpl)> = self
+            .declared_implementations
+            .iter()
+            .map(|(impl_key, member_impl)| (*impl_key, *member_impl))
+            .collect();
+
+        let mut specializations = Vec::with_capacity(self.specializations.len());
+        let mut specialization_lambda_sets = Vec::new();
+        for (symbol, info) in self.specializations.iter() {
+            let lambda_sets = &info.specialization_lambda_sets;
+            specializations.push((*symbol, info.symbol, lambda_sets.len() as u64));
+            specialization_lambda_sets.extend(lambda_sets.iter().map(|(i, var)| (*i, *var)));
+        }
+
+        let resolved_specializations: Vec<(SpecializationId, Symbol)> = self
+            .resolved_specializations
+            .iter()
+            .map(|(id, symbol)| (*id, *symbol))
+            .collect();
+
+        let header = AbilitiesStoreHeader {
+            members_of_ability: members_of_ability.len() as u64,
+            ability_member_symbols: ability_member_symbols.len() as u64,
+            specialization_to_root: specialization_to_root.len() as u64,
+            ability_members: ability_members.len() as u64,
+            declared_implementations: declared_implementations.len() as u64,
+            specializations: specializations.len() as u64,
+            specialization_lambda_sets: specialization_lambda_sets.len() as u64,
+            next_specialization_id: self.next_specialization_id.get() as u64,
+            resolved_specializations: resolved_specializations.len() as u64,
+        };
+
+        let mut written = Subs::serialize_slice(&[header], writer, written)?;
+        written = Subs::serialize_slice(&members_of_ability, writer, written)?;
+        written = Subs::serialize_slice(&ability_member_symbols, writer, written)?;
+        written = Subs::serialize_slice(&specialization_to_root, writer, written)?;
+        written = Subs::serialize_slice(&ability_members, writer, written)?;
+        written = Subs::serialize_slice(&declared_implementations, writer, written)?;
+        written = Subs::serialize_slice(&specializations, writer, written)?;
+        written = Subs::serialize_slice(&specialization_lambda_sets, writer, written)?;
+        written = Subs::serialize_slice(&resolved_specializations, writer, written)?;
+
+        Ok(written)
+    }
+
+    /// Reads back a store written with [Self::serialize], starting at `offset` in `bytes`.
+    pub fn deserialize(bytes: &[u8], offset: usize) -> (Self, usize) {
+        let (header, offset) = Subs::deserialize_slice::<AbilitiesStoreHeader>(bytes, 1, offset);
+        let header = header[0];
+
+        let (members_of_ability_slice, offset) = Subs::deserialize_slice::<(Symbol, u64)>(
+            bytes,
+            header.members_of_ability as usize,
+            offset,
+        );
+        let (ability_member_symbols, offset) = Subs::deserialize_slice::<Symbol>(
+            bytes,
+            header.ability_member_symbols as usize,
+            offset,
+        );
+        let (specialization_to_root, offset) = Subs::deserialize_slice::<(Symbol, ImplKey)>(
+            bytes,
+            header.specialization_to_root as usize,
+            offset,
+        );
+        let (ability_members, offset) = Subs::deserialize_slice::<SerializedMemberData>(
+            bytes,
+            header.ability_members as usize,
+            offset,
+        );
+        let (declared_implementations, offset) = Subs::deserialize_slice::<(ImplKey, MemberImpl)>(
+            bytes,
+            header.declared_implementations as usize,
+            offset,
+        );
+        let (specializations_slice, offset) = Subs::deserialize_slice::<(Symbol, Symbol, u64)>(
+            bytes,
+            header.specializations as usize,
+            offset,
+        );
+        let (specialization_lambda_sets, offset) = Subs::deserialize_slice::<(u8, Variable)>(
+            bytes,
+            header.specialization_lambda_sets as usize,
+            offset,
+        );
+        let (resolved_specializations, offset) =
+            Subs::deserialize_slice::<(SpecializationId, Symbol)>(
+                bytes,
+                header.resolved_specializations as usize,
+                offset,
+            );
+
+        let mut member_symbols = ability_member_symbols.iter().copied();
+        let members_of_ability = members_of_ability_slice
+            .iter()
+            .map(|(ability, length)| {
+                let members = member_symbols.by_ref().take(*length as usize).collect();
+                (*ability, members)
+            })
+            .collect();
+
+        let ability_members = ability_members
+            .iter()
+            .map(|data| {
+                let member_data = AbilityMemberData {
+                    parent_ability: data.parent_ability,
+                    region: data.region,
+                    typ: ResolvedMemberType(data.signature_var),
+                };
+                (data.member, member_data)
+            })
+            .collect();
+
+        let mut lambda_sets = specialization_lambda_sets.iter().copied();
+        let specializations = specializations_slice
+            .iter()
+            .map(|(symbol, specialization_symbol, length)| {
+                let specialization_lambda_sets =
+                    lambda_sets.by_ref().take(*length as usize).collect();
+                let info = MemberSpecializationInfo::new(
+                    *specialization_symbol,
+                    specialization_lambda_sets,
+                );
+                (*symbol, info)
+            })
+            .collect();
+
+        let store = AbilitiesStore {
+            members_of_ability,
+            specialization_to_root: specialization_to_root.iter().copied().collect(),
+            ability_members,
+            declared_implementations: declared_implementations.iter().copied().collect(),
+            specializations,
+            next_specialization_id: NonZeroU32::new(header.next_specialization_id as u32)
+                .expect("specialization IDs start at 1"),
+            resolved_specializations: resolved_specializations.iter().copied().collect(),
+        };
+
+        (store, offset)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct AbilitiesStoreHeader {
+    members_of_ability: u64,
+    ability_member_symbols: u64,
+    specialization_to_root: u64,
+    ability_members: u64,
+    declared_implementations: u64,
+    specializations: u64,
+    specialization_lambda_sets: u64,
+    next_specialization_id: u64,
+    resolved_specializations: u64,
+}
+
+/// An [AbilityMemberData] of a solved module, along with the member it describes.
+#[derive(Clone, Copy, Debug)]
+struct SerializedMemberData {
+    member: Symbol,
+    parent_ability: Symbol,
+    region: Region,
+    signature_var: Variable,
 }
 
 impl IAbilitiesStore<Pending> {
@@ -482,9 +678,11 @@ impl IAbilitiesStore<Pending> {
             ResolvedImpl::Error => MemberImpl::Error,
         };
 
+        // The implementation may already be known if it was introduced alongside its ability
+        // during canonicalization, in which case it must be the same implementation.
         let old_declared_impl = self.declared_implementations.insert(impl_key, member_impl);
         debug_assert!(
-            old_declared_impl.is_none(),
+            old_declared_impl.is_none() || old_declared_impl == Some(member_impl),
             "Replacing existing declared impl!"
         );
     }
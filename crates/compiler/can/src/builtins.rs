@@ -80,7 +80,6 @@ macro_rules! map_symbol_to_lowlevel_and_arity {
                 LowLevel::NumToFloatChecked => unreachable!(),
 
                 // these are used internally and not tied to a symbol
-                LowLevel::Hash => unimplemented!(),
                 LowLevel::PtrCast => unimplemented!(),
                 LowLevel::RefCountInc => unimplemented!(),
                 LowLevel::RefCountDec => unimplemented!(),
@@ -188,13 +187,14 @@ map_symbol_to_lowlevel_and_arity! {
     NumShiftRightZfBy; NUM_SHIFT_RIGHT_ZERO_FILL; 2,
     NumToStr; NUM_TO_STR; 1,
 
-    Eq; BOOL_EQ; 2,
-    NotEq; BOOL_NEQ; 2,
+    Eq; BOOL_STRUCTURAL_EQ; 2,
+    NotEq; BOOL_STRUCTURAL_NOT_EQ; 2,
     And; BOOL_AND; 2,
     Or; BOOL_OR; 2,
     Not; BOOL_NOT; 1,
     BoxExpr; BOX_BOX_FUNCTION; 1,
     UnboxExpr; BOX_UNBOX; 1,
+    Hash; DICT_HASH_BYTES_LOWLEVEL; 2,
     Unreachable; LIST_UNREACHABLE; 1,
 }
 
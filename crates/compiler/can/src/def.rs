@@ -1855,6 +1855,27 @@ fn pattern_to_vars_by_symbol(
             }
         }
 
+        TupleDestructure { destructs, .. } => {
+            for destruct in destructs {
+                let (var, nested) = &destruct.value.typ;
+                pattern_to_vars_by_symbol(vars_by_symbol, &nested.value, *var);
+            }
+        }
+
+        List {
+            list_var,
+            elem_var,
+            patterns,
+        } => {
+            for pat in patterns.patterns.iter() {
+                pattern_to_vars_by_symbol(vars_by_symbol, &pat.value, *elem_var);
+            }
+
+            if let Some(rest) = patterns.rest_symbol() {
+                vars_by_symbol.insert(rest.value, *list_var);
+            }
+        }
+
         NumLiteral(..)
         | IntLiteral(..)
         | FloatLiteral(..)
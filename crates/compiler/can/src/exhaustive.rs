@@ -4,11 +4,14 @@ use roc_collections::all::HumanIndex;
 use roc_collections::VecMap;
 use roc_error_macros::internal_error;
 use roc_exhaustive::{
-    is_useful, Ctor, CtorName, Error, Guard, Literal, Pattern, RenderAs, TagId, Union,
+    is_useful, Ctor, CtorName, Error, Guard, ListArity, Literal, Pattern, RenderAs, TagId, Union,
 };
 use roc_module::ident::{TagIdIntType, TagName};
+use roc_module::symbol::Symbol;
 use roc_region::all::{Loc, Region};
-use roc_types::subs::{Content, FlatType, RedundantMark, Subs, SubsFmtContent, Variable};
+use roc_types::subs::{
+    Content, FlatType, GetSubsSlice, RedundantMark, Subs, SubsFmtContent, Variable,
+};
 use roc_types::types::AliasKind;
 
 pub use roc_exhaustive::Context as ExhaustiveContext;
@@ -61,6 +64,7 @@ enum SketchedPattern {
     /// We'll know the whole union when reifying the sketched pattern against an expected case type.
     Ctor(TagName, Vec<SketchedPattern>),
     KnownCtor(Union, IndexCtor<'static>, TagId, Vec<SketchedPattern>),
+    List(ListArity, Vec<SketchedPattern>),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -69,6 +73,8 @@ enum IndexCtor<'a> {
     Opaque,
     /// Index a record type. The arguments are the types of the record fields.
     Record,
+    /// Index a tuple type. The arguments are the types of the tuple elements.
+    Tuple,
     /// Index a guard constructor. The arguments are a faux guard pattern, and then the real
     /// pattern being guarded. E.g. `A B if g` becomes Guard { [True, (A B)] }.
     Guard,
@@ -139,6 +145,13 @@ fn index_var(
 
                     return field_types;
                 }
+                FlatType::Tuple(elems, ext) => {
+                    debug_assert!(matches!(ctor, IndexCtor::Tuple));
+
+                    let (iter, _) = elems.sorted_iterator_and_ext(subs, *ext);
+
+                    return iter.map(|(_, var)| var).collect();
+                }
                 FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
                     let tag_ctor = match ctor {
                         IndexCtor::Tag(name) => name,
@@ -170,6 +183,10 @@ fn index_var(
                     };
                     return std::iter::repeat(Variable::NULL).take(num_fields).collect();
                 }
+                FlatType::EmptyTuple => {
+                    debug_assert!(matches!(ctor, IndexCtor::Tuple));
+                    return vec![];
+                }
                 FlatType::EmptyTagUnion => {
                     internal_error!("empty tag unions are not indexable")
                 }
@@ -185,6 +202,25 @@ fn index_var(
     }
 }
 
+/// Get the element type of a list type.
+fn index_list_var(subs: &Subs, mut var: Variable) -> Variable {
+    loop {
+        match subs.get_content_without_compacting(var) {
+            Content::Structure(FlatType::Apply(Symbol::LIST_LIST, args)) => {
+                debug_assert_eq!(args.len(), 1);
+                return subs.get_subs_slice(*args)[0];
+            }
+            Content::Alias(_, _, inner, AliasKind::Structural) => {
+                var = *inner;
+            }
+            Content::Error | Content::Structure(FlatType::Erroneous(_)) => {
+                internal_error!("errors should not be reachable during exhautiveness checking")
+            }
+            _ => internal_error!("not a list type"),
+        }
+    }
+}
+
 impl SketchedPattern {
     fn reify(self, subs: &Subs, real_var: Variable) -> Pattern {
         match self {
@@ -216,6 +252,16 @@ impl SketchedPattern {
 
                 Pattern::Ctor(union, tag_id, args)
             }
+            Self::List(arity, patterns) => {
+                let elem_var = index_list_var(subs, real_var);
+
+                let patterns = patterns
+                    .into_iter()
+                    .map(|pat| pat.reify(subs, elem_var))
+                    .collect();
+
+                Pattern::List(arity, patterns)
+            }
         }
     }
 }
@@ -286,6 +332,25 @@ fn sketch_pattern(pattern: &crate::pattern::Pattern) -> SketchedPattern {
             SP::KnownCtor(union, IndexCtor::Record, tag_id, patterns)
         }
 
+        TupleDestructure { destructs, .. } => {
+            let tag_id = TagId(0);
+            let patterns = destructs
+                .iter()
+                .map(|destruct| sketch_pattern(&destruct.value.typ.1.value))
+                .collect();
+
+            let union = Union {
+                render_as: RenderAs::Tuple,
+                alternatives: vec![Ctor {
+                    name: CtorName::Tag(TagName("#Tuple".into())),
+                    tag_id,
+                    arity: destructs.len(),
+                }],
+            };
+
+            SP::KnownCtor(union, IndexCtor::Tuple, tag_id, patterns)
+        }
+
         AppliedTag {
             tag_name,
             arguments,
@@ -323,6 +388,16 @@ fn sketch_pattern(pattern: &crate::pattern::Pattern) -> SketchedPattern {
             )
         }
 
+        List { patterns, .. } => {
+            let sketched_elem_patterns = patterns
+                .patterns
+                .iter()
+                .map(|p| sketch_pattern(&p.value))
+                .collect();
+
+            SP::List(patterns.arity(), sketched_elem_patterns)
+        }
+
         // Treat this like a literal so we mark it as non-exhaustive
         MalformedPattern(..) => SP::Literal(Literal::Byte(1)),
 
@@ -522,6 +597,10 @@ fn is_inhabited_pattern(pat: &Pattern) -> bool {
                 }
                 stack.extend(pats);
             }
+            Pattern::List(_, pats) => {
+                // List patterns are uninhabited if any of their element patterns are.
+                stack.extend(pats);
+            }
         }
     }
     true
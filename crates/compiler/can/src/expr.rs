@@ -175,6 +175,20 @@ pub enum Expr {
     /// field accessor as a function, e.g. (.foo) expr
     Accessor(AccessorData),
 
+    Tuple {
+        tuple_var: Variable,
+        elems: Vec<(Variable, Box<Loc<Expr>>)>,
+    },
+
+    /// Look up exactly one element on a tuple, e.g. (expr).1
+    TupleAccess {
+        tuple_var: Variable,
+        ext_var: Variable,
+        elem_var: Variable,
+        loc_expr: Box<Loc<Expr>>,
+        index: usize,
+    },
+
     Update {
         record_var: Variable,
         ext_var: Variable,
@@ -238,6 +252,12 @@ pub enum Expr {
         lookups_in_cond: Vec<(Symbol, Variable)>,
     },
 
+    Dbg {
+        loc_message: Box<Loc<Expr>>,
+        loc_continuation: Box<Loc<Expr>>,
+        variable: Variable,
+    },
+
     /// Rendered as empty box in editor
     TypedHole(Variable),
 
@@ -268,6 +288,8 @@ impl Expr {
             Self::EmptyRecord => Category::Record,
             Self::Access { field, .. } => Category::Access(field.clone()),
             Self::Accessor(data) => Category::Accessor(data.field.clone()),
+            Self::Tuple { .. } => Category::Tuple,
+            &Self::TupleAccess { index, .. } => Category::TupleAccess(index),
             Self::Update { .. } => Category::Record,
             Self::Tag {
                 name, arguments, ..
@@ -285,6 +307,9 @@ impl Expr {
             }
             Self::Expect { .. } => Category::Expect,
             Self::ExpectFx { .. } => Category::Expect,
+            Self::Dbg {
+                loc_continuation, ..
+            } => loc_continuation.value.category(),
 
             // these nodes place no constraints on the expression's type
             Self::TypedHole(_) | Self::RuntimeError(..) => Category::Unknown,
@@ -691,6 +716,33 @@ pub fn canonicalize_expr<'a>(
                 )
             }
         }
+        ast::Expr::Tuple(loc_elems) => {
+            let mut can_elems = Vec::with_capacity(loc_elems.len());
+            let mut references = References::new();
+
+            for loc_elem in loc_elems.iter() {
+                let (can_expr, elem_out) =
+                    canonicalize_expr(env, var_store, scope, loc_elem.region, &loc_elem.value);
+
+                references.union_mut(&elem_out.references);
+
+                can_elems.push((var_store.fresh(), Box::new(can_expr)));
+            }
+
+            let output = Output {
+                references,
+                tail_call: None,
+                ..Default::default()
+            };
+
+            (
+                Tuple {
+                    tuple_var: var_store.fresh(),
+                    elems: can_elems,
+                },
+                output,
+            )
+        }
         ast::Expr::Apply(loc_fn, loc_args, application_style) => {
             // The expression that evaluates to the function being called, e.g. `foo` in
             // (foo) bar baz
@@ -912,6 +964,22 @@ pub fn canonicalize_expr<'a>(
                 output,
             )
         }
+        ast::Expr::TupleAccess(tuple_expr, index) => {
+            let (loc_expr, output) = canonicalize_expr(env, var_store, scope, region, tuple_expr);
+
+            (
+                TupleAccess {
+                    tuple_var: var_store.fresh(),
+                    elem_var: var_store.fresh(),
+                    ext_var: var_store.fresh(),
+                    loc_expr: Box::new(loc_expr),
+                    // the parser only produces ascii digits here; an index too large to fit
+                    // in a usize can never be in bounds anyway.
+                    index: index.parse().unwrap_or(usize::MAX),
+                },
+                output,
+            )
+        }
         ast::Expr::AccessorFunction(field) => (
             Accessor(AccessorData {
                 name: scope.gen_unique_symbol(),
@@ -1006,6 +1074,32 @@ pub fn canonicalize_expr<'a>(
                 output,
             )
         }
+        ast::Expr::Dbg(message, continuation) => {
+            let mut output = Output::default();
+
+            let (loc_message, output1) =
+                canonicalize_expr(env, var_store, scope, message.region, &message.value);
+
+            let (loc_continuation, output2) = canonicalize_expr(
+                env,
+                var_store,
+                scope,
+                continuation.region,
+                &continuation.value,
+            );
+
+            output.union(output1);
+            output.union(output2);
+
+            (
+                Dbg {
+                    loc_message: Box::new(loc_message),
+                    loc_continuation: Box::new(loc_continuation),
+                    variable: var_store.fresh(),
+                },
+                output,
+            )
+        }
         ast::Expr::If(if_thens, final_else_branch) => {
             let mut branches = Vec::with_capacity(if_thens.len());
             let mut output = Output::default();
@@ -1802,6 +1896,28 @@ pub fn inline_calls(var_store: &mut VarStore, scope: &mut Scope, expr: Expr) ->
             }
         }
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable,
+        } => {
+            let loc_message = Loc {
+                region: loc_message.region,
+                value: inline_calls(var_store, scope, loc_message.value),
+            };
+
+            let loc_continuation = Loc {
+                region: loc_continuation.region,
+                value: inline_calls(var_store, scope, loc_continuation.value),
+            };
+
+            Dbg {
+                loc_message: Box::new(loc_message),
+                loc_continuation: Box::new(loc_continuation),
+                variable,
+            }
+        }
+
         LetRec(defs, loc_expr, mark) => {
             let mut new_defs = Vec::with_capacity(defs.len());
 
@@ -1892,6 +2008,24 @@ pub fn inline_calls(var_store: &mut VarStore, scope: &mut Scope, expr: Expr) ->
             todo!("Inlining for Access with record_var {:?}, ext_var {:?}, field_var {:?}, loc_expr {:?}, field {:?}", record_var, ext_var, field_var, loc_expr, field);
         }
 
+        Tuple { tuple_var, elems } => {
+            todo!(
+                "Inlining for Tuple with tuple_var {:?} and elems {:?}",
+                tuple_var,
+                elems
+            );
+        }
+
+        TupleAccess {
+            tuple_var,
+            ext_var,
+            elem_var,
+            loc_expr,
+            index,
+        } => {
+            todo!("Inlining for TupleAccess with tuple_var {:?}, ext_var {:?}, elem_var {:?}, loc_expr {:?}, index {:?}", tuple_var, ext_var, elem_var, loc_expr, index);
+        }
+
         Tag {
             tag_union_var: variant_var,
             ext_var,
@@ -2042,7 +2176,9 @@ fn flatten_str_literal<'a>(
 pub fn is_valid_interpolation(expr: &ast::Expr<'_>) -> bool {
     match expr {
         ast::Expr::Var { .. } => true,
-        ast::Expr::Access(sub_expr, _) => is_valid_interpolation(sub_expr),
+        ast::Expr::Access(sub_expr, _) | ast::Expr::TupleAccess(sub_expr, _) => {
+            is_valid_interpolation(sub_expr)
+        }
         _ => false,
     }
 }
@@ -2521,9 +2657,10 @@ impl Declarations {
             })
     }
 
-    pub fn expects(&self) -> VecMap<Region, Vec<(Symbol, Variable)>> {
+    pub fn expects(&self) -> ExpectsAndDbgs {
         let mut collector = ExpectCollector {
             expects: VecMap::default(),
+            dbgs: VecMap::default(),
         };
 
         let var = Variable::EMPTY_RECORD;
@@ -2556,7 +2693,25 @@ impl Declarations {
             }
         }
 
-        collector.expects
+        ExpectsAndDbgs {
+            expects: collector.expects,
+            dbgs: collector.dbgs,
+        }
+    }
+}
+
+/// The values we need to render the output of the `expect`s and `dbg`s in a module
+#[derive(Debug, Default)]
+pub struct ExpectsAndDbgs {
+    /// The symbols looked up in each `expect` condition, keyed by the region of the condition
+    pub expects: VecMap<Region, Vec<(Symbol, Variable)>>,
+    /// The type of each `dbg`ed value, keyed by the region of that value
+    pub dbgs: VecMap<Region, Variable>,
+}
+
+impl ExpectsAndDbgs {
+    pub fn is_empty(&self) -> bool {
+        self.expects.is_empty() && self.dbgs.is_empty()
     }
 }
 
@@ -2678,6 +2833,7 @@ fn get_lookup_symbols(expr: &Expr, var_store: &mut VarStore) -> Vec<(Symbol, Var
                 stack.push(&argument.1.value);
             }
             Expr::Access { loc_expr, .. }
+            | Expr::TupleAccess { loc_expr, .. }
             | Expr::Closure(ClosureData {
                 loc_body: loc_expr, ..
             }) => {
@@ -2686,6 +2842,9 @@ fn get_lookup_symbols(expr: &Expr, var_store: &mut VarStore) -> Vec<(Symbol, Var
             Expr::Record { fields, .. } => {
                 stack.extend(fields.iter().map(|(_, field)| &field.loc_expr.value));
             }
+            Expr::Tuple { elems, .. } => {
+                stack.extend(elems.iter().map(|(_, loc_elem)| &loc_elem.value));
+            }
             Expr::Expect {
                 loc_continuation, ..
             }
@@ -2697,6 +2856,14 @@ fn get_lookup_symbols(expr: &Expr, var_store: &mut VarStore) -> Vec<(Symbol, Var
                 // Intentionally ignore the lookups in the nested `expect` condition itself,
                 // because they couldn't possibly influence the outcome of this `expect`!
             }
+            Expr::Dbg {
+                loc_message,
+                loc_continuation,
+                ..
+            } => {
+                stack.push(&loc_message.value);
+                stack.push(&loc_continuation.value);
+            }
             Expr::Num(_, _, _, _)
             | Expr::Float(_, _, _, _, _)
             | Expr::Int(_, _, _, _, _)
@@ -2809,6 +2976,7 @@ fn toplevel_expect_to_inline_expect_help(mut loc_expr: Loc<Expr>, has_effects: b
 
 struct ExpectCollector {
     expects: VecMap<Region, Vec<(Symbol, Variable)>>,
+    dbgs: VecMap<Region, Variable>,
 }
 
 impl crate::traverse::Visitor for ExpectCollector {
@@ -2827,6 +2995,13 @@ impl crate::traverse::Visitor for ExpectCollector {
                 self.expects
                     .insert(loc_condition.region, lookups_in_cond.to_vec());
             }
+            Expr::Dbg {
+                loc_message,
+                variable,
+                ..
+            } => {
+                self.dbgs.insert(loc_message.region, *variable);
+            }
             _ => (),
         }
 
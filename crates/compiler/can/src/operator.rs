@@ -139,6 +139,16 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
 
             arena.alloc(Loc { region, value })
         }
+        TupleAccess(sub_expr, index) => {
+            let region = loc_expr.region;
+            let loc_sub_expr = Loc {
+                region,
+                value: **sub_expr,
+            };
+            let value = TupleAccess(&desugar_expr(arena, arena.alloc(loc_sub_expr)).value, index);
+
+            arena.alloc(Loc { region, value })
+        }
         List(items) => {
             let mut new_items = Vec::with_capacity_in(items.len(), arena);
 
@@ -153,6 +163,20 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                 value,
             })
         }
+        Tuple(items) => {
+            let mut new_items = Vec::with_capacity_in(items.len(), arena);
+
+            for item in items.iter() {
+                new_items.push(desugar_expr(arena, item));
+            }
+            let new_items = new_items.into_bump_slice();
+            let value: Expr<'a> = Tuple(items.replace_items(new_items));
+
+            arena.alloc(Loc {
+                region: loc_expr.region,
+                value,
+            })
+        }
         Record(fields) => arena.alloc(Loc {
             region: loc_expr.region,
             value: Record(fields.map_items(arena, |field| {
@@ -344,6 +368,14 @@ pub fn desugar_expr<'a>(arena: &'a Bump, loc_expr: &'a Loc<Expr<'a>>) -> &'a Loc
                 region: loc_expr.region,
             })
         }
+        Dbg(condition, continuation) => {
+            let desugared_condition = &*arena.alloc(desugar_expr(arena, condition));
+            let desugared_continuation = &*arena.alloc(desugar_expr(arena, continuation));
+            arena.alloc(Loc {
+                value: Dbg(desugared_condition, desugared_continuation),
+                region: loc_expr.region,
+            })
+        }
     }
 }
 
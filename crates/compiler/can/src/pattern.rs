@@ -6,6 +6,7 @@ use crate::num::{
     ParsedNumResult,
 };
 use crate::scope::{PendingAbilitiesInScope, Scope};
+use roc_exhaustive::ListArity;
 use roc_module::ident::{Ident, Lowercase, TagName};
 use roc_module::symbol::Symbol;
 use roc_parse::ast::{self, StrLiteral, StrSegment};
@@ -55,6 +56,15 @@ pub enum Pattern {
         ext_var: Variable,
         destructs: Vec<Loc<RecordDestruct>>,
     },
+    TupleDestructure {
+        whole_var: Variable,
+        destructs: Vec<Loc<TupleDestruct>>,
+    },
+    List {
+        list_var: Variable,
+        elem_var: Variable,
+        patterns: ListPatterns,
+    },
     NumLiteral(Variable, Box<str>, IntValue, NumBound),
     IntLiteral(Variable, Variable, Box<str>, IntValue, IntBound),
     FloatLiteral(Variable, Variable, Box<str>, f64, FloatBound),
@@ -91,6 +101,8 @@ impl Pattern {
             AppliedTag { whole_var, .. } => Some(*whole_var),
             UnwrappedOpaque { whole_var, .. } => Some(*whole_var),
             RecordDestructure { whole_var, .. } => Some(*whole_var),
+            TupleDestructure { whole_var, .. } => Some(*whole_var),
+            List { list_var, .. } => Some(*list_var),
             NumLiteral(var, ..) => Some(*var),
             IntLiteral(var, ..) => Some(*var),
             FloatLiteral(var, ..) => Some(*var),
@@ -118,6 +130,10 @@ impl Pattern {
             | MalformedPattern(..)
             | AbilityMemberSpecialization { .. } => true,
             RecordDestructure { destructs, .. } => destructs.is_empty(),
+            TupleDestructure { destructs, .. } => destructs
+                .iter()
+                .all(|d| d.value.typ.1.value.surely_exhaustive()),
+            List { patterns, .. } => patterns.surely_exhaustive(),
             AppliedTag { .. }
             | NumLiteral(..)
             | IntLiteral(..)
@@ -144,6 +160,8 @@ impl Pattern {
             UnwrappedOpaque { opaque, .. } => C::Opaque(*opaque),
             RecordDestructure { destructs, .. } if destructs.is_empty() => C::EmptyRecord,
             RecordDestructure { .. } => C::Record,
+            TupleDestructure { .. } => C::Tuple,
+            List { .. } => C::List,
             NumLiteral(..) => C::Num,
             IntLiteral(..) => C::Int,
             FloatLiteral(..) => C::Float,
@@ -160,6 +178,41 @@ impl Pattern {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct ListPatterns {
+    pub patterns: Vec<Loc<Pattern>>,
+    /// Where a rest pattern splits the patterns before and after it, if it does at all, and the
+    /// symbol the rest of the list is bound to, if any.
+    /// If present, patterns at index >= the rest index appear after the rest pattern.
+    /// For example:
+    ///   [ .., A, B ] -> patterns = [A, B], rest = 0
+    ///   [ A, .., B ] -> patterns = [A, B], rest = 1
+    ///   [ A, B, .. ] -> patterns = [A, B], rest = 2
+    pub opt_rest: Option<(usize, Option<Loc<Symbol>>)>,
+}
+
+impl ListPatterns {
+    /// Is this list pattern the trivially-exhaustive pattern `[..]`?
+    fn surely_exhaustive(&self) -> bool {
+        self.patterns.is_empty() && matches!(self.opt_rest, Some((0, _)))
+    }
+
+    /// The shape of lists this pattern can match.
+    pub fn arity(&self) -> ListArity {
+        match self.opt_rest {
+            Some((i, _)) => ListArity::Slice(i, self.patterns.len() - i),
+            None => ListArity::Exact(self.patterns.len()),
+        }
+    }
+
+    /// The symbol the rest of the list is bound to, if any.
+    pub fn rest_symbol(&self) -> Option<&Loc<Symbol>> {
+        self.opt_rest
+            .as_ref()
+            .and_then(|(_, opt_symbol)| opt_symbol.as_ref())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RecordDestruct {
     pub var: Variable,
@@ -168,6 +221,13 @@ pub struct RecordDestruct {
     pub typ: DestructType,
 }
 
+#[derive(Clone, Debug)]
+pub struct TupleDestruct {
+    pub var: Variable,
+    pub destruct_index: usize,
+    pub typ: (Variable, Loc<Pattern>),
+}
+
 #[derive(Clone, Debug)]
 pub enum DestructType {
     Required,
@@ -608,6 +668,150 @@ pub fn canonicalize_pattern<'a>(
             })
         }
 
+        Tuple(patterns) => {
+            let whole_var = var_store.fresh();
+
+            let destructs = patterns
+                .iter()
+                .enumerate()
+                .map(|(index, loc_pattern)| {
+                    let can_elem = canonicalize_pattern(
+                        env,
+                        var_store,
+                        scope,
+                        output,
+                        pattern_type,
+                        &loc_pattern.value,
+                        loc_pattern.region,
+                        permit_shadows,
+                    );
+
+                    Loc {
+                        region: loc_pattern.region,
+                        value: TupleDestruct {
+                            var: var_store.fresh(),
+                            destruct_index: index,
+                            typ: (var_store.fresh(), can_elem),
+                        },
+                    }
+                })
+                .collect();
+
+            Pattern::TupleDestructure {
+                whole_var,
+                destructs,
+            }
+        }
+
+        List(patterns) => {
+            // A rest pattern can appear anywhere in a list pattern, but at most once:
+            //
+            // []
+            // [..]
+            // [.., P_1, ..., P_n]
+            // [P_1, ..., P_n, ..]
+            // [P_1, ..., P_m, .., P_n, ..., P_q]
+            // [P_1, ..., P_n]
+            let list_var = var_store.fresh();
+            let elem_var = var_store.fresh();
+
+            let mut can_patterns = Vec::with_capacity(patterns.len());
+            let mut opt_rest = None;
+            let mut opt_erroneous = None;
+
+            for loc_pattern in patterns.iter() {
+                let mut inner = &loc_pattern.value;
+                while let SpaceBefore(sub_pattern, _) | SpaceAfter(sub_pattern, _) = inner {
+                    inner = sub_pattern;
+                }
+
+                match inner {
+                    ListRest(opt_name) => {
+                        if opt_rest.is_some() {
+                            env.problem(Problem::MultipleListRestPattern {
+                                region: loc_pattern.region,
+                            });
+
+                            opt_erroneous = Some(Pattern::MalformedPattern(
+                                MalformedPatternProblem::DuplicateListRestPattern,
+                                loc_pattern.region,
+                            ));
+
+                            continue;
+                        }
+
+                        let opt_symbol = match opt_name {
+                            None => None,
+                            Some(name) => match scope.introduce_str(name, loc_pattern.region) {
+                                Ok(symbol) => {
+                                    output.references.insert_bound(symbol);
+
+                                    Some(Loc::at(loc_pattern.region, symbol))
+                                }
+                                Err((shadowed_symbol, shadow, new_symbol)) => {
+                                    if permit_shadows.0 {
+                                        output.references.insert_bound(shadowed_symbol.value);
+
+                                        Some(Loc::at(loc_pattern.region, shadowed_symbol.value))
+                                    } else {
+                                        env.problem(Problem::RuntimeError(
+                                            RuntimeError::Shadowing {
+                                                original_region: shadowed_symbol.region,
+                                                shadow: shadow.clone(),
+                                                kind: ShadowKind::Variable,
+                                            },
+                                        ));
+                                        output.references.insert_bound(new_symbol);
+
+                                        opt_erroneous = Some(Pattern::Shadowed(
+                                            shadowed_symbol.region,
+                                            shadow,
+                                            new_symbol,
+                                        ));
+
+                                        None
+                                    }
+                                }
+                            },
+                        };
+
+                        opt_rest = Some((can_patterns.len(), opt_symbol));
+                    }
+                    _ => {
+                        let can_pattern = canonicalize_pattern(
+                            env,
+                            var_store,
+                            scope,
+                            output,
+                            pattern_type,
+                            &loc_pattern.value,
+                            loc_pattern.region,
+                            permit_shadows,
+                        );
+
+                        can_patterns.push(can_pattern);
+                    }
+                }
+            }
+
+            // If we encountered an erroneous pattern (e.g. a second rest pattern),
+            // use the resulting RuntimeError. Otherwise, return a successful list pattern.
+            opt_erroneous.unwrap_or(Pattern::List {
+                list_var,
+                elem_var,
+                patterns: ListPatterns {
+                    patterns: can_patterns,
+                    opt_rest,
+                },
+            })
+        }
+        ListRest(_) => {
+            // The parser only produces rest patterns inside of list patterns,
+            // which handle them above.
+            let problem = MalformedPatternProblem::Unknown;
+            malformed_pattern(env, problem, region)
+        }
+
         RequiredField(_name, _loc_pattern) => {
             unreachable!("should have been handled in RecordDestructure");
         }
@@ -720,6 +924,18 @@ impl<'a> BindingsFromPattern<'a> {
                             let it = destructs.iter().rev().map(Destruct);
                             stack.extend(it);
                         }
+                        TupleDestructure { destructs, .. } => {
+                            let it = destructs.iter().rev().map(|d| Pattern(&d.value.typ.1));
+                            stack.extend(it);
+                        }
+                        List { patterns, .. } => {
+                            let it = patterns.patterns.iter().rev().map(Pattern);
+                            stack.extend(it);
+
+                            if let Some(rest) = patterns.rest_symbol() {
+                                return Some((rest.value, rest.region));
+                            }
+                        }
                         NumLiteral(..)
                         | IntLiteral(..)
                         | FloatLiteral(..)
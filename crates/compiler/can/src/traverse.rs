@@ -234,6 +234,19 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr, var: Variable) {
             ext_var: _,
         } => visitor.visit_expr(&loc_expr.value, loc_expr.region, *field_var),
         Expr::Accessor(AccessorData { .. }) => { /* terminal */ }
+        Expr::Tuple {
+            tuple_var: _,
+            elems,
+        } => elems
+            .iter()
+            .for_each(|(v, le)| visitor.visit_expr(&le.value, le.region, *v)),
+        Expr::TupleAccess {
+            tuple_var,
+            loc_expr,
+            index: _,
+            elem_var: _,
+            ext_var: _,
+        } => visitor.visit_expr(&loc_expr.value, loc_expr.region, *tuple_var),
         Expr::OpaqueWrapFunction(OpaqueWrapFunctionData { .. }) => { /* terminal */ }
         Expr::Update {
             record_var: _,
@@ -289,6 +302,14 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr, var: Variable) {
                 Variable::NULL,
             );
         }
+        Expr::Dbg {
+            loc_message,
+            loc_continuation,
+            variable,
+        } => {
+            visitor.visit_expr(&loc_message.value, loc_message.region, *variable);
+            visitor.visit_expr(&loc_continuation.value, loc_continuation.region, var);
+        }
         Expr::TypedHole(_) => { /* terminal */ }
         Expr::RuntimeError(..) => { /* terminal */ }
     }
@@ -472,6 +493,16 @@ pub fn walk_pattern<V: Visitor>(visitor: &mut V, pattern: &Pattern) {
         RecordDestructure { destructs, .. } => destructs
             .iter()
             .for_each(|d| visitor.visit_record_destruct(&d.value, d.region)),
+        TupleDestructure { destructs, .. } => destructs.iter().for_each(|d| {
+            let (v, lp) = &d.value.typ;
+            visitor.visit_pattern(&lp.value, lp.region, Some(*v))
+        }),
+        List {
+            patterns, elem_var, ..
+        } => patterns
+            .patterns
+            .iter()
+            .for_each(|p| visitor.visit_pattern(&p.value, p.region, Some(*elem_var))),
         NumLiteral(..) => { /* terminal */ }
         IntLiteral(..) => { /* terminal */ }
         FloatLiteral(..) => { /* terminal */ }
@@ -227,6 +227,39 @@ pub fn constrain_expr(
                 constraints.exists(field_vars, and_constraint)
             }
         }
+        Expr::Tuple { tuple_var, elems } => {
+            let mut elem_types = Vec::with_capacity(elems.len());
+            let mut elem_vars = Vec::with_capacity(elems.len() + 1);
+
+            // Constraints need capacity for each element + 1 for the tuple itself
+            let mut tuple_constraints = Vec::with_capacity(1 + elems.len());
+
+            for (index, (elem_var, loc_elem_expr)) in elems.iter().enumerate() {
+                let (elem_type, elem_con) =
+                    constrain_field(constraints, env, *elem_var, loc_elem_expr);
+
+                elem_vars.push(*elem_var);
+                elem_types.push((index, elem_type));
+
+                tuple_constraints.push(elem_con);
+            }
+
+            let tuple_type = Type::Tuple(elem_types, TypeExtension::Closed);
+
+            let tuple_con = constraints.equal_types_with_storage(
+                tuple_type,
+                expected,
+                Category::Tuple,
+                region,
+                *tuple_var,
+            );
+
+            tuple_constraints.push(tuple_con);
+            elem_vars.push(*tuple_var);
+
+            let and_constraint = constraints.and_constraint(tuple_constraints);
+            constraints.exists(elem_vars, and_constraint)
+        }
         Update {
             record_var,
             ext_var,
@@ -567,6 +600,33 @@ pub fn constrain_expr(
             constraints.exists_many(vars, all_constraints)
         }
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable,
+        } => {
+            let dbg_type = Type::Variable(*variable);
+            let expected_dbg = NoExpectation(dbg_type);
+
+            let message_con = constrain_expr(
+                constraints,
+                env,
+                loc_message.region,
+                &loc_message.value,
+                expected_dbg,
+            );
+
+            let continuation_con = constrain_expr(
+                constraints,
+                env,
+                loc_continuation.region,
+                &loc_continuation.value,
+                expected,
+            );
+
+            constraints.exists_many([*variable], [message_con, continuation_con])
+        }
+
         If {
             cond_var,
             branch_var,
@@ -937,6 +997,39 @@ pub fn constrain_expr(
                 [constraint, eq, record_con],
             )
         }
+        TupleAccess {
+            tuple_var,
+            ext_var,
+            elem_var,
+            loc_expr,
+            index,
+        } => {
+            let ext_var = *ext_var;
+            let ext_type = Type::Variable(ext_var);
+            let elem_var = *elem_var;
+            let elem_type = Type::Variable(elem_var);
+
+            let tuple_type = Type::Tuple(
+                vec![(*index, elem_type)],
+                TypeExtension::from_type(ext_type),
+            );
+            let tuple_expected = Expected::NoExpectation(tuple_type);
+
+            let category = Category::TupleAccess(*index);
+
+            let tuple_con = constraints.equal_types_var(
+                *tuple_var,
+                tuple_expected.clone(),
+                category.clone(),
+                region,
+            );
+
+            let constraint =
+                constrain_expr(constraints, env, region, &loc_expr.value, tuple_expected);
+
+            let eq = constraints.equal_types_var(elem_var, expected, category, region);
+            constraints.exists_many([*tuple_var, elem_var, ext_var], [constraint, eq, tuple_con])
+        }
         Accessor(AccessorData {
             name: closure_name,
             function_var,
@@ -8,12 +8,12 @@ use roc_collections::SendMap;
 use roc_derive_key::decoding::FlatDecodableKey;
 use roc_error_macros::internal_error;
 use roc_module::called_via::CalledVia;
-use roc_module::ident::Lowercase;
+use roc_module::ident::{Lowercase, TagName};
 use roc_module::symbol::Symbol;
 use roc_region::all::{Loc, Region};
 use roc_types::subs::{
     Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
-    RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable,
+    RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
 };
 use roc_types::types::{AliasKind, RecordField};
 
@@ -28,6 +28,7 @@ pub(crate) fn derive_decoder(
     let (body, body_type) = match key {
         FlatDecodableKey::List() => decoder_list(env, def_symbol),
         FlatDecodableKey::Record(fields) => decoder_record(env, def_symbol, fields),
+        FlatDecodableKey::TagUnion(tags) => decoder_tag_union(env, def_symbol, tags),
     };
 
     let specialization_lambda_sets =
@@ -236,378 +237,38 @@ fn decoder_record_step_field(
         //                 }
         //     )
 
-        let this_custom_callback_var;
-        let custom_callback_ret_var;
-        let custom_callback = {
-            // \bytes, fmt ->
-            //     when Decode.decodeWith bytes Decode.decoder fmt is
-            //         rec ->
-            //             {
-            //                 rest: rec.rest,
-            //                 result: when rec.result is
-            //                     Ok val -> Ok {state & first: Ok val},
-            //                     Err err -> Err err
-            //             }
-            let bytes_arg_symbol = env.new_symbol("bytes");
-            let fmt_arg_symbol = env.new_symbol("fmt");
-            let bytes_arg_var = env.subs.fresh_unnamed_flex_var();
-            let fmt_arg_var = env.subs.fresh_unnamed_flex_var();
-
-            // rec.result : [Ok field_var, Err DecodeError]
-            let rec_dot_result = {
-                let tag_union = FlatType::TagUnion(
-                    UnionTags::for_result(env.subs, field_var, decode_err_var),
-                    Variable::EMPTY_TAG_UNION,
-                );
-
-                synth_var(env.subs, Content::Structure(tag_union))
-            };
-
-            // rec : { rest: List U8, result: (typeof rec.result) }
-            let rec_var = {
-                let fields = RecordFields::insert_into_subs(
-                    env.subs,
-                    [
-                        ("rest".into(), RecordField::Required(Variable::LIST_U8)),
-                        ("result".into(), RecordField::Required(rec_dot_result)),
-                    ],
-                );
-                let record = FlatType::Record(fields, Variable::EMPTY_RECORD);
-
-                synth_var(env.subs, Content::Structure(record))
-            };
-
-            // `Decode.decoder` for the field's value
-            let decoder_var = env.import_builtin_symbol_var(Symbol::DECODE_DECODER);
-            let decode_with_var = env.import_builtin_symbol_var(Symbol::DECODE_DECODE_WITH);
-            let lambda_set_var = env.subs.fresh_unnamed_flex_var();
-            let this_decode_with_var = {
-                let subs_slice = SubsSlice::insert_into_subs(
-                    env.subs,
-                    [bytes_arg_var, decoder_var, fmt_arg_var],
-                );
-                let this_decode_with_var = synth_var(
-                    env.subs,
-                    Content::Structure(FlatType::Func(subs_slice, lambda_set_var, rec_var)),
-                );
-
-                env.unify(decode_with_var, this_decode_with_var);
-
-                this_decode_with_var
-            };
-
-            // The result of decoding this field's value - either the updated state, or a decoding error.
-            let when_expr_var = {
-                let flat_type = FlatType::TagUnion(
-                    UnionTags::for_result(env.subs, state_record_var, decode_err_var),
-                    Variable::EMPTY_TAG_UNION,
-                );
-
-                synth_var(env.subs, Content::Structure(flat_type))
-            };
-
-            // What our decoder passed to `Decode.custom` returns - the result of decoding the
-            // field's value, and the remaining bytes.
-            custom_callback_ret_var = {
-                let rest_field = RecordField::Required(Variable::LIST_U8);
-                let result_field = RecordField::Required(when_expr_var);
-                let flat_type = FlatType::Record(
-                    RecordFields::insert_into_subs(
-                        env.subs,
-                        [("rest".into(), rest_field), ("result".into(), result_field)],
-                    ),
-                    Variable::EMPTY_RECORD,
-                );
-
-                synth_var(env.subs, Content::Structure(flat_type))
-            };
-
-            let custom_callback_body = {
-                let rec_symbol = env.new_symbol("rec");
-
-                // # Uses a single-branch `when` because `let` is more expensive to monomorphize
-                // # due to checks for polymorphic expressions, and `rec` would be polymorphic.
-                // when Decode.decodeWith bytes Decode.decoder fmt is
-                //     rec ->
-                //         {
-                //             rest: rec.rest,
-                //             result: when rec.result is
-                //                 Ok val -> Ok {state & first: Ok val},
-                //                 Err err -> Err err
-                //         }
-                let branch_body = {
-                    let result_val = {
-                        // result: when rec.result is
-                        //     Ok val -> Ok {state & first: Ok val},
-                        //     Err err -> Err err
-                        let ok_val_symbol = env.new_symbol("val");
-                        let err_val_symbol = env.new_symbol("err");
-                        let ok_branch_expr = {
-                            // Ok {state & first: Ok val},
-                            let mut updates = SendMap::default();
-
-                            updates.insert(
-                                field_name.clone(),
-                                Field {
-                                    var: result_field_var,
-                                    region: Region::zero(),
-                                    loc_expr: Box::new(Loc::at_zero(Expr::Tag {
-                                        tag_union_var: result_field_var,
-                                        ext_var: env.new_ext_var(ExtensionKind::TagUnion),
-                                        name: "Ok".into(),
-                                        arguments: vec![(
-                                            field_var,
-                                            Loc::at_zero(Expr::Var(ok_val_symbol)),
-                                        )],
-                                    })),
-                                },
-                            );
-
-                            let updated_record = Expr::Update {
-                                record_var: state_record_var,
-                                ext_var: env.new_ext_var(ExtensionKind::Record),
-                                symbol: state_arg_symbol,
-                                updates,
-                            };
-
-                            Expr::Tag {
-                                tag_union_var: when_expr_var,
-                                ext_var: env.new_ext_var(ExtensionKind::TagUnion),
-                                name: "Ok".into(),
-                                arguments: vec![(state_record_var, Loc::at_zero(updated_record))],
-                            }
-                        };
-
-                        let branches = vec![
-                            // Ok val -> Ok {state & first: Ok val},
-                            WhenBranch {
-                                patterns: vec![WhenBranchPattern {
-                                    pattern: Loc::at_zero(Pattern::AppliedTag {
-                                        whole_var: rec_dot_result,
-                                        ext_var: Variable::EMPTY_TAG_UNION,
-                                        tag_name: "Ok".into(),
-                                        arguments: vec![(
-                                            field_var,
-                                            Loc::at_zero(Pattern::Identifier(ok_val_symbol)),
-                                        )],
-                                    }),
-                                    degenerate: false,
-                                }],
-                                value: Loc::at_zero(ok_branch_expr),
-                                guard: None,
-                                redundant: RedundantMark::known_non_redundant(),
-                            },
-                            // Err err -> Err err
-                            WhenBranch {
-                                patterns: vec![WhenBranchPattern {
-                                    pattern: Loc::at_zero(Pattern::AppliedTag {
-                                        whole_var: rec_dot_result,
-                                        ext_var: Variable::EMPTY_TAG_UNION,
-                                        tag_name: "Err".into(),
-                                        arguments: vec![(
-                                            decode_err_var,
-                                            Loc::at_zero(Pattern::Identifier(err_val_symbol)),
-                                        )],
-                                    }),
-                                    degenerate: false,
-                                }],
-                                value: Loc::at_zero(Expr::Tag {
-                                    tag_union_var: when_expr_var,
-                                    ext_var: env.new_ext_var(ExtensionKind::TagUnion),
-                                    name: "Err".into(),
-                                    arguments: vec![(
-                                        decode_err_var,
-                                        Loc::at_zero(Expr::Var(err_val_symbol)),
-                                    )],
-                                }),
-                                guard: None,
-                                redundant: RedundantMark::known_non_redundant(),
-                            },
-                        ];
-
-                        // when rec.result is
-                        //     Ok val -> Ok {state & first: Ok val},
-                        //     Err err -> Err err
-                        Expr::When {
-                            loc_cond: Box::new(Loc::at_zero(Expr::Access {
-                                record_var: rec_var,
-                                ext_var: env.new_ext_var(ExtensionKind::Record),
-                                field_var: rec_dot_result,
-                                loc_expr: Box::new(Loc::at_zero(Expr::Var(rec_symbol))),
-                                field: "result".into(),
-                            })),
-                            cond_var: rec_dot_result,
-                            expr_var: when_expr_var,
-                            region: Region::zero(),
-                            branches,
-                            branches_cond_var: rec_dot_result,
-                            exhaustive: ExhaustiveMark::known_exhaustive(),
-                        }
-                    };
-
-                    // {
-                    //     rest: rec.rest,
-                    //     result: when rec.result is
-                    //         Ok val -> Ok {state & first: Ok val},
-                    //         Err err -> Err err
-                    // }
-                    let mut fields_map = SendMap::default();
-
-                    fields_map.insert(
-                        "rest".into(),
-                        Field {
-                            var: Variable::LIST_U8,
-                            region: Region::zero(),
-                            loc_expr: Box::new(Loc::at_zero(Expr::Access {
-                                record_var: rec_var,
-                                ext_var: env.new_ext_var(ExtensionKind::Record),
-                                field_var: Variable::LIST_U8,
-                                loc_expr: Box::new(Loc::at_zero(Expr::Var(rec_symbol))),
-                                field: "rest".into(),
-                            })),
-                        },
-                    );
-
-                    // result: when rec.result is
-                    //     Ok val -> Ok {state & first: Ok val},
-                    //     Err err -> Err err
-                    fields_map.insert(
-                        "result".into(),
-                        Field {
-                            var: when_expr_var,
-                            region: Region::zero(),
-                            loc_expr: Box::new(Loc::at_zero(result_val)),
-                        },
-                    );
-
-                    Expr::Record {
-                        record_var: custom_callback_ret_var,
-                        fields: fields_map,
-                    }
-                };
-
-                let branch = WhenBranch {
-                    patterns: vec![WhenBranchPattern {
-                        pattern: Loc::at_zero(Pattern::Identifier(rec_symbol)),
-                        degenerate: false,
-                    }],
-                    value: Loc::at_zero(branch_body),
-                    guard: None,
-                    redundant: RedundantMark::known_non_redundant(),
-                };
-
-                let condition_expr = Expr::Call(
-                    Box::new((
-                        this_decode_with_var,
-                        Loc::at_zero(Expr::Var(Symbol::DECODE_DECODE_WITH)),
-                        lambda_set_var,
-                        rec_var,
-                    )),
-                    vec![
-                        (Variable::LIST_U8, Loc::at_zero(Expr::Var(bytes_arg_symbol))),
-                        (
-                            decoder_var,
-                            Loc::at_zero(Expr::AbilityMember(
-                                Symbol::DECODE_DECODER,
-                                None,
-                                decoder_var,
-                            )),
-                        ),
-                        (fmt_arg_var, Loc::at_zero(Expr::Var(fmt_arg_symbol))),
-                    ],
-                    CalledVia::Space,
+        let (decode_custom, decode_custom_ret_var) = custom_decoder_updating_state(
+            env,
+            field_var,
+            state_record_var,
+            decode_err_var,
+            vec![(state_arg_symbol, state_record_var)],
+            |env, val_symbol| {
+                // {state & first: Ok val}
+                let mut updates = SendMap::default();
+
+                updates.insert(
+                    field_name.clone(),
+                    Field {
+                        var: result_field_var,
+                        region: Region::zero(),
+                        loc_expr: Box::new(Loc::at_zero(Expr::Tag {
+                            tag_union_var: result_field_var,
+                            ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+                            name: "Ok".into(),
+                            arguments: vec![(field_var, Loc::at_zero(Expr::Var(val_symbol)))],
+                        })),
+                    },
                 );
 
-                // when Decode.decodeWith bytes Decode.decoder fmt is
-                Expr::When {
-                    loc_cond: Box::new(Loc::at_zero(condition_expr)),
-                    cond_var: rec_var,
-                    expr_var: custom_callback_ret_var,
-                    region: Region::zero(),
-                    branches: vec![branch],
-                    branches_cond_var: rec_var,
-                    exhaustive: ExhaustiveMark::known_exhaustive(),
+                Expr::Update {
+                    record_var: state_record_var,
+                    ext_var: env.new_ext_var(ExtensionKind::Record),
+                    symbol: state_arg_symbol,
+                    updates,
                 }
-            };
-
-            let custom_closure_symbol = env.new_symbol("customCallback");
-            this_custom_callback_var = env.subs.fresh_unnamed_flex_var();
-            let custom_callback_lambda_set_var = {
-                let content = Content::LambdaSet(LambdaSet {
-                    solved: UnionLambdas::insert_into_subs(
-                        env.subs,
-                        [(custom_closure_symbol, [state_record_var])],
-                    ),
-                    recursion_var: OptVariable::NONE,
-                    unspecialized: Default::default(),
-                    ambient_function: this_custom_callback_var,
-                });
-                let custom_callback_lambda_set_var = synth_var(env.subs, content);
-                let subs_slice =
-                    SubsSlice::insert_into_subs(env.subs, [bytes_arg_var, fmt_arg_var]);
-
-                env.subs.set_content(
-                    this_custom_callback_var,
-                    Content::Structure(FlatType::Func(
-                        subs_slice,
-                        custom_callback_lambda_set_var,
-                        custom_callback_ret_var,
-                    )),
-                );
-
-                custom_callback_lambda_set_var
-            };
-
-            // \bytes, fmt -> …
-            Expr::Closure(ClosureData {
-                function_type: this_custom_callback_var,
-                closure_type: custom_callback_lambda_set_var,
-                return_type: custom_callback_ret_var,
-                name: custom_closure_symbol,
-                captured_symbols: vec![(state_arg_symbol, state_record_var)],
-                recursive: Recursive::NotRecursive,
-                arguments: vec![
-                    (
-                        bytes_arg_var,
-                        AnnotatedMark::known_exhaustive(),
-                        Loc::at_zero(Pattern::Identifier(bytes_arg_symbol)),
-                    ),
-                    (
-                        fmt_arg_var,
-                        AnnotatedMark::known_exhaustive(),
-                        Loc::at_zero(Pattern::Identifier(fmt_arg_symbol)),
-                    ),
-                ],
-                loc_body: Box::new(Loc::at_zero(custom_callback_body)),
-            })
-        };
-
-        let decode_custom_ret_var = env.subs.fresh_unnamed_flex_var();
-        let decode_custom = {
-            let decode_custom_var = env.import_builtin_symbol_var(Symbol::DECODE_CUSTOM);
-            let decode_custom_closure_var = env.subs.fresh_unnamed_flex_var();
-            let this_decode_custom_var = {
-                let subs_slice = SubsSlice::insert_into_subs(env.subs, [this_custom_callback_var]);
-                let flat_type =
-                    FlatType::Func(subs_slice, decode_custom_closure_var, decode_custom_ret_var);
-
-                synth_var(env.subs, Content::Structure(flat_type))
-            };
-
-            env.unify(decode_custom_var, this_decode_custom_var);
-
-            // Decode.custom \bytes, fmt -> …
-            Expr::Call(
-                Box::new((
-                    this_decode_custom_var,
-                    Loc::at_zero(Expr::Var(Symbol::DECODE_CUSTOM)),
-                    decode_custom_closure_var,
-                    decode_custom_ret_var,
-                )),
-                vec![(this_custom_callback_var, Loc::at_zero(custom_callback))],
-                CalledVia::Space,
-            )
-        };
+            },
+        );
 
         env.unify(keep_payload_var, decode_custom_ret_var);
 
@@ -732,6 +393,380 @@ fn decoder_record_step_field(
     (expr, function_type)
 }
 
+// Builds a decoder that decodes a value, and then uses it to update a decoding state.
+//
+// Example:
+// Decode.custom \bytes, fmt ->
+//     # Uses a single-branch `when` because `let` is more expensive to monomorphize
+//     # due to checks for polymorphic expressions, and `rec` would be polymorphic.
+//     when Decode.decodeWith bytes Decode.decoder fmt is
+//         rec ->
+//             {
+//                 rest: rec.rest,
+//                 result: when rec.result is
+//                     Ok val -> Ok {state & first: Ok val},
+//                     Err err -> Err err
+//             }
+//
+// where `{state & first: Ok val}` is built by `updated_state`, and `captures` are the symbols it
+// refers to.
+fn custom_decoder_updating_state(
+    env: &mut Env,
+    value_var: Variable,
+    state_var: Variable,
+    decode_err_var: Variable,
+    captures: Vec<(Symbol, Variable)>,
+    updated_state: impl FnOnce(&mut Env, Symbol) -> Expr,
+) -> (Expr, Variable) {
+    let this_custom_callback_var;
+    let custom_callback_ret_var;
+    let custom_callback = {
+        // \bytes, fmt ->
+        //     when Decode.decodeWith bytes Decode.decoder fmt is
+        //         rec ->
+        //             {
+        //                 rest: rec.rest,
+        //                 result: when rec.result is
+        //                     Ok val -> Ok {state & first: Ok val},
+        //                     Err err -> Err err
+        //             }
+        let bytes_arg_symbol = env.new_symbol("bytes");
+        let fmt_arg_symbol = env.new_symbol("fmt");
+        let bytes_arg_var = env.subs.fresh_unnamed_flex_var();
+        let fmt_arg_var = env.subs.fresh_unnamed_flex_var();
+
+        // rec.result : [Ok value_var, Err DecodeError]
+        let rec_dot_result = {
+            let tag_union = FlatType::TagUnion(
+                UnionTags::for_result(env.subs, value_var, decode_err_var),
+                Variable::EMPTY_TAG_UNION,
+            );
+
+            synth_var(env.subs, Content::Structure(tag_union))
+        };
+
+        // rec : { rest: List U8, result: (typeof rec.result) }
+        let rec_var = {
+            let fields = RecordFields::insert_into_subs(
+                env.subs,
+                [
+                    ("rest".into(), RecordField::Required(Variable::LIST_U8)),
+                    ("result".into(), RecordField::Required(rec_dot_result)),
+                ],
+            );
+            let record = FlatType::Record(fields, Variable::EMPTY_RECORD);
+
+            synth_var(env.subs, Content::Structure(record))
+        };
+
+        // `Decode.decoder` for the field's value
+        let decoder_var = env.import_builtin_symbol_var(Symbol::DECODE_DECODER);
+        let decode_with_var = env.import_builtin_symbol_var(Symbol::DECODE_DECODE_WITH);
+        let lambda_set_var = env.subs.fresh_unnamed_flex_var();
+        let this_decode_with_var = {
+            let subs_slice =
+                SubsSlice::insert_into_subs(env.subs, [bytes_arg_var, decoder_var, fmt_arg_var]);
+            let this_decode_with_var = synth_var(
+                env.subs,
+                Content::Structure(FlatType::Func(subs_slice, lambda_set_var, rec_var)),
+            );
+
+            env.unify(decode_with_var, this_decode_with_var);
+
+            this_decode_with_var
+        };
+
+        // The result of decoding this field's value - either the updated state, or a decoding error.
+        let when_expr_var = {
+            let flat_type = FlatType::TagUnion(
+                UnionTags::for_result(env.subs, state_var, decode_err_var),
+                Variable::EMPTY_TAG_UNION,
+            );
+
+            synth_var(env.subs, Content::Structure(flat_type))
+        };
+
+        // What our decoder passed to `Decode.custom` returns - the result of decoding the
+        // field's value, and the remaining bytes.
+        custom_callback_ret_var = {
+            let rest_field = RecordField::Required(Variable::LIST_U8);
+            let result_field = RecordField::Required(when_expr_var);
+            let flat_type = FlatType::Record(
+                RecordFields::insert_into_subs(
+                    env.subs,
+                    [("rest".into(), rest_field), ("result".into(), result_field)],
+                ),
+                Variable::EMPTY_RECORD,
+            );
+
+            synth_var(env.subs, Content::Structure(flat_type))
+        };
+
+        let custom_callback_body = {
+            let rec_symbol = env.new_symbol("rec");
+
+            // # Uses a single-branch `when` because `let` is more expensive to monomorphize
+            // # due to checks for polymorphic expressions, and `rec` would be polymorphic.
+            // when Decode.decodeWith bytes Decode.decoder fmt is
+            //     rec ->
+            //         {
+            //             rest: rec.rest,
+            //             result: when rec.result is
+            //                 Ok val -> Ok {state & first: Ok val},
+            //                 Err err -> Err err
+            //         }
+            let branch_body = {
+                let result_val = {
+                    // result: when rec.result is
+                    //     Ok val -> Ok {state & first: Ok val},
+                    //     Err err -> Err err
+                    let ok_val_symbol = env.new_symbol("val");
+                    let err_val_symbol = env.new_symbol("err");
+                    let ok_branch_expr = {
+                        // Ok {state & first: Ok val},
+                        let updated_state = updated_state(env, ok_val_symbol);
+
+                        Expr::Tag {
+                            tag_union_var: when_expr_var,
+                            ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+                            name: "Ok".into(),
+                            arguments: vec![(state_var, Loc::at_zero(updated_state))],
+                        }
+                    };
+
+                    let branches = vec![
+                        // Ok val -> Ok {state & first: Ok val},
+                        WhenBranch {
+                            patterns: vec![WhenBranchPattern {
+                                pattern: Loc::at_zero(Pattern::AppliedTag {
+                                    whole_var: rec_dot_result,
+                                    ext_var: Variable::EMPTY_TAG_UNION,
+                                    tag_name: "Ok".into(),
+                                    arguments: vec![(
+                                        value_var,
+                                        Loc::at_zero(Pattern::Identifier(ok_val_symbol)),
+                                    )],
+                                }),
+                                degenerate: false,
+                            }],
+                            value: Loc::at_zero(ok_branch_expr),
+                            guard: None,
+                            redundant: RedundantMark::known_non_redundant(),
+                        },
+                        // Err err -> Err err
+                        WhenBranch {
+                            patterns: vec![WhenBranchPattern {
+                                pattern: Loc::at_zero(Pattern::AppliedTag {
+                                    whole_var: rec_dot_result,
+                                    ext_var: Variable::EMPTY_TAG_UNION,
+                                    tag_name: "Err".into(),
+                                    arguments: vec![(
+                                        decode_err_var,
+                                        Loc::at_zero(Pattern::Identifier(err_val_symbol)),
+                                    )],
+                                }),
+                                degenerate: false,
+                            }],
+                            value: Loc::at_zero(Expr::Tag {
+                                tag_union_var: when_expr_var,
+                                ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+                                name: "Err".into(),
+                                arguments: vec![(
+                                    decode_err_var,
+                                    Loc::at_zero(Expr::Var(err_val_symbol)),
+                                )],
+                            }),
+                            guard: None,
+                            redundant: RedundantMark::known_non_redundant(),
+                        },
+                    ];
+
+                    // when rec.result is
+                    //     Ok val -> Ok {state & first: Ok val},
+                    //     Err err -> Err err
+                    Expr::When {
+                        loc_cond: Box::new(Loc::at_zero(Expr::Access {
+                            record_var: rec_var,
+                            ext_var: env.new_ext_var(ExtensionKind::Record),
+                            field_var: rec_dot_result,
+                            loc_expr: Box::new(Loc::at_zero(Expr::Var(rec_symbol))),
+                            field: "result".into(),
+                        })),
+                        cond_var: rec_dot_result,
+                        expr_var: when_expr_var,
+                        region: Region::zero(),
+                        branches,
+                        branches_cond_var: rec_dot_result,
+                        exhaustive: ExhaustiveMark::known_exhaustive(),
+                    }
+                };
+
+                // {
+                //     rest: rec.rest,
+                //     result: when rec.result is
+                //         Ok val -> Ok {state & first: Ok val},
+                //         Err err -> Err err
+                // }
+                let mut fields_map = SendMap::default();
+
+                fields_map.insert(
+                    "rest".into(),
+                    Field {
+                        var: Variable::LIST_U8,
+                        region: Region::zero(),
+                        loc_expr: Box::new(Loc::at_zero(Expr::Access {
+                            record_var: rec_var,
+                            ext_var: env.new_ext_var(ExtensionKind::Record),
+                            field_var: Variable::LIST_U8,
+                            loc_expr: Box::new(Loc::at_zero(Expr::Var(rec_symbol))),
+                            field: "rest".into(),
+                        })),
+                    },
+                );
+
+                // result: when rec.result is
+                //     Ok val -> Ok {state & first: Ok val},
+                //     Err err -> Err err
+                fields_map.insert(
+                    "result".into(),
+                    Field {
+                        var: when_expr_var,
+                        region: Region::zero(),
+                        loc_expr: Box::new(Loc::at_zero(result_val)),
+                    },
+                );
+
+                Expr::Record {
+                    record_var: custom_callback_ret_var,
+                    fields: fields_map,
+                }
+            };
+
+            let branch = WhenBranch {
+                patterns: vec![WhenBranchPattern {
+                    pattern: Loc::at_zero(Pattern::Identifier(rec_symbol)),
+                    degenerate: false,
+                }],
+                value: Loc::at_zero(branch_body),
+                guard: None,
+                redundant: RedundantMark::known_non_redundant(),
+            };
+
+            let condition_expr = Expr::Call(
+                Box::new((
+                    this_decode_with_var,
+                    Loc::at_zero(Expr::Var(Symbol::DECODE_DECODE_WITH)),
+                    lambda_set_var,
+                    rec_var,
+                )),
+                vec![
+                    (Variable::LIST_U8, Loc::at_zero(Expr::Var(bytes_arg_symbol))),
+                    (
+                        decoder_var,
+                        Loc::at_zero(Expr::AbilityMember(
+                            Symbol::DECODE_DECODER,
+                            None,
+                            decoder_var,
+                        )),
+                    ),
+                    (fmt_arg_var, Loc::at_zero(Expr::Var(fmt_arg_symbol))),
+                ],
+                CalledVia::Space,
+            );
+
+            // when Decode.decodeWith bytes Decode.decoder fmt is
+            Expr::When {
+                loc_cond: Box::new(Loc::at_zero(condition_expr)),
+                cond_var: rec_var,
+                expr_var: custom_callback_ret_var,
+                region: Region::zero(),
+                branches: vec![branch],
+                branches_cond_var: rec_var,
+                exhaustive: ExhaustiveMark::known_exhaustive(),
+            }
+        };
+
+        let custom_closure_symbol = env.new_symbol("customCallback");
+        this_custom_callback_var = env.subs.fresh_unnamed_flex_var();
+        let custom_callback_lambda_set_var = {
+            let content = Content::LambdaSet(LambdaSet {
+                solved: UnionLambdas::insert_into_subs(
+                    env.subs,
+                    [(custom_closure_symbol, captures.iter().map(|(_, var)| *var))],
+                ),
+                recursion_var: OptVariable::NONE,
+                unspecialized: Default::default(),
+                ambient_function: this_custom_callback_var,
+            });
+            let custom_callback_lambda_set_var = synth_var(env.subs, content);
+            let subs_slice = SubsSlice::insert_into_subs(env.subs, [bytes_arg_var, fmt_arg_var]);
+
+            env.subs.set_content(
+                this_custom_callback_var,
+                Content::Structure(FlatType::Func(
+                    subs_slice,
+                    custom_callback_lambda_set_var,
+                    custom_callback_ret_var,
+                )),
+            );
+
+            custom_callback_lambda_set_var
+        };
+
+        // \bytes, fmt -> …
+        Expr::Closure(ClosureData {
+            function_type: this_custom_callback_var,
+            closure_type: custom_callback_lambda_set_var,
+            return_type: custom_callback_ret_var,
+            name: custom_closure_symbol,
+            captured_symbols: captures,
+            recursive: Recursive::NotRecursive,
+            arguments: vec![
+                (
+                    bytes_arg_var,
+                    AnnotatedMark::known_exhaustive(),
+                    Loc::at_zero(Pattern::Identifier(bytes_arg_symbol)),
+                ),
+                (
+                    fmt_arg_var,
+                    AnnotatedMark::known_exhaustive(),
+                    Loc::at_zero(Pattern::Identifier(fmt_arg_symbol)),
+                ),
+            ],
+            loc_body: Box::new(Loc::at_zero(custom_callback_body)),
+        })
+    };
+
+    let decode_custom_ret_var = env.subs.fresh_unnamed_flex_var();
+    let decode_custom = {
+        let decode_custom_var = env.import_builtin_symbol_var(Symbol::DECODE_CUSTOM);
+        let decode_custom_closure_var = env.subs.fresh_unnamed_flex_var();
+        let this_decode_custom_var = {
+            let subs_slice = SubsSlice::insert_into_subs(env.subs, [this_custom_callback_var]);
+            let flat_type =
+                FlatType::Func(subs_slice, decode_custom_closure_var, decode_custom_ret_var);
+
+            synth_var(env.subs, Content::Structure(flat_type))
+        };
+
+        env.unify(decode_custom_var, this_decode_custom_var);
+
+        // Decode.custom \bytes, fmt -> …
+        Expr::Call(
+            Box::new((
+                this_decode_custom_var,
+                Loc::at_zero(Expr::Var(Symbol::DECODE_CUSTOM)),
+                decode_custom_closure_var,
+                decode_custom_ret_var,
+            )),
+            vec![(this_custom_callback_var, Loc::at_zero(custom_callback))],
+            CalledVia::Space,
+        )
+    };
+
+    (decode_custom, decode_custom_ret_var)
+}
+
 // Example:
 // finalizer = \rec ->
 //     when rec.first is
@@ -991,6 +1026,552 @@ fn decoder_record_initial_state(
     )
 }
 
+// Implements decoding of a tag union. For example, for
+//
+//   [A a b, B]
+//
+// we'd like to generate an impl like
+//
+// decoder : Decoder [A a b, B] fmt | a has Decoding, b has Decoding, fmt has DecoderFormatting
+// decoder =
+//     # The decoding state records which tag we are decoding, and the payloads decoded so far.
+//     # We write `A#1` for the state of the `A` tag with one payload decoded.
+//     stepTag = \tag ->
+//         when tag is
+//             "A" -> Keep A#0
+//             "B" -> Keep B#0
+//             _ -> Skip
+//
+//     stepPayload = \state ->
+//         when state is
+//             A#0 ->
+//                 Keep (Decode.custom \bytes, fmt ->
+//                     when Decode.decodeWith bytes Decode.decoder fmt is
+//                         rec ->
+//                             {
+//                                 rest: rec.rest,
+//                                 result: when rec.result is
+//                                     Ok val -> Ok (A#1 val),
+//                                     Err err -> Err err
+//                             })
+//             A#1 p0 ->
+//                 Keep (Decode.custom \bytes, fmt ->
+//                     when Decode.decodeWith bytes Decode.decoder fmt is
+//                         rec ->
+//                             {
+//                                 rest: rec.rest,
+//                                 result: when rec.result is
+//                                     Ok val -> Ok (A#2 p0 val),
+//                                     Err err -> Err err
+//                             })
+//             _ -> Skip
+//
+//     finalizer = \state ->
+//         when state is
+//             A#2 p0 p1 -> Ok (A p0 p1)
+//             B#0 -> Ok B
+//             _ -> Err TooShort
+//
+//     Decode.custom \bytes, fmt -> Decode.decodeWith bytes (Decode.tag stepTag stepPayload finalizer) fmt
+fn decoder_tag_union(
+    env: &mut Env,
+    _def_symbol: Symbol,
+    tags: Vec<(TagName, u16)>,
+) -> (Expr, Variable) {
+    // Generalized tag union var so we can reuse this impl between many unions:
+    // if tags = [ A arity=2, B arity=0 ], this is [ A t1 t2, B ] for fresh t1, t2
+    let tags: Vec<(TagName, Vec<Variable>)> = tags
+        .into_iter()
+        .map(|(tag_name, arity)| {
+            let payload_vars = std::iter::repeat_with(|| env.subs.fresh_unnamed_flex_var())
+                .take(arity as usize)
+                .collect();
+            (tag_name, payload_vars)
+        })
+        .collect();
+
+    let tag_union_var = {
+        let union_tags = tag_union_tags(
+            env,
+            tags.iter()
+                .map(|(tag_name, payload_vars)| (tag_name.clone(), payload_vars.as_slice())),
+        );
+
+        synth_var(
+            env.subs,
+            Content::Structure(FlatType::TagUnion(union_tags, Variable::EMPTY_TAG_UNION)),
+        )
+    };
+
+    // The decoding state, e.g. [A#0, A#1 t1, A#2 t1 t2, B#0]
+    let state_var = {
+        let partial_tags = tags.iter().flat_map(|(tag_name, payload_vars)| {
+            (0..=payload_vars.len()).map(move |decoded| {
+                (
+                    partial_tag_name(tag_name, decoded),
+                    &payload_vars[..decoded],
+                )
+            })
+        });
+        let union_tags = tag_union_tags(env, partial_tags);
+
+        synth_var(
+            env.subs,
+            Content::Structure(FlatType::TagUnion(union_tags, Variable::EMPTY_TAG_UNION)),
+        )
+    };
+
+    let decode_err_var = {
+        let flat_type = FlatType::TagUnion(
+            UnionTags::tag_without_arguments(env.subs, "TooShort".into()),
+            Variable::EMPTY_TAG_UNION,
+        );
+
+        synth_var(env.subs, Content::Structure(flat_type))
+    };
+
+    // stepTag = ...
+    let (step_tag, step_tag_var) = decoder_tag_union_step_tag(env, &tags, state_var);
+
+    // stepPayload = ...
+    let (step_payload, step_payload_var) =
+        decoder_tag_union_step_payload(env, &tags, state_var, decode_err_var);
+
+    // finalizer = ...
+    let (finalizer, finalizer_var) =
+        decoder_tag_union_finalizer(env, &tags, tag_union_var, state_var, decode_err_var);
+
+    // Build up the type of `Decode.tag` we expect
+    let tag_decoder_var = env.subs.fresh_unnamed_flex_var();
+    let decode_tag_lambda_set = env.subs.fresh_unnamed_flex_var();
+    let decode_tag_var = env.import_builtin_symbol_var(Symbol::DECODE_TAG);
+    let this_decode_tag_var = {
+        let flat_type = FlatType::Func(
+            SubsSlice::insert_into_subs(env.subs, [step_tag_var, step_payload_var, finalizer_var]),
+            decode_tag_lambda_set,
+            tag_decoder_var,
+        );
+
+        synth_var(env.subs, Content::Structure(flat_type))
+    };
+
+    env.unify(decode_tag_var, this_decode_tag_var);
+
+    // Decode.tag stepTag stepPayload finalizer
+    let call_decode_tag = Expr::Call(
+        Box::new((
+            this_decode_tag_var,
+            Loc::at_zero(Expr::AbilityMember(
+                Symbol::DECODE_TAG,
+                None,
+                this_decode_tag_var,
+            )),
+            decode_tag_lambda_set,
+            tag_decoder_var,
+        )),
+        vec![
+            (step_tag_var, Loc::at_zero(step_tag)),
+            (step_payload_var, Loc::at_zero(step_payload)),
+            (finalizer_var, Loc::at_zero(finalizer)),
+        ],
+        CalledVia::Space,
+    );
+
+    let bytes_sym = env.new_symbol("bytes");
+    let fmt_sym = env.new_symbol("fmt");
+    let fmt_var = env.subs.fresh_unnamed_flex_var();
+
+    wrap_in_decode_custom_decode_with(
+        env,
+        bytes_sym,
+        (fmt_sym, fmt_var),
+        vec![],
+        (call_decode_tag, tag_decoder_var),
+    )
+}
+
+// The name of the decoding state of a tag that has had `decoded` payloads decoded so far.
+// Tag names can't contain `#`, so this never collides with a tag of the decoded union.
+fn partial_tag_name(tag_name: &TagName, decoded: usize) -> TagName {
+    format!("{}#{}", tag_name.0.as_str(), decoded)
+        .as_str()
+        .into()
+}
+
+fn tag_union_tags<'v>(
+    env: &mut Env,
+    tags: impl Iterator<Item = (TagName, &'v [Variable])>,
+) -> UnionTags {
+    let mut tags: Vec<_> = tags
+        .map(|(tag_name, vars)| {
+            let vars_slice = VariableSubsSlice::insert_into_subs(env.subs, vars.iter().copied());
+            (tag_name, vars_slice)
+        })
+        .collect();
+
+    // Tags must be sorted by name.
+    tags.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+
+    UnionTags::insert_slices_into_subs(env.subs, tags)
+}
+
+fn tag_pattern(
+    whole_var: Variable,
+    tag_name: TagName,
+    arguments: impl IntoIterator<Item = (Variable, Symbol)>,
+) -> Loc<Pattern> {
+    Loc::at_zero(Pattern::AppliedTag {
+        whole_var,
+        ext_var: Variable::EMPTY_TAG_UNION,
+        tag_name,
+        arguments: arguments
+            .into_iter()
+            .map(|(var, symbol)| (var, Loc::at_zero(Pattern::Identifier(symbol))))
+            .collect(),
+    })
+}
+
+fn tag_expr(
+    env: &mut Env,
+    tag_union_var: Variable,
+    name: TagName,
+    arguments: impl IntoIterator<Item = (Variable, Symbol)>,
+) -> Expr {
+    Expr::Tag {
+        tag_union_var,
+        ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+        name,
+        arguments: arguments
+            .into_iter()
+            .map(|(var, symbol)| (var, Loc::at_zero(Expr::Var(symbol))))
+            .collect(),
+    }
+}
+
+// [Keep payload, Skip]
+fn keep_or_skip_var(env: &mut Env, keep_payload_var: Variable) -> Variable {
+    let keep_payload_subs_slice = SubsSlice::insert_into_subs(env.subs, [keep_payload_var]);
+    let flat_type = FlatType::TagUnion(
+        UnionTags::insert_slices_into_subs(
+            env.subs,
+            [
+                ("Keep".into(), keep_payload_subs_slice),
+                ("Skip".into(), Default::default()),
+            ],
+        ),
+        Variable::EMPTY_TAG_UNION,
+    );
+
+    synth_var(env.subs, Content::Structure(flat_type))
+}
+
+fn when_branch(pattern: Loc<Pattern>, value: Expr) -> WhenBranch {
+    WhenBranch {
+        patterns: vec![WhenBranchPattern {
+            pattern,
+            degenerate: false,
+        }],
+        value: Loc::at_zero(value),
+        guard: None,
+        redundant: RedundantMark::known_non_redundant(),
+    }
+}
+
+// Builds a closure `\arg -> body` with no captures.
+fn single_arg_closure(
+    env: &mut Env,
+    name: Symbol,
+    (arg_symbol, arg_var): (Symbol, Variable),
+    (body, body_var): (Expr, Variable),
+) -> (Expr, Variable) {
+    let function_type = env.subs.fresh_unnamed_flex_var();
+    let closure_type = {
+        let lambda_set = LambdaSet {
+            solved: UnionLambdas::tag_without_arguments(env.subs, name),
+            recursion_var: OptVariable::NONE,
+            unspecialized: Default::default(),
+            ambient_function: function_type,
+        };
+
+        synth_var(env.subs, Content::LambdaSet(lambda_set))
+    };
+
+    {
+        let args_slice = SubsSlice::insert_into_subs(env.subs, [arg_var]);
+
+        env.subs.set_content(
+            function_type,
+            Content::Structure(FlatType::Func(args_slice, closure_type, body_var)),
+        )
+    };
+
+    let expr = Expr::Closure(ClosureData {
+        function_type,
+        closure_type,
+        return_type: body_var,
+        name,
+        captured_symbols: Vec::new(),
+        recursive: Recursive::NotRecursive,
+        arguments: vec![(
+            arg_var,
+            AnnotatedMark::known_exhaustive(),
+            Loc::at_zero(Pattern::Identifier(arg_symbol)),
+        )],
+        loc_body: Box::new(Loc::at_zero(body)),
+    });
+
+    (expr, function_type)
+}
+
+// Example:
+// stepTag = \tag ->
+//     when tag is
+//         "A" -> Keep A#0
+//         "B" -> Keep B#0
+//         _ -> Skip
+fn decoder_tag_union_step_tag(
+    env: &mut Env,
+    tags: &[(TagName, Vec<Variable>)],
+    state_var: Variable,
+) -> (Expr, Variable) {
+    let tag_arg_symbol = env.new_symbol("tag");
+    let keep_or_skip_var = keep_or_skip_var(env, state_var);
+
+    // +1 because of the default branch.
+    let mut branches = Vec::with_capacity(tags.len() + 1);
+
+    for (tag_name, _) in tags {
+        // "A" -> Keep A#0
+        let initial_state = tag_expr(env, state_var, partial_tag_name(tag_name, 0), []);
+        let keep = Expr::Tag {
+            tag_union_var: keep_or_skip_var,
+            ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+            name: "Keep".into(),
+            arguments: vec![(state_var, Loc::at_zero(initial_state))],
+        };
+        let pattern = Loc::at_zero(Pattern::StrLiteral(tag_name.0.as_str().into()));
+
+        branches.push(when_branch(pattern, keep));
+    }
+
+    // _ -> Skip
+    let skip = tag_expr(env, keep_or_skip_var, "Skip".into(), []);
+    branches.push(when_branch(Loc::at_zero(Pattern::Underscore), skip));
+
+    // when tag is
+    let body = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(tag_arg_symbol))),
+        cond_var: Variable::STR,
+        expr_var: keep_or_skip_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: Variable::STR,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    let step_tag_closure = env.new_symbol("stepTag");
+
+    single_arg_closure(
+        env,
+        step_tag_closure,
+        (tag_arg_symbol, Variable::STR),
+        (body, keep_or_skip_var),
+    )
+}
+
+// Example:
+// stepPayload = \state ->
+//     when state is
+//         A#0 ->
+//             Keep (Decode.custom \bytes, fmt ->
+//                 when Decode.decodeWith bytes Decode.decoder fmt is
+//                     rec ->
+//                         {
+//                             rest: rec.rest,
+//                             result: when rec.result is
+//                                 Ok val -> Ok (A#1 val),
+//                                 Err err -> Err err
+//                         })
+//         A#1 p0 ->
+//             Keep (Decode.custom \bytes, fmt -> ... Ok (A#2 p0 val) ...)
+//         _ -> Skip
+fn decoder_tag_union_step_payload(
+    env: &mut Env,
+    tags: &[(TagName, Vec<Variable>)],
+    state_var: Variable,
+    decode_err_var: Variable,
+) -> (Expr, Variable) {
+    let state_arg_symbol = env.new_symbol("state");
+    let keep_payload_var = env.subs.fresh_unnamed_flex_var();
+    let keep_or_skip_var = keep_or_skip_var(env, keep_payload_var);
+
+    let mut branches = Vec::new();
+
+    for (tag_name, payload_vars) in tags {
+        for decoded in 0..payload_vars.len() {
+            // The payloads decoded so far; the decoder captures them to build the next state.
+            let payload_symbols: Vec<_> = std::iter::repeat_with(|| env.new_symbol("payload"))
+                .take(decoded)
+                .collect();
+            let decoded_payloads: Vec<_> = payload_vars[..decoded]
+                .iter()
+                .copied()
+                .zip(payload_symbols.iter().copied())
+                .collect();
+
+            // A#1 p0
+            let pattern = tag_pattern(
+                state_var,
+                partial_tag_name(tag_name, decoded),
+                decoded_payloads.iter().copied(),
+            );
+
+            // Decode.custom \bytes, fmt -> ... Ok (A#2 p0 val) ...
+            let next_tag_name = partial_tag_name(tag_name, decoded + 1);
+            let next_payload_var = payload_vars[decoded];
+            let (decode_custom, decode_custom_ret_var) = custom_decoder_updating_state(
+                env,
+                next_payload_var,
+                state_var,
+                decode_err_var,
+                decoded_payloads
+                    .iter()
+                    .map(|&(var, symbol)| (symbol, var))
+                    .collect(),
+                |env, val_symbol| {
+                    // A#2 p0 val
+                    let arguments = (decoded_payloads.iter().copied())
+                        .chain(std::iter::once((next_payload_var, val_symbol)));
+
+                    tag_expr(env, state_var, next_tag_name, arguments)
+                },
+            );
+
+            env.unify(keep_payload_var, decode_custom_ret_var);
+
+            let keep = Expr::Tag {
+                tag_union_var: keep_or_skip_var,
+                ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+                name: "Keep".into(),
+                arguments: vec![(decode_custom_ret_var, Loc::at_zero(decode_custom))],
+            };
+
+            branches.push(when_branch(pattern, keep));
+        }
+    }
+
+    // Example: `_ -> Skip`
+    let skip = tag_expr(env, keep_or_skip_var, "Skip".into(), []);
+    branches.push(when_branch(Loc::at_zero(Pattern::Underscore), skip));
+
+    // when state is
+    let body = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(state_arg_symbol))),
+        cond_var: state_var,
+        expr_var: keep_or_skip_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: state_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    let step_payload_closure = env.new_symbol("stepPayload");
+
+    single_arg_closure(
+        env,
+        step_payload_closure,
+        (state_arg_symbol, state_var),
+        (body, keep_or_skip_var),
+    )
+}
+
+// Example:
+// finalizer = \state ->
+//     when state is
+//         A#2 p0 p1 -> Ok (A p0 p1)
+//         B#0 -> Ok B
+//         _ -> Err TooShort
+fn decoder_tag_union_finalizer(
+    env: &mut Env,
+    tags: &[(TagName, Vec<Variable>)],
+    tag_union_var: Variable,
+    state_var: Variable,
+    decode_err_var: Variable,
+) -> (Expr, Variable) {
+    let state_arg_symbol = env.new_symbol("state");
+    let return_type_var = {
+        let flat_type = FlatType::TagUnion(
+            UnionTags::for_result(env.subs, tag_union_var, decode_err_var),
+            Variable::EMPTY_TAG_UNION,
+        );
+
+        synth_var(env.subs, Content::Structure(flat_type))
+    };
+
+    // +1 because of the default branch.
+    let mut branches = Vec::with_capacity(tags.len() + 1);
+
+    for (tag_name, payload_vars) in tags {
+        let payload_symbols: Vec<_> = std::iter::repeat_with(|| env.new_symbol("payload"))
+            .take(payload_vars.len())
+            .collect();
+        let payloads = || {
+            payload_vars
+                .iter()
+                .copied()
+                .zip(payload_symbols.iter().copied())
+        };
+
+        // A#2 p0 p1 -> Ok (A p0 p1)
+        let pattern = tag_pattern(
+            state_var,
+            partial_tag_name(tag_name, payload_vars.len()),
+            payloads(),
+        );
+        let decoded = tag_expr(env, tag_union_var, tag_name.clone(), payloads());
+        let ok = Expr::Tag {
+            tag_union_var: return_type_var,
+            ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+            name: "Ok".into(),
+            arguments: vec![(tag_union_var, Loc::at_zero(decoded))],
+        };
+
+        branches.push(when_branch(pattern, ok));
+    }
+
+    // _ -> Err TooShort
+    let err = Expr::Tag {
+        tag_union_var: return_type_var,
+        ext_var: env.new_ext_var(ExtensionKind::TagUnion),
+        name: "Err".into(),
+        arguments: vec![(
+            decode_err_var,
+            Loc::at_zero(tag_expr(env, decode_err_var, "TooShort".into(), [])),
+        )],
+    };
+    branches.push(when_branch(Loc::at_zero(Pattern::Underscore), err));
+
+    // when state is
+    let body = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(state_arg_symbol))),
+        cond_var: state_var,
+        expr_var: return_type_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: state_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    let finalizer_closure = env.new_symbol("finalizer");
+
+    single_arg_closure(
+        env,
+        finalizer_closure,
+        (state_arg_symbol, state_var),
+        (body, return_type_var),
+    )
+}
+
 fn decoder_list(env: &mut Env<'_>, _def_symbol: Symbol) -> (Expr, Variable) {
     // Build
     //
@@ -0,0 +1,409 @@
+//! Derivers for the `Hash` ability.
+
+use std::iter::once;
+
+use roc_can::{
+    expr::{AnnotatedMark, ClosureData, Expr, IntValue, Recursive, WhenBranch, WhenBranchPattern},
+    pattern::Pattern,
+};
+use roc_derive_key::hash::FlatHashKey;
+use roc_module::{
+    called_via::CalledVia,
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_region::all::{Loc, Region};
+use roc_types::{
+    num::{IntBound, IntLitWidth},
+    subs::{
+        Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
+        RedundantMark, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
+    },
+    types::RecordField,
+};
+
+use crate::{synth_var, util::Env, DerivedBody};
+
+pub(crate) fn derive_hash(env: &mut Env<'_>, key: FlatHashKey, def_symbol: Symbol) -> DerivedBody {
+    let (body, body_type) = match key {
+        FlatHashKey::Record(fields) => hash_record(env, def_symbol, fields),
+        FlatHashKey::TagUnion(tags) => hash_tag_union(env, def_symbol, tags),
+    };
+
+    let specialization_lambda_sets =
+        env.get_specialization_lambda_sets(body_type, Symbol::HASH_HASH);
+
+    DerivedBody {
+        body,
+        body_type,
+        specialization_lambda_sets,
+    }
+}
+
+fn hash_record(env: &mut Env<'_>, fn_name: Symbol, fields: Vec<Lowercase>) -> (Expr, Variable) {
+    // Suppose rcd = { f1, ..., fn }.
+    // Build a generalized type t_rcd = { f1: t1, ..., fn: tn }, with fresh t1, ..., tn,
+    // so that we can re-use the derived impl for many records of the same fields.
+    let (record_var, record_fields) = {
+        let flex_fields = fields
+            .into_iter()
+            .map(|name| {
+                (
+                    name,
+                    RecordField::Required(env.subs.fresh_unnamed_flex_var()),
+                )
+            })
+            .collect::<Vec<(Lowercase, _)>>();
+        let fields = RecordFields::insert_into_subs(env.subs, flex_fields);
+        let record_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::Record(fields, Variable::EMPTY_RECORD)),
+        );
+
+        (record_var, fields)
+    };
+
+    // Now, a hasher for this record is
+    //
+    // hash_rcd : hasher, { f1: t1, ..., fn: tn } -> hasher | hasher has Hasher
+    // hash_rcd = \hasher, rcd ->
+    //   Hash.hash (
+    //     Hash.hash
+    //       ...
+    //       (Hash.hash hasher rcd.f1)
+    //     ...
+    //     rcd.f_n1)
+    //   rcd.fn
+    //
+    // So, just a build a fold travelling up vars in reverse order.
+
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    let rcd_sym = env.new_symbol("rcd");
+
+    let initial_body = Expr::Var(hasher_sym);
+
+    let body = record_fields.iter_all().fold(
+        initial_body,
+        |body, (field_name_index, field_var_index, _)| {
+            let field_name = env.subs[field_name_index].clone();
+            let field_var = env.subs[field_var_index];
+
+            let field_access = Expr::Access {
+                record_var,
+                field_var,
+                ext_var: env.subs.fresh_unnamed_flex_var(),
+                loc_expr: Box::new(Loc::at_zero(Expr::Var(rcd_sym))),
+                field: field_name,
+            };
+
+            call_hash_hash(env, hasher_var, body, field_var, field_access)
+        },
+    );
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (record_var, Pattern::Identifier(rcd_sym)),
+        body,
+    )
+}
+
+fn hash_tag_union(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    tags: Vec<(TagName, u16)>,
+) -> (Expr, Variable) {
+    // Suppose tags = [ A p11 .. p1n, ..., Q pq1 .. pqm ]
+    // Build a generalized type t_tags = [ A t11 .. t1n, ..., Q tq1 .. tqm ],
+    // with fresh t1, ..., tqm, so that we can re-use the derived impl for many
+    // unions of the same tags and payloads.
+    let (union_var, union_tags) = {
+        let flex_tag_labels = tags
+            .into_iter()
+            .map(|(label, arity)| {
+                let variables_slice = VariableSubsSlice::reserve_into_subs(env.subs, arity.into());
+                for var_index in variables_slice {
+                    env.subs[var_index] = env.subs.fresh_unnamed_flex_var();
+                }
+                (label, variables_slice)
+            })
+            .collect::<Vec<_>>();
+        let union_tags = UnionTags::insert_slices_into_subs(env.subs, flex_tag_labels);
+        let tag_union_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::TagUnion(union_tags, Variable::EMPTY_TAG_UNION)),
+        );
+
+        (tag_union_var, union_tags)
+    };
+
+    // Now, a hasher for this tag union is
+    //
+    // hash_union : hasher, [ A t11 .. t1n, ..., Q tq1 .. tqm ] -> hasher | hasher has Hasher
+    // hash_union = \hasher, union ->
+    //   when union is
+    //     A x11 .. x1n -> Hash.hash (... (Hash.hash (Hash.addU8 hasher 0) x11) ...) x1n
+    //     ...
+    //     Q xq1 .. xqm -> Hash.hash (... (Hash.hash (Hash.addU8 hasher (q - 1)) xq1) ...) xqm
+    //
+    // If there is only one tag, the discriminant is not included, as it carries no
+    // information. Unions of more than 256 tags use `Hash.addU16` for the discriminant.
+    let hasher_sym = env.new_symbol("hasher");
+    let hasher_var = env.subs.fresh_unnamed_flex_var();
+
+    let union_sym = env.new_symbol("union");
+
+    let num_tags = union_tags.len();
+    let add_discriminant = if num_tags > 1 {
+        if num_tags <= u8::MAX as usize + 1 {
+            Some((Symbol::HASH_ADD_U8, Variable::U8, IntLitWidth::U8))
+        } else {
+            Some((Symbol::HASH_ADD_U16, Variable::U16, IntLitWidth::U16))
+        }
+    } else {
+        None
+    };
+
+    let branches = union_tags
+        .iter_all()
+        .enumerate()
+        .map(|(discriminant, (tag_name_index, tag_vars_slice_index))| {
+            // A
+            let tag_name = env.subs[tag_name_index].clone();
+            let vars_slice = env.subs[tag_vars_slice_index];
+            // t11 .. t1n
+            let payload_vars = env.subs.get_subs_slice(vars_slice).to_vec();
+            // x11 .. x1n
+            let payload_syms: Vec<_> = std::iter::repeat_with(|| env.unique_symbol())
+                .take(payload_vars.len())
+                .collect();
+
+            // `A x1 .. x1n` pattern
+            let pattern = Pattern::AppliedTag {
+                whole_var: union_var,
+                tag_name,
+                ext_var: Variable::EMPTY_TAG_UNION,
+                // (t1, v1) (t2, v2)
+                arguments: (payload_vars.iter())
+                    .zip(payload_syms.iter())
+                    .map(|(var, sym)| (*var, Loc::at_zero(Pattern::Identifier(*sym))))
+                    .collect(),
+            };
+            let branch_pattern = WhenBranchPattern {
+                pattern: Loc::at_zero(pattern),
+                degenerate: false,
+            };
+
+            // discrHasher = (Hash.addU8 hasher discriminant)
+            let hasher_with_discriminant = match add_discriminant {
+                Some((add_member, discr_var, width)) => call_hash_add_discriminant(
+                    env,
+                    add_member,
+                    hasher_var,
+                    (discr_var, width),
+                    discriminant,
+                    hasher_sym,
+                ),
+                None => Expr::Var(hasher_sym),
+            };
+
+            // Fold up `Hash.hash (... (Hash.hash discrHasher x11) ...) x1n`
+            let body = (payload_vars.into_iter()).zip(payload_syms).fold(
+                hasher_with_discriminant,
+                |total_hasher, (payload_var, payload_sym)| {
+                    call_hash_hash(
+                        env,
+                        hasher_var,
+                        total_hasher,
+                        payload_var,
+                        Expr::Var(payload_sym),
+                    )
+                },
+            );
+
+            WhenBranch {
+                patterns: vec![branch_pattern],
+                value: Loc::at_zero(body),
+                guard: None,
+                redundant: RedundantMark::known_non_redundant(),
+            }
+        })
+        .collect();
+
+    // when union is
+    //   ...
+    let when_var = env.subs.fresh_unnamed_flex_var();
+    env.unify(when_var, hasher_var);
+
+    let when_expr = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(union_sym))),
+        cond_var: union_var,
+        expr_var: when_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: union_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        (hasher_var, hasher_sym),
+        (union_var, Pattern::Identifier(union_sym)),
+        when_expr,
+    )
+}
+
+/// Build a `hash` implementation for a type, given the hasher argument, the argument of the type
+/// being hashed, and the body of the closure.
+fn build_outer_derived_closure(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    hasher: (Variable, Symbol),
+    val: (Variable, Pattern),
+    body: Expr,
+) -> (Expr, Variable) {
+    let (hasher_var, hasher_sym) = hasher;
+    let (val_var, val_pattern) = val;
+
+    // Create fn_var for ambient capture; we fix it up below.
+    let fn_var = synth_var(env.subs, Content::Error);
+
+    // -[fn_name]->
+    let fn_name_labels = UnionLambdas::insert_into_subs(env.subs, once((fn_name, vec![])));
+    let fn_clos_var = synth_var(
+        env.subs,
+        Content::LambdaSet(LambdaSet {
+            solved: fn_name_labels,
+            recursion_var: OptVariable::NONE,
+            unspecialized: SubsSlice::default(),
+            ambient_function: fn_var,
+        }),
+    );
+
+    // hasher, val -[fn_name]-> hasher
+    let args_slice = VariableSubsSlice::insert_into_subs(env.subs, [hasher_var, val_var]);
+    env.subs.set_content(
+        fn_var,
+        Content::Structure(FlatType::Func(args_slice, fn_clos_var, hasher_var)),
+    );
+
+    let clos_expr = Expr::Closure(ClosureData {
+        function_type: fn_var,
+        closure_type: fn_clos_var,
+        return_type: hasher_var,
+        name: fn_name,
+        captured_symbols: vec![],
+        recursive: Recursive::NotRecursive,
+        arguments: vec![
+            (
+                hasher_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(Pattern::Identifier(hasher_sym)),
+            ),
+            (
+                val_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(val_pattern),
+            ),
+        ],
+        loc_body: Box::new(Loc::at_zero(body)),
+    });
+
+    (clos_expr, fn_var)
+}
+
+/// Build `Hash.hash hasher val`.
+fn call_hash_hash(
+    env: &mut Env<'_>,
+    hasher_var: Variable,
+    hasher_expr: Expr,
+    val_var: Variable,
+    val_expr: Expr,
+) -> Expr {
+    call_hash_ability_member(
+        env,
+        Symbol::HASH_HASH,
+        (hasher_var, hasher_expr),
+        (val_var, val_expr),
+    )
+}
+
+/// Build `Hash.addU8 hasher discriminant` (or `Hash.addU16`, depending on `add_member`).
+fn call_hash_add_discriminant(
+    env: &mut Env<'_>,
+    add_member: Symbol,
+    hasher_var: Variable,
+    (discr_var, width): (Variable, IntLitWidth),
+    discriminant: usize,
+    hasher_sym: Symbol,
+) -> Expr {
+    let discr_precision_var = env.subs.fresh_unnamed_flex_var();
+    let discr_expr = Expr::Int(
+        discr_var,
+        discr_precision_var,
+        format!("{}", discriminant).into_boxed_str(),
+        IntValue::I128((discriminant as i128).to_ne_bytes()),
+        IntBound::Exact(width),
+    );
+
+    call_hash_ability_member(
+        env,
+        add_member,
+        (hasher_var, Expr::Var(hasher_sym)),
+        (discr_var, discr_expr),
+    )
+}
+
+/// Build a call to a `Hash` or `Hasher` ability member of shape `hasher, val -> hasher`.
+fn call_hash_ability_member(
+    env: &mut Env<'_>,
+    member: Symbol,
+    hasher: (Variable, Expr),
+    val: (Variable, Expr),
+) -> Expr {
+    let (in_hasher_var, in_hasher_expr) = hasher;
+    let (in_val_var, in_val_expr) = val;
+
+    // build `member ...` function type. `member` here is `Hash.hash` or a `Hasher` member.
+    //
+    // hasher, val -[uls]-> hasher | hasher has Hasher, val has Hash
+    let exposed_hash_fn_var = env.import_builtin_symbol_var(member);
+
+    // (typeof body), (typeof field) -[clos]-> hasher_result
+    let this_arguments_slice =
+        VariableSubsSlice::insert_into_subs(env.subs, [in_hasher_var, in_val_var]);
+    let this_hash_clos_var = env.subs.fresh_unnamed_flex_var();
+    let this_out_hasher_var = env.subs.fresh_unnamed_flex_var();
+    let this_hash_fn_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Func(
+            this_arguments_slice,
+            this_hash_clos_var,
+            this_out_hasher_var,
+        )),
+    );
+
+    //   hasher,        val          -[uls]->  hasher | hasher has Hasher, val has Hash
+    // ~ (typeof body), (typeof val) -[clos]-> hasher_result
+    env.unify(exposed_hash_fn_var, this_hash_fn_var);
+
+    // member : hasher, (typeof val) -[clos]-> hasher | hasher has Hasher, (typeof val) has Hash
+    let hash_fn_head = Expr::AbilityMember(member, None, this_hash_fn_var);
+    let hash_fn_data = Box::new((
+        this_hash_fn_var,
+        Loc::at_zero(hash_fn_head),
+        this_hash_clos_var,
+        this_out_hasher_var,
+    ));
+
+    let hash_arguments = vec![
+        (in_hasher_var, Loc::at_zero(in_hasher_expr)),
+        (in_val_var, Loc::at_zero(in_val_expr)),
+    ];
+
+    Expr::Call(hash_fn_data, hash_arguments, CalledVia::Space)
+}
@@ -18,6 +18,7 @@ use util::Env;
 
 mod decoding;
 mod encoding;
+mod hash;
 
 mod util;
 
@@ -77,6 +78,7 @@ fn build_derived_body(
         DeriveKey::Decoder(decoder_key) => {
             decoding::derive_decoder(&mut env, decoder_key, derived_symbol)
         }
+        DeriveKey::Hash(hash_key) => hash::derive_hash(&mut env, hash_key, derived_symbol),
     };
 
     let def = Def {
@@ -36,6 +36,7 @@ pub enum RenderAs {
     Tag,
     Opaque,
     Record(Vec<Lowercase>),
+    Tuple,
     Guard,
 }
 
@@ -69,6 +70,45 @@ pub enum Pattern {
     Anything,
     Literal(Literal),
     Ctor(Union, TagId, std::vec::Vec<Pattern>),
+    List(ListArity, std::vec::Vec<Pattern>),
+}
+
+/// The shape of a list pattern, in terms of the lists it can match.
+///
+/// `[a, b]` has arity `Exact(2)`, while `[a, .., b, c]` has arity `Slice(1, 2)`, since it matches
+/// any list with at least one element in the front and two in the back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ListArity {
+    Exact(usize),
+    Slice(usize, usize),
+}
+
+impl ListArity {
+    /// The number of elements a list must have at minimum to be matched by this arity.
+    pub fn min_len(&self) -> usize {
+        match self {
+            ListArity::Exact(n) => *n,
+            ListArity::Slice(before, after) => before + after,
+        }
+    }
+
+    /// Does this arity match lists of the given length?
+    pub fn covers_length(&self, length: usize) -> bool {
+        match self {
+            ListArity::Exact(n) => *n == length,
+            ListArity::Slice(before, after) => before + after <= length,
+        }
+    }
+
+    /// Does this arity match every list that a pattern of arity `other` matches?
+    fn covers_arities_of(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ListArity::Exact(l), ListArity::Exact(r)) => l == r,
+            (ListArity::Exact(_), ListArity::Slice(..)) => false,
+            (ListArity::Slice(lb, la), ListArity::Slice(rb, ra)) => lb <= rb && la <= ra,
+            (ListArity::Slice(lb, la), ListArity::Exact(r)) => lb + la <= *r,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -145,6 +185,22 @@ fn is_exhaustive(matrix: &RefPatternMatrix, n: usize) -> PatternMatrix {
         vec![std::iter::repeat(Anything).take(n).collect()]
     } else if n == 0 {
         vec![]
+    } else if column_has_lists(matrix) {
+        let mut result = Vec::new();
+
+        for spec_arity in list_ctors_covering(collect_list_arities(matrix)) {
+            let new_matrix: Vec<_> = matrix
+                .iter()
+                .filter_map(|row| specialize_row_by_list(spec_arity, row))
+                .collect();
+            let rest: Vec<Vec<Pattern>> = is_exhaustive(&new_matrix, spec_arity.min_len() + n - 1);
+
+            for row in rest {
+                result.push(recover_list(spec_arity, row));
+            }
+        }
+
+        result
     } else {
         let ctors = collect_ctors(matrix);
         let num_seen = ctors.len();
@@ -226,6 +282,15 @@ fn is_missing<T>(union: Union, ctors: &MutMap<TagId, T>, ctor: &Ctor) -> Option<
     }
 }
 
+fn recover_list(arity: ListArity, mut patterns: Vec<Pattern>) -> Vec<Pattern> {
+    let mut rest = patterns.split_off(arity.min_len());
+    let args = patterns;
+
+    rest.push(List(arity, args));
+
+    rest
+}
+
 fn recover_ctor(
     union: Union,
     tag_id: TagId,
@@ -269,6 +334,49 @@ pub fn is_useful(mut old_matrix: PatternMatrix, mut vector: Row) -> bool {
                         vector.extend(args);
                     }
 
+                    List(arity, args) => {
+                        // Check if any list constructor this pattern covers is useful.
+                        let mut arities = collect_list_arities(&old_matrix);
+                        arities.push(arity);
+
+                        for spec_arity in list_ctors_covering(arities) {
+                            let spec_args = match specialize_list_args(arity, &args, spec_arity) {
+                                Some(spec_args) => spec_args,
+                                None => continue,
+                            };
+
+                            let mut spec_matrix = Vec::with_capacity(old_matrix.len());
+                            specialize_matrix_by_list(spec_arity, &old_matrix, &mut spec_matrix);
+
+                            let mut vector = vector.clone();
+                            vector.extend(spec_args);
+
+                            if is_useful(spec_matrix, vector) {
+                                break 'outer true;
+                            }
+                        }
+
+                        break false;
+                    }
+
+                    Anything if column_has_lists(&old_matrix) => {
+                        // This Anything is useful if any list constructor isn't yet covered
+                        // by the lists in the matrix.
+                        for spec_arity in list_ctors_covering(collect_list_arities(&old_matrix)) {
+                            let mut spec_matrix = Vec::with_capacity(old_matrix.len());
+                            specialize_matrix_by_list(spec_arity, &old_matrix, &mut spec_matrix);
+
+                            let mut vector = vector.clone();
+                            vector.extend(std::iter::repeat(Anything).take(spec_arity.min_len()));
+
+                            if is_useful(spec_matrix, vector) {
+                                break 'outer true;
+                            }
+                        }
+
+                        break false;
+                    }
+
                     Anything => {
                         // check if all alternatives appear in matrix
                         match is_complete(&old_matrix) {
@@ -334,6 +442,10 @@ pub fn is_useful(mut old_matrix: PatternMatrix, mut vector: Row) -> bool {
                                     r#"Compiler bug! After type checking, constructors and literals should never align in pattern match exhaustiveness checks."#
                                 ),
 
+                                Some(List(_, _)) => panic!(
+                                    r#"Compiler bug! After type checking, lists and literals should never align in pattern match exhaustiveness checks."#
+                                ),
+
                                 None => panic!(
                                     "Compiler error! Empty matrices should not get specialized."
                                 ),
@@ -372,6 +484,7 @@ fn specialize_row_by_ctor2(
             matrix.push(patterns);
             }
         Some(Literal(_)) => panic!( "Compiler bug! After type checking, constructors and literal should never align in pattern match exhaustiveness checks."),
+        Some(List(_, _)) => panic!( "Compiler bug! After type checking, constructors and lists should never align in pattern match exhaustiveness checks."),
         None => panic!("Compiler error! Empty matrices should not get specialized."),
     }
     }
@@ -407,6 +520,93 @@ fn specialize_row_by_ctor(tag_id: TagId, arity: usize, row: &RefRow) -> Option<R
         Some(Literal(_)) => unreachable!(
             r#"Compiler bug! After type checking, a constructor can never align with a literal: that should be a type error!"#
         ),
+        Some(List(_, _)) => unreachable!(
+            r#"Compiler bug! After type checking, a constructor can never align with a list: that should be a type error!"#
+        ),
+        None => panic!("Compiler error! Empty matrices should not get specialized."),
+    }
+}
+
+/// The patterns a list pattern of the given arity contributes when specialized by the list
+/// constructor `spec_arity`, or `None` if the list pattern does not match that constructor.
+///
+/// INVARIANT: (result is Some) ==> (length result == min_len spec_arity)
+fn specialize_list_args(
+    arity: ListArity,
+    args: &[Pattern],
+    spec_arity: ListArity,
+) -> Option<Vec<Pattern>> {
+    if !arity.covers_arities_of(&spec_arity) {
+        return None;
+    }
+
+    match arity {
+        ListArity::Exact(_) => Some(args.to_vec()),
+        ListArity::Slice(before, _) => {
+            // A slice `[before.., .., after..]` covering a longer constructor gets wildcards
+            // in its middle, so that its patterns line up with the constructor's elements.
+            let (before, after) = args.split_at(before);
+            let num_middle = spec_arity.min_len() - arity.min_len();
+
+            let patterns = (before.iter().cloned())
+                .chain(std::iter::repeat(Anything).take(num_middle))
+                .chain(after.iter().cloned())
+                .collect();
+
+            Some(patterns)
+        }
+    }
+}
+
+/// INVARIANT: (length row == N) ==> (length result == min_len spec_arity + N - 1)
+fn specialize_matrix_by_list(
+    spec_arity: ListArity,
+    old_matrix: &RefPatternMatrix,
+    matrix: &mut PatternMatrix,
+) {
+    for row in old_matrix {
+        let mut patterns = row.clone();
+
+        match patterns.pop() {
+            Some(List(arity, args)) => {
+                if let Some(spec_args) = specialize_list_args(arity, &args, spec_arity) {
+                    patterns.extend(spec_args);
+                    matrix.push(patterns);
+                }
+            }
+            Some(Anything) => {
+                patterns.extend(std::iter::repeat(Anything).take(spec_arity.min_len()));
+                matrix.push(patterns);
+            }
+            Some(Ctor(..) | Literal(_)) => panic!("Compiler bug! After type checking, lists should never align with constructors or literals in pattern match exhaustiveness checks."),
+            None => panic!("Compiler error! Empty matrices should not get specialized."),
+        }
+    }
+}
+
+/// INVARIANT: (length row == N) ==> (length result == min_len spec_arity + N - 1)
+fn specialize_row_by_list(spec_arity: ListArity, row: &RefRow) -> Option<Row> {
+    let mut row = row.to_vec();
+
+    let head = row.pop();
+    let patterns = row;
+
+    match head {
+        Some(List(arity, args)) => {
+            let mut new_patterns = specialize_list_args(arity, &args, spec_arity)?;
+            new_patterns.extend(patterns);
+            Some(new_patterns)
+        }
+        Some(Anything) => {
+            let new_patterns = std::iter::repeat(Anything)
+                .take(spec_arity.min_len())
+                .chain(patterns)
+                .collect();
+            Some(new_patterns)
+        }
+        Some(Ctor(..) | Literal(_)) => unreachable!(
+            r#"Compiler bug! After type checking, a list can never align with a constructor or literal: that should be a type error!"#
+        ),
         None => panic!("Compiler error! Empty matrices should not get specialized."),
     }
 }
@@ -463,3 +663,55 @@ fn collect_ctors(matrix: &RefPatternMatrix) -> MutMap<TagId, Union> {
 
     ctors
 }
+
+/// COLLECT LIST ARITIES
+
+fn column_has_lists(matrix: &RefPatternMatrix) -> bool {
+    matrix
+        .iter()
+        .any(|row| matches!(row.last(), Some(List(..))))
+}
+
+fn collect_list_arities(matrix: &RefPatternMatrix) -> Vec<ListArity> {
+    matrix
+        .iter()
+        .filter_map(|row| match row.last() {
+            Some(List(arity, _)) => Some(*arity),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Builds the list constructors that, taken together, match lists of every length, and are
+/// each matched either entirely or not at all by the list patterns of the given arities.
+///
+/// These are an exact constructor for every length up to some bound `l`, and a single slice
+/// constructor for all lists of length `l` and more.
+fn list_ctors_covering(arities: impl IntoIterator<Item = ListArity>) -> Vec<ListArity> {
+    let mut max_exact_len = 0;
+    let mut max_before_len = 0;
+    let mut max_after_len = 0;
+
+    for arity in arities {
+        match arity {
+            ListArity::Exact(n) => max_exact_len = max_exact_len.max(n),
+            ListArity::Slice(before, after) => {
+                max_before_len = max_before_len.max(before);
+                max_after_len = max_after_len.max(after);
+            }
+        }
+    }
+
+    let (before, after) = if max_exact_len >= max_before_len + max_after_len {
+        // The slice constructor must only cover lists longer than any exact pattern. For example,
+        // `[_, _, _]` and `[.., _]` need `[_, _, _, ..]` to cover lists of length 4 and more.
+        (max_exact_len + 1 - max_after_len, max_after_len)
+    } else {
+        (max_before_len, max_after_len)
+    };
+
+    (0..before + after)
+        .map(ListArity::Exact)
+        .chain(std::iter::once(ListArity::Slice(before, after)))
+        .collect()
+}
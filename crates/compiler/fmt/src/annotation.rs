@@ -185,6 +185,15 @@ impl<'a> Formattable for TypeAnnotation<'a> {
                 fields.items.iter().any(|field| field.value.is_multiline())
             }
 
+            Tuple { elems, ext } => {
+                match ext {
+                    Some(ann) if ann.value.is_multiline() => return true,
+                    _ => {}
+                }
+
+                elems.iter().any(|elem| elem.value.is_multiline())
+            }
+
             TagUnion { tags, ext } => {
                 match ext {
                     Some(ann) if ann.value.is_multiline() => return true,
@@ -307,6 +316,14 @@ impl<'a> Formattable for TypeAnnotation<'a> {
                 }
             }
 
+            Tuple { elems, ext } => {
+                fmt_collection(buf, indent, Braces::Round, *elems, newlines);
+
+                if let Some(loc_ext_ann) = *ext {
+                    loc_ext_ann.value.format(buf, indent);
+                }
+            }
+
             As(lhs, _spaces, TypeHeader { name, vars }) => {
                 // TODO use _spaces?
                 lhs.value
@@ -454,6 +471,7 @@ fn format_assigned_field_help<'a, 'buf, T>(
 
             buf.spaces(separator_spaces);
             buf.push('?');
+            buf.spaces(1);
             ann.value.format(buf, indent);
         }
         LabelOnly(name) => {
@@ -6,7 +6,7 @@ use crate::Buf;
 use roc_parse::ast::{Collection, Module, Spaced};
 use roc_parse::header::{
     AppHeader, ExposedName, HostedHeader, ImportsEntry, InterfaceHeader, ModuleName, PackageEntry,
-    PackageName, PlatformHeader, PlatformRequires, To, TypedIdent,
+    PackageHeader, PackageName, PlatformHeader, PlatformRequires, To, TypedIdent,
 };
 use roc_parse::ident::UppercaseIdent;
 use roc_region::all::Loc;
@@ -19,6 +19,9 @@ pub fn fmt_module<'a>(buf: &mut Buf<'_>, module: &'a Module<'a>) {
         Module::App { header } => {
             fmt_app_header(buf, header);
         }
+        Module::Package { header } => {
+            fmt_package_header(buf, header);
+        }
         Module::Platform { header } => {
             fmt_platform_header(buf, header);
         }
@@ -127,6 +130,37 @@ pub fn fmt_app_header<'a, 'buf>(buf: &mut Buf<'buf>, header: &'a AppHeader<'a>)
     fmt_to(buf, header.to.value, indent);
 }
 
+pub fn fmt_package_header<'a, 'buf>(buf: &mut Buf<'buf>, header: &'a PackageHeader<'a>) {
+    let indent = INDENT;
+
+    buf.indent(0);
+    buf.push_str("package");
+
+    fmt_default_spaces(buf, header.after_package_keyword, indent);
+    fmt_package_name(buf, header.name.value, indent);
+
+    // exposes
+    fmt_default_spaces(buf, header.before_exposes, indent);
+    buf.indent(indent);
+    buf.push_str("exposes");
+    fmt_default_spaces(buf, header.after_exposes, indent);
+    fmt_exposes(buf, header.exposes, indent);
+
+    // packages
+    fmt_default_spaces(buf, header.before_packages, indent);
+    buf.indent(indent);
+    buf.push_str("packages");
+    fmt_default_spaces(buf, header.after_packages, indent);
+    fmt_packages(buf, header.packages, indent);
+
+    // imports
+    fmt_default_spaces(buf, header.before_imports, indent);
+    buf.indent(indent);
+    buf.push_str("imports");
+    fmt_default_spaces(buf, header.after_imports, indent);
+    fmt_imports(buf, header.imports, indent);
+}
+
 pub fn fmt_platform_header<'a, 'buf>(buf: &mut Buf<'buf>, header: &'a PlatformHeader<'a>) {
     let indent = INDENT;
 
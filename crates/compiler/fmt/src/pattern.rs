@@ -28,6 +28,10 @@ impl<'a> Formattable for Pattern<'a> {
 
             Pattern::OptionalField(_, expr) => expr.is_multiline(),
 
+            Pattern::List(patterns) | Pattern::Tuple(patterns) => {
+                patterns.iter().any(|p| p.is_multiline())
+            }
+
             Pattern::Identifier(_)
             | Pattern::Tag(_)
             | Pattern::OpaqueRef(_)
@@ -38,6 +42,7 @@ impl<'a> Formattable for Pattern<'a> {
             | Pattern::StrLiteral(_)
             | Pattern::SingleQuote(_)
             | Pattern::Underscore(_)
+            | Pattern::ListRest(_)
             | Pattern::Malformed(_)
             | Pattern::MalformedIdent(_, _)
             | Pattern::QualifiedIdentifier { .. } => false,
@@ -159,6 +164,48 @@ impl<'a> Formattable for Pattern<'a> {
                 buf.push_str(name);
             }
 
+            List(loc_patterns) => {
+                buf.indent(indent);
+                buf.push_str("[");
+
+                let mut it = loc_patterns.iter().peekable();
+                while let Some(loc_pattern) = it.next() {
+                    loc_pattern.format(buf, indent);
+
+                    if it.peek().is_some() {
+                        buf.push_str(",");
+                        buf.spaces(1);
+                    }
+                }
+
+                buf.push_str("]");
+            }
+
+            Tuple(loc_patterns) => {
+                buf.indent(indent);
+                buf.push_str("(");
+
+                let mut it = loc_patterns.iter().peekable();
+                while let Some(loc_pattern) = it.next() {
+                    loc_pattern.format(buf, indent);
+
+                    if it.peek().is_some() {
+                        buf.push_str(",");
+                        buf.spaces(1);
+                    }
+                }
+
+                buf.push_str(")");
+            }
+
+            ListRest(opt_name) => {
+                buf.indent(indent);
+                buf.push_str("..");
+                if let Some(name) = opt_name {
+                    buf.push_str(name);
+                }
+            }
+
             // Space
             SpaceBefore(sub_pattern, spaces) => {
                 if !sub_pattern.is_multiline() {
@@ -9,7 +9,7 @@ use roc_parse::{
     },
     header::{
         AppHeader, ExposedName, HostedHeader, ImportsEntry, InterfaceHeader, ModuleName,
-        PackageEntry, PackageName, PlatformHeader, PlatformRequires, To, TypedIdent,
+        PackageEntry, PackageHeader, PackageName, PlatformHeader, PlatformRequires, To, TypedIdent,
     },
     ident::UppercaseIdent,
 };
@@ -278,6 +278,22 @@ impl<'a> RemoveSpaces<'a> for Module<'a> {
                     after_to: &[],
                 },
             },
+            Module::Package { header } => Module::Package {
+                header: PackageHeader {
+                    name: header.name.remove_spaces(arena),
+                    exposes: header.exposes.remove_spaces(arena),
+                    packages: header.packages.remove_spaces(arena),
+                    imports: header.imports.remove_spaces(arena),
+                    before_header: &[],
+                    after_package_keyword: &[],
+                    before_exposes: &[],
+                    after_exposes: &[],
+                    before_packages: &[],
+                    after_packages: &[],
+                    before_imports: &[],
+                    after_imports: &[],
+                },
+            },
             Module::Platform { header } => Module::Platform {
                 header: PlatformHeader {
                     name: header.name.remove_spaces(arena),
@@ -642,7 +658,9 @@ impl<'a> RemoveSpaces<'a> for Expr<'a> {
             Expr::Str(a) => Expr::Str(a.remove_spaces(arena)),
             Expr::Access(a, b) => Expr::Access(arena.alloc(a.remove_spaces(arena)), b),
             Expr::AccessorFunction(a) => Expr::AccessorFunction(a),
+            Expr::TupleAccess(a, b) => Expr::TupleAccess(arena.alloc(a.remove_spaces(arena)), b),
             Expr::List(a) => Expr::List(a.remove_spaces(arena)),
+            Expr::Tuple(a) => Expr::Tuple(a.remove_spaces(arena)),
             Expr::RecordUpdate { update, fields } => Expr::RecordUpdate {
                 update: arena.alloc(update.remove_spaces(arena)),
                 fields: fields.remove_spaces(arena),
@@ -682,6 +700,10 @@ impl<'a> RemoveSpaces<'a> for Expr<'a> {
                 arena.alloc(a.remove_spaces(arena)),
                 arena.alloc(b.remove_spaces(arena)),
             ),
+            Expr::Dbg(a, b) => Expr::Dbg(
+                arena.alloc(a.remove_spaces(arena)),
+                arena.alloc(b.remove_spaces(arena)),
+            ),
             Expr::Apply(a, b, c) => Expr::Apply(
                 arena.alloc(a.remove_spaces(arena)),
                 b.remove_spaces(arena),
@@ -728,6 +750,9 @@ impl<'a> RemoveSpaces<'a> for Pattern<'a> {
             Pattern::OptionalField(a, b) => {
                 Pattern::OptionalField(a, arena.alloc(b.remove_spaces(arena)))
             }
+            Pattern::List(a) => Pattern::List(a.remove_spaces(arena)),
+            Pattern::Tuple(a) => Pattern::Tuple(a.remove_spaces(arena)),
+            Pattern::ListRest(a) => Pattern::ListRest(a),
             Pattern::NumLiteral(a) => Pattern::NumLiteral(a),
             Pattern::NonBase10Literal {
                 string,
@@ -774,6 +799,10 @@ impl<'a> RemoveSpaces<'a> for TypeAnnotation<'a> {
                 fields: fields.remove_spaces(arena),
                 ext: ext.remove_spaces(arena),
             },
+            TypeAnnotation::Tuple { elems, ext } => TypeAnnotation::Tuple {
+                elems: elems.remove_spaces(arena),
+                ext: ext.remove_spaces(arena),
+            },
             TypeAnnotation::TagUnion { ext, tags } => TypeAnnotation::TagUnion {
                 ext: ext.remove_spaces(arena),
                 tags: tags.remove_spaces(arena),
@@ -3911,6 +3911,40 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn when_with_list_patterns() {
+        expr_formats_same(indoc!(
+            r#"
+            when list is
+                [] ->
+                    Empty
+
+                [first, .., last] ->
+                    Ends first last
+
+                [[A], ..rest] ->
+                    Nested rest
+            "#
+        ));
+
+        expr_formats_to(
+            indoc!(
+                r#"
+                when list is
+                    [ a,..,  b ] -> a
+                    [ .. ] -> 0
+                "#
+            ),
+            indoc!(
+                r#"
+                when list is
+                    [a, .., b] -> a
+                    [..] -> 0
+                "#
+            ),
+        );
+    }
+
     // ACCESSOR
 
     #[test]
@@ -4649,6 +4683,17 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn package_header() {
+        module_formats_same(indoc!(
+            r#"
+                package "acme/shout"
+                    exposes [Shout, Shout.Loudly]
+                    packages { json: "json/main.roc" }
+                    imports []"#
+        ));
+    }
+
     #[test]
     fn single_line_app() {
         module_formats_same(indoc!(
@@ -4996,6 +5041,18 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn record_type_with_optional_field() {
+        expr_formats_same(indoc!(
+            r#"
+            f : { foo ? Int * } -> Int *
+            f = \{ foo ? 1000 } -> foo
+
+            a
+            "#
+        ));
+    }
+
     #[test]
     fn record_pattern_with_apply_guard() {
         expr_formats_same(indoc!(
@@ -5018,6 +5075,69 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn tuple_type() {
+        expr_formats_same(indoc!(
+            r#"
+            f : (Str, U64) -> (U64, Str)
+            f = \(s, n) -> (n, s)
+
+            f
+            "#
+        ));
+    }
+
+    #[test]
+    fn tuple_type_with_ext() {
+        expr_formats_same(indoc!(
+            r#"
+            f : (Str, U64)a -> Str
+            f = \t -> t.0
+
+            f
+            "#
+        ));
+    }
+
+    #[test]
+    fn tuple_spaces() {
+        expr_formats_to(
+            indoc!(
+                r#"
+                ( 1,2 ,  "three" ).1
+                "#
+            ),
+            indoc!(
+                r#"
+                (1, 2, "three").1
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn multiline_tuple() {
+        expr_formats_same(indoc!(
+            r#"
+            (
+                1,
+                "two",
+            )
+            "#
+        ));
+    }
+
+    #[test]
+    fn tuple_pattern() {
+        expr_formats_same(indoc!(
+            r#"
+            when pair is
+                (Ok a, _) -> a
+                (_, b) -> b
+            "#
+        ));
+    }
+
     #[test]
     fn body_starts_with_spaces_multiline() {
         expr_formats_same(indoc!(
@@ -5524,6 +5644,50 @@ mod test_fmt {
         ));
     }
 
+    #[test]
+    fn dbg_single_line() {
+        expr_formats_same(indoc!(
+            r#"
+            x = 5
+
+            dbg x + 1
+
+            42
+            "#
+        ));
+
+        expr_formats_to(
+            indoc!(
+                r#"
+                dbg    x
+                42
+                "#
+            ),
+            indoc!(
+                r#"
+                dbg x
+
+                42
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    fn dbg_multiline() {
+        expr_formats_same(indoc!(
+            r#"
+            x = 5
+
+            dbg
+                foo bar
+                |> baz
+
+            42
+            "#
+        ));
+    }
+
     #[test]
     fn single_line_string_literal_in_pattern() {
         expr_formats_same(indoc!(
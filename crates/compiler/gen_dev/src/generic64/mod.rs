@@ -6,12 +6,13 @@ use bumpalo::collections::Vec;
 use roc_builtins::bitcode::{self, FloatWidth, IntWidth};
 use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
-use roc_module::symbol::{Interns, Symbol};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::CodeGenHelp;
 use roc_mono::ir::{
     BranchInfo, JoinPointId, ListLiteralElement, Literal, Param, ProcLayout, SelfRecursive, Stmt,
 };
 use roc_mono::layout::{Builtin, Layout, TagIdIntType, UnionLayout};
+use roc_region::all::Region;
 use roc_target::TargetInfo;
 use std::marker::PhantomData;
 
@@ -776,6 +777,42 @@ impl<
         }
     }
 
+    fn build_expect(
+        &mut self,
+        condition: &Symbol,
+        region: Region,
+        lookups: &[Symbol],
+        layouts: &[Layout<'a>],
+    ) {
+        // Everything is freed to the stack so that the storage is the same whether or not the frame is written.
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        let cond_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, condition);
+
+        // Skip writing the frame if the condition holds.
+        // Since we don't know the offset yet, set it to 0 and overwrite later.
+        let jne_location = self.buf.len();
+        let start_offset = ASM::jne_reg64_imm64_imm32(&mut self.buf, cond_reg, 0, 0);
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+
+        self.write_expect_frame(condition.module_id(), region, lookups, layouts);
+
+        // Overwrite the original jne with the correct offset.
+        let mut tmp = bumpalo::vec![in self.env.arena];
+        let end_offset = self.buf.len();
+        let jne_offset = end_offset - start_offset;
+        ASM::jne_reg64_imm64_imm32(&mut tmp, cond_reg, 0, jne_offset as i32);
+        for (i, byte) in tmp.iter().enumerate() {
+            self.buf[jne_location + i] = *byte;
+        }
+    }
+
+    fn build_dbg(&mut self, symbol: &Symbol, region: Region, layout: &Layout<'a>) {
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+        self.write_expect_frame(symbol.module_id(), region, &[*symbol], &[*layout]);
+    }
+
     fn build_num_abs(&mut self, dst: &Symbol, src: &Symbol, layout: &Layout<'a>) {
         match layout {
             Layout::Builtin(Builtin::Int(IntWidth::I64 | IntWidth::U64)) => {
@@ -1708,6 +1745,200 @@ impl<
             self.buf[jmp_location as usize + i] = *byte;
         }
     }
+
+    /// Writes a frame with the region and the looked up values to the shared memory of the test runner.
+    /// All symbols must be on the stack.
+    fn write_expect_frame(
+        &mut self,
+        module_id: ModuleId,
+        region: Region,
+        lookups: &[Symbol],
+        layouts: &[Layout<'a>],
+    ) {
+        let target_info = self.storage_manager.target_info();
+        let u64_layout = Layout::Builtin(Builtin::Int(IntWidth::U64));
+        let str_layout = Layout::Builtin(Builtin::Str);
+
+        // A frame starts with the region, the module id, the end of the frame and the offsets of the values.
+        let values_start = 20 + 8 * lookups.len() as i32;
+
+        // The values are written one after another, with the string bytes after all of them.
+        let mut positions = bumpalo::vec![in self.env.arena];
+        let mut strings = bumpalo::vec![in self.env.arena];
+        let mut values_size = 0;
+        for (index, layout) in layouts.iter().enumerate() {
+            positions.push(values_size);
+            self.expect_frame_strings(&mut strings, index, 0, layout);
+            let size = layout.stack_size(self.env.layout_interner, target_info) as i32;
+            values_size += (size + 7) & !7;
+        }
+
+        self.build_fn_call(
+            &Symbol::DEV_TMP,
+            bitcode::UTILS_EXPECT_FAILED_START.to_string(),
+            &[],
+            &[],
+            &u64_layout,
+        );
+
+        let module_id: u32 = unsafe { std::mem::transmute(module_id) };
+        let region_bits = region.start().offset as u64 | (region.end().offset as u64) << 32;
+        let ptr_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &Symbol::DEV_TMP);
+        self.storage_manager.with_tmp_general_reg(
+            &mut self.buf,
+            |storage_manager, buf, frame_reg| {
+                storage_manager.with_tmp_general_reg(buf, |storage_manager, buf, tmp_reg| {
+                    // The offset of the frame is stored right after the frame count.
+                    ASM::mov_reg64_mem64_offset32(buf, frame_reg, ptr_reg, 8);
+                    ASM::add_reg64_reg64_reg64(buf, frame_reg, frame_reg, ptr_reg);
+
+                    // The end of the frame overwrites the upper half of the module id later.
+                    ASM::mov_reg64_imm64(buf, tmp_reg, region_bits as i64);
+                    ASM::mov_mem64_offset32_reg64(buf, frame_reg, 0, tmp_reg);
+                    ASM::mov_reg64_imm64(buf, tmp_reg, module_id as i64);
+                    ASM::mov_mem64_offset32_reg64(buf, frame_reg, 8, tmp_reg);
+
+                    for (i, (lookup, layout)) in lookups.iter().zip(layouts).enumerate() {
+                        let value_start = values_start + positions[i];
+                        ASM::mov_reg64_mem64_offset32(buf, tmp_reg, ptr_reg, 8);
+                        ASM::add_reg64_reg64_imm32(buf, tmp_reg, tmp_reg, value_start);
+                        ASM::mov_mem64_offset32_reg64(buf, frame_reg, 20 + 8 * i as i32, tmp_reg);
+
+                        if layout.stack_size(storage_manager.env.layout_interner, target_info) == 0
+                        {
+                            continue;
+                        }
+                        let (base_offset, size) = storage_manager.stack_offset_and_size(lookup);
+                        for chunk in (0..size as i32).step_by(8) {
+                            ASM::mov_reg64_base32(buf, tmp_reg, base_offset + chunk);
+                            ASM::mov_mem64_offset32_reg64(
+                                buf,
+                                frame_reg,
+                                value_start + chunk,
+                                tmp_reg,
+                            );
+                        }
+                    }
+                });
+            },
+        );
+
+        // Copy the bytes of every string to the end of the frame.
+        let ptr_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &Symbol::DEV_TMP);
+        let extra_reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &Symbol::DEV_TMP3);
+        ASM::mov_reg64_mem64_offset32(&mut self.buf, extra_reg, ptr_reg, 8);
+        ASM::add_reg64_reg64_imm32(
+            &mut self.buf,
+            extra_reg,
+            extra_reg,
+            values_start + values_size,
+        );
+        for (index, offset) in strings {
+            let str_size = str_layout.stack_size(self.env.layout_interner, target_info);
+            self.storage_manager.load_reference_at_offset(
+                &Symbol::DEV_TMP4,
+                &lookups[index],
+                offset,
+                str_size,
+            );
+
+            let ptr_reg = self
+                .storage_manager
+                .load_to_general_reg(&mut self.buf, &Symbol::DEV_TMP);
+            let position_reg = self
+                .storage_manager
+                .claim_general_reg(&mut self.buf, &Symbol::DEV_TMP2);
+            ASM::mov_reg64_mem64_offset32(&mut self.buf, position_reg, ptr_reg, 8);
+            ASM::add_reg64_reg64_imm32(
+                &mut self.buf,
+                position_reg,
+                position_reg,
+                values_start + positions[index] + offset,
+            );
+
+            self.build_fn_call(
+                &Symbol::DEV_TMP5,
+                bitcode::STR_CLONE_TO.to_string(),
+                &[
+                    Symbol::DEV_TMP4,
+                    Symbol::DEV_TMP,
+                    Symbol::DEV_TMP2,
+                    Symbol::DEV_TMP3,
+                ],
+                &[str_layout, u64_layout, u64_layout, u64_layout],
+                &u64_layout,
+            );
+            self.free_symbol(&Symbol::DEV_TMP2);
+            self.free_symbol(&Symbol::DEV_TMP3);
+            self.free_symbol(&Symbol::DEV_TMP4);
+
+            // The returned offset is where the next string's bytes go.
+            let src_reg = self
+                .storage_manager
+                .load_to_general_reg(&mut self.buf, &Symbol::DEV_TMP5);
+            let extra_reg = self
+                .storage_manager
+                .claim_general_reg(&mut self.buf, &Symbol::DEV_TMP3);
+            ASM::mov_reg64_reg64(&mut self.buf, extra_reg, src_reg);
+            self.free_symbol(&Symbol::DEV_TMP5);
+        }
+
+        // Store the end of the frame and bump the frame count.
+        let ptr_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &Symbol::DEV_TMP);
+        let extra_reg = self
+            .storage_manager
+            .load_to_general_reg(&mut self.buf, &Symbol::DEV_TMP3);
+        self.storage_manager
+            .with_tmp_general_reg(&mut self.buf, |_, buf, tmp_reg| {
+                ASM::mov_reg64_mem64_offset32(buf, tmp_reg, ptr_reg, 8);
+                ASM::add_reg64_reg64_reg64(buf, tmp_reg, tmp_reg, ptr_reg);
+                ASM::mov_mem64_offset32_reg64(buf, tmp_reg, 12, extra_reg);
+
+                ASM::mov_reg64_mem64_offset32(buf, tmp_reg, ptr_reg, 0);
+                ASM::add_reg64_reg64_imm32(buf, tmp_reg, tmp_reg, 1);
+                ASM::mov_mem64_offset32_reg64(buf, ptr_reg, 0, tmp_reg);
+                ASM::mov_mem64_offset32_reg64(buf, ptr_reg, 8, extra_reg);
+            });
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP3);
+        self.storage_manager.free_all_to_stack(&mut self.buf);
+    }
+
+    /// Finds the offsets of all strings in a value that is looked up by an expect.
+    fn expect_frame_strings(
+        &self,
+        strings: &mut Vec<'a, (usize, i32)>,
+        index: usize,
+        offset: i32,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            Layout::Builtin(Builtin::Str) => strings.push((index, offset)),
+            Layout::Struct { field_layouts, .. } => {
+                let mut field_offset = offset;
+                for field_layout in field_layouts.iter() {
+                    self.expect_frame_strings(strings, index, field_offset, field_layout);
+                    field_offset += field_layout
+                        .stack_size(self.env.layout_interner, self.storage_manager.target_info())
+                        as i32;
+                }
+            }
+            Layout::LambdaSet(_) => {}
+            x if x.safe_to_memcpy(self.env.layout_interner) => {}
+            x => internal_error!(
+                "looking up values with layout, {:?}, in an expect should have been ruled out by `expects_supported`",
+                x
+            ),
+        }
+    }
 }
 
 #[macro_export]
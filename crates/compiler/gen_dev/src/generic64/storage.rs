@@ -576,6 +576,39 @@ impl<
         }
     }
 
+    /// Loads a reference to the complex data `offset` bytes into the stack data of `structure`.
+    /// This is like `load_field_at_index`, but the data can be nested arbitrarily deep.
+    pub fn load_reference_at_offset(
+        &mut self,
+        sym: &Symbol,
+        structure: &Symbol,
+        offset: i32,
+        size: u32,
+    ) {
+        // This must be removed and reinserted for ownership and mutability reasons.
+        let owned_data = self.remove_allocation_for_sym(structure);
+        self.allocation_map
+            .insert(*structure, Rc::clone(&owned_data));
+        match self.get_storage_for_sym(structure) {
+            Stack(Complex {
+                base_offset,
+                size: structure_size,
+            }) => {
+                debug_assert!(offset as u32 + size <= *structure_size);
+                let base_offset = *base_offset + offset;
+                self.allocation_map.insert(*sym, owned_data);
+                self.symbol_storage_map
+                    .insert(*sym, Stack(Complex { base_offset, size }));
+            }
+            storage => {
+                internal_error!(
+                    "Cannot load reference from data with storage type: {:?}",
+                    storage
+                );
+            }
+        }
+    }
+
     pub fn load_union_tag_id(
         &mut self,
         _buf: &mut Vec<'a, u8>,
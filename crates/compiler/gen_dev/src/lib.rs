@@ -6,10 +6,9 @@ use bumpalo::{collections::Vec, Bump};
 use roc_builtins::bitcode::{self, FloatWidth, IntWidth};
 use roc_collections::all::{MutMap, MutSet};
 use roc_error_macros::internal_error;
-use roc_module::ident::ModuleName;
 use roc_module::low_level::{LowLevel, LowLevelWrapperType};
-use roc_module::symbol::{Interns, ModuleId, Symbol};
-use roc_mono::code_gen_help::CodeGenHelp;
+use roc_module::symbol::{IdentIds, Interns, ModuleId, Symbol};
+use roc_mono::code_gen_help::{is_rc_implemented_yet, CodeGenHelp};
 use roc_mono::ir::{
     BranchInfo, CallType, Expr, JoinPointId, ListLiteralElement, Literal, Param, Proc, ProcLayout,
     SelfRecursive, Stmt,
@@ -17,6 +16,8 @@ use roc_mono::ir::{
 use roc_mono::layout::{
     Builtin, Layout, LayoutId, LayoutIds, STLayoutInterner, TagIdIntType, UnionLayout,
 };
+use roc_region::all::Region;
+use roc_target::TargetInfo;
 
 mod generic64;
 mod object_builder;
@@ -30,6 +31,7 @@ pub struct Env<'a> {
     pub exposed_to_host: MutSet<Symbol>,
     pub lazy_literals: bool,
     pub generate_allocators: bool,
+    pub runs_expects: bool,
 }
 
 // These relocations likely will need a length.
@@ -58,6 +60,410 @@ pub enum Relocation {
     },
 }
 
+/// Whether the dev backend can write out every value that the `expect`s and `dbg`s in these
+/// procedures look up. When it can't, they have to be built with another backend.
+pub fn expects_supported<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    procedures: &MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+) -> bool {
+    procedures.values().all(|proc| {
+        let mut layouts = proc
+            .args
+            .iter()
+            .map(|(layout, symbol)| (*symbol, *layout))
+            .collect();
+
+        stmt_expects_supported(layout_interner, &mut layouts, &proc.body)
+    })
+}
+
+fn stmt_expects_supported<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    layouts: &mut MutMap<Symbol, Layout<'a>>,
+    stmt: &Stmt<'a>,
+) -> bool {
+    match stmt {
+        Stmt::Let(symbol, _, layout, remainder) => {
+            layouts.insert(*symbol, *layout);
+            stmt_expects_supported(layout_interner, layouts, remainder)
+        }
+        Stmt::Switch {
+            branches,
+            default_branch,
+            ..
+        } => {
+            branches
+                .iter()
+                .all(|(_, _, branch)| stmt_expects_supported(layout_interner, layouts, branch))
+                && stmt_expects_supported(layout_interner, layouts, default_branch.1)
+        }
+        Stmt::Refcounting(_, remainder) => {
+            stmt_expects_supported(layout_interner, layouts, remainder)
+        }
+        Stmt::Expect {
+            layouts: lookup_layouts,
+            remainder,
+            ..
+        }
+        | Stmt::ExpectFx {
+            layouts: lookup_layouts,
+            remainder,
+            ..
+        } => {
+            lookup_layouts
+                .iter()
+                .all(|layout| expect_layout_supported(layout_interner, layout))
+                && stmt_expects_supported(layout_interner, layouts, remainder)
+        }
+        Stmt::Dbg {
+            symbol, remainder, ..
+        } => {
+            layouts.get(symbol).map_or(false, |layout| {
+                expect_layout_supported(layout_interner, layout)
+            }) && stmt_expects_supported(layout_interner, layouts, remainder)
+        }
+        Stmt::Join {
+            parameters,
+            body,
+            remainder,
+            ..
+        } => {
+            for param in parameters.iter() {
+                layouts.insert(param.symbol, param.layout);
+            }
+
+            stmt_expects_supported(layout_interner, layouts, body)
+                && stmt_expects_supported(layout_interner, layouts, remainder)
+        }
+        Stmt::Ret(_) | Stmt::Jump(_, _) | Stmt::RuntimeError(_) => true,
+    }
+}
+
+/// Values are copied into an expect frame as they are, except for strings, whose bytes are
+/// written out after the values. Anything else that points into the heap can't be looked up yet.
+fn expect_layout_supported<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    layout: &Layout<'a>,
+) -> bool {
+    match layout {
+        Layout::Builtin(Builtin::Str) | Layout::LambdaSet(_) => true,
+        Layout::Struct { field_layouts, .. } => field_layouts
+            .iter()
+            .all(|field_layout| expect_layout_supported(layout_interner, field_layout)),
+        _ => layout.safe_to_memcpy(layout_interner),
+    }
+}
+
+/// Whether the dev backend can generate code for all of these procedures. It still panics on
+/// whatever it doesn't support yet, so anything that can fall back to another backend should
+/// check this first.
+pub fn procedures_supported<'a>(
+    env: &Env<'a>,
+    interns: &Interns,
+    procedures: &MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+) -> bool {
+    // The backend generates refcounting helper procs for the `Refcounting` statements it builds,
+    // and then builds those helpers too. Generate them the same way here, to check them as well.
+    let mut helpers = Helpers {
+        ident_ids: interns
+            .all_ident_ids
+            .get(&env.module_id)
+            .cloned()
+            .unwrap_or_default(),
+        proc_gen: CodeGenHelp::new(
+            env.arena,
+            env.layout_interner,
+            TargetInfo::default_x86_64(),
+            env.module_id,
+        ),
+    };
+
+    procedures
+        .values()
+        .all(|proc| proc_supported(env.layout_interner, &mut helpers, proc))
+        && helpers
+            .proc_gen
+            .take_procs()
+            .iter()
+            .all(|proc| proc_supported(env.layout_interner, &mut helpers, proc))
+}
+
+struct Helpers<'a> {
+    ident_ids: IdentIds,
+    proc_gen: CodeGenHelp<'a>,
+}
+
+fn proc_supported<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    helpers: &mut Helpers<'a>,
+    proc: &Proc<'a>,
+) -> bool {
+    let mut layouts = proc
+        .args
+        .iter()
+        .map(|(layout, symbol)| (*symbol, *layout))
+        .collect();
+
+    proc.args
+        .iter()
+        .all(|(layout, _)| arg_layout_supported(layout_interner, layout))
+        && stmt_supported(layout_interner, helpers, &mut layouts, &proc.body)
+}
+
+fn stmt_supported<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    helpers: &mut Helpers<'a>,
+    layouts: &mut MutMap<Symbol, Layout<'a>>,
+    stmt: &Stmt<'a>,
+) -> bool {
+    match stmt {
+        Stmt::Let(symbol, expr, layout, remainder) => {
+            layouts.insert(*symbol, *layout);
+
+            expr_supported(layout_interner, layouts, expr, layout)
+                && stmt_supported(layout_interner, helpers, layouts, remainder)
+        }
+        Stmt::Switch {
+            branches,
+            default_branch,
+            ..
+        } => {
+            branches
+                .iter()
+                .all(|(_, _, branch)| stmt_supported(layout_interner, helpers, layouts, branch))
+                && stmt_supported(layout_interner, helpers, layouts, default_branch.1)
+        }
+        Stmt::Refcounting(modify, remainder) => {
+            let layout = match layouts.get(&modify.get_symbol()) {
+                Some(layout) if is_rc_implemented_yet(layout_interner, layout) => *layout,
+                _ => return false,
+            };
+
+            // This is a call to the helper proc, followed by the remainder
+            let (rc_stmt, _) = helpers.proc_gen.expand_refcount_stmt(
+                &mut helpers.ident_ids,
+                layout,
+                modify,
+                *remainder,
+            );
+
+            stmt_supported(layout_interner, helpers, layouts, rc_stmt)
+        }
+        Stmt::Expect { remainder, .. }
+        | Stmt::ExpectFx { remainder, .. }
+        | Stmt::Dbg { remainder, .. } => {
+            stmt_supported(layout_interner, helpers, layouts, remainder)
+        }
+        Stmt::Join {
+            parameters,
+            body,
+            remainder,
+            ..
+        } => {
+            for param in parameters.iter() {
+                layouts.insert(param.symbol, param.layout);
+            }
+
+            stmt_supported(layout_interner, helpers, layouts, body)
+                && stmt_supported(layout_interner, helpers, layouts, remainder)
+        }
+        Stmt::Ret(_) | Stmt::Jump(_, _) => true,
+        Stmt::RuntimeError(_) => false,
+    }
+}
+
+fn expr_supported<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    layouts: &MutMap<Symbol, Layout<'a>>,
+    expr: &Expr<'a>,
+    layout: &Layout<'a>,
+) -> bool {
+    match expr {
+        Expr::Literal(literal) => literal_supported(literal, layout),
+        Expr::Call(roc_mono::ir::Call {
+            call_type,
+            arguments,
+        }) => match call_type {
+            CallType::ByName {
+                name,
+                arg_layouts,
+                ret_layout,
+                ..
+            } => {
+                let args_supported = arg_layouts
+                    .iter()
+                    .all(|layout| arg_layout_supported(layout_interner, layout));
+
+                let call_supported = match LowLevelWrapperType::from_symbol(name.name()) {
+                    LowLevelWrapperType::CanBeReplacedBy(lowlevel) => {
+                        low_level_supported(lowlevel, arg_layouts, ret_layout)
+                    }
+                    LowLevelWrapperType::NotALowLevelWrapper if !name.name().is_builtin() => true,
+                    LowLevelWrapperType::NotALowLevelWrapper => matches!(
+                        name.name(),
+                        Symbol::NUM_IS_ZERO
+                            | Symbol::LIST_GET
+                            | Symbol::LIST_SET
+                            | Symbol::LIST_REPLACE
+                            | Symbol::NUM_ADD_CHECKED
+                            | Symbol::BOOL_TRUE
+                            | Symbol::BOOL_FALSE
+                    ),
+                };
+
+                args_supported && call_supported
+            }
+            CallType::LowLevel { op, .. } => {
+                let arg_layouts: std::vec::Vec<_> = arguments
+                    .iter()
+                    .filter_map(|argument| layouts.get(argument).copied())
+                    .collect();
+
+                arg_layouts.len() == arguments.len()
+                    && low_level_supported(*op, &arg_layouts, layout)
+            }
+            CallType::Foreign { .. } | CallType::HigherOrder(_) => false,
+        },
+        Expr::EmptyArray => true,
+        Expr::Array { elem_layout, elems } => {
+            copy_to_list_supported(layout_interner, elem_layout)
+                && elems.iter().all(|elem| match elem {
+                    ListLiteralElement::Literal(literal) => literal_supported(literal, elem_layout),
+                    ListLiteralElement::Symbol(_) => true,
+                })
+        }
+        Expr::Struct(fields) => fields.iter().all(|field| {
+            layouts.get(field).map_or(false, |field_layout| {
+                copy_to_stack_supported(layout_interner, field_layout)
+            })
+        }),
+        Expr::StructAtIndex { .. } => true,
+        Expr::UnionAtIndex { union_layout, .. } => matches!(
+            union_layout,
+            UnionLayout::NonRecursive(_) | UnionLayout::Recursive(_)
+        ),
+        Expr::GetTagId { union_layout, .. } => {
+            matches!(union_layout, UnionLayout::NonRecursive(_))
+        }
+        Expr::Tag { tag_layout, .. } => match tag_layout {
+            UnionLayout::NonRecursive(tags) => {
+                let (_, data_alignment) = tag_layout
+                    .data_size_and_alignment(layout_interner, TargetInfo::default_x86_64());
+
+                data_alignment >= 8
+                    && data_alignment % 8 == 0
+                    && tags.iter().all(|fields| {
+                        fields
+                            .iter()
+                            .all(|field| copy_to_stack_supported(layout_interner, field))
+                    })
+            }
+            _ => false,
+        },
+        Expr::ExprBox { .. }
+        | Expr::ExprUnbox { .. }
+        | Expr::Reuse { .. }
+        | Expr::Reset { .. }
+        | Expr::RuntimeErrorFunction(_) => false,
+    }
+}
+
+fn literal_supported(literal: &Literal, layout: &Layout) -> bool {
+    match (literal, layout) {
+        (Literal::Int(_), Layout::Builtin(Builtin::Int(width))) => {
+            !matches!(width, IntWidth::I128 | IntWidth::U128)
+        }
+        (Literal::Bool(_), Layout::Builtin(Builtin::Bool)) => true,
+        (Literal::Float(_), Layout::Builtin(Builtin::Float(_))) => true,
+        // only small strings, which don't need an allocation
+        (Literal::Str(string), Layout::Builtin(Builtin::Str)) => string.len() < 24,
+        _ => false,
+    }
+}
+
+/// Values get passed to and from functions in registers, or on the stack when they are big.
+fn arg_layout_supported<'a>(layout_interner: &STLayoutInterner<'a>, layout: &Layout<'a>) -> bool {
+    let size = layout.stack_size(layout_interner, TargetInfo::default_x86_64());
+
+    match layout {
+        Layout::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128)) => false,
+        Layout::Builtin(Builtin::Int(_) | Builtin::Float(_) | Builtin::Bool)
+        | Layout::RecursivePointer => true,
+        _ => size == 0 || size > 16,
+    }
+}
+
+fn copy_to_stack_supported<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    layout: &Layout<'a>,
+) -> bool {
+    let size = layout.stack_size(layout_interner, TargetInfo::default_x86_64());
+
+    match layout {
+        Layout::Builtin(Builtin::Int(IntWidth::I64 | IntWidth::U64))
+        | Layout::Builtin(Builtin::Float(FloatWidth::F64)) => true,
+        _ => size == 0 || size > 8,
+    }
+}
+
+fn copy_to_list_supported<'a>(layout_interner: &STLayoutInterner<'a>, layout: &Layout<'a>) -> bool {
+    let size = layout.stack_size(layout_interner, TargetInfo::default_x86_64());
+
+    match layout {
+        Layout::Builtin(Builtin::Int(IntWidth::I64 | IntWidth::U64)) => true,
+        _ => size == 0 || size > 8,
+    }
+}
+
+fn low_level_supported(lowlevel: LowLevel, arg_layouts: &[Layout], ret_layout: &Layout) -> bool {
+    use IntWidth::*;
+
+    let arg_layout = match arg_layouts.first() {
+        Some(arg_layout) => arg_layout,
+        None => return false,
+    };
+
+    let int = |widths: &[IntWidth]| matches!(arg_layout, Layout::Builtin(Builtin::Int(width)) if widths.contains(width));
+    let float = |widths: &[FloatWidth]| matches!(arg_layout, Layout::Builtin(Builtin::Float(width)) if widths.contains(width));
+    let word_sized = [I8, I16, I32, I64, U8, U16, U32, U64];
+
+    match lowlevel {
+        LowLevel::NumAbs => int(&[I64, U64]) || float(&[FloatWidth::F64]),
+        LowLevel::NumAdd
+        | LowLevel::NumMul
+        | LowLevel::NumDivTruncUnchecked
+        | LowLevel::NumDivFrac => int(&word_sized) || float(&[FloatWidth::F32, FloatWidth::F64]),
+        LowLevel::NumAddChecked => int(&[I8, I16, I32, I64]),
+        LowLevel::NumNeg
+        | LowLevel::NumSub
+        | LowLevel::NumSubWrap
+        | LowLevel::NotEq
+        | LowLevel::NumLt => int(&[I64, U64]),
+        LowLevel::Eq | LowLevel::NumLte | LowLevel::NumGte => int(&word_sized),
+        LowLevel::NumToFrac => {
+            (int(&[I32, I64]) || float(&[FloatWidth::F32, FloatWidth::F64]))
+                && matches!(ret_layout, Layout::Builtin(Builtin::Float(_)))
+        }
+        LowLevel::NumBitwiseAnd | LowLevel::NumBitwiseOr | LowLevel::NumBitwiseXor => {
+            matches!(ret_layout, Layout::Builtin(Builtin::Int(width)) if word_sized.contains(width))
+        }
+        LowLevel::NumAcos
+        | LowLevel::NumAsin
+        | LowLevel::NumAtan
+        | LowLevel::NumPowInt
+        | LowLevel::NumRound
+        | LowLevel::ListLen
+        | LowLevel::ListGetUnsafe
+        | LowLevel::ListReplaceUnsafe
+        | LowLevel::StrConcat
+        | LowLevel::Hash
+        | LowLevel::PtrCast
+        | LowLevel::RefCountDec
+        | LowLevel::RefCountInc => true,
+        _ => false,
+    }
+}
+
 trait Backend<'a> {
     fn env(&self) -> &Env<'a>;
     fn interns(&self) -> &Interns;
@@ -71,12 +477,6 @@ trait Backend<'a> {
         layout_id.to_symbol_string(symbol, self.interns())
     }
 
-    fn defined_in_app_module(&self, symbol: Symbol) -> bool {
-        symbol
-            .module_string(self.interns())
-            .starts_with(ModuleName::APP)
-    }
-
     fn helper_proc_gen_mut(&mut self) -> &mut CodeGenHelp<'a>;
 
     fn helper_proc_symbols_mut(&mut self) -> &mut Vec<'a, (Symbol, ProcLayout<'a>)>;
@@ -209,6 +609,41 @@ trait Backend<'a> {
                 self.build_jump(id, args, arg_layouts.into_bump_slice(), ret_layout);
                 self.free_symbols(stmt);
             }
+            Stmt::Expect {
+                condition,
+                region,
+                lookups,
+                layouts,
+                remainder,
+            }
+            | Stmt::ExpectFx {
+                condition,
+                region,
+                lookups,
+                layouts,
+                remainder,
+            } => {
+                if self.env().runs_expects {
+                    self.load_literal_symbols(&[*condition]);
+                    self.load_literal_symbols(lookups);
+                    self.build_expect(condition, *region, lookups, layouts);
+                }
+                self.free_symbols(stmt);
+                self.build_stmt(remainder, ret_layout);
+            }
+            Stmt::Dbg {
+                symbol,
+                region,
+                remainder,
+            } => {
+                if self.env().runs_expects {
+                    self.load_literal_symbols(&[*symbol]);
+                    let layout = *self.layout_map().get(symbol).unwrap();
+                    self.build_dbg(symbol, *region, &layout);
+                }
+                self.free_symbols(stmt);
+                self.build_stmt(remainder, ret_layout);
+            }
             x => todo!("the statement, {:?}", x),
         }
     }
@@ -241,6 +676,19 @@ trait Backend<'a> {
         ret_layout: &Layout<'a>,
     );
 
+    /// build_expect writes the looked up values to the shared memory of the test runner
+    /// when the condition is false.
+    fn build_expect(
+        &mut self,
+        condition: &Symbol,
+        region: Region,
+        lookups: &[Symbol],
+        layouts: &[Layout<'a>],
+    );
+
+    /// build_dbg writes the value of the symbol to the shared memory of the test runner.
+    fn build_dbg(&mut self, symbol: &Symbol, region: Region, layout: &Layout<'a>);
+
     /// build_expr builds the expressions for the specified symbol.
     /// The builder must keep track of the symbol because it may be referred to later.
     fn build_expr(&mut self, sym: &Symbol, expr: &Expr<'a>, layout: &Layout<'a>) {
@@ -273,7 +721,7 @@ trait Backend<'a> {
                                 arg_layouts,
                                 ret_layout,
                             )
-                        } else if self.defined_in_app_module(func_sym.name()) {
+                        } else if !func_sym.name().is_builtin() {
                             let layout_id = LayoutIds::default().get(func_sym.name(), layout);
                             let fn_name = self.symbol_to_string(func_sym.name(), layout_id);
                             // Now that the arguments are needed, load them if they are literals.
@@ -483,7 +931,7 @@ trait Backend<'a> {
                 arg_layouts,
                 ret_layout,
             ),
-            LowLevel::NumSub => {
+            LowLevel::NumSub | LowLevel::NumSubWrap => {
                 debug_assert_eq!(
                     2,
                     args.len(),
@@ -655,6 +1103,13 @@ trait Backend<'a> {
                 arg_layouts,
                 ret_layout,
             ),
+            LowLevel::Hash => self.build_fn_call(
+                sym,
+                bitcode::DICT_HASH_BYTES.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
             LowLevel::PtrCast => {
                 debug_assert_eq!(
                     1,
@@ -1104,8 +1559,31 @@ trait Backend<'a> {
                 }
             }
 
-            Stmt::Expect { .. } => todo!("expect is not implemented in the dev backend"),
-            Stmt::ExpectFx { .. } => todo!("expect-fx is not implemented in the dev backend"),
+            Stmt::Expect {
+                condition,
+                lookups,
+                remainder,
+                ..
+            }
+            | Stmt::ExpectFx {
+                condition,
+                lookups,
+                remainder,
+                ..
+            } => {
+                self.set_last_seen(*condition, stmt);
+                for sym in *lookups {
+                    self.set_last_seen(*sym, stmt);
+                }
+                self.scan_ast(remainder);
+            }
+
+            Stmt::Dbg {
+                symbol, remainder, ..
+            } => {
+                self.set_last_seen(*symbol, stmt);
+                self.scan_ast(remainder);
+            }
 
             Stmt::RuntimeError(_) => {}
         }
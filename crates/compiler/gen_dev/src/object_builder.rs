@@ -329,7 +329,9 @@ fn build_proc_symbol<'a, B: Backend<'a>>(
     let layout_id = layout_ids.get_toplevel(sym, &layout);
     let base_name = backend.symbol_to_string(sym, layout_id);
 
-    let fn_name = if backend.env().exposed_to_host.contains(&sym) {
+    // The test runner looks expects up by their full name, since their generated idents can be
+    // the same in different modules.
+    let fn_name = if backend.env().exposed_to_host.contains(&sym) && !backend.env().runs_expects {
         layout_ids
             .get_toplevel(sym, &layout)
             .to_exposed_symbol_string(sym, backend.interns())
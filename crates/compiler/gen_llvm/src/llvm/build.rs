@@ -2917,6 +2917,40 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             )
         }
 
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            if env.mode.runs_expects() {
+                match env.target_info.ptr_width() {
+                    roc_target::PtrWidth::Bytes8 => {
+                        // the test runner renders the value once the expect has finished
+                        clone_to_shared_memory(
+                            env,
+                            scope,
+                            layout_ids,
+                            *symbol,
+                            *region,
+                            &[*symbol],
+                        );
+                    }
+                    roc_target::PtrWidth::Bytes4 => {
+                        // there is no shared memory to write to on 32-bit targets
+                    }
+                }
+            }
+
+            build_exp_stmt(
+                env,
+                layout_ids,
+                func_spec_solutions,
+                scope,
+                parent,
+                remainder,
+            )
+        }
+
         RuntimeError(error_msg) => {
             throw_exception(env, error_msg);
 
@@ -6568,7 +6602,16 @@ fn run_low_level<'a, 'ctx, 'env>(
             BasicValueEnum::IntValue(bool_val)
         }
         Hash => {
-            unimplemented!()
+            debug_assert_eq!(args.len(), 2);
+            let list = load_symbol(scope, &args[0]).into_struct_value();
+            let seed = load_symbol(scope, &args[1]);
+            call_list_bitcode_fn(
+                env,
+                &[list],
+                &[seed],
+                BitcodeReturns::Basic,
+                bitcode::DICT_HASH_BYTES,
+            )
         }
 
         ListMap | ListMap2 | ListMap3 | ListMap4 | ListSortWith => {
@@ -714,6 +714,9 @@ impl<'a> WasmBackend<'a> {
             Stmt::Expect { .. } => todo!("expect is not implemented in the wasm backend"),
             Stmt::ExpectFx { .. } => todo!("expect-fx is not implemented in the wasm backend"),
 
+            // There is no test runner to print the value to, so `dbg` is a no-op here
+            Stmt::Dbg { remainder, .. } => self.stmt(remainder),
+
             Stmt::RuntimeError(msg) => self.stmt_runtime_error(msg),
         }
     }
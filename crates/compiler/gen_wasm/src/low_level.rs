@@ -1852,7 +1852,7 @@ impl<'a> LowLevelCall<'a> {
                 backend.storage.load_symbols(code_builder, self.arguments);
             }
 
-            Hash => todo!("{:?}", self.lowlevel),
+            Hash => self.load_args_and_call_zig(backend, bitcode::DICT_HASH_BYTES),
 
             Eq | NotEq => self.eq_or_neq(backend),
 
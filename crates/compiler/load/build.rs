@@ -23,6 +23,7 @@ const MODULES: &[(ModuleId, &str)] = &[
     (ModuleId::ENCODE, "Encode.roc"),
     (ModuleId::DECODE, "Decode.roc"),
     (ModuleId::JSON, "Json.roc"),
+    (ModuleId::HASH, "Hash.roc"),
 ];
 
 fn main() {
@@ -97,5 +98,9 @@ fn write_subs_for_module_real(module_id: ModuleId, filename: &str, output_path:
     let exposed_vars_by_symbol: Vec<_> = module.exposed_to_host.into_iter().collect();
 
     let mut file = std::fs::File::create(&output_path).unwrap();
-    subs.serialize(&exposed_vars_by_symbol, &mut file).unwrap();
+    let written = subs.serialize(&exposed_vars_by_symbol, &mut file).unwrap();
+    module
+        .abilities_store
+        .serialize(&mut file, written)
+        .unwrap();
 }
@@ -1,10 +1,12 @@
 use bumpalo::Bump;
+use roc_can::abilities::AbilitiesStore;
 use roc_can::module::ExposedByModule;
 use roc_collections::all::MutMap;
-use roc_module::symbol::{ModuleId, Symbol};
+use roc_load_internal::file::CachedTypeState;
+use roc_module::symbol::ModuleId;
 use roc_reporting::report::RenderTarget;
 use roc_target::TargetInfo;
-use roc_types::subs::{Subs, Variable};
+use roc_types::subs::Subs;
 use std::path::PathBuf;
 
 const SKIP_SUBS_CACHE: bool = {
@@ -166,13 +168,14 @@ const SET: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Set.dat")) as &[_];
 const BOX: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Box.dat")) as &[_];
 const NUM: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Num.dat")) as &[_];
 
-fn deserialize_help(bytes: &[u8]) -> (Subs, Vec<(Symbol, Variable)>) {
-    let (subs, slice) = Subs::deserialize(bytes);
+fn deserialize_help(bytes: &[u8]) -> CachedTypeState {
+    let (subs, slice, offset) = Subs::deserialize(bytes);
+    let (abilities_store, _) = AbilitiesStore::deserialize(bytes, offset);
 
-    (subs, slice.to_vec())
+    (subs, slice.to_vec(), abilities_store)
 }
 
-fn read_cached_subs() -> MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)> {
+fn read_cached_subs() -> MutMap<ModuleId, CachedTypeState> {
     let mut output = MutMap::default();
 
     // Wasm seems to re-order definitions between build time and runtime, but only in release mode.
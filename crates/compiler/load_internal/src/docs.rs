@@ -2,11 +2,14 @@ use crate::docs::DocEntry::DetachedDoc;
 use crate::docs::TypeAnnotation::{Apply, BoundVariable, Function, NoTypeAnn, Record, TagUnion};
 use crate::file::LoadedModule;
 use roc_can::scope::Scope;
+use roc_collections::VecSet;
 use roc_module::ident::ModuleName;
-use roc_module::symbol::IdentIds;
+use roc_module::symbol::{IdentIds, Interns, Symbol};
 use roc_parse::ast::AssignedField;
 use roc_parse::ast::{self, ExtractSpaces, TypeHeader};
 use roc_parse::ast::{CommentOrNewline, TypeDef, ValueDef};
+use roc_region::all::Region;
+use std::ops::Range;
 
 // Documentation generation requirements
 
@@ -23,6 +26,17 @@ pub struct ModuleDocumentation {
     pub name: String,
     pub entries: Vec<DocEntry>,
     pub scope: Scope,
+    /// The values, types and abilities in the module header's `exposes`
+    pub exposed_symbols: VecSet<Symbol>,
+}
+
+impl ModuleDocumentation {
+    pub fn exposed_names<'a>(&self, interns: &'a Interns) -> Vec<&'a str> {
+        self.exposed_symbols
+            .iter()
+            .map(|symbol| symbol.as_str(interns))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +51,7 @@ pub struct DocDef {
     pub type_vars: Vec<String>,
     pub type_annotation: TypeAnnotation,
     pub docs: Option<String>,
+    pub region: Region,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +103,7 @@ pub struct AbilityMember {
     pub type_annotation: TypeAnnotation,
     pub able_variables: Vec<(String, TypeAnnotation)>,
     pub docs: Option<String>,
+    pub region: Region,
 }
 
 #[derive(Debug, Clone)]
@@ -99,6 +115,7 @@ pub struct Tag {
 pub fn generate_module_docs(
     scope: Scope,
     module_name: ModuleName,
+    exposed_symbols: VecSet<Symbol>,
     parsed_defs: &roc_parse::ast::Defs,
 ) -> ModuleDocumentation {
     let entries = generate_entry_docs(&scope.locals.ident_ids, parsed_defs);
@@ -107,6 +124,7 @@ pub fn generate_module_docs(
         name: module_name.as_str().to_string(),
         scope,
         entries,
+        exposed_symbols,
     }
 }
 
@@ -160,6 +178,7 @@ fn generate_entry_docs<'a>(
         scratchpad.extend(spaces_before);
 
         let docs = comments_or_new_lines_to_docs(&scratchpad);
+        let region = defs.regions[index];
 
         match either_index.split() {
             Err(value_index) => match &defs.value_defs[value_index.index()] {
@@ -173,6 +192,7 @@ fn generate_entry_docs<'a>(
                                 type_annotation: type_to_docs(false, loc_ann.value),
                                 type_vars: Vec::new(),
                                 docs,
+                                region,
                             };
                             acc.push(DocEntry::DocDef(doc_def));
                         }
@@ -192,6 +212,7 @@ fn generate_entry_docs<'a>(
                                 type_annotation: type_to_docs(false, ann_type.value),
                                 type_vars: Vec::new(),
                                 docs,
+                                region,
                             };
                             acc.push(DocEntry::DocDef(doc_def));
                         }
@@ -226,6 +247,7 @@ fn generate_entry_docs<'a>(
                         type_annotation: type_to_docs(false, ann.value),
                         type_vars,
                         docs,
+                        region,
                     };
                     acc.push(DocEntry::DocDef(doc_def));
                 }
@@ -247,6 +269,7 @@ fn generate_entry_docs<'a>(
                         type_annotation: TypeAnnotation::NoTypeAnn,
                         type_vars,
                         docs,
+                        region,
                     };
                     acc.push(DocEntry::DocDef(doc_def));
                 }
@@ -276,6 +299,7 @@ fn generate_entry_docs<'a>(
                                 type_annotation,
                                 able_variables,
                                 docs: comments_or_new_lines_to_docs(extracted.before),
+                                region: Region::span_across(&mem.name.region, &mem.typ.region),
                             }
                         })
                         .collect();
@@ -285,6 +309,7 @@ fn generate_entry_docs<'a>(
                         type_annotation: TypeAnnotation::Ability { members },
                         type_vars,
                         docs,
+                        region,
                     };
                     acc.push(DocEntry::DocDef(doc_def));
                 }
@@ -474,3 +499,88 @@ fn comments_or_new_lines_to_docs<'a>(
         Some(docs)
     }
 }
+
+/// Where a line of a doc comment is, relative to the fenced code blocks in it
+enum Fence {
+    Outside,
+    /// In a Roc example, with the byte ranges of its lines so far
+    Roc(Vec<(usize, usize)>),
+    /// In a code block of some other language
+    Other,
+}
+
+/// Returns `src` with everything but the code examples in its doc comments blanked out,
+/// along with where each example is, or None if none of its examples have any `expect`s
+/// in them.
+///
+/// Blanking keeps every byte where it was, so whatever gets parsed from the examples
+/// has the same regions as it does in the module's own source. The byte before each line
+/// of an example, which is part of its `##`, becomes a newline so the example's defs
+/// start at column 0 like top-level defs have to.
+pub fn doc_examples_source(src: &str) -> Option<(String, Vec<Range<usize>>)> {
+    let mut examples = src
+        .bytes()
+        .map(|byte| match byte {
+            b'\n' | b'\r' => byte,
+            _ => b' ',
+        })
+        .collect::<Vec<u8>>();
+
+    let mut ranges = Vec::new();
+    let mut fence = Fence::Outside;
+    let mut offset = 0;
+
+    for line in src.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let doc = match line.trim_start().strip_prefix("##") {
+            Some(doc) if !doc.starts_with('#') => doc.strip_prefix(' ').unwrap_or(doc),
+            _ => {
+                // Examples left open at the end of their doc comment don't get run.
+                fence = Fence::Outside;
+                continue;
+            }
+        };
+
+        let doc_start = line_start + (line.len() - doc.len());
+
+        fence = match (fence, doc.trim_start().strip_prefix("```")) {
+            (Fence::Outside, Some(info)) => match info.trim() {
+                // Untagged code blocks get rendered as Roc too.
+                "" | "roc" => Fence::Roc(Vec::new()),
+                _ => Fence::Other,
+            },
+            (Fence::Roc(lines), Some(_)) => {
+                let has_expect = lines
+                    .iter()
+                    .any(|&(start, end)| src[start..end].trim_start().starts_with("expect"));
+
+                if has_expect {
+                    for &(start, end) in lines.iter() {
+                        examples[start - 1] = b'\n';
+                        examples[start..end].copy_from_slice(&src.as_bytes()[start..end]);
+                    }
+
+                    ranges.push(lines[0].0..lines[lines.len() - 1].1);
+                }
+
+                Fence::Outside
+            }
+            (Fence::Roc(mut lines), None) => {
+                lines.push((doc_start, doc_start + doc.trim_end().len()));
+
+                Fence::Roc(lines)
+            }
+            (Fence::Other, Some(_)) => Fence::Outside,
+            (fence, None) => fence,
+        };
+    }
+
+    if !ranges.is_empty() {
+        // Only whole lines of the original were copied over the spaces, so this is still UTF-8.
+        Some((String::from_utf8(examples).unwrap(), ranges))
+    } else {
+        None
+    }
+}
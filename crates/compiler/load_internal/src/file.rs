@@ -8,6 +8,7 @@ use roc_builtins::roc::module_source;
 use roc_can::abilities::{AbilitiesStore, PendingAbilitiesStore, ResolvedImpl};
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints};
 use roc_can::expr::Declarations;
+use roc_can::expr::ExpectsAndDbgs;
 use roc_can::expr::PendingDerives;
 use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
@@ -37,12 +38,12 @@ use roc_mono::ir::{
 use roc_mono::layout::{
     CapturesNiche, LambdaName, Layout, LayoutCache, LayoutProblem, STLayoutInterner,
 };
-use roc_parse::ast::{self, Defs, ExtractSpaces, Spaced, StrLiteral, TypeAnnotation};
+use roc_parse::ast::{self, Defs, ExtractSpaces, Spaced, StrLiteral, TypeAnnotation, ValueDef};
 use roc_parse::header::{ExposedName, ImportsEntry, PackageEntry, PlatformHeader, To, TypedIdent};
 use roc_parse::header::{HeaderFor, ModuleNameEnum, PackageName};
 use roc_parse::ident::UppercaseIdent;
 use roc_parse::module::module_defs;
-use roc_parse::parser::{FileError, Parser, SyntaxError};
+use roc_parse::parser::{FileError, Parser, SourceError, SyntaxError};
 use roc_region::all::{LineInfo, Loc, Region};
 use roc_reporting::report::RenderTarget;
 use roc_solve::module::{extract_module_owned_implementations, Solved, SolvedModule};
@@ -132,6 +133,11 @@ const MODULE_DECODE_TYPES: &[(&str, Symbol)] = &[
     ("DecoderFormatting", Symbol::DECODE_DECODERFORMATTING),
 ];
 
+const MODULE_HASH_TYPES: &[(&str, Symbol)] = &[
+    ("Hash", Symbol::HASH_HASH_ABILITY),
+    ("Hasher", Symbol::HASH_HASHER),
+];
+
 macro_rules! log {
     ($($arg:tt)*) => (dbg_do!(ROC_PRINT_LOAD_LOG, println!($($arg)*)))
 }
@@ -147,7 +153,12 @@ pub struct LoadConfig {
 #[derive(Debug, Clone, Copy)]
 pub enum ExecutionMode {
     Test,
+    /// Like [`ExecutionMode::Test`], but also runs the `expect`s in the code examples of doc comments.
+    TestWithDocs,
     Check,
+    /// Like [`ExecutionMode::Check`], but keeps the declarations of every module that was loaded,
+    /// not just the root module's. Editor tooling uses them to find where imports are defined.
+    CheckWithDeclarations,
     Executable,
     /// Like [`ExecutionMode::Executable`], but stops in the presence of type errors.
     ExecutableIfCheck,
@@ -156,8 +167,12 @@ pub enum ExecutionMode {
 impl ExecutionMode {
     fn goal_phase(&self) -> Phase {
         match self {
-            ExecutionMode::Test | ExecutionMode::Executable => Phase::MakeSpecializations,
-            ExecutionMode::Check | ExecutionMode::ExecutableIfCheck => Phase::SolveTypes,
+            ExecutionMode::Test | ExecutionMode::TestWithDocs | ExecutionMode::Executable => {
+                Phase::MakeSpecializations
+            }
+            ExecutionMode::Check
+            | ExecutionMode::CheckWithDeclarations
+            | ExecutionMode::ExecutableIfCheck => Phase::SolveTypes,
         }
     }
 }
@@ -230,6 +245,7 @@ impl Default for ModuleCache<'_> {
             ENCODE,
             DECODE,
             JSON,
+            HASH,
         }
 
         Self {
@@ -307,8 +323,12 @@ fn start_phase<'a>(
             Phase::Parse => {
                 // parse the file
                 let header = state.module_cache.headers.remove(&module_id).unwrap();
+                let doc_examples = matches!(state.exec_mode, ExecutionMode::TestWithDocs);
 
-                BuildTask::Parse { header }
+                BuildTask::Parse {
+                    header,
+                    doc_examples,
+                }
             }
             Phase::CanonicalizeAndConstrain => {
                 // canonicalize the file
@@ -480,8 +500,10 @@ fn start_phase<'a>(
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
-                let build_expects = matches!(state.exec_mode, ExecutionMode::Test)
-                    && state.module_cache.expectations.contains_key(&module_id);
+                let build_expects = matches!(
+                    state.exec_mode,
+                    ExecutionMode::Test | ExecutionMode::TestWithDocs
+                ) && state.module_cache.expectations.contains_key(&module_id);
 
                 BuildTask::BuildPendingSpecializations {
                     layout_cache,
@@ -544,24 +566,8 @@ fn start_phase<'a>(
                             procs_base,
                             layout_cache,
                             module_timing,
-                            abilities_store,
                         } = found_specializations;
 
-                        let our_exposed_types = state
-                            .exposed_types
-                            .get(&module_id)
-                            .unwrap_or_else(|| {
-                                internal_error!("Exposed types for {:?} missing", module_id)
-                            })
-                            .clone();
-
-                        // Add our abilities to the world.
-                        state.world_abilities.insert(
-                            module_id,
-                            abilities_store,
-                            our_exposed_types.exposed_types_storage_subs,
-                        );
-
                         (ident_ids, subs, procs_base, layout_cache, module_timing)
                     } else {
                         let LateSpecializationsModule {
@@ -717,7 +723,6 @@ struct FoundSpecializationsModule<'a> {
     procs_base: ProcsBase<'a>,
     subs: Subs,
     module_timing: ModuleTiming,
-    abilities_store: AbilitiesStore,
 }
 
 #[derive(Debug)]
@@ -733,6 +738,8 @@ struct LateSpecializationsModule<'a> {
 pub struct ToplevelExpects {
     pub pure: VecMap<Symbol, Region>,
     pub fx: VecMap<Symbol, Region>,
+    /// The top-level def that comes right before each expect in the source
+    pub preceding_defs: VecMap<Symbol, Symbol>,
 }
 
 #[derive(Debug)]
@@ -768,6 +775,7 @@ pub struct Expectations {
     pub subs: roc_types::subs::Subs,
     pub path: PathBuf,
     pub expectations: VecMap<Region, Vec<(Symbol, Variable)>>,
+    pub dbgs: VecMap<Region, Variable>,
     pub ident_ids: IdentIds,
 }
 
@@ -811,7 +819,7 @@ struct ParsedModule<'a> {
     header_for: HeaderFor<'a>,
 }
 
-type LocExpects = VecMap<Region, Vec<(Symbol, Variable)>>;
+type LocExpects = ExpectsAndDbgs;
 
 /// A message sent out _from_ a worker thread,
 /// representing a result of work done, or a request for further work
@@ -982,7 +990,11 @@ struct State<'a> {
     layout_interner: Arc<GlobalInterner<'a, Layout<'a>>>,
 }
 
-type CachedSubs = Arc<Mutex<MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>>>;
+type CachedSubs = Arc<Mutex<MutMap<ModuleId, CachedTypeState>>>;
+
+/// The solved subs of a builtin module, the variables it exposes and its abilities, as cached
+/// when the compiler is built, so that the module doesn't need to be constrained and solved.
+pub type CachedTypeState = (Subs, Vec<(Symbol, Variable)>, AbilitiesStore);
 
 impl<'a> State<'a> {
     fn goal_phase(&self) -> Phase {
@@ -996,7 +1008,7 @@ impl<'a> State<'a> {
         exposed_types: ExposedByModule,
         arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
-        cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+        cached_subs: MutMap<ModuleId, CachedTypeState>,
         render: RenderTarget,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
@@ -1119,6 +1131,8 @@ enum BuildTask<'a> {
     },
     Parse {
         header: ModuleHeader<'a>,
+        /// Whether to include the `expect`s in the code examples of doc comments
+        doc_examples: bool,
     },
     CanonicalizeAndConstrain {
         parsed: ParsedModule<'a>,
@@ -1193,6 +1207,43 @@ pub enum LoadingProblem<'a> {
     FormattedReport(String),
 }
 
+impl<'a> LoadingProblem<'a> {
+    /// Renders the problem as a report, like the ones for problems found in the modules themselves.
+    pub fn into_report(self, render: RenderTarget) -> String {
+        match self {
+            LoadingProblem::FormattedReport(report) => report,
+            LoadingProblem::FileProblem { filename, error } => {
+                to_file_problem_report(&filename, error)
+            }
+            LoadingProblem::ParsingFailed(problem) => to_parse_problem_report(
+                problem,
+                ModuleIds::default(),
+                IdentIds::exposed_builtins(0),
+                render,
+            ),
+            LoadingProblem::UnexpectedHeader(message) => {
+                to_loading_problem_report("UNEXPECTED HEADER", &message, render)
+            }
+            LoadingProblem::TriedToImportAppModule => to_loading_problem_report(
+                "APP MODULE IMPORTED",
+                "An `app` module can't be imported by another module.",
+                render,
+            ),
+            problem
+            @ (LoadingProblem::MsgChannelDied | LoadingProblem::ErrJoiningWorkerThreads) => {
+                to_loading_problem_report(
+                    "COMPILER PROBLEM",
+                    &format!(
+                        "Loading stopped because of a problem in the compiler: {:?}",
+                        problem
+                    ),
+                    render,
+                )
+            }
+        }
+    }
+}
+
 pub enum Phases {
     /// Parse, canonicalize, check types
     TypeCheck,
@@ -1454,7 +1505,7 @@ pub fn load<'a>(
     arena: &'a Bump,
     load_start: LoadStart<'a>,
     exposed_types: ExposedByModule,
-    cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+    cached_subs: MutMap<ModuleId, CachedTypeState>,
     load_config: LoadConfig,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     enum Threads {
@@ -1511,7 +1562,7 @@ pub fn load_single_threaded<'a>(
     load_start: LoadStart<'a>,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
-    cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+    cached_subs: MutMap<ModuleId, CachedTypeState>,
     render: RenderTarget,
     exec_mode: ExecutionMode,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
@@ -1714,7 +1765,7 @@ fn load_multi_threaded<'a>(
     load_start: LoadStart<'a>,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
-    cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+    cached_subs: MutMap<ModuleId, CachedTypeState>,
     render: RenderTarget,
     available_threads: usize,
     exec_mode: ExecutionMode,
@@ -2200,6 +2251,10 @@ fn update<'a>(
                 header
                     .exposed_imports
                     .insert(Ident::from("Bool"), (Symbol::BOOL_BOOL, Region::zero()));
+
+                header
+                    .exposed_imports
+                    .insert(Ident::from("Eq"), (Symbol::BOOL_EQ, Region::zero()));
             }
 
             if header.module_id == ModuleId::NUM {
@@ -2286,6 +2341,21 @@ fn update<'a>(
                         .exposed_imports
                         .insert(Ident::from(*type_name), (*symbol, Region::zero()));
                 }
+
+                // HASH
+                header
+                    .package_qualified_imported_modules
+                    .insert(PackageQualified::Unqualified(ModuleId::HASH));
+
+                header
+                    .imported_modules
+                    .insert(ModuleId::HASH, Region::zero());
+
+                for (type_name, symbol) in MODULE_HASH_TYPES {
+                    header
+                        .exposed_imports
+                        .insert(Ident::from(*type_name), (*symbol, Region::zero()));
+                }
             }
 
             state
@@ -2422,7 +2492,8 @@ fn update<'a>(
                 let (path, _) = state.module_cache.sources.get(&module_id).unwrap();
 
                 let expectations = Expectations {
-                    expectations: loc_expects,
+                    expectations: loc_expects.expects,
+                    dbgs: loc_expects.dbgs,
                     subs: solved_subs.clone().into_inner(),
                     path: path.to_owned(),
                     ident_ids: ident_ids.clone(),
@@ -2445,7 +2516,7 @@ fn update<'a>(
 
             let add_to_host_exposed = is_host_exposed &&
                 // During testing, we don't need to expose anything to the host.
-                !matches!(state.exec_mode, ExecutionMode::Test);
+                !matches!(state.exec_mode, ExecutionMode::Test | ExecutionMode::TestWithDocs);
 
             if add_to_host_exposed {
                 state.exposed_to_host.values.extend(
@@ -2554,6 +2625,10 @@ fn update<'a>(
                         .typechecked
                         .insert(module_id, typechecked);
                 } else {
+                    if matches!(state.exec_mode, ExecutionMode::CheckWithDeclarations) {
+                        state.declarations_by_id.insert(module_id, decls);
+                    }
+
                     state.constrained_ident_ids.insert(module_id, ident_ids);
                     state.timings.insert(module_id, module_timing);
                 }
@@ -2598,6 +2673,10 @@ fn update<'a>(
 
             state.toplevel_expects.pure.extend(toplevel_expects.pure);
             state.toplevel_expects.fx.extend(toplevel_expects.fx);
+            state
+                .toplevel_expects
+                .preceding_defs
+                .extend(toplevel_expects.preceding_defs);
 
             state
                 .module_cache
@@ -2606,13 +2685,28 @@ fn update<'a>(
                 .or_default()
                 .extend(procs_base.module_thunks.iter().copied());
 
+            let our_exposed_types = state
+                .exposed_types
+                .get(&module_id)
+                .unwrap_or_else(|| internal_error!("Exposed types for {:?} missing", module_id))
+                .clone();
+
+            // Add our abilities to the world now, rather than when we make our specializations.
+            // Specializations are made from the app down to the builtins, so a module that
+            // specializes an ability member of one of its dependencies (e.g. `Dict` using
+            // `Hash.addU8`) would otherwise find that dependency missing from the world.
+            state.world_abilities.insert(
+                module_id,
+                abilities_store,
+                our_exposed_types.exposed_types_storage_subs,
+            );
+
             let found_specializations_module = FoundSpecializationsModule {
                 ident_ids,
                 layout_cache,
                 procs_base,
                 subs,
                 module_timing,
-                abilities_store,
             };
 
             state
@@ -2973,7 +3067,7 @@ fn finish_specialization<'a>(
 
     let entry_point = {
         match exec_mode {
-            ExecutionMode::Test => EntryPoint::Test,
+            ExecutionMode::Test | ExecutionMode::TestWithDocs => EntryPoint::Test,
             ExecutionMode::Executable | ExecutionMode::ExecutableIfCheck => {
                 let path_to_platform = {
                     use PlatformPath::*;
@@ -3024,7 +3118,7 @@ fn finish_specialization<'a>(
                     }
                 }
             }
-            ExecutionMode::Check => unreachable!(),
+            ExecutionMode::Check | ExecutionMode::CheckWithDeclarations => unreachable!(),
         }
     };
 
@@ -3160,6 +3254,12 @@ fn load_platform_module<'a>(
                         header
                     )))
                 }
+                Ok((ast::Module::Package { header }, _parse_state)) => {
+                    Err(LoadingProblem::UnexpectedHeader(format!(
+                        "expected platform module, got Package with header\n{:?}",
+                        header
+                    )))
+                }
                 Ok((ast::Module::Platform { header }, parser_state)) => {
                     // make a `platform` module that ultimately exposes `main` to the host
                     let platform_module_msg = fabricate_platform_module(
@@ -3304,6 +3404,7 @@ fn load_module<'a>(
         "Encode", ModuleId::ENCODE
         "Decode", ModuleId::DECODE
         "Json", ModuleId::JSON
+        "Hash", ModuleId::HASH
     }
 
     let (filename, opt_shorthand) = module_name_to_path(src_dir, module_name, arc_shorthands);
@@ -3563,6 +3664,12 @@ fn parse_header<'a>(
                 To::NewPackage(_package_name) => Ok((module_id, app_module_header_msg)),
             }
         }
+        Ok((ast::Module::Package { header }, _parse_state)) => {
+            Err(LoadingProblem::UnexpectedHeader(format!(
+                "package modules can only be documented for now, got Package with header\n{:?}",
+                header
+            )))
+        }
         Ok((ast::Module::Platform { header }, parse_state)) => Ok(fabricate_platform_module(
             arena,
             None,
@@ -4315,6 +4422,16 @@ pub fn add_imports(
                     .storage_subs
                     .export_variable_to(ctx.subs, *var);
 
+                // The specialization is polymorphic in the type arguments of its opaque, just
+                // like an imported symbol. Generalize its ambient function, turning rigids from
+                // an annotated implementation into flex vars, so that every compaction of this
+                // lambda set works on a fresh instance rather than the imported type itself.
+                let ambient_function = ctx
+                    .subs
+                    .get_lambda_set(copied_import.variable)
+                    .ambient_function;
+                roc_types::subs::instantiate_rigids(ctx.subs, ambient_function);
+
                 copied_import.variable
             }
             None => internal_error!("Imported module {:?} is not available", module),
@@ -4462,15 +4579,16 @@ fn run_solve<'a>(
                     module,
                     derived_module,
                 ),
-                Some((subs, exposed_vars_by_symbol)) => {
+                Some((subs, exposed_vars_by_symbol, abilities_store)) => {
+                    let solved_implementations =
+                        extract_module_owned_implementations(module_id, &abilities_store);
+
                     (
                         Solved(subs),
-                        // TODO(abilities) cache abilities for builtins
-                        VecMap::default(),
+                        solved_implementations,
                         exposed_vars_by_symbol.to_vec(),
                         vec![],
-                        // TODO(abilities) cache abilities for builtins
-                        AbilitiesStore::default(),
+                        abilities_store,
                     )
                 }
             }
@@ -4648,6 +4766,7 @@ fn canonicalize_and_constrain<'a>(
             let docs = crate::docs::generate_module_docs(
                 scope,
                 name.as_str().into(),
+                exposed_symbols.clone(),
                 &parsed_defs_for_docs,
             );
 
@@ -4699,7 +4818,11 @@ fn canonicalize_and_constrain<'a>(
             Vacant(vacant) => {
                 let should_include_builtin = matches!(
                     name.module_id(),
-                    ModuleId::ENCODE | ModuleId::DECODE | ModuleId::DICT | ModuleId::SET
+                    ModuleId::ENCODE
+                        | ModuleId::DECODE
+                        | ModuleId::DICT
+                        | ModuleId::SET
+                        | ModuleId::HASH
                 );
 
                 if !name.is_builtin() || should_include_builtin {
@@ -4741,12 +4864,16 @@ fn canonicalize_and_constrain<'a>(
     }
 }
 
-fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, LoadingProblem<'a>> {
+fn parse<'a>(
+    arena: &'a Bump,
+    header: ModuleHeader<'a>,
+    doc_examples: bool,
+) -> Result<Msg<'a>, LoadingProblem<'a>> {
     let mut module_timing = header.module_timing;
     let parse_start = Instant::now();
     let source = header.parse_state.original_bytes();
     let parse_state = header.parse_state;
-    let parsed_defs = match module_defs().parse(arena, parse_state) {
+    let mut parsed_defs = match module_defs().parse(arena, parse_state) {
         Ok((_, success, _state)) => success,
         Err((_, fail, state)) => {
             return Err(LoadingProblem::ParsingFailed(
@@ -4780,6 +4907,10 @@ fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, Loadi
         ..
     } = header;
 
+    if doc_examples {
+        add_doc_example_expects(arena, &module_path, src, &mut parsed_defs)?;
+    }
+
     let parsed = ParsedModule {
         module_id,
         module_name,
@@ -4798,6 +4929,100 @@ fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, Loadi
     Ok(Msg::Parsed(parsed))
 }
 
+/// Adds the `expect`s in the code examples of the module's doc comments to its defs,
+/// so they get run along with the module's own.
+fn add_doc_example_expects<'a>(
+    arena: &'a Bump,
+    module_path: &Path,
+    src: &'a str,
+    defs: &mut Defs<'a>,
+) -> Result<(), LoadingProblem<'a>> {
+    let (examples_src, example_ranges) = match crate::docs::doc_examples_source(src) {
+        Some((examples_src, example_ranges)) => (arena.alloc_str(&examples_src), example_ranges),
+        None => return Ok(()),
+    };
+
+    let examples_state = roc_parse::state::State::new(examples_src.as_bytes());
+    let examples = match module_defs().parse(arena, examples_state) {
+        Ok((_, examples, _state)) => examples,
+        Err((_, fail, _state)) => {
+            // Report the problem against the module's own source, where the example is,
+            // since the examples' source has extra newlines in it.
+            let source_error = SourceError {
+                problem: fail,
+                bytes: src.as_bytes(),
+            };
+
+            return Err(LoadingProblem::ParsingFailed(
+                source_error.into_file_error(module_path.to_path_buf()),
+            ));
+        }
+    };
+
+    // An example's other defs can be used by the expects after them in the same example,
+    // so each expect gets its own block with those in it. That keeps the examples from
+    // seeing each other's defs, or adding any to the module.
+    for range in example_ranges {
+        let mut example_defs = Defs::default();
+
+        for (index, def) in examples.defs().enumerate() {
+            let region = examples.regions[index];
+
+            if !range.contains(&(region.start().offset as usize)) {
+                continue;
+            }
+
+            match def {
+                Ok(type_def) => {
+                    example_defs.push_type_def(*type_def, region, &[], &[]);
+                }
+                Err(ValueDef::Expect {
+                    condition,
+                    preceding_comment,
+                }) => {
+                    let value_def = ValueDef::Expect {
+                        condition: in_example_block(arena, &example_defs, condition),
+                        preceding_comment: *preceding_comment,
+                    };
+
+                    defs.push_value_def(value_def, region, &[], &[]);
+                }
+                Err(ValueDef::ExpectFx {
+                    condition,
+                    preceding_comment,
+                }) => {
+                    let value_def = ValueDef::ExpectFx {
+                        condition: in_example_block(arena, &example_defs, condition),
+                        preceding_comment: *preceding_comment,
+                    };
+
+                    defs.push_value_def(value_def, region, &[], &[]);
+                }
+                Err(value_def) => {
+                    example_defs.push_value_def(*value_def, region, &[], &[]);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Puts an example's expect condition in a block with the defs before it in the example.
+fn in_example_block<'a>(
+    arena: &'a Bump,
+    example_defs: &Defs<'a>,
+    condition: &'a Loc<ast::Expr<'a>>,
+) -> &'a Loc<ast::Expr<'a>> {
+    if example_defs.is_empty() {
+        return condition;
+    }
+
+    let block = ast::Expr::Defs(arena.alloc(example_defs.clone()), condition);
+
+    arena.alloc(Loc::at(condition.region, block))
+}
+
 fn exposed_from_import<'a>(entry: &ImportsEntry<'a>) -> (QualifiedModuleName<'a>, Vec<Ident>) {
     use roc_parse::header::ImportsEntry::*;
 
@@ -5245,6 +5470,10 @@ fn build_pending_specializations<'a>(
                 let expr_region = declarations.expressions[index].region;
                 let region = Region::span_across(&name_region, &expr_region);
 
+                if let Some(def_symbol) = preceding_def(&declarations, expr_region) {
+                    toplevel_expects.preceding_defs.insert(symbol, def_symbol);
+                }
+
                 toplevel_expects.pure.insert(symbol, region);
                 procs_base.partial_procs.insert(symbol, proc);
             }
@@ -5318,6 +5547,10 @@ fn build_pending_specializations<'a>(
                 let expr_region = declarations.expressions[index].region;
                 let region = Region::span_across(&name_region, &expr_region);
 
+                if let Some(def_symbol) = preceding_def(&declarations, expr_region) {
+                    toplevel_expects.preceding_defs.insert(symbol, def_symbol);
+                }
+
                 toplevel_expects.fx.insert(symbol, region);
                 procs_base.partial_procs.insert(symbol, proc);
             }
@@ -5342,6 +5575,24 @@ fn build_pending_specializations<'a>(
     }
 }
 
+/// Finds the top-level def that starts closest before `region`. Top-level expects usually
+/// follow the def they test, so this is what `roc test --filter` matches their names against.
+fn preceding_def(declarations: &Declarations, region: Region) -> Option<Symbol> {
+    use roc_can::expr::DeclarationTag::*;
+
+    (0..declarations.len())
+        .filter(|index| {
+            matches!(
+                declarations.declarations[*index],
+                Value | Function(_) | Recursive(_) | TailRecursive(_)
+            )
+        })
+        .map(|index| declarations.symbols[index])
+        .filter(|loc_symbol| loc_symbol.region.start() < region.start())
+        .max_by_key(|loc_symbol| loc_symbol.region.start())
+        .map(|loc_symbol| loc_symbol.value)
+}
+
 /// Loads derived ability members up for specialization into the Derived module, prior to making
 /// their specializations.
 // TODO: right now, this runs sequentially, and no other modules are mono'd in parallel to the
@@ -5474,7 +5725,10 @@ fn run_task<'a>(
             ident_ids_by_module,
         )
         .map(|(_, msg)| msg),
-        Parse { header } => parse(arena, header),
+        Parse {
+            header,
+            doc_examples,
+        } => parse(arena, header, doc_examples),
         CanonicalizeAndConstrain {
             parsed,
             module_ids,
@@ -5708,6 +5962,30 @@ fn to_parse_problem_report<'a>(
     buf
 }
 
+fn to_loading_problem_report(title: &str, message: &str, render: RenderTarget) -> String {
+    use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
+    use ven_pretty::DocAllocator;
+
+    let mut module_ids = ModuleIds::default();
+    let module_id = module_ids.get_or_insert(&"find module name somehow?".into());
+    let interns = Interns::default();
+    let alloc = RocDocAllocator::new(&[], module_id, &interns);
+
+    let report = Report {
+        filename: "UNKNOWN.roc".into(),
+        doc: alloc.stack(message.lines().map(|line| alloc.text(line))),
+        title: title.to_string(),
+        severity: Severity::RuntimeError,
+    };
+
+    let mut buf = String::new();
+    let palette = DEFAULT_PALETTE;
+
+    report.render(render, &mut buf, &alloc, &palette);
+
+    buf
+}
+
 fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> String {
     use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
     use ven_pretty::DocAllocator;
@@ -481,12 +481,12 @@ fn load_astar() {
     expect_types(
         loaded_module,
         hashmap! {
-            "findPath" => "{ costFunction : position, position -> F64, end : position, moveFunction : position -> Set position, start : position } -> Result (List position) [KeyNotFound]*",
-            "initialModel" => "position -> Model position",
-            "reconstructPath" => "Dict position position, position -> List position",
-            "updateCost" => "position, position, Model position -> Model position",
-            "cheapestOpen" => "(position -> F64), Model position -> Result position [KeyNotFound]*",
-            "astar" => "(position, position -> F64), (position -> Set position), position, Model position -> [Err [KeyNotFound]*, Ok (List position)]*",
+            "findPath" => "{ costFunction : position, position -> F64, end : position, moveFunction : position -> Set position, start : position } -> Result (List position) [KeyNotFound]* | position has Hash",
+            "initialModel" => "position -> Model position | position has Hash",
+            "reconstructPath" => "Dict position position, position -> List position | position has Hash",
+            "updateCost" => "position, position, Model position -> Model position | position has Hash",
+            "cheapestOpen" => "(position -> F64), Model position -> Result position [KeyNotFound]* | position has Hash",
+            "astar" => "(position, position -> F64), (position -> Set position), position, Model position -> [Err [KeyNotFound]*, Ok (List position)]* | position has Hash",
         },
     );
 }
@@ -909,3 +909,154 @@ fn import_builtin_in_platform_and_check_app() {
     let result = multiple_modules("issue_2863_module_type_does_not_exist", modules);
     assert!(result.is_ok(), "should check");
 }
+
+fn load_doc_example_expects(src: &str, exec_mode: ExecutionMode) -> Result<Vec<String>, String> {
+    use LoadResult::*;
+
+    let arena = Bump::new();
+    let filename = PathBuf::from("Greeting.roc");
+    let load_start = LoadStart::from_str(&arena, filename, src, PathBuf::from(".")).unwrap();
+    let load_config = LoadConfig {
+        target_info: TARGET_INFO,
+        render: RenderTarget::Generic,
+        threading: Threading::Single,
+        exec_mode,
+    };
+
+    let loaded = roc_load_internal::file::load(
+        &arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        load_config,
+    );
+
+    match loaded {
+        Ok(Monomorphized(module)) => {
+            let mut regions: Vec<_> = module.toplevel_expects.pure.values().copied().collect();
+            regions.sort_by_key(|region| region.start());
+
+            Ok(regions
+                .iter()
+                .map(|region| {
+                    src[region.start().offset as usize..region.end().offset as usize].to_string()
+                })
+                .collect())
+        }
+        Ok(TypeChecked(_)) => unreachable!(""),
+        Err(LoadingProblem::FormattedReport(report)) => Err(report),
+        Err(problem) => panic!("{:?}", problem),
+    }
+}
+
+#[test]
+fn doc_example_expects() {
+    let src = indoc!(
+        r#"
+            interface Greeting
+                exposes [greet]
+                imports []
+
+            ## Greets someone by name.
+            ##
+            ## ```
+            ## expect greet "Ari" == "Hello, Ari!"
+            ## ```
+            ##
+            ## ```
+            ## expect
+            ##     greeting = greet "Bo"
+            ##     Str.startsWith greeting "Hello"
+            ## ```
+            ##
+            ## Examples without expectations don't get run.
+            ##
+            ## ```roc
+            ## greet "Sam"
+            ## ```
+            ##
+            ## ```sh
+            ## expect this-is-not-roc
+            ## ```
+            greet : Str -> Str
+            greet = \name -> "Hello, \(name)!"
+
+            expect greet "" == "Hello, !"
+            "#
+    );
+
+    assert_eq!(
+        load_doc_example_expects(src, ExecutionMode::TestWithDocs).unwrap(),
+        vec![
+            r#"expect greet "Ari" == "Hello, Ari!""#,
+            "expect\n##     greeting = greet \"Bo\"\n##     Str.startsWith greeting \"Hello\"",
+            r#"expect greet "" == "Hello, !""#,
+        ]
+    );
+
+    assert_eq!(
+        load_doc_example_expects(src, ExecutionMode::Test).unwrap(),
+        vec![r#"expect greet "" == "Hello, !""#]
+    );
+}
+
+#[test]
+fn doc_example_defs() {
+    let src = indoc!(
+        r#"
+            interface Greeting
+                exposes [greet]
+                imports []
+
+            ## Greets someone by name.
+            ##
+            ## ```
+            ## greeting = greet "Ari"
+            ##
+            ## expect greeting == "Hello, Ari!"
+            ## ```
+            ##
+            ## ```
+            ## greeting = greet ""
+            ##
+            ## expect greeting == "Hello, !"
+            ## ```
+            greet : Str -> Str
+            greet = \name -> "Hello, \(name)!"
+            "#
+    );
+
+    assert_eq!(
+        load_doc_example_expects(src, ExecutionMode::TestWithDocs).unwrap(),
+        vec![
+            r#"expect greeting == "Hello, Ari!""#,
+            r#"expect greeting == "Hello, !""#,
+        ]
+    );
+}
+
+#[test]
+fn doc_example_syntax_error() {
+    let src = indoc!(
+        r#"
+            interface Greeting
+                exposes [greet]
+                imports []
+
+            ## ```
+            ## expect greet "Ari" == "Hello
+            ## ```
+            greet : Str -> Str
+            greet = \name -> "Hello, \(name)!"
+            "#
+    );
+
+    let report = load_doc_example_expects(src, ExecutionMode::TestWithDocs).unwrap_err();
+
+    // The problem gets shown where it is in the doc comment.
+    assert!(
+        report.contains(r#"6│  ## expect greet "Ari" == "Hello"#),
+        "{}",
+        report
+    );
+}
@@ -50,6 +50,15 @@ const PRETTY_PRINT_DEBUG_SYMBOLS: bool = true;
 pub const DERIVABLE_ABILITIES: &[(Symbol, &[Symbol])] = &[
     (Symbol::ENCODE_ENCODING, &[Symbol::ENCODE_TO_ENCODER]),
     (Symbol::DECODE_DECODING, &[Symbol::DECODE_DECODER]),
+    (Symbol::HASH_HASH_ABILITY, &[Symbol::HASH_HASH]),
+    (Symbol::BOOL_EQ, &[Symbol::BOOL_IS_EQ]),
+];
+
+/// Builtin abilities that imply another ability, as (ability, implied ability) pairs. A type that
+/// implements an ability must also implement all abilities it implies.
+pub const IMPLIED_ABILITIES: &[(Symbol, Symbol)] = &[
+    // Values that hash the same must be equal.
+    (Symbol::HASH_HASH_ABILITY, Symbol::BOOL_EQ),
 ];
 
 /// In Debug builds only, Symbol has a name() method that lets
@@ -97,6 +106,11 @@ impl Symbol {
         DERIVABLE_ABILITIES.iter().find(|(name, _)| *name == self)
     }
 
+    /// Whether every type implementing this ability must also implement the `other` ability.
+    pub fn ability_implies(self, other: Symbol) -> bool {
+        self == other || IMPLIED_ABILITIES.contains(&(self, other))
+    }
+
     pub fn module_string<'a>(&self, interns: &'a Interns) -> &'a ModuleName {
         interns
             .module_ids
@@ -1193,8 +1207,11 @@ define_builtins! {
         4 BOOL_OR: "or"
         5 BOOL_NOT: "not"
         6 BOOL_XOR: "xor"
-        7 BOOL_EQ: "isEq"
-        8 BOOL_NEQ: "isNotEq"
+        7 BOOL_EQ: "Eq"
+        8 BOOL_IS_EQ: "isEq"
+        9 BOOL_IS_NOT_EQ: "isNotEq"
+        10 BOOL_STRUCTURAL_EQ: "structuralEq"
+        11 BOOL_STRUCTURAL_NOT_EQ: "structuralNotEq"
     }
     5 STR: "Str" => {
         0 STR_STR: "Str" imported // the Str.Str type alias
@@ -1363,6 +1380,9 @@ define_builtins! {
 
         15 DICT_WITH_CAPACITY: "withCapacity"
         16 DICT_CAPACITY: "capacity"
+
+        17 DICT_LOWLEVEL_HASHER: "LowLevelHasher" // the Dict.LowLevelHasher opaque type
+        18 DICT_HASH_BYTES_LOWLEVEL: "hashBytesLowlevel"
     }
     9 SET: "Set" => {
         0 SET_SET: "Set" // the Set.Set type alias
@@ -1439,14 +1459,36 @@ define_builtins! {
         20 DECODE_STRING: "string"
         21 DECODE_LIST: "list"
         22 DECODE_RECORD: "record"
-        23 DECODE_CUSTOM: "custom"
-        24 DECODE_DECODE_WITH: "decodeWith"
-        25 DECODE_FROM_BYTES_PARTIAL: "fromBytesPartial"
-        26 DECODE_FROM_BYTES: "fromBytes"
+        23 DECODE_TAG: "tag"
+        24 DECODE_CUSTOM: "custom"
+        25 DECODE_DECODE_WITH: "decodeWith"
+        26 DECODE_FROM_BYTES_PARTIAL: "fromBytesPartial"
+        27 DECODE_FROM_BYTES: "fromBytes"
     }
     13 JSON: "Json" => {
         0 JSON_JSON: "Json"
     }
-
-    num_modules: 14 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
+    14 HASH: "Hash" => {
+        0 HASH_HASH_ABILITY: "Hash"
+        1 HASH_HASH: "hash"
+        2 HASH_HASHER: "Hasher"
+        3 HASH_ADD_BYTES: "addBytes"
+        4 HASH_ADD_U8: "addU8"
+        5 HASH_ADD_U16: "addU16"
+        6 HASH_ADD_U32: "addU32"
+        7 HASH_ADD_U64: "addU64"
+        8 HASH_ADD_U128: "addU128"
+        9 HASH_COMPLETE: "complete"
+        10 HASH_HASH_BOOL: "hashBool"
+        11 HASH_HASH_I8: "hashI8"
+        12 HASH_HASH_I16: "hashI16"
+        13 HASH_HASH_I32: "hashI32"
+        14 HASH_HASH_I64: "hashI64"
+        15 HASH_HASH_I128: "hashI128"
+        16 HASH_HASH_NAT: "hashNat"
+        17 HASH_HASH_STR_BYTES: "hashStrBytes"
+        18 HASH_HASH_LIST: "hashList"
+    }
+
+    num_modules: 15 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
 }
@@ -310,6 +310,7 @@ impl<'a> ParamMap<'a> {
 
                 Expect { remainder, .. } => stack.push(remainder),
                 ExpectFx { remainder, .. } => stack.push(remainder),
+                Dbg { remainder, .. } => stack.push(remainder),
 
                 Switch {
                     branches,
@@ -825,6 +826,10 @@ impl<'a> BorrowInfState<'a> {
                 self.collect_stmt(param_map, remainder);
             }
 
+            Dbg { remainder, .. } => {
+                self.collect_stmt(param_map, remainder);
+            }
+
             Refcounting(_, _) => unreachable!("these have not been introduced yet"),
 
             Ret(_) | RuntimeError(_) => {
@@ -994,6 +999,7 @@ fn call_info_stmt<'a>(arena: &'a Bump, stmt: &Stmt<'a>, info: &mut CallInfo<'a>)
 
             Expect { remainder, .. } => stack.push(remainder),
             ExpectFx { remainder, .. } => stack.push(remainder),
+            Dbg { remainder, .. } => stack.push(remainder),
 
             Refcounting(_, _) => unreachable!("these have not been introduced yet"),
 
@@ -13,6 +13,8 @@ use crate::layout::{Builtin, CapturesNiche, LambdaName, Layout, STLayoutInterner
 mod equality;
 mod refcount;
 
+pub use refcount::is_rc_implemented_yet;
+
 const LAYOUT_BOOL: Layout = Layout::Builtin(Builtin::Bool);
 const LAYOUT_UNIT: Layout = Layout::UNIT;
 
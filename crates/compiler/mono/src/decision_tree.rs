@@ -1,11 +1,12 @@
 use crate::ir::{
-    BranchInfo, DestructType, Env, Expr, JoinPointId, Literal, Param, Pattern, Procs, Stmt,
+    build_list_index_probe, BranchInfo, Call, CallType, DestructType, Env, Expr, JoinPointId,
+    ListIndex, Literal, Param, Pattern, Procs, Stmt,
 };
 use crate::layout::{Builtin, Layout, LayoutCache, TagIdIntType, UnionLayout};
 use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_collections::all::{MutMap, MutSet};
 use roc_error_macros::internal_error;
-use roc_exhaustive::{Ctor, CtorName, RenderAs, TagId, Union};
+use roc_exhaustive::{Ctor, CtorName, ListArity, RenderAs, TagId, Union};
 use roc_module::ident::TagName;
 use roc_module::low_level::LowLevel;
 use roc_module::symbol::Symbol;
@@ -14,6 +15,7 @@ use roc_module::symbol::Symbol;
 
 type Label = u64;
 const RECORD_TAG_NAME: &str = "#Record";
+const TUPLE_TAG_NAME: &str = "#Tuple";
 
 /// Users of this module will mainly interact with this function. It takes
 /// some normal branches and gives out a decision tree that has "labels" at all
@@ -95,6 +97,16 @@ enum Test<'a> {
         tag_id: TagIdIntType,
         num_alts: usize,
     },
+    IsListLen {
+        bound: ListLenBound,
+        len: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum ListLenBound {
+    Exact,
+    AtLeast,
 }
 
 impl<'a> Test<'a> {
@@ -110,6 +122,7 @@ impl<'a> Test<'a> {
             Test::IsStr(_) => false,
             Test::IsBit(_) => true,
             Test::IsByte { .. } => true,
+            Test::IsListLen { .. } => false,
         }
     }
 }
@@ -153,6 +166,11 @@ impl<'a> Hash for Test<'a> {
                 state.write_u8(6);
                 v.hash(state);
             }
+            IsListLen { bound, len } => {
+                state.write_u8(7);
+                bound.hash(state);
+                len.hash(state);
+            }
         }
     }
 }
@@ -331,6 +349,7 @@ fn tests_are_complete_help(last_test: &Test, number_of_tests: usize) -> bool {
         Test::IsFloat(_, _) => false,
         Test::IsDecimal(_) => false,
         Test::IsStr(_) => false,
+        Test::IsListLen { .. } => false,
     }
 }
 
@@ -467,6 +486,8 @@ fn tests_at_path<'a>(
         all_tests.extend(test_at_path(selected_path, branch));
     }
 
+    let all_tests = disjoint_list_len_tests(all_tests);
+
     // The rust HashMap also uses equality, here we really want to use the custom hash function
     // defined on Test to determine whether a test is unique. So we have to do the hashing
     // explicitly
@@ -492,6 +513,87 @@ fn tests_at_path<'a>(
     unique
 }
 
+/// Unlike the tests for constructors or literals, tests on the length of a list overlap: both
+/// `[_, ..]` and `[_, _]` match a list of two elements. At most one test at a path may succeed,
+/// so we replace the tests for list patterns by tests for disjoint lengths, such that each list
+/// pattern matches either all or none of the lists passing a test.
+fn disjoint_list_len_tests(tests: Vec<GuardedTest>) -> Vec<GuardedTest> {
+    let mut first_index = None;
+    let mut exact_lens = Vec::new();
+    let mut slice_lens = Vec::new();
+
+    for (index, guarded_test) in tests.iter().enumerate() {
+        if let GuardedTest::TestNotGuarded {
+            test: Test::IsListLen { bound, len },
+        } = guarded_test
+        {
+            first_index.get_or_insert(index);
+
+            match bound {
+                ListLenBound::Exact => exact_lens.push(*len),
+                ListLenBound::AtLeast => slice_lens.push(*len),
+            }
+        }
+    }
+
+    let (first_index, min_slice_len, max_slice_len) = match (
+        first_index,
+        slice_lens.iter().min(),
+        slice_lens.iter().max(),
+    ) {
+        (Some(index), Some(min), Some(max)) => (index, *min, *max),
+        // no list patterns, or only exact ones, which are already disjoint
+        _ => return tests,
+    };
+
+    // Lists shorter than every slice pattern are only matched by exact patterns. From the
+    // shortest slice pattern onwards, every length gets its own test, until the lengths are
+    // long enough that all list patterns agree on them.
+    let past_exact_lens = exact_lens.iter().max().map_or(0, |len| len + 1);
+    let open_len = max_slice_len.max(past_exact_lens);
+
+    exact_lens.retain(|len| *len < min_slice_len);
+    exact_lens.extend(min_slice_len..open_len);
+    exact_lens.sort_unstable();
+    exact_lens.dedup();
+
+    let mut list_tests: Vec<_> = exact_lens
+        .into_iter()
+        .map(|len| Test::IsListLen {
+            bound: ListLenBound::Exact,
+            len,
+        })
+        .collect();
+
+    list_tests.push(Test::IsListLen {
+        bound: ListLenBound::AtLeast,
+        len: open_len,
+    });
+
+    let mut result = Vec::with_capacity(tests.len() + list_tests.len());
+
+    for (index, guarded_test) in tests.into_iter().enumerate() {
+        if index == first_index {
+            result.extend(
+                list_tests
+                    .drain(..)
+                    .map(|test| GuardedTest::TestNotGuarded { test }),
+            );
+        }
+
+        if !matches!(
+            guarded_test,
+            GuardedTest::TestNotGuarded {
+                test: Test::IsListLen { .. }
+            }
+        ) {
+            result.push(guarded_test);
+        }
+    }
+
+    result
+}
+
 fn test_at_path<'a>(
     selected_path: &[PathInstruction],
     branch: &Branch<'a>,
@@ -549,6 +651,30 @@ fn test_at_path<'a>(
                     }
                 }
 
+                TupleDestructure(destructs, _) => {
+                    // not rendered, so pick the easiest
+                    let union = Union {
+                        render_as: RenderAs::Tag,
+                        alternatives: vec![Ctor {
+                            tag_id: TagId(0),
+                            name: CtorName::Tag(TagName(TUPLE_TAG_NAME.into())),
+                            arity: destructs.len(),
+                        }],
+                    };
+
+                    let arguments = destructs
+                        .iter()
+                        .map(|destruct| (destruct.pat.clone(), destruct.layout))
+                        .collect();
+
+                    IsCtor {
+                        tag_id: 0,
+                        ctor_name: CtorName::Tag(TagName(TUPLE_TAG_NAME.into())),
+                        union,
+                        arguments,
+                    }
+                }
+
                 NewtypeDestructure {
                     tag_name,
                     arguments,
@@ -607,6 +733,17 @@ fn test_at_path<'a>(
                 FloatLiteral(v, precision) => IsFloat(*v, *precision),
                 DecimalLiteral(v) => IsDecimal(*v),
                 StrLiteral(v) => IsStr(v.clone()),
+
+                List { arity, .. } => match arity {
+                    ListArity::Exact(len) => IsListLen {
+                        bound: ListLenBound::Exact,
+                        len: *len as u64,
+                    },
+                    ListArity::Slice(..) => IsListLen {
+                        bound: ListLenBound::AtLeast,
+                        len: arity.min_len() as u64,
+                    },
+                },
             };
 
             let guarded_test = GuardedTest::TestNotGuarded { test };
@@ -730,6 +867,40 @@ fn to_relevant_branch_help<'a>(
             _ => None,
         },
 
+        TupleDestructure(destructs, _) => match test {
+            IsCtor {
+                ctor_name: test_name,
+                tag_id,
+                ..
+            } => {
+                debug_assert!(test_name == &CtorName::Tag(TagName(TUPLE_TAG_NAME.into())));
+                let destructs_len = destructs.len();
+                let sub_positions = destructs.into_iter().enumerate().map(|(index, destruct)| {
+                    let mut new_path = path.to_vec();
+                    let next_instr = if destructs_len == 1 {
+                        PathInstruction::NewType
+                    } else {
+                        PathInstruction::TagIndex {
+                            index: index as u64,
+                            tag_id: *tag_id,
+                        }
+                    };
+                    new_path.push(next_instr);
+
+                    (new_path, destruct.pat)
+                });
+                start.extend(sub_positions);
+                start.extend(end);
+
+                Some(Branch {
+                    goal: branch.goal,
+                    guard: branch.guard.clone(),
+                    patterns: start,
+                })
+            }
+            _ => None,
+        },
+
         OpaqueUnwrap { opaque, argument } => match test {
             IsCtor {
                 ctor_name: test_opaque_tag_name,
@@ -755,6 +926,39 @@ fn to_relevant_branch_help<'a>(
             _ => None,
         },
 
+        List {
+            arity, elements, ..
+        } => match test {
+            IsListLen { bound, len }
+                if arity.covers_length(*len as usize)
+                    && !(*bound == ListLenBound::AtLeast
+                        && matches!(arity, ListArity::Exact(_))) =>
+            {
+                let sub_positions =
+                    elements
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, element_pattern)| {
+                            let mut new_path = path.to_vec();
+                            new_path.push(PathInstruction::ListIndex {
+                                index: ListIndex::from_pattern_index(index, arity),
+                            });
+
+                            (new_path, element_pattern)
+                        });
+
+                start.extend(sub_positions);
+                start.extend(end);
+
+                Some(Branch {
+                    goal: branch.goal,
+                    guard: branch.guard.clone(),
+                    patterns: start,
+                })
+            }
+            _ => None,
+        },
+
         NewtypeDestructure {
             tag_name,
             arguments,
@@ -1014,8 +1218,10 @@ fn needs_tests(pattern: &Pattern) -> bool {
 
         NewtypeDestructure { .. }
         | RecordDestructure(..)
+        | TupleDestructure(..)
         | AppliedTag { .. }
         | OpaqueUnwrap { .. }
+        | List { .. }
         | BitLiteral { .. }
         | EnumLiteral { .. }
         | IntLiteral(_, _)
@@ -1268,6 +1474,7 @@ pub fn optimize_when<'a>(
 enum PathInstruction {
     NewType,
     TagIndex { index: u64, tag_id: TagIdIntType },
+    ListIndex { index: ListIndex },
 }
 
 fn path_to_expr_help<'a>(
@@ -1337,19 +1544,42 @@ fn path_to_expr_help<'a>(
                     }
                 }
             }
+
+            PathInstruction::ListIndex { index } => {
+                let element_layout = match layout {
+                    Layout::Builtin(Builtin::List(element_layout)) => *element_layout,
+                    _ => internal_error!("list index into a non-list layout: {:?}", layout),
+                };
+
+                let (index_symbol, index_stores) = build_list_index_probe(env, symbol, *index);
+                stores.extend(index_stores);
+
+                let load = Expr::Call(Call {
+                    call_type: CallType::LowLevel {
+                        op: LowLevel::ListGetUnsafe,
+                        update_mode: env.next_update_mode_id(),
+                    },
+                    arguments: env.arena.alloc([symbol, index_symbol]),
+                });
+
+                symbol = env.unique_symbol();
+                stores.push((symbol, element_layout, load));
+
+                layout = element_layout;
+            }
         }
     }
 
     (symbol, stores, layout)
 }
 
-fn test_to_equality<'a>(
+fn test_to_comparison<'a>(
     env: &mut Env<'a, '_>,
     cond_symbol: Symbol,
     cond_layout: &Layout<'a>,
     path: &[PathInstruction],
     test: Test<'a>,
-) -> (StoresVec<'a>, Symbol, Symbol, Option<ConstructorKnown<'a>>) {
+) -> (StoresVec<'a>, Comparison, Option<ConstructorKnown<'a>>) {
     let (rhs_symbol, mut stores, test_layout) =
         path_to_expr_help(env, cond_symbol, path, *cond_layout);
 
@@ -1379,8 +1609,7 @@ fn test_to_equality<'a>(
 
                     (
                         stores,
-                        lhs_symbol,
-                        rhs_symbol,
+                        (lhs_symbol, Comparator::Eq, rhs_symbol),
                         Some(ConstructorKnown::OnlyPass {
                             scrutinee: path_symbol,
                             layout: *cond_layout,
@@ -1397,7 +1626,7 @@ fn test_to_equality<'a>(
             let lhs_symbol = env.unique_symbol();
             stores.push((lhs_symbol, Layout::int_width(precision), lhs));
 
-            (stores, lhs_symbol, rhs_symbol, None)
+            (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
         }
 
         Test::IsFloat(test_int, precision) => {
@@ -1407,7 +1636,7 @@ fn test_to_equality<'a>(
             let lhs_symbol = env.unique_symbol();
             stores.push((lhs_symbol, Layout::float_width(precision), lhs));
 
-            (stores, lhs_symbol, rhs_symbol, None)
+            (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
         }
 
         Test::IsDecimal(test_dec) => {
@@ -1415,7 +1644,7 @@ fn test_to_equality<'a>(
             let lhs_symbol = env.unique_symbol();
             stores.push((lhs_symbol, *cond_layout, lhs));
 
-            (stores, lhs_symbol, rhs_symbol, None)
+            (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
         }
 
         Test::IsByte {
@@ -1427,7 +1656,7 @@ fn test_to_equality<'a>(
             let lhs_symbol = env.unique_symbol();
             stores.push((lhs_symbol, Layout::u8(), lhs));
 
-            (stores, lhs_symbol, rhs_symbol, None)
+            (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
         }
 
         Test::IsBit(test_bit) => {
@@ -1435,7 +1664,7 @@ fn test_to_equality<'a>(
             let lhs_symbol = env.unique_symbol();
             stores.push((lhs_symbol, Layout::Builtin(Builtin::Bool), lhs));
 
-            (stores, lhs_symbol, rhs_symbol, None)
+            (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
         }
 
         Test::IsStr(test_str) => {
@@ -1444,15 +1673,51 @@ fn test_to_equality<'a>(
 
             stores.push((lhs_symbol, Layout::Builtin(Builtin::Str), lhs));
 
-            (stores, lhs_symbol, rhs_symbol, None)
+            (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
+        }
+
+        Test::IsListLen { bound, len } => {
+            let list_symbol = rhs_symbol;
+            debug_assert!(matches!(test_layout, Layout::Builtin(Builtin::List(_))));
+
+            let usize_layout = Layout::usize(env.target_info);
+
+            let real_len_expr = Expr::Call(Call {
+                call_type: CallType::LowLevel {
+                    op: LowLevel::ListLen,
+                    update_mode: env.next_update_mode_id(),
+                },
+                arguments: env.arena.alloc([list_symbol]),
+            });
+            let test_len_expr = Expr::Literal(Literal::Int((len as i128).to_ne_bytes()));
+
+            let real_len = env.unique_symbol();
+            let test_len = env.unique_symbol();
+
+            stores.push((real_len, usize_layout, real_len_expr));
+            stores.push((test_len, usize_layout, test_len_expr));
+
+            let comparator = match bound {
+                ListLenBound::Exact => Comparator::Eq,
+                ListLenBound::AtLeast => Comparator::Geq,
+            };
+
+            (stores, (real_len, comparator, test_len), None)
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparator {
+    Eq,
+    Geq,
+}
+
+type Comparison = (Symbol, Comparator, Symbol);
+
 type Tests<'a> = std::vec::Vec<(
     bumpalo::collections::Vec<'a, (Symbol, Layout<'a>, Expr<'a>)>,
-    Symbol,
-    Symbol,
+    Comparison,
     Option<ConstructorKnown<'a>>,
 )>;
 
@@ -1466,7 +1731,13 @@ fn stores_and_condition<'a>(
 
     // Assumption: there is at most 1 guard, and it is the outer layer.
     for (path, test) in test_chain {
-        tests.push(test_to_equality(env, cond_symbol, cond_layout, &path, test))
+        tests.push(test_to_comparison(
+            env,
+            cond_symbol,
+            cond_layout,
+            &path,
+            test,
+        ))
     }
 
     tests
@@ -1476,8 +1747,7 @@ fn compile_test<'a>(
     env: &mut Env<'a, '_>,
     ret_layout: Layout<'a>,
     stores: bumpalo::collections::Vec<'a, (Symbol, Layout<'a>, Expr<'a>)>,
-    lhs: Symbol,
-    rhs: Symbol,
+    comparison: Comparison,
     fail: &'a Stmt<'a>,
     cond: Stmt<'a>,
 ) -> Stmt<'a> {
@@ -1486,8 +1756,7 @@ fn compile_test<'a>(
         ConstructorKnown::Neither,
         ret_layout,
         stores,
-        lhs,
-        rhs,
+        comparison,
         fail,
         cond,
     )
@@ -1499,8 +1768,7 @@ fn compile_test_help<'a>(
     branch_info: ConstructorKnown<'a>,
     ret_layout: Layout<'a>,
     stores: bumpalo::collections::Vec<'a, (Symbol, Layout<'a>, Expr<'a>)>,
-    lhs: Symbol,
-    rhs: Symbol,
+    (lhs, cmp, rhs): Comparison,
     fail: &'a Stmt<'a>,
     mut cond: Stmt<'a>,
 ) -> Stmt<'a> {
@@ -1560,7 +1828,10 @@ fn compile_test_help<'a>(
         default_branch,
     };
 
-    let op = LowLevel::Eq;
+    let op = match cmp {
+        Comparator::Eq => LowLevel::Eq,
+        Comparator::Geq => LowLevel::NumGte,
+    };
     let test = Expr::Call(crate::ir::Call {
         call_type: crate::ir::CallType::LowLevel {
             op,
@@ -1592,13 +1863,14 @@ fn compile_tests<'a>(
     fail: &'a Stmt<'a>,
     mut cond: Stmt<'a>,
 ) -> Stmt<'a> {
-    for (new_stores, lhs, rhs, opt_constructor_info) in tests.into_iter() {
+    for (new_stores, comparison, opt_constructor_info) in tests.into_iter() {
         match opt_constructor_info {
             None => {
-                cond = compile_test(env, ret_layout, new_stores, lhs, rhs, fail, cond);
+                cond = compile_test(env, ret_layout, new_stores, comparison, fail, cond);
             }
             Some(cinfo) => {
-                cond = compile_test_help(env, cinfo, ret_layout, new_stores, lhs, rhs, fail, cond);
+                cond =
+                    compile_test_help(env, cinfo, ret_layout, new_stores, comparison, fail, cond);
             }
         }
     }
@@ -1781,15 +2053,14 @@ fn decide_to_branching<'a>(
             if number_of_tests == 1 {
                 // if there is just one test, compile to a simple if-then-else
 
-                let (new_stores, lhs, rhs, _cinfo) = tests.into_iter().next().unwrap();
+                let (new_stores, comparison, _cinfo) = tests.into_iter().next().unwrap();
 
                 compile_test_help(
                     env,
                     chain_branch_info,
                     ret_layout,
                     new_stores,
-                    lhs,
-                    rhs,
+                    comparison,
                     fail,
                     pass_expr,
                 )
@@ -1856,6 +2127,7 @@ fn decide_to_branching<'a>(
                     Test::IsCtor { tag_id, .. } => tag_id as u64,
                     Test::IsDecimal(_) => unreachable!("decimals cannot be switched on"),
                     Test::IsStr(_) => unreachable!("strings cannot be switched on"),
+                    Test::IsListLen { .. } => unreachable!("list lengths cannot be switched on"),
                 };
 
                 // branch info is only useful for refcounted values
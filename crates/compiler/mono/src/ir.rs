@@ -21,7 +21,7 @@ use roc_debug_flags::{
 };
 use roc_derive::SharedDerivedModule;
 use roc_error_macros::{internal_error, todo_abilities};
-use roc_exhaustive::{Ctor, CtorName, RenderAs, TagId};
+use roc_exhaustive::{Ctor, CtorName, ListArity, RenderAs, TagId};
 use roc_intern::Interner;
 use roc_late_solve::storage::{ExternalModuleStorage, ExternalModuleStorageSnapshot};
 use roc_late_solve::{resolve_ability_specialization, AbilitiesView, Resolved, UnificationFailed};
@@ -1571,6 +1571,14 @@ pub enum Stmt<'a> {
         /// what happens after the expect
         remainder: &'a Stmt<'a>,
     },
+    Dbg {
+        /// the value to print
+        symbol: Symbol,
+        /// the region of the `dbg`ed expression, which identifies it to the renderer
+        region: Region,
+        /// what happens after the dbg
+        remainder: &'a Stmt<'a>,
+    },
     /// a join point `join f <params> = <continuation> in remainder`
     Join {
         id: JoinPointId,
@@ -2170,6 +2178,15 @@ impl<'a> Stmt<'a> {
                 .append(alloc.hardline())
                 .append(remainder.to_doc(alloc, interner)),
 
+            Dbg {
+                symbol, remainder, ..
+            } => alloc
+                .text("dbg ")
+                .append(symbol_to_doc(alloc, *symbol))
+                .append(";")
+                .append(alloc.hardline())
+                .append(remainder.to_doc(alloc, interner)),
+
             Ret(symbol) => alloc
                 .text("ret ")
                 .append(symbol_to_doc(alloc, *symbol))
@@ -2737,7 +2754,11 @@ fn pattern_to_when<'a>(
             (env.unique_symbol(), Loc::at_zero(RuntimeError(error)))
         }
 
-        AppliedTag { .. } | RecordDestructure { .. } | UnwrappedOpaque { .. } => {
+        AppliedTag { .. }
+        | RecordDestructure { .. }
+        | TupleDestructure { .. }
+        | UnwrappedOpaque { .. }
+        | roc_can::pattern::Pattern::List { .. } => {
             let symbol = env.unique_symbol();
 
             let wrapped_body = When {
@@ -4309,9 +4330,154 @@ pub fn with_hole<'a>(
 
         EmptyRecord => let_empty_struct(assigned, hole),
 
+        Tuple {
+            tuple_var, elems, ..
+        } => {
+            let sorted_elems_result = {
+                let mut layout_env = layout::Env::from_components(
+                    layout_cache,
+                    env.subs,
+                    env.arena,
+                    env.target_info,
+                );
+                layout::sort_tuple_elems(&mut layout_env, tuple_var)
+            };
+            let sorted_elems = match sorted_elems_result {
+                Ok(elems) => elems,
+                Err(_) => return Stmt::RuntimeError("Can't create tuple with improper layout"),
+            };
+
+            let mut elems: std::vec::Vec<_> = elems.into_iter().map(Some).collect();
+
+            let mut elem_symbols = Vec::with_capacity_in(elems.len(), env.arena);
+            let mut can_elems = Vec::with_capacity_in(elems.len(), env.arena);
+
+            enum Elem {
+                FunctionOrUnspecialized(Symbol, Variable),
+                ValueSymbol,
+                Expr(Variable, Loc<roc_can::expr::Expr>),
+            }
+
+            for (index, variable, _) in sorted_elems.into_iter() {
+                use ReuseSymbol::*;
+                let (var, loc_expr) = elems[index]
+                    .take()
+                    .expect("tuple element not in its own type");
+
+                match can_reuse_symbol(env, procs, &loc_expr.value, var) {
+                    Imported(symbol) | LocalFunction(symbol) | UnspecializedExpr(symbol) => {
+                        elem_symbols.push(symbol);
+                        can_elems.push(Elem::FunctionOrUnspecialized(symbol, variable));
+                    }
+                    Value(symbol) => {
+                        let reusable = procs.symbol_specializations.get_or_insert(
+                            env,
+                            layout_cache,
+                            symbol,
+                            var,
+                        );
+                        elem_symbols.push(reusable);
+                        can_elems.push(Elem::ValueSymbol);
+                    }
+                    NotASymbol => {
+                        elem_symbols.push(env.unique_symbol());
+                        can_elems.push(Elem::Expr(var, *loc_expr));
+                    }
+                }
+            }
+
+            // creating a tuple from the var will unpack it if it's just a single element.
+            let layout = match layout_cache.from_var(env.arena, tuple_var, env.subs) {
+                Ok(layout) => layout,
+                Err(_) => return Stmt::RuntimeError("Can't create tuple with improper layout"),
+            };
+
+            let elem_symbols = elem_symbols.into_bump_slice();
+
+            let mut stmt = if let [only_elem] = elem_symbols {
+                let mut hole = hole.clone();
+                substitute_in_exprs(env.arena, &mut hole, assigned, *only_elem);
+                hole
+            } else {
+                Stmt::Let(assigned, Expr::Struct(elem_symbols), layout, hole)
+            };
+
+            for (opt_elem, symbol) in can_elems.into_iter().rev().zip(elem_symbols.iter().rev()) {
+                match opt_elem {
+                    Elem::ValueSymbol => {
+                        // this symbol is already defined; nothing to do
+                    }
+                    Elem::FunctionOrUnspecialized(symbol, variable) => {
+                        stmt = specialize_symbol(
+                            env,
+                            procs,
+                            layout_cache,
+                            Some(variable),
+                            symbol,
+                            stmt,
+                            symbol,
+                        );
+                    }
+                    Elem::Expr(var, loc_expr) => {
+                        stmt = with_hole(
+                            env,
+                            loc_expr.value,
+                            var,
+                            procs,
+                            layout_cache,
+                            *symbol,
+                            env.arena.alloc(stmt),
+                        );
+                    }
+                }
+            }
+
+            stmt
+        }
+
         Expect { .. } => unreachable!("I think this is unreachable"),
         ExpectFx { .. } => unreachable!("I think this is unreachable"),
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable: dbg_var,
+        } => {
+            let rest = with_hole(
+                env,
+                loc_continuation.value,
+                variable,
+                procs,
+                layout_cache,
+                assigned,
+                hole,
+            );
+
+            let dbg_symbol = possible_reuse_symbol_or_specialize(
+                env,
+                procs,
+                layout_cache,
+                &loc_message.value,
+                dbg_var,
+            );
+
+            let stmt = Stmt::Dbg {
+                symbol: dbg_symbol,
+                region: loc_message.region,
+                remainder: env.arena.alloc(rest),
+            };
+
+            assign_to_symbol(
+                env,
+                procs,
+                layout_cache,
+                dbg_var,
+                *loc_message,
+                dbg_symbol,
+                stmt,
+            )
+        }
+
         If {
             cond_var,
             branch_var,
@@ -4587,6 +4753,83 @@ pub fn with_hole<'a>(
             assign_to_symbols(env, procs, layout_cache, iter, stmt)
         }
 
+        TupleAccess {
+            tuple_var,
+            elem_var,
+            index: accessed_index,
+            loc_expr,
+            ..
+        } => {
+            let sorted_elems_result = {
+                let mut layout_env = layout::Env::from_components(
+                    layout_cache,
+                    env.subs,
+                    env.arena,
+                    env.target_info,
+                );
+                layout::sort_tuple_elems(&mut layout_env, tuple_var)
+            };
+            let sorted_elems = match sorted_elems_result {
+                Ok(elems) => elems,
+                Err(_) => return Stmt::RuntimeError("Can't access tuple with improper layout"),
+            };
+
+            let mut index = None;
+            let mut elem_layouts = Vec::with_capacity_in(sorted_elems.len(), env.arena);
+
+            for (current, (elem_index, _, elem_layout)) in sorted_elems.into_iter().enumerate() {
+                elem_layouts.push(elem_layout);
+
+                if elem_index == accessed_index {
+                    index = Some(current);
+                }
+            }
+
+            let tuple_symbol = possible_reuse_symbol_or_specialize(
+                env,
+                procs,
+                layout_cache,
+                &loc_expr.value,
+                tuple_var,
+            );
+
+            let mut stmt = match elem_layouts.as_slice() {
+                [_] => {
+                    let mut hole = hole.clone();
+                    substitute_in_exprs(env.arena, &mut hole, assigned, tuple_symbol);
+
+                    hole
+                }
+                _ => {
+                    let expr = Expr::StructAtIndex {
+                        index: index.expect("elem not in its own type") as u64,
+                        field_layouts: elem_layouts.into_bump_slice(),
+                        structure: tuple_symbol,
+                    };
+
+                    let layout = layout_cache
+                        .from_var(env.arena, elem_var, env.subs)
+                        .unwrap_or_else(|err| {
+                            panic!("TODO turn fn_var into a RuntimeError {:?}", err)
+                        });
+
+                    Stmt::Let(assigned, expr, layout, hole)
+                }
+            };
+
+            stmt = assign_to_symbol(
+                env,
+                procs,
+                layout_cache,
+                tuple_var,
+                *loc_expr,
+                tuple_symbol,
+                stmt,
+            );
+
+            stmt
+        }
+
         Access {
             record_var,
             field_var,
@@ -5484,24 +5727,10 @@ fn late_resolve_ability_specialization<'a>(
     if let Some(spec_symbol) = opt_resolved {
         // Fast path: specialization is monomorphic, was found during solving.
         spec_symbol
-    } else if let Content::Structure(FlatType::Func(_, lambda_set, _)) =
-        env.subs.get_content_without_compacting(specialization_var)
-    {
+    } else if let Some(spec_symbol) = specialization_from_lambda_set(env.subs, specialization_var) {
         // Fast path: the member is a function, so the lambda set will tell us the
         // specialization.
-        use roc_types::subs::LambdaSet;
-        let LambdaSet {
-            solved,
-            unspecialized,
-            recursion_var: _,
-            ambient_function: _,
-        } = env.subs.get_lambda_set(*lambda_set);
-
-        debug_assert!(unspecialized.is_empty());
-        let mut iter_lambda_set = solved.iter_all();
-        debug_assert_eq!(iter_lambda_set.len(), 1);
-        let spec_symbol_index = iter_lambda_set.next().unwrap().0;
-        env.subs[spec_symbol_index]
+        spec_symbol
     } else {
         // Otherwise, resolve by checking the able var.
         let specialization = resolve_ability_specialization(
@@ -5519,7 +5748,7 @@ fn late_resolve_ability_specialization<'a>(
                 let derive_key = roc_derive_key::Derived::builtin(
                     member.try_into().expect("derived symbols must be builtins"),
                     env.subs,
-                    var,
+                    default_number_literal(env.subs, var),
                 )
                 .expect("specialization var not derivable!");
 
@@ -5528,6 +5757,10 @@ fn late_resolve_ability_specialization<'a>(
                         // The immediate is an ability member itself, so it must be resolved!
                         late_resolve_ability_specialization(env, imm, None, specialization_var)
                     }
+                    roc_derive_key::Derived::SingleLambdaSetImmediate(imm) => {
+                        // The immediate is a plain function, it needs no further resolution.
+                        imm
+                    }
                     roc_derive_key::Derived::Key(derive_key) => {
                         let mut derived_module = env
                             .derived_module
@@ -5544,6 +5777,46 @@ fn late_resolve_ability_specialization<'a>(
     }
 }
 
+/// If the member is a function, the specialization is the one lambda in its lambda set - unless
+/// solving dropped it, because the type the member is used at (like that of an unsuffixed number
+/// literal) was not known yet.
+fn specialization_from_lambda_set(subs: &Subs, specialization_var: Variable) -> Option<Symbol> {
+    use roc_types::subs::LambdaSet;
+
+    match subs.get_content_without_compacting(specialization_var) {
+        Content::Structure(FlatType::Func(_, lambda_set, _)) => {
+            let LambdaSet {
+                solved,
+                unspecialized,
+                recursion_var: _,
+                ambient_function: _,
+            } = subs.get_lambda_set(*lambda_set);
+
+            debug_assert!(unspecialized.is_empty());
+            let mut iter_lambda_set = solved.iter_all();
+            debug_assert!(iter_lambda_set.len() <= 1);
+            iter_lambda_set
+                .next()
+                .map(|(spec_symbol_index, _)| subs[spec_symbol_index])
+        }
+        _ => None,
+    }
+}
+
+/// A number literal whose type is never determined is compiled at its default width, so the
+/// ability implementations it uses must be derived for that width too.
+fn default_number_literal(subs: &Subs, var: Variable) -> Variable {
+    let num_var = match subs.get_content_without_compacting(var) {
+        Content::Alias(Symbol::NUM_NUM, _, real_var, _) => *real_var,
+        _ => var,
+    };
+
+    match subs.get_content_without_compacting(num_var) {
+        Content::RangedNumber(range) => range.default_compilation_variable(),
+        _ => var,
+    }
+}
+
 fn find_lambda_name<'a, I>(
     env: &mut Env<'a, '_>,
     layout_cache: &mut LayoutCache<'a>,
@@ -6407,6 +6680,37 @@ pub fn from_can<'a>(
             stmt
         }
 
+        Dbg {
+            loc_message,
+            loc_continuation,
+            variable: dbg_var,
+        } => {
+            let rest = from_can(env, variable, loc_continuation.value, procs, layout_cache);
+            let dbg_symbol = possible_reuse_symbol_or_specialize(
+                env,
+                procs,
+                layout_cache,
+                &loc_message.value,
+                dbg_var,
+            );
+
+            let stmt = Stmt::Dbg {
+                symbol: dbg_symbol,
+                region: loc_message.region,
+                remainder: env.arena.alloc(rest),
+            };
+
+            assign_to_symbol(
+                env,
+                procs,
+                layout_cache,
+                dbg_var,
+                *loc_message,
+                dbg_symbol,
+                stmt,
+            )
+        }
+
         LetRec(defs, cont, _cycle_mark) => {
             // because Roc is strict, only functions can be recursive!
             for def in defs.into_iter() {
@@ -6820,6 +7124,23 @@ fn substitute_in_stmt_help<'a>(
             Some(arena.alloc(expect))
         }
 
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            let new_remainder =
+                substitute_in_stmt_help(arena, remainder, subs).unwrap_or(remainder);
+
+            let dbg = Dbg {
+                symbol: substitute(subs, *symbol).unwrap_or(*symbol),
+                region: *region,
+                remainder: new_remainder,
+            };
+
+            Some(arena.alloc(dbg))
+        }
+
         Jump(id, args) => {
             let mut did_change = false;
             let new_args = Vec::from_iter_in(
@@ -7154,6 +7475,25 @@ fn store_pattern_help<'a>(
             return store_pattern_help(env, procs, layout_cache, pattern, outer_symbol, stmt);
         }
 
+        List {
+            arity,
+            element_layout,
+            elements,
+            opt_rest,
+        } => {
+            return store_list_pattern(
+                env,
+                procs,
+                layout_cache,
+                outer_symbol,
+                *arity,
+                *element_layout,
+                elements,
+                *opt_rest,
+                stmt,
+            );
+        }
+
         RecordDestructure(destructs, [_single_field]) => {
             for destruct in destructs {
                 match &destruct.typ {
@@ -7212,6 +7552,45 @@ fn store_pattern_help<'a>(
                 return StorePattern::NotProductive(stmt);
             }
         }
+
+        TupleDestructure(destructs, [_single_elem]) => {
+            // a single-element tuple is represented as just that element
+            return store_pattern_help(
+                env,
+                procs,
+                layout_cache,
+                &destructs[0].pat,
+                outer_symbol,
+                stmt,
+            );
+        }
+        TupleDestructure(destructs, sorted_elems) => {
+            let mut is_productive = false;
+            for (index, destruct) in destructs.iter().enumerate().rev() {
+                match store_tuple_destruct(
+                    env,
+                    procs,
+                    layout_cache,
+                    destruct,
+                    index as u64,
+                    outer_symbol,
+                    sorted_elems,
+                    stmt,
+                ) {
+                    StorePattern::Productive(new) => {
+                        is_productive = true;
+                        stmt = new;
+                    }
+                    StorePattern::NotProductive(new) => {
+                        stmt = new;
+                    }
+                }
+            }
+
+            if !is_productive {
+                return StorePattern::NotProductive(stmt);
+            }
+        }
     }
 
     StorePattern::Productive(stmt)
@@ -7296,6 +7675,226 @@ fn store_tag_pattern<'a>(
     }
 }
 
+/// The index of an element in a list, counted either from the front or from the back of the
+/// list. Elements after a rest pattern `..` are indexed from the back, since their position from
+/// the front depends on the length of the list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ListIndex {
+    FromFront(u64),
+    /// `FromBack(1)` is the last element of the list.
+    FromBack(u64),
+}
+
+impl ListIndex {
+    /// The index of the element that the `index`th element pattern of a list pattern of the
+    /// given arity matches against.
+    pub fn from_pattern_index(index: usize, arity: ListArity) -> Self {
+        match arity {
+            ListArity::Exact(_) => ListIndex::FromFront(index as u64),
+            ListArity::Slice(before, after) => {
+                if index < before {
+                    ListIndex::FromFront(index as u64)
+                } else {
+                    ListIndex::FromBack((before + after - index) as u64)
+                }
+            }
+        }
+    }
+}
+
+/// Builds the statements that compute `index` as a position in `list`, returning the symbol
+/// the position is stored in.
+pub(crate) fn build_list_index_probe<'a>(
+    env: &mut Env<'a, '_>,
+    list: Symbol,
+    index: ListIndex,
+) -> (Symbol, std::vec::Vec<(Symbol, Layout<'a>, Expr<'a>)>) {
+    let usize_layout = Layout::usize(env.target_info);
+    let index_symbol = env.unique_symbol();
+
+    let stores = match index {
+        ListIndex::FromFront(index) => {
+            let index_expr = Expr::Literal(Literal::Int((index as i128).to_ne_bytes()));
+
+            vec![(index_symbol, usize_layout, index_expr)]
+        }
+        ListIndex::FromBack(offset) => {
+            let len_symbol = env.unique_symbol();
+            let len_expr = Expr::Call(Call {
+                call_type: CallType::LowLevel {
+                    op: LowLevel::ListLen,
+                    update_mode: env.next_update_mode_id(),
+                },
+                arguments: env.arena.alloc([list]),
+            });
+
+            let offset_symbol = env.unique_symbol();
+            let offset_expr = Expr::Literal(Literal::Int((offset as i128).to_ne_bytes()));
+
+            let index_expr = Expr::Call(Call {
+                call_type: CallType::LowLevel {
+                    op: LowLevel::NumSubWrap,
+                    update_mode: env.next_update_mode_id(),
+                },
+                arguments: env.arena.alloc([len_symbol, offset_symbol]),
+            });
+
+            vec![
+                (len_symbol, usize_layout, len_expr),
+                (offset_symbol, usize_layout, offset_expr),
+                (index_symbol, usize_layout, index_expr),
+            ]
+        }
+    };
+
+    (index_symbol, stores)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn store_list_pattern<'a>(
+    env: &mut Env<'a, '_>,
+    procs: &mut Procs<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    list: Symbol,
+    arity: ListArity,
+    element_layout: Layout<'a>,
+    elements: &[Pattern<'a>],
+    opt_rest: Option<Symbol>,
+    mut stmt: Stmt<'a>,
+) -> StorePattern<'a> {
+    use Pattern::*;
+
+    let mut is_productive = false;
+
+    if let Some(rest) = opt_rest {
+        // the rest of the list is the sublist between the elements in the front and the back
+        let (before, after) = match arity {
+            ListArity::Slice(before, after) => (before, after),
+            ListArity::Exact(_) => internal_error!("list rest binding in an exact list pattern"),
+        };
+
+        let rest = procs
+            .symbol_specializations
+            .remove_single(rest)
+            .unwrap_or(rest);
+
+        let usize_layout = Layout::usize(env.target_info);
+        let len_symbol = env.unique_symbol();
+        let start_symbol = env.unique_symbol();
+        let skipped_symbol = env.unique_symbol();
+        let rest_len_symbol = env.unique_symbol();
+
+        let rest_expr = Expr::Call(Call {
+            call_type: CallType::LowLevel {
+                op: LowLevel::ListSublist,
+                update_mode: env.next_update_mode_id(),
+            },
+            arguments: env.arena.alloc([list, start_symbol, rest_len_symbol]),
+        });
+        let rest_len_expr = Expr::Call(Call {
+            call_type: CallType::LowLevel {
+                op: LowLevel::NumSubWrap,
+                update_mode: env.next_update_mode_id(),
+            },
+            arguments: env.arena.alloc([len_symbol, skipped_symbol]),
+        });
+        let len_expr = Expr::Call(Call {
+            call_type: CallType::LowLevel {
+                op: LowLevel::ListLen,
+                update_mode: env.next_update_mode_id(),
+            },
+            arguments: env.arena.alloc([list]),
+        });
+
+        let list_layout = Layout::Builtin(Builtin::List(env.arena.alloc(element_layout)));
+
+        stmt = Stmt::Let(rest, rest_expr, list_layout, env.arena.alloc(stmt));
+        stmt = Stmt::Let(
+            rest_len_symbol,
+            rest_len_expr,
+            usize_layout,
+            env.arena.alloc(stmt),
+        );
+        stmt = Stmt::Let(
+            skipped_symbol,
+            Expr::Literal(Literal::Int(((before + after) as i128).to_ne_bytes())),
+            usize_layout,
+            env.arena.alloc(stmt),
+        );
+        stmt = Stmt::Let(
+            start_symbol,
+            Expr::Literal(Literal::Int((before as i128).to_ne_bytes())),
+            usize_layout,
+            env.arena.alloc(stmt),
+        );
+        stmt = Stmt::Let(len_symbol, len_expr, usize_layout, env.arena.alloc(stmt));
+
+        is_productive = true;
+    }
+
+    for (index, element) in elements.iter().enumerate().rev() {
+        let symbol = match element {
+            Identifier(symbol) => procs
+                .symbol_specializations
+                .remove_single(*symbol)
+                .unwrap_or(*symbol),
+            Underscore
+            | IntLiteral(_, _)
+            | FloatLiteral(_, _)
+            | DecimalLiteral(_)
+            | EnumLiteral { .. }
+            | BitLiteral { .. }
+            | StrLiteral(_) => continue,
+            _ => {
+                // store the element in a symbol, and continue matching on it
+                let symbol = env.unique_symbol();
+
+                match store_pattern_help(env, procs, layout_cache, element, symbol, stmt) {
+                    StorePattern::Productive(new) => stmt = new,
+                    StorePattern::NotProductive(new) => {
+                        // only if we bind one of its (sub)fields to a used name should we
+                        // load the element
+                        stmt = new;
+                        continue;
+                    }
+                }
+
+                symbol
+            }
+        };
+
+        let (index_symbol, index_stores) =
+            build_list_index_probe(env, list, ListIndex::from_pattern_index(index, arity));
+
+        let load = Expr::Call(Call {
+            call_type: CallType::LowLevel {
+                op: LowLevel::ListGetUnsafe,
+                update_mode: env.next_update_mode_id(),
+            },
+            arguments: env.arena.alloc([list, index_symbol]),
+        });
+
+        stmt = Stmt::Let(symbol, load, element_layout, env.arena.alloc(stmt));
+
+        for (store_symbol, store_layout, store_expr) in index_stores.into_iter().rev() {
+            stmt = Stmt::Let(
+                store_symbol,
+                store_expr,
+                store_layout,
+                env.arena.alloc(stmt),
+            );
+        }
+
+        is_productive = true;
+    }
+
+    if is_productive {
+        StorePattern::Productive(stmt)
+    } else {
+        StorePattern::NotProductive(stmt)
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn store_newtype_pattern<'a>(
     env: &mut Env<'a, '_>,
@@ -7477,6 +8076,67 @@ fn store_record_destruct<'a>(
     StorePattern::Productive(stmt)
 }
 
+#[allow(clippy::too_many_arguments)]
+fn store_tuple_destruct<'a>(
+    env: &mut Env<'a, '_>,
+    procs: &mut Procs<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    destruct: &TupleDestruct<'a>,
+    index: u64,
+    outer_symbol: Symbol,
+    sorted_elems: &'a [Layout<'a>],
+    mut stmt: Stmt<'a>,
+) -> StorePattern<'a> {
+    use Pattern::*;
+
+    let load = Expr::StructAtIndex {
+        index,
+        field_layouts: sorted_elems,
+        structure: outer_symbol,
+    };
+
+    match &destruct.pat {
+        Identifier(symbol) => {
+            let specialization_symbol = procs
+                .symbol_specializations
+                .remove_single(*symbol)
+                // Can happen when the symbol was never used under this body, and hence has no
+                // requested specialization.
+                .unwrap_or(*symbol);
+
+            stmt = Stmt::Let(
+                specialization_symbol,
+                load,
+                destruct.layout,
+                env.arena.alloc(stmt),
+            );
+        }
+        Underscore
+        | IntLiteral(_, _)
+        | FloatLiteral(_, _)
+        | DecimalLiteral(_)
+        | EnumLiteral { .. }
+        | BitLiteral { .. }
+        | StrLiteral(_) => {
+            return StorePattern::NotProductive(stmt);
+        }
+
+        _ => {
+            let symbol = env.unique_symbol();
+
+            match store_pattern_help(env, procs, layout_cache, &destruct.pat, symbol, stmt) {
+                StorePattern::Productive(new) => {
+                    stmt = new;
+                    stmt = Stmt::Let(symbol, load, destruct.layout, env.arena.alloc(stmt));
+                }
+                StorePattern::NotProductive(stmt) => return StorePattern::NotProductive(stmt),
+            }
+        }
+    }
+
+    StorePattern::Productive(stmt)
+}
+
 /// We want to re-use symbols that are not function symbols
 /// for any other expression, we create a new symbol, and will
 /// later make sure it gets assigned the correct value.
@@ -8753,6 +9413,7 @@ pub enum Pattern<'a> {
     StrLiteral(Box<str>),
 
     RecordDestructure(Vec<'a, RecordDestruct<'a>>, &'a [Layout<'a>]),
+    TupleDestructure(Vec<'a, TupleDestruct<'a>>, &'a [Layout<'a>]),
     NewtypeDestructure {
         tag_name: TagName,
         arguments: Vec<'a, (Pattern<'a>, Layout<'a>)>,
@@ -8771,6 +9432,13 @@ pub enum Pattern<'a> {
         opaque: Symbol,
         argument: Box<(Pattern<'a>, Layout<'a>)>,
     },
+    List {
+        arity: ListArity,
+        element_layout: Layout<'a>,
+        elements: Vec<'a, Pattern<'a>>,
+        /// The symbol the rest of the list is bound to, if any.
+        opt_rest: Option<Symbol>,
+    },
 }
 
 impl<'a> Pattern<'a> {
@@ -8799,6 +9467,9 @@ impl<'a> Pattern<'a> {
                         }
                     }
                 }
+                Pattern::TupleDestructure(destructs, _) => {
+                    stack.extend(destructs.iter().map(|destruct| &destruct.pat))
+                }
                 Pattern::NewtypeDestructure { arguments, .. } => {
                     stack.extend(arguments.iter().map(|(t, _)| t))
                 }
@@ -8807,6 +9478,7 @@ impl<'a> Pattern<'a> {
                     stack.extend(arguments.iter().map(|(t, _)| t))
                 }
                 Pattern::OpaqueUnwrap { argument, .. } => stack.push(&argument.0),
+                Pattern::List { elements, .. } => stack.extend(elements),
             }
         }
 
@@ -8822,6 +9494,14 @@ pub struct RecordDestruct<'a> {
     pub typ: DestructType<'a>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleDestruct<'a> {
+    pub index: usize,
+    pub variable: Variable,
+    pub layout: Layout<'a>,
+    pub pat: Pattern<'a>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum DestructType<'a> {
     Required(Symbol),
@@ -9425,6 +10105,35 @@ fn from_can_pattern_help<'a>(
             })
         }
 
+        List {
+            list_var,
+            elem_var: _,
+            patterns,
+        } => {
+            let list_layout = layout_cache
+                .from_var(env.arena, *list_var, env.subs)
+                .map_err(RuntimeError::from)?;
+
+            let element_layout = match list_layout {
+                Layout::Builtin(Builtin::List(elem_layout)) => *elem_layout,
+                _ => internal_error!("list pattern has a non-list layout: {:?}", list_layout),
+            };
+
+            let mut mono_patterns = Vec::with_capacity_in(patterns.patterns.len(), env.arena);
+            for loc_pat in patterns.patterns.iter() {
+                let mono_pat =
+                    from_can_pattern_help(env, procs, layout_cache, &loc_pat.value, assignments)?;
+                mono_patterns.push(mono_pat);
+            }
+
+            Ok(Pattern::List {
+                arity: patterns.arity(),
+                element_layout,
+                elements: mono_patterns,
+                opt_rest: patterns.rest_symbol().map(|loc_symbol| loc_symbol.value),
+            })
+        }
+
         RecordDestructure {
             whole_var,
             destructs,
@@ -9555,6 +10264,58 @@ fn from_can_pattern_help<'a>(
                 field_layouts.into_bump_slice(),
             ))
         }
+
+        TupleDestructure {
+            whole_var,
+            destructs,
+            ..
+        } => {
+            // sorted elements based on the type
+            let sorted_elems = {
+                let mut layout_env = layout::Env::from_components(
+                    layout_cache,
+                    env.subs,
+                    env.arena,
+                    env.target_info,
+                );
+                crate::layout::sort_tuple_elems(&mut layout_env, *whole_var)
+                    .map_err(RuntimeError::from)?
+            };
+
+            // tuple patterns are closed, so every element of the type is destructured
+            let mut destructs_by_index = BumpMap::with_capacity_in(destructs.len(), env.arena);
+            destructs_by_index.extend(destructs.iter().map(|x| (x.value.destruct_index, x)));
+
+            let mut mono_destructs = Vec::with_capacity_in(sorted_elems.len(), env.arena);
+            let mut elem_layouts = Vec::with_capacity_in(sorted_elems.len(), env.arena);
+
+            for (index, variable, elem_layout) in sorted_elems.into_iter() {
+                let pat = match destructs_by_index.remove(&index) {
+                    Some(destruct) => from_can_pattern_help(
+                        env,
+                        procs,
+                        layout_cache,
+                        &destruct.value.typ.1.value,
+                        assignments,
+                    )?,
+                    None => Pattern::Underscore,
+                };
+
+                mono_destructs.push(TupleDestruct {
+                    index,
+                    variable,
+                    layout: elem_layout,
+                    pat,
+                });
+
+                elem_layouts.push(elem_layout);
+            }
+
+            Ok(Pattern::TupleDestructure(
+                mono_destructs,
+                elem_layouts.into_bump_slice(),
+            ))
+        }
     }
 }
 
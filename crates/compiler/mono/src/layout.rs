@@ -12,10 +12,13 @@ use roc_problem::can::RuntimeError;
 use roc_target::{PtrWidth, TargetInfo};
 use roc_types::num::NumericRange;
 use roc_types::subs::{
-    self, Content, FlatType, GetSubsSlice, Label, OptVariable, RecordFields, Subs, UnionTags,
-    UnsortedUnionLabels, Variable,
+    self, Content, FlatType, GetSubsSlice, Label, OptVariable, RecordFields, Subs, TupleElems,
+    UnionTags, UnsortedUnionLabels, Variable,
+};
+use roc_types::types::{
+    gather_fields_unsorted_iter, gather_tuple_elems_unsorted_iter, RecordField, RecordFieldsError,
+    TupleElemsError,
 };
-use roc_types::types::{gather_fields_unsorted_iter, RecordField, RecordFieldsError};
 use std::cmp::Ordering;
 use std::collections::hash_map::{DefaultHasher, Entry};
 use std::collections::HashMap;
@@ -651,6 +654,17 @@ impl FieldOrderHash {
         fields.iter().for_each(|field| field.hash(&mut hasher));
         Self(hasher.finish())
     }
+
+    pub fn from_ordered_tuple_elems(elems: &[usize]) -> Self {
+        if elems.is_empty() {
+            // HACK: we must make sure this is always equivalent to a `ZERO_FIELD_HASH`.
+            return Self::ZERO_FIELD_HASH;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        elems.iter().for_each(|elem| elem.hash(&mut hasher));
+        Self(hasher.finish())
+    }
 }
 
 /// Types for code gen must be monomorphic. No type variables allowed!
@@ -1955,6 +1969,13 @@ fn lambda_set_size(subs: &Subs, var: Variable) -> (usize, usize, usize) {
                     }
                     stack.push((*ext, depth_any + 1, depth_lset));
                 }
+                FlatType::Tuple(elems, ext) => {
+                    for var_index in elems.iter_variables() {
+                        let var = subs[var_index];
+                        stack.push((var, depth_any + 1, depth_lset));
+                    }
+                    stack.push((*ext, depth_any + 1, depth_lset));
+                }
                 FlatType::FunctionOrTagUnion(_, _, ext) => {
                     stack.push((*ext, depth_any + 1, depth_lset));
                 }
@@ -1975,7 +1996,10 @@ fn lambda_set_size(subs: &Subs, var: Variable) -> (usize, usize, usize) {
                     }
                     stack.push((*ext, depth_any + 1, depth_lset));
                 }
-                FlatType::Erroneous(_) | FlatType::EmptyRecord | FlatType::EmptyTagUnion => {}
+                FlatType::Erroneous(_)
+                | FlatType::EmptyRecord
+                | FlatType::EmptyTuple
+                | FlatType::EmptyTagUnion => {}
             },
             Content::FlexVar(_)
             | Content::RigidVar(_)
@@ -2263,33 +2287,13 @@ impl<'a> Layout<'a> {
         env: &mut Env<'a, '_>,
         range: NumericRange,
     ) -> Cacheable<LayoutResult<'a>> {
-        use roc_types::num::IntLitWidth;
-
         // If we chose the default int layout then the real var might have been `Num *`, or
-        // similar. In this case fix-up width if we need to. Choose I64 if the range says
-        // that the number will fit, otherwise choose the next-largest number layout.
+        // similar. In this case fix-up width if we need to.
         //
         // We don't pass the range down because `RangedNumber`s are somewhat rare, they only
         // appear due to number literals, so no need to increase parameter list sizes.
-        let num_layout = match range {
-            NumericRange::IntAtLeastSigned(w) | NumericRange::NumAtLeastSigned(w) => {
-                [IntLitWidth::I64, IntLitWidth::I128]
-                    .iter()
-                    .find(|candidate| candidate.is_superset(&w, true))
-                    .expect("if number doesn't fit, should have been a type error")
-            }
-            NumericRange::IntAtLeastEitherSign(w) | NumericRange::NumAtLeastEitherSign(w) => [
-                IntLitWidth::I64,
-                IntLitWidth::U64,
-                IntLitWidth::I128,
-                IntLitWidth::U128,
-            ]
-            .iter()
-            .find(|candidate| candidate.is_superset(&w, false))
-            .expect("if number doesn't fit, should have been a type error"),
-        };
         cacheable(Ok(Layout::int_literal_width_to_int(
-            *num_layout,
+            range.default_compilation_width(),
             env.target_info,
         )))
     }
@@ -3145,6 +3149,51 @@ fn layout_from_flat_type<'a>(
 
             Cacheable(result, criteria)
         }
+        Tuple(elems, ext_var) => {
+            let mut criteria = CACHEABLE;
+
+            // extract any values from the ext_var
+            let mut sortables = Vec::with_capacity_in(elems.len(), arena);
+            let it = match elems.unsorted_iterator(subs, ext_var) {
+                Ok(it) => it,
+                Err(TupleElemsError) => return Cacheable(Err(LayoutProblem::Erroneous), criteria),
+            };
+
+            for (index, elem) in it {
+                sortables.push((index, cached!(Layout::from_var(env, elem), criteria)));
+            }
+
+            sortables.sort_by(|(index1, layout1), (index2, layout2)| {
+                cmp_fields(
+                    &env.cache.interner,
+                    index1,
+                    layout1,
+                    index2,
+                    layout2,
+                    target_info,
+                )
+            });
+
+            let ordered_elem_indices =
+                Vec::from_iter_in(sortables.iter().map(|(index, _)| *index), arena);
+            let field_order_hash =
+                FieldOrderHash::from_ordered_tuple_elems(ordered_elem_indices.as_slice());
+
+            let result = if sortables.len() == 1 {
+                // If the tuple has only one element that isn't zero-sized,
+                // unwrap it.
+                Ok(sortables.pop().unwrap().1)
+            } else {
+                let layouts = Vec::from_iter_in(sortables.into_iter().map(|t| t.1), arena);
+
+                Ok(Layout::Struct {
+                    field_order_hash,
+                    field_layouts: layouts.into_bump_slice(),
+                })
+            };
+
+            Cacheable(result, criteria)
+        }
         TagUnion(tags, ext_var) => {
             let (tags, ext_var) = tags.unsorted_tags_and_ext(subs, ext_var);
 
@@ -3173,6 +3222,7 @@ fn layout_from_flat_type<'a>(
         EmptyTagUnion => cacheable(Ok(Layout::VOID)),
         Erroneous(_) => cacheable(Err(LayoutProblem::Erroneous)),
         EmptyRecord => cacheable(Ok(Layout::UNIT)),
+        EmptyTuple => cacheable(Ok(Layout::UNIT)),
     }
 }
 
@@ -3234,6 +3284,47 @@ fn sort_record_fields_help<'a>(
     Ok(sorted_fields)
 }
 
+pub type SortedTupleElem<'a> = (usize, Variable, Layout<'a>);
+
+pub fn sort_tuple_elems<'a>(
+    env: &mut Env<'a, '_>,
+    var: Variable,
+) -> Result<Vec<'a, SortedTupleElem<'a>>, LayoutProblem> {
+    let (it, _) = match gather_tuple_elems_unsorted_iter(env.subs, TupleElems::empty(), var) {
+        Ok(it) => it,
+        Err(_) => return Err(LayoutProblem::Erroneous),
+    };
+
+    sort_tuple_elems_help(env, it)
+}
+
+fn sort_tuple_elems_help<'a>(
+    env: &mut Env<'a, '_>,
+    elems_map: impl Iterator<Item = (usize, Variable)>,
+) -> Result<Vec<'a, SortedTupleElem<'a>>, LayoutProblem> {
+    let target_info = env.target_info;
+
+    let mut sorted_elems = Vec::with_capacity_in(elems_map.size_hint().0, env.arena);
+
+    for (index, elem) in elems_map {
+        let Cacheable(layout, _) = Layout::from_var(env, elem);
+        sorted_elems.push((index, elem, layout?));
+    }
+
+    sorted_elems.sort_by(|(index1, _, layout1), (index2, _, layout2)| {
+        cmp_fields(
+            &env.cache.interner,
+            index1,
+            layout1,
+            index2,
+            layout2,
+            target_info,
+        )
+    });
+
+    Ok(sorted_elems)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TagOrClosure {
     Tag(TagName),
@@ -4057,6 +4148,23 @@ pub fn ext_var_is_empty_record(_subs: &Subs, _ext_var: Variable) -> bool {
     unreachable!();
 }
 
+#[cfg(debug_assertions)]
+pub fn ext_var_is_empty_tuple(subs: &Subs, ext_var: Variable) -> bool {
+    // the ext_var is empty
+    let elems = match roc_types::types::gather_tuple_elems(subs, TupleElems::empty(), ext_var) {
+        Ok(elems) => elems,
+        Err(_) => return false,
+    };
+
+    elems.elems.is_empty()
+}
+
+#[cfg(not(debug_assertions))]
+pub fn ext_var_is_empty_tuple(_subs: &Subs, _ext_var: Variable) -> bool {
+    // This should only ever be used in debug_assert! macros
+    unreachable!();
+}
+
 #[cfg(debug_assertions)]
 pub fn ext_var_is_empty_tag_union(subs: &Subs, ext_var: Variable) -> bool {
     use roc_types::pretty_print::ChasedExt;
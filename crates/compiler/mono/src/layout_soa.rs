@@ -1,4 +1,4 @@
-use crate::layout::{ext_var_is_empty_record, ext_var_is_empty_tag_union};
+use crate::layout::{ext_var_is_empty_record, ext_var_is_empty_tag_union, ext_var_is_empty_tuple};
 use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_collections::all::MutMap;
 use roc_module::symbol::Symbol;
@@ -481,12 +481,11 @@ impl Layouts {
                 let tag_id_align = IntWidth::I64.alignment_bytes(self.target_info) as u16;
 
                 self.align_of_layout_slices(slices).max(tag_id_align)
-            }
-//            Layout::UnionNonNullableUnwrapped(_) => todo!(),
-//            Layout::UnionNullableWrapper { data, tag_id } => todo!(),
-//            Layout::UnionNullableUnwrappedTrue(_) => todo!(),
-//            Layout::UnionNullableUnwrappedFalse(_) => todo!(),
-//            Layout::RecursivePointer => todo!(),
+            } //            Layout::UnionNonNullableUnwrapped(_) => todo!(),
+              //            Layout::UnionNullableWrapper { data, tag_id } => todo!(),
+              //            Layout::UnionNullableUnwrappedTrue(_) => todo!(),
+              //            Layout::UnionNullableUnwrappedFalse(_) => todo!(),
+              //            Layout::RecursivePointer => todo!(),
         }
     }
 
@@ -559,12 +558,11 @@ impl Layouts {
                     .unwrap_or_default();
 
                 tag_id.stack_size() as u16 + max_slice_size
-            }
-//            Layout::UnionNonNullableUnwrapped(_) => todo!(),
-//            Layout::UnionNullableWrapper { data, tag_id } => todo!(),
-//            Layout::UnionNullableUnwrappedTrue(_) => todo!(),
-//            Layout::UnionNullableUnwrappedFalse(_) => todo!(),
-//            Layout::RecursivePointer => todo!(),
+            } //            Layout::UnionNonNullableUnwrapped(_) => todo!(),
+              //            Layout::UnionNullableWrapper { data, tag_id } => todo!(),
+              //            Layout::UnionNullableUnwrappedTrue(_) => todo!(),
+              //            Layout::UnionNullableUnwrappedFalse(_) => todo!(),
+              //            Layout::RecursivePointer => todo!(),
         }
     }
 }
@@ -825,6 +823,23 @@ impl Layout {
 
                 Ok(Layout::Struct(slice))
             }
+            FlatType::Tuple(elems, ext) => {
+                debug_assert!(ext_var_is_empty_tuple(subs, *ext));
+
+                let slice = Slice::reserve(layouts, elems.len());
+
+                let it = slice.indices().zip(elems.iter_variables());
+                for (target_index, var_index) in it {
+                    let var = subs.variables[var_index.index as usize];
+                    let layout = Layout::from_var_help(layouts, subs, var)?;
+
+                    layouts.layouts[target_index] = layout;
+                }
+
+                layouts.sort_slice_by_alignment(slice);
+
+                Ok(Layout::Struct(slice))
+            }
             FlatType::TagUnion(union_tags, ext) => {
                 debug_assert!(ext_var_is_empty_tag_union(subs, *ext));
 
@@ -866,7 +881,7 @@ impl Layout {
                 Ok(Layout::UnionRecursive(slices))
             }
             FlatType::Erroneous(_) => Err(TypeError(())),
-            FlatType::EmptyRecord => Ok(Layout::UNIT),
+            FlatType::EmptyRecord | FlatType::EmptyTuple => Ok(Layout::UNIT),
             FlatType::EmptyTagUnion => Ok(Layout::VOID),
         }
     }
@@ -241,6 +241,27 @@ fn function_s<'a, 'i>(
             }
         }
 
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            let continuation: &Stmt = remainder;
+            let new_continuation = function_s(env, w, c, continuation);
+
+            if std::ptr::eq(continuation, new_continuation) || continuation == new_continuation {
+                stmt
+            } else {
+                let new_dbg = Dbg {
+                    symbol: *symbol,
+                    region: *region,
+                    remainder: new_continuation,
+                };
+
+                arena.alloc(new_dbg)
+            }
+        }
+
         Ret(_) | Jump(_, _) | RuntimeError(_) => stmt,
     }
 }
@@ -504,6 +525,33 @@ fn function_d_main<'a, 'i>(
                 (arena.alloc(refcounting), found)
             }
         }
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            let (b, found) = function_d_main(env, x, c, remainder);
+
+            if found || *symbol != x {
+                let dbg = Dbg {
+                    symbol: *symbol,
+                    region: *region,
+                    remainder: b,
+                };
+
+                (arena.alloc(dbg), found)
+            } else {
+                let b = try_function_s(env, x, c, b);
+
+                let dbg = Dbg {
+                    symbol: *symbol,
+                    region: *region,
+                    remainder: b,
+                };
+
+                (arena.alloc(dbg), found)
+            }
+        }
         Join {
             id,
             parameters,
@@ -696,6 +744,22 @@ fn function_r<'a, 'i>(env: &mut Env<'a, 'i>, stmt: &'a Stmt<'a>) -> &'a Stmt<'a>
             arena.alloc(expect)
         }
 
+        Dbg {
+            symbol,
+            region,
+            remainder,
+        } => {
+            let b = function_r(env, remainder);
+
+            let dbg = Dbg {
+                symbol: *symbol,
+                region: *region,
+                remainder: b,
+            };
+
+            arena.alloc(dbg)
+        }
+
         Ret(_) | Jump(_, _) | RuntimeError(_) => {
             // terminals
             stmt
@@ -736,6 +800,9 @@ fn has_live_var<'a>(jp_live_vars: &JPLiveVarMap, stmt: &'a Stmt<'a>, needle: Sym
             remainder,
             ..
         } => *condition == needle || has_live_var(jp_live_vars, remainder, needle),
+        Dbg {
+            symbol, remainder, ..
+        } => *symbol == needle || has_live_var(jp_live_vars, remainder, needle),
         Join {
             id,
             parameters,
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use crate::header::{AppHeader, HostedHeader, InterfaceHeader, PlatformHeader};
+use crate::header::{AppHeader, HostedHeader, InterfaceHeader, PackageHeader, PlatformHeader};
 use crate::ident::Ident;
 use bumpalo::collections::{String, Vec};
 use bumpalo::Bump;
@@ -84,6 +84,7 @@ impl<'a, T: ExtractSpaces<'a>> ExtractSpaces<'a> for Loc<T> {
 pub enum Module<'a> {
     Interface { header: InterfaceHeader<'a> },
     App { header: AppHeader<'a> },
+    Package { header: PackageHeader<'a> },
     Platform { header: PlatformHeader<'a> },
     Hosted { header: HostedHeader<'a> },
 }
@@ -165,6 +166,8 @@ pub enum Expr<'a> {
     Str(StrLiteral<'a>), // string without escapes in it
     /// Look up exactly one field on a record, e.g. (expr).foo.
     Access(&'a Expr<'a>, &'a str),
+    /// Look up exactly one element on a tuple, e.g. (expr).1
+    TupleAccess(&'a Expr<'a>, &'a str),
     /// e.g. `.foo`
     AccessorFunction(&'a str),
     /// eg 'b'
@@ -173,6 +176,9 @@ pub enum Expr<'a> {
     // Collection Literals
     List(Collection<'a, &'a Loc<Expr<'a>>>),
 
+    /// A tuple of two or more elements, e.g. `(1, "one")`
+    Tuple(Collection<'a, &'a Loc<Expr<'a>>>),
+
     RecordUpdate {
         update: &'a Loc<Expr<'a>>,
         fields: Collection<'a, Loc<AssignedField<'a, Expr<'a>>>>,
@@ -200,6 +206,7 @@ pub enum Expr<'a> {
     Defs(&'a Defs<'a>, &'a Loc<Expr<'a>>),
     Backpassing(&'a [Loc<Pattern<'a>>], &'a Loc<Expr<'a>>, &'a Loc<Expr<'a>>),
     Expect(&'a Loc<Expr<'a>>, &'a Loc<Expr<'a>>),
+    Dbg(&'a Loc<Expr<'a>>, &'a Loc<Expr<'a>>),
 
     // Application
     /// To apply by name, do Apply(Var(...), ...)
@@ -519,6 +526,14 @@ pub enum TypeAnnotation<'a> {
         ext: Option<&'a Loc<TypeAnnotation<'a>>>,
     },
 
+    /// A tuple type, e.g. `(Str, I64)`
+    Tuple {
+        elems: Collection<'a, Loc<TypeAnnotation<'a>>>,
+        /// The row type variable in an open tuple, e.g. the `a` in `(Str, I64)a`.
+        /// This is None if it's a closed tuple annotation like `(Str, I64)`.
+        ext: Option<&'a Loc<TypeAnnotation<'a>>>,
+    },
+
     /// A tag union, e.g. `[
     TagUnion {
         /// The row type variable in an open tag union, e.g. the `a` in `[Foo, Bar]a`.
@@ -641,6 +656,16 @@ pub enum Pattern<'a> {
     /// Can only occur inside of a RecordDestructure
     OptionalField(&'a str, &'a Loc<Expr<'a>>),
 
+    /// A list pattern, e.g. [first, _, ..rest] -> ...
+    List(Collection<'a, Loc<Pattern<'a>>>),
+
+    /// A tuple pattern, e.g. (first, _) -> ...
+    Tuple(Collection<'a, Loc<Pattern<'a>>>),
+
+    /// A rest pattern matching any number of list elements, e.g. `..` or `..rest`
+    /// Can only occur inside of a List
+    ListRest(Option<&'a str>),
+
     // Literal
     NumLiteral(&'a str),
     NonBase10Literal {
@@ -750,6 +775,21 @@ impl<'a> Pattern<'a> {
                 //      { x, y ? False } = rec
                 x == y
             }
+            (List(patterns_x), List(patterns_y)) => {
+                patterns_x.len() == patterns_y.len()
+                    && patterns_x
+                        .iter()
+                        .zip(patterns_y.iter())
+                        .all(|(p, q)| p.value.equivalent(&q.value))
+            }
+            (ListRest(x), ListRest(y)) => x == y,
+            (Tuple(patterns_x), Tuple(patterns_y)) => {
+                patterns_x.len() == patterns_y.len()
+                    && patterns_x
+                        .iter()
+                        .zip(patterns_y.iter())
+                        .all(|(p, q)| p.value.equivalent(&q.value))
+            }
             // Literal
             (NumLiteral(x), NumLiteral(y)) => x == y,
             (
@@ -5,12 +5,13 @@ use crate::ast::{
 use crate::blankspace::{
     space0_after_e, space0_around_ee, space0_before_e, space0_before_optional_after, space0_e,
 };
-use crate::ident::{lowercase_ident, parse_ident, Ident};
+use crate::ident::{lowercase_ident, parse_ident, tuple_index, Ident};
 use crate::keyword;
 use crate::parser::{
-    self, backtrackable, optional, sep_by1, sep_by1_e, specialize, specialize_ref, then,
-    trailing_sep_by0, word1, word2, EExpect, EExpr, EIf, EInParens, ELambda, EList, ENumber,
-    EPattern, ERecord, EString, EType, EWhen, Either, ParseResult, Parser,
+    self, backtrackable, in_parens_or_tuple, optional, sep_by1, sep_by1_e, specialize,
+    specialize_ref, then, trailing_sep_by0, word1, word2, EExpect, EExpr, EIf, EInParens, ELambda,
+    EList, ENumber, EPattern, ERecord, EString, EType, EWhen, Either, InParens, ParseResult,
+    Parser,
 };
 use crate::pattern::{loc_closure_param, loc_has_parser};
 use crate::state::State;
@@ -84,35 +85,45 @@ pub fn expr_help<'a>(min_indent: u32) -> impl Parser<'a, Expr<'a>, EExpr<'a>> {
 }
 
 fn loc_expr_in_parens_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Expr<'a>>, EInParens<'a>> {
-    move |arena, state| {
-        let (_, loc_expr, state) = loc_expr_in_parens_help_help(min_indent).parse(arena, state)?;
+    move |arena, state: State<'a>| {
+        let start = state.pos();
 
-        Ok((
-            MadeProgress,
-            Loc {
+        let (_, contents, state) = in_parens_or_tuple(
+            specialize_ref(EInParens::Expr, move |arena, state: State<'a>| {
+                // `(a, b <- f x ...)` is a multi-backpassing expression in parentheses, but
+                // `(a, b)` is a tuple, which we only find out once the `<-` is missing.
+                match parse_loc_expr(min_indent, arena, state.clone()) {
+                    Ok(good) => Ok(good),
+                    Err(fail) => parse_loc_expr_no_multi_backpassing(min_indent, arena, state)
+                        .map_err(|_| fail),
+                }
+            }),
+            specialize_ref(EInParens::Expr, move |arena, state| {
+                parse_loc_expr_no_multi_backpassing(min_indent, arena, state)
+            }),
+            min_indent,
+            EInParens::Open,
+            EInParens::End,
+            EInParens::IndentOpen,
+            EInParens::IndentEnd,
+            EInParens::End,
+        )
+        .parse(arena, state)?;
+
+        let loc_expr = match contents {
+            InParens::Single(loc_expr) => Loc {
                 region: loc_expr.region,
                 value: Expr::ParensAround(arena.alloc(loc_expr.value)),
             },
-            state,
-        ))
-    }
-}
+            InParens::Tuple(elems) => {
+                let region = Region::new(start, state.pos());
 
-fn loc_expr_in_parens_help_help<'a>(
-    min_indent: u32,
-) -> impl Parser<'a, Loc<Expr<'a>>, EInParens<'a>> {
-    between!(
-        word1(b'(', EInParens::Open),
-        space0_around_ee(
-            specialize_ref(EInParens::Expr, move |arena, state| parse_loc_expr(
-                min_indent, arena, state
-            )),
-            min_indent,
-            EInParens::IndentOpen,
-            EInParens::IndentEnd,
-        ),
-        word1(b')', EInParens::End)
-    )
+                Loc::at(region, Expr::Tuple(elems.ptrify_items(arena)))
+            }
+        };
+
+        Ok((MadeProgress, loc_expr, state))
+    }
 }
 
 fn loc_expr_in_parens_etc_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Expr<'a>>, EExpr<'a>> {
@@ -146,7 +157,7 @@ fn loc_expr_in_parens_etc_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Expr<
                 // Wrap the previous answer in the new one, so we end up
                 // with a nested Expr. That way, `foo.bar.baz` gets represented
                 // in the AST as if it had been written (foo.bar).baz all along.
-                value = Expr::Access(arena.alloc(value), field);
+                value = access_expr(arena.alloc(value), field);
             }
         }
 
@@ -183,10 +194,23 @@ fn record_field_access_chain<'a>() -> impl Parser<'a, Vec<'a, &'a str>, EExpr<'a
 fn record_field_access<'a>() -> impl Parser<'a, &'a str, EExpr<'a>> {
     skip_first!(
         word1(b'.', EExpr::Access),
-        specialize(|_, pos| EExpr::Access(pos), lowercase_ident())
+        specialize(
+            |_, pos| EExpr::Access(pos),
+            one_of!(lowercase_ident(), tuple_index())
+        )
     )
 }
 
+/// Wrap an expression in an access of the given record field or tuple element,
+/// e.g. `.foo` or `.1`
+fn access_expr<'a>(expr: &'a Expr<'a>, field: &'a str) -> Expr<'a> {
+    if field.starts_with(|c: char| c.is_ascii_digit()) {
+        Expr::TupleAccess(expr, field)
+    } else {
+        Expr::Access(expr, field)
+    }
+}
+
 /// In some contexts we want to parse the `_` as an expression, so it can then be turned into a
 /// pattern later
 fn parse_loc_term_or_underscore_or_conditional<'a>(
@@ -355,6 +379,7 @@ fn parse_expr_start<'a>(
             when::expr_help(min_indent, options)
         )),
         loc!(specialize(EExpr::Expect, expect_help(min_indent, options))),
+        loc!(specialize(EExpr::Dbg, dbg_help(min_indent, options))),
         loc!(specialize(EExpr::Lambda, closure_help(min_indent, options))),
         loc!(move |a, s| parse_expr_operator_chain(min_indent, options, start_column, a, s)),
         fail_expr_start_e()
@@ -1854,8 +1879,19 @@ fn expr_to_pattern_help<'a>(arena: &'a Bump, expr: &Expr<'a>) -> Result<Pattern<
             is_negative: *is_negative,
         }),
         // These would not have parsed as patterns
+        Expr::Tuple(elems) => {
+            let patterns = elems.map_items_result(arena, |loc_elem| {
+                let region = loc_elem.region;
+                let value = expr_to_pattern_help(arena, &loc_elem.value)?;
+                Ok(Loc { region, value })
+            })?;
+
+            Ok(Pattern::Tuple(patterns))
+        }
+
         Expr::AccessorFunction(_)
         | Expr::Access(_, _)
+        | Expr::TupleAccess(_, _)
         | Expr::List { .. }
         | Expr::Closure(_, _)
         | Expr::Backpassing(_, _, _)
@@ -1864,6 +1900,7 @@ fn expr_to_pattern_help<'a>(arena: &'a Bump, expr: &Expr<'a>) -> Result<Pattern<
         | Expr::If(_, _)
         | Expr::When(_, _)
         | Expr::Expect(_, _)
+        | Expr::Dbg(_, _)
         | Expr::MalformedClosure
         | Expr::PrecedenceConflict { .. }
         | Expr::RecordUpdate { .. }
@@ -2361,6 +2398,42 @@ fn expect_help<'a>(
     }
 }
 
+fn dbg_help<'a>(
+    min_indent: u32,
+    options: ExprParseOptions,
+) -> impl Parser<'a, Expr<'a>, EExpect<'a>> {
+    move |arena: &'a Bump, state: State<'a>| {
+        let start_column = state.column();
+
+        let (_, _, state) = parser::keyword_e(keyword::DBG, EExpect::Dbg).parse(arena, state)?;
+
+        let (_, condition, state) = space0_before_e(
+            specialize_ref(EExpect::Condition, move |arena, state| {
+                parse_loc_expr_with_options(start_column + 1, options, arena, state)
+            }),
+            start_column + 1,
+            EExpect::IndentCondition,
+        )
+        .parse(arena, state)
+        .map_err(|(_, f, s)| (MadeProgress, f, s))?;
+
+        let parse_cont = specialize_ref(
+            EExpect::Continuation,
+            space0_before_e(
+                move |a, s| parse_loc_expr(min_indent, a, s),
+                min_indent,
+                EExpr::IndentEnd,
+            ),
+        );
+
+        let (_, loc_cont, state) = parse_cont.parse(arena, state)?;
+
+        let expr = Expr::Dbg(arena.alloc(condition), arena.alloc(loc_cont));
+
+        Ok((MadeProgress, expr, state))
+    }
+}
+
 fn if_expr_help<'a>(
     min_indent: u32,
     options: ExprParseOptions,
@@ -2466,13 +2539,13 @@ fn ident_to_expr<'a>(arena: &'a Bump, src: Ident<'a>) -> Expr<'a> {
                 }
             };
 
-            // The remaining items in the iterator are record field accesses,
+            // The remaining items in the iterator are record field or tuple element accesses,
             // e.g. `bar` in `foo.bar.baz`, followed by `baz`
             for field in iter {
                 // Wrap the previous answer in the new one, so we end up
                 // with a nested Expr. That way, `foo.bar.baz` gets represented
                 // in the AST as if it had been written (foo.bar).baz all along.
-                answer = Expr::Access(arena.alloc(answer), field);
+                answer = access_expr(arena.alloc(answer), field);
             }
 
             answer
@@ -2656,7 +2729,7 @@ fn record_literal_help<'a>(min_indent: u32) -> impl Parser<'a, Expr<'a>, EExpr<'
                     // Wrap the previous answer in the new one, so we end up
                     // with a nested Expr. That way, `foo.bar.baz` gets represented
                     // in the AST as if it had been written (foo.bar).baz all along.
-                    value = Expr::Access(arena.alloc(value), field);
+                    value = access_expr(arena.alloc(value), field);
                 }
             }
 
@@ -2,7 +2,8 @@ use crate::ast::{Collection, CommentOrNewline, Defs, Module, Spaced};
 use crate::blankspace::{space0_around_ee, space0_before_e, space0_e};
 use crate::header::{
     package_entry, package_name, AppHeader, ExposedName, HostedHeader, ImportsEntry,
-    InterfaceHeader, ModuleName, PackageEntry, PlatformHeader, PlatformRequires, To, TypedIdent,
+    InterfaceHeader, ModuleName, PackageEntry, PackageHeader, PlatformHeader, PlatformRequires, To,
+    TypedIdent,
 };
 use crate::ident::{self, lowercase_ident, unqualified_ident, uppercase, UppercaseIdent};
 use crate::parser::Progress::{self, *};
@@ -75,6 +76,15 @@ fn header<'a>() -> impl Parser<'a, Module<'a>, EHeader<'a>> {
                         })
                     }
                 ),
+                map!(
+                    skip_first!(keyword_e("package", EHeader::Start), package_header()),
+                    |mut header: PackageHeader<'a>| -> Clos<'a> {
+                        Box::new(|spaces| {
+                            header.before_header = spaces;
+                            Module::Package { header }
+                        })
+                    }
+                ),
                 map!(
                     skip_first!(keyword_e("platform", EHeader::Start), platform_header()),
                     |mut header: PlatformHeader<'a>| -> Clos<'a> {
@@ -295,6 +305,43 @@ fn app_header<'a>() -> impl Parser<'a, AppHeader<'a>, EHeader<'a>> {
     }
 }
 
+#[inline(always)]
+fn package_header<'a>() -> impl Parser<'a, PackageHeader<'a>, EHeader<'a>> {
+    |arena, state| {
+        let min_indent = 1;
+
+        let (_, after_package_keyword, state) =
+            space0_e(min_indent, EHeader::IndentStart).parse(arena, state)?;
+        let (_, name, state) =
+            loc!(specialize(EHeader::PackageName, package_name())).parse(arena, state)?;
+
+        let (_, ((before_exposes, after_exposes), exposes), state) =
+            specialize(EHeader::Exposes, exposes_modules()).parse(arena, state)?;
+
+        let (_, packages, state) = specialize(EHeader::Packages, packages()).parse(arena, state)?;
+
+        let (_, ((before_imports, after_imports), imports), state) =
+            specialize(EHeader::Imports, imports()).parse(arena, state)?;
+
+        let header = PackageHeader {
+            name,
+            exposes,
+            packages: packages.entries,
+            imports,
+            before_header: &[] as &[_],
+            after_package_keyword,
+            before_exposes,
+            after_exposes,
+            before_packages: packages.before_packages_keyword,
+            after_packages: packages.after_packages_keyword,
+            before_imports,
+            after_imports,
+        };
+
+        Ok((MadeProgress, header, state))
+    }
+}
+
 #[inline(always)]
 fn platform_header<'a>() -> impl Parser<'a, PlatformHeader<'a>, EHeader<'a>> {
     |arena, state| {
@@ -107,7 +107,8 @@ impl_space_problem! {
     EWhen<'a>,
     EAbility<'a>,
     PInParens<'a>,
-    PRecord<'a>
+    PRecord<'a>,
+    PList<'a>
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -124,10 +125,32 @@ pub enum EHeader<'a> {
     Start(Position),
     ModuleName(Position),
     AppName(EString<'a>, Position),
+    PackageName(EPackageName<'a>, Position),
     PlatformName(EPackageName<'a>, Position),
     IndentStart(Position),
 }
 
+impl<'a> EHeader<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EHeader::Space(_, pos)
+            | EHeader::Start(pos)
+            | EHeader::ModuleName(pos)
+            | EHeader::IndentStart(pos) => Region::from_pos(*pos),
+            EHeader::Provides(inner, _) => inner.get_region(),
+            EHeader::Exposes(inner, _) => inner.get_region(),
+            EHeader::Imports(inner, _) => inner.get_region(),
+            EHeader::Requires(inner, _) => inner.get_region(),
+            EHeader::Packages(inner, _) => inner.get_region(),
+            EHeader::Generates(inner, _) => inner.get_region(),
+            EHeader::GeneratesWith(inner, _) => inner.get_region(),
+            EHeader::AppName(inner, _) => inner.get_region(),
+            EHeader::PackageName(inner, _) => inner.get_region(),
+            EHeader::PlatformName(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EProvides<'a> {
     Provides(Position),
@@ -145,6 +168,26 @@ pub enum EProvides<'a> {
     Space(BadInputError, Position),
 }
 
+impl<'a> EProvides<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EProvides::Provides(pos)
+            | EProvides::Open(pos)
+            | EProvides::To(pos)
+            | EProvides::IndentProvides(pos)
+            | EProvides::IndentTo(pos)
+            | EProvides::IndentListStart(pos)
+            | EProvides::IndentListEnd(pos)
+            | EProvides::IndentPackage(pos)
+            | EProvides::ListStart(pos)
+            | EProvides::ListEnd(pos)
+            | EProvides::Identifier(pos)
+            | EProvides::Space(_, pos) => Region::from_pos(*pos),
+            EProvides::Package(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EExposes {
     Exposes(Position),
@@ -158,6 +201,22 @@ pub enum EExposes {
     Space(BadInputError, Position),
 }
 
+impl EExposes {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EExposes::Exposes(pos)
+            | EExposes::Open(pos)
+            | EExposes::IndentExposes(pos)
+            | EExposes::IndentListStart(pos)
+            | EExposes::IndentListEnd(pos)
+            | EExposes::ListStart(pos)
+            | EExposes::ListEnd(pos)
+            | EExposes::Identifier(pos)
+            | EExposes::Space(_, pos) => Region::from_pos(*pos),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ERequires<'a> {
     Requires(Position),
@@ -172,6 +231,23 @@ pub enum ERequires<'a> {
     Space(BadInputError, Position),
 }
 
+impl<'a> ERequires<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ERequires::Requires(pos)
+            | ERequires::Open(pos)
+            | ERequires::IndentRequires(pos)
+            | ERequires::IndentListStart(pos)
+            | ERequires::IndentListEnd(pos)
+            | ERequires::ListStart(pos)
+            | ERequires::ListEnd(pos)
+            | ERequires::Rigid(pos)
+            | ERequires::Space(_, pos) => Region::from_pos(*pos),
+            ERequires::TypedIdent(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ETypedIdent<'a> {
     Space(BadInputError, Position),
@@ -183,6 +259,20 @@ pub enum ETypedIdent<'a> {
     Identifier(Position),
 }
 
+impl<'a> ETypedIdent<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ETypedIdent::Space(_, pos)
+            | ETypedIdent::HasType(pos)
+            | ETypedIdent::IndentHasType(pos)
+            | ETypedIdent::Name(pos)
+            | ETypedIdent::IndentType(pos)
+            | ETypedIdent::Identifier(pos) => Region::from_pos(*pos),
+            ETypedIdent::Type(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EPackages<'a> {
     Open(Position),
@@ -196,6 +286,22 @@ pub enum EPackages<'a> {
     PackageEntry(EPackageEntry<'a>, Position),
 }
 
+impl<'a> EPackages<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EPackages::Open(pos)
+            | EPackages::Space(_, pos)
+            | EPackages::Packages(pos)
+            | EPackages::IndentPackages(pos)
+            | EPackages::ListStart(pos)
+            | EPackages::ListEnd(pos)
+            | EPackages::IndentListStart(pos)
+            | EPackages::IndentListEnd(pos) => Region::from_pos(*pos),
+            EPackages::PackageEntry(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EPackageName<'a> {
     BadPath(EString<'a>, Position),
@@ -203,6 +309,15 @@ pub enum EPackageName<'a> {
     Multiline(Position),
 }
 
+impl<'a> EPackageName<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EPackageName::Escapes(pos) | EPackageName::Multiline(pos) => Region::from_pos(*pos),
+            EPackageName::BadPath(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EPackageEntry<'a> {
     BadPackage(EPackageName<'a>, Position),
@@ -212,6 +327,18 @@ pub enum EPackageEntry<'a> {
     Space(BadInputError, Position),
 }
 
+impl<'a> EPackageEntry<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EPackageEntry::Shorthand(pos)
+            | EPackageEntry::Colon(pos)
+            | EPackageEntry::IndentPackage(pos)
+            | EPackageEntry::Space(_, pos) => Region::from_pos(*pos),
+            EPackageEntry::BadPackage(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EImports {
     Open(Position),
@@ -233,6 +360,30 @@ pub enum EImports {
     SetEnd(Position),
 }
 
+impl EImports {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EImports::Open(pos)
+            | EImports::Imports(pos)
+            | EImports::IndentImports(pos)
+            | EImports::IndentListStart(pos)
+            | EImports::IndentListEnd(pos)
+            | EImports::ListStart(pos)
+            | EImports::ListEnd(pos)
+            | EImports::Identifier(pos)
+            | EImports::ExposingDot(pos)
+            | EImports::ShorthandDot(pos)
+            | EImports::Shorthand(pos)
+            | EImports::ModuleName(pos)
+            | EImports::Space(_, pos)
+            | EImports::IndentSetStart(pos)
+            | EImports::IndentSetEnd(pos)
+            | EImports::SetStart(pos)
+            | EImports::SetEnd(pos) => Region::from_pos(*pos),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EGenerates {
     Open(Position),
@@ -244,6 +395,20 @@ pub enum EGenerates {
     IndentTypeEnd(Position),
 }
 
+impl EGenerates {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EGenerates::Open(pos)
+            | EGenerates::Generates(pos)
+            | EGenerates::IndentGenerates(pos)
+            | EGenerates::Identifier(pos)
+            | EGenerates::Space(_, pos)
+            | EGenerates::IndentTypeStart(pos)
+            | EGenerates::IndentTypeEnd(pos) => Region::from_pos(*pos),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EGeneratesWith {
     Open(Position),
@@ -257,6 +422,22 @@ pub enum EGeneratesWith {
     Space(BadInputError, Position),
 }
 
+impl EGeneratesWith {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EGeneratesWith::Open(pos)
+            | EGeneratesWith::With(pos)
+            | EGeneratesWith::IndentWith(pos)
+            | EGeneratesWith::IndentListStart(pos)
+            | EGeneratesWith::IndentListEnd(pos)
+            | EGeneratesWith::ListStart(pos)
+            | EGeneratesWith::ListEnd(pos)
+            | EGeneratesWith::Identifier(pos)
+            | EGeneratesWith::Space(_, pos) => Region::from_pos(*pos),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BadInputError {
     HasTab,
@@ -300,6 +481,28 @@ impl<'a, T> SourceError<'a, T> {
 }
 
 impl<'a> SyntaxError<'a> {
+    /// Where in the source the problem is, if we know
+    pub fn get_region(&self) -> Option<Region> {
+        match self {
+            SyntaxError::Unexpected(region)
+            | SyntaxError::Eof(region)
+            | SyntaxError::ReservedKeyword(region)
+            | SyntaxError::ArgumentsBeforeEquals(region) => Some(*region),
+            SyntaxError::Type(inner) => Some(inner.get_region()),
+            SyntaxError::Pattern(inner) => Some(inner.get_region()),
+            SyntaxError::Expr(inner, _) => Some(inner.get_region()),
+            SyntaxError::Header(inner) => Some(inner.get_region()),
+            SyntaxError::NotEndOfFile(pos) => Some(Region::from_pos(*pos)),
+            SyntaxError::OutdentedTooFar
+            | SyntaxError::TooManyLines
+            | SyntaxError::InvalidPattern
+            | SyntaxError::BadUtf8
+            | SyntaxError::NotYetImplemented(_)
+            | SyntaxError::Todo
+            | SyntaxError::Space(_) => None,
+        }
+    }
+
     pub fn into_source_error(self, state: &State<'a>) -> SourceError<'a, SyntaxError<'a>> {
         SourceError {
             problem: self,
@@ -351,6 +554,7 @@ pub enum EExpr<'a> {
     If(EIf<'a>, Position),
 
     Expect(EExpect<'a>, Position),
+    Dbg(EExpect<'a>, Position),
 
     Lambda(ELambda<'a>, Position),
     Underscore(Position),
@@ -366,6 +570,53 @@ pub enum EExpr<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> EExpr<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EExpr::Start(pos)
+            | EExpr::End(pos)
+            | EExpr::BadExprEnd(pos)
+            | EExpr::Space(_, pos)
+            | EExpr::Dot(pos)
+            | EExpr::Access(pos)
+            | EExpr::UnaryNot(pos)
+            | EExpr::UnaryNegate(pos)
+            | EExpr::BadOperator(_, pos)
+            | EExpr::DefMissingFinalExpr(pos)
+            | EExpr::IndentDefBody(pos)
+            | EExpr::IndentEquals(pos)
+            | EExpr::IndentAnnotation(pos)
+            | EExpr::Equals(pos)
+            | EExpr::Colon(pos)
+            | EExpr::DoubleColon(pos)
+            | EExpr::Ident(pos)
+            | EExpr::MalformedPattern(pos)
+            | EExpr::QualifiedTag(pos)
+            | EExpr::BackpassComma(pos)
+            | EExpr::BackpassArrow(pos)
+            | EExpr::Underscore(pos)
+            | EExpr::Number(_, pos)
+            | EExpr::IndentStart(pos)
+            | EExpr::IndentEnd(pos) => Region::from_pos(*pos),
+            EExpr::DefMissingFinalExpr2(inner, _) => inner.get_region(),
+            EExpr::Type(inner, _) => inner.get_region(),
+            EExpr::Pattern(inner, _) => inner.get_region(),
+            EExpr::Ability(inner, _) => inner.get_region(),
+            EExpr::When(inner, _) => inner.get_region(),
+            EExpr::If(inner, _) => inner.get_region(),
+            EExpr::Expect(inner, _) => inner.get_region(),
+            EExpr::Dbg(inner, _) => inner.get_region(),
+            EExpr::Lambda(inner, _) => inner.get_region(),
+            EExpr::InParens(inner, _) => inner.get_region(),
+            EExpr::Record(inner, _) => inner.get_region(),
+            EExpr::Str(inner, _) => inner.get_region(),
+            EExpr::SingleQuote(inner, _) => inner.get_region(),
+            EExpr::List(inner, _) => inner.get_region(),
+            EExpr::ElmStyleFunction(region, _) => *region,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ENumber {
     End,
@@ -387,6 +638,23 @@ pub enum EString<'a> {
     MultilineInsufficientIndent(Position),
 }
 
+impl<'a> EString<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EString::Open(pos)
+            | EString::CodePtOpen(pos)
+            | EString::CodePtEnd(pos)
+            | EString::Space(_, pos)
+            | EString::EndlessSingle(pos)
+            | EString::EndlessMulti(pos)
+            | EString::UnknownEscape(pos)
+            | EString::FormatEnd(pos)
+            | EString::MultilineInsufficientIndent(pos) => Region::from_pos(*pos),
+            EString::Format(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ERecord<'a> {
     End(Position),
@@ -411,6 +679,28 @@ pub enum ERecord<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> ERecord<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ERecord::End(pos)
+            | ERecord::Open(pos)
+            | ERecord::Updateable(pos)
+            | ERecord::Field(pos)
+            | ERecord::Colon(pos)
+            | ERecord::QuestionMark(pos)
+            | ERecord::Bar(pos)
+            | ERecord::Ampersand(pos)
+            | ERecord::Space(_, pos)
+            | ERecord::IndentOpen(pos)
+            | ERecord::IndentColon(pos)
+            | ERecord::IndentBar(pos)
+            | ERecord::IndentAmpersand(pos)
+            | ERecord::IndentEnd(pos) => Region::from_pos(*pos),
+            ERecord::Expr(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EInParens<'a> {
     End(Position),
@@ -425,6 +715,19 @@ pub enum EInParens<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> EInParens<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EInParens::End(pos)
+            | EInParens::Open(pos)
+            | EInParens::Space(_, pos)
+            | EInParens::IndentOpen(pos)
+            | EInParens::IndentEnd(pos) => Region::from_pos(*pos),
+            EInParens::Expr(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ELambda<'a> {
     Space(BadInputError, Position),
@@ -440,6 +743,23 @@ pub enum ELambda<'a> {
     IndentArg(Position),
 }
 
+impl<'a> ELambda<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ELambda::Space(_, pos)
+            | ELambda::Start(pos)
+            | ELambda::Arrow(pos)
+            | ELambda::Comma(pos)
+            | ELambda::Arg(pos)
+            | ELambda::IndentArrow(pos)
+            | ELambda::IndentBody(pos)
+            | ELambda::IndentArg(pos) => Region::from_pos(*pos),
+            ELambda::Pattern(inner, _) => inner.get_region(),
+            ELambda::Body(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EList<'a> {
     Open(Position),
@@ -452,6 +772,19 @@ pub enum EList<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> EList<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EList::Open(pos)
+            | EList::End(pos)
+            | EList::Space(_, pos)
+            | EList::IndentOpen(pos)
+            | EList::IndentEnd(pos) => Region::from_pos(*pos),
+            EList::Expr(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EWhen<'a> {
     Space(BadInputError, Position),
@@ -476,6 +809,30 @@ pub enum EWhen<'a> {
     PatternAlignment(u32, Position),
 }
 
+impl<'a> EWhen<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EWhen::Space(_, pos)
+            | EWhen::When(pos)
+            | EWhen::Is(pos)
+            | EWhen::Arrow(pos)
+            | EWhen::Bar(pos)
+            | EWhen::IfToken(pos)
+            | EWhen::IndentIs(pos)
+            | EWhen::IndentCondition(pos)
+            | EWhen::IndentPattern(pos)
+            | EWhen::IndentArrow(pos)
+            | EWhen::IndentBranch(pos)
+            | EWhen::IndentIfGuard(pos)
+            | EWhen::PatternAlignment(_, pos) => Region::from_pos(*pos),
+            EWhen::Pattern(inner, _) => inner.get_region(),
+            EWhen::IfGuard(inner, _) => inner.get_region(),
+            EWhen::Condition(inner, _) => inner.get_region(),
+            EWhen::Branch(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EAbility<'a> {
     Space(BadInputError, Position),
@@ -486,6 +843,18 @@ pub enum EAbility<'a> {
     DemandColon(Position),
 }
 
+impl<'a> EAbility<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EAbility::Space(_, pos)
+            | EAbility::DemandAlignment(_, pos)
+            | EAbility::DemandName(pos)
+            | EAbility::DemandColon(pos) => Region::from_pos(*pos),
+            EAbility::Type(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EIf<'a> {
     Space(BadInputError, Position),
@@ -505,18 +874,53 @@ pub enum EIf<'a> {
     IndentElseBranch(Position),
 }
 
+impl<'a> EIf<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EIf::Space(_, pos)
+            | EIf::If(pos)
+            | EIf::Then(pos)
+            | EIf::Else(pos)
+            | EIf::IndentCondition(pos)
+            | EIf::IndentIf(pos)
+            | EIf::IndentThenToken(pos)
+            | EIf::IndentElseToken(pos)
+            | EIf::IndentThenBranch(pos)
+            | EIf::IndentElseBranch(pos) => Region::from_pos(*pos),
+            EIf::Condition(inner, _) => inner.get_region(),
+            EIf::ThenBranch(inner, _) => inner.get_region(),
+            EIf::ElseBranch(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EExpect<'a> {
     Space(BadInputError, Position),
     Expect(Position),
+    Dbg(Position),
     Condition(&'a EExpr<'a>, Position),
     Continuation(&'a EExpr<'a>, Position),
     IndentCondition(Position),
 }
 
+impl<'a> EExpect<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EExpect::Space(_, pos)
+            | EExpect::Expect(pos)
+            | EExpect::Dbg(pos)
+            | EExpect::IndentCondition(pos) => Region::from_pos(*pos),
+            EExpect::Condition(inner, _) => inner.get_region(),
+            EExpect::Continuation(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EPattern<'a> {
     Record(PRecord<'a>, Position),
+    List(PList<'a>, Position),
     Underscore(Position),
 
     Start(Position),
@@ -531,6 +935,24 @@ pub enum EPattern<'a> {
     AsIndentStart(Position),
 }
 
+impl<'a> EPattern<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EPattern::Underscore(pos)
+            | EPattern::Start(pos)
+            | EPattern::End(pos)
+            | EPattern::Space(_, pos)
+            | EPattern::NumLiteral(_, pos)
+            | EPattern::IndentStart(pos)
+            | EPattern::IndentEnd(pos)
+            | EPattern::AsIndentStart(pos) => Region::from_pos(*pos),
+            EPattern::Record(inner, _) => inner.get_region(),
+            EPattern::List(inner, _) => inner.get_region(),
+            EPattern::PInParens(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PRecord<'a> {
     End(Position),
@@ -551,6 +973,52 @@ pub enum PRecord<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> PRecord<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            PRecord::End(pos)
+            | PRecord::Open(pos)
+            | PRecord::Field(pos)
+            | PRecord::Colon(pos)
+            | PRecord::Optional(pos)
+            | PRecord::Space(_, pos)
+            | PRecord::IndentOpen(pos)
+            | PRecord::IndentColon(pos)
+            | PRecord::IndentOptional(pos)
+            | PRecord::IndentEnd(pos) => Region::from_pos(*pos),
+            PRecord::Pattern(inner, _) => inner.get_region(),
+            PRecord::Expr(inner, _) => inner.get_region(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PList<'a> {
+    End(Position),
+    Open(Position),
+
+    Rest(Position),
+    Pattern(&'a EPattern<'a>, Position),
+
+    Space(BadInputError, Position),
+    IndentOpen(Position),
+    IndentEnd(Position),
+}
+
+impl<'a> PList<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            PList::End(pos)
+            | PList::Open(pos)
+            | PList::Rest(pos)
+            | PList::Space(_, pos)
+            | PList::IndentOpen(pos)
+            | PList::IndentEnd(pos) => Region::from_pos(*pos),
+            PList::Pattern(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PInParens<'a> {
     End(Position),
@@ -562,6 +1030,19 @@ pub enum PInParens<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> PInParens<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            PInParens::End(pos)
+            | PInParens::Open(pos)
+            | PInParens::Space(_, pos)
+            | PInParens::IndentOpen(pos)
+            | PInParens::IndentEnd(pos) => Region::from_pos(*pos),
+            PInParens::Pattern(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EType<'a> {
     Space(BadInputError, Position),
@@ -587,6 +1068,31 @@ pub enum EType<'a> {
     TAsIndentStart(Position),
 }
 
+impl<'a> EType<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            EType::Space(_, pos)
+            | EType::TBadTypeVariable(pos)
+            | EType::TWildcard(pos)
+            | EType::TInferred(pos)
+            | EType::TStart(pos)
+            | EType::TEnd(pos)
+            | EType::TFunctionArgument(pos)
+            | EType::TWhereBar(pos)
+            | EType::THasClause(pos)
+            | EType::TIndentStart(pos)
+            | EType::TIndentEnd(pos)
+            | EType::TAsIndentStart(pos) => Region::from_pos(*pos),
+            EType::TRecord(inner, _) => inner.get_region(),
+            EType::TTagUnion(inner, _) => inner.get_region(),
+            EType::TInParens(inner, _) => inner.get_region(),
+            EType::TApply(inner, _) => inner.get_region(),
+            EType::TInlineAlias(inner, _) => inner.get_region(),
+            EType::TAbilityImpl(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ETypeRecord<'a> {
     End(Position),
@@ -605,6 +1111,24 @@ pub enum ETypeRecord<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> ETypeRecord<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ETypeRecord::End(pos)
+            | ETypeRecord::Open(pos)
+            | ETypeRecord::Field(pos)
+            | ETypeRecord::Colon(pos)
+            | ETypeRecord::Optional(pos)
+            | ETypeRecord::Space(_, pos)
+            | ETypeRecord::IndentOpen(pos)
+            | ETypeRecord::IndentColon(pos)
+            | ETypeRecord::IndentOptional(pos)
+            | ETypeRecord::IndentEnd(pos) => Region::from_pos(*pos),
+            ETypeRecord::Type(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ETypeTagUnion<'a> {
     End(Position),
@@ -618,6 +1142,19 @@ pub enum ETypeTagUnion<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> ETypeTagUnion<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ETypeTagUnion::End(pos)
+            | ETypeTagUnion::Open(pos)
+            | ETypeTagUnion::Space(_, pos)
+            | ETypeTagUnion::IndentOpen(pos)
+            | ETypeTagUnion::IndentEnd(pos) => Region::from_pos(*pos),
+            ETypeTagUnion::Type(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ETypeInParens<'a> {
     End(Position),
@@ -632,6 +1169,19 @@ pub enum ETypeInParens<'a> {
     IndentEnd(Position),
 }
 
+impl<'a> ETypeInParens<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ETypeInParens::End(pos)
+            | ETypeInParens::Open(pos)
+            | ETypeInParens::Space(_, pos)
+            | ETypeInParens::IndentOpen(pos)
+            | ETypeInParens::IndentEnd(pos) => Region::from_pos(*pos),
+            ETypeInParens::Type(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ETypeApply {
     ///
@@ -644,6 +1194,19 @@ pub enum ETypeApply {
     StartIsNumber(Position),
 }
 
+impl ETypeApply {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ETypeApply::StartNotUppercase(pos)
+            | ETypeApply::End(pos)
+            | ETypeApply::Space(_, pos)
+            | ETypeApply::DoubleDot(pos)
+            | ETypeApply::TrailingDot(pos)
+            | ETypeApply::StartIsNumber(pos) => Region::from_pos(*pos),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ETypeInlineAlias {
     NotAnAlias(Position),
@@ -651,6 +1214,16 @@ pub enum ETypeInlineAlias {
     ArgumentNotLowercase(Position),
 }
 
+impl ETypeInlineAlias {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ETypeInlineAlias::NotAnAlias(pos)
+            | ETypeInlineAlias::Qualified(pos)
+            | ETypeInlineAlias::ArgumentNotLowercase(pos) => Region::from_pos(*pos),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ETypeAbilityImpl<'a> {
     End(Position),
@@ -676,6 +1249,31 @@ pub enum ETypeAbilityImpl<'a> {
     IndentAmpersand(Position),
 }
 
+impl<'a> ETypeAbilityImpl<'a> {
+    pub fn get_region(&self) -> Region {
+        match self {
+            ETypeAbilityImpl::End(pos)
+            | ETypeAbilityImpl::Open(pos)
+            | ETypeAbilityImpl::Field(pos)
+            | ETypeAbilityImpl::Colon(pos)
+            | ETypeAbilityImpl::Optional(pos)
+            | ETypeAbilityImpl::Space(_, pos)
+            | ETypeAbilityImpl::IndentOpen(pos)
+            | ETypeAbilityImpl::IndentColon(pos)
+            | ETypeAbilityImpl::IndentOptional(pos)
+            | ETypeAbilityImpl::IndentEnd(pos)
+            | ETypeAbilityImpl::Updateable(pos)
+            | ETypeAbilityImpl::QuestionMark(pos)
+            | ETypeAbilityImpl::Bar(pos)
+            | ETypeAbilityImpl::Ampersand(pos)
+            | ETypeAbilityImpl::IndentBar(pos)
+            | ETypeAbilityImpl::IndentAmpersand(pos) => Region::from_pos(*pos),
+            ETypeAbilityImpl::Type(inner, _) => inner.get_region(),
+            ETypeAbilityImpl::Expr(inner, _) => inner.get_region(),
+        }
+    }
+}
+
 impl<'a> From<ERecord<'a>> for ETypeAbilityImpl<'a> {
     fn from(e: ERecord<'a>) -> Self {
         match e {
@@ -1313,6 +1911,110 @@ macro_rules! collection_trailing_sep_e {
     };
 }
 
+/// The contents of a pair of parentheses: either a single item, e.g. `(a)`,
+/// or a tuple of several items, e.g. `(a, b)`.
+pub enum InParens<'a, T> {
+    Single(Loc<T>),
+    Tuple(crate::ast::Collection<'a, Loc<T>>),
+}
+
+/// Parse an item in parentheses, or a tuple of items. Until a `,` follows the first item, this
+/// parses exactly like a single item in parentheses, so unfinished parentheses are reported the
+/// same way they were before tuples existed. The first item gets its own parser so that callers
+/// can accept things there (like multi-backpassing) that are ambiguous in later tuple elements.
+#[allow(clippy::too_many_arguments)]
+pub fn in_parens_or_tuple<'a, T, E, P1, P2>(
+    first: P1,
+    elem: P2,
+    min_indent: u32,
+    open_problem: fn(Position) -> E,
+    end_problem: fn(Position) -> E,
+    indent_open_problem: fn(Position) -> E,
+    indent_end_problem: fn(Position) -> E,
+    close_problem: fn(Position) -> E,
+) -> impl Parser<'a, InParens<'a, T>, E>
+where
+    T: crate::ast::Spaceable<'a> + 'a,
+    E: SpaceProblem + 'a,
+    P1: Parser<'a, Loc<T>, E> + 'a,
+    P2: Parser<'a, Loc<T>, E> + 'a,
+{
+    let first = crate::blankspace::space0_around_ee(
+        first,
+        min_indent,
+        indent_open_problem,
+        indent_end_problem,
+    );
+
+    let rest = trailing_sep_by0(
+        word1(b',', end_problem),
+        crate::blankspace::space0_before_optional_after(
+            elem,
+            min_indent,
+            indent_end_problem,
+            indent_end_problem,
+        ),
+    );
+
+    // we use min_indent=0 because we want to parse incorrectly indented closing parens
+    // and later fix these up in the formatter.
+    let final_spaces = crate::blankspace::space0_e(0, indent_end_problem);
+
+    move |arena, state: State<'a>| {
+        let original_state = state.clone();
+
+        let (_, (), state) = word1(b'(', open_problem).parse(arena, state)?;
+
+        let parse_contents = |state: State<'a>| {
+            let (_, first, state) = first.parse(arena, state)?;
+
+            if !state.bytes().starts_with(b",") {
+                let (_, (), state) = word1(b')', close_problem).parse(arena, state)?;
+
+                return Ok((InParens::Single(first), state));
+            }
+
+            let (_, mut elems, state) = rest.parse(arena, state.advance(1))?;
+            let end_pos = state.pos();
+            let (_, final_comments, state) = final_spaces.parse(arena, state)?;
+
+            // report a missing `)` right after the last element, rather than after any
+            // whitespace that follows it
+            let (_, (), state) = word1(b')', close_problem)
+                .parse(arena, state)
+                .map_err(|(progress, _, state)| (progress, close_problem(end_pos), state))?;
+
+            if elems.is_empty() {
+                // a trailing comma after a single item, e.g. `(a,)`
+                let first = if final_comments.is_empty() {
+                    first
+                } else {
+                    arena
+                        .alloc(first.value)
+                        .with_spaces_after(final_comments, first.region)
+                };
+
+                return Ok((InParens::Single(first), state));
+            }
+
+            elems.insert(0, first);
+
+            let collection = crate::ast::Collection::with_items_and_comments(
+                arena,
+                elems.into_bump_slice(),
+                final_comments,
+            );
+
+            Ok((InParens::Tuple(collection), state))
+        };
+
+        match parse_contents(state) {
+            Ok((contents, state)) => Ok((MadeProgress, contents, state)),
+            Err((_, fail, _)) => Err((MadeProgress, fail, original_state)),
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! succeed {
     ($value:expr) => {
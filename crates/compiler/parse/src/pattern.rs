@@ -1,10 +1,10 @@
 use crate::ast::{Has, Pattern};
-use crate::blankspace::{space0_around_ee, space0_before_e, space0_e};
+use crate::blankspace::{space0_before_e, space0_e};
 use crate::ident::{lowercase_ident, parse_ident, Ident};
 use crate::parser::Progress::{self, *};
 use crate::parser::{
-    backtrackable, optional, specialize, specialize_ref, then, word1, EPattern, PInParens, PRecord,
-    ParseResult, Parser,
+    backtrackable, in_parens_or_tuple, optional, specialize, specialize_ref, then, word1, word2,
+    EPattern, InParens, PInParens, PList, PRecord, ParseResult, Parser,
 };
 use crate::state::State;
 use bumpalo::collections::string::String;
@@ -43,6 +43,8 @@ fn parse_closure_param<'a>(
             EPattern::Record,
             crate::pattern::record_pattern_help(min_indent)
         )),
+        // You can also destructure lists, e.g. \[first, ..] -> ...
+        loc!(specialize(EPattern::List, list_pattern_help(min_indent))),
         // If you wrap it in parens, you can match any arbitrary pattern at all.
         // e.g. \User.UserId userId -> ...
         specialize(EPattern::PInParens, loc_pattern_in_parens_help(min_indent))
@@ -59,6 +61,7 @@ pub fn loc_pattern_help<'a>(min_indent: u32) -> impl Parser<'a, Loc<Pattern<'a>>
             EPattern::Record,
             crate::pattern::record_pattern_help(min_indent)
         )),
+        loc!(specialize(EPattern::List, list_pattern_help(min_indent))),
         loc!(number_pattern_help()),
         loc!(string_pattern_help()),
         loc!(single_quote_pattern_help()),
@@ -139,6 +142,7 @@ fn loc_parse_tag_pattern_arg<'a>(
             EPattern::Record,
             crate::pattern::record_pattern_help(min_indent)
         )),
+        loc!(specialize(EPattern::List, list_pattern_help(min_indent))),
         loc!(string_pattern_help()),
         loc!(single_quote_pattern_help()),
         loc!(number_pattern_help())
@@ -149,19 +153,80 @@ fn loc_parse_tag_pattern_arg<'a>(
 fn loc_pattern_in_parens_help<'a>(
     min_indent: u32,
 ) -> impl Parser<'a, Loc<Pattern<'a>>, PInParens<'a>> {
-    between!(
-        word1(b'(', PInParens::Open),
-        space0_around_ee(
-            move |arena, state| specialize_ref(PInParens::Pattern, loc_pattern_help(min_indent))
-                .parse(arena, state),
+    move |arena, state: State<'a>| {
+        let start = state.pos();
+
+        let (_, contents, state) = in_parens_or_tuple(
+            specialize_ref(PInParens::Pattern, loc_pattern_help(min_indent)),
+            specialize_ref(PInParens::Pattern, loc_pattern_help(min_indent)),
             min_indent,
+            PInParens::Open,
+            PInParens::End,
             PInParens::IndentOpen,
             PInParens::IndentEnd,
-        ),
-        word1(b')', PInParens::End)
+            PInParens::End,
+        )
+        .parse(arena, state)?;
+
+        let loc_pattern = match contents {
+            InParens::Single(loc_pattern) => loc_pattern,
+            InParens::Tuple(patterns) => {
+                let region = Region::new(start, state.pos());
+
+                Loc::at(region, Pattern::Tuple(patterns))
+            }
+        };
+
+        Ok((MadeProgress, loc_pattern, state))
+    }
+}
+
+fn list_pattern_help<'a>(min_indent: u32) -> impl Parser<'a, Pattern<'a>, PList<'a>> {
+    move |arena, state| {
+        let (_, patterns, state) = collection_trailing_sep_e!(
+            word1(b'[', PList::Open),
+            list_element_pattern(min_indent),
+            word1(b',', PList::End),
+            word1(b']', PList::End),
+            min_indent,
+            PList::Open,
+            PList::IndentEnd,
+            Pattern::SpaceBefore
+        )
+        .parse(arena, state)?;
+
+        Ok((MadeProgress, Pattern::List(patterns), state))
+    }
+}
+
+fn list_element_pattern<'a>(min_indent: u32) -> impl Parser<'a, Loc<Pattern<'a>>, PList<'a>> {
+    one_of!(
+        list_rest_pattern(),
+        specialize_ref(PList::Pattern, loc_pattern_help(min_indent)),
     )
 }
 
+fn list_rest_pattern<'a>() -> impl Parser<'a, Loc<Pattern<'a>>, PList<'a>> {
+    move |arena: &'a Bump, state: State<'a>| {
+        let start = state.pos();
+        let (_, _, state) = word2(b'.', b'.', PList::Rest).parse(arena, state)?;
+
+        // The rest of the list can be bound to a name, e.g. `..rest`
+        let pos = state.pos();
+        let (_, opt_name, state) =
+            optional(specialize(move |_, _| PList::Rest(pos), lowercase_ident()))
+                .parse(arena, state)?;
+
+        let region = Region::new(start, state.pos());
+
+        Ok((
+            MadeProgress,
+            Loc::at(region, Pattern::ListRest(opt_name)),
+            state,
+        ))
+    }
+}
+
 fn number_pattern_help<'a>() -> impl Parser<'a, Pattern<'a>, EPattern<'a>> {
     specialize(
         EPattern::NumLiteral,
@@ -7,8 +7,9 @@ use crate::expr::record_value_field;
 use crate::ident::lowercase_ident;
 use crate::keyword;
 use crate::parser::{
-    allocated, backtrackable, optional, specialize, specialize_ref, word1, word2, word3, EType,
-    ETypeApply, ETypeInParens, ETypeInlineAlias, ETypeRecord, ETypeTagUnion, ParseResult, Parser,
+    allocated, backtrackable, in_parens_or_tuple, optional, specialize, specialize_ref, word1,
+    word2, word3, EType, ETypeApply, ETypeInParens, ETypeInlineAlias, ETypeRecord, ETypeTagUnion,
+    InParens, ParseResult, Parser,
     Progress::{self, *},
 };
 use crate::parser::{then, ERecord, ETypeAbilityImpl};
@@ -123,7 +124,10 @@ fn term<'a>(
             one_of!(
                 loc_wildcard(),
                 loc_inferred(),
-                specialize(EType::TInParens, loc_type_in_parens(min_indent)),
+                specialize(
+                    EType::TInParens,
+                    loc_type_in_parens(min_indent, stop_at_surface_has)
+                ),
                 loc!(specialize(
                     EType::TRecord,
                     record_type(min_indent, stop_at_surface_has)
@@ -196,7 +200,10 @@ fn loc_applied_arg<'a>(
             one_of!(
                 loc_wildcard(),
                 loc_inferred(),
-                specialize(EType::TInParens, loc_type_in_parens(min_indent)),
+                specialize(
+                    EType::TInParens,
+                    loc_type_in_parens(min_indent, stop_at_surface_has)
+                ),
                 loc!(specialize(
                     EType::TRecord,
                     record_type(min_indent, stop_at_surface_has)
@@ -222,21 +229,43 @@ fn loc_applied_arg<'a>(
 
 fn loc_type_in_parens<'a>(
     min_indent: u32,
+    stop_at_surface_has: bool,
 ) -> impl Parser<'a, Loc<TypeAnnotation<'a>>, ETypeInParens<'a>> {
-    between!(
-        word1(b'(', ETypeInParens::Open),
-        space0_around_ee(
-            move |arena, state| specialize_ref(
-                ETypeInParens::Type,
-                expression(min_indent, true, false)
-            )
-            .parse(arena, state),
+    move |arena, state: State<'a>| {
+        let start = state.pos();
+
+        let (_, contents, state) = in_parens_or_tuple(
+            specialize_ref(ETypeInParens::Type, expression(min_indent, true, false)),
+            specialize_ref(ETypeInParens::Type, expression(min_indent, true, false)),
             min_indent,
+            ETypeInParens::Open,
+            ETypeInParens::End,
             ETypeInParens::IndentOpen,
             ETypeInParens::IndentEnd,
-        ),
-        word1(b')', ETypeInParens::IndentEnd)
-    )
+            ETypeInParens::IndentEnd,
+        )
+        .parse(arena, state)?;
+
+        match contents {
+            InParens::Single(loc_annotation) => Ok((MadeProgress, loc_annotation, state)),
+            InParens::Tuple(elems) => {
+                // This could be an open tuple, e.g. `(Str, I64)a`
+                let (_, ext, state) = optional(allocated(specialize_ref(
+                    ETypeInParens::Type,
+                    term(min_indent, stop_at_surface_has),
+                )))
+                .parse(arena, state)?;
+
+                let region = Region::new(start, state.pos());
+
+                Ok((
+                    MadeProgress,
+                    Loc::at(region, TypeAnnotation::Tuple { elems, ext }),
+                    state,
+                ))
+            }
+        }
+    }
 }
 
 #[inline(always)]
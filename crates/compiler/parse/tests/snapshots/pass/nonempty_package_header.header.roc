@@ -0,0 +1,4 @@
+package "acme/shout"
+    exposes [Shout]
+    packages { json: "json/main.roc" }
+    imports [Json]
@@ -0,0 +1 @@
+pair.1 + (1, 2).0
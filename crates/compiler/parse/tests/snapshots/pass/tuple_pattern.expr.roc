@@ -0,0 +1,3 @@
+when pair is
+    (Ok a, _) -> a
+    (_, b) -> b
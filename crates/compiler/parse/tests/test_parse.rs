@@ -147,6 +147,7 @@ mod test_parse {
         pass/comment_before_op.expr,
         pass/comment_inside_empty_list.expr,
         pass/comment_with_non_ascii.expr,
+        pass/dbg.expr,
         pass/destructure_tag_assignment.expr,
         pass/empty_app_header.header,
         pass/empty_hosted_header.header,
@@ -170,6 +171,7 @@ mod test_parse {
         pass/list_closing_indent_not_enough.expr,
         pass/list_closing_same_indent_no_trailing_comma.expr,
         pass/list_closing_same_indent_with_trailing_comma.expr,
+        pass/list_patterns.expr,
         pass/lowest_float.expr,
         pass/lowest_int.expr,
         pass/malformed_ident_due_to_underscore.expr,
@@ -200,6 +202,7 @@ mod test_parse {
         pass/newline_inside_empty_list.expr,
         pass/newline_singleton_list.expr,
         pass/nonempty_hosted_header.header,
+        pass/nonempty_package_header.header,
         pass/nonempty_platform_header.header,
         pass/not_docs.expr,
         pass/number_literal_suffixes.expr,
@@ -258,6 +261,10 @@ mod test_parse {
         pass/tag_pattern.expr,
         pass/ten_times_eleven.expr,
         pass/three_arg_closure.expr,
+        pass/tuple_access.expr,
+        pass/tuple_expr.expr,
+        pass/tuple_pattern.expr,
+        pass/tuple_type.expr,
         pass/two_arg_closure.expr,
         pass/two_backpassing.expr,
         pass/two_branch_when.expr,
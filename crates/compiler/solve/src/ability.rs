@@ -2,11 +2,9 @@ use roc_can::abilities::AbilitiesStore;
 use roc_can::expr::PendingDerives;
 use roc_collections::{VecMap, VecSet};
 use roc_error_macros::{internal_error, todo_abilities};
-use roc_module::symbol::Symbol;
+use roc_module::symbol::{Symbol, IMPLIED_ABILITIES};
 use roc_region::all::{Loc, Region};
-use roc_solve_problem::{
-    NotDerivableContext, NotDerivableDecode, TypeError, UnderivableReason, Unfulfilled,
-};
+use roc_solve_problem::{NotDerivableContext, TypeError, UnderivableReason, Unfulfilled};
 use roc_types::num::NumericRange;
 use roc_types::subs::{
     instantiate_rigids, Content, FlatType, GetSubsSlice, Rank, RecordFields, Subs, Variable,
@@ -272,6 +270,12 @@ impl ObligationCache {
                 var,
             )),
 
+            Symbol::HASH_HASH_ABILITY => {
+                Some(DeriveHash::is_derivable(self, abilities_store, subs, var))
+            }
+
+            Symbol::BOOL_EQ => Some(DeriveEq::is_derivable(self, abilities_store, subs, var)),
+
             _ => None,
         };
 
@@ -334,15 +338,21 @@ impl ObligationCache {
         }
 
         let ImplKey { opaque, ability } = impl_key;
-        let has_declared_impl = abilities_store.has_declared_implementation(opaque, ability);
 
-        let obligation_result = if !has_declared_impl {
-            Err(Unfulfilled::OpaqueDoesNotImplement {
+        // An opaque implementing an ability must also implement all abilities it implies.
+        let missing_ability = IMPLIED_ABILITIES
+            .iter()
+            .filter(|(implying, _)| *implying == ability)
+            .map(|(_, implied)| *implied)
+            .chain(std::iter::once(ability))
+            .find(|&ability| !abilities_store.has_declared_implementation(opaque, ability));
+
+        let obligation_result = match missing_ability {
+            Some(ability) => Err(Unfulfilled::OpaqueDoesNotImplement {
                 typ: opaque,
                 ability,
-            })
-        } else {
-            Ok(())
+            }),
+            None => Ok(()),
         };
 
         self.impl_cache.insert(impl_key, obligation_result);
@@ -415,8 +425,13 @@ impl ObligationCache {
 }
 
 #[inline(always)]
-#[rustfmt::skip]
 fn is_builtin_number_alias(symbol: Symbol) -> bool {
+    is_builtin_int_alias(symbol) || is_builtin_float_alias(symbol)
+}
+
+#[inline(always)]
+#[rustfmt::skip]
+fn is_builtin_int_alias(symbol: Symbol) -> bool {
     matches!(symbol,
           Symbol::NUM_U8   | Symbol::NUM_UNSIGNED8
         | Symbol::NUM_U16  | Symbol::NUM_UNSIGNED16
@@ -428,8 +443,15 @@ fn is_builtin_number_alias(symbol: Symbol) -> bool {
         | Symbol::NUM_I32  | Symbol::NUM_SIGNED32
         | Symbol::NUM_I64  | Symbol::NUM_SIGNED64
         | Symbol::NUM_I128 | Symbol::NUM_SIGNED128
-        | Symbol::NUM_NAT  | Symbol::NUM_NATURAL
-        | Symbol::NUM_F32  | Symbol::NUM_BINARY32
+        | Symbol::NUM_NAT  | Symbol::NUM_NATURAL,
+    )
+}
+
+#[inline(always)]
+#[rustfmt::skip]
+fn is_builtin_float_alias(symbol: Symbol) -> bool {
+    matches!(symbol,
+          Symbol::NUM_F32  | Symbol::NUM_BINARY32
         | Symbol::NUM_F64  | Symbol::NUM_BINARY64
         | Symbol::NUM_DEC  | Symbol::NUM_DECIMAL,
     )
@@ -450,16 +472,11 @@ trait DerivableVisitor {
         false
     }
 
+    /// Whether the ability is derivable for all numbers, so that the precision of a number type
+    /// need not be inspected.
     #[inline(always)]
-    fn visit_flex_able(var: Variable, ability: Symbol) -> Result<(), NotDerivable> {
-        if ability != Self::ABILITY {
-            Err(NotDerivable {
-                var,
-                context: NotDerivableContext::NoContext,
-            })
-        } else {
-            Ok(())
-        }
+    fn is_derivable_for_all_numbers() -> bool {
+        false
     }
 
     #[inline(always)]
@@ -594,7 +611,8 @@ trait DerivableVisitor {
             match *content {
                 FlexVar(opt_name) => {
                     // Promote the flex var to be bound to the ability.
-                    subs.set_content(var, Content::FlexAbleVar(opt_name, Self::ABILITY));
+                    let abilities = subs.new_ability_set([Self::ABILITY]);
+                    subs.set_content(var, Content::FlexAbleVar(opt_name, abilities));
                 }
                 RigidVar(_) => {
                     return Err(NotDerivable {
@@ -602,7 +620,12 @@ trait DerivableVisitor {
                         context: NotDerivableContext::NoContext,
                     })
                 }
-                FlexAbleVar(_, ability) => Self::visit_flex_able(var, ability)?,
+                FlexAbleVar(opt_name, abilities) => {
+                    // Promote the flex able var to also be bound to the ability.
+                    let ability = subs.new_ability_set([Self::ABILITY]);
+                    let abilities = subs.merge_abilities(abilities, ability);
+                    subs.set_content(var, Content::FlexAbleVar(opt_name, abilities));
+                }
                 RigidAbleVar(_, ability) => Self::visit_rigid_able(var, ability)?,
                 RecursionVar {
                     structure,
@@ -671,7 +694,8 @@ trait DerivableVisitor {
                     EmptyRecord => Self::visit_empty_record(var)?,
                     EmptyTagUnion => Self::visit_empty_tag_union(var)?,
 
-                    Erroneous(_) => {
+                    // Abilities can't be derived for tuples yet.
+                    Tuple(..) | EmptyTuple | Erroneous(_) => {
                         return Err(NotDerivable {
                             var,
                             context: NotDerivableContext::NoContext,
@@ -684,8 +708,10 @@ trait DerivableVisitor {
                     real_var,
                     AliasKind::Opaque,
                 ) => {
-                    // Numbers: always decay until a ground is hit.
-                    stack.push(real_var);
+                    // Numbers: always decay until a ground is hit, unless any number will do.
+                    if !Self::is_derivable_for_all_numbers() {
+                        stack.push(real_var);
+                    }
                 }
                 Alias(opaque, _alias_variables, _real_var, AliasKind::Opaque) => {
                     if obligation_cache
@@ -810,7 +836,7 @@ impl DerivableVisitor for DeriveDecoding {
 
     #[inline(always)]
     fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
-        is_builtin_number_alias(symbol)
+        is_builtin_number_alias(symbol) || symbol == Symbol::BOOL_BOOL
     }
 
     #[inline(always)]
@@ -835,21 +861,186 @@ impl DerivableVisitor for DeriveDecoding {
 
     #[inline(always)]
     fn visit_record(
-        subs: &Subs,
-        var: Variable,
-        fields: RecordFields,
+        _subs: &Subs,
+        _var: Variable,
+        _fields: RecordFields,
     ) -> Result<Descend, NotDerivable> {
-        for (field_name, _, field) in fields.iter_all() {
-            if subs[field].is_optional() {
-                return Err(NotDerivable {
-                    var,
-                    context: NotDerivableContext::Decode(NotDerivableDecode::OptionalRecordField(
-                        subs[field_name].clone(),
-                    )),
-                });
-            }
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tag_union(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_recursive_tag_union(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_function_or_tag_union(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_empty_record(_var: Variable) -> Result<(), NotDerivable> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tag_union(_var: Variable) -> Result<(), NotDerivable> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_alias(_var: Variable, symbol: Symbol) -> Result<Descend, NotDerivable> {
+        if is_builtin_number_alias(symbol) {
+            Ok(Descend(false))
+        } else {
+            Ok(Descend(true))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_ranged_number(_var: Variable, _range: NumericRange) -> Result<(), NotDerivable> {
+        Ok(())
+    }
+}
+
+struct DeriveHash;
+impl DerivableVisitor for DeriveHash {
+    const ABILITY: Symbol = Symbol::HASH_HASH_ABILITY;
+
+    #[inline(always)]
+    fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
+        is_builtin_int_alias(symbol) || symbol == Symbol::BOOL_BOOL
+    }
+
+    #[inline(always)]
+    fn visit_recursion(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_apply(var: Variable, symbol: Symbol) -> Result<Descend, NotDerivable> {
+        if matches!(symbol, Symbol::LIST_LIST | Symbol::STR_STR) {
+            Ok(Descend(true))
+        } else {
+            Err(NotDerivable {
+                var,
+                context: NotDerivableContext::NoContext,
+            })
+        }
+    }
+
+    #[inline(always)]
+    fn visit_record(
+        _subs: &Subs,
+        _var: Variable,
+        _fields: RecordFields,
+    ) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tag_union(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_recursive_tag_union(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_function_or_tag_union(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_empty_record(_var: Variable) -> Result<(), NotDerivable> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tag_union(_var: Variable) -> Result<(), NotDerivable> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_alias(var: Variable, symbol: Symbol) -> Result<Descend, NotDerivable> {
+        if is_builtin_float_alias(symbol) {
+            // Floats can't be hashed, since they are not equal to themselves in general.
+            Err(NotDerivable {
+                var,
+                context: NotDerivableContext::NoContext,
+            })
+        } else if is_builtin_int_alias(symbol) {
+            Ok(Descend(false))
+        } else {
+            Ok(Descend(true))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_ranged_number(_var: Variable, _range: NumericRange) -> Result<(), NotDerivable> {
+        Ok(())
+    }
+}
+
+struct DeriveEq;
+impl DerivableVisitor for DeriveEq {
+    const ABILITY: Symbol = Symbol::BOOL_EQ;
+
+    #[inline(always)]
+    fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
+        is_builtin_number_alias(symbol) || symbol == Symbol::BOOL_BOOL
+    }
+
+    #[inline(always)]
+    fn is_derivable_for_all_numbers() -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn visit_rigid_able(var: Variable, ability: Symbol) -> Result<(), NotDerivable> {
+        if ability.ability_implies(Self::ABILITY) {
+            Ok(())
+        } else {
+            Err(NotDerivable {
+                var,
+                context: NotDerivableContext::NoContext,
+            })
         }
+    }
 
+    #[inline(always)]
+    fn visit_recursion(_var: Variable) -> Result<Descend, NotDerivable> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_apply(var: Variable, symbol: Symbol) -> Result<Descend, NotDerivable> {
+        if matches!(
+            symbol,
+            Symbol::LIST_LIST | Symbol::STR_STR | Symbol::BOX_BOX_TYPE
+        ) {
+            Ok(Descend(true))
+        } else {
+            Err(NotDerivable {
+                var,
+                context: NotDerivableContext::NoContext,
+            })
+        }
+    }
+
+    #[inline(always)]
+    fn visit_record(
+        _subs: &Subs,
+        _var: Variable,
+        _fields: RecordFields,
+    ) -> Result<Descend, NotDerivable> {
         Ok(Descend(true))
     }
 
@@ -1007,6 +1198,10 @@ pub fn resolve_ability_specialization<R: AbilityResolver>(
                 roc_types::types::MemberImpl::Impl(spec_symbol) => {
                     Resolved::Specialization(spec_symbol)
                 }
+                roc_types::types::MemberImpl::Derived if ability_member == Symbol::BOOL_IS_EQ => {
+                    // Derived equality is always structural equality, regardless of the opaque.
+                    Resolved::Specialization(Symbol::BOOL_STRUCTURAL_EQ)
+                }
                 roc_types::types::MemberImpl::Derived => {
                     todo_abilities!("get type from obligated opaque")
                 }
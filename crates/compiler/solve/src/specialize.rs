@@ -17,8 +17,8 @@ use roc_error_macros::{internal_error, todo_abilities};
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_types::{
     subs::{
-        get_member_lambda_sets_at_region, Content, Descriptor, GetSubsSlice, LambdaSet, Mark,
-        OptVariable, Rank, Subs, SubsSlice, UlsOfVar, Variable,
+        get_member_lambda_sets_at_region, Content, Descriptor, FlatType, GetSubsSlice, LambdaSet,
+        Mark, OptVariable, Rank, Subs, SubsSlice, UlsOfVar, Variable,
     },
     types::{AliasKind, MemberImpl, Uls},
 };
@@ -542,7 +542,7 @@ fn compact_lambda_set<P: Phase>(
         Err(()) => {
             // Do nothing other than to remove the concrete lambda to drop from the lambda set,
             // which we already did in 1b above.
-            trace_compact!(3iter_end_skipped. subs, t_f1);
+            trace_compact!(3iter_end_skipped.subs, t_f1);
             return OneCompactionResult::Compacted {
                 new_obligations: Default::default(),
                 new_lambda_sets_to_specialize: Default::default(),
@@ -565,7 +565,7 @@ fn compact_lambda_set<P: Phase>(
         Err(()) => {
             // Do nothing other than to remove the concrete lambda to drop from the lambda set,
             // which we already did in 1b above.
-            trace_compact!(3iter_end_skipped. subs, t_f1);
+            trace_compact!(3iter_end_skipped.subs, t_f1);
             return OneCompactionResult::Compacted {
                 new_obligations: Default::default(),
                 new_lambda_sets_to_specialize: Default::default(),
@@ -578,7 +578,7 @@ fn compact_lambda_set<P: Phase>(
     let t_f2 = deep_copy_var_in(subs, target_rank, pools, t_f2, arena);
 
     // 3. Unify `t_f1 ~ t_f2`.
-    trace_compact!(3iter_start. subs, this_lambda_set, t_f1, t_f2);
+    trace_compact!(3iter_start.subs, this_lambda_set, t_f1, t_f2);
     let (vars, new_obligations, new_lambda_sets_to_specialize, _meta) = unify(
         &mut UEnv::new(subs),
         t_f1,
@@ -586,7 +586,7 @@ fn compact_lambda_set<P: Phase>(
         Mode::LAMBDA_SET_SPECIALIZATION,
     )
     .expect_success("ambient functions don't unify");
-    trace_compact!(3iter_end. subs, t_f1);
+    trace_compact!(3iter_end.subs, t_f1);
 
     introduce(subs, target_rank, pools, &vars);
 
@@ -601,6 +601,7 @@ enum SpecializationTypeKey {
     Opaque(Symbol),
     Derived(DeriveKey),
     Immediate(Symbol),
+    SingleLambdaSetImmediate(Symbol),
 }
 
 enum SpecializeDecision {
@@ -623,71 +624,59 @@ fn make_specialization_decision<P: Phase>(
     use SpecializationTypeKey::*;
     match subs.get_content_without_compacting(var) {
         Alias(opaque, _, _, AliasKind::Opaque) if opaque.module_id() != ModuleId::NUM => {
-            if P::IS_LATE {
-                SpecializeDecision::Specialize(Opaque(*opaque))
-            } else {
-                // Solving within a module.
-                phase.with_module_abilities_store(opaque.module_id(), |abilities_store| {
-                    let impl_key = ImplKey {
-                        opaque: *opaque,
-                        ability_member,
-                    };
-                    match abilities_store.get_implementation(impl_key) {
-                        None => {
-                            // Doesn't specialize; an error will already be reported for this.
-                            SpecializeDecision::Drop
-                        }
-                        Some(MemberImpl::Error | MemberImpl::Derived) => {
-                            // TODO: probably not right, we may want to choose a derive decision!
-                            SpecializeDecision::Specialize(Opaque(*opaque))
-                        }
-                        Some(MemberImpl::Impl(specialization_symbol)) => {
-                            match abilities_store.specialization_info(*specialization_symbol) {
-                                Some(_) => SpecializeDecision::Specialize(Opaque(*opaque)),
-
-                                // If we expect a specialization impl but don't yet know it, we must hold off
-                                // compacting the lambda set until the specialization is well-known.
-                                None => SpecializeDecision::PendingSpecialization(impl_key),
-                            }
-                        }
-                    }
-                })
-            }
-        }
-        Structure(_) | Alias(_, _, _, _) => {
-            let builtin = match ability_member.try_into() {
-                Ok(builtin) => builtin,
-                Err(_) => return SpecializeDecision::Drop,
+            let impl_key = ImplKey {
+                opaque: *opaque,
+                ability_member,
             };
-
-            // This is a structural type, find the derived ability function it should use.
-            match roc_derive_key::Derived::builtin(builtin, subs, var) {
-                Ok(derived) => match derived {
-                    roc_derive_key::Derived::Immediate(imm) => {
-                        SpecializeDecision::Specialize(Immediate(imm))
-                    }
-                    roc_derive_key::Derived::Key(derive_key) => {
-                        SpecializeDecision::Specialize(Derived(derive_key))
-                    }
-                },
-                Err(DeriveError::UnboundVar) => {
-                    // not specialized yet, but that also means that it can't possibly be derivable
-                    // at this point?
-                    // TODO: is this right? Revisit if it causes us problems in the future.
-                    SpecializeDecision::Drop
+            let opt_impl = phase
+                .with_module_abilities_store(opaque.module_id(), |abilities_store| {
+                    abilities_store.get_implementation(impl_key).copied()
+                });
+
+            match opt_impl {
+                Some(MemberImpl::Derived) => {
+                    // The opaque derives the ability, so it uses the same implementation as its
+                    // structural representation would.
+                    make_derived_specialization_decision(subs, var, ability_member)
+                }
+                None if *opaque == Symbol::BOOL_BOOL => {
+                    // Bool's other builtin abilities are implemented by the derivers directly.
+                    make_derived_specialization_decision(subs, var, ability_member)
                 }
-                Err(DeriveError::Underivable) => {
-                    // we should have reported an error for this; drop the lambda set.
+                _ if P::IS_LATE => SpecializeDecision::Specialize(Opaque(*opaque)),
+                None => {
+                    // Doesn't specialize; an error will already be reported for this.
                     SpecializeDecision::Drop
                 }
+                Some(MemberImpl::Error) => SpecializeDecision::Specialize(Opaque(*opaque)),
+                Some(MemberImpl::Impl(specialization_symbol)) => {
+                    // Solving within a module.
+                    phase.with_module_abilities_store(opaque.module_id(), |abilities_store| {
+                        match abilities_store.specialization_info(specialization_symbol) {
+                            Some(_) => SpecializeDecision::Specialize(Opaque(*opaque)),
+
+                            // If we expect a specialization impl but don't yet know it, we must hold off
+                            // compacting the lambda set until the specialization is well-known.
+                            None => SpecializeDecision::PendingSpecialization(impl_key),
+                        }
+                    })
+                }
             }
         }
+        Structure(_) | Alias(_, _, _, _) => {
+            // This is a structural type, find the derived ability function it should use.
+            make_derived_specialization_decision(subs, var, ability_member)
+        }
+        RecursionVar { structure, .. } => {
+            // A recursion var stands for the recursive tag union it is part of, so it must use
+            // the same implementation as that tag union.
+            make_derived_specialization_decision(subs, *structure, ability_member)
+        }
         Error => SpecializeDecision::Drop,
         FlexAbleVar(_, _)
         | RigidAbleVar(..)
         | FlexVar(..)
         | RigidVar(..)
-        | RecursionVar { .. }
         | LambdaSet(..)
         | RangedNumber(..) => {
             internal_error!("unexpected")
@@ -695,6 +684,43 @@ fn make_specialization_decision<P: Phase>(
     }
 }
 
+fn make_derived_specialization_decision(
+    subs: &Subs,
+    var: Variable,
+    ability_member: Symbol,
+) -> SpecializeDecision {
+    use SpecializationTypeKey::*;
+
+    let builtin = match ability_member.try_into() {
+        Ok(builtin) => builtin,
+        Err(_) => return SpecializeDecision::Drop,
+    };
+
+    match roc_derive_key::Derived::builtin(builtin, subs, var) {
+        Ok(derived) => match derived {
+            roc_derive_key::Derived::Immediate(imm) => {
+                SpecializeDecision::Specialize(Immediate(imm))
+            }
+            roc_derive_key::Derived::SingleLambdaSetImmediate(imm) => {
+                SpecializeDecision::Specialize(SingleLambdaSetImmediate(imm))
+            }
+            roc_derive_key::Derived::Key(derive_key) => {
+                SpecializeDecision::Specialize(Derived(derive_key))
+            }
+        },
+        Err(DeriveError::UnboundVar) => {
+            // not specialized yet, but that also means that it can't possibly be derivable
+            // at this point?
+            // TODO: is this right? Revisit if it causes us problems in the future.
+            SpecializeDecision::Drop
+        }
+        Err(DeriveError::Underivable) => {
+            // we should have reported an error for this; drop the lambda set.
+            SpecializeDecision::Drop
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn get_specialization_lambda_set_ambient_function<P: Phase>(
     subs: &mut Subs,
@@ -785,5 +811,41 @@ fn get_specialization_lambda_set_ambient_function<P: Phase>(
 
             Ok(immediate_lambda_set_at_region)
         }
+
+        SpecializationTypeKey::SingleLambdaSetImmediate(imm) => {
+            let module_id = imm.module_id();
+            debug_assert!(module_id.is_builtin());
+
+            let module_types = &derived_env
+                .exposed_types
+                .get(&module_id)
+                .unwrap()
+                .exposed_types_storage_subs;
+
+            // Since this immediate has only one lambda set, the region must be pointing to 1, and
+            // moreover the ambient function of the single lset is the immediate's function type.
+            debug_assert_eq!(lset_region, 1);
+            let storage_var = module_types.stored_vars_by_symbol.get(&imm).unwrap();
+            let imported = module_types
+                .storage_subs
+                .export_variable_to(subs, *storage_var);
+
+            // The exposed type of the immediate need not be the same variable as the ambient
+            // function of its lambda set, so find the ambient function explicitly.
+            let ambient_function = match subs.get_content_without_compacting(imported.variable) {
+                Content::Structure(FlatType::Func(_, lambda_set, _)) => {
+                    subs.get_lambda_set(*lambda_set).ambient_function
+                }
+                content => internal_error!(
+                    "immediate {:?} is not a function, found: {:?}",
+                    imm,
+                    roc_types::subs::SubsFmtContent(content, subs)
+                ),
+            };
+
+            roc_types::subs::instantiate_rigids(subs, ambient_function);
+
+            Ok(ambient_function)
+        }
     }
 }
@@ -3469,7 +3469,7 @@ mod solve_expr {
                 Dict.insert
                 "#
             ),
-            "Dict k v, k, v -> Dict k v",
+            "Dict k v, k, v -> Dict k v | k has Hash",
         );
     }
 
@@ -3727,11 +3727,14 @@ mod solve_expr {
 
     #[test]
     fn reconstruct_path() {
-        infer_eq_without_problem(
+        infer_queries!(
             indoc!(
                 r#"
-                reconstructPath : Dict position position, position -> List position
+                app "test" provides [main] to "./platform"
+
+                reconstructPath : Dict position position, position -> List position | position has Hash
                 reconstructPath = \cameFrom, goal ->
+                #^^^^^^^^^^^^^^^{-1}
                     when Dict.get cameFrom goal is
                         Err KeyNotFound ->
                             []
@@ -3739,10 +3742,105 @@ mod solve_expr {
                         Ok next ->
                             List.append (reconstructPath cameFrom next) goal
 
-                reconstructPath
+                main = 1
+                "#
+            ),
+            @r###"
+        reconstructPath : Dict position position, position -[[reconstructPath(1)]]-> List position | position has Hash
+        "###
+        );
+    }
+
+    #[test]
+    fn rigid_able_bound_kept_through_unification_with_flex() {
+        infer_queries!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                toEncoders : List a -> List (Encoder fmt) | a has Encoding, fmt has EncoderFormatting
+                toEncoders = \lst -> List.map lst \elem -> Encode.toEncoder elem
+                #^^^^^^^^^^{-1}
+
+                main = toEncoders
                 "#
             ),
-            "Dict position position, position -> List position",
+            @r###"
+        toEncoders : List a -[[toEncoders(1)]]-> List (Encoder fmt) | a has Encoding, fmt has EncoderFormatting
+        "###
+        );
+    }
+
+    #[test]
+    fn equality_requires_eq() {
+        infer_queries!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = \a, b -> a == b
+                #^^^^{-1}
+                "#
+            ),
+            @r###"
+        main : a, a -[[main(0)]]-> Bool | a has Eq
+        "###
+        );
+    }
+
+    #[test]
+    fn equality_with_another_ability() {
+        infer_queries!(
+            indoc!(
+                r#"
+                app "test" imports [Json] provides [main] to "./platform"
+
+                main = \a, b -> if a == b then Encode.toBytes a Json.toUtf8 else []
+                #^^^^{-1}
+                "#
+            ),
+            @r###"
+        main : val, val -[[main(0)]]-> List U8 | val has Encoding, val has Eq
+        "###
+        );
+    }
+
+    #[test]
+    fn opaque_with_custom_eq() {
+        infer_queries!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                Id := U64 has [Eq {isEq: idEq}]
+
+                idEq = \@Id m, @Id n -> m == n
+
+                main = @Id 1 != @Id 2
+                #      ^^^^^^^^^^^^^^
+                "#
+            ),
+            @r###"
+        @Id 1 != @Id 2 : Bool
+        "###
+        );
+    }
+
+    #[test]
+    fn imported_opaque_with_annotated_custom_eq() {
+        // Dict's `isEq` has an annotation, so its rigids must be instantiated at each use.
+        infer_queries!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = Dict.single 1u8 "one" == Dict.single 2u8 "two" && Dict.single "one" 1u8 != Dict.empty
+                #^^^^{-1}
+                "#
+            ),
+            @r###"
+        main : Bool
+        "###
         );
     }
 
@@ -3777,7 +3875,7 @@ mod solve_expr {
 
                 Model position : { openSet : Set position }
 
-                cheapestOpen : Model position -> Result position [KeyNotFound]*
+                cheapestOpen : Model position -> Result position [KeyNotFound]* | position has Eq
                 cheapestOpen = \model ->
 
                     folder = \resSmallestSoFar, position ->
@@ -3792,14 +3890,14 @@ mod solve_expr {
                     Set.walk model.openSet (Ok { position: boom {}, cost: 0.0 }) folder
                         |> Result.map (\x -> x.position)
 
-                astar : Model position -> Result position [KeyNotFound]*
+                astar : Model position -> Result position [KeyNotFound]* | position has Eq
                 astar = \model -> cheapestOpen model
 
                 main =
                     astar
                 "#
             ),
-            "Model position -> Result position [KeyNotFound]*",
+            "Model position -> Result position [KeyNotFound]* | position has Eq",
         );
     }
 
@@ -5956,10 +6054,10 @@ mod solve_expr {
                 r#"
                 app "test" provides [hash] to "./platform"
 
-                Hash has hash : a -> U64 | a has Hash
+                MHash has hash : a -> U64 | a has MHash
                 "#
             ),
-            "a -> U64 | a has Hash",
+            "a -> U64 | a has MHash",
         )
     }
 
@@ -5970,14 +6068,14 @@ mod solve_expr {
                 r#"
                 app "test" provides [hash] to "./platform"
 
-                Hash has hash : a -> U64 | a has Hash
+                MHash has hash : a -> U64 | a has MHash
 
-                Id := U64 has [Hash {hash}]
+                Id := U64 has [MHash {hash}]
 
                 hash = \@Id n -> n
                 "#
             ),
-            [("Hash:hash", "Id")],
+            [("MHash:hash", "Id")],
         )
     }
 
@@ -5988,17 +6086,17 @@ mod solve_expr {
                 r#"
                 app "test" provides [hash, hash32] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
-                    hash32 : a -> U32 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
+                    hash32 : a -> U32 | a has MHash
 
-                Id := U64 has [Hash {hash, hash32}]
+                Id := U64 has [MHash {hash, hash32}]
 
                 hash = \@Id n -> n
                 hash32 = \@Id n -> Num.toU32 n
                 "#
             ),
-            [("Hash:hash", "Id"), ("Hash:hash32", "Id")],
+            [("MHash:hash", "Id"), ("MHash:hash32", "Id")],
         )
     }
 
@@ -6009,15 +6107,15 @@ mod solve_expr {
                 r#"
                 app "test" provides [hash, hash32, eq, le] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
-                    hash32 : a -> U32 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
+                    hash32 : a -> U32 | a has MHash
 
                 Ord has
                     eq : a, a -> Bool | a has Ord
                     le : a, a -> Bool | a has Ord
 
-                Id := U64 has [Hash {hash, hash32}, Ord {eq, le}]
+                Id := U64 has [MHash {hash, hash32}, Ord {eq, le}]
 
                 hash = \@Id n -> n
                 hash32 = \@Id n -> Num.toU32 n
@@ -6027,8 +6125,8 @@ mod solve_expr {
                 "#
             ),
             [
-                ("Hash:hash", "Id"),
-                ("Hash:hash32", "Id"),
+                ("MHash:hash", "Id"),
+                ("MHash:hash32", "Id"),
                 ("Ord:eq", "Id"),
                 ("Ord:le", "Id"),
             ],
@@ -6042,16 +6140,16 @@ mod solve_expr {
                 r#"
                 app "test" provides [hash] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
-                Id := U64 has [Hash {hash}]
+                Id := U64 has [MHash {hash}]
 
                 hash : Id -> U64
                 hash = \@Id n -> n
                 "#
             ),
-            [("Hash:hash", "Id")],
+            [("MHash:hash", "Id")],
         )
     }
 
@@ -6062,15 +6160,15 @@ mod solve_expr {
                 r#"
                 app "test" provides [hash] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
-                Id := U64 has [Hash {hash}]
+                Id := U64 has [MHash {hash}]
 
                 hash : Id -> U64
                 "#
             ),
-            [("Hash:hash", "Id")],
+            [("MHash:hash", "Id")],
         )
     }
 
@@ -6081,10 +6179,10 @@ mod solve_expr {
                 r#"
                 app "test" provides [zero] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
-                Id := U64 has [Hash {hash}]
+                Id := U64 has [MHash {hash}]
 
                 hash = \@Id n -> n
 
@@ -6102,15 +6200,15 @@ mod solve_expr {
                 r#"
                 app "test" provides [thething] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
                 thething =
                     itis = hash
                     itis
                 "#
             ),
-            "a -> U64 | a has Hash",
+            "a -> U64 | a has MHash",
         )
     }
 
@@ -6138,14 +6236,14 @@ mod solve_expr {
                 r#"
                 app "test" provides [hashEq] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
-                hashEq : a, a -> Bool | a has Hash
+                hashEq : a, a -> Bool | a has MHash
                 hashEq = \x, y -> hash x == hash y
                 "#
             ),
-            "a, a -> Bool | a has Hash",
+            "a, a -> Bool | a has MHash",
         )
     }
 
@@ -6156,13 +6254,13 @@ mod solve_expr {
                 r#"
                 app "test" provides [hashEq] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
                 hashEq = \x, y -> hash x == hash y
                 "#
             ),
-            "a, a1 -> Bool | a has Hash, a1 has Hash",
+            "a, a1 -> Bool | a has MHash, a1 has MHash",
         )
     }
 
@@ -6173,12 +6271,12 @@ mod solve_expr {
                 r#"
                 app "test" provides [result] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
                 hashEq = \x, y -> hash x == hash y
 
-                Id := U64 has [Hash {hash}]
+                Id := U64 has [MHash {hash}]
                 hash = \@Id n -> n
 
                 result = hashEq (@Id 100) (@Id 101)
@@ -6195,15 +6293,15 @@ mod solve_expr {
                 r#"
                 app "test" provides [result] to "./platform"
 
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
                 mulHashes = \x, y -> hash x * hash y
 
-                Id := U64 has [Hash { hash: hashId }]
+                Id := U64 has [MHash { hash: hashId }]
                 hashId = \@Id n -> n
 
-                Three := {} has [Hash { hash: hashThree }]
+                Three := {} has [MHash { hash: hashThree }]
                 hashThree = \@Three _ -> 3
 
                 result = mulHashes (@Id 100) (@Three {})
@@ -7823,4 +7921,88 @@ mod solve_expr {
             "Result Str [] -> Str",
         );
     }
+
+    #[test]
+    fn infer_list_patterns() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                \list ->
+                    when list is
+                        [] -> []
+                        [x, ..rest] -> List.append rest x
+                "#
+            ),
+            "List a -> List a",
+        );
+    }
+
+    #[test]
+    fn infer_list_pattern_element_literals() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                \list ->
+                    when list is
+                        ["a", x] -> x
+                        _ -> ""
+                "#
+            ),
+            "List Str -> Str",
+        );
+    }
+
+    #[test]
+    fn infer_tuple_expr() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                ("a", 1u8, {})
+                "#
+            ),
+            "( Str, U8, {} )",
+        );
+    }
+
+    #[test]
+    fn infer_tuple_access() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                \t -> t.1
+                "#
+            ),
+            "( *, a )* -> a",
+        );
+    }
+
+    #[test]
+    fn infer_tuple_pattern() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                swap : (a, b) -> (b, a)
+                swap = \(x, y) -> (y, x)
+
+                swap
+                "#
+            ),
+            "( a, b ) -> ( b, a )",
+        );
+    }
+
+    #[test]
+    fn infer_tuple_pattern_in_when() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                \pair ->
+                    when pair is
+                        (0, x) -> x
+                        (_, x) -> x + 1
+                "#
+            ),
+            "( Num *, Num a ) -> Num a",
+        );
+    }
 }
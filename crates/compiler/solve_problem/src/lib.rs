@@ -1,9 +1,9 @@
 use roc_can::expected::{Expected, PExpected};
-use roc_module::{ident::Lowercase, symbol::Symbol};
+use roc_module::symbol::Symbol;
 use roc_problem::can::CycleEntry;
 use roc_region::all::Region;
 
-use roc_types::types::{Category, ErrorType, PatternCategory};
+use roc_types::types::{Category, ErrorType, PatternCategory, Problem};
 
 #[derive(Debug, Clone)]
 pub enum TypeError {
@@ -31,6 +31,45 @@ pub enum TypeError {
     },
 }
 
+impl TypeError {
+    /// Where in the module the error is, unless it's about the module as a whole
+    pub fn region(&self) -> Option<Region> {
+        match self {
+            TypeError::BadExpr(region, ..)
+            | TypeError::BadPattern(region, ..)
+            | TypeError::CircularType(region, ..)
+            | TypeError::BadExprMissingAbility(region, ..)
+            | TypeError::BadPatternMissingAbility(region, ..)
+            | TypeError::StructuralSpecialization { region, .. }
+            | TypeError::WrongSpecialization { region, .. } => Some(*region),
+            TypeError::CircularDef(cycle) => cycle.first().map(|entry| entry.symbol_region),
+            TypeError::BadType(problem) => match problem {
+                Problem::CircularType(_, _, region)
+                | Problem::CyclicAlias(_, region, _)
+                | Problem::Shadowed(region, _)
+                | Problem::BadTypeArguments { region, .. }
+                | Problem::HasClauseIsNotAbility(region) => Some(*region),
+                _ => None,
+            },
+            TypeError::UnfulfilledAbility(Unfulfilled::OpaqueUnderivable {
+                derive_region, ..
+            }) => Some(*derive_region),
+            TypeError::UnfulfilledAbility(_) | TypeError::UnexposedLookup(_) => None,
+            TypeError::Exhaustive(error) => match error {
+                roc_exhaustive::Error::Incomplete(region, ..)
+                | roc_exhaustive::Error::Redundant {
+                    branch_region: region,
+                    ..
+                }
+                | roc_exhaustive::Error::Unmatchable {
+                    branch_region: region,
+                    ..
+                } => Some(*region),
+            },
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum Unfulfilled {
     /// No claimed implementation of an ability for an opaque type.
@@ -66,10 +105,4 @@ pub enum NotDerivableContext {
     Function,
     UnboundVar,
     Opaque(Symbol),
-    Decode(NotDerivableDecode),
-}
-
-#[derive(PartialEq, Debug, Clone)]
-pub enum NotDerivableDecode {
-    OptionalRecordField(Lowercase),
 }
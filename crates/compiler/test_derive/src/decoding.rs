@@ -6,14 +6,14 @@
 
 use crate::{
     test_key_eq, test_key_neq,
-    util::{check_derivable, check_immediate, check_underivable, derive_test},
+    util::{check_derivable, check_immediate, derive_test},
     v,
 };
 use insta::assert_snapshot;
 use roc_module::symbol::Symbol;
 use roc_types::subs::Variable;
 
-use roc_derive_key::{decoding::FlatDecodableKey, DeriveBuiltin::Decoder, DeriveError, DeriveKey};
+use roc_derive_key::{decoding::FlatDecodableKey, DeriveBuiltin::Decoder, DeriveKey};
 
 test_key_eq! {
     Decoder,
@@ -28,6 +28,20 @@ test_key_eq! {
     explicit_empty_record_and_implicit_empty_record:
         v!(EMPTY_RECORD), v!({})
 
+    same_tag_union:
+        v!([ A v!(U8) v!(STR), B v!(STR) ]), v!([ A v!(U8) v!(STR), B v!(STR) ])
+    same_tag_union_tags_diff_types:
+        v!([ A v!(U8) v!(U8), B v!(U8) ]), v!([ A v!(STR) v!(STR), B v!(STR) ])
+    same_tag_union_tags_any_order:
+        v!([ A v!(U8) v!(U8), B v!(U8), C ]), v!([ C, B v!(STR), A v!(STR) v!(STR) ])
+    explicit_empty_tag_union_and_implicit_empty_tag_union:
+        v!(EMPTY_TAG_UNION), v!([])
+
+    same_recursive_tag_union:
+        v!([ Nil, Cons v!(^lst)] as lst), v!([ Nil, Cons v!(^lst)] as lst)
+    same_tag_union_and_recursive_tag_union_fields:
+        v!([ Nil, Cons v!(STR)]), v!([ Nil, Cons v!(^lst)] as lst)
+
     list_list_diff_types:
         v!(Symbol::LIST_LIST v!(STR)), v!(Symbol::LIST_LIST v!(U8))
     str_str:
@@ -41,6 +55,13 @@ test_key_neq! {
         v!({ a: v!(U8), }), v!({ b: v!(U8), })
     record_empty_vs_nonempty:
         v!(EMPTY_RECORD), v!({ a: v!(U8), })
+
+    different_tag_union_tags:
+        v!([ A v!(U8) ]), v!([ B v!(U8) ])
+    tag_union_empty_vs_nonempty:
+        v!(EMPTY_TAG_UNION), v!([ B v!(U8) ])
+    different_recursive_tag_union_tags:
+        v!([ Nil, Cons v!(^lst) ] as lst), v!([ Nil, Next v!(^lst) ] as lst)
 }
 
 #[test]
@@ -59,11 +80,20 @@ fn immediates() {
     check_immediate(Decoder, v!(F32), Symbol::DECODE_F32);
     check_immediate(Decoder, v!(F64), Symbol::DECODE_F64);
     check_immediate(Decoder, v!(STR), Symbol::DECODE_STRING);
+    check_immediate(
+        Decoder,
+        v!(@Symbol::BOOL_BOOL => v!([ True, False ])),
+        Symbol::DECODE_BOOL,
+    );
 }
 
 #[test]
-fn optional_record_field_derive_error() {
-    check_underivable(Decoder, v!({ ?a: v!(U8), }), DeriveError::Underivable);
+fn derivable_record_with_optional_field() {
+    check_derivable(
+        Decoder,
+        v!({ a: v!(STR), ?b: v!(U8), }),
+        DeriveKey::Decoder(FlatDecodableKey::Record(vec!["a".into()])),
+    );
 }
 
 #[test]
@@ -93,6 +123,27 @@ fn derivable_record_with_record_ext() {
     );
 }
 
+#[test]
+fn derivable_tag_ext_flex_var() {
+    check_derivable(
+        Decoder,
+        v!([ A v!(STR) ]* ),
+        DeriveKey::Decoder(FlatDecodableKey::TagUnion(vec![("A".into(), 1)])),
+    );
+}
+
+#[test]
+fn derivable_tag_with_tag_ext() {
+    check_derivable(
+        Decoder,
+        v!([ B v!(STR) v!(U8) ][ A v!(STR) ]),
+        DeriveKey::Decoder(FlatDecodableKey::TagUnion(vec![
+            ("A".into(), 1),
+            ("B".into(), 2),
+        ])),
+    );
+}
+
 #[test]
 fn list() {
     derive_test(Decoder, v!(Symbol::LIST_LIST v!(STR)), |golden| {
@@ -173,3 +224,153 @@ fn record_2_fields() {
         )
     })
 }
+
+#[test]
+fn tag_one_label_zero_args() {
+    derive_test(Decoder, v!([A]), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [A]
+        # Decoder [A] fmt | fmt has DecoderFormatting
+        # List U8, fmt -[[custom(9)]]-> { rest : List U8, result : [Err [TooShort], Ok [A]] } | fmt has DecoderFormatting
+        # Specialization lambda sets:
+        #   @<1>: [[custom(9)]]
+        #Derived.decoder_[A 0] =
+          Decode.custom
+            \#Derived.bytes, #Derived.fmt ->
+              Decode.decodeWith
+                #Derived.bytes
+                (Decode.tag
+                  \#Derived.tag -> when #Derived.tag is "A" -> Keep A#0 _ -> Skip
+                  \#Derived.state -> when #Derived.state is _ -> Skip
+                  \#Derived.state2 ->
+                    when #Derived.state2 is A#0 -> Ok A _ -> Err TooShort)
+                #Derived.fmt
+        "###
+        )
+    })
+}
+
+#[test]
+fn tag_two_labels() {
+    derive_test(Decoder, v!([A v!(U8) v!(STR), B]), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [A U8 Str, B]
+        # Decoder [A val val1, B] fmt | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # List U8, fmt -[[custom(24)]]-> { rest : List U8, result : [Err [TooShort], Ok [A val val1, B]] } | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # Specialization lambda sets:
+        #   @<1>: [[custom(24)]]
+        #Derived.decoder_[A 2,B 0] =
+          Decode.custom
+            \#Derived.bytes3, #Derived.fmt3 ->
+              Decode.decodeWith
+                #Derived.bytes3
+                (Decode.tag
+                  \#Derived.tag ->
+                    when #Derived.tag is "A" -> Keep A#0 "B" -> Keep B#0 _ -> Skip
+                  \#Derived.state ->
+                    when #Derived.state is
+                      A#0 ->
+                        Keep (Decode.custom
+                          \#Derived.bytes, #Derived.fmt ->
+                            when Decode.decodeWith
+                                #Derived.bytes
+                                Decode.decoder
+                                #Derived.fmt is
+                              #Derived.rec ->
+                                {
+                                  result: when #Derived.rec.result is
+                                      Ok #Derived.val -> Ok (A#1 #Derived.val)
+                                      Err #Derived.err -> Err #Derived.err,
+                                  rest: #Derived.rec.rest
+                                })
+                      A#1 #Derived.payload ->
+                        Keep (Decode.custom
+                          \#Derived.bytes2, #Derived.fmt2 ->
+                            when Decode.decodeWith
+                                #Derived.bytes2
+                                Decode.decoder
+                                #Derived.fmt2 is
+                              #Derived.rec2 ->
+                                {
+                                  result: when #Derived.rec2.result is
+                                      Ok #Derived.val2 ->
+                                        Ok (A#2 #Derived.payload #Derived.val2)
+                                      Err #Derived.err2 -> Err #Derived.err2,
+                                  rest: #Derived.rec2.rest
+                                })
+                      _ -> Skip
+                  \#Derived.state2 ->
+                    when #Derived.state2 is
+                      A#2 #Derived.payload2 #Derived.payload3 ->
+                        Ok (A #Derived.payload2 #Derived.payload3)
+                      B#0 -> Ok B
+                      _ -> Err TooShort)
+                #Derived.fmt3
+        "###
+        )
+    })
+}
+
+#[test]
+fn recursive_tag_union() {
+    derive_test(Decoder, v!([Nil, Cons v!(U8) v!(^lst) ] as lst), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [Cons U8 $rec, Nil] as $rec
+        # Decoder [Cons val val1, Nil] fmt | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # List U8, fmt -[[custom(24)]]-> { rest : List U8, result : [Err [TooShort], Ok [Cons val val1, Nil]] } | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # Specialization lambda sets:
+        #   @<1>: [[custom(24)]]
+        #Derived.decoder_[Cons 2,Nil 0] =
+          Decode.custom
+            \#Derived.bytes3, #Derived.fmt3 ->
+              Decode.decodeWith
+                #Derived.bytes3
+                (Decode.tag
+                  \#Derived.tag ->
+                    when #Derived.tag is
+                      "Cons" -> Keep Cons#0
+                      "Nil" -> Keep Nil#0
+                      _ -> Skip
+                  \#Derived.state ->
+                    when #Derived.state is
+                      Cons#0 ->
+                        Keep (Decode.custom
+                          \#Derived.bytes, #Derived.fmt ->
+                            when Decode.decodeWith
+                                #Derived.bytes
+                                Decode.decoder
+                                #Derived.fmt is
+                              #Derived.rec ->
+                                {
+                                  result: when #Derived.rec.result is
+                                      Ok #Derived.val -> Ok (Cons#1 #Derived.val)
+                                      Err #Derived.err -> Err #Derived.err,
+                                  rest: #Derived.rec.rest
+                                })
+                      Cons#1 #Derived.payload ->
+                        Keep (Decode.custom
+                          \#Derived.bytes2, #Derived.fmt2 ->
+                            when Decode.decodeWith
+                                #Derived.bytes2
+                                Decode.decoder
+                                #Derived.fmt2 is
+                              #Derived.rec2 ->
+                                {
+                                  result: when #Derived.rec2.result is
+                                      Ok #Derived.val2 ->
+                                        Ok (Cons#2 #Derived.payload #Derived.val2)
+                                      Err #Derived.err2 -> Err #Derived.err2,
+                                  rest: #Derived.rec2.rest
+                                })
+                      _ -> Skip
+                  \#Derived.state2 ->
+                    when #Derived.state2 is
+                      Cons#2 #Derived.payload2 #Derived.payload3 ->
+                        Ok (Cons #Derived.payload2 #Derived.payload3)
+                      Nil#0 -> Ok Nil
+                      _ -> Err TooShort)
+                #Derived.fmt3
+        "###
+        )
+    })
+}
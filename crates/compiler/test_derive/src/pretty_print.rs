@@ -197,11 +197,31 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
             .append(f.text("}"))
             .group(),
         EmptyRecord => f.text("{}"),
+        Tuple { elems, .. } => f
+            .reflow("(")
+            .append(
+                f.intersperse(
+                    elems
+                        .iter()
+                        .map(|(_, elem)| f.line().append(expr(c, Free, f, &elem.value))),
+                    f.reflow(","),
+                )
+                .nest(2)
+                .group(),
+            )
+            .append(f.line())
+            .append(f.text(")"))
+            .group(),
         Access {
             loc_expr, field, ..
         } => expr(c, AppArg, f, &loc_expr.value)
             .append(f.text(format!(".{}", field.as_str())))
             .group(),
+        TupleAccess {
+            loc_expr, index, ..
+        } => expr(c, AppArg, f, &loc_expr.value)
+            .append(f.text(format!(".{}", index)))
+            .group(),
         OpaqueWrapFunction(OpaqueWrapFunctionData { opaque_name, .. }) => {
             f.text(format!("@{}", opaque_name.as_str(c.interns)))
         }
@@ -258,6 +278,7 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
         OpaqueRef { .. } => todo!(),
         Expect { .. } => todo!(),
         ExpectFx { .. } => todo!(),
+        Dbg { .. } => todo!(),
         TypedHole(_) => todo!(),
         RuntimeError(_) => todo!(),
     }
@@ -340,6 +361,26 @@ fn pattern<'a>(
             .text(format!("@{} ", opaque.module_string(c.interns)))
             .append(pattern(c, Free, f, &argument.1.value))
             .group(),
+        List { patterns, .. } => {
+            let mut elems: Vec<_> = patterns
+                .patterns
+                .iter()
+                .map(|lp| pattern(c, Free, f, &lp.value))
+                .collect();
+
+            if let Some((index, opt_symbol)) = &patterns.opt_rest {
+                let rest = match opt_symbol {
+                    Some(sym) => f.text(format!("..{}", sym.value.as_str(c.interns))),
+                    None => f.text(".."),
+                };
+                elems.insert(*index, rest);
+            }
+
+            f.text("[")
+                .append(f.intersperse(elems, f.text(", ")))
+                .append(f.text("]"))
+                .group()
+        }
         RecordDestructure { destructs, .. } => f
             .text("{")
             .append(
@@ -362,6 +403,18 @@ fn pattern<'a>(
             )
             .append(f.text("}"))
             .group(),
+        TupleDestructure { destructs, .. } => f
+            .text("(")
+            .append(
+                f.intersperse(
+                    destructs
+                        .iter()
+                        .map(|l| pattern(c, Free, f, &l.value.typ.1.value)),
+                    f.text(", "),
+                ),
+            )
+            .append(f.text(")"))
+            .group(),
         NumLiteral(_, n, _, _) | IntLiteral(_, _, n, _, _) | FloatLiteral(_, _, n, _, _) => {
             f.text(&**n)
         }
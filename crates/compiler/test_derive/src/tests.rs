@@ -2,6 +2,8 @@
 
 mod decoding;
 mod encoding;
+mod eq;
+mod hash;
 
 mod pretty_print;
 mod util;
@@ -20,10 +20,10 @@ fn hash_specialization() {
             r#"
             app "test" provides [main] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            Id := U64 has [Hash {hash}]
+            Id := U64 has [MHash {hash}]
 
             hash = \@Id n -> n
 
@@ -43,14 +43,14 @@ fn hash_specialization_multiple_add() {
             r#"
             app "test" provides [main] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            Id := U64 has [ Hash {hash: hashId} ]
+            Id := U64 has [ MHash {hash: hashId} ]
 
             hashId = \@Id n -> n
 
-            One := {} has [ Hash {hash: hashOne} ]
+            One := {} has [ MHash {hash: hashOne} ]
 
             hashOne = \@One _ -> 1
 
@@ -70,10 +70,10 @@ fn alias_member_specialization() {
             r#"
             app "test" provides [main] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            Id := U64 has [Hash {hash}]
+            Id := U64 has [MHash {hash}]
 
             hash = \@Id n -> n
 
@@ -95,13 +95,13 @@ fn ability_constrained_in_non_member_usage() {
             r#"
             app "test" provides [result] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            mulHashes : a, a -> U64 | a has Hash
+            mulHashes : a, a -> U64 | a has MHash
             mulHashes = \x, y -> hash x * hash y
 
-            Id := U64 has [Hash {hash}]
+            Id := U64 has [MHash {hash}]
             hash = \@Id n -> n
 
             result = mulHashes (@Id 5) (@Id 7)
@@ -120,12 +120,12 @@ fn ability_constrained_in_non_member_usage_inferred() {
             r#"
             app "test" provides [result] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
             mulHashes = \x, y -> hash x * hash y
 
-            Id := U64 has [Hash {hash}]
+            Id := U64 has [MHash {hash}]
             hash = \@Id n -> n
 
             result = mulHashes (@Id 5) (@Id 7)
@@ -144,16 +144,16 @@ fn ability_constrained_in_non_member_multiple_specializations() {
             r#"
             app "test" provides [result] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            mulHashes : a, b -> U64 | a has Hash, b has Hash
+            mulHashes : a, b -> U64 | a has MHash, b has MHash
             mulHashes = \x, y -> hash x * hash y
 
-            Id := U64 has [Hash { hash: hashId }]
+            Id := U64 has [MHash { hash: hashId }]
             hashId = \@Id n -> n
 
-            Three := {} has [Hash { hash: hashThree }]
+            Three := {} has [MHash { hash: hashThree }]
             hashThree = \@Three _ -> 3
 
             result = mulHashes (@Id 100) (@Three {})
@@ -172,15 +172,15 @@ fn ability_constrained_in_non_member_multiple_specializations_inferred() {
             r#"
             app "test" provides [result] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
             mulHashes = \x, y -> hash x * hash y
 
-            Id := U64 has [Hash { hash: hashId }]
+            Id := U64 has [MHash { hash: hashId }]
             hashId = \@Id n -> n
 
-            Three := {} has [Hash { hash: hashThree }]
+            Three := {} has [MHash { hash: hashThree }]
             hashThree = \@Three _ -> 3
 
             result = mulHashes (@Id 100) (@Three {})
@@ -199,16 +199,16 @@ fn ability_used_as_type_still_compiles() {
             r#"
             app "test" provides [result] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            mulHashes : Hash, Hash -> U64
+            mulHashes : MHash, MHash -> U64
             mulHashes = \x, y -> hash x * hash y
 
-            Id := U64 has [Hash { hash: hashId }]
+            Id := U64 has [MHash { hash: hashId }]
             hashId = \@Id n -> n
 
-            Three := {} has [Hash { hash: hashThree }]
+            Three := {} has [MHash { hash: hashThree }]
             hashThree = \@Three _ -> 3
 
             result = mulHashes (@Id 100) (@Three {})
@@ -519,6 +519,30 @@ fn encode_derived_record_two_fields_strings() {
     )
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_derived_record_with_field_name_mapping() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [Encode.{ toEncoder }, Json]
+                provides [main] to "./platform"
+
+            main =
+                rcd = {firstName: "foo", lastName: {middleName: "bar"}}
+                format = Json.jsonWithOptions { fieldNameMapping: SnakeCase }
+                result = Str.fromUtf8 (Encode.toBytes rcd format)
+                when result is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from(r#"{"first_name":"foo","last_name":{"middle_name":"bar"}}"#),
+        RocStr
+    )
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn encode_derived_nested_record_string() {
@@ -1062,7 +1086,6 @@ fn decode_record_two_fields_string_and_string_infer_local_var_destructured() {
 
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
-#[ignore = "json parsing impl must be fixed first"]
 fn decode_empty_record() {
     assert_evals_to!(
         indoc!(
@@ -1102,3 +1125,197 @@ fn decode_record_of_record() {
         RocStr
     )
 }
+
+#[test]
+#[cfg(all(
+    any(feature = "gen-llvm", feature = "gen-wasm"),
+    not(debug_assertions) // https://github.com/roc-lang/roc/issues/3898
+))]
+fn decode_record_with_field_name_mapping() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                pascal = Json.jsonWithOptions { fieldNameMapping: PascalCase }
+                kebab = Json.jsonWithOptions { fieldNameMapping: KebabCase }
+
+                when Str.toUtf8 "{\"FirstName\":\"a\",\"Inner\":{\"LastName\":\"b\"}}" |> Decode.fromBytes pascal is
+                    Ok {firstName, inner: {lastName}} ->
+                        when Str.toUtf8 "{\"first-name\":\"c\"}" |> Decode.fromBytes kebab is
+                            Ok {firstName: other} -> Str.concat (Str.concat firstName lastName) other
+                            _ -> "something went wrong"
+
+                    _ -> "something went wrong"
+            "#
+        ),
+        RocStr::from("abc"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(all(
+    any(feature = "gen-llvm", feature = "gen-wasm"),
+    not(debug_assertions) // https://github.com/roc-lang/roc/issues/3898
+))]
+fn decode_record_with_absent_optional_field() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "{\"first\":\"a\"}" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok {first, second ? "b"} -> Str.concat first second
+                    _ -> "something went wrong"
+            "#
+        ),
+        RocStr::from("ab"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(all(
+    any(feature = "gen-llvm", feature = "gen-wasm"),
+    not(debug_assertions) // https://github.com/roc-lang/roc/issues/3898
+))]
+fn decode_tag_union_no_payload() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "{\"B\":[]}" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok A -> "a"
+                    Ok B -> "b"
+                    _ -> "something went wrong"
+            "#
+        ),
+        RocStr::from("b"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(all(
+    any(feature = "gen-llvm", feature = "gen-wasm"),
+    not(debug_assertions) // https://github.com/roc-lang/roc/issues/3898
+))]
+fn decode_tag_union_with_payloads() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "{\"A\":[10,\"ab\"]}" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok (A 10u8 s) -> Str.concat s "10"
+                    Ok (B s) -> s
+                    _ -> "something went wrong"
+            "#
+        ),
+        RocStr::from("ab10"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(all(
+    any(feature = "gen-llvm", feature = "gen-wasm"),
+    not(debug_assertions) // https://github.com/roc-lang/roc/issues/3898
+))]
+fn decode_recursive_tag_union() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            LinkedList : [Nil, Cons U8 LinkedList]
+
+            sum : LinkedList -> U8
+            sum = \list ->
+                when list is
+                    Nil -> 0
+                    Cons n rest -> n + sum rest
+
+            main =
+                result : Result LinkedList _
+                result = Str.toUtf8 "{\"Cons\":[1,{\"Cons\":[2,{\"Nil\":[]}]}]}" |> Decode.fromBytes Json.fromUtf8
+
+                when result is
+                    Ok list -> sum list
+                    _ -> 0
+            "#
+        ),
+        3,
+        u8
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn eq_custom_opaque_impl() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            Parity := U8 has [Eq {isEq: parityEq}]
+
+            parityEq = \@Parity m, @Parity n -> Num.isEven m == Num.isEven n
+
+            main = @Parity 2 == @Parity 4 && @Parity 2 != @Parity 3
+            "#
+        ),
+        true,
+        bool
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn eq_derived_opaque() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            Point := { x : I64, y : I64 } has [Eq]
+
+            main = @Point { x: 1, y: 2 } == @Point { x: 1, y: 2 } && @Point { x: 1, y: 2 } != @Point { x: 2, y: 1 }
+            "#
+        ),
+        true,
+        bool
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn eq_through_ability_constrained_function() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" provides [main] to "./platform"
+
+            Id := U64 has [Eq {isEq: idEq}]
+
+            idEq = \@Id m, @Id n -> m == n
+
+            allEq : List a -> Bool | a has Eq
+            allEq = \list ->
+                when List.first list is
+                    Ok first -> List.all list \elem -> elem == first
+                    Err ListWasEmpty -> Bool.true
+
+            main = allEq [@Id 1, @Id 1, @Id 1]
+            "#
+        ),
+        true,
+        bool
+    )
+}
@@ -13,7 +13,7 @@ use indoc::indoc;
 use roc_std::{RocList, RocStr};
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_empty_len() {
     assert_evals_to!(
         indoc!(
@@ -27,7 +27,7 @@ fn dict_empty_len() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_insert_empty() {
     assert_evals_to!(
         indoc!(
@@ -43,7 +43,7 @@ fn dict_insert_empty() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_empty_contains() {
     assert_evals_to!(
         indoc!(
@@ -60,7 +60,7 @@ fn dict_empty_contains() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_nonempty_contains() {
     assert_evals_to!(
         indoc!(
@@ -77,7 +77,7 @@ fn dict_nonempty_contains() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_empty_remove() {
     assert_evals_to!(
         indoc!(
@@ -96,7 +96,7 @@ fn dict_empty_remove() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_nonempty_remove() {
     assert_evals_to!(
         indoc!(
@@ -116,7 +116,7 @@ fn dict_nonempty_remove() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_nonempty_get() {
     assert_evals_to!(
         indoc!(
@@ -324,7 +324,7 @@ fn big_str_values() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn unit_values() {
     assert_evals_to!(
         indoc!(
@@ -346,7 +346,7 @@ fn unit_values() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn single() {
     assert_evals_to!(
         indoc!(
@@ -364,7 +364,7 @@ fn single() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn insert_all() {
     assert_evals_to!(
         indoc!(
@@ -402,7 +402,7 @@ fn insert_all_prefer_first() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn keep_shared() {
     assert_evals_to!(
         indoc!(
@@ -465,7 +465,7 @@ fn keep_shared_prefer_first() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn remove_all() {
     assert_evals_to!(
         indoc!(
@@ -497,7 +497,7 @@ fn remove_all() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn remove_all_prefer_first() {
     assert_evals_to!(
         indoc!(
@@ -528,7 +528,7 @@ fn remove_all_prefer_first() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn walk_sum_keys() {
     assert_evals_to!(
         indoc!(
@@ -549,3 +549,127 @@ fn walk_sum_keys() {
         i64
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn insert_remove_many() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            full : Dict.Dict I64 I64
+            full =
+                List.range 0 100
+                    |> List.walk Dict.empty (\accum, value -> Dict.insert accum value value)
+
+            dict : Dict.Dict I64 I64
+            dict =
+                List.range 0 95
+                    |> List.walk full Dict.remove
+
+            Dict.values dict
+            "#
+        ),
+        RocList::from_slice(&[95, 96, 97, 98, 99]),
+        RocList<i64>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn number_literal_keys() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            unsuffixed =
+                Dict.empty
+                    |> Dict.insert 1 "one"
+                    |> Dict.insert 2 "two"
+
+            narrowed =
+                Dict.empty
+                    |> Dict.insert 1 "one"
+                    |> Dict.insert 2u8 "two"
+
+            Dict.contains unsuffixed 2 && Dict.contains narrowed 1
+            "#
+        ),
+        true,
+        bool
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn record_keys() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            dict : Dict.Dict { x : U8, y : U8 } Str
+            dict =
+                Dict.empty
+                    |> Dict.insert { x: 1, y: 2 } "a"
+                    |> Dict.insert { x: 2, y: 1 } "b"
+                    |> Dict.insert { x: 1, y: 2 } "c"
+
+            when Dict.get dict { x: 1, y: 2 } is
+                Ok v -> v
+                Err _ -> "not found"
+            "#
+        ),
+        RocStr::from("c"),
+        RocStr
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn tag_union_keys() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            dict : Dict.Dict [A, B U8, C Str] U8
+            dict =
+                Dict.empty
+                    |> Dict.insert A 1
+                    |> Dict.insert (B 7) 2
+                    |> Dict.insert (C "hello") 3
+
+            Dict.len dict + Num.toNat (Dict.get dict (C "hello") |> Result.withDefault 0)
+            "#
+        ),
+        6,
+        usize
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn eq_ignores_index_and_order() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            small : Dict.Dict U8 Str
+            small =
+                Dict.empty
+                    |> Dict.insert 1 "one"
+                    |> Dict.insert 2 "two"
+
+            large : Dict.Dict U8 Str
+            large =
+                Dict.withCapacity 100
+                    |> Dict.insert 1 "one"
+                    |> Dict.insert 2 "two"
+
+            reordered : Dict.Dict U8 Str
+            reordered =
+                Dict.empty
+                    |> Dict.insert 2 "two"
+                    |> Dict.insert 1 "one"
+
+            small == large && small == reordered
+            "#
+        ),
+        true,
+        bool
+    );
+}
@@ -0,0 +1,424 @@
+// Conformance tests for decoding JSON, as specified by https://www.rfc-editor.org/rfc/rfc8259.
+//
+// These don't run on the dev backend yet, because it can't call procedures defined outside of the
+// app module, like the ones in `Json`.
+
+#[cfg(feature = "gen-llvm")]
+use crate::helpers::llvm::assert_evals_to;
+
+#[cfg(feature = "gen-wasm")]
+use crate::helpers::wasm::assert_evals_to;
+
+#[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+use indoc::indoc;
+
+#[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+use roc_std::{RocDec, RocList, RocStr};
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn string_with_escapes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "\"a\\\"b\\\\c\\/d\\be\\ff\\ng\\rh\\ti\"" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("a\"b\\c/d\u{8}e\u{c}f\ng\rh\ti"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn string_with_unicode_escapes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "\"\\u0041\\u00e9\\u4E2D\"" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("Aé中"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn string_with_surrogate_pair() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "\"\\uD83D\\uDE00!\"" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("😀!"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn string_with_lone_surrogate() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result Str _
+                decoded = Str.toUtf8 "\"\\uDE00\"" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "decoded"
+                    Err _ -> "rejected"
+            "#
+        ),
+        RocStr::from("rejected"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn string_with_unescaped_control_character() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result Str _
+                decoded = Str.toUtf8 "\"a\nb\"" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "decoded"
+                    Err _ -> "rejected"
+            "#
+        ),
+        RocStr::from("rejected"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn string_with_whitespace_inside() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 " \" a b \" " |> Decode.fromBytes Json.fromUtf8 is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from(" a b "),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn whitespace_around_everything() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                input = "\n\t{ \"first\" : [ 1 ,\r\n 2 ] , \"second\" :\t\"x\" , \"third\" : true }\n"
+
+                decoded : Result { first : List U8, second : Str, third : Bool } _
+                decoded = Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok { first, second, third } ->
+                        if third then
+                            Str.concat second (Num.toStr (List.len first))
+                        else
+                            "<false>"
+
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("x2"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn nested_empty_collections() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                input = "{\"list\": [], \"record\": { }, \"inner\": {\"items\": [ ]}, \"last\": [3]}"
+
+                decoded : Result { list : List U8, record : {}, inner : { items : List Str }, last : List U8 } _
+                decoded = Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok { list, inner, last } -> [List.len list, List.len inner.items, List.len last]
+                    Err _ -> []
+            "#
+        ),
+        RocList::from_slice(&[0usize, 0, 1]),
+        RocList<usize>
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn empty_list() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result (List Str) _
+                decoded = Str.toUtf8 " [ ] " |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok list -> List.len list
+                    Err _ -> 100
+            "#
+        ),
+        0,
+        usize
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn empty_record() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result {} _
+                decoded = Str.toUtf8 "{ }" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok {} -> "decoded"
+                    Err _ -> "rejected"
+            "#
+        ),
+        RocStr::from("decoded"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn trailing_comma() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result (List U8) _
+                decoded = Str.toUtf8 "[1, 2,]" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "decoded"
+                    Err _ -> "rejected"
+            "#
+        ),
+        RocStr::from("rejected"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn skip_unknown_fields() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                input = "{\"skipped\": {\"a\": [null, true, false, -1.5e3, \"\\\"}\", {}, []]}, \"kept\": 7, \"null\": null}"
+
+                when Str.toUtf8 input |> Decode.fromBytes Json.fromUtf8 is
+                    Ok { kept } -> kept
+                    _ -> 100u8
+            "#
+        ),
+        7,
+        u8
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn bools() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result (List Bool) _
+                decoded = Str.toUtf8 "[ true,false ]" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok bools -> bools
+                    Err _ -> []
+            "#
+        ),
+        RocList::from_slice(&[true, false]),
+        RocList<bool>
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn negative_integer() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "-42" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok n -> n
+                    _ -> 0i64
+            "#
+        ),
+        -42,
+        i64
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn integer_with_exponent() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result (List U32) _
+                decoded = Str.toUtf8 "[1e2, 1.5E1, 2500e-2, 7.0]" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok ns -> ns
+                    Err _ -> []
+            "#
+        ),
+        RocList::from_slice(&[100u32, 15, 25, 7]),
+        RocList<u32>
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn integer_with_fraction() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result U32 _
+                decoded = Str.toUtf8 "1.25e1" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "decoded"
+                    Err _ -> "rejected"
+            "#
+        ),
+        RocStr::from("rejected"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn leading_zero() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result U32 _
+                decoded = Str.toUtf8 "012" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "decoded"
+                    Err _ -> "rejected"
+            "#
+        ),
+        RocStr::from("rejected"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn float_with_exponent() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result (List F64) _
+                decoded = Str.toUtf8 "[1.5e3, -2.5E-2, 4E+1, -0]" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok ns -> ns
+                    Err _ -> []
+            "#
+        ),
+        RocList::from_slice(&[1500.0f64, -0.025, 40.0, -0.0]),
+        RocList<f64>
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn dec_with_exponent() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Json] provides [main] to "./platform"
+
+            main =
+                when Str.toUtf8 "-1.25e-1" |> Decode.fromBytes Json.fromUtf8 is
+                    Ok n -> n
+                    _ -> 101dec
+            "#
+        ),
+        RocDec::from_str("-0.125").unwrap(),
+        RocDec
+    )
+}
@@ -3395,3 +3395,78 @@ fn list_let_generalization() {
         usize
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn list_pattern_match_on_length() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            f : List I64 -> I64
+            f = \list ->
+                when list is
+                    [] -> 0
+                    [x] -> x
+                    [_, .., y] -> y
+
+            f [] + f [7] * 10 + f [1, 2, 3] * 100
+            "#
+        ),
+        370,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
+fn list_pattern_overlapping_lengths() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            f : List I64 -> I64
+            f = \list ->
+                when list is
+                    [1, ..] -> 1
+                    [_, _] -> 2
+                    [.., 3] -> 3
+                    _ -> 4
+
+            f [1, 5] + f [5, 6] * 10 + f [5, 6, 3] * 100 + f [] * 1000
+            "#
+        ),
+        4321,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn list_pattern_bind_rest() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            when [1, 2, 3, 4] is
+                [_, ..middle, _] -> middle
+                _ -> []
+            "#
+        ),
+        RocList::from_slice(&[2, 3]),
+        RocList<i64>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn list_pattern_refcounted_elements() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            when ["foo", "bar", "a string that is longer than 24 bytes"] is
+                [.., "bar", last] -> last
+                _ -> "no match"
+            "#
+        ),
+        RocStr::from("a string that is longer than 24 bytes"),
+        RocStr
+    );
+}
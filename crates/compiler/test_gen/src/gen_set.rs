@@ -66,11 +66,11 @@ fn single_to_list() {
     assert_evals_to!(
         indoc!(
             r#"
-            Set.toList (Set.single 1.0)
+            Set.toList (Set.single 1u8)
             "#
         ),
-        RocList::from_slice(&[1.0]),
-        RocList<f64>
+        RocList::from_slice(&[1u8]),
+        RocList<u8>
     );
 }
 
@@ -182,6 +182,7 @@ pub fn helper(
         exposed_to_host: exposed_to_host.values.keys().copied().collect(),
         lazy_literals,
         generate_allocators: true, // Needed for testing, since we don't have a platform
+        runs_expects: false,
     };
 
     let target = target_lexicon::Triple::host();
@@ -370,6 +370,15 @@ fn dict() {
     "#
 }
 
+#[mono_test]
+fn set_eq() {
+    r#"
+    s = Set.single 1u8
+
+    s == s
+    "#
+}
+
 #[mono_test]
 fn list_append_closure() {
     r#"
@@ -1325,10 +1334,10 @@ fn specialize_ability_call() {
         r#"
         app "test" provides [main] to "./platform"
 
-        Hash has
-            hash : a -> U64 | a has Hash
+        MHash has
+            hash : a -> U64 | a has MHash
 
-        Id := U64 has [Hash {hash}]
+        Id := U64 has [MHash {hash}]
 
         hash : Id -> U64
         hash = \@Id n -> n
@@ -2000,3 +2009,140 @@ fn unreachable_branch_is_eliminated_but_produces_lambda_specializations() {
         "#
     )
 }
+
+#[mono_test]
+fn list_contains_specializes_eq_in_cached_module() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main = List.contains [1u8, 2] 1u8
+        "#
+    )
+}
+
+#[mono_test]
+fn match_list_patterns_of_different_lengths() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            when [1u8, 2, 3] is
+                [] -> 0u8
+                [x] -> x
+                [_, .., y] -> y
+        "#
+    )
+}
+
+#[mono_test]
+fn match_overlapping_list_patterns() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            when [5u8, 6] is
+                [1, ..] -> "starts with one"
+                [_, _] -> "two elements"
+                _ -> "other"
+        "#
+    )
+}
+
+#[mono_test]
+fn bind_list_pattern_rest() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            when [1u8, 2, 3] is
+                [first, ..rest] -> List.len rest + Num.toNat first
+                [] -> 0
+        "#
+    )
+}
+
+#[mono_test]
+fn create_and_access_tuple() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            t = (1u8, "a", 2u64)
+
+            t.0 + Num.toU8 t.2
+        "#
+    )
+}
+
+#[mono_test]
+fn destructure_tuple_in_closure() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        swap : (a, b) -> (b, a)
+        swap = \(x, y) -> (y, x)
+
+        main = swap (1u8, "a")
+        "#
+    )
+}
+
+#[mono_test]
+fn match_on_tuple() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            when (2u8, 3u8) is
+                (0, _) -> 0u8
+                (x, 1) -> x
+                (x, y) -> x + y
+        "#
+    )
+}
+
+#[mono_test]
+fn dbg_in_function() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        double : I64 -> I64
+        double = \x ->
+            dbg x
+
+            x * 2
+
+        main =
+            y =
+                dbg "computing y"
+
+                double 21
+
+            y + 1
+        "#
+    )
+}
+
+#[mono_test]
+fn dbg_refcounted_value() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            list = [1, 2, 3]
+
+            dbg list
+
+            List.len list
+        "#
+    )
+}
@@ -82,6 +82,34 @@ impl NumericRange {
         }
     }
 
+    /// The width a number in this range is compiled as, if nothing else determines its type.
+    /// Choose I64 if the range says that the number will fit, otherwise choose the next-largest
+    /// integer width.
+    pub fn default_compilation_width(&self) -> IntLitWidth {
+        use NumericRange::*;
+        match self {
+            IntAtLeastSigned(w) | NumAtLeastSigned(w) => [IntLitWidth::I64, IntLitWidth::I128]
+                .into_iter()
+                .find(|candidate| candidate.is_superset(w, true))
+                .expect("if number doesn't fit, should have been a type error"),
+            IntAtLeastEitherSign(w) | NumAtLeastEitherSign(w) => [
+                IntLitWidth::I64,
+                IntLitWidth::U64,
+                IntLitWidth::I128,
+                IntLitWidth::U128,
+            ]
+            .into_iter()
+            .find(|candidate| candidate.is_superset(w, false))
+            .expect("if number doesn't fit, should have been a type error"),
+        }
+    }
+
+    /// The variable of the type a number in this range is compiled as, if nothing else
+    /// determines its type.
+    pub fn default_compilation_variable(&self) -> Variable {
+        int_lit_width_to_variable(self.default_compilation_width())
+    }
+
     pub fn variable_slice(&self) -> &'static [Variable] {
         use NumericRange::*;
 
@@ -10,6 +10,7 @@ use std::fmt::Write;
 
 pub static WILDCARD: &str = "*";
 static EMPTY_RECORD: &str = "{}";
+static EMPTY_TUPLE: &str = "()";
 static EMPTY_TAG_UNION: &str = "[]";
 
 /// Requirements for parentheses.
@@ -255,6 +256,28 @@ fn find_names_needed(
                 find_under_alias,
             );
         }
+        Structure(Tuple(elems, ext_var)) => {
+            for index in elems.iter_variables() {
+                let var = subs[index];
+                find_names_needed(
+                    var,
+                    subs,
+                    roots,
+                    root_appearances,
+                    names_taken,
+                    find_under_alias,
+                );
+            }
+
+            find_names_needed(
+                *ext_var,
+                subs,
+                roots,
+                root_appearances,
+                names_taken,
+                find_under_alias,
+            );
+        }
         Structure(TagUnion(tags, ext_var)) => {
             for slice_index in tags.variables() {
                 let slice = subs[slice_index];
@@ -402,7 +425,11 @@ fn find_names_needed(
                 find_under_alias,
             );
         }
-        Error | Structure(Erroneous(_)) | Structure(EmptyRecord) | Structure(EmptyTagUnion) => {
+        Error
+        | Structure(Erroneous(_))
+        | Structure(EmptyRecord)
+        | Structure(EmptyTuple)
+        | Structure(EmptyTagUnion) => {
             // Errors and empty records don't need names.
         }
     }
@@ -511,9 +538,9 @@ fn set_root_name(root: Variable, name: Lowercase, subs: &mut Subs) {
             let content = FlexVar(Some(name_index));
             subs.set_content(root, content);
         }
-        &FlexAbleVar(_, ability) => {
+        &FlexAbleVar(_, abilities) => {
             let name_index = SubsIndex::push_new(&mut subs.field_names, name);
-            let content = FlexAbleVar(Some(name_index), ability);
+            let content = FlexAbleVar(Some(name_index), abilities);
             subs.set_content(root, content);
         }
         RecursionVar {
@@ -621,11 +648,13 @@ fn write_content<'a>(
             let name = &subs.field_names[name_index.index as usize];
             buf.push_str(name.as_str())
         }
-        FlexAbleVar(opt_name_index, ability) => {
+        FlexAbleVar(opt_name_index, abilities) => {
             let name = opt_name_index
                 .map(|name_index| subs.field_names[name_index.index as usize].as_str())
                 .unwrap_or(WILDCARD);
-            ctx.able_variables.push((name, *ability));
+            for &ability in subs.get_subs_slice(*abilities) {
+                ctx.able_variables.push((name, ability));
+            }
             buf.push_str(name);
         }
         RigidAbleVar(name_index, ability) => {
@@ -920,6 +949,7 @@ impl<'a> ExtContent<'a> {
         match content {
             Content::Structure(FlatType::EmptyTagUnion) => ExtContent::Empty,
             Content::Structure(FlatType::EmptyRecord) => ExtContent::Empty,
+            Content::Structure(FlatType::EmptyTuple) => ExtContent::Empty,
 
             Content::FlexVar(_)
             | Content::FlexAbleVar(..)
@@ -1062,6 +1092,7 @@ fn write_flat_type<'a>(
             parens,
         ),
         EmptyRecord => buf.push_str(EMPTY_RECORD),
+        EmptyTuple => buf.push_str(EMPTY_TUPLE),
         EmptyTagUnion => buf.push_str(EMPTY_TAG_UNION),
         Func(args, closure, ret) => write_fn(
             env,
@@ -1137,6 +1168,50 @@ fn write_flat_type<'a>(
                 }
             }
         }
+        Tuple(elems, ext_var) => {
+            use crate::types::{gather_tuple_elems, TupleStructure};
+
+            // If the `ext` has concrete elements, merge them
+            let TupleStructure {
+                elems: sorted_elems,
+                ext,
+            } = gather_tuple_elems(subs, *elems, *ext_var)
+                .expect("Something ended up weird in this tuple type");
+
+            buf.push_str("( ");
+
+            // Positions we know nothing about yet (e.g. the first element of the tuple
+            // `t` in `t.1`) are shown as `*`
+            let mut next_index = 0;
+            for (index, var) in sorted_elems {
+                while next_index < index {
+                    if next_index > 0 {
+                        buf.push_str(", ");
+                    }
+                    buf.push_str(WILDCARD);
+                    next_index += 1;
+                }
+
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+
+                write_content(
+                    env,
+                    ctx,
+                    subs.get_content_without_compacting(var),
+                    subs,
+                    buf,
+                    Parens::Unnecessary,
+                );
+
+                next_index = index + 1;
+            }
+
+            buf.push_str(" )");
+
+            write_ext_content(env, ctx, subs, buf, ExtContent::from_var(subs, ext), parens)
+        }
         TagUnion(tags, ext_var) => {
             buf.push('[');
 
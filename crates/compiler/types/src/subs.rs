@@ -1,6 +1,7 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 use crate::types::{
-    name_type_var, AliasKind, ErrorType, Problem, RecordField, RecordFieldsError, TypeExt, Uls,
+    name_type_var, AliasKind, ErrorType, Problem, RecordField, RecordFieldsError, TupleElemsError,
+    TypeExt, Uls,
 };
 use roc_collections::all::{FnvMap, ImMap, ImSet, MutSet, SendMap};
 use roc_collections::{VecMap, VecSet};
@@ -75,13 +76,15 @@ struct SubsHeader {
     closure_names: u64,
     field_names: u64,
     record_fields: u64,
+    tuple_elem_indices: u64,
     variable_slices: u64,
     unspecialized_lambda_sets: u64,
+    uls_of_var: u64,
     exposed_vars_by_symbol: u64,
 }
 
 impl SubsHeader {
-    fn from_subs(subs: &Subs, exposed_vars_by_symbol: usize) -> Self {
+    fn from_subs(subs: &Subs, uls_of_var: usize, exposed_vars_by_symbol: usize) -> Self {
         // TODO what do we do with problems? they should
         // be reported and then removed from Subs I think
         debug_assert!(subs.problems.is_empty(), "{:?}", &subs.problems);
@@ -93,8 +96,10 @@ impl SubsHeader {
             closure_names: subs.closure_names.len() as u64,
             field_names: subs.field_names.len() as u64,
             record_fields: subs.record_fields.len() as u64,
+            tuple_elem_indices: subs.tuple_elem_indices.len() as u64,
             variable_slices: subs.variable_slices.len() as u64,
             unspecialized_lambda_sets: subs.unspecialized_lambda_sets.len() as u64,
+            uls_of_var: uls_of_var as u64,
             exposed_vars_by_symbol: exposed_vars_by_symbol as u64,
         }
     }
@@ -131,7 +136,12 @@ impl Subs {
     ) -> std::io::Result<usize> {
         let mut written = 0;
 
-        let header = SubsHeader::from_subs(self, exposed_vars_by_symbol.len()).to_array();
+        // Keep track of the lambda sets depending on ability-bound variables, so that they can
+        // still be specialized when the cached module is monomorphized.
+        let uls_of_var: Vec<(Variable, Variable)> = self.uls_of_var.flatten().collect();
+
+        let header =
+            SubsHeader::from_subs(self, uls_of_var.len(), exposed_vars_by_symbol.len()).to_array();
         written += header.len();
         writer.write_all(&header)?;
 
@@ -142,8 +152,10 @@ impl Subs {
         written = Self::serialize_slice(&self.closure_names, writer, written)?;
         written = Self::serialize_field_names(&self.field_names, writer, written)?;
         written = Self::serialize_slice(&self.record_fields, writer, written)?;
+        written = Self::serialize_slice(&self.tuple_elem_indices, writer, written)?;
         written = Self::serialize_slice(&self.variable_slices, writer, written)?;
         written = Self::serialize_slice(&self.unspecialized_lambda_sets, writer, written)?;
+        written = Self::serialize_slice(&uls_of_var, writer, written)?;
         written = Self::serialize_slice(exposed_vars_by_symbol, writer, written)?;
 
         Ok(written)
@@ -190,7 +202,7 @@ impl Subs {
         Self::serialize_slice(&buf, writer, written)
     }
 
-    pub(crate) fn serialize_slice<T>(
+    pub fn serialize_slice<T>(
         slice: &[T],
         writer: &mut impl std::io::Write,
         written: usize,
@@ -208,7 +220,8 @@ impl Subs {
         Ok(written + padding_bytes + bytes_slice.len())
     }
 
-    pub fn deserialize(bytes: &[u8]) -> (Self, &[(Symbol, Variable)]) {
+    /// Also returns the offset right after the subs, where anything serialized after them starts.
+    pub fn deserialize(bytes: &[u8]) -> (Self, &[(Symbol, Variable)], usize) {
         let mut offset = 0;
         let header_slice = &bytes[..std::mem::size_of::<SubsHeader>()];
         offset += header_slice.len();
@@ -225,11 +238,18 @@ impl Subs {
             Self::deserialize_field_names(bytes, header.field_names as usize, offset);
         let (record_fields, offset) =
             Self::deserialize_slice(bytes, header.record_fields as usize, offset);
+        let (tuple_elem_indices, offset) =
+            Self::deserialize_slice(bytes, header.tuple_elem_indices as usize, offset);
         let (variable_slices, offset) =
             Self::deserialize_slice(bytes, header.variable_slices as usize, offset);
         let (unspecialized_lambda_sets, offset) =
             Self::deserialize_slice(bytes, header.unspecialized_lambda_sets as usize, offset);
-        let (exposed_vars_by_symbol, _) =
+        let (uls_of_var, offset) = Self::deserialize_slice::<(Variable, Variable)>(
+            bytes,
+            header.uls_of_var as usize,
+            offset,
+        );
+        let (exposed_vars_by_symbol, offset) =
             Self::deserialize_slice(bytes, header.exposed_vars_by_symbol as usize, offset);
 
         (
@@ -240,13 +260,15 @@ impl Subs {
                 closure_names: closure_names.to_vec(),
                 field_names,
                 record_fields: record_fields.to_vec(),
+                tuple_elem_indices: tuple_elem_indices.to_vec(),
                 variable_slices: variable_slices.to_vec(),
                 unspecialized_lambda_sets: unspecialized_lambda_sets.to_vec(),
                 tag_name_cache: Default::default(),
                 problems: Default::default(),
-                uls_of_var: Default::default(),
+                uls_of_var: UlsOfVar::from_flat(uls_of_var),
             },
             exposed_vars_by_symbol,
+            offset,
         )
     }
 
@@ -291,11 +313,7 @@ impl Subs {
         (tag_names, offset)
     }
 
-    pub(crate) fn deserialize_slice<T>(
-        bytes: &[u8],
-        length: usize,
-        mut offset: usize,
-    ) -> (&[T], usize) {
+    pub fn deserialize_slice<T>(bytes: &[u8], length: usize, mut offset: usize) -> (&[T], usize) {
         let alignment = std::mem::align_of::<T>();
         let size = std::mem::size_of::<T>();
 
@@ -353,6 +371,21 @@ impl UlsOfVar {
         self.0.len()
     }
 
+    /// Every (variable, dependent lambda set) pair, for serialization.
+    fn flatten(&self) -> impl Iterator<Item = (Variable, Variable)> + '_ {
+        self.0
+            .iter()
+            .flat_map(|(var, lambda_sets)| lambda_sets.iter().map(move |lset| (*var, *lset)))
+    }
+
+    fn from_flat(pairs: &[(Variable, Variable)]) -> Self {
+        let mut uls_of_var = Self::default();
+        for &(var, lambda_set) in pairs {
+            uls_of_var.add(var, lambda_set);
+        }
+        uls_of_var
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
@@ -389,6 +422,7 @@ pub struct Subs {
     pub closure_names: Vec<Symbol>,
     pub field_names: Vec<Lowercase>,
     pub record_fields: Vec<RecordField<()>>,
+    pub tuple_elem_indices: Vec<usize>,
     pub variable_slices: Vec<VariableSubsSlice>,
     pub unspecialized_lambda_sets: Vec<Uls>,
     pub tag_name_cache: TagNameCache,
@@ -525,6 +559,14 @@ impl std::ops::IndexMut<SubsIndex<RecordField<()>>> for Subs {
     }
 }
 
+impl std::ops::Index<SubsIndex<usize>> for Subs {
+    type Output = usize;
+
+    fn index(&self, index: SubsIndex<usize>) -> &Self::Output {
+        &self.tuple_elem_indices[index.index as usize]
+    }
+}
+
 impl std::ops::Index<SubsIndex<VariableSubsSlice>> for Subs {
     type Output = VariableSubsSlice;
 
@@ -736,6 +778,12 @@ impl GetSubsSlice<RecordField<()>> for Subs {
     }
 }
 
+impl GetSubsSlice<usize> for Subs {
+    fn get_subs_slice(&self, subs_slice: SubsSlice<usize>) -> &[usize] {
+        subs_slice.get_slice(&self.tuple_elem_indices)
+    }
+}
+
 impl GetSubsSlice<Lowercase> for Subs {
     fn get_subs_slice(&self, subs_slice: SubsSlice<Lowercase>) -> &[Lowercase] {
         subs_slice.get_slice(&self.field_names)
@@ -809,12 +857,13 @@ fn subs_fmt_content(this: &Content, subs: &Subs, f: &mut fmt::Formatter) -> fmt:
             };
             write!(f, "Flex({})", name)
         }
-        Content::FlexAbleVar(name, symbol) => {
+        Content::FlexAbleVar(name, abilities) => {
             let name = match name {
                 Some(index) => subs[*index].as_str(),
                 None => "_",
             };
-            write!(f, "FlexAble({}, {:?})", name, symbol)
+            let abilities = subs.get_subs_slice(*abilities);
+            write!(f, "FlexAble({}, {:?})", name, abilities)
         }
         Content::RigidVar(name) => write!(f, "Rigid({:?})", name),
         Content::RigidAbleVar(name, symbol) => write!(f, "RigidAble({:?}, {:?})", name, symbol),
@@ -943,6 +992,21 @@ fn subs_fmt_flat_type(this: &FlatType, subs: &Subs, f: &mut fmt::Formatter) -> f
 
             write!(f, "}}<{:?}>", new_ext)
         }
+        FlatType::Tuple(elems, ext) => {
+            write!(f, "( ")?;
+
+            let (it, new_ext) = elems.sorted_iterator_and_ext(subs, *ext);
+            for (index, var) in it {
+                write!(
+                    f,
+                    "{:?}: {:?}, ",
+                    index,
+                    SubsFmtContent(subs.get_content_without_compacting(var), subs)
+                )?;
+            }
+
+            write!(f, ")<{:?}>", new_ext)
+        }
         FlatType::TagUnion(tags, ext) => {
             write!(f, "[")?;
 
@@ -983,6 +1047,7 @@ fn subs_fmt_flat_type(this: &FlatType, subs: &Subs, f: &mut fmt::Formatter) -> f
         }
         FlatType::Erroneous(e) => write!(f, "Erroneous({:?})", e),
         FlatType::EmptyRecord => write!(f, "EmptyRecord"),
+        FlatType::EmptyTuple => write!(f, "EmptyTuple"),
         FlatType::EmptyTagUnion => write!(f, "EmptyTagUnion"),
     }
 }
@@ -1706,6 +1771,7 @@ impl Subs {
             closure_names: Vec::new(),
             field_names: Vec::new(),
             record_fields: Vec::new(),
+            tuple_elem_indices: Vec::new(),
             // store an empty slice at the first position
             // used for "TagOrFunction"
             variable_slices: vec![VariableSubsSlice::default()],
@@ -1790,6 +1856,43 @@ impl Subs {
         self.fresh(Descriptor::from(unnamed_flex_var()))
     }
 
+    /// Stores a set of abilities a [Content::FlexAbleVar] is bound to.
+    pub fn new_ability_set(
+        &mut self,
+        abilities: impl IntoIterator<Item = Symbol>,
+    ) -> SubsSlice<Symbol> {
+        SubsSlice::extend_new(&mut self.closure_names, abilities)
+    }
+
+    /// Unions two sets of abilities, leaving out any ability that is implied by another
+    /// member of the result.
+    pub fn merge_abilities(
+        &mut self,
+        left: SubsSlice<Symbol>,
+        right: SubsSlice<Symbol>,
+    ) -> SubsSlice<Symbol> {
+        let mut merged = self.get_subs_slice(left).to_vec();
+
+        for &ability in self.get_subs_slice(right) {
+            if merged.iter().any(|other| other.ability_implies(ability)) {
+                continue;
+            }
+
+            merged.retain(|other| !ability.ability_implies(*other));
+            merged.push(ability);
+        }
+
+        merged.sort();
+
+        if merged == self.get_subs_slice(left) {
+            left
+        } else if merged == self.get_subs_slice(right) {
+            right
+        } else {
+            self.new_ability_set(merged)
+        }
+    }
+
     pub fn rigid_var(&mut self, var: Variable, name: Lowercase) {
         let name_index = SubsIndex::push_new(&mut self.field_names, name);
         let content = Content::RigidVar(name_index);
@@ -2248,9 +2351,9 @@ pub enum Content {
     FlexVar(Option<SubsIndex<Lowercase>>),
     /// name given in a user-written annotation
     RigidVar(SubsIndex<Lowercase>),
-    /// Like a [Self::FlexVar], but is also bound to an ability.
-    /// This can only happen when unified with a [Self::RigidAbleVar].
-    FlexAbleVar(Option<SubsIndex<Lowercase>>, Symbol),
+    /// Like a [Self::FlexVar], but is also bound to one or more abilities.
+    /// The abilities live in [Subs::closure_names].
+    FlexAbleVar(Option<SubsIndex<Lowercase>>, SubsSlice<Symbol>),
     /// Like a [Self::RigidVar], but is also bound to an ability.
     /// For example, "a has Hash".
     RigidAbleVar(SubsIndex<Lowercase>, Symbol),
@@ -2423,11 +2526,13 @@ pub enum FlatType {
     Apply(Symbol, VariableSubsSlice),
     Func(VariableSubsSlice, Variable, Variable),
     Record(RecordFields, Variable),
+    Tuple(TupleElems, Variable),
     TagUnion(UnionTags, Variable),
     FunctionOrTagUnion(SubsIndex<TagName>, Symbol, Variable),
     RecursiveTagUnion(Variable, UnionTags, Variable),
     Erroneous(SubsIndex<Problem>),
     EmptyRecord,
+    EmptyTuple,
     EmptyTagUnion,
 }
 
@@ -3102,6 +3207,108 @@ impl RecordFields {
     }
 }
 
+/// The elements of a tuple type. Like record fields, each element is stored alongside its
+/// (0-based) position, so that a tuple type can be open: `t.1` only tells us that `t` has an
+/// element at position 1.
+#[derive(Clone, Copy, Debug)]
+pub struct TupleElems {
+    pub length: u16,
+    pub elem_index_start: u32,
+    pub variables_start: u32,
+}
+
+impl TupleElems {
+    pub const fn len(&self) -> usize {
+        self.length as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            length: 0,
+            elem_index_start: 0,
+            variables_start: 0,
+        }
+    }
+
+    pub const fn variables(&self) -> SubsSlice<Variable> {
+        SubsSlice::new(self.variables_start, self.length)
+    }
+
+    pub const fn elem_indices(&self) -> SubsSlice<usize> {
+        SubsSlice::new(self.elem_index_start, self.length)
+    }
+
+    pub fn iter_variables(&self) -> impl Iterator<Item = SubsIndex<Variable>> {
+        self.variables().into_iter()
+    }
+
+    pub fn insert_into_subs<I>(subs: &mut Subs, input: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, Variable)>,
+    {
+        let elem_index_start = subs.tuple_elem_indices.len() as u32;
+        let variables_start = subs.variables.len() as u32;
+
+        let it = input.into_iter();
+        let size_hint = it.size_hint().0;
+
+        subs.tuple_elem_indices.reserve(size_hint);
+        subs.variables.reserve(size_hint);
+
+        let mut length = 0;
+        for (index, var) in it {
+            subs.tuple_elem_indices.push(index);
+            subs.variables.push(var);
+
+            length += 1;
+        }
+
+        TupleElems {
+            length,
+            elem_index_start,
+            variables_start,
+        }
+    }
+
+    #[inline(always)]
+    pub fn unsorted_iterator<'a>(
+        &'a self,
+        subs: &'a Subs,
+        ext: Variable,
+    ) -> Result<impl Iterator<Item = (usize, Variable)> + 'a, TupleElemsError> {
+        let (it, _) = crate::types::gather_tuple_elems_unsorted_iter(subs, *self, ext)?;
+
+        Ok(it)
+    }
+
+    /// get a sorted iterator over the elements of this tuple type, chasing the `ext` var
+    pub fn sorted_iterator_and_ext(
+        &self,
+        subs: &Subs,
+        ext: Variable,
+    ) -> (std::vec::IntoIter<(usize, Variable)>, Variable) {
+        let tuple_structure = crate::types::gather_tuple_elems(subs, *self, ext)
+            .expect("Something ended up weird in this tuple type");
+
+        (tuple_structure.elems.into_iter(), tuple_structure.ext)
+    }
+
+    pub fn iter_all(&self) -> impl Iterator<Item = (SubsIndex<usize>, SubsIndex<Variable>)> {
+        let helper = |start| start..(start + self.length as u32);
+
+        let range1 = helper(self.elem_index_start);
+        let range2 = helper(self.variables_start);
+
+        range1
+            .zip(range2)
+            .map(|(i1, i2)| (SubsIndex::new(i1), SubsIndex::new(i2)))
+    }
+}
+
 fn is_empty_record(subs: &Subs, mut var: Variable) -> bool {
     use crate::subs::Content::*;
     use crate::subs::FlatType::*;
@@ -3169,6 +3376,10 @@ fn occurs(
                         let it = once(ext_var).chain(subs.get_subs_slice(slice).iter());
                         short_circuit(subs, root_var, &new_seen, it)
                     }
+                    Tuple(elems, ext_var) => {
+                        let it = once(ext_var).chain(subs.get_subs_slice(elems.variables()).iter());
+                        short_circuit(subs, root_var, &new_seen, it)
+                    }
                     TagUnion(tags, ext_var) => {
                         occurs_union(subs, root_var, &new_seen, tags)?;
 
@@ -3183,7 +3394,7 @@ fn occurs(
 
                         short_circuit_help(subs, root_var, &new_seen, *ext_var)
                     }
-                    EmptyRecord | EmptyTagUnion | Erroneous(_) => Ok(()),
+                    EmptyRecord | EmptyTuple | EmptyTagUnion | Erroneous(_) => Ok(()),
                 }
             }
             Alias(_, args, _, _) => {
@@ -3353,8 +3564,19 @@ fn explicit_substitute(
 
                         subs.set_content(in_var, Structure(Record(vars_by_field, new_ext_var)));
                     }
+                    Tuple(elems, ext_var) => {
+                        let new_ext_var = explicit_substitute(subs, from, to, ext_var, seen);
+
+                        for index in elems.iter_variables() {
+                            let var = subs[index];
+                            let new_var = explicit_substitute(subs, from, to, var, seen);
+                            subs[index] = new_var;
+                        }
+
+                        subs.set_content(in_var, Structure(Tuple(elems, new_ext_var)));
+                    }
 
-                    EmptyRecord | EmptyTagUnion | Erroneous(_) => {}
+                    EmptyRecord | EmptyTuple | EmptyTagUnion | Erroneous(_) => {}
                 }
 
                 in_var
@@ -3537,9 +3759,10 @@ fn get_var_names(
                     accum
                 }
 
-                FlatType::EmptyRecord | FlatType::EmptyTagUnion | FlatType::Erroneous(_) => {
-                    taken_names
-                }
+                FlatType::EmptyRecord
+                | FlatType::EmptyTuple
+                | FlatType::EmptyTagUnion
+                | FlatType::Erroneous(_) => taken_names,
 
                 FlatType::Record(vars_by_field, ext_var) => {
                     let mut accum = get_var_names(subs, ext_var, taken_names);
@@ -3552,6 +3775,17 @@ fn get_var_names(
 
                     accum
                 }
+                FlatType::Tuple(elems, ext_var) => {
+                    let mut accum = get_var_names(subs, ext_var, taken_names);
+
+                    for var_index in elems.iter_variables() {
+                        let arg_var = subs[var_index];
+
+                        accum = get_var_names(subs, arg_var, accum)
+                    }
+
+                    accum
+                }
                 FlatType::TagUnion(tags, ext_var) => {
                     let taken_names = get_var_names(subs, ext_var, taken_names);
                     get_var_names_union(subs, tags, taken_names)
@@ -3702,7 +3936,9 @@ fn content_to_err_type(
                 }
             };
 
-            ErrorType::FlexAbleVar(name, ability)
+            let abilities = subs.get_subs_slice(ability).to_vec();
+
+            ErrorType::FlexAbleVar(name, abilities)
         }
 
         RigidAbleVar(name_index, ability) => {
@@ -3816,6 +4052,7 @@ fn flat_type_to_err_type(
         }
 
         EmptyRecord => ErrorType::Record(SendMap::default(), TypeExt::Closed),
+        EmptyTuple => ErrorType::Tuple(Vec::new(), TypeExt::Closed),
         EmptyTagUnion => ErrorType::TagUnion(SendMap::default(), TypeExt::Closed),
 
         Record(vars_by_field, ext_var) => {
@@ -3857,6 +4094,39 @@ fn flat_type_to_err_type(
             }
         }
 
+        Tuple(elems, ext_var) => {
+            let mut err_elems = Vec::with_capacity(elems.len());
+
+            for (i1, i2) in elems.iter_all() {
+                let index = subs[i1];
+                let var = subs[i2];
+
+                err_elems.push((index, var_to_err_type(subs, state, var)));
+            }
+
+            match var_to_err_type(subs, state, ext_var).unwrap_structural_alias() {
+                ErrorType::Tuple(sub_elems, sub_ext) => {
+                    err_elems.extend(sub_elems);
+                    err_elems.sort_by_key(|(index, _)| *index);
+
+                    ErrorType::Tuple(err_elems, sub_ext)
+                }
+
+                ErrorType::FlexVar(var) => {
+                    err_elems.sort_by_key(|(index, _)| *index);
+                    ErrorType::Tuple(err_elems, TypeExt::FlexOpen(var))
+                }
+
+                ErrorType::RigidVar(var) => {
+                    err_elems.sort_by_key(|(index, _)| *index);
+                    ErrorType::Tuple(err_elems, TypeExt::RigidOpen(var))
+                }
+
+                other =>
+                    panic!("Tried to convert a tuple extension to an error, but the tuple extension had the ErrorType of {:?}", other)
+            }
+        }
+
         TagUnion(tags, ext_var) => {
             let err_tags = union_tags_to_err_tags(subs, state, tags);
 
@@ -4014,6 +4284,7 @@ struct StorageSubsOffsets {
     closure_names: u32,
     field_names: u32,
     record_fields: u32,
+    tuple_elem_indices: u32,
     variable_slices: u32,
     unspecialized_lambda_sets: u32,
     problems: u32,
@@ -4098,6 +4369,7 @@ impl StorageSubs {
             closure_names: self.subs.closure_names.len() as u32,
             field_names: self.subs.field_names.len() as u32,
             record_fields: self.subs.record_fields.len() as u32,
+            tuple_elem_indices: self.subs.tuple_elem_indices.len() as u32,
             variable_slices: self.subs.variable_slices.len() as u32,
             unspecialized_lambda_sets: self.subs.unspecialized_lambda_sets.len() as u32,
             problems: self.subs.problems.len() as u32,
@@ -4110,6 +4382,7 @@ impl StorageSubs {
             closure_names: target.closure_names.len() as u32,
             field_names: target.field_names.len() as u32,
             record_fields: target.record_fields.len() as u32,
+            tuple_elem_indices: target.tuple_elem_indices.len() as u32,
             variable_slices: target.variable_slices.len() as u32,
             unspecialized_lambda_sets: target.unspecialized_lambda_sets.len() as u32,
             problems: target.problems.len() as u32,
@@ -4158,6 +4431,9 @@ impl StorageSubs {
         target.closure_names.extend(self.subs.closure_names);
         target.field_names.extend(self.subs.field_names);
         target.record_fields.extend(self.subs.record_fields);
+        target
+            .tuple_elem_indices
+            .extend(self.subs.tuple_elem_indices);
         target
             .unspecialized_lambda_sets
             .extend(self.subs.unspecialized_lambda_sets);
@@ -4198,6 +4474,10 @@ impl StorageSubs {
                 Self::offset_record_fields(offsets, *record_fields),
                 Self::offset_variable(offsets, *ext),
             ),
+            FlatType::Tuple(elems, ext) => FlatType::Tuple(
+                Self::offset_tuple_elems(offsets, *elems),
+                Self::offset_variable(offsets, *ext),
+            ),
             FlatType::TagUnion(union_tags, ext) => FlatType::TagUnion(
                 Self::offset_tag_union(offsets, *union_tags),
                 Self::offset_variable(offsets, *ext),
@@ -4216,6 +4496,7 @@ impl StorageSubs {
                 FlatType::Erroneous(Self::offset_problem(offsets, *problem))
             }
             FlatType::EmptyRecord => FlatType::EmptyRecord,
+            FlatType::EmptyTuple => FlatType::EmptyTuple,
             FlatType::EmptyTagUnion => FlatType::EmptyTagUnion,
         }
     }
@@ -4226,7 +4507,9 @@ impl StorageSubs {
         match content {
             FlexVar(opt_name) => FlexVar(*opt_name),
             RigidVar(name) => RigidVar(*name),
-            FlexAbleVar(opt_name, ability) => FlexAbleVar(*opt_name, *ability),
+            FlexAbleVar(opt_name, abilities) => {
+                FlexAbleVar(*opt_name, Self::offset_symbol_slice(offsets, *abilities))
+            }
             RigidAbleVar(name, ability) => RigidAbleVar(*name, *ability),
             RecursionVar {
                 structure,
@@ -4258,6 +4541,15 @@ impl StorageSubs {
         }
     }
 
+    fn offset_symbol_slice(
+        offsets: &StorageSubsOffsets,
+        mut symbols: SubsSlice<Symbol>,
+    ) -> SubsSlice<Symbol> {
+        symbols.start += offsets.closure_names;
+
+        symbols
+    }
+
     fn offset_alias_variables(
         offsets: &StorageSubsOffsets,
         mut alias_variables: AliasVariables,
@@ -4295,6 +4587,13 @@ impl StorageSubs {
         record_fields
     }
 
+    fn offset_tuple_elems(offsets: &StorageSubsOffsets, mut elems: TupleElems) -> TupleElems {
+        elems.elem_index_start += offsets.tuple_elem_indices;
+        elems.variables_start += offsets.variables;
+
+        elems
+    }
+
     fn offset_tag_name_index(
         offsets: &StorageSubsOffsets,
         mut tag_name: SubsIndex<TagName>,
@@ -4498,7 +4797,10 @@ fn storage_copy_var_to_help(env: &mut StorageCopyVarToEnv<'_>, var: Variable) ->
                     Func(new_arguments, new_closure_var, new_ret_var)
                 }
 
-                same @ EmptyRecord | same @ EmptyTagUnion | same @ Erroneous(_) => same,
+                same @ EmptyRecord
+                | same @ EmptyTuple
+                | same @ EmptyTagUnion
+                | same @ Erroneous(_) => same,
 
                 Record(fields, ext_var) => {
                     let record_fields = {
@@ -4535,6 +4837,36 @@ fn storage_copy_var_to_help(env: &mut StorageCopyVarToEnv<'_>, var: Variable) ->
                     Record(record_fields, storage_copy_var_to_help(env, ext_var))
                 }
 
+                Tuple(elems, ext_var) => {
+                    let new_elems = {
+                        let new_variables =
+                            VariableSubsSlice::reserve_into_subs(env.target, elems.len());
+
+                        let it = (new_variables.indices()).zip(elems.iter_variables());
+                        for (target_index, var_index) in it {
+                            let var = env.source[var_index];
+                            let copy_var = storage_copy_var_to_help(env, var);
+                            env.target.variables[target_index] = copy_var;
+                        }
+
+                        let elem_index_start = env.target.tuple_elem_indices.len() as u32;
+
+                        let elem_indices =
+                            &env.source.tuple_elem_indices[elems.elem_indices().indices()];
+                        env.target
+                            .tuple_elem_indices
+                            .extend(elem_indices.iter().copied());
+
+                        TupleElems {
+                            length: elems.len() as _,
+                            elem_index_start,
+                            variables_start: new_variables.start,
+                        }
+                    };
+
+                    Tuple(new_elems, storage_copy_var_to_help(env, ext_var))
+                }
+
                 TagUnion(tags, ext_var) => {
                     let new_ext = storage_copy_var_to_help(env, ext_var);
                     let union_tags = storage_copy_union(env, tags);
@@ -4606,13 +4938,14 @@ fn storage_copy_var_to_help(env: &mut StorageCopyVarToEnv<'_>, var: Variable) ->
             copy
         }
 
-        FlexAbleVar(opt_name_index, ability) => {
+        FlexAbleVar(opt_name_index, abilities) => {
             let new_name_index = opt_name_index.map(|name_index| {
                 let name = env.source.field_names[name_index.index as usize].clone();
                 SubsIndex::push_new(&mut env.target.field_names, name)
             });
+            let new_abilities = copy_ability_slice(env.source, env.target, abilities);
 
-            let content = FlexAbleVar(new_name_index, ability);
+            let content = FlexAbleVar(new_name_index, new_abilities);
             env.target.set_content(copy, content);
 
             copy
@@ -4621,9 +4954,10 @@ fn storage_copy_var_to_help(env: &mut StorageCopyVarToEnv<'_>, var: Variable) ->
         RigidAbleVar(name_index, ability) => {
             let name = env.source.field_names[name_index.index as usize].clone();
             let new_name_index = SubsIndex::push_new(&mut env.target.field_names, name);
+            let abilities = env.target.new_ability_set([ability]);
             env.target.set(
                 copy,
-                make_descriptor(FlexAbleVar(Some(new_name_index), ability)),
+                make_descriptor(FlexAbleVar(Some(new_name_index), abilities)),
             );
 
             copy
@@ -4850,6 +5184,14 @@ fn copy_union<L: Label>(
     UnionLabels::from_slices(new_tag_names, new_variable_slices)
 }
 
+fn copy_ability_slice(
+    source: &Subs,
+    target: &mut Subs,
+    abilities: SubsSlice<Symbol>,
+) -> SubsSlice<Symbol> {
+    target.new_ability_set(source.get_subs_slice(abilities).iter().copied())
+}
+
 fn copy_import_to_help(env: &mut CopyImportEnv<'_>, max_rank: Rank, var: Variable) -> Variable {
     use Content::*;
     use FlatType::*;
@@ -4936,7 +5278,7 @@ fn copy_import_to_help(env: &mut CopyImportEnv<'_>, max_rank: Rank, var: Variabl
 
                 Erroneous(_) => internal_error!("I thought this was handled above"),
 
-                same @ EmptyRecord | same @ EmptyTagUnion => same,
+                same @ EmptyRecord | same @ EmptyTuple | same @ EmptyTagUnion => same,
 
                 Record(fields, ext_var) => {
                     let record_fields = {
@@ -4973,6 +5315,36 @@ fn copy_import_to_help(env: &mut CopyImportEnv<'_>, max_rank: Rank, var: Variabl
                     Record(record_fields, copy_import_to_help(env, max_rank, ext_var))
                 }
 
+                Tuple(elems, ext_var) => {
+                    let new_elems = {
+                        let new_variables =
+                            VariableSubsSlice::reserve_into_subs(env.target, elems.len());
+
+                        let it = (new_variables.indices()).zip(elems.iter_variables());
+                        for (target_index, var_index) in it {
+                            let var = env.source[var_index];
+                            let copy_var = copy_import_to_help(env, max_rank, var);
+                            env.target.variables[target_index] = copy_var;
+                        }
+
+                        let elem_index_start = env.target.tuple_elem_indices.len() as u32;
+
+                        let elem_indices =
+                            &env.source.tuple_elem_indices[elems.elem_indices().indices()];
+                        env.target
+                            .tuple_elem_indices
+                            .extend(elem_indices.iter().copied());
+
+                        TupleElems {
+                            length: elems.len() as _,
+                            elem_index_start,
+                            variables_start: new_variables.start,
+                        }
+                    };
+
+                    Tuple(new_elems, copy_import_to_help(env, max_rank, ext_var))
+                }
+
                 TagUnion(tags, ext_var) => {
                     let new_ext = copy_import_to_help(env, max_rank, ext_var);
 
@@ -5023,14 +5395,15 @@ fn copy_import_to_help(env: &mut CopyImportEnv<'_>, max_rank: Rank, var: Variabl
             copy
         }
 
-        FlexAbleVar(opt_name_index, ability) => {
-            if let Some(name_index) = opt_name_index {
+        FlexAbleVar(opt_name_index, abilities) => {
+            let new_name_index = opt_name_index.map(|name_index| {
                 let name = env.source.field_names[name_index.index as usize].clone();
-                let new_name_index = SubsIndex::push_new(&mut env.target.field_names, name);
+                SubsIndex::push_new(&mut env.target.field_names, name)
+            });
+            let new_abilities = copy_ability_slice(env.source, env.target, abilities);
 
-                let content = FlexAbleVar(Some(new_name_index), ability);
-                env.target.set_content(copy, content);
-            }
+            let content = FlexAbleVar(new_name_index, new_abilities);
+            env.target.set_content(copy, content);
 
             env.flex_able.push(copy);
 
@@ -5218,9 +5591,10 @@ fn instantiate_rigids_help(subs: &mut Subs, max_rank: Rank, initial: Variable) {
             }
             &RigidAbleVar(name, ability) => {
                 // Same as `RigidVar` above
+                let abilities = subs.new_ability_set([ability]);
                 subs.modify(var, |d| {
                     *d = Descriptor {
-                        content: FlexAbleVar(Some(name), ability),
+                        content: FlexAbleVar(Some(name), abilities),
                         rank: max_rank,
                         mark: Mark::NONE,
                         copy: OptVariable::NONE,
@@ -5250,6 +5624,7 @@ fn instantiate_rigids_help(subs: &mut Subs, max_rank: Rank, initial: Variable) {
                 }
 
                 EmptyRecord => (),
+                EmptyTuple => (),
                 EmptyTagUnion => (),
 
                 Record(fields, ext_var) => {
@@ -5259,6 +5634,13 @@ fn instantiate_rigids_help(subs: &mut Subs, max_rank: Rank, initial: Variable) {
 
                     stack.push(ext_var);
                 }
+                Tuple(elems, ext_var) => {
+                    let elems = *elems;
+                    let ext_var = *ext_var;
+                    stack.extend(var_slice!(elems.variables()));
+
+                    stack.push(ext_var);
+                }
                 TagUnion(tags, ext_var) => {
                     let tags = *tags;
                     let ext_var = *ext_var;
@@ -5371,6 +5753,10 @@ pub fn get_member_lambda_sets_at_region(subs: &Subs, var: Variable, target_regio
                     stack.extend(subs.get_subs_slice(fields.variables()));
                     stack.push(*ext);
                 }
+                FlatType::Tuple(elems, ext) => {
+                    stack.extend(subs.get_subs_slice(elems.variables()));
+                    stack.push(*ext);
+                }
                 FlatType::TagUnion(tags, ext) => {
                     stack.extend(
                         subs.get_subs_slice(tags.variables())
@@ -5391,7 +5777,10 @@ pub fn get_member_lambda_sets_at_region(subs: &Subs, var: Variable, target_regio
                     );
                     stack.push(*ext);
                 }
-                FlatType::Erroneous(_) | FlatType::EmptyRecord | FlatType::EmptyTagUnion => {}
+                FlatType::Erroneous(_)
+                | FlatType::EmptyRecord
+                | FlatType::EmptyTuple
+                | FlatType::EmptyTagUnion => {}
             },
             Content::Alias(_, _, real_var, _) => {
                 stack.push(*real_var);
@@ -5443,6 +5832,11 @@ fn is_inhabited(subs: &Subs, var: Variable) -> bool {
                         stack.extend(field_vars)
                     }
                 }
+                FlatType::Tuple(elems, ext) => {
+                    if let Ok(iter) = elems.unsorted_iterator(subs, *ext) {
+                        stack.extend(iter.map(|(_, var)| var))
+                    }
+                }
                 FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
                     let mut is_uninhabited = true;
                     // If any tag is inhabited, the union is inhabited!
@@ -5460,6 +5854,7 @@ fn is_inhabited(subs: &Subs, var: Variable) -> bool {
                 FlatType::FunctionOrTagUnion(_, _, _) => {}
                 FlatType::Erroneous(_) => {}
                 FlatType::EmptyRecord => {}
+                FlatType::EmptyTuple => {}
                 FlatType::EmptyTagUnion => {
                     return false;
                 }
@@ -10,8 +10,8 @@ use roc_types::num::{FloatWidth, IntLitWidth, NumericRange};
 use roc_types::subs::Content::{self, *};
 use roc_types::subs::{
     AliasVariables, Descriptor, ErrorTypeContext, FlatType, GetSubsSlice, LambdaSet, Mark,
-    OptVariable, RecordFields, Subs, SubsIndex, SubsSlice, UlsOfVar, UnionLabels, UnionLambdas,
-    UnionTags, Variable, VariableSubsSlice,
+    OptVariable, RecordFields, Subs, SubsIndex, SubsSlice, TupleElems, UlsOfVar, UnionLabels,
+    UnionLambdas, UnionTags, Variable, VariableSubsSlice,
 };
 use roc_types::types::{AliasKind, DoesNotImplementAbility, ErrorType, Mismatch, RecordField, Uls};
 
@@ -504,8 +504,8 @@ fn unify_context<M: MetaCollector>(env: &mut Env, pool: &mut Pool, ctx: Context)
     #[allow(clippy::let_and_return)]
     let result = match &ctx.first_desc.content {
         FlexVar(opt_name) => unify_flex(env, &ctx, opt_name, &ctx.second_desc.content),
-        FlexAbleVar(opt_name, ability) => {
-            unify_flex_able(env, &ctx, opt_name, *ability, &ctx.second_desc.content)
+        FlexAbleVar(opt_name, abilities) => {
+            unify_flex_able(env, &ctx, opt_name, *abilities, &ctx.second_desc.content)
         }
         RecursionVar {
             opt_name,
@@ -863,8 +863,9 @@ fn unify_alias<M: MetaCollector>(
 #[inline(always)]
 fn opaque_obligation(opaque: Symbol, opaque_var: Variable) -> Obligated {
     match opaque.module_id() {
-        // Numbers should be treated as ad-hoc obligations for ability checking.
+        // Numbers and Bool should be treated as ad-hoc obligations for ability checking.
         ModuleId::NUM => Obligated::Adhoc(opaque_var),
+        _ if opaque == Symbol::BOOL_BOOL => Obligated::Adhoc(opaque_var),
         _ => Obligated::Opaque(opaque),
     }
 }
@@ -887,13 +888,13 @@ fn unify_opaque<M: MetaCollector>(
             // Alias wins
             merge(env, ctx, Alias(symbol, args, real_var, kind))
         }
-        FlexAbleVar(_, ability) => {
+        FlexAbleVar(_, abilities) => {
             // Opaque type wins
             merge_flex_able_with_concrete(
                 env,
                 ctx,
                 ctx.second,
-                *ability,
+                *abilities,
                 Alias(symbol, args, real_var, kind),
                 opaque_obligation(symbol, ctx.first),
             )
@@ -946,13 +947,13 @@ fn unify_structure<M: MetaCollector>(
             // If the other is flex, Structure wins!
             merge(env, ctx, Structure(*flat_type))
         }
-        FlexAbleVar(_, ability) => {
+        FlexAbleVar(_, abilities) => {
             // Structure wins
             merge_flex_able_with_concrete(
                 env,
                 ctx,
                 ctx.second,
-                *ability,
+                *abilities,
                 Structure(*flat_type),
                 Obligated::Adhoc(ctx.first),
             )
@@ -1916,6 +1917,180 @@ fn unify_shared_fields<M: MetaCollector>(
     }
 }
 
+fn unify_tuple<M: MetaCollector>(
+    env: &mut Env,
+    pool: &mut Pool,
+    ctx: &Context,
+    elems1: TupleElems,
+    ext1: Variable,
+    elems2: TupleElems,
+    ext2: Variable,
+) -> Outcome<M> {
+    let subs = &mut env.subs;
+
+    let (separate, ext1, ext2) = separate_tuple_elems(subs, elems1, ext1, elems2, ext2);
+
+    let shared_elems = separate.in_both;
+
+    if separate.only_in_1.is_empty() {
+        if separate.only_in_2.is_empty() {
+            // these variable will be the empty tuple, but we must still unify them
+            let ext_outcome = unify_pool(env, pool, ext1, ext2, ctx.mode);
+
+            if !ext_outcome.mismatches.is_empty() {
+                return ext_outcome;
+            }
+
+            let mut elem_outcome =
+                unify_shared_tuple_elems(env, pool, ctx, shared_elems, OtherElems::None, ext1);
+
+            elem_outcome.union(ext_outcome);
+
+            elem_outcome
+        } else {
+            let only_in_2 = TupleElems::insert_into_subs(subs, separate.only_in_2);
+            let flat_type = FlatType::Tuple(only_in_2, ext2);
+            let sub_tuple = fresh(env, pool, ctx, Structure(flat_type));
+            let ext_outcome = unify_pool(env, pool, ext1, sub_tuple, ctx.mode);
+
+            if !ext_outcome.mismatches.is_empty() {
+                return ext_outcome;
+            }
+
+            let mut elem_outcome =
+                unify_shared_tuple_elems(env, pool, ctx, shared_elems, OtherElems::None, sub_tuple);
+
+            elem_outcome.union(ext_outcome);
+
+            elem_outcome
+        }
+    } else if separate.only_in_2.is_empty() {
+        let only_in_1 = TupleElems::insert_into_subs(subs, separate.only_in_1);
+        let flat_type = FlatType::Tuple(only_in_1, ext1);
+        let sub_tuple = fresh(env, pool, ctx, Structure(flat_type));
+        let ext_outcome = unify_pool(env, pool, sub_tuple, ext2, ctx.mode);
+
+        if !ext_outcome.mismatches.is_empty() {
+            return ext_outcome;
+        }
+
+        let mut elem_outcome =
+            unify_shared_tuple_elems(env, pool, ctx, shared_elems, OtherElems::None, sub_tuple);
+
+        elem_outcome.union(ext_outcome);
+
+        elem_outcome
+    } else {
+        let only_in_1 = TupleElems::insert_into_subs(subs, separate.only_in_1);
+        let only_in_2 = TupleElems::insert_into_subs(subs, separate.only_in_2);
+
+        let other_elems = OtherElems::Other(only_in_1, only_in_2);
+
+        let ext = fresh(env, pool, ctx, Content::FlexVar(None));
+        let flat_type1 = FlatType::Tuple(only_in_1, ext);
+        let flat_type2 = FlatType::Tuple(only_in_2, ext);
+
+        let sub1 = fresh(env, pool, ctx, Structure(flat_type1));
+        let sub2 = fresh(env, pool, ctx, Structure(flat_type2));
+
+        let tuple1_outcome = unify_pool(env, pool, ext1, sub2, ctx.mode);
+        if !tuple1_outcome.mismatches.is_empty() {
+            return tuple1_outcome;
+        }
+
+        let tuple2_outcome = unify_pool(env, pool, sub1, ext2, ctx.mode);
+        if !tuple2_outcome.mismatches.is_empty() {
+            return tuple2_outcome;
+        }
+
+        let mut elem_outcome =
+            unify_shared_tuple_elems(env, pool, ctx, shared_elems, other_elems, ext);
+
+        elem_outcome
+            .mismatches
+            .reserve(tuple1_outcome.mismatches.len() + tuple2_outcome.mismatches.len());
+        elem_outcome.union(tuple1_outcome);
+        elem_outcome.union(tuple2_outcome);
+
+        elem_outcome
+    }
+}
+
+enum OtherElems {
+    None,
+    Other(TupleElems, TupleElems),
+}
+
+type SharedElems = Vec<(usize, (Variable, Variable))>;
+
+fn unify_shared_tuple_elems<M: MetaCollector>(
+    env: &mut Env,
+    pool: &mut Pool,
+    ctx: &Context,
+    shared_elems: SharedElems,
+    other_elems: OtherElems,
+    ext: Variable,
+) -> Outcome<M> {
+    let mut matching_elems = Vec::with_capacity(shared_elems.len());
+    let num_shared_elems = shared_elems.len();
+
+    let mut whole_outcome = Outcome::default();
+
+    for (index, (actual, expected)) in shared_elems {
+        let local_outcome = unify_pool(env, pool, actual, expected, ctx.mode);
+
+        if local_outcome.mismatches.is_empty() {
+            matching_elems.push((index, actual));
+            whole_outcome.union(local_outcome);
+        }
+    }
+
+    if num_shared_elems == matching_elems.len() {
+        // pull elements in from the ext_var
+
+        let (ext_elems, new_ext_var) = TupleElems::empty().sorted_iterator_and_ext(env.subs, ext);
+        let ext_elems: Vec<_> = ext_elems.collect();
+
+        let mut all_elems = merge_sorted(matching_elems, ext_elems);
+
+        if let OtherElems::Other(other1, other2) = other_elems {
+            for other in [other1, other2] {
+                let other = other
+                    .iter_all()
+                    .map(|(i1, i2)| (env.subs[i1], env.subs[i2]))
+                    .collect::<Vec<_>>();
+
+                all_elems = merge_sorted(all_elems, other);
+            }
+        }
+
+        let elems = TupleElems::insert_into_subs(env.subs, all_elems);
+        let flat_type = FlatType::Tuple(elems, new_ext_var);
+
+        let merge_outcome = merge(env, ctx, Structure(flat_type));
+        whole_outcome.union(merge_outcome);
+        whole_outcome
+    } else {
+        mismatch!("in unify_shared_tuple_elems")
+    }
+}
+
+fn separate_tuple_elems(
+    subs: &Subs,
+    elems1: TupleElems,
+    ext1: Variable,
+    elems2: TupleElems,
+    ext2: Variable,
+) -> (Separate<usize, Variable>, Variable, Variable) {
+    let (it1, new_ext1) = elems1.sorted_iterator_and_ext(subs, ext1);
+    let (it2, new_ext2) = elems2.sorted_iterator_and_ext(subs, ext2);
+
+    let it1 = it1.collect::<Vec<_>>();
+    let it2 = it2.collect::<Vec<_>>();
+
+    (separate(it1, it2), new_ext1, new_ext2)
+}
+
 fn separate_record_fields(
     subs: &Subs,
     fields1: RecordFields,
@@ -2582,6 +2757,20 @@ fn unify_flat_type<M: MetaCollector>(
             unify_record(env, pool, ctx, *fields1, *ext1, *fields2, *ext2)
         }
 
+        (EmptyTuple, EmptyTuple) => merge(env, ctx, Structure(*left)),
+
+        (Tuple(elems, ext), EmptyTuple) if elems.is_empty() => {
+            unify_pool(env, pool, *ext, ctx.second, ctx.mode)
+        }
+
+        (EmptyTuple, Tuple(elems, ext)) if elems.is_empty() => {
+            unify_pool(env, pool, ctx.first, *ext, ctx.mode)
+        }
+
+        (Tuple(elems1, ext1), Tuple(elems2, ext2)) => {
+            unify_tuple(env, pool, ctx, *elems1, *ext1, *elems2, *ext2)
+        }
+
         (EmptyTagUnion, EmptyTagUnion) => merge(env, ctx, Structure(*left)),
 
         (TagUnion(tags, ext), EmptyTagUnion) if tags.is_empty() => {
@@ -2778,11 +2967,12 @@ fn unify_rigid<M: MetaCollector>(
             // If the other is flex, rigid wins!
             merge(env, ctx, RigidVar(*name))
         }
-        FlexAbleVar(_, other_ability) => {
+        FlexAbleVar(_, other_abilities) => {
             // Mismatch - Rigid can unify with FlexAble only when the Rigid has an ability
             // bound as well, otherwise the user failed to correctly annotate the bound.
+            let other_ability = env.subs.get_subs_slice(*other_abilities)[0];
             mismatch!(
-                %not_able, ctx.first, *other_ability,
+                %not_able, ctx.first, other_ability,
                 "Rigid {:?} with FlexAble {:?}", ctx.first, other
             )
         }
@@ -2819,22 +3009,24 @@ fn unify_rigid_able<M: MetaCollector>(
 ) -> Outcome<M> {
     match other {
         FlexVar(_) => {
-            // If the other is flex, rigid wins!
-            merge(env, ctx, RigidVar(*name))
-        }
-        FlexAbleVar(_, other_ability) => {
-            if ability == *other_ability {
-                // The ability bounds are the same, so rigid wins!
+            // If the other is flex, rigid wins, keeping its ability bound!
+            merge(env, ctx, RigidAbleVar(*name, ability))
+        }
+        FlexAbleVar(_, other_abilities) => {
+            let other_abilities = env.subs.get_subs_slice(*other_abilities);
+            if other_abilities
+                .iter()
+                .all(|other_ability| ability.ability_implies(*other_ability))
+            {
+                // The rigid's ability bound is at least as strong, so rigid wins!
                 merge(env, ctx, RigidAbleVar(*name, ability))
             } else {
-                // Mismatch for now.
-                // TODO check ability hierarchies.
                 mismatch!(
                     %not_able, ctx.second, ability,
-                    "RigidAble {:?} with ability {:?} not compatible with ability {:?}",
+                    "RigidAble {:?} with ability {:?} not compatible with abilities {:?}",
                     ctx.first,
                     ability,
-                    other_ability
+                    other_abilities
                 )
             }
         }
@@ -2872,10 +3064,10 @@ fn unify_flex<M: MetaCollector>(
             merge(env, ctx, FlexVar(opt_name))
         }
 
-        FlexAbleVar(opt_other_name, ability) => {
+        FlexAbleVar(opt_other_name, abilities) => {
             // Prefer using right's name.
             let opt_name = (opt_other_name).or(*opt_name);
-            merge(env, ctx, FlexAbleVar(opt_name, *ability))
+            merge(env, ctx, FlexAbleVar(opt_name, *abilities))
         }
 
         RigidVar(_)
@@ -2899,45 +3091,52 @@ fn unify_flex_able<M: MetaCollector>(
     env: &mut Env,
     ctx: &Context,
     opt_name: &Option<SubsIndex<Lowercase>>,
-    ability: Symbol,
+    abilities: SubsSlice<Symbol>,
     other: &Content,
 ) -> Outcome<M> {
     match other {
         FlexVar(opt_other_name) => {
             // Prefer using right's name.
             let opt_name = (opt_other_name).or(*opt_name);
-            merge(env, ctx, FlexAbleVar(opt_name, ability))
+            merge(env, ctx, FlexAbleVar(opt_name, abilities))
         }
 
-        FlexAbleVar(opt_other_name, other_ability) => {
+        FlexAbleVar(opt_other_name, other_abilities) => {
             // Prefer the right's name when possible.
             let opt_name = (opt_other_name).or(*opt_name);
 
-            if ability == *other_ability {
-                merge(env, ctx, FlexAbleVar(opt_name, ability))
-            } else {
-                // Ability names differ; mismatch for now.
-                // TODO check ability hierarchies.
-                mismatch!(
-                    %not_able, ctx.second, ability,
-                    "FlexAble {:?} with ability {:?} not compatible with ability {:?}",
-                    ctx.first,
-                    ability,
-                    other_ability
-                )
-            }
+            // The variable must now implement the abilities of both sides.
+            let merged_abilities = env.subs.merge_abilities(abilities, *other_abilities);
+            merge(env, ctx, FlexAbleVar(opt_name, merged_abilities))
         }
 
         RigidAbleVar(_, other_ability) => {
-            if ability == *other_ability {
-                merge(env, ctx, *other)
-            } else {
-                mismatch!(%not_able, ctx.second, ability, "RigidAble {:?} vs {:?}", ability, other_ability)
+            let unimplied = env
+                .subs
+                .get_subs_slice(abilities)
+                .iter()
+                .find(|ability| !other_ability.ability_implies(**ability));
+
+            match unimplied {
+                None => merge(env, ctx, *other),
+                Some(&ability) => {
+                    mismatch!(%not_able, ctx.second, ability, "RigidAble {:?} vs {:?}", ability, other_ability)
+                }
             }
         }
 
         RigidVar(_) => mismatch!("FlexAble can never unify with non-able Rigid"),
-        RecursionVar { .. } => mismatch!("FlexAble with RecursionVar"),
+        RecursionVar { structure, .. } => {
+            // Recursion var wins; the type it stands for must implement the abilities.
+            merge_flex_able_with_concrete(
+                env,
+                ctx,
+                ctx.first,
+                abilities,
+                *other,
+                Obligated::Adhoc(*structure),
+            )
+        }
         LambdaSet(..) => mismatch!("FlexAble with LambdaSet"),
 
         Alias(name, _args, _real_var, AliasKind::Opaque) => {
@@ -2946,7 +3145,7 @@ fn unify_flex_able<M: MetaCollector>(
                 env,
                 ctx,
                 ctx.first,
-                ability,
+                abilities,
                 *other,
                 opaque_obligation(*name, ctx.second),
             )
@@ -2958,7 +3157,7 @@ fn unify_flex_able<M: MetaCollector>(
                 env,
                 ctx,
                 ctx.first,
-                ability,
+                abilities,
                 *other,
                 Obligated::Adhoc(ctx.second),
             )
@@ -2972,16 +3171,18 @@ fn merge_flex_able_with_concrete<M: MetaCollector>(
     env: &mut Env,
     ctx: &Context,
     flex_able_var: Variable,
-    ability: Symbol,
+    abilities: SubsSlice<Symbol>,
     concrete_content: Content,
     concrete_obligation: Obligated,
 ) -> Outcome<M> {
     let mut outcome = merge(env, ctx, concrete_content);
-    let must_implement_ability = MustImplementAbility {
-        typ: concrete_obligation,
-        ability,
-    };
-    outcome.must_implement_ability.push(must_implement_ability);
+    for &ability in env.subs.get_subs_slice(abilities) {
+        let must_implement_ability = MustImplementAbility {
+            typ: concrete_obligation,
+            ability,
+        };
+        outcome.must_implement_ability.push(must_implement_ability);
+    }
 
     // Figure which, if any, lambda sets should be specialized thanks to the flex able var
     // being instantiated. Now as much as I would love to do that here, we don't, because we might
@@ -3035,7 +3236,22 @@ fn unify_recursion<M: MetaCollector>(
             mismatch!("RecursionVar {:?} with rigid {:?}", ctx.first, &other)
         }
 
-        FlexAbleVar(..) | RigidAbleVar(..) => {
+        FlexAbleVar(_, abilities) => {
+            // Recursion var wins; the type it stands for must implement the abilities.
+            merge_flex_able_with_concrete(
+                env,
+                ctx,
+                ctx.second,
+                *abilities,
+                RecursionVar {
+                    structure,
+                    opt_name: *opt_name,
+                },
+                Obligated::Adhoc(structure),
+            )
+        }
+
+        RigidAbleVar(..) => {
             mismatch!("RecursionVar {:?} with able var {:?}", ctx.first, &other)
         }
 
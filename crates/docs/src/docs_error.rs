@@ -3,6 +3,8 @@ use roc_ast::ast_error::ASTError;
 use roc_module::module_err::ModuleError;
 use roc_parse::parser::SyntaxError;
 use snafu::Snafu;
+use std::io;
+use std::path::PathBuf;
 
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
@@ -22,6 +24,15 @@ pub enum DocsError {
     WrapPegParseError {
         source: ParseError<usize>,
     },
+    #[snafu(display("{}", report))]
+    WrapLoadingProblem {
+        report: String,
+    },
+    #[snafu(display("Unable to access {}: {}", path.display(), source))]
+    WrapIoError {
+        path: PathBuf,
+        source: io::Error,
+    },
 }
 
 pub type DocsResult<T, E = DocsError> = std::result::Result<T, E>;
@@ -0,0 +1,277 @@
+//! The documentation model as JSON, for tools other than the html renderer.
+//!
+//! The schema is versioned by [`SCHEMA_VERSION`], which gets bumped whenever a field is
+//! removed or changes meaning. Adding fields doesn't bump it, so consumers should ignore
+//! fields they don't know about.
+
+use roc_load::docs::{self, DocEntry, Documentation};
+use roc_load::LoadedModule;
+use roc_region::all::{LineInfo, Region};
+use serde::Serialize;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Package<'a> {
+    schema_version: u32,
+    name: &'a str,
+    version: &'a str,
+    /// The README, as markdown
+    docs: &'a str,
+    modules: Vec<Module<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Module<'a> {
+    name: &'a str,
+    path: String,
+    entries: Vec<Entry<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum Entry<'a> {
+    #[serde(rename_all = "camelCase")]
+    Def {
+        name: &'a str,
+        type_vars: &'a [String],
+        #[serde(rename = "type")]
+        type_annotation: TypeAnnotation<'a>,
+        docs: Option<&'a str>,
+        location: Location,
+    },
+    /// Doc comments that aren't attached to a def, like the ones at the top of a module
+    DetachedDoc { docs: &'a str },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum TypeAnnotation<'a> {
+    TagUnion {
+        tags: Vec<Tag<'a>>,
+        extension: Box<TypeAnnotation<'a>>,
+    },
+    Function {
+        args: Vec<TypeAnnotation<'a>>,
+        output: Box<TypeAnnotation<'a>>,
+    },
+    ObscuredTagUnion,
+    ObscuredRecord,
+    BoundVariable {
+        name: &'a str,
+    },
+    Apply {
+        name: &'a str,
+        parts: Vec<TypeAnnotation<'a>>,
+    },
+    Record {
+        fields: Vec<RecordField<'a>>,
+        extension: Box<TypeAnnotation<'a>>,
+    },
+    Ability {
+        members: Vec<AbilityMember<'a>>,
+    },
+    Wildcard,
+    /// Opaque types, and the extensions of closed tag unions and records
+    None,
+}
+
+#[derive(Serialize)]
+struct Tag<'a> {
+    name: &'a str,
+    values: Vec<TypeAnnotation<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum RecordField<'a> {
+    Required {
+        name: &'a str,
+        #[serde(rename = "type")]
+        type_annotation: TypeAnnotation<'a>,
+    },
+    Optional {
+        name: &'a str,
+        #[serde(rename = "type")]
+        type_annotation: TypeAnnotation<'a>,
+    },
+    LabelOnly {
+        name: &'a str,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AbilityMember<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    type_annotation: TypeAnnotation<'a>,
+    able_variables: Vec<AbleVariable<'a>>,
+    docs: Option<&'a str>,
+    location: Location,
+}
+
+#[derive(Serialize)]
+struct AbleVariable<'a> {
+    name: &'a str,
+    ability: TypeAnnotation<'a>,
+}
+
+/// Where something is in its module's source, as 1-based lines and columns
+#[derive(Serialize)]
+struct Location {
+    start: Position,
+    end: Position,
+}
+
+#[derive(Serialize)]
+struct Position {
+    line: u32,
+    column: u32,
+}
+
+impl<'a> Package<'a> {
+    pub fn new(package: &'a Documentation) -> Self {
+        Package {
+            schema_version: SCHEMA_VERSION,
+            name: &package.name,
+            version: &package.version,
+            docs: &package.docs,
+            modules: package.modules.iter().filter_map(Module::new).collect(),
+        }
+    }
+}
+
+impl<'a> Module<'a> {
+    fn new(loaded_module: &'a LoadedModule) -> Option<Self> {
+        let module_id = loaded_module.module_id;
+        let module_docs = loaded_module.documentation.get(&module_id)?;
+        let (path, src) = loaded_module.sources.get(&module_id)?;
+        let line_info = LineInfo::new(src);
+        let exposed_values = module_docs.exposed_names(&loaded_module.interns);
+
+        let entries = module_docs
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                // Like in the html, only the exposed defs get documented.
+                DocEntry::DocDef(def) if exposed_values.contains(&def.name.as_str()) => {
+                    Some(Entry::Def {
+                        name: &def.name,
+                        type_vars: &def.type_vars,
+                        type_annotation: TypeAnnotation::new(&def.type_annotation, &line_info),
+                        docs: def.docs.as_deref(),
+                        location: Location::new(&line_info, def.region),
+                    })
+                }
+                DocEntry::DocDef(_) => None,
+                DocEntry::DetachedDoc(docs) => Some(Entry::DetachedDoc { docs }),
+            })
+            .collect();
+
+        Some(Module {
+            name: &module_docs.name,
+            path: path.display().to_string(),
+            entries,
+        })
+    }
+}
+
+impl<'a> TypeAnnotation<'a> {
+    fn new(type_ann: &'a docs::TypeAnnotation, line_info: &LineInfo) -> Self {
+        let to_json = |type_ann: &'a docs::TypeAnnotation| Self::new(type_ann, line_info);
+        let to_json_all = |type_anns: &'a [docs::TypeAnnotation]| {
+            type_anns
+                .iter()
+                .map(|type_ann| Self::new(type_ann, line_info))
+                .collect()
+        };
+
+        match type_ann {
+            docs::TypeAnnotation::TagUnion { tags, extension } => TypeAnnotation::TagUnion {
+                tags: tags
+                    .iter()
+                    .map(|tag| Tag {
+                        name: &tag.name,
+                        values: to_json_all(&tag.values),
+                    })
+                    .collect(),
+                extension: Box::new(to_json(extension)),
+            },
+            docs::TypeAnnotation::Function { args, output } => TypeAnnotation::Function {
+                args: to_json_all(args),
+                output: Box::new(to_json(output)),
+            },
+            docs::TypeAnnotation::ObscuredTagUnion => TypeAnnotation::ObscuredTagUnion,
+            docs::TypeAnnotation::ObscuredRecord => TypeAnnotation::ObscuredRecord,
+            docs::TypeAnnotation::BoundVariable(name) => TypeAnnotation::BoundVariable { name },
+            docs::TypeAnnotation::Apply { name, parts } => TypeAnnotation::Apply {
+                name,
+                parts: to_json_all(parts),
+            },
+            docs::TypeAnnotation::Record { fields, extension } => TypeAnnotation::Record {
+                fields: fields
+                    .iter()
+                    .map(|field| match field {
+                        docs::RecordField::RecordField {
+                            name,
+                            type_annotation,
+                        } => RecordField::Required {
+                            name,
+                            type_annotation: to_json(type_annotation),
+                        },
+                        docs::RecordField::OptionalField {
+                            name,
+                            type_annotation,
+                        } => RecordField::Optional {
+                            name,
+                            type_annotation: to_json(type_annotation),
+                        },
+                        docs::RecordField::LabelOnly { name } => RecordField::LabelOnly { name },
+                    })
+                    .collect(),
+                extension: Box::new(to_json(extension)),
+            },
+            docs::TypeAnnotation::Ability { members } => TypeAnnotation::Ability {
+                members: members
+                    .iter()
+                    .map(|member| AbilityMember {
+                        name: &member.name,
+                        type_annotation: to_json(&member.type_annotation),
+                        able_variables: member
+                            .able_variables
+                            .iter()
+                            .map(|(name, ability)| AbleVariable {
+                                name,
+                                ability: to_json(ability),
+                            })
+                            .collect(),
+                        docs: member.docs.as_deref(),
+                        location: Location::new(line_info, member.region),
+                    })
+                    .collect(),
+            },
+            docs::TypeAnnotation::Wildcard => TypeAnnotation::Wildcard,
+            docs::TypeAnnotation::NoTypeAnn => TypeAnnotation::None,
+        }
+    }
+}
+
+impl Location {
+    fn new(line_info: &LineInfo, region: Region) -> Self {
+        let region = line_info.convert_region(region);
+
+        Location {
+            start: Position {
+                line: region.start.line + 1,
+                column: region.start.column + 1,
+            },
+            end: Position {
+                line: region.end.line + 1,
+                column: region.end.column + 1,
+            },
+        }
+    }
+}
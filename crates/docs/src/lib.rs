@@ -1,18 +1,21 @@
 extern crate pulldown_cmark;
 extern crate roc_load;
 use bumpalo::Bump;
-use docs_error::{DocsError, DocsResult};
+pub use docs_error::{DocsError, DocsResult};
 use html::mark_node_to_html;
 use roc_can::scope::Scope;
 use roc_code_markup::markup::nodes::MarkupNode;
 use roc_code_markup::slow_pool::SlowPool;
+use roc_collections::MutMap;
 use roc_highlight::highlight_parser::{highlight_defs, highlight_expr};
 use roc_load::docs::DocEntry::DocDef;
-use roc_load::docs::{DocEntry, TypeAnnotation};
+use roc_load::docs::{AbilityMember, DocEntry, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
-use roc_module::symbol::{IdentIdsByModule, Interns, ModuleId};
+use roc_load::{ExecutionMode, LoadConfig, LoadedModule, Threading};
+use roc_module::symbol::{IdentIdsByModule, Interns, ModuleId, Symbol};
+use roc_parse::ast::{ExtractSpaces, Module};
 use roc_parse::ident::{parse_ident, Ident};
+use roc_parse::module::parse_header;
 use roc_parse::state::State;
 use roc_region::all::Region;
 use std::fs;
@@ -20,43 +23,95 @@ use std::path::{Path, PathBuf};
 
 mod docs_error;
 mod html;
+mod json;
 
-const BUILD_DIR: &str = "./generated-docs";
+pub const DEFAULT_BUILD_DIR: &str = "./generated-docs";
 
-pub fn generate_docs_html(filenames: Vec<PathBuf>) {
-    let build_dir = Path::new(BUILD_DIR);
-    let loaded_modules = load_modules_for_files(filenames);
+/// What the docs are named when none of the files given is a package or platform module
+const DEFAULT_PACKAGE_NAME: &str = "documentation";
 
-    // TODO: get info from a package module; this is all hardcoded for now.
-    let mut package = roc_load::docs::Documentation {
-        name: "documentation".to_string(),
-        version: "".to_string(),
-        docs: "Package introduction or README.".to_string(),
-        modules: loaded_modules,
-    };
+/// The package's front page, shown in place of a module's docs
+const README: &str = "README.md";
+
+/// The file `generate_docs_json` writes the docs to
+const DOCS_JSON: &str = "docs.json";
+
+/// Where types from the builtins link to, unless the builtins are what's being documented
+const BUILTINS_DOCS_URL: &str = "https://www.roc-lang.org/builtins/";
+
+/// The formats `roc docs` can write the documentation in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    Html,
+    /// For tools like search indexes, see the `json` module for the schema
+    Json,
+}
+
+impl DocsFormat {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            DocsFormat::Html => "html",
+            DocsFormat::Json => "json",
+        }
+    }
+
+    /// NOTE keep up to date!
+    pub const OPTIONS: &'static [&'static str] =
+        &[DocsFormat::Html.as_str(), DocsFormat::Json.as_str()];
+}
+
+impl std::str::FromStr for DocsFormat {
+    type Err = String;
 
-    if !build_dir.exists() {
-        fs::create_dir_all(build_dir).expect("TODO gracefully handle unable to create build dir");
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "html" => Ok(DocsFormat::Html),
+            "json" => Ok(DocsFormat::Json),
+            _ => Err(format!("`roc docs` can not write docs as {}", string)),
+        }
     }
+}
+
+/// The name and modules of a package, from its package or platform module's header
+struct PackageHeader {
+    name: String,
+    /// The paths of the modules the package exposes
+    exposed_modules: Vec<PathBuf>,
+    /// The directory the package's modules and README are in
+    root_dir: PathBuf,
+}
+
+/// Writes html docs for the modules in `filenames` to `build_dir`, with links relative to `base_url`.
+///
+/// If one of `filenames` is a package or platform module, the docs are named after it,
+/// include the modules it exposes, and get its README as a front page.
+pub fn generate_docs_html(
+    filenames: Vec<PathBuf>,
+    build_dir: &Path,
+    base_url: &str,
+) -> DocsResult<()> {
+    let base_url = normalize_base_url(base_url);
+    let mut package = load_package(filenames)?;
+
+    create_dir(build_dir)?;
 
     // Copy over the assets
-    fs::write(
-        build_dir.join("search.js"),
+    write_file(
+        &build_dir.join("search.js"),
         include_str!("./static/search.js"),
-    )
-    .expect("TODO gracefully handle failing to make the search javascript");
+    )?;
 
-    fs::write(
-        build_dir.join("styles.css"),
+    write_file(
+        &build_dir.join("styles.css"),
         include_str!("./static/styles.css"),
-    )
-    .expect("TODO gracefully handle failing to make the stylesheet");
+    )?;
 
-    fs::write(
-        build_dir.join("favicon.svg"),
+    write_file(
+        &build_dir.join("favicon.svg"),
         include_str!("./static/favicon.svg"),
-    )
-    .expect("TODO gracefully handle failing to make the favicon");
+    )?;
+
+    let package_links = PackageLinks::new(&package.modules);
 
     let module_pairs = package.modules.iter().flat_map(|loaded_module| {
         loaded_module
@@ -69,10 +124,10 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
                 // being a hash map in the first place if only one of its entries
                 // actually has interesting information in it?
                 if *module_id == loaded_module.module_id {
-                    let exposed_values = loaded_module
-                        .exposed_values
-                        .iter()
-                        .map(|symbol| symbol.as_str(&loaded_module.interns).to_string())
+                    let exposed_values = module
+                        .exposed_names(&loaded_module.interns)
+                        .into_iter()
+                        .map(str::to_string)
                         .collect::<Vec<String>>();
 
                     Some((module, exposed_values))
@@ -91,7 +146,7 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
             &module_pairs
                 .clone()
                 .map(|(module, _)| {
-                    let href = sidebar_link_url(module);
+                    let href = sidebar_link_url(&base_url, module);
 
                     format!(r#"<link rel="prefetch" href="{href}"/>"#)
                 })
@@ -100,41 +155,179 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
         )
         .replace(
             "<!-- Module links -->",
-            render_sidebar(module_pairs).as_str(),
+            render_sidebar(&base_url, module_pairs).as_str(),
+        )
+        .replace(
+            "<!-- Package Name and Version -->",
+            render_name_and_version(&base_url, package.name.as_str(), package.version.as_str())
+                .as_str(),
         );
 
+    if !package.docs.is_empty() {
+        let rendered_readme = template_html.replace(
+            "<!-- Module Docs -->",
+            readme_to_html(package.docs.as_str()).as_str(),
+        );
+
+        write_file(&build_dir.join("index.html"), &rendered_readme)?;
+    }
+
     // Write each package's module docs html file
     for loaded_module in package.modules.iter_mut() {
         for (module_id, module_docs) in loaded_module.documentation.iter() {
             if *module_id == loaded_module.module_id {
                 let module_dir = build_dir.join(module_docs.name.replace('.', "/").as_str());
 
-                fs::create_dir_all(&module_dir)
-                    .expect("TODO gracefully handle not being able to create the module dir");
+                create_dir(&module_dir)?;
 
-                let rendered_module = template_html
-                    .replace(
-                        "<!-- Package Name and Version -->",
-                        render_name_and_version(package.name.as_str(), package.version.as_str())
-                            .as_str(),
+                let rendered_module = template_html.replace(
+                    "<!-- Module Docs -->",
+                    render_module_documentation(
+                        &base_url,
+                        &package_links,
+                        module_docs,
+                        loaded_module,
                     )
-                    .replace(
-                        "<!-- Module Docs -->",
-                        render_module_documentation(module_docs, loaded_module).as_str(),
-                    );
-
-                fs::write(module_dir.join("index.html"), rendered_module).expect(
-                    "TODO gracefully handle failing to write index.html inside module's dir",
+                    .as_str(),
                 );
+
+                write_file(&module_dir.join("index.html"), &rendered_module)?;
             }
         }
     }
 
     println!("🎉 Docs generated in {}", build_dir.display());
+
+    Ok(())
+}
+
+/// Loads the modules in `filenames`, along with the modules and README of the package
+/// if one of them is a package or platform module.
+fn load_package(filenames: Vec<PathBuf>) -> DocsResult<roc_load::docs::Documentation> {
+    let mut package_header = None;
+    let mut module_filenames = Vec::with_capacity(filenames.len());
+
+    for filename in filenames {
+        match read_package_header(&filename)? {
+            Some(header) => package_header = Some(header),
+            None => module_filenames.push(filename),
+        }
+    }
+
+    let (name, docs) = match package_header {
+        Some(header) => {
+            for exposed_module in header.exposed_modules {
+                if !module_filenames
+                    .iter()
+                    .any(|filename| same_file(filename, &exposed_module))
+                {
+                    module_filenames.push(exposed_module);
+                }
+            }
+
+            let readme_path = header.root_dir.join(README);
+            let readme = if readme_path.exists() {
+                read_file(&readme_path)?
+            } else {
+                String::new()
+            };
+
+            (header.name, readme)
+        }
+        None => (DEFAULT_PACKAGE_NAME.to_string(), String::new()),
+    };
+
+    let loaded_modules = load_modules_for_files(module_filenames)?;
+
+    Ok(roc_load::docs::Documentation {
+        // Package headers don't have a version yet.
+        name,
+        version: String::new(),
+        docs,
+        modules: loaded_modules,
+    })
+}
+
+/// Writes the docs for the modules in `filenames` to `docs.json` in `build_dir`, for tools
+/// other than the html renderer. The schema is described in the `json` module.
+pub fn generate_docs_json(filenames: Vec<PathBuf>, build_dir: &Path) -> DocsResult<()> {
+    let package = load_package(filenames)?;
+    let json_path = build_dir.join(DOCS_JSON);
+
+    let json = serde_json::to_string_pretty(&json::Package::new(&package))
+        .expect("the docs can always be serialized");
+
+    create_dir(build_dir)?;
+    write_file(&json_path, &json)?;
+
+    println!("🎉 Docs generated in {}", json_path.display());
+
+    Ok(())
+}
+
+/// Returns the package's header if `filename` is a package or platform module.
+///
+/// Other modules, and ones whose headers don't parse, are left for loading to deal with.
+fn read_package_header(filename: &Path) -> DocsResult<Option<PackageHeader>> {
+    let arena = Bump::new();
+    let src = read_file(filename)?;
+
+    let (name, exposes) = match parse_header(&arena, State::new(src.as_bytes())) {
+        Ok((Module::Package { header }, _)) => (header.name, header.exposes),
+        Ok((Module::Platform { header }, _)) => (header.name, header.exposes),
+        _ => return Ok(None),
+    };
+
+    let root_dir = filename.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let exposed_modules = exposes
+        .iter()
+        .map(|module_name| {
+            let module_path: &str = module_name.value.extract_spaces().item.into();
+
+            root_dir
+                .join(module_path.replace('.', "/"))
+                .with_extension("roc")
+        })
+        .collect();
+
+    Ok(Some(PackageHeader {
+        name: name.value.to_str().to_string(),
+        exposed_modules,
+        root_dir,
+    }))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
 }
 
-fn sidebar_link_url(module: &ModuleDocumentation) -> String {
-    let mut href_buf = base_url();
+fn read_file(path: &Path) -> DocsResult<String> {
+    fs::read_to_string(path).map_err(|source| DocsError::WrapIoError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write_file(path: &Path, contents: &str) -> DocsResult<()> {
+    fs::write(path, contents).map_err(|source| DocsError::WrapIoError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn create_dir(path: &Path) -> DocsResult<()> {
+    fs::create_dir_all(path).map_err(|source| DocsError::WrapIoError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn sidebar_link_url(base_url: &str, module: &ModuleDocumentation) -> String {
+    let mut href_buf = base_url.to_string();
     href_buf.push_str(module.name.as_str());
 
     href_buf
@@ -183,11 +376,133 @@ pub fn syntax_highlight_top_level_defs(code_str: &str) -> DocsResult<String> {
     }
 }
 
+/// What the pages of a package can link to, across all of its modules
+struct PackageLinks {
+    /// The exposed defs of each module being documented, by module name
+    exposed_by_module: MutMap<String, Vec<String>>,
+    /// The (module name, type name) of the opaque types implementing each ability,
+    /// by the ability's qualified name
+    implementors: MutMap<String, Vec<(String, String)>>,
+}
+
+impl PackageLinks {
+    fn new(loaded_modules: &[LoadedModule]) -> Self {
+        let mut exposed_by_module = MutMap::default();
+        let mut implementors: MutMap<String, Vec<(String, String)>> = MutMap::default();
+
+        for loaded_module in loaded_modules {
+            let interns = &loaded_module.interns;
+            let abilities_store = &loaded_module.abilities_store;
+
+            if let Some(module_docs) = loaded_module.documentation.get(&loaded_module.module_id) {
+                let exposed_values = module_docs
+                    .exposed_names(interns)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+
+                exposed_by_module.insert(module_docs.name.clone(), exposed_values);
+            }
+
+            // Each module was loaded on its own, so their symbols can't be compared; their names can.
+            for (impl_key, _) in abilities_store.iter_declared_implementations() {
+                if let Some(member) = abilities_store.member_def(impl_key.ability_member) {
+                    let opaque = (
+                        impl_key.opaque.module_string(interns).to_string(),
+                        impl_key.opaque.as_str(interns).to_string(),
+                    );
+                    let types = implementors
+                        .entry(qualified_name(member.parent_ability, interns))
+                        .or_default();
+
+                    if !types.contains(&opaque) {
+                        types.push(opaque);
+                    }
+                }
+            }
+        }
+
+        for types in implementors.values_mut() {
+            types.sort();
+        }
+
+        Self {
+            exposed_by_module,
+            implementors,
+        }
+    }
+
+    /// The url of the docs for `ident` from `module_name`, if it gets documented in this package
+    fn url(&self, base_url: &str, module_name: &str, ident: &str) -> Option<String> {
+        let exposed = self.exposed_by_module.get(module_name)?;
+
+        if exposed.iter().any(|exposed_ident| exposed_ident == ident) {
+            Some(format!("{}{}#{}", base_url, module_name, ident))
+        } else {
+            None
+        }
+    }
+}
+
+/// Resolves the type names in a module's signatures, so they can link to their docs
+struct TypeLinks<'a> {
+    base_url: &'a str,
+    package: &'a PackageLinks,
+    scope: &'a Scope,
+    interns: &'a Interns,
+}
+
+impl<'a> TypeLinks<'a> {
+    /// The url of the docs for the type `name`, which is qualified unless it's in scope
+    fn url(&self, name: &str) -> Option<String> {
+        let (module_id, module_name, ident) = match name.rsplit_once('.') {
+            Some((module_name, ident)) => {
+                let module_id = self.interns.module_ids.get_id(&module_name.into())?;
+
+                (module_id, module_name, ident)
+            }
+            None => {
+                let symbol = self.scope.lookup_str(name, Region::zero()).ok()?;
+
+                (
+                    symbol.module_id(),
+                    symbol.module_string(self.interns).as_str(),
+                    name,
+                )
+            }
+        };
+
+        if self.package.exposed_by_module.contains_key(module_name) {
+            self.package.url(self.base_url, module_name, ident)
+        } else if module_id.is_builtin() {
+            Some(format!("{}{}#{}", BUILTINS_DOCS_URL, module_name, ident))
+        } else {
+            None
+        }
+    }
+}
+
+fn qualified_name(symbol: Symbol, interns: &Interns) -> String {
+    format!(
+        "{}.{}",
+        symbol.module_string(interns).as_str(),
+        symbol.as_str(interns)
+    )
+}
+
 fn render_module_documentation(
+    base_url: &str,
+    package_links: &PackageLinks,
     module: &ModuleDocumentation,
     loaded_module: &LoadedModule,
 ) -> String {
     let mut buf = String::new();
+    let type_links = TypeLinks {
+        base_url,
+        package: package_links,
+        scope: &module.scope,
+        interns: &loaded_module.interns,
+    };
 
     buf.push_str(
         html_to_string(
@@ -198,7 +513,7 @@ fn render_module_documentation(
         .as_str(),
     );
 
-    let exposed_values = loaded_module.exposed_values_str();
+    let exposed_values = module.exposed_names(&loaded_module.interns);
 
     for entry in &module.entries {
         let mut should_render_entry = true;
@@ -234,12 +549,15 @@ fn render_module_documentation(
 
                     match type_ann {
                         TypeAnnotation::NoTypeAnn => {}
+                        TypeAnnotation::Ability { .. } => {
+                            content.push_str(" has");
+                        }
                         _ => {
                             content.push_str(" : ");
                         }
                     }
 
-                    type_annotation_to_html(0, &mut content, type_ann, false);
+                    type_annotation_to_html(0, &mut content, type_ann, false, Some(&type_links));
 
                     buf.push_str(
                         html_to_string(
@@ -253,6 +571,7 @@ fn render_module_documentation(
                     if let Some(docs) = &doc_def.docs {
                         buf.push_str(
                             markdown_to_html(
+                                base_url,
                                 &exposed_values,
                                 &module.scope,
                                 docs.to_string(),
@@ -262,10 +581,34 @@ fn render_module_documentation(
                         );
                     }
 
+                    if let TypeAnnotation::Ability { members } = type_ann {
+                        for member in members {
+                            buf.push_str(
+                                render_ability_member(
+                                    &type_links,
+                                    &exposed_values,
+                                    module,
+                                    loaded_module,
+                                    member,
+                                )
+                                .as_str(),
+                            );
+                        }
+
+                        let ability = format!("{}.{}", module.name, name);
+
+                        if let Some(types) = package_links.implementors.get(&ability) {
+                            buf.push_str(
+                                render_implementors(base_url, package_links, types).as_str(),
+                            );
+                        }
+                    }
+
                     buf.push_str("</section>");
                 }
                 DocEntry::DetachedDoc(docs) => {
                     let markdown = markdown_to_html(
+                        base_url,
                         &exposed_values,
                         &module.scope,
                         docs.to_string(),
@@ -280,6 +623,80 @@ fn render_module_documentation(
     buf
 }
 
+fn render_ability_member(
+    type_links: &TypeLinks,
+    exposed_values: &[&str],
+    module: &ModuleDocumentation,
+    loaded_module: &LoadedModule,
+    member: &AbilityMember,
+) -> String {
+    let name = member.name.as_str();
+    let href = format!("#{}", name);
+    let mut content = html_to_string("a", vec![("href", href.as_str())], name);
+
+    content.push_str(" : ");
+    type_annotation_to_html(
+        0,
+        &mut content,
+        &member.type_annotation,
+        false,
+        Some(type_links),
+    );
+
+    for (index, (var_name, ability)) in member.able_variables.iter().enumerate() {
+        content.push_str(if index == 0 { " | " } else { ", " });
+        content.push_str(var_name);
+        content.push_str(" has ");
+        type_annotation_to_html(0, &mut content, ability, false, Some(type_links));
+    }
+
+    let mut buf = html_to_string(
+        "h4",
+        vec![("id", name), ("class", "entry-name")],
+        content.as_str(),
+    );
+
+    if let Some(docs) = &member.docs {
+        buf.push_str(
+            markdown_to_html(
+                type_links.base_url,
+                exposed_values,
+                &module.scope,
+                docs.to_string(),
+                loaded_module,
+            )
+            .as_str(),
+        );
+    }
+
+    html_to_string("div", vec![("class", "ability-member")], buf.as_str())
+}
+
+fn render_implementors(
+    base_url: &str,
+    package_links: &PackageLinks,
+    types: &[(String, String)],
+) -> String {
+    let links = types
+        .iter()
+        .map(|(module_name, type_name)| {
+            let name = format!("{}.{}", module_name, type_name);
+
+            match package_links.url(base_url, module_name, type_name) {
+                Some(url) => html_to_string("a", vec![("href", url.as_str())], name.as_str()),
+                None => name,
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    html_to_string(
+        "p",
+        vec![("class", "ability-implementors")],
+        format!("Implemented by {}", links).as_str(),
+    )
+}
+
 fn html_to_string(tag_name: &str, attrs: Vec<(&str, &str)>, content: &str) -> String {
     let mut buf = String::new();
 
@@ -309,54 +726,42 @@ fn html_to_string(tag_name: &str, attrs: Vec<(&str, &str)>, content: &str) -> St
     buf
 }
 
-fn base_url() -> String {
+fn normalize_base_url(root_path: &str) -> String {
     // e.g. "builtins/" in "https://roc-lang.org/builtins/Str"
-    //
-    // TODO make this a CLI flag to the `docs` subcommand instead of an env var
-    match std::env::var("ROC_DOCS_URL_ROOT") {
-        Ok(root_builtins_path) => {
-            let mut url_str = String::with_capacity(root_builtins_path.len() + 64);
-
-            if !root_builtins_path.starts_with('/') {
-                url_str.push('/');
-            }
+    let mut url_str = String::with_capacity(root_path.len() + 64);
 
-            url_str.push_str(&root_builtins_path);
-
-            if !root_builtins_path.ends_with('/') {
-                url_str.push('/');
-            }
-
-            url_str
-        }
-        _ => {
-            let mut url_str = String::with_capacity(64);
+    if !root_path.starts_with('/') {
+        url_str.push('/');
+    }
 
-            url_str.push('/');
+    url_str.push_str(root_path);
 
-            url_str
-        }
+    if !root_path.ends_with('/') {
+        url_str.push('/');
     }
+
+    url_str
 }
 
-fn render_name_and_version(name: &str, version: &str) -> String {
+fn render_name_and_version(base_url: &str, name: &str, version: &str) -> String {
     let mut buf = String::new();
-    let mut url_str = base_url();
-
-    url_str.push_str(name);
 
+    // The package's front page
     buf.push_str(
         html_to_string(
             "h1",
             vec![("class", "pkg-full-name")],
-            html_to_string("a", vec![("href", url_str.as_str())], name).as_str(),
+            html_to_string("a", vec![("href", base_url)], name).as_str(),
         )
         .as_str(),
     );
 
-    let mut versions_url_str = base_url();
+    if version.is_empty() {
+        return buf;
+    }
+
+    let mut versions_url_str = base_url.to_string();
 
-    versions_url_str.push('/');
     versions_url_str.push_str(name);
     versions_url_str.push('/');
     versions_url_str.push_str(version);
@@ -374,12 +779,13 @@ fn render_name_and_version(name: &str, version: &str) -> String {
 }
 
 fn render_sidebar<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>>(
+    base_url: &str,
     modules: I,
 ) -> String {
     let mut buf = String::new();
 
     for (module, exposed_values) in modules {
-        let href = sidebar_link_url(module);
+        let href = sidebar_link_url(base_url, module);
         let mut sidebar_entry_content = String::new();
 
         sidebar_entry_content.push_str(
@@ -403,14 +809,25 @@ fn render_sidebar<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>
                         entry_href.push('#');
                         entry_href.push_str(doc_def.name.as_str());
 
-                        entries_buf.push_str(
-                            html_to_string(
-                                "a",
-                                vec![("href", entry_href.as_str())],
-                                doc_def.name.as_str(),
-                            )
-                            .as_str(),
+                        // The search looks for types in here, e.g. `List a -> Nat`
+                        let mut signature = String::new();
+                        type_annotation_to_html(
+                            0,
+                            &mut signature,
+                            &doc_def.type_annotation,
+                            false,
+                            None,
                         );
+                        let signature = signature.split_whitespace().collect::<Vec<_>>().join(" ");
+
+                        let mut attrs = vec![("href", entry_href.as_str())];
+
+                        if !signature.is_empty() {
+                            attrs.push(("data-type", signature.as_str()));
+                        }
+
+                        entries_buf
+                            .push_str(html_to_string("a", attrs, doc_def.name.as_str()).as_str());
                     }
                 }
             }
@@ -440,28 +857,28 @@ fn render_sidebar<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>
     buf
 }
 
-pub fn load_modules_for_files(filenames: Vec<PathBuf>) -> Vec<LoadedModule> {
+pub fn load_modules_for_files(filenames: Vec<PathBuf>) -> DocsResult<Vec<LoadedModule>> {
     let arena = Bump::new();
     let mut modules = Vec::with_capacity(filenames.len());
+    let render = roc_reporting::report::RenderTarget::ColorTerminal;
 
     for filename in filenames {
         let load_config = LoadConfig {
             target_info: roc_target::TargetInfo::default_x86_64(), // This is just type-checking for docs, so "target" doesn't matter
-            render: roc_reporting::report::RenderTarget::ColorTerminal,
+            render,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Check,
         };
-        match roc_load::load_and_typecheck(&arena, filename, Default::default(), load_config) {
-            Ok(loaded) => modules.push(loaded),
-            Err(LoadingProblem::FormattedReport(report)) => {
-                eprintln!("{}", report);
-                std::process::exit(1);
-            }
-            Err(e) => panic!("{:?}", e),
-        }
+        let loaded =
+            roc_load::load_and_typecheck(&arena, filename, Default::default(), load_config)
+                .map_err(|problem| DocsError::WrapLoadingProblem {
+                    report: problem.into_report(render),
+                })?;
+
+        modules.push(loaded);
     }
 
-    modules
+    Ok(modules)
 }
 
 const INDENT: &str = "    ";
@@ -476,12 +893,13 @@ fn new_line(buf: &mut String) {
     buf.push('\n');
 }
 
-// html is written to buf
+// html is written to buf, with type names linking to their docs if there are `type_links`
 fn type_annotation_to_html(
     indent_level: usize,
     buf: &mut String,
     type_ann: &TypeAnnotation,
     needs_parens: bool,
+    type_links: Option<&TypeLinks>,
 ) {
     let is_multiline = should_be_multiline(type_ann);
     match type_ann {
@@ -516,7 +934,13 @@ fn type_annotation_to_html(
 
                     for type_value in &tag.values {
                         buf.push(' ');
-                        type_annotation_to_html(next_indent_level, buf, type_value, true);
+                        type_annotation_to_html(
+                            next_indent_level,
+                            buf,
+                            type_value,
+                            true,
+                            type_links,
+                        );
                     }
 
                     if is_multiline {
@@ -535,23 +959,23 @@ fn type_annotation_to_html(
                 buf.push(']');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, type_links);
         }
         TypeAnnotation::BoundVariable(var_name) => {
             buf.push_str(var_name);
         }
         TypeAnnotation::Apply { name, parts } => {
             if parts.is_empty() {
-                buf.push_str(name);
+                push_type_name(buf, name, type_links);
             } else {
                 if needs_parens {
                     buf.push('(');
                 }
 
-                buf.push_str(name);
+                push_type_name(buf, name, type_links);
                 for part in parts {
                     buf.push(' ');
-                    type_annotation_to_html(indent_level, buf, part, true);
+                    type_annotation_to_html(indent_level, buf, part, true, type_links);
                 }
 
                 if needs_parens {
@@ -599,13 +1023,25 @@ fn type_annotation_to_html(
                             type_annotation, ..
                         } => {
                             buf.push_str(" : ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                type_links,
+                            );
                         }
                         RecordField::OptionalField {
                             type_annotation, ..
                         } => {
                             buf.push_str(" ? ");
-                            type_annotation_to_html(next_indent_level, buf, type_annotation, false);
+                            type_annotation_to_html(
+                                next_indent_level,
+                                buf,
+                                type_annotation,
+                                false,
+                                type_links,
+                            );
                         }
                         RecordField::LabelOnly { .. } => {}
                     }
@@ -628,7 +1064,7 @@ fn type_annotation_to_html(
                 buf.push('}');
             }
 
-            type_annotation_to_html(indent_level, buf, extension, true);
+            type_annotation_to_html(indent_level, buf, extension, true, type_links);
         }
         TypeAnnotation::Function { args, output } => {
             let mut peekable_args = args.iter().peekable();
@@ -640,7 +1076,7 @@ fn type_annotation_to_html(
                     indent(buf, indent_level + 1);
                 }
 
-                type_annotation_to_html(indent_level, buf, arg, false);
+                type_annotation_to_html(indent_level, buf, arg, false, type_links);
 
                 if peekable_args.peek().is_some() {
                     buf.push_str(", ");
@@ -660,10 +1096,10 @@ fn type_annotation_to_html(
                 next_indent_level += 1;
             }
 
-            type_annotation_to_html(next_indent_level, buf, output, false);
+            type_annotation_to_html(next_indent_level, buf, output, false, type_links);
         }
         TypeAnnotation::Ability { members: _ } => {
-            // TODO(abilities): fill me in
+            // Abilities' members get rendered below their docs, see `render_ability_member`
         }
         TypeAnnotation::ObscuredTagUnion => {
             buf.push_str("[@..]");
@@ -676,6 +1112,20 @@ fn type_annotation_to_html(
     }
 }
 
+fn push_type_name(buf: &mut String, name: &str, type_links: Option<&TypeLinks>) {
+    match type_links.and_then(|type_links| type_links.url(name)) {
+        Some(url) => buf.push_str(
+            html_to_string(
+                "a",
+                vec![("class", "type-link"), ("href", url.as_str())],
+                name,
+            )
+            .as_str(),
+        ),
+        None => buf.push_str(name),
+    }
+}
+
 fn should_be_multiline(type_ann: &TypeAnnotation) -> bool {
     match type_ann {
         TypeAnnotation::TagUnion { tags, extension } => {
@@ -752,7 +1202,9 @@ struct DocUrl {
     title: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn doc_url<'a>(
+    base_url: &str,
     home: ModuleId,
     exposed_values: &[&str],
     dep_idents: &IdentIdsByModule,
@@ -823,7 +1275,7 @@ fn doc_url<'a>(
         }
     }
 
-    let mut url = base_url();
+    let mut url = base_url.to_string();
 
     // Example:
     //
@@ -839,6 +1291,7 @@ fn doc_url<'a>(
 }
 
 fn markdown_to_html(
+    base_url: &str,
     exposed_values: &[&str],
     scope: &Scope,
     markdown: String,
@@ -869,6 +1322,7 @@ fn markdown_to_html(
                         match iter.next() {
                             Some(symbol_name) if iter.next().is_none() => {
                                 let DocUrl { url, title } = doc_url(
+                                    base_url,
                                     loaded_module.module_id,
                                     exposed_values,
                                     &loaded_module.dep_idents,
@@ -892,6 +1346,7 @@ fn markdown_to_html(
                         // This looks like a tag name, but it could
                         // be a type alias that's in scope, e.g. [I64]
                         let DocUrl { url, title } = doc_url(
+                            base_url,
                             loaded_module.module_id,
                             exposed_values,
                             &loaded_module.dep_idents,
@@ -1006,6 +1461,10 @@ fn markdown_to_html(
                     Ok(highlighted_code_str) => {
                         docs_parser.push(Event::Html(CowStr::from(highlighted_code_str)));
                     }
+                    // The highlighter doesn't know about the `expect`s that `roc test --doc` runs yet.
+                    Err(_) if code_str.lines().any(|line| line.trim_start().starts_with("expect")) => {
+                        docs_parser.push(event);
+                    }
                     Err(syntax_error) => {
                         panic!("Unexpected parse failure when parsing this for rendering in docs:\n\n{}\n\nParse error was:\n\n{:?}\n\n", code_str, syntax_error)
                     }
@@ -1026,3 +1485,15 @@ fn markdown_to_html(
 
     docs_html
 }
+
+/// Unlike module docs, READMEs are plain markdown, without links resolved against a module's scope.
+fn readme_to_html(markdown: &str) -> String {
+    let mut readme_html = String::new();
+
+    pulldown_cmark::html::push_html(
+        &mut readme_html,
+        pulldown_cmark::Parser::new_ext(markdown, pulldown_cmark::Options::ENABLE_TABLES),
+    );
+
+    readme_html
+}
@@ -2,8 +2,40 @@
   let sidebar = document.getElementById("sidebar-nav");
   let searchBox = document.getElementById("module-search");
 
+  // Collapses whitespace, and renames type variables to the order they first appear in,
+  // so that searching for `List elem -> Nat` finds `List a -> Nat`.
+  function normalizeType(type) {
+    let typeVars = new Map();
+
+    return type
+      .replace(/\s+/g, " ")
+      .trim()
+      // Lowercase names are type variables, except for record fields, which are followed by `:` or `?`
+      .replace(/\b[a-z][a-zA-Z0-9_]*\b(?!\s*[:?])/g, (name) => {
+        if (!typeVars.has(name)) {
+          typeVars.set(name, `#${typeVars.size}`);
+        }
+
+        return typeVars.get(name);
+      })
+      .toLowerCase();
+  }
+
+  // Searches with an uppercase letter or an arrow in them look at type signatures too, e.g. `Str` or `a -> a`.
+  function matches(entry, text, typeQuery) {
+    if (entry.textContent.toLowerCase().includes(text)) {
+      return true;
+    }
+
+    let type = entry.dataset.type;
+
+    return typeQuery !== null && type !== undefined && normalizeType(type).includes(typeQuery);
+  }
+
   function search() {
-    let text = searchBox.value.toLowerCase(); // Search is case-insensitive.
+    let query = searchBox.value.trim();
+    let text = query.toLowerCase(); // Search is case-insensitive.
+    let typeQuery = /[A-Z]|->/.test(query) ? normalizeType(query) : null;
 
     if (text === "") {
       // Un-hide everything
@@ -23,7 +55,7 @@
     } else {
       // First, show/hide all the sub-entries within each module (top-level functions etc.)
       sidebar.querySelectorAll(".sidebar-sub-entries a").forEach((entry) => {
-        if (entry.textContent.toLowerCase().includes(text)) {
+        if (matches(entry, text, typeQuery)) {
           entry.classList.remove("hidden");
         } else {
           entry.classList.add("hidden");
@@ -0,0 +1,7 @@
+interface Shout
+    exposes [shout]
+    imports []
+
+## Says something loudly
+shout : Str -> Str
+shout = \message -> Str.concat message "!"
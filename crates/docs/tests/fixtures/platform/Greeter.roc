@@ -0,0 +1,17 @@
+interface Greeter
+    exposes [Greeter, greet, Person, person]
+    imports []
+
+## Things that can be greeted
+Greeter has
+    ## Says hello to it
+    greet : a -> Str | a has Greeter
+
+## Someone to greet
+Person := Str has [Greeter { greet: greetPerson }]
+
+## Makes a [Person] with the given name
+person : Str -> Person
+person = \name -> @Person name
+
+greetPerson = \@Person name -> "Hello, \(name)!"
@@ -0,0 +1,11 @@
+interface Greeting
+    exposes [hello]
+    imports []
+
+## Says hello to someone, like [Str]
+##
+## ```
+## expect hello "Ari" == "Hello, Ari!"
+## ```
+hello : Str -> Str
+hello = \name -> "Hello, \(name)!"
@@ -0,0 +1,9 @@
+platform "acme/greet"
+    requires {} { main : Str }
+    exposes [Greeting, Greeter]
+    packages {}
+    imports []
+    provides [mainForHost]
+
+mainForHost : Str
+mainForHost = main
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod generate_docs {
+    use roc_docs::{generate_docs_html, generate_docs_json, DocsError};
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(name)
+    }
+
+    fn output_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("roc_docs_tests").join(name);
+
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+
+        dir
+    }
+
+    #[test]
+    fn platform_package() {
+        let output_dir = output_dir("platform_package");
+
+        generate_docs_html(
+            vec![fixture("platform").join("main.roc")],
+            &output_dir,
+            "docs/greet",
+        )
+        .unwrap();
+
+        // The README becomes the front page.
+        let index = fs::read_to_string(output_dir.join("index.html")).unwrap();
+        assert!(index.contains("<h1>Greet</h1>"));
+        assert!(index.contains("<em>friendly</em>"));
+
+        // The modules the platform exposes get documented, even though only main.roc was given.
+        let greeting = fs::read_to_string(output_dir.join("Greeting").join("index.html")).unwrap();
+        assert!(greeting.contains(r#"<a href="/docs/greet/" >acme/greet</a>"#));
+        assert!(greeting.contains(r#"href="/docs/greet/Greeting#hello""#));
+
+        // Examples with `expect`s in them get highlighted as defs.
+        assert!(greeting.contains("expect"));
+
+        // Types link to their docs, in the builtins or elsewhere in the package.
+        assert!(greeting.contains(
+            r#"<a class="type-link" href="https://www.roc-lang.org/builtins/Str#Str" >Str</a>"#
+        ));
+        let greeter = fs::read_to_string(output_dir.join("Greeter").join("index.html")).unwrap();
+        assert!(greeter
+            .contains(r#"<a class="type-link" href="/docs/greet/Greeter#Person" >Person</a>"#));
+
+        // The search indexes defs by their type signatures.
+        assert!(greeter.contains(r#"href="/docs/greet/Greeter#person" data-type="Str -> Person""#));
+
+        // Abilities get their members and the types implementing them.
+        assert!(greeter.contains(r##"<a href="#Greeter" >Greeter</a> has</h3>"##));
+        assert!(greeter.contains(r#"<h4 id="greet" class="entry-name" >"#));
+        assert!(greeter.contains("<p>Says hello to it</p>"));
+        assert!(greeter.contains(
+            r#"Implemented by <a href="/docs/greet/Greeter#Person" >Greeter.Person</a>"#
+        ));
+    }
+
+    #[test]
+    fn package_header() {
+        let output_dir = output_dir("package_header");
+
+        generate_docs_html(vec![fixture("package").join("main.roc")], &output_dir, "/").unwrap();
+
+        let shout = fs::read_to_string(output_dir.join("Shout").join("index.html")).unwrap();
+        assert!(shout.contains(r#"<a href="/" >acme/shout</a>"#));
+        assert!(shout.contains("<p>Says something loudly</p>"));
+    }
+
+    #[test]
+    fn json_export() {
+        let output_dir = output_dir("json_export");
+
+        generate_docs_json(vec![fixture("platform").join("main.roc")], &output_dir).unwrap();
+
+        let json = fs::read_to_string(output_dir.join("docs.json")).unwrap();
+        let package: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(package["schemaVersion"], 1);
+        assert_eq!(package["name"], "acme/greet");
+        assert!(package["docs"].as_str().unwrap().starts_with("# Greet"));
+
+        let module = &package["modules"][0];
+        assert_eq!(module["name"], "Greeting");
+        assert!(module["path"].as_str().unwrap().ends_with("Greeting.roc"));
+
+        let hello = &module["entries"][0];
+        assert_eq!(hello["kind"], "def");
+        assert_eq!(hello["name"], "hello");
+        assert!(hello["docs"]
+            .as_str()
+            .unwrap()
+            .starts_with("Says hello to someone"));
+        assert_eq!(
+            hello["type"],
+            json!({
+                "kind": "function",
+                "args": [{ "kind": "apply", "name": "Str", "parts": [] }],
+                "output": { "kind": "apply", "name": "Str", "parts": [] },
+            })
+        );
+        assert_eq!(
+            hello["location"],
+            json!({
+                "start": { "line": 10, "column": 1 },
+                "end": { "line": 11, "column": 35 },
+            })
+        );
+
+        let greeter = &package["modules"][1]["entries"][0];
+        assert_eq!(greeter["name"], "Greeter");
+        assert_eq!(greeter["type"]["kind"], "ability");
+        assert_eq!(greeter["type"]["members"][0]["name"], "greet");
+        assert_eq!(greeter["type"]["members"][0]["docs"], "Says hello to it\n");
+        assert_eq!(
+            greeter["type"]["members"][0]["ableVariables"],
+            json!([{
+                "name": "a",
+                "ability": { "kind": "apply", "name": "Greeter", "parts": [] },
+            }])
+        );
+    }
+
+    #[test]
+    fn unwritable_output_dir() {
+        let output_dir = output_dir("unwritable_output_dir");
+        fs::create_dir_all(output_dir.parent().unwrap()).unwrap();
+        fs::write(&output_dir, "not a directory").unwrap();
+
+        let result = generate_docs_html(
+            vec![fixture("platform").join("Greeting.roc")],
+            &output_dir.join("docs"),
+            "/",
+        );
+
+        fs::remove_file(&output_dir).unwrap();
+
+        assert!(matches!(result, Err(DocsError::WrapIoError { .. })));
+    }
+
+    #[test]
+    fn load_problem() {
+        let output_dir = output_dir("load_problem");
+        let module = output_dir.with_extension("roc");
+        fs::create_dir_all(output_dir.parent().unwrap()).unwrap();
+        fs::write(&module, "interfac Broken exposes [] imports []").unwrap();
+
+        let result = generate_docs_html(vec![module.clone()], &output_dir, "/");
+
+        fs::remove_file(&module).unwrap();
+
+        match result {
+            Err(DocsError::WrapLoadingProblem { report }) => {
+                assert!(report.contains("MISSING HEADER"))
+            }
+            other => panic!("expected a loading problem, got {:?}", other.map(|_| ())),
+        }
+    }
+}
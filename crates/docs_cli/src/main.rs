@@ -1,10 +1,13 @@
 use clap::{Arg, Command};
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, generate_docs_json, DocsFormat, DEFAULT_BUILD_DIR};
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
 
 pub const DIRECTORY_OR_FILES: &str = "DIRECTORY_OR_FILES";
+pub const FLAG_OUTPUT: &str = "output";
+pub const FLAG_BASE_URL: &str = "base-url";
+pub const FLAG_FORMAT: &str = "format";
 
 fn main() -> io::Result<()> {
     let matches = Command::new("roc-docs")
@@ -16,6 +19,29 @@ fn main() -> io::Result<()> {
                 .help("The directory or files to build documentation for")
                 .allow_invalid_utf8(true),
         )
+        .arg(
+            Arg::new(FLAG_OUTPUT)
+                .long(FLAG_OUTPUT)
+                .help("The directory to write the documentation to")
+                .takes_value(true)
+                .allow_invalid_utf8(true)
+                .default_value(DEFAULT_BUILD_DIR),
+        )
+        .arg(
+            Arg::new(FLAG_BASE_URL)
+                .long(FLAG_BASE_URL)
+                .help("The path the documentation will be served from, e.g. /builtins for https://roc-lang.org/builtins")
+                .takes_value(true)
+                .default_value("/"),
+        )
+        .arg(
+            Arg::new(FLAG_FORMAT)
+                .long(FLAG_FORMAT)
+                .help("Write the documentation as html pages, or as a docs.json file for other tools to read")
+                .possible_values(DocsFormat::OPTIONS)
+                .takes_value(true)
+                .default_value("html"),
+        )
         .get_matches();
 
     let mut roc_files = Vec::new();
@@ -26,7 +52,19 @@ fn main() -> io::Result<()> {
         roc_files_recursive(os_str, metadata.file_type(), &mut roc_files)?;
     }
 
-    generate_docs_html(roc_files);
+    let output_dir = Path::new(matches.value_of_os(FLAG_OUTPUT).unwrap());
+    let base_url = matches.value_of(FLAG_BASE_URL).unwrap();
+    let format = matches.value_of_t(FLAG_FORMAT).unwrap();
+
+    let result = match format {
+        DocsFormat::Html => generate_docs_html(roc_files, output_dir, base_url),
+        DocsFormat::Json => generate_docs_json(roc_files, output_dir),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
 
     Ok(())
 }
@@ -0,0 +1,871 @@
+use crate::rust_glue::{max_pointer_tagged_variants, tagged_pointer_bitmask};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types};
+use indexmap::IndexMap;
+use roc_collections::MutSet;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt::{Display, Write};
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.h");
+const INDENT: &str = "    ";
+
+/// Each declaration, along with the targets it was generated for
+#[derive(Default)]
+struct Decls {
+    bodies: IndexMap<String, Vec<TargetInfo>>,
+    /// For each declaration, the ones which came before it when generating for some target
+    preds: Vec<MutSet<usize>>,
+    /// The declaration most recently added for the target currently being generated
+    prev: Option<usize>,
+}
+
+impl Decls {
+    fn add(&mut self, target_info: TargetInfo, body: String) {
+        let entry = self.bodies.entry(body);
+        let index = entry.index();
+
+        entry.or_default().push(target_info);
+
+        if index == self.preds.len() {
+            self.preds.push(MutSet::default());
+        }
+
+        if let Some(prev) = self.prev.replace(index) {
+            self.preds[index].insert(prev);
+        }
+    }
+
+    /// The declarations, ordered so that each one comes after everything it came after
+    /// for any of the targets. Declarations whose bodies differ between targets
+    /// (for example because of their size asserts) can't rely on the first target's order.
+    fn into_ordered(self) -> Vec<(String, Vec<TargetInfo>)> {
+        let Decls { bodies, preds, .. } = self;
+        let mut bodies: Vec<_> = bodies.into_iter().map(Some).collect();
+        let mut done = vec![false; bodies.len()];
+        let mut answer = Vec::with_capacity(bodies.len());
+
+        while answer.len() < bodies.len() {
+            let ready = (0..bodies.len())
+                .find(|&index| !done[index] && preds[index].iter().all(|&pred| done[pred]))
+                // Each target's order is valid on its own, so they can only conflict if
+                // two targets put independent declarations in opposite orders.
+                .unwrap_or_else(|| done.iter().position(|&is_done| !is_done).unwrap());
+
+            done[ready] = true;
+            answer.push(bodies[ready].take().unwrap());
+        }
+
+        answer
+    }
+}
+
+/// C needs a type to be declared before it's used by value, so the output is split into sections.
+#[derive(Default)]
+struct Output {
+    /// A typedef for every struct and union, so they can be used by pointer anywhere
+    typedefs: Decls,
+    /// The structs holding the pointer to a recursive tag union. Their payloads refer to them
+    /// by value, so they have to come before everything else.
+    handles: Decls,
+    /// Everything else, with each declaration after the ones it uses by value
+    decls: Decls,
+    /// The functions for calling closures, which take and return the other types by value
+    functions: Decls,
+}
+
+pub fn emit(types_and_targets: &[(Types, TargetInfo)]) -> String {
+    let mut output = Output::default();
+
+    for (types, target_info) in types_and_targets {
+        let mut added = MutSet::default();
+
+        output.typedefs.prev = None;
+        output.handles.prev = None;
+        output.decls.prev = None;
+        output.functions.prev = None;
+
+        for id in types.ids() {
+            add_type_and_deps(*target_info, id, types, &mut added, &mut output);
+        }
+    }
+
+    let mut buf = String::new();
+    let target_count = types_and_targets.len();
+
+    write_decls(&mut buf, output.typedefs, target_count, false);
+    write_decls(&mut buf, output.handles, target_count, true);
+    write_decls(&mut buf, output.decls, target_count, true);
+    write_decls(&mut buf, output.functions, target_count, true);
+
+    buf
+}
+
+/// Declarations which only apply to some targets are wrapped in an `#if`
+fn write_decls(buf: &mut String, decls: Decls, target_count: usize, blank_lines: bool) {
+    let decls = decls.into_ordered();
+
+    if decls.is_empty() {
+        return;
+    }
+
+    if !blank_lines {
+        buf.push('\n');
+    }
+
+    for (decl, targets) in decls {
+        if blank_lines {
+            buf.push('\n');
+        }
+
+        if targets.len() == target_count {
+            buf.push_str(&decl);
+            buf.push('\n');
+        } else {
+            // We should never have a decl recorded with 0 targets!
+            debug_assert_ne!(targets.len(), 0);
+
+            let condition = targets
+                .iter()
+                .map(|target_info| format!("defined({})", arch_macro(target_info.architecture)))
+                .collect::<Vec<_>>()
+                .join(" || ");
+
+            writeln!(buf, "#if {condition}\n{decl}\n#endif").unwrap();
+        }
+    }
+}
+
+fn add_type_and_deps(
+    target_info: TargetInfo,
+    id: TypeId,
+    types: &Types,
+    added: &mut MutSet<TypeId>,
+    output: &mut Output,
+) {
+    if added.insert(id) {
+        for dep in value_deps(types.get_type(id)) {
+            add_type_and_deps(target_info, dep, types, added, output);
+        }
+
+        add_type(target_info, id, types, output);
+    }
+}
+
+/// The types which are stored inline in the given one, rather than behind a pointer
+fn value_deps(typ: &RocType) -> Vec<TypeId> {
+    match typ {
+        RocType::Struct { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::TagUnionPayload { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::RocResult(ok_id, err_id) => vec![*ok_id, *err_id],
+        // Its arguments and return value are only used by the function for calling it,
+        // which comes after all the other declarations.
+        RocType::Function { .. } => Vec::new(),
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { tags, .. }) => {
+            tags.iter().filter_map(|(_, payload)| *payload).collect()
+        }
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { payload, .. }) => vec![*payload],
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => {
+            payload_fields.clone()
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { .. })
+        | RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Num(_)
+        | RocType::Bool
+        | RocType::RocStr
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_)
+        | RocType::RecursivePointer(_) => Vec::new(),
+    }
+}
+
+fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, output: &mut Output) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } => add_struct(
+            name,
+            target_info,
+            id,
+            fields
+                .iter()
+                .map(|(label, field_id)| (escape_kw(label.clone()), *field_id)),
+            types,
+            output,
+        ),
+        RocType::TagUnionPayload { name, fields } => add_struct(
+            name,
+            target_info,
+            id,
+            // Tag union payloads have numbered fields, so we prefix them
+            // with an "f" because C doesn't allow struct fields to be numbers.
+            fields
+                .iter()
+                .map(|(label, field_id)| (format!("f{label}"), *field_id)),
+            types,
+            output,
+        ),
+        RocType::RocResult(ok_id, err_id) => {
+            add_result(target_info, id, *ok_id, *err_id, types, output)
+        }
+        RocType::TagUnion(tag_union) => {
+            match tag_union {
+                RocTagUnion::Enumeration { tags, name, size } => {
+                    add_enumeration(name, target_info, tags.iter(), *size, output)
+                }
+                RocTagUnion::NonRecursive {
+                    tags,
+                    name,
+                    discriminant_size,
+                    discriminant_offset,
+                } => {
+                    // Empty tag unions can never come up at runtime,
+                    // and so don't need declared types.
+                    if !tags.is_empty() {
+                        add_tag_union(
+                            name,
+                            target_info,
+                            id,
+                            tags,
+                            *discriminant_size,
+                            *discriminant_offset,
+                            types,
+                            output,
+                        );
+                    }
+                }
+                RocTagUnion::Recursive {
+                    tags,
+                    name,
+                    discriminant_size,
+                    ..
+                } => {
+                    if !tags.is_empty() {
+                        add_recursive_tag_union(
+                            name,
+                            target_info,
+                            tags,
+                            None,
+                            *discriminant_size,
+                            types,
+                            output,
+                        );
+                    }
+                }
+                RocTagUnion::NullableWrapped {
+                    name,
+                    index_of_null_tag,
+                    tags,
+                    discriminant_size,
+                    ..
+                } => add_recursive_tag_union(
+                    name,
+                    target_info,
+                    tags,
+                    Some(*index_of_null_tag as usize),
+                    *discriminant_size,
+                    types,
+                    output,
+                ),
+                RocTagUnion::NullableUnwrapped {
+                    name,
+                    null_tag,
+                    non_null_tag,
+                    non_null_payload,
+                    ..
+                } => add_nullable_unwrapped(
+                    name,
+                    target_info,
+                    null_tag,
+                    non_null_tag,
+                    *non_null_payload,
+                    types,
+                    output,
+                ),
+                RocTagUnion::SingleTagStruct {
+                    name,
+                    payload_fields,
+                    ..
+                } => add_struct(
+                    name,
+                    target_info,
+                    id,
+                    payload_fields
+                        .iter()
+                        .enumerate()
+                        .map(|(index, field_id)| (format!("f{index}"), *field_id)),
+                    types,
+                    output,
+                ),
+                RocTagUnion::NonNullableUnwrapped {
+                    name,
+                    tag_name,
+                    payload,
+                } => add_recursive_tag_union(
+                    name,
+                    target_info,
+                    &[(tag_name.clone(), Some(*payload))],
+                    None,
+                    0,
+                    types,
+                    output,
+                ),
+            }
+        }
+        // These types are either declared in the header, or zero-sized.
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Num(_)
+        | RocType::Bool
+        | RocType::RocStr
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_) => {}
+        RocType::RecursivePointer { .. } => {
+            // This is recursively pointing to a type that should already have been added,
+            // so no extra work needs to happen.
+        }
+        RocType::Function {
+            name,
+            extern_name,
+            args,
+            ret,
+        } => add_function(
+            name,
+            target_info,
+            extern_name,
+            args,
+            *ret,
+            id,
+            types,
+            output,
+        ),
+    }
+}
+
+/// C doesn't allow empty structs or unions, so zero-sized types are left out entirely,
+/// both as declarations and as fields.
+fn is_zero_sized(id: TypeId, types: &Types) -> bool {
+    types.size_ignoring_alignment(id) == 0
+}
+
+fn add_struct<I: Iterator<Item = (String, TypeId)>>(
+    name: &str,
+    target_info: TargetInfo,
+    struct_id: TypeId,
+    fields: I,
+    types: &Types,
+    output: &mut Output,
+) {
+    if is_zero_sized(struct_id, types) {
+        return;
+    }
+
+    let name = escape_kw(name.to_string());
+    let mut buf = format!("struct {name} {{\n");
+
+    for (label, type_id) in fields {
+        if !is_zero_sized(type_id, types) {
+            writeln!(buf, "{INDENT}{};", declarator(type_id, types, &label)).unwrap();
+        }
+    }
+
+    buf.push_str("};");
+
+    // The sizes recorded for the payloads of recursive tag unions are those of the pointer,
+    // so only check the size of records.
+    if matches!(types.get_type(struct_id), RocType::Struct { .. }) {
+        buf.push_str("\n\n");
+        buf.push_str(&size_assert(&name, struct_id, types));
+    }
+
+    output
+        .typedefs
+        .add(target_info, format!("typedef struct {name} {name};"));
+    output.decls.add(target_info, buf);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn add_function(
+    name: &str,
+    target_info: TargetInfo,
+    extern_name: &str,
+    args: &[TypeId],
+    ret: TypeId,
+    fn_id: TypeId,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let size = types.size_rounded_to_alignment(fn_id);
+    let align = types.align(fn_id).max(1);
+
+    // The host never looks inside the environment the closure captured; it only passes it back
+    // to Roc when calling the closure. A closure which captured nothing still needs a type to
+    // call it through, so it gets a byte, but like other zero-sized types it's left out of fields.
+    let mut buf = format!(
+        "struct {name} {{\n{INDENT}_Alignas({align}) uint8_t closure_data[{}];\n}};",
+        size.max(1)
+    );
+
+    if size > 0 {
+        buf.push_str("\n\n");
+        buf.push_str(&size_assert(&name, fn_id, types));
+    }
+
+    output
+        .typedefs
+        .add(target_info, format!("typedef struct {name} {name};"));
+    output.decls.add(target_info, buf);
+
+    // The caller takes everything by pointer, writing the return value through the last one.
+    // Zero-sized values have no C type, so they're passed as a pointer to an unused byte.
+    let mut extern_params = Vec::with_capacity(args.len() + 2);
+    let mut params = vec![format!("const {name} *closure")];
+    let mut call_args = Vec::with_capacity(args.len() + 2);
+    let mut needs_unit = false;
+
+    for (index, arg_id) in args.iter().enumerate() {
+        if is_zero_sized(*arg_id, types) {
+            extern_params.push(format!("void *arg{index}"));
+            call_args.push("&unit".to_string());
+            needs_unit = true;
+        } else {
+            extern_params.push(declarator(*arg_id, types, &format!("*arg{index}")));
+            params.push(declarator(*arg_id, types, &format!("arg{index}")));
+            call_args.push(format!("&arg{index}"));
+        }
+    }
+
+    extern_params.push(format!("const {name} *closure_data"));
+    call_args.push("closure".to_string());
+
+    let returns_value = !is_zero_sized(ret, types);
+    let ret_declarator;
+    let mut body = String::new();
+
+    if returns_value {
+        ret_declarator = declarator(ret, types, &format!("{name}_call"));
+        extern_params.push(declarator(ret, types, "*output"));
+        call_args.push("&output".to_string());
+        writeln!(body, "{INDENT}{};", declarator(ret, types, "output")).unwrap();
+    } else {
+        ret_declarator = format!("void {name}_call");
+        extern_params.push("void *output".to_string());
+        call_args.push("&unit".to_string());
+        needs_unit = true;
+    }
+
+    if needs_unit {
+        body.insert_str(0, &format!("{INDENT}char unit = 0;\n"));
+    }
+
+    writeln!(body, "\n{INDENT}{extern_name}({});", call_args.join(", ")).unwrap();
+
+    if returns_value {
+        writeln!(body, "\n{INDENT}return output;").unwrap();
+    }
+
+    output.functions.add(
+        target_info,
+        format!(
+            r#"extern void {extern_name}({});
+
+// Roc takes ownership of both the arguments and the environment the closure captured.
+static inline {ret_declarator}({}) {{
+{body}}}"#,
+            extern_params.join(", "),
+            params.join(", ")
+        ),
+    );
+}
+
+fn add_result(
+    target_info: TargetInfo,
+    id: TypeId,
+    ok_id: TypeId,
+    err_id: TypeId,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = result_name(ok_id, err_id, types);
+    let mut buf = format!("struct {name} {{\n");
+    let payloads = [("ok", ok_id), ("err", err_id)]
+        .into_iter()
+        .filter(|(_, payload_id)| !is_zero_sized(*payload_id, types))
+        .collect::<Vec<_>>();
+
+    if !payloads.is_empty() {
+        writeln!(buf, "{INDENT}union {{").unwrap();
+
+        for (label, payload_id) in payloads {
+            writeln!(
+                buf,
+                "{INDENT}{INDENT}{};",
+                declarator(payload_id, types, label)
+            )
+            .unwrap();
+        }
+
+        writeln!(buf, "{INDENT}}} payload;").unwrap();
+    }
+
+    writeln!(buf, "{INDENT}uint8_t tag; // 1 for Ok, 0 for Err").unwrap();
+    buf.push_str("};\n\n");
+    buf.push_str(&size_assert(&name, id, types));
+
+    output
+        .typedefs
+        .add(target_info, format!("typedef struct {name} {name};"));
+    output.decls.add(target_info, buf);
+}
+
+fn add_enumeration<I: ExactSizeIterator<Item = S>, S: Display>(
+    name: &str,
+    target_info: TargetInfo,
+    tags: I,
+    tag_bytes: u32,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let repr_bits = tag_bytes * 8;
+
+    // The enum only declares the constants; the values are stored in an integer of the
+    // right size, since the size of a C enum is up to the compiler.
+    let mut buf = format!("typedef uint{repr_bits}_t {name};\n\nenum {{\n");
+
+    for (index, tag_name) in tags.enumerate() {
+        writeln!(buf, "{INDENT}{name}_{tag_name} = {index},").unwrap();
+    }
+
+    buf.push_str("};");
+
+    output.decls.add(target_info, buf);
+}
+
+fn add_discriminant(
+    name: &str,
+    target_info: TargetInfo,
+    tag_names: Vec<String>,
+    size: u32,
+    output: &mut Output,
+) -> String {
+    // The tag union's discriminant, e.g.
+    //
+    // typedef uint8_t discriminant_MyTagUnion;
+    //
+    // enum {
+    //     discriminant_MyTagUnion_Bar = 0,
+    //     discriminant_MyTagUnion_Foo = 1,
+    // };
+    let discriminant_name = format!("discriminant_{name}");
+
+    add_enumeration(
+        &discriminant_name,
+        target_info,
+        tag_names.iter(),
+        size,
+        output,
+    );
+
+    discriminant_name
+}
+
+#[allow(clippy::too_many_arguments)]
+fn add_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    type_id: TypeId,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let size_rounded_to_alignment = types.size_rounded_to_alignment(type_id);
+
+    // As in the Rust glue, the whole tag union is a C union, and the discriminant
+    // is at a fixed offset in it - often in what would otherwise be padding.
+    let mut buf = format!("union {name} {{\n");
+
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            if !is_zero_sized(*payload_id, types) {
+                writeln!(buf, "{INDENT}{};", declarator(*payload_id, types, tag_name)).unwrap();
+            }
+        }
+    }
+
+    if tags.len() > 1 {
+        // Make room for the discriminant, which may come after the largest payload.
+        writeln!(buf, "{INDENT}uint8_t _sizer[{size_rounded_to_alignment}];").unwrap();
+    }
+
+    buf.push_str("};\n\n");
+    buf.push_str(&size_assert(&name, type_id, types));
+
+    if discriminant_size > 0 {
+        let tag_names = tags.iter().map(|(tag_name, _)| tag_name.clone()).collect();
+        let discriminant_name =
+            add_discriminant(&name, target_info, tag_names, discriminant_size, output);
+
+        write!(
+            buf,
+            r#"
+
+static inline {discriminant_name} {name}_discriminant(const {name} *tag_union) {{
+    return *(const {discriminant_name} *)((const char *)tag_union + {discriminant_offset});
+}}
+
+static inline void {name}_set_discriminant({name} *tag_union, {discriminant_name} discriminant) {{
+    *({discriminant_name} *)((char *)tag_union + {discriminant_offset}) = discriminant;
+}}"#
+        )
+        .unwrap();
+    }
+
+    output
+        .typedefs
+        .add(target_info, format!("typedef union {name} {name};"));
+    output.decls.add(target_info, buf);
+}
+
+/// Recursive tag unions are a pointer to a union of their payloads. Unless there's only one
+/// tag, the discriminant is stored in the unused bits at the end of the pointer.
+fn add_recursive_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    tags: &[(String, Option<TypeId>)],
+    null_tag_index: Option<usize>, // used only in the nullable-wrapped case
+    discriminant_size: u32,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let union_name = format!("union_{name}");
+
+    // We should never be attempting to generate glue for empty tag unions;
+    // RocType should not have let this happen.
+    debug_assert_ne!(tags.len(), 0);
+
+    output.typedefs.add(
+        target_info,
+        format!("typedef union {union_name} {union_name};"),
+    );
+    output.handles.add(
+        target_info,
+        format!("typedef struct {name} {{\n{INDENT}{union_name} *pointer;\n}} {name};"),
+    );
+
+    let mut buf = format!("union {union_name} {{\n");
+
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            if !is_zero_sized(*payload_id, types) {
+                writeln!(buf, "{INDENT}{};", declarator(*payload_id, types, tag_name)).unwrap();
+            }
+        }
+    }
+
+    buf.push_str("};");
+
+    if discriminant_size > 0 {
+        if tags.len() > max_pointer_tagged_variants(target_info.architecture) {
+            todo!(
+                "Support {} tags in a recursive tag union on target_info {:?}. (This is too many tags for pointer tagging to work, so we need to generate different glue.)",
+                tags.len(),
+                target_info
+            );
+        }
+
+        let tag_names = tags.iter().map(|(tag_name, _)| tag_name.clone()).collect();
+        let discriminant_name =
+            add_discriminant(&name, target_info, tag_names, discriminant_size, output);
+        let bitmask = format!("{:#x}", tagged_pointer_bitmask(target_info.architecture));
+        let null_check = match null_tag_index {
+            Some(index) => format!("if (tag_union.pointer == NULL) {{\n{INDENT}{INDENT}return {index};\n{INDENT}}}\n\n{INDENT}"),
+            None => String::new(),
+        };
+
+        write!(
+            buf,
+            r#"
+
+static inline {discriminant_name} {name}_discriminant({name} tag_union) {{
+    {null_check}// The discriminant is stored in the unused bits at the end of the pointer
+    return ({discriminant_name})((uintptr_t)tag_union.pointer & {bitmask});
+}}
+
+static inline {union_name} *{name}_union_pointer({name} tag_union) {{
+    return ({union_name} *)((uintptr_t)tag_union.pointer & ~(uintptr_t){bitmask});
+}}"#
+        )
+        .unwrap();
+    }
+
+    output.decls.add(target_info, buf);
+}
+
+/// A recursive tag union with two tags, where the one without a payload is a null pointer,
+/// and the other one is a pointer to its payload.
+#[allow(clippy::too_many_arguments)]
+fn add_nullable_unwrapped(
+    name: &str,
+    target_info: TargetInfo,
+    null_tag: &str,
+    non_null_tag: &str,
+    non_null_payload: TypeId,
+    types: &Types,
+    output: &mut Output,
+) {
+    let name = escape_kw(name.to_string());
+    let mut tag_names = vec![null_tag.to_string(), non_null_tag.to_string()];
+
+    tag_names.sort();
+
+    let discriminant_name = add_discriminant(&name, target_info, tag_names, 1, output);
+    let pointer = declarator(non_null_payload, types, "*pointer");
+
+    output.handles.add(
+        target_info,
+        format!("typedef struct {name} {{\n{INDENT}{pointer};\n}} {name};"),
+    );
+    output.decls.add(target_info,
+        format!(
+            r#"static inline {discriminant_name} {name}_discriminant({name} tag_union) {{
+    return tag_union.pointer == NULL ? {discriminant_name}_{null_tag} : {discriminant_name}_{non_null_tag};
+}}"#
+        ),
+    );
+}
+
+fn size_assert(name: &str, id: TypeId, types: &Types) -> String {
+    let size = types.size_rounded_to_alignment(id);
+
+    format!("_Static_assert(sizeof({name}) == {size}, \"{name} should be {size} bytes\");")
+}
+
+/// A declaration of the given name with the given type, e.g. `int64_t foo` or `Expr *foo`
+fn declarator(id: TypeId, types: &Types, name: &str) -> String {
+    let type_str = type_name(id, types);
+
+    if type_str.ends_with('*') {
+        format!("{type_str}{name}")
+    } else {
+        format!("{type_str} {name}")
+    }
+}
+
+fn type_name(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        // These are only ever used behind a pointer
+        RocType::Unit | RocType::EmptyTagUnion => "void".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(RocNum::U8) => "uint8_t".to_string(),
+        RocType::Num(RocNum::U16) => "uint16_t".to_string(),
+        RocType::Num(RocNum::U32) => "uint32_t".to_string(),
+        RocType::Num(RocNum::U64) => "uint64_t".to_string(),
+        RocType::Num(RocNum::U128) => "RocU128".to_string(),
+        RocType::Num(RocNum::I8) => "int8_t".to_string(),
+        RocType::Num(RocNum::I16) => "int16_t".to_string(),
+        RocType::Num(RocNum::I32) => "int32_t".to_string(),
+        RocType::Num(RocNum::I64) => "int64_t".to_string(),
+        RocType::Num(RocNum::I128) => "RocI128".to_string(),
+        RocType::Num(RocNum::F32) => "float".to_string(),
+        RocType::Num(RocNum::F64) => "double".to_string(),
+        RocType::Num(RocNum::F128) => "long double".to_string(),
+        RocType::Num(RocNum::Dec) => "RocDec".to_string(),
+        RocType::RocDict(_, _) => "RocDict".to_string(),
+        RocType::RocSet(_) => "RocSet".to_string(),
+        RocType::RocList(_) => "RocList".to_string(),
+        RocType::RocBox(elem_id) => format!("{} *", type_name(*elem_id, types)),
+        RocType::RocResult(ok_id, err_id) => result_name(*ok_id, *err_id, types),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. }) => escape_kw(name.clone()),
+        RocType::RecursivePointer(content) => type_name(*content, types),
+        RocType::Function { name, .. } => escape_kw(name.clone()),
+    }
+}
+
+/// C has no generics, so every `Result` type gets its own struct, e.g. `RocResult_I64_Str`
+pub(crate) fn result_name(ok_id: TypeId, err_id: TypeId, types: &Types) -> String {
+    format!(
+        "RocResult_{}_{}",
+        mangled_name(ok_id, types),
+        mangled_name(err_id, types)
+    )
+}
+
+fn mangled_name(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        RocType::Unit => "Unit".to_string(),
+        RocType::EmptyTagUnion => "Never".to_string(),
+        RocType::RocStr => "Str".to_string(),
+        RocType::Bool => "Bool".to_string(),
+        RocType::Num(num) => format!("{num:?}"),
+        RocType::RocDict(key_id, val_id) => format!(
+            "Dict_{}_{}",
+            mangled_name(*key_id, types),
+            mangled_name(*val_id, types)
+        ),
+        RocType::RocSet(elem_id) => format!("Set_{}", mangled_name(*elem_id, types)),
+        RocType::RocList(elem_id) => format!("List_{}", mangled_name(*elem_id, types)),
+        RocType::RocBox(elem_id) => format!("Box_{}", mangled_name(*elem_id, types)),
+        RocType::RocResult(ok_id, err_id) => format!(
+            "Result_{}_{}",
+            mangled_name(*ok_id, types),
+            mangled_name(*err_id, types)
+        ),
+        RocType::RecursivePointer(content) => mangled_name(*content, types),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. })
+        | RocType::Function { name, .. } => name.clone(),
+    }
+}
+
+/// The predefined macro that is set when compiling for the given architecture
+fn arch_macro(architecture: Architecture) -> &'static str {
+    match architecture {
+        Architecture::X86_64 => "__x86_64__",
+        Architecture::X86_32 => "__i386__",
+        Architecture::Aarch64 => "__aarch64__",
+        Architecture::Aarch32 => "__arm__",
+        Architecture::Wasm32 => "__wasm32__",
+    }
+}
+
+// Based on the C11 keywords, plus the macros from stdbool.h
+const RESERVED_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while", "bool", "true", "false",
+];
+
+/// Escape a C reserved keyword, if necessary.
+fn escape_kw(input: String) -> String {
+    if RESERVED_KEYWORDS.contains(&input.as_str()) {
+        format!("{input}_")
+    } else {
+        input
+    }
+}
@@ -1,10 +1,17 @@
-use crate::rust_glue;
+use crate::report::{glue_problem, Problem};
 use crate::types::{Env, Types};
+use crate::{c_glue, rust_glue, zig_glue};
 use bumpalo::Bump;
+use roc_collections::MutMap;
 use roc_intern::GlobalInterner;
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
-use roc_reporting::report::RenderTarget;
+use roc_load::{ExecutionMode, LoadConfig, LoadedModule, Threading};
+use roc_module::symbol::{Interns, ModuleId};
+use roc_region::all::LineInfo;
+use roc_reporting::report::{
+    can_problem, type_problem, RenderTarget, Report, RocDocAllocator, Severity, DEFAULT_PALETTE,
+};
 use roc_target::{Architecture, OperatingSystem, TargetInfo};
+use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{self, ErrorKind, Write};
 use std::path::{Path, PathBuf};
@@ -12,7 +19,27 @@ use std::process;
 use strum::IntoEnumIterator;
 use target_lexicon::Triple;
 
-pub fn generate(input_path: &Path, output_path: &Path) -> io::Result<i32> {
+/// The languages `roc glue` can generate declarations in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlueLanguage {
+    Rust,
+    C,
+    Zig,
+}
+
+impl GlueLanguage {
+    /// Picks the language from the extension of the file the glue gets written to
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("rs") => Some(GlueLanguage::Rust),
+            Some("h") => Some(GlueLanguage::C),
+            Some("zig") => Some(GlueLanguage::Zig),
+            _ => None,
+        }
+    }
+}
+
+pub fn generate(input_path: &Path, output_path: &Path, language: GlueLanguage) -> io::Result<i32> {
     match load_types(input_path.to_path_buf(), Threading::AllAvailable) {
         Ok(types_and_targets) => {
             let mut file = File::create(output_path).unwrap_or_else(|err| {
@@ -25,8 +52,12 @@ pub fn generate(input_path: &Path, output_path: &Path) -> io::Result<i32> {
                 process::exit(1);
             });
 
-            let mut buf = std::str::from_utf8(rust_glue::HEADER).unwrap().to_string();
-            let body = rust_glue::emit(&types_and_targets);
+            let (header, body) = match language {
+                GlueLanguage::Rust => (rust_glue::HEADER, rust_glue::emit(&types_and_targets)),
+                GlueLanguage::C => (c_glue::HEADER, c_glue::emit(&types_and_targets)),
+                GlueLanguage::Zig => (zig_glue::HEADER, zig_glue::emit(&types_and_targets)),
+            };
+            let mut buf = std::str::from_utf8(header).unwrap().to_string();
 
             buf.push_str(&body);
 
@@ -79,6 +110,7 @@ pub fn load_types(
         mut declarations_by_id,
         mut solved,
         interns,
+        sources,
         ..
     } = roc_load::load_and_typecheck(
         arena,
@@ -91,36 +123,61 @@ pub fn load_types(
             exec_mode: ExecutionMode::Check,
         },
     )
-    .unwrap_or_else(|problem| match problem {
-        LoadingProblem::FormattedReport(report) => {
-            eprintln!("{}", report);
+    .unwrap_or_else(|problem| {
+        eprintln!("{}", problem.into_report(RenderTarget::Generic));
 
-            process::exit(1);
-        }
-        problem => {
-            todo!("{:?}", problem);
-        }
+        process::exit(1);
     });
 
-    let decls = declarations_by_id.remove(&home).unwrap();
-    let subs = solved.inner_mut();
+    let mut errors = 0;
+
+    for (module_id, (module_path, src)) in sources.iter() {
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let alloc = RocDocAllocator::new(&src_lines, *module_id, &interns);
+
+        let can_reports = can_problems
+            .remove(module_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|problem| can_problem(&alloc, &lines, module_path.clone(), problem));
+        let type_reports = type_problems
+            .remove(module_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|problem| type_problem(&alloc, &lines, module_path.clone(), problem));
+
+        for report in can_reports.chain(type_reports) {
+            if report.severity == Severity::RuntimeError {
+                errors += 1;
+            }
 
-    let can_problems = can_problems.remove(&home).unwrap_or_default();
-    let type_problems = type_problems.remove(&home).unwrap_or_default();
+            print_report(report, &alloc);
+        }
+    }
 
-    if !can_problems.is_empty() || !type_problems.is_empty() {
-        todo!(
-            "Gracefully report compilation problems during glue generation: {:?}, {:?}",
-            can_problems,
-            type_problems
-        );
+    // Warnings don't stop us from generating glue, but errors do.
+    if errors > 0 {
+        process::exit(1);
     }
 
+    let decls = declarations_by_id.remove(&home).unwrap();
+    let subs = solved.inner_mut();
+
     let variables = (0..decls.len()).filter_map(|index| {
         use roc_can::expr::DeclarationTag::*;
 
         match decls.declarations[index] {
-            Value | Function(_) | Recursive(_) | TailRecursive(_) => Some(decls.variables[index]),
+            Value | Function(_) | Recursive(_) | TailRecursive(_) => {
+                let loc_symbol = decls.symbols[index];
+                // Point problems with the value's type at its annotation, if it has one.
+                let region = match &decls.annotations[index] {
+                    Some(annotation) => annotation.region,
+                    None => loc_symbol.region,
+                };
+
+                Some((loc_symbol.value, region, decls.variables[index]))
+            }
             Destructure(_) => {
                 // figure out if we need to export non-identifier defs - when would that
                 // happen?
@@ -150,7 +207,13 @@ pub fn load_types(
         let types = {
             let mut env = Env::new(arena, subs, &interns, layout_interner.fork(), target_info);
 
+            // The problems are the same for every target, so this exits on the first one.
             env.vars_to_types(variables.clone())
+                .unwrap_or_else(|problems| {
+                    report_glue_problems(home, &sources, &interns, problems);
+
+                    process::exit(1);
+                })
         };
 
         types_and_targets.push((types, target_info));
@@ -158,3 +221,29 @@ pub fn load_types(
 
     Ok(types_and_targets)
 }
+
+fn report_glue_problems(
+    home: ModuleId,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    problems: Vec<Problem>,
+) {
+    let (module_path, src) = &sources[&home];
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let lines = LineInfo::new(src);
+    let alloc = RocDocAllocator::new(&src_lines, home, interns);
+
+    for problem in problems {
+        let report = glue_problem(&alloc, &lines, module_path.clone(), problem);
+
+        print_report(report, &alloc);
+    }
+}
+
+fn print_report<'b>(report: Report<'b>, alloc: &'b RocDocAllocator<'b>) {
+    let mut buf = String::new();
+
+    report.render_color_terminal(&mut buf, alloc, &DEFAULT_PALETTE);
+
+    eprintln!("\n{}\n", buf);
+}
@@ -0,0 +1,101 @@
+use roc_module::ident::Lowercase;
+use roc_module::symbol::Symbol;
+use roc_region::all::{LineInfo, Region};
+use roc_reporting::report::{Report, RocDocAllocator, Severity};
+use std::path::PathBuf;
+use ven_pretty::DocAllocator;
+
+/// A reason glue can't be generated for a value the platform provides to the host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The value's type still has a type variable in it, e.g. `List a` or `List *`,
+    /// so there's no single layout to describe to the host.
+    TypeVariable {
+        symbol: Symbol,
+        region: Region,
+        /// None for wildcards and inferred variables
+        name: Option<Lowercase>,
+    },
+    /// The value's type has a function in it which wasn't named with `as`,
+    /// so Roc won't generate a caller the host could use to run it.
+    UnnamedFunction { symbol: Symbol, region: Region },
+}
+
+const TYPE_VARIABLE: &str = "TYPE VARIABLE PASSED TO HOST";
+const UNNAMED_FUNCTION: &str = "UNNAMED FUNCTION PASSED TO HOST";
+
+pub fn glue_problem<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    filename: PathBuf,
+    problem: Problem,
+) -> Report<'b> {
+    let doc;
+    let title;
+
+    match problem {
+        Problem::TypeVariable {
+            symbol,
+            region,
+            name,
+        } => {
+            let var_doc = match name {
+                Some(name) => alloc
+                    .reflow("the type variable ")
+                    .append(alloc.type_variable(name)),
+                None => alloc.reflow("an unspecified type"),
+            };
+
+            doc = alloc.stack([
+                alloc.concat([
+                    alloc.reflow("The type of "),
+                    alloc.symbol_unqualified(symbol),
+                    alloc.reflow(" has "),
+                    var_doc,
+                    alloc.reflow(" in it, so I can't generate glue for it:"),
+                ]),
+                alloc.region(lines.convert_region(region)),
+                alloc.reflow(
+                    "The host needs to know the exact layout of everything the platform provides. \
+                    Try replacing the type variable with a concrete type, like ",
+                )
+                .append(alloc.type_str("Str"))
+                .append(alloc.reflow(" or "))
+                .append(alloc.type_str("I64"))
+                .append(alloc.reflow(".")),
+            ]);
+
+            title = TYPE_VARIABLE.to_string();
+        }
+        Problem::UnnamedFunction { symbol, region } => {
+            doc = alloc.stack([
+                alloc.concat([
+                    alloc.reflow("The type of "),
+                    alloc.symbol_unqualified(symbol),
+                    alloc.reflow(" has a function in it that isn't named with "),
+                    alloc.keyword("as"),
+                    alloc.reflow(", so I can't generate glue for it:"),
+                ]),
+                alloc.region(lines.convert_region(region)),
+                alloc.concat([
+                    alloc.reflow(
+                        "The host can only call functions the platform gives a name, like ",
+                    ),
+                    alloc.type_str("(Str -> Str) as Handler"),
+                    alloc.reflow(". Try naming the function's type with "),
+                    alloc.keyword("as"),
+                    alloc.reflow("."),
+                ]),
+            ]);
+
+            title = UNNAMED_FUNCTION.to_string();
+        }
+    }
+
+    Report {
+        title,
+        filename,
+        doc,
+        severity: Severity::RuntimeError,
+    }
+}
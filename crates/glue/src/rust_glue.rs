@@ -294,9 +294,12 @@ fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, impls: &mut Impl
             // This is recursively pointing to a type that should already have been added,
             // so no extra work needs to happen.
         }
-        RocType::Function { .. } => {
-            // TODO actually generate glue functions!
-        }
+        RocType::Function {
+            name,
+            extern_name,
+            args,
+            ret,
+        } => add_function(name, target_info, extern_name, args, *ret, id, types, impls),
     }
 }
 
@@ -942,7 +945,8 @@ pub struct {name} {{
                     | RocType::RocBox(_)
                     | RocType::TagUnion(_)
                     | RocType::RocResult(_, _)
-                    | RocType::RecursivePointer { .. } => {
+                    | RocType::RecursivePointer { .. }
+                    | RocType::Function { .. } => {
                         owned_ret_type = type_name(*payload_id, types);
                         borrowed_ret_type = format!("&{}", owned_ret_type);
                         owned_ret = "payload".to_string();
@@ -976,7 +980,6 @@ pub struct {name} {{
                         payload_args = answer.payload_args;
                         args_to_payload = answer.args_to_payload;
                     }
-                    RocType::Function { .. } => todo!(),
                 };
 
                 {
@@ -1565,7 +1568,8 @@ pub struct {name} {{
                             | RocType::TagUnion(_)
                             | RocType::RocResult(_, _)
                             | RocType::Struct { .. }
-                            | RocType::RecursivePointer { .. } => {
+                            | RocType::RecursivePointer { .. }
+                            | RocType::Function { .. } => {
                                 format!(".field({deref_str}{actual_self}.{tag_name})")
                             }
                             RocType::TagUnionPayload { fields, .. } => {
@@ -1580,7 +1584,6 @@ pub struct {name} {{
 
                                 buf.join("\n")
                             }
-                            RocType::Function { .. } => todo!(),
                         };
 
                         format!(
@@ -1711,6 +1714,99 @@ fn add_struct<S: Display>(
     add_decl(impls, None, target_info, buf);
 }
 
+#[allow(clippy::too_many_arguments)]
+fn add_function(
+    name: &str,
+    target_info: TargetInfo,
+    extern_name: &str,
+    args: &[TypeId],
+    ret: TypeId,
+    fn_id: TypeId,
+    types: &Types,
+    impls: &mut Impls,
+) {
+    let name = escape_kw(name.to_string());
+    let size = types.size_rounded_to_alignment(fn_id);
+    let align = types.align(fn_id).max(1);
+
+    // The host never looks inside the environment the closure captured;
+    // it only passes it back to Roc when calling the closure.
+    add_decl(
+        impls,
+        None,
+        target_info,
+        format!(
+            r#"#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+#[repr(C, align({align}))]
+pub struct {name} {{
+    closure_data: [u8; {size}],
+}}"#
+        ),
+    );
+
+    let ret_type = type_name(ret, types);
+    let mut params = Vec::with_capacity(args.len());
+    let mut extern_params = Vec::with_capacity(args.len() + 2);
+    let mut wrap_args = String::new();
+    let mut call_args = Vec::with_capacity(args.len() + 2);
+
+    for (index, arg_id) in args.iter().enumerate() {
+        let arg_type = type_name(*arg_id, types);
+
+        extern_params.push(format!("arg{index}: *const {arg_type}"));
+
+        // There's no need to make the host pass zero-sized arguments, like the `{}` in `{} -> a`
+        if types.size_ignoring_alignment(*arg_id) == 0 {
+            call_args.push(format!("&{arg_type}"));
+        } else {
+            params.push(format!("arg{index}: {arg_type}"));
+            // Roc takes ownership of the arguments, so we must not drop them here.
+            writeln!(
+                wrap_args,
+                "{INDENT}{INDENT}let arg{index} = core::mem::ManuallyDrop::new(arg{index});"
+            )
+            .unwrap();
+            call_args.push(format!("&*arg{index}"));
+        }
+    }
+
+    extern_params.push("closure_data: *const u8".to_string());
+    extern_params.push(format!("output: *mut {ret_type}"));
+    call_args.push("self.closure_data.as_ptr()".to_string());
+    call_args.push("output.as_mut_ptr()".to_string());
+
+    let params = std::iter::once("self".to_string())
+        .chain(params)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let extern_params = extern_params.join(", ");
+    let call_args = call_args.join(", ");
+
+    add_decl(
+        impls,
+        Some(format!("impl {name}")),
+        target_info,
+        format!(
+            r#"/// Calls this closure with the environment it captured.
+    /// Roc takes ownership of both the arguments and the captured environment.
+    pub fn call({params}) -> {ret_type} {{
+        extern "C" {{
+            #[link_name = "{extern_name}"]
+            fn caller({extern_params});
+        }}
+
+{wrap_args}        let mut output = core::mem::MaybeUninit::uninit();
+
+        unsafe {{
+            caller({call_args});
+
+            output.assume_init()
+        }}
+    }}"#
+        ),
+    );
+}
+
 fn type_name(id: TypeId, types: &Types) -> String {
     match types.get_type(id) {
         RocType::Unit => "()".to_string(),
@@ -1874,7 +1970,8 @@ pub struct {name} {{
             | RocType::RocBox(_)
             | RocType::RocResult(_, _)
             | RocType::TagUnion(_)
-            | RocType::RecursivePointer { .. } => {
+            | RocType::RecursivePointer { .. }
+            | RocType::Function { .. } => {
                 owned_ret_type = type_name(non_null_payload, types);
                 borrowed_ret_type = format!("&{}", owned_ret_type);
                 payload_args = format!("arg: {owned_ret_type}");
@@ -1904,7 +2001,6 @@ pub struct {name} {{
                 owned_ret_type = answer.owned_ret_type;
                 borrowed_ret_type = answer.borrowed_ret_type;
             }
-            RocType::Function { .. } => todo!(),
         };
 
         // Add a convenience constructor function for the tag with the payload, e.g.
@@ -2123,7 +2219,8 @@ pub struct {name} {{
             | RocType::RocBox(_)
             | RocType::RocResult(_, _)
             | RocType::TagUnion(_)
-            | RocType::RecursivePointer { .. } => {
+            | RocType::RecursivePointer { .. }
+            | RocType::Function { .. } => {
                 format!(
                     r#"f.debug_tuple("{non_null_tag}").field(&*{extra_deref}self.pointer).finish()"#
                 )
@@ -2147,7 +2244,6 @@ pub struct {name} {{
 
                 buf.join(&format!("\n{INDENT}{INDENT}{INDENT}{INDENT}{INDENT}"))
             }
-            RocType::Function { .. } => todo!(),
         };
 
         let body = format!(
@@ -2186,7 +2282,7 @@ fn write_indents(indentations: usize, buf: &mut String) {
     }
 }
 
-fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
+pub(crate) fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused, so return 2^3 = 8
         Architecture::X86_64 | Architecture::Aarch64 => 8,
@@ -2196,7 +2292,7 @@ fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
 }
 
 #[inline(always)]
-fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
+pub(crate) fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused
         Architecture::X86_64 | Architecture::Aarch64 => 0b0000_0111,
@@ -2379,8 +2475,7 @@ fn cannot_derive_copy(roc_type: &RocType, types: &Types) -> bool {
         | RocType::EmptyTagUnion
         | RocType::Bool
         | RocType::Num(_)
-        | RocType::TagUnion(RocTagUnion::Enumeration { .. })
-        | RocType::Function { .. } => false,
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. }) => false,
         RocType::RocStr
         | RocType::RocList(_)
         | RocType::RocDict(_, _)
@@ -2390,7 +2485,9 @@ fn cannot_derive_copy(roc_type: &RocType, types: &Types) -> bool {
         | RocType::TagUnion(RocTagUnion::NullableWrapped { .. })
         | RocType::TagUnion(RocTagUnion::Recursive { .. })
         | RocType::RecursivePointer { .. }
-        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { .. }) => true,
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { .. })
+        // The environment a closure captured may hold refcounted values
+        | RocType::Function { .. } => true,
         RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => payload_fields
             .iter()
             .any(|type_id| cannot_derive_copy(types.get_type(*type_id), types)),
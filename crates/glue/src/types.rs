@@ -1,4 +1,5 @@
 use crate::enums::Enums;
+use crate::report::Problem;
 use crate::structs::Structs;
 use bumpalo::Bump;
 use fnv::FnvHashMap;
@@ -8,17 +9,18 @@ use roc_builtins::bitcode::{
 };
 use roc_collections::VecMap;
 use roc_module::{
-    ident::TagName,
+    ident::{Lowercase, TagName},
     symbol::{Interns, Symbol},
 };
 use roc_mono::layout::{
     cmp_fields, ext_var_is_empty_tag_union, round_up_to_alignment, Builtin, Discriminant, Layout,
     LayoutCache, LayoutInterner, UnionLayout,
 };
+use roc_region::all::Region;
 use roc_target::TargetInfo;
 use roc_types::{
     subs::{Content, FlatType, GetSubsSlice, Subs, UnionLabels, UnionTags, Variable},
-    types::{AliasKind, RecordField},
+    types::RecordField,
 };
 use std::fmt::Display;
 
@@ -299,11 +301,13 @@ impl Types {
             (
                 Function {
                     name: name_a,
+                    extern_name: extern_name_a,
                     args: args_a,
                     ret: ret_a,
                 },
                 Function {
                     name: name_b,
+                    extern_name: extern_name_b,
                     args: args_b,
                     ret: ret_b,
                 },
@@ -311,6 +315,7 @@ impl Types {
                 // for functions, the name is actually important because two functions
                 // with the same type could have completely different implementations!
                 if name_a == name_b
+                    && extern_name_a == extern_name_b
                     && args_a.len() == args_b.len()
                     && self.is_equivalent_help(
                         self.get_type_or_pending(*ret_a),
@@ -521,8 +526,14 @@ pub enum RocType {
     /// this would be the field of Cons containing the (recursive) StrConsList type,
     /// and the TypeId is the TypeId of StrConsList itself.
     RecursivePointer(TypeId),
+    /// A closure the host can call, e.g. `update` in
+    /// `mainForHost : { update : (Model, Event -> Model) as Update }`.
+    /// Its size and alignment are those of the environment it captured.
     Function {
+        /// The name the platform gave it with `as`, e.g. `Update`
         name: String,
+        /// The caller Roc generates for the host, e.g. `roc__mainForHost_1__Update_caller`
+        extern_name: String,
         args: Vec<TypeId>,
         ret: TypeId,
     },
@@ -668,6 +679,11 @@ pub struct Env<'a> {
     enum_names: Enums,
     pending_recursive_types: VecMap<TypeId, Variable>,
     known_recursive_types: VecMap<Variable, TypeId>,
+    /// The exposed value whose type is currently being added
+    exposed_symbol: Option<Symbol>,
+    /// The region of that value's annotation, for reporting problems with its type
+    exposed_region: Region,
+    problems: Vec<Problem>,
     target: TargetInfo,
 }
 
@@ -687,24 +703,67 @@ impl<'a> Env<'a> {
             enum_names: Default::default(),
             pending_recursive_types: Default::default(),
             known_recursive_types: Default::default(),
+            exposed_symbol: None,
+            exposed_region: Region::zero(),
+            problems: Vec::new(),
             layout_cache: LayoutCache::new(layout_interner, target),
             target,
         }
     }
 
-    pub fn vars_to_types<I>(&mut self, variables: I) -> Types
+    /// Returns the problems found instead if any of the exposed types
+    /// can't be described to the host.
+    pub fn vars_to_types<I>(&mut self, variables: I) -> Result<Types, Vec<Problem>>
     where
-        I: Iterator<Item = Variable>,
+        I: Iterator<Item = (Symbol, Region, Variable)>,
     {
         let mut types = Types::with_capacity(variables.size_hint().0, self.target);
 
-        for var in variables {
-            self.add_type(var, &mut types);
+        for (symbol, region, var) in variables {
+            self.exposed_symbol = Some(symbol);
+            self.exposed_region = region;
+            self.add_toplevel_type(var, &mut types);
+        }
+
+        self.exposed_symbol = None;
+
+        if !self.problems.is_empty() {
+            return Err(core::mem::take(&mut self.problems));
         }
 
         self.resolve_pending_recursive_types(&mut types);
 
-        types
+        Ok(types)
+    }
+
+    fn add_problem(&mut self, problem: Problem) {
+        // The same type variable can show up in several places in one type,
+        // but it only needs to be reported once.
+        if !self.problems.contains(&problem) {
+            self.problems.push(problem);
+        }
+    }
+
+    fn exposed_symbol(&self) -> Symbol {
+        self.exposed_symbol
+            .expect("Types are only added while adding an exposed value's type")
+    }
+
+    fn add_toplevel_type(&mut self, var: Variable, types: &mut Types) {
+        match self.subs.get_content_without_compacting(var) {
+            Content::Structure(FlatType::Func(args, _, ret_var)) => {
+                // The host calls exposed functions directly rather than through a caller,
+                // so only their arguments and return value need declarations.
+                for arg_var in self.subs.get_subs_slice(*args) {
+                    self.add_type(*arg_var, types);
+                }
+
+                self.add_type(*ret_var, types);
+            }
+            _ => {
+                self.add_type(var, types);
+            }
+        }
     }
 
     fn add_type(&mut self, var: Variable, types: &mut Types) -> TypeId {
@@ -756,11 +815,15 @@ fn add_type_help<'a>(
     let subs = env.subs;
 
     match subs.get_content_without_compacting(var) {
-        Content::FlexVar(_)
-        | Content::RigidVar(_)
-        | Content::FlexAbleVar(_, _)
-        | Content::RigidAbleVar(_, _) => {
-            todo!("TODO give a nice error message for a non-concrete type being passed to the host")
+        Content::FlexVar(opt_name) | Content::FlexAbleVar(opt_name, _) => {
+            let name = opt_name.map(|index| subs[index].clone());
+
+            add_type_variable_problem(env, name, types, layout)
+        }
+        Content::RigidVar(name) | Content::RigidAbleVar(name, _) => {
+            let name = subs[*name].clone();
+
+            add_type_variable_problem(env, Some(name), types, layout)
         }
         Content::Structure(FlatType::Record(fields, ext)) => {
             let it = fields
@@ -787,6 +850,27 @@ fn add_type_help<'a>(
                 RocType::Struct { name, fields }
             })
         }
+        Content::Structure(FlatType::Tuple(elems, ext)) => {
+            let it = elems
+                .unsorted_iterator(subs, *ext)
+                .expect("something weird in content");
+
+            let name = match opt_name {
+                Some(sym) => sym.as_str(env.interns).to_string(),
+                None => env.struct_names.get_name(var),
+            };
+
+            // Tuple elements are named by their position, as `f0`, `f1`, and so on.
+            add_struct(env, name, it, types, layout, |name, elems| {
+                RocType::Struct {
+                    name,
+                    fields: elems
+                        .into_iter()
+                        .map(|(index, type_id)| (format!("f{index}"), type_id))
+                        .collect(),
+                }
+            })
+        }
         Content::Structure(FlatType::TagUnion(tags, ext_var)) => {
             debug_assert!(ext_var_is_empty_tag_union(subs, *ext_var));
 
@@ -819,7 +903,7 @@ fn add_type_help<'a>(
                 }
             }
         },
-        Content::Structure(FlatType::Func(args, closure_var, ret_var)) => {
+        Content::Structure(FlatType::Func(args, _, ret_var)) => {
             let args = env.subs.get_subs_slice(*args);
             let mut arg_type_ids = Vec::with_capacity(args.len());
 
@@ -841,12 +925,33 @@ fn add_type_help<'a>(
                 add_type_help(env, ret_layout, *ret_var, None, types)
             };
 
-            let name = format!("TODO_roc_function_{:?}", closure_var);
+            // Roc only generates a caller for functions the platform named with `as`,
+            // e.g. `mainForHost : Task {} [] as Fx`, and names the caller after that alias.
+            let alias = match opt_name {
+                Some(alias) => alias,
+                None => {
+                    env.add_problem(Problem::UnnamedFunction {
+                        symbol: env.exposed_symbol(),
+                        region: env.exposed_region,
+                    });
+
+                    return types.add_anonymous(&env.layout_cache.interner, RocType::Unit, layout);
+                }
+            };
+            let exposed_symbol = env.exposed_symbol();
+            let name = alias.as_str(env.interns).to_string();
+            let extern_name = format!(
+                "roc__{}_1_{}_{}_caller",
+                exposed_symbol.as_str(env.interns),
+                alias.module_string(env.interns),
+                name
+            );
             let fn_type_id = types.add_named(
                 &env.layout_cache.interner,
                 name.clone(),
                 RocType::Function {
                     name,
+                    extern_name,
                     args: arg_type_ids.clone(),
                     ret: ret_type_id,
                 },
@@ -865,7 +970,7 @@ fn add_type_help<'a>(
             todo!()
         }
         Content::Structure(FlatType::Erroneous(_)) => todo!(),
-        Content::Structure(FlatType::EmptyRecord) => {
+        Content::Structure(FlatType::EmptyRecord | FlatType::EmptyTuple) => {
             types.add_anonymous(&env.layout_cache.interner, RocType::Unit, layout)
         }
         Content::Structure(FlatType::EmptyTagUnion) => {
@@ -932,14 +1037,68 @@ fn add_type_help<'a>(
                             }
                         }
                     }
+                    Layout::Struct { .. } if *name == Symbol::DICT_DICT => {
+                        let type_vars = env.subs.get_subs_slice(alias_vars.type_variables());
+
+                        debug_assert_eq!(type_vars.len(), 2);
+
+                        let key_var = type_vars[0];
+                        let key_layout =
+                            env.layout_cache.from_var(env.arena, key_var, subs).unwrap();
+                        let key_id = add_type_help(env, key_layout, key_var, None, types);
+
+                        let val_var = type_vars[1];
+                        let val_layout =
+                            env.layout_cache.from_var(env.arena, val_var, subs).unwrap();
+                        let val_id = add_type_help(env, val_layout, val_var, None, types);
+
+                        let dict_id = types.add_anonymous(
+                            &env.layout_cache.interner,
+                            RocType::RocDict(key_id, val_id),
+                            layout,
+                        );
+
+                        types.depends(dict_id, key_id);
+                        types.depends(dict_id, val_id);
+
+                        dict_id
+                    }
+                    Layout::Struct { .. } if *name == Symbol::SET_SET => {
+                        let type_vars = env.subs.get_subs_slice(alias_vars.type_variables());
+
+                        debug_assert_eq!(type_vars.len(), 1);
+
+                        let elem_var = type_vars[0];
+                        let elem_layout = env
+                            .layout_cache
+                            .from_var(env.arena, elem_var, subs)
+                            .unwrap();
+                        let elem_id = add_type_help(env, elem_layout, elem_var, None, types);
+
+                        let set_id = types.add_anonymous(
+                            &env.layout_cache.interner,
+                            RocType::RocSet(elem_id),
+                            layout,
+                        );
+
+                        types.depends(set_id, elem_id);
+
+                        set_id
+                    }
                     _ => {
                         unreachable!()
                     }
                 }
             } else {
                 // If this was a non-builtin type alias, we can use that alias name
-                // in the generated bindings.
-                add_type_help(env, layout, *real_var, Some(*name), types)
+                // in the generated bindings. Functions keep the outermost alias instead,
+                // because that's the one Roc names their caller after.
+                let name = match (opt_name, layout) {
+                    (Some(outer_name), Layout::LambdaSet(_)) => outer_name,
+                    _ => *name,
+                };
+
+                add_type_help(env, layout, *real_var, Some(name), types)
             }
         }
         Content::RangedNumber(_) => todo!(),
@@ -965,10 +1124,31 @@ fn add_type_help<'a>(
 
             type_id
         }
-        Content::LambdaSet(_) => todo!(),
+        Content::LambdaSet(_) => {
+            // The captured environment is declared as part of the function which owns
+            // this lambda set, using the function's layout.
+            unreachable!("Glue never adds a closure's lambda set on its own");
+        }
     }
 }
 
+/// Records that a type variable is being passed to the host, and adds a
+/// placeholder in its place so the rest of the type can still be checked.
+fn add_type_variable_problem<'a>(
+    env: &mut Env<'a>,
+    name: Option<Lowercase>,
+    types: &mut Types,
+    layout: Layout<'a>,
+) -> TypeId {
+    env.add_problem(Problem::TypeVariable {
+        symbol: env.exposed_symbol(),
+        region: env.exposed_region,
+        name,
+    });
+
+    types.add_anonymous(&env.layout_cache.interner, RocType::Unit, layout)
+}
+
 fn add_builtin_type<'a>(
     env: &mut Env<'a>,
     builtin: Builtin<'a>,
@@ -1070,91 +1250,6 @@ fn add_builtin_type<'a>(
 
             list_id
         }
-        (
-            Builtin::List(elem_layout),
-            Alias(Symbol::DICT_DICT, _alias_variables, alias_var, AliasKind::Opaque),
-        ) => {
-            match (
-                elem_layout,
-                env.subs.get_content_without_compacting(*alias_var),
-            ) {
-                (
-                    Layout::Struct { field_layouts, .. },
-                    Content::Structure(FlatType::Apply(Symbol::LIST_LIST, args_subs_slice)),
-                ) => {
-                    let (key_var, val_var) = {
-                        let args_tuple = env.subs.get_subs_slice(*args_subs_slice);
-
-                        debug_assert_eq!(args_tuple.len(), 1);
-
-                        match env.subs.get_content_without_compacting(args_tuple[0]) {
-                            Content::Structure(FlatType::TagUnion(union_tags, ext_var)) => {
-                                let (mut iter, _) = union_tags.sorted_iterator_and_ext(env.subs, *ext_var);
-                                let payloads = iter.next().unwrap().1;
-
-                                debug_assert_eq!(iter.next(), None);
-
-                                (payloads[0], payloads[1])
-                            }
-                            _ => {
-                                unreachable!()
-                            }
-                        }
-                    };
-
-                    debug_assert_eq!(field_layouts.len(), 2);
-
-                    let key_id = add_type_help(env, field_layouts[0], key_var, opt_name, types);
-                    let val_id = add_type_help(env, field_layouts[1], val_var, opt_name, types);
-                    let dict_id = types.add_anonymous(&env.layout_cache.interner,RocType::RocDict(key_id, val_id), layout);
-
-                    types.depends(dict_id, key_id);
-                    types.depends(dict_id, val_id);
-
-                    dict_id
-                }
-                (elem_layout, alias_content) => unreachable!(
-                    "Unrecognized List element for Dict. Layout was: {:?} and alias_content was: {:?}",
-                    elem_layout,
-                    alias_content
-                ),
-            }
-        }
-        (
-            Builtin::List(elem_layout),
-            Alias(Symbol::SET_SET, _alias_vars, alias_var, AliasKind::Opaque),
-        ) => {
-            match (
-                elem_layout,
-                env.subs.get_content_without_compacting(*alias_var),
-            ) {
-                (
-                    Layout::Struct { field_layouts, .. },
-                    Alias(Symbol::DICT_DICT, alias_args, _alias_var, AliasKind::Opaque),
-                ) => {
-                    let dict_type_vars = env.subs.get_subs_slice(alias_args.type_variables());
-
-                    debug_assert_eq!(dict_type_vars.len(), 2);
-
-                    // Sets only use the key of the Dict they wrap, not the value
-                    let elem_var = dict_type_vars[0];
-
-                    debug_assert_eq!(field_layouts.len(), 2);
-
-                    let elem_id = add_type_help(env, field_layouts[0], elem_var, opt_name, types);
-                    let set_id = types.add_anonymous(&env.layout_cache.interner,RocType::RocSet(elem_id), layout);
-
-                    types.depends(set_id, elem_id);
-
-                    set_id
-                }
-                (elem_layout, alias_content) => unreachable!(
-                    "Unrecognized List element for Set. Layout was: {:?} and alias_content was: {:?}",
-                    elem_layout,
-                    alias_content
-                ),
-            }
-        }
         (Builtin::List(elem_layout), alias) => {
             unreachable!(
                 "The type alias {:?} was not an Apply(Symbol::LIST_LIST) as expected, given that its builtin was Builtin::List({:?})",
@@ -1427,7 +1522,15 @@ fn add_tag_union<'a>(
             }
         }
         Layout::LambdaSet(_) => {
-            todo!();
+            // A single tag wrapping a function, e.g. `Handler : [Handler ((Event -> Model) as Handle)]`
+            let (tag_name, payload_fields) =
+                single_tag_payload_fields(union_tags, subs, &[layout], env, types);
+
+            RocTagUnion::SingleTagStruct {
+                name: name.clone(),
+                tag_name: tag_name.to_string(),
+                payload_fields,
+            }
         }
         Layout::RecursivePointer => {
             // A single-tag union which only wraps itself is erroneous and should have
@@ -0,0 +1,764 @@
+use crate::c_glue::result_name;
+use crate::rust_glue::{max_pointer_tagged_variants, tagged_pointer_bitmask};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types};
+use indexmap::IndexMap;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt::{Display, Write};
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.zig");
+const INDENT: &str = "    ";
+
+/// Each declaration, along with the targets it was generated for
+type Decls = IndexMap<String, Vec<TargetInfo>>;
+
+fn add_decl(decls: &mut Decls, target_info: TargetInfo, body: String) {
+    decls.entry(body).or_default().push(target_info);
+}
+
+pub fn emit(types_and_targets: &[(Types, TargetInfo)]) -> String {
+    let mut buf = String::new();
+    let mut decls = Decls::default();
+
+    for (types, target_info) in types_and_targets {
+        for id in types.sorted_ids() {
+            add_type(*target_info, id, types, &mut decls);
+        }
+    }
+
+    for (decl, targets) in decls {
+        buf.push('\n');
+
+        if targets.len() == types_and_targets.len() {
+            buf.push_str(&decl);
+            buf.push('\n');
+        } else {
+            // We should never have a decl recorded with 0 targets!
+            debug_assert_ne!(targets.len(), 0);
+
+            // Declarations which only apply to some targets are only pulled into
+            // the file's namespace when compiling for one of them.
+            let condition = targets
+                .iter()
+                .map(|target_info| {
+                    format!(
+                        "builtin.cpu.arch == .{}",
+                        arch_to_str(target_info.architecture)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" or ");
+
+            writeln!(buf, "pub usingnamespace if ({condition}) struct {{").unwrap();
+
+            for line in decl.lines() {
+                if !line.is_empty() {
+                    buf.push_str(INDENT);
+                    buf.push_str(line);
+                }
+
+                buf.push('\n');
+            }
+
+            buf.push_str("} else struct {};\n");
+        }
+    }
+
+    buf
+}
+
+fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, decls: &mut Decls) {
+    match types.get_type(id) {
+        RocType::Struct { name, fields } => add_struct(
+            name,
+            target_info,
+            id,
+            fields
+                .iter()
+                .map(|(label, field_id)| (escape_kw(label.clone()), *field_id)),
+            types,
+            decls,
+        ),
+        RocType::TagUnionPayload { name, fields } => add_struct(
+            name,
+            target_info,
+            id,
+            // Tag union payloads have numbered fields, so we prefix them with an "f",
+            // since numbered fields would make this a tuple.
+            fields
+                .iter()
+                .map(|(label, field_id)| (format!("f{label}"), *field_id)),
+            types,
+            decls,
+        ),
+        RocType::RocResult(ok_id, err_id) => {
+            add_result(target_info, id, *ok_id, *err_id, types, decls)
+        }
+        RocType::TagUnion(tag_union) => {
+            match tag_union {
+                RocTagUnion::Enumeration { tags, name, size } => {
+                    add_enumeration(name, target_info, tags.iter(), *size, decls)
+                }
+                RocTagUnion::NonRecursive {
+                    tags,
+                    name,
+                    discriminant_size,
+                    discriminant_offset,
+                } => {
+                    // Empty tag unions can never come up at runtime,
+                    // and so don't need declared types.
+                    if !tags.is_empty() {
+                        add_tag_union(
+                            name,
+                            target_info,
+                            id,
+                            tags,
+                            *discriminant_size,
+                            *discriminant_offset,
+                            types,
+                            decls,
+                        );
+                    }
+                }
+                RocTagUnion::Recursive {
+                    tags,
+                    name,
+                    discriminant_size,
+                    ..
+                } => {
+                    if !tags.is_empty() {
+                        add_recursive_tag_union(
+                            name,
+                            target_info,
+                            tags,
+                            None,
+                            *discriminant_size,
+                            types,
+                            decls,
+                        );
+                    }
+                }
+                RocTagUnion::NullableWrapped {
+                    name,
+                    index_of_null_tag,
+                    tags,
+                    discriminant_size,
+                    ..
+                } => add_recursive_tag_union(
+                    name,
+                    target_info,
+                    tags,
+                    Some(*index_of_null_tag as usize),
+                    *discriminant_size,
+                    types,
+                    decls,
+                ),
+                RocTagUnion::NullableUnwrapped {
+                    name,
+                    null_tag,
+                    non_null_tag,
+                    non_null_payload,
+                    ..
+                } => add_nullable_unwrapped(
+                    name,
+                    target_info,
+                    null_tag,
+                    non_null_tag,
+                    *non_null_payload,
+                    types,
+                    decls,
+                ),
+                RocTagUnion::SingleTagStruct {
+                    name,
+                    payload_fields,
+                    ..
+                } => add_struct(
+                    name,
+                    target_info,
+                    id,
+                    payload_fields
+                        .iter()
+                        .enumerate()
+                        .map(|(index, field_id)| (format!("f{index}"), *field_id)),
+                    types,
+                    decls,
+                ),
+                RocTagUnion::NonNullableUnwrapped {
+                    name,
+                    tag_name,
+                    payload,
+                } => add_recursive_tag_union(
+                    name,
+                    target_info,
+                    &[(tag_name.clone(), Some(*payload))],
+                    None,
+                    0,
+                    types,
+                    decls,
+                ),
+            }
+        }
+        // These types are either declared in the header, or zero-sized.
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::Num(_)
+        | RocType::Bool
+        | RocType::RocStr
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_) => {}
+        RocType::RecursivePointer { .. } => {
+            // This is recursively pointing to a type that should already have been added,
+            // so no extra work needs to happen.
+        }
+        RocType::Function {
+            name,
+            extern_name,
+            args,
+            ret,
+        } => add_function(name, target_info, extern_name, args, *ret, id, types, decls),
+    }
+}
+
+/// Zero-sized types are left out, both as declarations and as fields, like in the C glue.
+fn is_zero_sized(id: TypeId, types: &Types) -> bool {
+    types.size_ignoring_alignment(id) == 0
+}
+
+fn add_struct<I: Iterator<Item = (String, TypeId)>>(
+    name: &str,
+    target_info: TargetInfo,
+    struct_id: TypeId,
+    fields: I,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    if is_zero_sized(struct_id, types) {
+        return;
+    }
+
+    let name = escape_kw(name.to_string());
+    let mut buf = format!("pub const {name} = extern struct {{\n");
+
+    for (label, type_id) in fields {
+        if !is_zero_sized(type_id, types) {
+            writeln!(buf, "{INDENT}{label}: {},", type_name(type_id, types)).unwrap();
+        }
+    }
+
+    buf.push_str("};");
+
+    add_decl(decls, target_info, buf);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn add_function(
+    name: &str,
+    target_info: TargetInfo,
+    extern_name: &str,
+    args: &[TypeId],
+    ret: TypeId,
+    fn_id: TypeId,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    let name = escape_kw(name.to_string());
+    let size = types.size_rounded_to_alignment(fn_id);
+    let align = types.align(fn_id).max(1);
+
+    // The caller takes everything by pointer, writing the return value through the last one.
+    // Pointers to zero-sized values aren't allowed in extern functions, so those get
+    // a pointer to an unused byte instead.
+    let mut extern_params = Vec::with_capacity(args.len() + 2);
+    // Zig doesn't allow unused parameters, and a closure which captured nothing has nothing to pass.
+    let self_param = if size > 0 { "self" } else { "_" };
+    let mut params = vec![format!("{self_param}: *const {name}")];
+    let mut call_args = Vec::with_capacity(args.len() + 2);
+    let mut needs_unit = false;
+
+    for (index, arg_id) in args.iter().enumerate() {
+        if is_zero_sized(*arg_id, types) {
+            extern_params.push(format!("arg{index}: *const anyopaque"));
+            call_args.push("&unit".to_string());
+            needs_unit = true;
+        } else {
+            let arg_type = type_name(*arg_id, types);
+
+            extern_params.push(format!("arg{index}: *const {arg_type}"));
+            params.push(format!("arg{index}: {arg_type}"));
+            call_args.push(format!("&arg{index}"));
+        }
+    }
+
+    extern_params.push("closure_data: *const anyopaque".to_string());
+
+    if size > 0 {
+        call_args.push("&self.closure_data".to_string());
+    } else {
+        call_args.push("&unit".to_string());
+        needs_unit = true;
+    }
+
+    let returns_value = !is_zero_sized(ret, types);
+    let ret_type;
+    let mut body = String::new();
+
+    if returns_value {
+        ret_type = type_name(ret, types);
+        extern_params.push(format!("output: *{ret_type}"));
+        call_args.push("&output".to_string());
+        writeln!(body, "{INDENT}{INDENT}var output: {ret_type} = undefined;").unwrap();
+    } else {
+        ret_type = "void".to_string();
+        extern_params.push("output: *anyopaque".to_string());
+        call_args.push("&unit".to_string());
+        needs_unit = true;
+    }
+
+    if needs_unit {
+        body.insert_str(0, &format!("{INDENT}{INDENT}var unit: u8 = 0;\n"));
+    }
+
+    writeln!(
+        body,
+        "\n{INDENT}{INDENT}{extern_name}({});",
+        call_args.join(", ")
+    )
+    .unwrap();
+
+    if returns_value {
+        writeln!(body, "\n{INDENT}{INDENT}return output;").unwrap();
+    }
+
+    // The host never looks inside the environment the closure captured;
+    // it only passes it back to Roc when calling the closure.
+    add_decl(
+        decls,
+        target_info,
+        format!(
+            r#"pub const {name} = extern struct {{
+    closure_data: [{size}]u8 align({align}),
+
+    /// Roc takes ownership of both the arguments and the environment the closure captured.
+    pub fn call({}) {ret_type} {{
+{body}    }}
+}};"#,
+            params.join(", ")
+        ),
+    );
+
+    add_decl(
+        decls,
+        target_info,
+        format!(
+            "extern fn {extern_name}({}) void;",
+            extern_params.join(", ")
+        ),
+    );
+}
+
+fn add_result(
+    target_info: TargetInfo,
+    id: TypeId,
+    ok_id: TypeId,
+    err_id: TypeId,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    if is_zero_sized(id, types) {
+        return;
+    }
+
+    let name = result_name(ok_id, err_id, types);
+    let mut buf = format!("pub const {name} = extern struct {{\n");
+    let payloads = [("ok", ok_id), ("err", err_id)]
+        .into_iter()
+        .filter(|(_, payload_id)| !is_zero_sized(*payload_id, types))
+        .collect::<Vec<_>>();
+
+    if !payloads.is_empty() {
+        writeln!(buf, "{INDENT}payload: extern union {{").unwrap();
+
+        for (label, payload_id) in payloads {
+            writeln!(
+                buf,
+                "{INDENT}{INDENT}{label}: {},",
+                type_name(payload_id, types)
+            )
+            .unwrap();
+        }
+
+        writeln!(buf, "{INDENT}}},").unwrap();
+    }
+
+    writeln!(buf, "{INDENT}tag: u8, // 1 for Ok, 0 for Err").unwrap();
+    buf.push_str("};");
+
+    add_decl(decls, target_info, buf);
+}
+
+fn add_enumeration<I: ExactSizeIterator<Item = S>, S: Display>(
+    name: &str,
+    target_info: TargetInfo,
+    tags: I,
+    tag_bytes: u32,
+    decls: &mut Decls,
+) {
+    let name = escape_kw(name.to_string());
+    let repr_bits = tag_bytes * 8;
+
+    // e.g. "pub const Foo = enum(u8) {\n"
+    let mut buf = format!("pub const {name} = enum(u{repr_bits}) {{\n");
+
+    for (index, tag_name) in tags.enumerate() {
+        writeln!(
+            buf,
+            "{INDENT}{} = {index},",
+            escape_kw(tag_name.to_string())
+        )
+        .unwrap();
+    }
+
+    buf.push_str("};");
+
+    add_decl(decls, target_info, buf);
+}
+
+fn add_discriminant(
+    name: &str,
+    target_info: TargetInfo,
+    tag_names: Vec<String>,
+    size: u32,
+    decls: &mut Decls,
+) -> String {
+    // The tag union's discriminant, e.g.
+    //
+    // pub const discriminant_MyTagUnion = enum(u8) {
+    //     Bar = 0,
+    //     Foo = 1,
+    // };
+    let discriminant_name = format!("discriminant_{name}");
+
+    add_enumeration(
+        &discriminant_name,
+        target_info,
+        tag_names.iter(),
+        size,
+        decls,
+    );
+
+    discriminant_name
+}
+
+#[allow(clippy::too_many_arguments)]
+fn add_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    type_id: TypeId,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    let name = escape_kw(name.to_string());
+    let size_rounded_to_alignment = types.size_rounded_to_alignment(type_id);
+
+    // As in the Rust glue, the whole tag union is a union, and the discriminant
+    // is at a fixed offset in it - often in what would otherwise be padding.
+    let mut buf = format!("pub const {name} = extern union {{\n");
+
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            if !is_zero_sized(*payload_id, types) {
+                writeln!(
+                    buf,
+                    "{INDENT}{}: {},",
+                    escape_kw(tag_name.clone()),
+                    type_name(*payload_id, types)
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    if tags.len() > 1 {
+        // Make room for the discriminant, which may come after the largest payload.
+        writeln!(buf, "{INDENT}_sizer: [{size_rounded_to_alignment}]u8,").unwrap();
+    }
+
+    if discriminant_size > 0 {
+        let tag_names = tags.iter().map(|(tag_name, _)| tag_name.clone()).collect();
+        let discriminant_name =
+            add_discriminant(&name, target_info, tag_names, discriminant_size, decls);
+
+        write!(
+            buf,
+            r#"
+    pub fn discriminant(self: *const {name}) {discriminant_name} {{
+        const bytes = @ptrCast([*]const u8, self);
+
+        return @ptrCast(*align(1) const {discriminant_name}, bytes + {discriminant_offset}).*;
+    }}
+
+    pub fn setDiscriminant(self: *{name}, discriminant: {discriminant_name}) void {{
+        const bytes = @ptrCast([*]u8, self);
+
+        @ptrCast(*align(1) {discriminant_name}, bytes + {discriminant_offset}).* = discriminant;
+    }}
+"#
+        )
+        .unwrap();
+    }
+
+    buf.push_str("};");
+
+    add_decl(decls, target_info, buf);
+}
+
+/// Recursive tag unions are a pointer to a union of their payloads. Unless there's only one
+/// tag, the discriminant is stored in the unused bits at the end of the pointer, so it's
+/// kept as an integer.
+fn add_recursive_tag_union(
+    name: &str,
+    target_info: TargetInfo,
+    tags: &[(String, Option<TypeId>)],
+    null_tag_index: Option<usize>, // used only in the nullable-wrapped case
+    discriminant_size: u32,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    let name = escape_kw(name.to_string());
+    let union_name = format!("union_{name}");
+
+    // We should never be attempting to generate glue for empty tag unions;
+    // RocType should not have let this happen.
+    debug_assert_ne!(tags.len(), 0);
+
+    let mut buf = format!("pub const {name} = extern struct {{\n");
+
+    if discriminant_size > 0 {
+        if tags.len() > max_pointer_tagged_variants(target_info.architecture) {
+            todo!(
+                "Support {} tags in a recursive tag union on target_info {:?}. (This is too many tags for pointer tagging to work, so we need to generate different glue.)",
+                tags.len(),
+                target_info
+            );
+        }
+
+        let tag_names = tags.iter().map(|(tag_name, _)| tag_name.clone()).collect();
+        let discriminant_name =
+            add_discriminant(&name, target_info, tag_names, discriminant_size, decls);
+        let repr_bits = discriminant_size * 8;
+        let bitmask = format!("{:#b}", tagged_pointer_bitmask(target_info.architecture));
+        let (null_check, pointer_type) = match null_tag_index {
+            Some(index) => (
+                format!(
+                    "if (self.pointer == 0) {{\n{INDENT}{INDENT}{INDENT}return @intToEnum({discriminant_name}, {index});\n{INDENT}{INDENT}}}\n\n{INDENT}{INDENT}"
+                ),
+                format!("?*{union_name}"),
+            ),
+            None => (String::new(), format!("*{union_name}")),
+        };
+
+        write!(
+            buf,
+            r#"    pointer: usize,
+
+    pub fn discriminant(self: {name}) {discriminant_name} {{
+        {null_check}// The discriminant is stored in the unused bits at the end of the pointer
+        return @intToEnum({discriminant_name}, @truncate(u{repr_bits}, self.pointer & {bitmask}));
+    }}
+
+    pub fn unionPointer(self: {name}) {pointer_type} {{
+        return @intToPtr({pointer_type}, self.pointer & ~@as(usize, {bitmask}));
+    }}
+"#
+        )
+        .unwrap();
+    } else {
+        writeln!(buf, "{INDENT}pointer: *{union_name},").unwrap();
+    }
+
+    buf.push_str("};");
+
+    add_decl(decls, target_info, buf);
+
+    let mut buf = format!("pub const {union_name} = extern union {{\n");
+
+    for (tag_name, opt_payload_id) in tags {
+        if let Some(payload_id) = opt_payload_id {
+            if !is_zero_sized(*payload_id, types) {
+                writeln!(
+                    buf,
+                    "{INDENT}{}: {},",
+                    escape_kw(tag_name.clone()),
+                    type_name(*payload_id, types)
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    buf.push_str("};");
+
+    add_decl(decls, target_info, buf);
+}
+
+/// A recursive tag union with two tags, where the one without a payload is a null pointer,
+/// and the other one is a pointer to its payload.
+fn add_nullable_unwrapped(
+    name: &str,
+    target_info: TargetInfo,
+    null_tag: &str,
+    non_null_tag: &str,
+    non_null_payload: TypeId,
+    types: &Types,
+    decls: &mut Decls,
+) {
+    let name = escape_kw(name.to_string());
+    let mut tag_names = vec![null_tag.to_string(), non_null_tag.to_string()];
+
+    tag_names.sort();
+
+    let discriminant_name = add_discriminant(&name, target_info, tag_names, 1, decls);
+    let payload_type_name = type_name(non_null_payload, types);
+    let null_tag = escape_kw(null_tag.to_string());
+    let non_null_tag = escape_kw(non_null_tag.to_string());
+
+    add_decl(
+        decls,
+        target_info,
+        format!(
+            r#"pub const {name} = extern struct {{
+    pointer: ?*{payload_type_name},
+
+    pub fn discriminant(self: {name}) {discriminant_name} {{
+        return if (self.pointer == null) .{null_tag} else .{non_null_tag};
+    }}
+}};"#
+        ),
+    );
+}
+
+fn type_name(id: TypeId, types: &Types) -> String {
+    match types.get_type(id) {
+        // These are only ever used behind a pointer
+        RocType::Unit | RocType::EmptyTagUnion => "void".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(RocNum::U8) => "u8".to_string(),
+        RocType::Num(RocNum::U16) => "u16".to_string(),
+        RocType::Num(RocNum::U32) => "u32".to_string(),
+        RocType::Num(RocNum::U64) => "u64".to_string(),
+        RocType::Num(RocNum::U128) => "u128".to_string(),
+        RocType::Num(RocNum::I8) => "i8".to_string(),
+        RocType::Num(RocNum::I16) => "i16".to_string(),
+        RocType::Num(RocNum::I32) => "i32".to_string(),
+        RocType::Num(RocNum::I64) => "i64".to_string(),
+        RocType::Num(RocNum::I128) => "i128".to_string(),
+        RocType::Num(RocNum::F32) => "f32".to_string(),
+        RocType::Num(RocNum::F64) => "f64".to_string(),
+        RocType::Num(RocNum::F128) => "f128".to_string(),
+        RocType::Num(RocNum::Dec) => "RocDec".to_string(),
+        RocType::RocDict(_, _) => "RocDict".to_string(),
+        RocType::RocSet(_) => "RocSet".to_string(),
+        RocType::RocList(elem_id) => format!("RocList({})", type_name(*elem_id, types)),
+        RocType::RocBox(elem_id) => format!("*{}", type_name(*elem_id, types)),
+        RocType::RocResult(ok_id, err_id) => result_name(*ok_id, *err_id, types),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. }) => escape_kw(name.clone()),
+        RocType::RecursivePointer(content) => type_name(*content, types),
+        RocType::Function { name, .. } => escape_kw(name.clone()),
+    }
+}
+
+/// The name of the architecture in `std.Target.Cpu.Arch`
+fn arch_to_str(architecture: Architecture) -> &'static str {
+    match architecture {
+        Architecture::X86_64 => "x86_64",
+        Architecture::X86_32 => "i386",
+        Architecture::Aarch64 => "aarch64",
+        Architecture::Aarch32 => "arm",
+        Architecture::Wasm32 => "wasm32",
+    }
+}
+
+// Based on https://ziglang.org/documentation/0.9.1/#Keyword-Reference, plus the
+// primitive values and types a field could be confused with
+const RESERVED_KEYWORDS: &[&str] = &[
+    "addrspace",
+    "align",
+    "allowzero",
+    "and",
+    "anyframe",
+    "anytype",
+    "asm",
+    "async",
+    "await",
+    "break",
+    "callconv",
+    "catch",
+    "comptime",
+    "const",
+    "continue",
+    "defer",
+    "else",
+    "enum",
+    "errdefer",
+    "error",
+    "export",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "inline",
+    "noalias",
+    "nosuspend",
+    "opaque",
+    "or",
+    "orelse",
+    "packed",
+    "pub",
+    "resume",
+    "return",
+    "linksection",
+    "struct",
+    "suspend",
+    "switch",
+    "test",
+    "threadlocal",
+    "try",
+    "union",
+    "unreachable",
+    "usingnamespace",
+    "var",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+    "undefined",
+    "type",
+    "bool",
+    "void",
+    "anyopaque",
+    "noreturn",
+];
+
+/// Escape a Zig reserved keyword, if necessary.
+fn escape_kw(input: String) -> String {
+    if RESERVED_KEYWORDS.contains(&input.as_str()) {
+        // @"..." lets any string be used as an identifier
+        format!("@\"{input}\"")
+    } else {
+        input
+    }
+}
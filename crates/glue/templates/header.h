@@ -0,0 +1,44 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct RocStr {
+    char *bytes;
+    size_t len;
+    size_t capacity;
+} RocStr;
+
+typedef struct RocList {
+    void *elements;
+    size_t len;
+    size_t capacity;
+} RocList;
+
+// The fields are in the order Roc lays out the record backing a Dict.
+// The layout of the items in `data` depends on the key and value types.
+typedef struct RocDict {
+    RocList data;
+    RocList data_indices;
+    size_t deleted_slots;
+    RocList metadata;
+} RocDict;
+
+typedef struct RocSet {
+    RocDict dict;
+} RocSet;
+
+typedef struct RocI128 {
+    _Alignas(16) uint8_t bytes[16];
+} RocI128;
+
+typedef struct RocU128 {
+    _Alignas(16) uint8_t bytes[16];
+} RocU128;
+
+typedef struct RocDec {
+    _Alignas(16) uint8_t bytes[16];
+} RocDec;
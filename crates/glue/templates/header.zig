@@ -0,0 +1,34 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+const builtin = @import("builtin");
+
+pub const RocStr = extern struct {
+    bytes: ?[*]u8,
+    len: usize,
+    capacity: usize,
+};
+
+pub fn RocList(comptime T: type) type {
+    return extern struct {
+        elements: ?[*]T,
+        len: usize,
+        capacity: usize,
+    };
+}
+
+/// The fields are in the order Roc lays out the record backing a Dict.
+/// The layout of the items in `data` depends on the key and value types.
+pub const RocDict = extern struct {
+    data: RocList(u8),
+    data_indices: RocList(usize),
+    deleted_slots: usize,
+    metadata: RocList(i8),
+};
+
+pub const RocSet = extern struct {
+    dict: RocDict,
+};
+
+pub const RocDec = extern struct {
+    num: i128,
+};
@@ -0,0 +1,260 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_c {
+    use crate::helpers::generate_c_bindings;
+
+    #[test]
+    fn basic_record_aliased() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : U64, b : I128 }
+
+            main : MyRcd
+            main = { a: 1u64, b: 2i128 }
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef struct MyRcd MyRcd;
+
+                struct MyRcd {
+                    RocI128 b;
+                    uint64_t a;
+                };
+
+                _Static_assert(sizeof(MyRcd) == 32, "MyRcd should be 32 bytes");
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn nested_record_aliased() {
+        let module = indoc!(
+            r#"
+            Outer : { x : Inner, y : Str, z : List U8 }
+
+            Inner : { a : U16, b : F32 }
+
+            main : Outer
+            main = { x: { a: 5, b: 24 }, y: "foo", z: [1, 2] }
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef struct Inner Inner;
+                typedef struct Outer Outer;
+
+                struct Inner {
+                    float b;
+                    uint16_t a;
+                };
+
+                _Static_assert(sizeof(Inner) == 8, "Inner should be 8 bytes");
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                struct Outer {
+                    Inner x;
+                    RocStr y;
+                    RocList z;
+                };
+
+                _Static_assert(sizeof(Outer) == 32, "Outer should be 32 bytes");
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                struct Outer {
+                    RocStr y;
+                    RocList z;
+                    Inner x;
+                };
+
+                _Static_assert(sizeof(Outer) == 56, "Outer should be 56 bytes");
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn tag_union_enumeration() {
+        let module = indoc!(
+            r#"
+            Enumeration : [Blah, Foo, Bar]
+
+            main : Enumeration
+            main = Foo
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef uint8_t Enumeration;
+
+                enum {
+                    Enumeration_Bar = 0,
+                    Enumeration_Blah = 1,
+                    Enumeration_Foo = 2,
+                };
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn recursive_tag_union() {
+        let module = indoc!(
+            r#"
+            Expr : [Concat Expr Expr, Num I64]
+
+            main : Expr
+            main = Num 1
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef struct Expr_Concat Expr_Concat;
+                typedef struct Expr_Num Expr_Num;
+                typedef union union_Expr union_Expr;
+
+                typedef struct Expr {
+                    union_Expr *pointer;
+                } Expr;
+
+                struct Expr_Concat {
+                    Expr f0;
+                    Expr f1;
+                };
+
+                struct Expr_Num {
+                    int64_t f0;
+                };
+
+                typedef uint8_t discriminant_Expr;
+
+                enum {
+                    discriminant_Expr_Concat = 0,
+                    discriminant_Expr_Num = 1,
+                };
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                union union_Expr {
+                    Expr_Concat Concat;
+                    Expr_Num Num;
+                };
+
+                static inline discriminant_Expr Expr_discriminant(Expr tag_union) {
+                    // The discriminant is stored in the unused bits at the end of the pointer
+                    return (discriminant_Expr)((uintptr_t)tag_union.pointer & 0x3);
+                }
+
+                static inline union_Expr *Expr_union_pointer(Expr tag_union) {
+                    return (union_Expr *)((uintptr_t)tag_union.pointer & ~(uintptr_t)0x3);
+                }
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                union union_Expr {
+                    Expr_Concat Concat;
+                    Expr_Num Num;
+                };
+
+                static inline discriminant_Expr Expr_discriminant(Expr tag_union) {
+                    // The discriminant is stored in the unused bits at the end of the pointer
+                    return (discriminant_Expr)((uintptr_t)tag_union.pointer & 0x7);
+                }
+
+                static inline union_Expr *Expr_union_pointer(Expr tag_union) {
+                    return (union_Expr *)((uintptr_t)tag_union.pointer & ~(uintptr_t)0x7);
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn closure_in_record() {
+        let module = indoc!(
+            r#"
+            main : { update : (I64 -> I64) as Update, offset : I64 }
+            main =
+                offset = 5
+
+                { update: \n -> n + offset, offset }
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef struct Update Update;
+                typedef struct R1 R1;
+
+                #if defined(__arm__) || defined(__aarch64__) || defined(__wasm32__) || defined(__x86_64__)
+                struct Update {
+                    _Alignas(8) uint8_t closure_data[8];
+                };
+
+                _Static_assert(sizeof(Update) == 8, "Update should be 8 bytes");
+                #endif
+
+                #if defined(__i386__)
+                struct Update {
+                    _Alignas(4) uint8_t closure_data[8];
+                };
+
+                _Static_assert(sizeof(Update) == 8, "Update should be 8 bytes");
+                #endif
+
+                struct R1 {
+                    int64_t offset;
+                    Update update;
+                };
+
+                _Static_assert(sizeof(R1) == 16, "R1 should be 16 bytes");
+
+                extern void roc__main_1__Update_caller(int64_t *arg0, const Update *closure_data, int64_t *output);
+
+                // Roc takes ownership of both the arguments and the environment the closure captured.
+                static inline int64_t Update_call(const Update *closure, int64_t arg0) {
+                    int64_t output;
+
+                    roc__main_1__Update_caller(&arg0, closure, &output);
+
+                    return output;
+                }
+            "#
+            )
+        );
+    }
+}
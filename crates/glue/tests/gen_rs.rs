@@ -233,4 +233,86 @@ mod test_gen_rs {
             )
         );
     }
+
+    #[test]
+    fn closure_in_record() {
+        let module = indoc!(
+            r#"
+            main : { update : (I64 -> I64) as Update, offset : I64 }
+            main =
+                offset = 5
+
+                { update: \n -> n + offset, offset }
+        "#
+        );
+
+        assert_eq!(
+            generate_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86",
+                    target_arch = "x86_64"
+                ))]
+                #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+                #[repr(C)]
+                pub struct R1 {
+                    pub offset: i64,
+                    pub update: Update,
+                }
+
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86_64"
+                ))]
+                #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+                #[repr(C, align(8))]
+                pub struct Update {
+                    closure_data: [u8; 8],
+                }
+
+                #[cfg(target_arch = "x86")]
+                #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+                #[repr(C, align(4))]
+                pub struct Update {
+                    closure_data: [u8; 8],
+                }
+
+                impl Update {
+                    #[cfg(any(
+                        target_arch = "arm",
+                        target_arch = "aarch64",
+                        target_arch = "wasm32",
+                        target_arch = "x86",
+                        target_arch = "x86_64"
+                    ))]
+                    /// Calls this closure with the environment it captured.
+                    /// Roc takes ownership of both the arguments and the captured environment.
+                    pub fn call(self, arg0: i64) -> i64 {
+                        extern "C" {
+                            #[link_name = "roc__main_1__Update_caller"]
+                            fn caller(arg0: *const i64, closure_data: *const u8, output: *mut i64);
+                        }
+
+                        let arg0 = core::mem::ManuallyDrop::new(arg0);
+                        let mut output = core::mem::MaybeUninit::uninit();
+
+                        unsafe {
+                            caller(&*arg0, self.closure_data.as_ptr(), output.as_mut_ptr());
+
+                            output.assume_init()
+                        }
+                    }
+                }
+            "#
+            )
+        );
+    }
 }
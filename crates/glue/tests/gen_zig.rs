@@ -0,0 +1,194 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_zig {
+    use crate::helpers::generate_zig_bindings;
+
+    #[test]
+    fn basic_record_aliased() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : U64, b : I128 }
+
+            main : MyRcd
+            main = { a: 1u64, b: 2i128 }
+        "#
+        );
+
+        assert_eq!(
+            generate_zig_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                pub const MyRcd = extern struct {
+                    b: i128,
+                    a: u64,
+                };
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn tag_union_enumeration() {
+        let module = indoc!(
+            r#"
+            Enumeration : [Blah, Foo, Bar]
+
+            main : Enumeration
+            main = Foo
+        "#
+        );
+
+        assert_eq!(
+            generate_zig_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                pub const Enumeration = enum(u8) {
+                    Bar = 0,
+                    Blah = 1,
+                    Foo = 2,
+                };
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn recursive_tag_union() {
+        let module = indoc!(
+            r#"
+            Expr : [Concat Expr Expr, Num I64]
+
+            main : Expr
+            main = Num 1
+        "#
+        );
+
+        assert_eq!(
+            generate_zig_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                pub const discriminant_Expr = enum(u8) {
+                    Concat = 0,
+                    Num = 1,
+                };
+
+                pub usingnamespace if (builtin.cpu.arch == .arm or builtin.cpu.arch == .wasm32 or builtin.cpu.arch == .i386) struct {
+                    pub const Expr = extern struct {
+                        pointer: usize,
+
+                        pub fn discriminant(self: Expr) discriminant_Expr {
+                            // The discriminant is stored in the unused bits at the end of the pointer
+                            return @intToEnum(discriminant_Expr, @truncate(u8, self.pointer & 0b11));
+                        }
+
+                        pub fn unionPointer(self: Expr) *union_Expr {
+                            return @intToPtr(*union_Expr, self.pointer & ~@as(usize, 0b11));
+                        }
+                    };
+                } else struct {};
+
+                pub const union_Expr = extern union {
+                    Concat: Expr_Concat,
+                    Num: Expr_Num,
+                };
+
+                pub const Expr_Num = extern struct {
+                    f0: i64,
+                };
+
+                pub const Expr_Concat = extern struct {
+                    f0: Expr,
+                    f1: Expr,
+                };
+
+                pub usingnamespace if (builtin.cpu.arch == .aarch64 or builtin.cpu.arch == .x86_64) struct {
+                    pub const Expr = extern struct {
+                        pointer: usize,
+
+                        pub fn discriminant(self: Expr) discriminant_Expr {
+                            // The discriminant is stored in the unused bits at the end of the pointer
+                            return @intToEnum(discriminant_Expr, @truncate(u8, self.pointer & 0b111));
+                        }
+
+                        pub fn unionPointer(self: Expr) *union_Expr {
+                            return @intToPtr(*union_Expr, self.pointer & ~@as(usize, 0b111));
+                        }
+                    };
+                } else struct {};
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn closure_in_record() {
+        let module = indoc!(
+            r#"
+            main : { update : (I64 -> I64) as Update, offset : I64 }
+            main =
+                offset = 5
+
+                { update: \n -> n + offset, offset }
+        "#
+        );
+
+        assert_eq!(
+            generate_zig_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                pub const R1 = extern struct {
+                    offset: i64,
+                    update: Update,
+                };
+
+                pub usingnamespace if (builtin.cpu.arch == .arm or builtin.cpu.arch == .aarch64 or builtin.cpu.arch == .wasm32 or builtin.cpu.arch == .x86_64) struct {
+                    pub const Update = extern struct {
+                        closure_data: [8]u8 align(8),
+
+                        /// Roc takes ownership of both the arguments and the environment the closure captured.
+                        pub fn call(self: *const Update, arg0: i64) i64 {
+                            var output: i64 = undefined;
+
+                            roc__main_1__Update_caller(&arg0, &self.closure_data, &output);
+
+                            return output;
+                        }
+                    };
+                } else struct {};
+
+                extern fn roc__main_1__Update_caller(arg0: *const i64, closure_data: *const anyopaque, output: *i64) void;
+
+                pub usingnamespace if (builtin.cpu.arch == .i386) struct {
+                    pub const Update = extern struct {
+                        closure_data: [8]u8 align(4),
+
+                        /// Roc takes ownership of both the arguments and the environment the closure captured.
+                        pub fn call(self: *const Update, arg0: i64) i64 {
+                            var output: i64 = undefined;
+
+                            roc__main_1__Update_caller(&arg0, &self.closure_data, &output);
+
+                            return output;
+                        }
+                    };
+                } else struct {};
+            "#
+            )
+        );
+    }
+}
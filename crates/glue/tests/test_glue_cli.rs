@@ -129,6 +129,46 @@ mod glue_cli_run {
         "#),
     }
 
+    #[test]
+    fn type_variable_passed_to_host() {
+        let out = generate_glue_for_invalid_platform(indoc!(
+            r#"
+                platform "test-platform"
+                    requires {} { main : _ }
+                    exposes []
+                    packages {}
+                    imports []
+                    provides [mainForHost]
+
+                mainForHost : List a
+                mainForHost = []
+            "#
+        ));
+
+        assert!(out.stderr.contains("TYPE VARIABLE PASSED TO HOST"));
+        assert!(out.stderr.contains("mainForHost : List a"));
+    }
+
+    #[test]
+    fn unnamed_function_passed_to_host() {
+        let out = generate_glue_for_invalid_platform(indoc!(
+            r#"
+                platform "test-platform"
+                    requires {} { main : _ }
+                    exposes []
+                    packages {}
+                    imports []
+                    provides [mainForHost]
+
+                mainForHost : { update : I64 -> I64 }
+                mainForHost = { update: \n -> n + 1 }
+            "#
+        ));
+
+        assert!(out.stderr.contains("UNNAMED FUNCTION PASSED TO HOST"));
+        assert!(out.stderr.contains("mainForHost : { update : I64 -> I64 }"));
+    }
+
     fn check_for_tests(all_fixtures: &mut roc_collections::VecSet<String>) {
         use roc_collections::VecSet;
 
@@ -207,6 +247,27 @@ mod glue_cli_run {
         glue_out
     }
 
+    /// Runs `roc glue` on a platform it should refuse to generate glue for,
+    /// and returns the output of the failed run.
+    fn generate_glue_for_invalid_platform(platform_src: &str) -> Out {
+        let dir = tempfile::tempdir().expect("Unable to create tempdir");
+        let platform_module_path = dir.path().join("platform.roc");
+        let glue_file = dir.path().join("test_glue.rs");
+
+        fs::write(&platform_module_path, platform_src).unwrap();
+
+        let glue_out = run_glue([
+            "glue",
+            platform_module_path.to_str().unwrap(),
+            glue_file.to_str().unwrap(),
+        ]);
+
+        assert!(!glue_out.status.success(), "bad status {:?}", glue_out);
+        assert!(!glue_file.exists());
+
+        glue_out
+    }
+
     fn run_app<'a, I: IntoIterator<Item = &'a str>>(app_file: &'a Path, args: I) -> Out {
         // Generate test_glue.rs for this platform
         let compile_out = run_roc(
@@ -292,8 +292,14 @@ mod test_peg_grammar {
 
             // content of type_annotation without Colon(:)
             pub rule type_annotation() =
-              function_type()
-              / type_annotation_no_fun()
+              (function_type() / type_annotation_no_fun()) has_clauses()?
+
+            // e.g. `| a has Hash, b has Hash & Eq`
+            rule has_clauses() =
+              [T::Pipe] has_clause() ([T::Comma] has_clause())*
+
+            rule has_clause() =
+              bound_variable() [T::LowercaseIdent] concrete_type() ([T::Ampersand] concrete_type())*
 
             rule type_annotation_no_fun() =
               [T::OpenParen] type_annotation_no_fun() [T::CloseParen]
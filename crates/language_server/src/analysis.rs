@@ -0,0 +1,404 @@
+//! Checking a module with `roc_load`, and answering questions about the result.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+
+use bumpalo::Bump;
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, Hover, HoverContents, Location, MarkupContent, MarkupKind,
+    Position, Range, Url,
+};
+use roc_can::expr::{Declarations, Expr};
+use roc_can::pattern::{DestructType, Pattern, RecordDestruct};
+use roc_can::traverse::{walk_expr, walk_pattern, walk_record_destruct, Visitor};
+use roc_load::{ExecutionMode, LoadResult, LoadStart, LoadedModule, LoadingProblem};
+use roc_module::symbol::{Interns, ModuleIds, Symbol};
+use roc_parse::parser::{FileError, SourceError};
+use roc_region::all::{self, LineInfo, Region};
+use roc_reporting::report::{
+    can_problem, parse_problem, type_problem, CiWrite, RenderTarget, Report, RocDocAllocator,
+    Severity,
+};
+use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use roc_types::subs::Variable;
+
+use crate::convert::LineIndex;
+use crate::format::parse_all;
+
+const REPORT_WIDTH: usize = 70;
+
+pub struct Analysis {
+    /// `None` when the module couldn't be loaded at all, e.g. because it doesn't parse
+    loaded: Option<LoadedModule>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Analysis {
+    /// Parse, canonicalize and type check the module at `path`, using `src` as its contents.
+    /// Any modules it imports are read from disk.
+    pub fn new(path: &Path, src: &str) -> Self {
+        if let Some(diagnostic) = syntax_error_diagnostic(path, src) {
+            return Analysis {
+                loaded: None,
+                diagnostics: vec![diagnostic],
+            };
+        }
+
+        let src_dir = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+
+        // The compiler still panics on some inputs it doesn't support yet. That shouldn't take
+        // the whole language server down with it.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let arena = Bump::new();
+
+            let load_start = LoadStart::from_str(&arena, path.to_path_buf(), src, src_dir)
+                .map_err(loading_problem_report)?;
+
+            // Keep the declarations of imported modules too, to find where their symbols are
+            // defined.
+            let result = roc_load::load_single_threaded(
+                &arena,
+                load_start,
+                Default::default(),
+                TargetInfo::default_x86_64(),
+                RenderTarget::Generic,
+                ExecutionMode::CheckWithDeclarations,
+            )
+            .map_err(loading_problem_report)?;
+
+            match result {
+                LoadResult::TypeChecked(loaded) => Ok(loaded),
+                LoadResult::Monomorphized(_) => unreachable!(),
+            }
+        }));
+
+        match result {
+            Ok(Ok(mut loaded)) => {
+                let diagnostics = problem_diagnostics(&mut loaded);
+
+                Analysis {
+                    loaded: Some(loaded),
+                    diagnostics,
+                }
+            }
+            Ok(Err(report)) => Analysis {
+                loaded: None,
+                diagnostics: vec![Diagnostic {
+                    range: Range::default(),
+                    severity: Some(DiagnosticSeverity::Error),
+                    source: Some("roc".to_string()),
+                    message: report,
+                    ..Default::default()
+                }],
+            },
+            Err(_) => Analysis {
+                loaded: None,
+                diagnostics: vec![Diagnostic {
+                    range: Range::default(),
+                    severity: Some(DiagnosticSeverity::Error),
+                    source: Some("roc".to_string()),
+                    message: "The compiler crashed while checking this module.".to_string(),
+                    ..Default::default()
+                }],
+            },
+        }
+    }
+
+    /// The inferred type of the innermost expression or pattern at `position`
+    pub fn hover(&mut self, position: Position) -> Option<Hover> {
+        let loaded = self.loaded.as_mut()?;
+        let (_, src) = loaded.sources.get(&loaded.module_id)?;
+        let line_index = LineIndex::new(src);
+        let offset = line_index.offset(position)?;
+        let decls = loaded.declarations_by_id.get(&loaded.module_id)?;
+
+        let mut finder = InnermostFinder::new(offset);
+        finder.visit_decls(decls);
+        let found = finder.found?;
+
+        let typ = name_and_print_var(
+            found.var,
+            loaded.solved.inner_mut(),
+            loaded.module_id,
+            &loaded.interns,
+            DebugPrint::NOTHING,
+        );
+        let signature = match found.symbol {
+            Some(symbol) => format!("{} : {}", symbol.as_str(&loaded.interns), typ),
+            None => typ,
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```roc\n{}\n```", signature),
+            }),
+            range: Some(line_index.range(found.region)),
+        })
+    }
+
+    /// Where the value referenced at `position` is defined
+    pub fn definition(&self, position: Position) -> Option<Location> {
+        let loaded = self.loaded.as_ref()?;
+        let (_, src) = loaded.sources.get(&loaded.module_id)?;
+        let offset = LineIndex::new(src).offset(position)?;
+        let decls = loaded.declarations_by_id.get(&loaded.module_id)?;
+
+        let mut finder = InnermostFinder::new(offset);
+        finder.visit_decls(decls);
+        let found = finder.found.filter(|found| found.is_reference)?;
+        let symbol = found.symbol?;
+
+        let home = symbol.module_id();
+        let (path, src) = loaded.sources.get(&home)?;
+        let region = match loaded.abilities_store.member_def(symbol) {
+            Some(member) => member.region,
+            None => find_definition(symbol, loaded.declarations_by_id.get(&home)?)?,
+        };
+
+        Some(Location {
+            uri: Url::from_file_path(path).ok()?,
+            range: LineIndex::new(src).range(region),
+        })
+    }
+}
+
+fn problem_diagnostics(loaded: &mut LoadedModule) -> Vec<Diagnostic> {
+    let home = loaded.module_id;
+    let (path, src) = match loaded.sources.get(&home) {
+        Some(source) => source,
+        None => return Vec::new(),
+    };
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let lines = LineInfo::new(src);
+    let line_index = LineIndex::new(src);
+    let alloc = RocDocAllocator::new(&src_lines, home, &loaded.interns);
+
+    let can_problems = loaded.can_problems.remove(&home).unwrap_or_default();
+    let type_problems = loaded.type_problems.remove(&home).unwrap_or_default();
+
+    let can_diagnostics = can_problems.into_iter().map(|problem| {
+        let region = problem.region();
+        let report = can_problem(&alloc, &lines, path.clone(), problem);
+
+        report_diagnostic(&line_index, region, report)
+    });
+    let type_diagnostics = type_problems.into_iter().filter_map(|problem| {
+        let region = problem.region();
+        let report = type_problem(&alloc, &lines, path.clone(), problem)?;
+
+        Some(report_diagnostic(&line_index, region, report))
+    });
+
+    can_diagnostics.chain(type_diagnostics).collect()
+}
+
+fn report_diagnostic(line_index: &LineIndex, region: Option<Region>, report: Report) -> Diagnostic {
+    let severity = match report.severity {
+        Severity::RuntimeError => DiagnosticSeverity::Error,
+        Severity::Warning => DiagnosticSeverity::Warning,
+    };
+
+    // Only the body; the title and file name are already shown next to the diagnostic.
+    let mut message = String::new();
+    report
+        .doc
+        .1
+        .render_raw(REPORT_WIDTH, &mut CiWrite::new(&mut message))
+        .expect("rendering a report to a String cannot fail");
+
+    Diagnostic {
+        range: region
+            .map(|region| line_index.range(region))
+            .unwrap_or_default(),
+        severity: Some(severity),
+        source: Some("roc".to_string()),
+        message: message.trim().to_string(),
+        ..Default::default()
+    }
+}
+
+/// Syntax errors stop the module from loading, so the module is parsed here first to find
+/// out where they are.
+fn syntax_error_diagnostic(path: &Path, src: &str) -> Option<Diagnostic> {
+    let arena = Bump::new();
+    let problem = parse_all(&arena, src).err()?;
+    let region = problem.get_region();
+
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let lines = LineInfo::new(src);
+    let mut module_ids = ModuleIds::default();
+    let home = module_ids.get_or_insert(&"".into());
+    let interns = Interns {
+        module_ids,
+        ..Default::default()
+    };
+    let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+    let problem = FileError {
+        problem: SourceError {
+            problem,
+            bytes: src.as_bytes(),
+        },
+        filename: path.to_path_buf(),
+    };
+    let report = parse_problem(&alloc, &lines, path.to_path_buf(), 0, problem);
+
+    Some(report_diagnostic(&LineIndex::new(src), region, report))
+}
+
+/// Problems with imported modules, e.g. ones that are missing or don't parse, only come back
+/// as a formatted report.
+fn loading_problem_report(problem: LoadingProblem) -> String {
+    match problem {
+        LoadingProblem::FormattedReport(report) => report,
+        problem => format!("{:?}", problem),
+    }
+}
+
+struct Found {
+    region: Region,
+    var: Variable,
+    /// The symbol this expression or pattern names, if any
+    symbol: Option<Symbol>,
+    /// Whether this is a use of `symbol`, rather than where it's defined
+    is_reference: bool,
+}
+
+/// Finds the innermost expression or pattern with a type that contains an offset.
+struct InnermostFinder {
+    position: Region,
+    found: Option<Found>,
+}
+
+impl InnermostFinder {
+    fn new(offset: u32) -> Self {
+        let position = all::Position::new(offset);
+
+        InnermostFinder {
+            position: Region::new(position, position),
+            found: None,
+        }
+    }
+
+    fn record(
+        &mut self,
+        region: Region,
+        var: Variable,
+        symbol: Option<Symbol>,
+        is_reference: bool,
+    ) {
+        let is_innermost = match &self.found {
+            Some(found) => found.region.contains(&region),
+            None => true,
+        };
+
+        if is_innermost {
+            self.found = Some(Found {
+                region,
+                var,
+                symbol,
+                is_reference,
+            });
+        }
+    }
+}
+
+impl Visitor for InnermostFinder {
+    fn should_visit(&mut self, region: Region) -> bool {
+        region.contains(&self.position)
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if !region.contains(&self.position) {
+            return;
+        }
+
+        match expr {
+            Expr::Var(symbol) | Expr::AbilityMember(symbol, _, _) => {
+                self.record(region, var, Some(*symbol), true)
+            }
+            _ => self.record(region, var, None, false),
+        }
+
+        walk_expr(self, expr, var);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern, region: Region, opt_var: Option<Variable>) {
+        if !region.contains(&self.position) {
+            return;
+        }
+
+        if let Some(var) = opt_var {
+            let symbol = match pattern {
+                Pattern::Identifier(symbol)
+                | Pattern::Shadowed(_, _, symbol)
+                | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => Some(*symbol),
+                _ => None,
+            };
+
+            self.record(region, var, symbol, false);
+        }
+
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+        if !region.contains(&self.position) {
+            return;
+        }
+
+        if let DestructType::Required | DestructType::Optional(..) = destruct.typ {
+            self.record(region, destruct.var, Some(destruct.symbol), false);
+        }
+
+        walk_record_destruct(self, destruct);
+    }
+}
+
+/// The region of the pattern that introduces `symbol`
+fn find_definition(symbol: Symbol, decls: &Declarations) -> Option<Region> {
+    let mut finder = DefinitionFinder {
+        symbol,
+        found: None,
+    };
+    finder.visit_decls(decls);
+
+    return finder.found;
+
+    struct DefinitionFinder {
+        symbol: Symbol,
+        found: Option<Region>,
+    }
+
+    impl Visitor for DefinitionFinder {
+        fn should_visit(&mut self, _region: Region) -> bool {
+            self.found.is_none()
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            match pattern {
+                Pattern::Identifier(symbol)
+                | Pattern::Shadowed(_, _, symbol)
+                | Pattern::AbilityMemberSpecialization { ident: symbol, .. }
+                    if *symbol == self.symbol =>
+                {
+                    self.found = Some(region);
+                }
+                _ => walk_pattern(self, pattern),
+            }
+        }
+
+        fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+            match &destruct.typ {
+                DestructType::Required | DestructType::Optional(..)
+                    if destruct.symbol == self.symbol =>
+                {
+                    self.found = Some(region);
+                }
+                _ => walk_record_destruct(self, destruct),
+            }
+        }
+    }
+}
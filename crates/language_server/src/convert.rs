@@ -0,0 +1,76 @@
+//! The compiler works with byte offsets, while LSP positions are a line and a
+//! UTF-16 code unit offset within that line.
+
+use lsp_types::{Position, Range};
+use roc_region::all::Region;
+
+pub struct LineIndex<'a> {
+    src: &'a str,
+    /// The byte offset each line starts at
+    line_starts: Vec<u32>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(src: &'a str) -> Self {
+        let mut line_starts = vec![0];
+
+        line_starts.extend(
+            src.bytes()
+                .enumerate()
+                .filter(|(_, byte)| *byte == b'\n')
+                .map(|(index, _)| index as u32 + 1),
+        );
+
+        LineIndex { src, line_starts }
+    }
+
+    pub fn position(&self, offset: u32) -> Position {
+        let offset = offset.min(self.src.len() as u32);
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line] as usize;
+        let character = utf16_len(&self.src[line_start..offset as usize]);
+
+        Position::new(line as u32, character)
+    }
+
+    /// The byte offset of an LSP position, clamped to the end of its line.
+    pub fn offset(&self, position: Position) -> Option<u32> {
+        let line_start = *self.line_starts.get(position.line as usize)? as usize;
+        let line_end = self
+            .line_starts
+            .get(position.line as usize + 1)
+            .map(|next_start| *next_start as usize - 1)
+            .unwrap_or_else(|| self.src.len());
+
+        let mut character = 0;
+
+        for (index, ch) in self.src[line_start..line_end].char_indices() {
+            if character >= position.character {
+                return Some((line_start + index) as u32);
+            }
+
+            character += ch.len_utf16() as u32;
+        }
+
+        Some(line_end as u32)
+    }
+
+    pub fn range(&self, region: Region) -> Range {
+        Range::new(
+            self.position(region.start().offset),
+            self.position(region.end().offset),
+        )
+    }
+
+    /// A range covering the whole source
+    pub fn full_range(&self) -> Range {
+        Range::new(Position::new(0, 0), self.position(self.src.len() as u32))
+    }
+}
+
+pub fn utf16_len(text: &str) -> u32 {
+    text.chars().map(|ch| ch.len_utf16() as u32).sum()
+}
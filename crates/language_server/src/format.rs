@@ -0,0 +1,43 @@
+//! Formatting whole documents with `roc_fmt`, the same way `roc format` does.
+
+use bumpalo::Bump;
+use lsp_types::TextEdit;
+use roc_fmt::def::fmt_defs;
+use roc_fmt::module::fmt_module;
+use roc_fmt::{Ast, Buf};
+use roc_parse::{
+    module::{self, module_defs},
+    parser::{Parser, SyntaxError},
+    state::State,
+};
+
+use crate::convert::LineIndex;
+
+/// The edits that format `src`, or `None` if it doesn't parse.
+pub fn format(src: &str) -> Option<Vec<TextEdit>> {
+    let arena = Bump::new();
+    let ast = parse_all(&arena, src).ok()?;
+
+    let mut buf = Buf::new_in(&arena);
+    fmt_module(&mut buf, &ast.module);
+    fmt_defs(&mut buf, &ast.defs, 0);
+    buf.fmt_end_of_file();
+
+    if buf.as_str() == src {
+        return Some(Vec::new());
+    }
+
+    Some(vec![TextEdit {
+        range: LineIndex::new(src).full_range(),
+        new_text: buf.as_str().to_string(),
+    }])
+}
+
+pub(crate) fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
+    let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
+        .map_err(|e| SyntaxError::Header(e.problem))?;
+
+    let (_, defs, _) = module_defs().parse(arena, state).map_err(|(_, e, _)| e)?;
+
+    Ok(Ast { module, defs })
+}
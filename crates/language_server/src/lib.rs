@@ -0,0 +1,223 @@
+//! A language server for Roc.
+//!
+//! It speaks the [Language Server Protocol](https://microsoft.github.io/language-server-protocol/)
+//! and provides diagnostics, hover with inferred types, go to definition, formatting and
+//! semantic tokens. Modules are checked with `roc_load` whenever they're opened or changed.
+mod analysis;
+mod convert;
+mod format;
+mod semantic_tokens;
+
+use analysis::Analysis;
+use lsp_server::{Connection, ErrorCode, Message, ProtocolError, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{
+    Formatting, GotoDefinition, HoverRequest, Request as _, SemanticTokensFullRequest,
+};
+use lsp_types::{
+    GotoDefinitionResponse, HoverProviderCapability, OneOf, PublishDiagnosticsParams,
+    SemanticTokens, SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use roc_collections::all::MutMap;
+
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::Full)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: semantic_tokens::legend(),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..Default::default()
+            },
+        )),
+        ..Default::default()
+    }
+}
+
+/// Serve requests on `connection` until the client shuts the server down.
+pub fn run(connection: &Connection) -> Result<(), ProtocolError> {
+    let capabilities = serde_json::to_value(capabilities()).unwrap();
+    connection.initialize(capabilities)?;
+
+    let mut server = Server {
+        connection,
+        documents: MutMap::default(),
+    };
+
+    for msg in &connection.receiver {
+        match msg {
+            Message::Request(req) => {
+                if connection.handle_shutdown(&req)? {
+                    return Ok(());
+                }
+
+                let response = server.handle_request(req);
+                server.send(response.into());
+            }
+            Message::Notification(notification) => server.handle_notification(notification),
+            Message::Response(_) => {
+                // We never send the client requests, so there's nothing to handle here.
+            }
+        }
+    }
+
+    Ok(())
+}
+
+struct Document {
+    text: String,
+    /// `None` for documents that aren't files on disk, since their imports can't be found
+    analysis: Option<Analysis>,
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    documents: MutMap<Url, Document>,
+}
+
+impl<'a> Server<'a> {
+    fn send(&self, msg: Message) {
+        // This only fails once the client has disconnected, which ends the main loop anyway.
+        let _ = self.connection.sender.send(msg);
+    }
+
+    fn handle_request(&mut self, req: Request) -> Response {
+        match req.method.as_str() {
+            HoverRequest::METHOD => respond::<HoverRequest>(req, |params| {
+                let position = params.text_document_position_params;
+
+                self.documents
+                    .get_mut(&position.text_document.uri)?
+                    .analysis
+                    .as_mut()?
+                    .hover(position.position)
+            }),
+            GotoDefinition::METHOD => respond::<GotoDefinition>(req, |params| {
+                let position = params.text_document_position_params;
+
+                self.documents
+                    .get(&position.text_document.uri)?
+                    .analysis
+                    .as_ref()?
+                    .definition(position.position)
+                    .map(GotoDefinitionResponse::Scalar)
+            }),
+            Formatting::METHOD => respond::<Formatting>(req, |params| {
+                let document = self.documents.get(&params.text_document.uri)?;
+
+                format::format(&document.text)
+            }),
+            SemanticTokensFullRequest::METHOD => {
+                respond::<SemanticTokensFullRequest>(req, |params| {
+                    let document = self.documents.get(&params.text_document.uri)?;
+
+                    Some(SemanticTokensResult::Tokens(SemanticTokens {
+                        result_id: None,
+                        data: semantic_tokens::semantic_tokens(&document.text),
+                    }))
+                })
+            }
+            _ => Response::new_err(
+                req.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request: {}", req.method),
+            ),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: lsp_server::Notification) {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                if let Some(params) = extract::<DidOpenTextDocument>(notification) {
+                    let document = params.text_document;
+
+                    self.update(document.uri, document.text, Some(document.version));
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                if let Some(mut params) = extract::<DidChangeTextDocument>(notification) {
+                    // We only ask for full document syncs, so the last change has the whole text.
+                    if let Some(change) = params.content_changes.pop() {
+                        let document = params.text_document;
+
+                        self.update(document.uri, change.text, Some(document.version));
+                    }
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Some(params) = extract::<DidCloseTextDocument>(notification) {
+                    let uri = params.text_document.uri;
+
+                    self.documents.remove(&uri);
+                    self.publish_diagnostics(uri, Vec::new(), None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check the new text of a document, and tell the client about any problems.
+    fn update(&mut self, uri: Url, text: String, version: Option<i32>) {
+        let analysis = uri
+            .to_file_path()
+            .ok()
+            .map(|path| Analysis::new(&path, &text));
+        let diagnostics = analysis
+            .as_ref()
+            .map(|analysis| analysis.diagnostics.clone())
+            .unwrap_or_default();
+
+        self.documents
+            .insert(uri.clone(), Document { text, analysis });
+        self.publish_diagnostics(uri, diagnostics, version);
+    }
+
+    fn publish_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+        version: Option<i32>,
+    ) {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        };
+
+        self.send(
+            lsp_server::Notification::new(PublishDiagnostics::METHOD.to_string(), params).into(),
+        );
+    }
+}
+
+fn respond<R>(req: Request, handler: impl FnOnce(R::Params) -> R::Result) -> Response
+where
+    R: lsp_types::request::Request,
+{
+    match serde_json::from_value(req.params) {
+        Ok(params) => Response::new_ok(req.id, handler(params)),
+        Err(err) => Response::new_err(req.id, ErrorCode::InvalidParams as i32, err.to_string()),
+    }
+}
+
+fn extract<N>(notification: lsp_server::Notification) -> Option<N::Params>
+where
+    N: lsp_types::notification::Notification,
+{
+    match serde_json::from_value(notification.params) {
+        Ok(params) => Some(params),
+        Err(err) => {
+            eprintln!("Invalid {} notification: {}", N::METHOD, err);
+
+            None
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use lsp_server::Connection;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    roc_language_server::run(&connection)?;
+
+    // Drop the connection so the writer thread sees its channel close and can finish.
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Semantic tokens from the `roc_highlight` tokenizer.
+
+use lsp_types::{SemanticToken, SemanticTokenType, SemanticTokensLegend};
+use roc_highlight::tokenizer::{full_tokenize, Token};
+
+use crate::convert::{utf16_len, LineIndex};
+
+/// The token types we report, in the order of their indices in the legend.
+const TOKEN_TYPES: [SemanticTokenType; 6] = [
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::TYPE,
+];
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: Vec::new(),
+    }
+}
+
+fn token_type(token: Token) -> Option<SemanticTokenType> {
+    use Token::*;
+
+    let token_type = match token {
+        KeywordIf | KeywordThen | KeywordElse | KeywordWhen | KeywordAs | KeywordIs
+        | KeywordExpect | KeywordApp | KeywordInterface | KeywordPackages | KeywordImports
+        | KeywordProvides | KeywordTo | KeywordExposes | KeywordEffects | KeywordPlatform
+        | KeywordRequires | KeywordDbg => SemanticTokenType::KEYWORD,
+        OpPlus | OpMinus | OpSlash | OpPercent | OpCaret | OpGreaterThan | OpLessThan
+        | OpAssignment | OpPizza | OpEquals | OpNotEquals | OpGreaterThanOrEq | OpLessThanOrEq
+        | OpAnd | OpOr | OpDoubleSlash | OpBackpassing | QuestionMark | Ampersand | Pipe | Bang
+        | LambdaStart | Arrow | FatArrow | Asterisk => SemanticTokenType::OPERATOR,
+        String => SemanticTokenType::STRING,
+        Number | NumberBase => SemanticTokenType::NUMBER,
+        LowercaseIdent => SemanticTokenType::VARIABLE,
+        UppercaseIdent => SemanticTokenType::TYPE,
+        _ => return None,
+    };
+
+    Some(token_type)
+}
+
+/// The tokens of `src`, encoded relative to each other like LSP expects.
+pub fn semantic_tokens(src: &str) -> Vec<SemanticToken> {
+    let line_index = LineIndex::new(src);
+    let table = full_tokenize(src);
+
+    let mut tokens = Vec::with_capacity(table.tokens.len());
+    let mut prev_line = 0;
+    let mut prev_start = 0;
+
+    for ((token, offset), length) in table
+        .tokens
+        .iter()
+        .zip(table.offsets.iter())
+        .zip(table.lengths.iter())
+    {
+        let token_type = match token_type(*token) {
+            Some(token_type) => token_type,
+            None => continue,
+        };
+        let index = TOKEN_TYPES.iter().position(|t| *t == token_type).unwrap() as u32;
+        let start = line_index.position(*offset as u32);
+
+        // Not every client supports tokens spanning several lines, e.g. multiline strings,
+        // so those only get highlighted on their first line.
+        let text = &src[*offset..*offset + *length];
+        let first_line = text.split('\n').next().unwrap_or_default();
+        let length = utf16_len(first_line);
+
+        if length == 0 {
+            continue;
+        }
+
+        let delta_line = start.line - prev_line;
+        let delta_start = if delta_line == 0 {
+            start.character - prev_start
+        } else {
+            start.character
+        };
+
+        tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type: index,
+            token_modifiers_bitset: 0,
+        });
+
+        prev_line = start.line;
+        prev_start = start.character;
+    }
+
+    tokens
+}
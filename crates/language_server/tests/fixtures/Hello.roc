@@ -0,0 +1,8 @@
+interface Hello
+    exposes [greet]
+    imports [Names]
+
+greet = \name ->
+    greeting = "Hello, "
+
+    Str.concat greeting (Names.polish name)
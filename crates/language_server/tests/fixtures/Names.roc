@@ -0,0 +1,7 @@
+interface Names
+    exposes [polish]
+    imports []
+
+## Tidies up a name before greeting someone with it
+polish : Str -> Str
+polish = \name -> Str.trim name
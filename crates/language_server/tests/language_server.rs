@@ -0,0 +1,445 @@
+#[cfg(test)]
+mod language_server {
+    use indoc::indoc;
+    use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId};
+    use lsp_types::notification::{
+        DidChangeTextDocument, DidOpenTextDocument, Exit, Initialized,
+        Notification as NotificationType, PublishDiagnostics,
+    };
+    use lsp_types::request::{
+        Formatting, GotoDefinition, HoverRequest, Initialize, Request as RequestType,
+        SemanticTokensFullRequest, Shutdown,
+    };
+    use lsp_types::{
+        ClientCapabilities, DiagnosticSeverity, DidChangeTextDocumentParams,
+        DidOpenTextDocumentParams, DocumentFormattingParams, FormattingOptions,
+        GotoDefinitionParams, GotoDefinitionResponse, HoverContents, HoverParams, InitializeParams,
+        InitializedParams, Location, Position, PublishDiagnosticsParams, Range, SemanticToken,
+        SemanticTokensParams, SemanticTokensResult, TextDocumentContentChangeEvent,
+        TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams, Url,
+        VersionedTextDocumentIdentifier,
+    };
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(120);
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(name)
+    }
+
+    fn fixture_uri(name: &str) -> Url {
+        Url::from_file_path(fixture(name)).unwrap()
+    }
+
+    /// Talks to a language server running on another thread, like an editor would.
+    struct Client {
+        connection: Connection,
+        server: JoinHandle<()>,
+        next_id: i32,
+    }
+
+    impl Client {
+        fn start() -> Self {
+            let (server_connection, connection) = Connection::memory();
+            let server =
+                thread::spawn(move || roc_language_server::run(&server_connection).unwrap());
+
+            let mut client = Client {
+                connection,
+                server,
+                next_id: 0,
+            };
+
+            // `root_path` is deprecated, but still has to be given.
+            #[allow(deprecated)]
+            let result = client.request::<Initialize>(InitializeParams {
+                process_id: None,
+                root_path: None,
+                root_uri: None,
+                initialization_options: None,
+                capabilities: ClientCapabilities::default(),
+                trace: None,
+                workspace_folders: None,
+                client_info: None,
+                locale: None,
+            });
+            assert_eq!(result.capabilities, roc_language_server::capabilities());
+
+            client.notify::<Initialized>(InitializedParams {});
+
+            client
+        }
+
+        fn request<R: RequestType>(&mut self, params: R::Params) -> R::Result {
+            let result = self.request_raw(R::METHOD, serde_json::to_value(params).unwrap());
+
+            serde_json::from_value(result.unwrap()).unwrap()
+        }
+
+        fn request_raw(
+            &mut self,
+            method: &str,
+            params: serde_json::Value,
+        ) -> Result<serde_json::Value, lsp_server::ResponseError> {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+
+            self.send(Request::new(id.clone(), method.to_string(), params).into());
+
+            match self.recv() {
+                Message::Response(response) => {
+                    assert_eq!(response.id, id);
+
+                    match response.error {
+                        Some(error) => Err(error),
+                        None => Ok(response.result.unwrap_or_default()),
+                    }
+                }
+                msg => panic!("Expected a response to {}, but got {:?}", method, msg),
+            }
+        }
+
+        fn notify<N: NotificationType>(&self, params: N::Params) {
+            self.send(Notification::new(N::METHOD.to_string(), params).into());
+        }
+
+        fn send(&self, msg: Message) {
+            self.connection.sender.send(msg).unwrap();
+        }
+
+        fn recv(&self) -> Message {
+            self.connection.receiver.recv_timeout(TIMEOUT).unwrap()
+        }
+
+        fn diagnostics(&self) -> PublishDiagnosticsParams {
+            match self.recv() {
+                Message::Notification(notification)
+                    if notification.method == PublishDiagnostics::METHOD =>
+                {
+                    serde_json::from_value(notification.params).unwrap()
+                }
+                msg => panic!("Expected diagnostics, but got {:?}", msg),
+            }
+        }
+
+        fn open(&self, uri: &Url, text: &str) -> PublishDiagnosticsParams {
+            self.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "roc".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            });
+
+            self.diagnostics()
+        }
+
+        fn open_fixture(&self, name: &str) -> PublishDiagnosticsParams {
+            self.open(
+                &fixture_uri(name),
+                &fs::read_to_string(fixture(name)).unwrap(),
+            )
+        }
+
+        fn shutdown(mut self) {
+            self.request::<Shutdown>(());
+            self.notify::<Exit>(());
+
+            self.server.join().unwrap();
+        }
+    }
+
+    fn position_params(uri: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+        TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            position: Position::new(line, character),
+        }
+    }
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+        Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+    }
+
+    #[test]
+    fn no_diagnostics() {
+        let client = Client::start();
+
+        let published = client.open_fixture("Hello.roc");
+
+        assert_eq!(published.uri, fixture_uri("Hello.roc"));
+        assert_eq!(published.version, Some(1));
+        assert!(published.diagnostics.is_empty());
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn diagnostics() {
+        let client = Client::start();
+        let uri = fixture_uri("Hello.roc");
+
+        let published = client.open(
+            &uri,
+            indoc!(
+                r#"
+                interface Hello
+                    exposes [greet]
+                    imports []
+
+                greet = \name ->
+                    unused = 1
+
+                    Str.concat name nam
+                "#
+            ),
+        );
+
+        let diagnostics = published.diagnostics;
+        assert_eq!(diagnostics.len(), 2);
+
+        let not_found = &diagnostics[0];
+        assert_eq!(not_found.severity, Some(DiagnosticSeverity::Error));
+        assert_eq!(not_found.range, range((7, 20), (7, 23)));
+        assert!(not_found
+            .message
+            .starts_with("Nothing is named `nam` in this scope."));
+
+        let unused = &diagnostics[1];
+        assert_eq!(unused.severity, Some(DiagnosticSeverity::Warning));
+        assert_eq!(unused.range, range((5, 4), (5, 10)));
+        assert!(unused.message.contains("`unused` is not used anywhere"));
+
+        // Fixing the problems clears them.
+        client.notify::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: uri.clone(),
+                version: 2,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: fs::read_to_string(fixture("Hello.roc")).unwrap(),
+            }],
+        });
+
+        let published = client.diagnostics();
+        assert_eq!(published.version, Some(2));
+        assert!(published.diagnostics.is_empty());
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn syntax_error() {
+        let client = Client::start();
+
+        let published = client.open(
+            &fixture_uri("Hello.roc"),
+            indoc!(
+                r#"
+                interface Hello
+                    exposes [greet]
+                    imports []
+
+                greet = \name ->
+                    Str.concat "Hello, " (name
+                "#
+            ),
+        );
+
+        let diagnostics = published.diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Error));
+        assert_eq!(diagnostics[0].range.start, Position::new(5, 30));
+        assert!(diagnostics[0].message.starts_with("I am partway through"));
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn hover() {
+        let mut client = Client::start();
+        let uri = fixture_uri("Hello.roc");
+        client.open_fixture("Hello.roc");
+
+        let hover_at = |client: &mut Client, line, character| {
+            let hover = client
+                .request::<HoverRequest>(HoverParams {
+                    text_document_position_params: position_params(&uri, line, character),
+                    work_done_progress_params: Default::default(),
+                })
+                .unwrap();
+
+            match hover.contents {
+                HoverContents::Markup(markup) => (markup.value, hover.range.unwrap()),
+                contents => panic!("Expected markup, but got {:?}", contents),
+            }
+        };
+
+        assert_eq!(
+            hover_at(&mut client, 7, 17),
+            (
+                "```roc\ngreeting : Str\n```".to_string(),
+                range((7, 15), (7, 23))
+            )
+        );
+        assert_eq!(
+            hover_at(&mut client, 4, 2),
+            (
+                "```roc\ngreet : Str -> Str\n```".to_string(),
+                range((4, 0), (4, 5))
+            )
+        );
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn go_to_definition() {
+        let mut client = Client::start();
+        let uri = fixture_uri("Hello.roc");
+        client.open_fixture("Hello.roc");
+
+        let definition_at = |client: &mut Client, line, character| {
+            let response = client.request::<GotoDefinition>(GotoDefinitionParams {
+                text_document_position_params: position_params(&uri, line, character),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            });
+
+            match response {
+                Some(GotoDefinitionResponse::Scalar(location)) => Some(location),
+                None => None,
+                response => panic!("Expected a single location, but got {:?}", response),
+            }
+        };
+
+        // A local def
+        assert_eq!(
+            definition_at(&mut client, 7, 17),
+            Some(Location::new(uri.clone(), range((5, 4), (5, 12))))
+        );
+
+        // A def in an imported module
+        assert_eq!(
+            definition_at(&mut client, 7, 31),
+            Some(Location::new(
+                fixture_uri("Names.roc"),
+                range((6, 0), (6, 6))
+            ))
+        );
+
+        // Not a reference to anything
+        assert_eq!(definition_at(&mut client, 5, 17), None);
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn formatting() {
+        let mut client = Client::start();
+        let uri = fixture_uri("Hello.roc");
+
+        client.open(
+            &uri,
+            "interface Hello exposes [greet] imports [Names]\n\ngreet = \\name  ->\n    Str.concat \"Hello, \"   name\n",
+        );
+
+        let edits = client
+            .request::<Formatting>(DocumentFormattingParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                options: FormattingOptions::default(),
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range, range((0, 0), (4, 0)));
+        assert_eq!(
+            edits[0].new_text,
+            "interface Hello exposes [greet] imports [Names]\n\ngreet = \\name ->\n    Str.concat \"Hello, \" name\n"
+        );
+
+        // Already formatted code doesn't need any edits.
+        client.open_fixture("Hello.roc");
+
+        let edits = client
+            .request::<Formatting>(DocumentFormattingParams {
+                text_document: TextDocumentIdentifier { uri },
+                options: FormattingOptions::default(),
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+
+        assert!(edits.is_empty());
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn semantic_tokens() {
+        let mut client = Client::start();
+        let uri = fixture_uri("Hello.roc");
+        client.open(&uri, "x = \"hi\"\n\ny : Num *\ny = 42\n");
+
+        let result = client
+            .request::<SemanticTokensFullRequest>(SemanticTokensParams {
+                text_document: TextDocumentIdentifier { uri },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+
+        let data = match result {
+            SemanticTokensResult::Tokens(tokens) => tokens.data,
+            result => panic!("Expected tokens, but got {:?}", result),
+        };
+
+        // The legend is keyword, operator, string, number, variable, type
+        let token = |delta_line, delta_start, length, token_type| SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset: 0,
+        };
+
+        assert_eq!(
+            data,
+            vec![
+                token(0, 0, 1, 4),
+                token(0, 2, 1, 1),
+                token(0, 2, 4, 2),
+                token(2, 0, 1, 4),
+                token(0, 4, 3, 5),
+                token(0, 4, 1, 1),
+                token(1, 0, 1, 4),
+                token(0, 2, 1, 1),
+                token(0, 2, 2, 3),
+            ]
+        );
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn unsupported_request() {
+        let mut client = Client::start();
+
+        let error = client
+            .request_raw("textDocument/rename", serde_json::Value::Null)
+            .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::MethodNotFound as i32);
+
+        client.shutdown();
+    }
+}
@@ -0,0 +1,34 @@
+// Stands in for a Roc application, using the relocations LLVM emits for
+// AArch64 code: ADRP/ADD and ADRP/LDR pairs into rodata, calls and tail
+// calls, and a GOT-relative load of a host function.
+
+    .text
+
+    .globl roc__mainForHost_1_exposed_generic
+    .type roc__mainForHost_1_exposed_generic, %function
+roc__mainForHost_1_exposed_generic:
+    stp x29, x30, [sp, #-16]!
+    adrp x0, .Lmessage
+    add x0, x0, :lo12:.Lmessage
+    adrp x1, .Lnumber
+    ldr x1, [x1, :lo12:.Lnumber]
+    bl roc_alloc
+    adrp x2, :got:roc_panic
+    ldr x2, [x2, :got_lo12:roc_panic]
+    ldp x29, x30, [sp], #16
+    b roc__mainForHost_size
+    .size roc__mainForHost_1_exposed_generic, .-roc__mainForHost_1_exposed_generic
+
+    .globl roc__mainForHost_size
+    .type roc__mainForHost_size, %function
+roc__mainForHost_size:
+    mov x0, #8
+    ret
+    .size roc__mainForHost_size, .-roc__mainForHost_size
+
+    .section .rodata
+.Lmessage:
+    .asciz "Hello, World!"
+    .p2align 3
+.Lnumber:
+    .quad 42
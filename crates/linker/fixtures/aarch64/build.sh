@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+
+# Rebuilds the aarch64 fixtures used by the surgical linker tests.
+# Needs llvm-mc and ld.lld; any host architecture works.
+
+set -euxo pipefail
+
+cd "$(dirname "$0")"
+
+LLD=${LLD:-ld.lld}
+
+llvm-mc -triple=aarch64-linux-gnu -filetype=obj host.s -o host.o
+llvm-mc -triple=aarch64-linux-gnu -filetype=obj libapp.s -o libapp.o
+llvm-mc -triple=aarch64-linux-gnu -filetype=obj app.s -o app.o
+
+$LLD -m aarch64linux -shared -soname libapp.so libapp.o -o libapp.so
+$LLD -m aarch64linux -pie --eh-frame-hdr \
+    -dynamic-linker /lib/ld-linux-aarch64.so.1 \
+    host.o libapp.so -o dynhost
+
+rm host.o libapp.o libapp.so
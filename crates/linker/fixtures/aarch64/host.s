@@ -0,0 +1,41 @@
+// A tiny platform host which reaches the app in each of the ways the
+// surgical linker has to patch: a direct call and a tail call through the
+// PLT, and a function pointer loaded from the GOT.
+
+    .text
+
+    .globl _start
+    .type _start, %function
+_start:
+    bl run
+    mov x0, #0
+    mov x8, #93 // exit
+    svc #0
+
+    .type run, %function
+run:
+    stp x29, x30, [sp, #-16]!
+    bl roc__mainForHost_1_exposed_generic
+    adrp x1, :got:roc__mainForHost_size
+    ldr x1, [x1, :got_lo12:roc__mainForHost_size]
+    blr x1
+    ldp x29, x30, [sp], #16
+    b roc__mainForHost_1_exposed_generic
+
+    .globl roc_alloc
+    .type roc_alloc, %function
+roc_alloc:
+    mov x0, #0
+    ret
+
+    .globl roc_panic
+    .type roc_panic, %function
+roc_panic:
+    mov x0, #1
+    mov x8, #93 // exit
+    svc #0
+
+    .section .data.rel.ro, "aw"
+    .p2align 3
+allocator:
+    .quad roc_alloc
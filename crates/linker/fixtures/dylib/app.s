@@ -0,0 +1,29 @@
+// Stands in for a Roc application: it loads from its own rodata and calls
+// back into the host.
+
+    .text
+
+    .globl roc__mainForHost_1_exposed_generic
+    .type roc__mainForHost_1_exposed_generic, @function
+roc__mainForHost_1_exposed_generic:
+    sub $8, %rsp
+    lea .Lmessage(%rip), %rdi
+    call roc_alloc@PLT
+    add .Ltwo(%rip), %rax
+    add $8, %rsp
+    ret
+    .size roc__mainForHost_1_exposed_generic, .-roc__mainForHost_1_exposed_generic
+
+    .globl roc__mainForHost_size
+    .type roc__mainForHost_size, @function
+roc__mainForHost_size:
+    mov $8, %eax
+    ret
+    .size roc__mainForHost_size, .-roc__mainForHost_size
+
+    .section .rodata
+.Lmessage:
+    .asciz "Hello, World!"
+    .p2align 3
+.Ltwo:
+    .quad 2
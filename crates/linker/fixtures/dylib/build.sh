@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+
+# Rebuilds the x86_64 shared library host fixtures used by the surgical linker tests.
+# Needs llvm-mc and ld.lld; any host architecture works.
+
+set -euxo pipefail
+
+cd "$(dirname "$0")"
+
+LLD=${LLD:-ld.lld}
+
+llvm-mc -triple=x86_64-linux-gnu -filetype=obj host.s -o host.o
+llvm-mc -triple=x86_64-linux-gnu -filetype=obj libapp.s -o libapp.o
+llvm-mc -triple=x86_64-linux-gnu -filetype=obj app.s -o app.o
+
+$LLD -m elf_x86_64 -shared -soname libapp.so libapp.o -o libapp.so
+$LLD -m elf_x86_64 -shared -soname dynhost.so.1 --eh-frame-hdr \
+    host.o libapp.so -o dynhost
+
+rm host.o libapp.o libapp.so
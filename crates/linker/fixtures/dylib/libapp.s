@@ -0,0 +1,13 @@
+// The dummy app library the host gets dynamically linked against.
+
+    .text
+
+    .globl roc__mainForHost_1_exposed_generic
+    .type roc__mainForHost_1_exposed_generic, @function
+roc__mainForHost_1_exposed_generic:
+    ret
+
+    .globl roc__mainForHost_size
+    .type roc__mainForHost_size, @function
+roc__mainForHost_size:
+    ret
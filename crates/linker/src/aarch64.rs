@@ -0,0 +1,256 @@
+//! Decoding and patching the few AArch64 instructions the surgical linker has to touch.
+//!
+//! AArch64 instructions are always 4 bytes, and relocations patch an immediate
+//! inside the instruction instead of a whole word like on x86_64.
+
+use object::elf;
+use roc_error_macros::internal_error;
+
+/// `b label`
+const B: u32 = 0x1400_0000;
+/// `bl label`
+const BL: u32 = 0x9400_0000;
+const BRANCH_OPCODE_MASK: u32 = 0xFC00_0000;
+const IMM26_MASK: u32 = 0x03FF_FFFF;
+
+/// `add xd, xn, #imm`
+const ADD_X_IMM: u32 = 0x9100_0000;
+/// `ldr xt, [xn, #imm]`
+const LDR_X_IMM: u32 = 0xF940_0000;
+const LOAD_STORE_OPCODE_MASK: u32 = 0xFFC0_0000;
+const IMM12_MASK: u32 = 0xFFF << 10;
+const REGISTERS_MASK: u32 = 0x3FF;
+
+/// `adrp xd, label`
+const ADRP: u32 = 0x9000_0000;
+const ADRP_OPCODE_MASK: u32 = 0x9F00_0000;
+
+pub(crate) const NOP: u32 = 0xD503_201F;
+
+pub(crate) fn read(bytes: &[u8], offset: usize) -> u32 {
+    let mut inst = [0; 4];
+    inst.copy_from_slice(&bytes[offset..offset + 4]);
+
+    u32::from_le_bytes(inst)
+}
+
+pub(crate) fn write(bytes: &mut [u8], offset: usize, inst: u32) {
+    bytes[offset..offset + 4].copy_from_slice(&inst.to_le_bytes());
+}
+
+/// If `inst` is a `b` or `bl`, returns the address it branches to when placed at `address`.
+pub(crate) fn branch_target(inst: u32, address: u64) -> Option<u64> {
+    match inst & BRANCH_OPCODE_MASK {
+        B | BL => {
+            // Sign extend the 26 bit immediate, and scale it from instructions to bytes.
+            let offset = (((inst & IMM26_MASK) << 6) as i32 >> 4) as i64;
+
+            Some(address.wrapping_add(offset as u64))
+        }
+        _ => None,
+    }
+}
+
+/// A `b` from `address` to `target`
+pub(crate) fn branch(address: u64, target: u64) -> u32 {
+    B | branch_imm26(address, target)
+}
+
+/// Points the `b` or `bl` at `address` to `target`
+pub(crate) fn set_branch_target(inst: u32, address: u64, target: u64) -> u32 {
+    if branch_target(inst, address).is_none() {
+        internal_error!(
+            "Expected a branch instruction at {:+x}, found {:#x}",
+            address,
+            inst
+        );
+    }
+
+    (inst & BRANCH_OPCODE_MASK) | branch_imm26(address, target)
+}
+
+fn branch_imm26(address: u64, target: u64) -> u32 {
+    let offset = target as i64 - address as i64;
+
+    // Branches can reach 128MiB either way.
+    if offset % 4 != 0 || !(-(1 << 27)..(1 << 27)).contains(&offset) {
+        internal_error!(
+            "Cannot branch from {:+x} to {:+x}, it is out of range",
+            address,
+            target
+        );
+    }
+
+    (offset >> 2) as u32 & IMM26_MASK
+}
+
+/// Points the `adrp` at `address` to the 4KiB page containing `target`
+fn set_adrp_target(inst: u32, address: u64, target: u64) -> u32 {
+    if inst & ADRP_OPCODE_MASK != ADRP {
+        internal_error!(
+            "Expected an adrp instruction at {:+x}, found {:#x}",
+            address,
+            inst
+        );
+    }
+
+    let pages = (target as i64 >> 12) - (address as i64 >> 12);
+
+    // adrp can reach 4GiB either way.
+    if !(-(1 << 20)..(1 << 20)).contains(&pages) {
+        internal_error!(
+            "Cannot address {:+x} from {:+x}, it is out of range",
+            target,
+            address
+        );
+    }
+
+    let imm21 = pages as u32 & 0x1F_FFFF;
+    let immlo = imm21 & 0b11;
+    let immhi = imm21 >> 2;
+
+    (inst & (ADRP_OPCODE_MASK | 0x1F)) | (immlo << 29) | (immhi << 5)
+}
+
+/// Sets the unsigned 12 bit immediate of an `add` or a load/store, where loads and stores
+/// scale it by the size of the access.
+fn set_lo12(inst: u32, address: u64, target: u64, scale: u32) -> u32 {
+    let lo12 = (target & 0xFFF) as u32;
+
+    if lo12 % (1 << scale) != 0 {
+        internal_error!(
+            "The access at {:+x} to {:+x} is misaligned for its size",
+            address,
+            target
+        );
+    }
+
+    (inst & !IMM12_MASK) | ((lo12 >> scale) << 10)
+}
+
+/// Applies an ELF relocation of type `r_type` to `inst`, which is placed at `address`,
+/// where `target` is the address of the relocation's symbol plus its addend.
+///
+/// The app doesn't get a GOT of its own once it's linked into the host, so GOT-relative
+/// relocations get relaxed to refer to their symbol directly, the same way linkers do for
+/// symbols they know can't be preempted.
+pub(crate) fn relocate(r_type: u32, inst: u32, address: u64, target: u64) -> u32 {
+    match r_type {
+        elf::R_AARCH64_CALL26 | elf::R_AARCH64_JUMP26 => set_branch_target(inst, address, target),
+        elf::R_AARCH64_ADR_PREL_PG_HI21 | elf::R_AARCH64_ADR_GOT_PAGE => {
+            set_adrp_target(inst, address, target)
+        }
+        elf::R_AARCH64_ADD_ABS_LO12_NC | elf::R_AARCH64_LDST8_ABS_LO12_NC => {
+            set_lo12(inst, address, target, 0)
+        }
+        elf::R_AARCH64_LDST16_ABS_LO12_NC => set_lo12(inst, address, target, 1),
+        elf::R_AARCH64_LDST32_ABS_LO12_NC => set_lo12(inst, address, target, 2),
+        elf::R_AARCH64_LDST64_ABS_LO12_NC => set_lo12(inst, address, target, 3),
+        elf::R_AARCH64_LDST128_ABS_LO12_NC => set_lo12(inst, address, target, 4),
+        elf::R_AARCH64_LD64_GOT_LO12_NC => {
+            // Turn `ldr xt, [xn, :got_lo12:sym]` into `add xt, xn, :lo12:sym`.
+            if inst & LOAD_STORE_OPCODE_MASK != LDR_X_IMM {
+                internal_error!(
+                    "Expected an ldr instruction at {:+x}, found {:#x}",
+                    address,
+                    inst
+                );
+            }
+
+            set_lo12(ADD_X_IMM | (inst & REGISTERS_MASK), address, target, 0)
+        }
+        _ => {
+            internal_error!("AArch64 relocation type not yet supported: {}", r_type);
+        }
+    }
+}
+
+/// The address of the 4KiB page the `adrp` at `address` refers to
+#[cfg(test)]
+pub(crate) fn adrp_page(adrp: u32, address: u64) -> u64 {
+    assert_eq!(adrp & ADRP_OPCODE_MASK, ADRP, "{:#x} is not an adrp", adrp);
+
+    let immlo = (adrp >> 29) & 0b11;
+    let immhi = (adrp >> 5) & 0x7_FFFF;
+    // Sign extend the 21 bit immediate.
+    let pages = ((((immhi << 2) | immlo) << 11) as i32 >> 11) as i64;
+
+    (((address as i64 >> 12) + pages) << 12) as u64
+}
+
+/// The unsigned 12 bit immediate of an `add` or a load/store, before any scaling
+#[cfg(test)]
+pub(crate) fn imm12(inst: u32) -> u64 {
+    ((inst & IMM12_MASK) >> 10) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branches() {
+        // bl 0x10378, from 0x10368
+        assert_eq!(branch_target(0x9400_0004, 0x10368), Some(0x10378));
+        // b 0x103d0, from 0x10390
+        assert_eq!(branch_target(0x1400_0010, 0x10390), Some(0x103d0));
+        // ret
+        assert_eq!(branch_target(0xD65F_03C0, 0x10390), None);
+
+        let inst = set_branch_target(0x9400_0004, 0x1000, 0x800);
+        assert_eq!(inst & BRANCH_OPCODE_MASK, BL);
+        assert_eq!(branch_target(inst, 0x1000), Some(0x800));
+
+        assert_eq!(
+            branch_target(branch(0x2000, 0x40_0000), 0x2000),
+            Some(0x40_0000)
+        );
+    }
+
+    #[test]
+    fn adrp_add() {
+        let address = 0x41_2344;
+        let target = 0x40_1ABC;
+
+        // adrp x3, 0; add x3, x3, #0
+        let adrp = relocate(
+            elf::R_AARCH64_ADR_PREL_PG_HI21,
+            0x9000_0003,
+            address,
+            target,
+        );
+        let add = relocate(
+            elf::R_AARCH64_ADD_ABS_LO12_NC,
+            0x9100_0063,
+            address + 4,
+            target,
+        );
+
+        assert_eq!(adrp & 0x1F, 3);
+        assert_eq!(add & REGISTERS_MASK, 3 << 5 | 3);
+        assert_eq!(adrp_page(adrp, address) + imm12(add), target);
+    }
+
+    #[test]
+    fn got_load_becomes_add() {
+        // ldr x2, [x2]
+        let add = relocate(elf::R_AARCH64_LD64_GOT_LO12_NC, 0xF940_0042, 0x1000, 0x2468);
+
+        // add x2, x2, #0x468
+        assert_eq!(add, 0x9111_A042);
+    }
+
+    #[test]
+    fn scaled_load() {
+        // ldr x1, [x1]
+        let ldr = relocate(
+            elf::R_AARCH64_LDST64_ABS_LO12_NC,
+            0xF940_0021,
+            0x1000,
+            0x3010,
+        );
+
+        // ldr x1, [x1, #0x10]
+        assert_eq!(ldr, 0xF940_0821);
+    }
+}
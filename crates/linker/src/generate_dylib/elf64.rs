@@ -1,8 +1,18 @@
 use object::{elf, Endianness};
+use target_lexicon::{Architecture, Triple};
 
-pub fn create_dylib_elf64(custom_names: &[String]) -> object::read::Result<Vec<u8>> {
+pub fn create_dylib_elf64(
+    target: &Triple,
+    custom_names: &[String],
+) -> object::read::Result<Vec<u8>> {
     let endian = Endianness::Little;
 
+    let e_machine = match target.architecture {
+        Architecture::X86_64 => elf::EM_X86_64,
+        Architecture::Aarch64(_) => elf::EM_AARCH64,
+        other => unimplemented!("elf dylib creation for {:?}", other),
+    };
+
     let mut out_data = Vec::new();
     let mut writer = object::write::elf::Writer::new(endian, true, &mut out_data);
 
@@ -54,7 +64,7 @@ pub fn create_dylib_elf64(custom_names: &[String]) -> object::read::Result<Vec<u
             os_abi: 0,
             abi_version: 0,
             e_type: 3,
-            e_machine: 62,
+            e_machine,
             e_entry: 0x1000,
             e_flags: 0,
         })
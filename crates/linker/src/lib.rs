@@ -4,10 +4,10 @@ use memmap2::{Mmap, MmapMut};
 use object::{elf, endian, macho};
 use object::{
     CompressedFileRange, CompressionFormat, LittleEndian, NativeEndian, Object, ObjectSection,
-    ObjectSymbol, RelocationKind, RelocationTarget, Section, SectionIndex, SectionKind, Symbol,
-    SymbolIndex, SymbolSection,
+    ObjectSymbol, RelocationEncoding, RelocationKind, RelocationTarget, Section, SectionIndex,
+    SectionKind, Symbol, SymbolIndex, SymbolSection,
 };
-use roc_build::link::{rebuild_host, LinkType};
+use roc_build::link::{rebuild_dylib_host, rebuild_host, LinkType};
 use roc_collections::all::MutMap;
 use roc_error_macros::{internal_error, user_error};
 use roc_mono::ir::OptLevel;
@@ -18,10 +18,11 @@ use std::fs::{self, File};
 use std::io::{BufReader, BufWriter};
 use std::mem;
 use std::os::raw::c_char;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 
+mod aarch64;
 mod generate_dylib;
 mod metadata;
 mod pe;
@@ -33,11 +34,69 @@ const MIN_SECTION_ALIGNMENT: usize = 0x40;
 const PLT_ADDRESS_OFFSET: u64 = 0x10;
 const STUB_ADDRESS_OFFSET: u64 = 0x06;
 
+/// The parts of surgically linking an ELF executable that depend on its architecture
+struct ElfArch {
+    machine: u16,
+    /// The PLT starts with a header, and then has an entry for each function
+    plt_header_size: u64,
+    plt_entry_size: u64,
+    jump_slot: u32,
+    glob_dat: u32,
+    relative: u32,
+    /// Preprocessing shifts everything after the program headers by a multiple of this.
+    /// Code that addresses things relative to itself only keeps working if the shift
+    /// doesn't change which page anything is in, relative to the code.
+    shift_alignment: u64,
+}
+
+const X86_64_ELF: ElfArch = ElfArch {
+    machine: elf::EM_X86_64,
+    plt_header_size: PLT_ADDRESS_OFFSET,
+    plt_entry_size: PLT_ADDRESS_OFFSET,
+    jump_slot: elf::R_X86_64_JUMP_SLOT,
+    glob_dat: elf::R_X86_64_GLOB_DAT,
+    relative: elf::R_X86_64_RELATIVE,
+    shift_alignment: MIN_SECTION_ALIGNMENT as u64,
+};
+
+const AARCH64_ELF: ElfArch = ElfArch {
+    machine: elf::EM_AARCH64,
+    plt_header_size: 0x20,
+    plt_entry_size: 0x10,
+    jump_slot: elf::R_AARCH64_JUMP_SLOT,
+    glob_dat: elf::R_AARCH64_GLOB_DAT,
+    relative: elf::R_AARCH64_RELATIVE,
+    // adrp addresses 4KiB pages
+    shift_alignment: 0x1000,
+};
+
+impl ElfArch {
+    fn from_machine(machine: u16) -> &'static Self {
+        match machine {
+            elf::EM_X86_64 => &X86_64_ELF,
+            elf::EM_AARCH64 => &AARCH64_ELF,
+            _ => {
+                internal_error!(
+                    "Surgical linking does not support ELF machine type {}",
+                    machine
+                );
+            }
+        }
+    }
+
+    fn from_header(exec_data: &[u8]) -> &'static Self {
+        let exec_header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(exec_data, 0);
+
+        Self::from_machine(exec_header.e_machine.get(NativeEndian))
+    }
+}
+
 struct ElfDynamicDeps {
     got_app_syms: Vec<(String, usize)>,
     got_sections: Vec<(usize, usize)>,
     dynamic_lib_count: usize,
     shared_lib_index: usize,
+    soname_index: Option<usize>,
 }
 
 // struct MachoDynamicDeps {
@@ -52,10 +111,11 @@ fn report_timing(label: &str, duration: Duration) {
 }
 
 pub fn supported(link_type: LinkType, target: &Triple) -> bool {
-    if let LinkType::Executable = link_type {
+    if let LinkType::Executable | LinkType::Dylib = link_type {
         match target {
             Triple {
-                architecture: target_lexicon::Architecture::X86_64,
+                architecture:
+                    target_lexicon::Architecture::X86_64 | target_lexicon::Architecture::Aarch64(_),
                 operating_system: target_lexicon::OperatingSystem::Linux,
                 binary_format: target_lexicon::BinaryFormat::Elf,
                 ..
@@ -90,6 +150,7 @@ pub fn build_and_preprocess_host(
     preprocessed_host_path: &Path,
     exposed_to_host: Vec<String>,
     exported_closure_types: Vec<String>,
+    link_type: LinkType,
 ) {
     let dummy_lib = if let target_lexicon::OperatingSystem::Windows = target.operating_system {
         host_input_path.with_file_name("libapp.obj")
@@ -98,9 +159,14 @@ pub fn build_and_preprocess_host(
     };
 
     generate_dynamic_lib(target, exposed_to_host, exported_closure_types, &dummy_lib);
-    rebuild_host(opt_level, target, host_input_path, Some(&dummy_lib));
-    let dynhost = host_input_path.with_file_name("dynhost");
-    let metadata = host_input_path.with_file_name("metadata");
+    let dynhost = match link_type {
+        LinkType::Dylib => rebuild_dylib_host(opt_level, target, host_input_path, &dummy_lib),
+        _ => {
+            rebuild_host(opt_level, target, host_input_path, Some(&dummy_lib));
+            host_input_path.with_file_name("dynhost")
+        }
+    };
+    let metadata = metadata_path(host_input_path);
     // let prehost = host_input_path.with_file_name("preprocessedhost");
 
     preprocess(
@@ -120,7 +186,7 @@ pub fn link_preprocessed_host(
     roc_app_obj: &Path,
     binary_path: &Path,
 ) {
-    let metadata = host_input_path.with_file_name("metadata");
+    let metadata = metadata_path(host_input_path);
     surgery(
         roc_app_obj.to_str().unwrap(),
         metadata.to_str().unwrap(),
@@ -131,6 +197,11 @@ pub fn link_preprocessed_host(
     )
 }
 
+/// Where preprocessing the host at `host_input_path` leaves the metadata surgery needs
+pub fn metadata_path(host_input_path: &Path) -> PathBuf {
+    host_input_path.with_file_name("metadata")
+}
+
 fn generate_dynamic_lib(
     target: &Triple,
     exposed_to_host: Vec<String>,
@@ -334,11 +405,17 @@ impl<'a> Surgeries<'a> {
         }
 
         for text_section in text_sections {
-            self.append_text_section(object_bytes, &text_section, verbose)
+            self.append_text_section(object_bytes, &text_section, object.architecture(), verbose)
         }
     }
 
-    fn append_text_section(&mut self, object_bytes: &[u8], sec: &Section, verbose: bool) {
+    fn append_text_section(
+        &mut self,
+        object_bytes: &[u8],
+        sec: &Section,
+        architecture: object::Architecture,
+        verbose: bool,
+    ) {
         let (file_offset, compressed) = match sec.compressed_file_range() {
             Ok(CompressedFileRange {
                 format: CompressionFormat::None,
@@ -361,6 +438,13 @@ impl<'a> Surgeries<'a> {
                 internal_error!("Failed to load text section, {:+x?}: {}", sec, err);
             }
         };
+
+        if let object::Architecture::Aarch64 = architecture {
+            self.append_aarch64_branches(&data, sec, file_offset, compressed, verbose);
+
+            return;
+        }
+
         let mut decoder = Decoder::with_ip(64, &data, sec.address(), DecoderOptions::NONE);
         let mut inst = Instruction::default();
 
@@ -452,6 +536,62 @@ impl<'a> Surgeries<'a> {
             }
         }
     }
+
+    fn append_aarch64_branches(
+        &mut self,
+        data: &[u8],
+        sec: &Section,
+        file_offset: u64,
+        compressed: bool,
+        verbose: bool,
+    ) {
+        // Every instruction is 4 bytes, so there's nothing to decode beyond the branches themselves.
+        // Calls through a register get their function pointer from the GOT, which gets
+        // dealt with when preprocessing the dynamic relocations.
+        for offset in (0..data.len() / 4).map(|i| i * 4) {
+            let inst = aarch64::read(data, offset);
+            let address = sec.address() + offset as u64;
+
+            let target = match aarch64::branch_target(inst, address) {
+                Some(target) => target,
+                None => continue,
+            };
+
+            if let Some(func_name) = self.app_func_addresses.get(&target) {
+                if compressed {
+                    internal_error!(
+                        "Surgical linking does not work with compressed text sections: {:+x?}",
+                        sec
+                    );
+                }
+
+                if verbose {
+                    println!(
+                        "Found branch from {:+x} to {:+x}({})",
+                        address, target, func_name
+                    );
+                }
+
+                // Unlike on x86_64, the branch's offset is relative to the branch itself,
+                // and gets encoded in the low 26 bits of the instruction.
+                let offset = file_offset + offset as u64;
+                if verbose {
+                    println!(
+                        "\tNeed to surgically replace the branch at file offset {:+x}",
+                        offset
+                    );
+                }
+                self.surgeries
+                    .get_mut(*func_name)
+                    .unwrap()
+                    .push(metadata::SurgeryEntry {
+                        file_offset: offset,
+                        virtual_offset: VirtualOffset::Relative(address),
+                        size: 4,
+                    });
+            }
+        }
+    }
 }
 
 /// Constructs a `metadata::Metadata` from a host executable binary, and writes it to disk
@@ -537,6 +677,7 @@ pub fn preprocess(
 
     match target.binary_format {
         target_lexicon::BinaryFormat::Elf => {
+            let elf_arch = ElfArch::from_header(exec_data);
             let plt_relocs = (match exec_obj.dynamic_relocations() {
                 Some(relocs) => relocs,
                 None => {
@@ -544,7 +685,7 @@ pub fn preprocess(
                 }
             })
             .filter_map(|(_, reloc)| {
-                if let RelocationKind::Elf(7) = reloc.kind() {
+                if reloc.kind() == RelocationKind::Elf(elf_arch.jump_slot) {
                     Some(reloc)
                 } else {
                     None
@@ -553,8 +694,10 @@ pub fn preprocess(
             for (i, reloc) in plt_relocs.enumerate() {
                 for symbol in app_syms.iter() {
                     if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
-                        let func_address = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_address;
-                        let func_offset = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_offset;
+                        let plt_entry_offset =
+                            elf_arch.plt_header_size + i as u64 * elf_arch.plt_entry_size;
+                        let func_address = plt_address + plt_entry_offset;
+                        let func_offset = plt_offset + plt_entry_offset;
                         app_func_addresses.insert(func_address, symbol.name().unwrap());
                         md.plt_addresses.insert(
                             symbol.name().unwrap().to_string(),
@@ -747,6 +890,7 @@ pub fn preprocess(
                     got_sections,
                     dynamic_lib_count,
                     shared_lib_index,
+                    soname_index,
                 } = scan_elf_dynamic_deps(
                     &exec_obj, &mut md, &app_syms, shared_lib, exec_data, verbose,
                 );
@@ -764,6 +908,7 @@ pub fn preprocess(
                     &got_sections,
                     dynamic_lib_count,
                     shared_lib_index,
+                    soname_index,
                     verbose,
                 )
             }
@@ -1465,6 +1610,7 @@ fn gen_elf_le(
     got_sections: &[(usize, usize)],
     dynamic_lib_count: usize,
     shared_lib_index: usize,
+    soname_index: Option<usize>,
     verbose: bool,
 ) -> (MmapMut, File) {
     let exec_header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(exec_data, 0);
@@ -1474,6 +1620,7 @@ fn gen_elf_le(
     let sh_offset = exec_header.e_shoff.get(NativeEndian);
     let sh_ent_size = exec_header.e_shentsize.get(NativeEndian);
     let sh_num = exec_header.e_shnum.get(NativeEndian);
+    let elf_arch = ElfArch::from_header(exec_data);
 
     if verbose {
         println!();
@@ -1489,7 +1636,7 @@ fn gen_elf_le(
     let added_header_count = 2;
     md.added_byte_count = ph_ent_size as u64 * added_header_count;
     md.added_byte_count = md.added_byte_count
-        + (MIN_SECTION_ALIGNMENT as u64 - md.added_byte_count % MIN_SECTION_ALIGNMENT as u64);
+        + (elf_arch.shift_alignment - md.added_byte_count % elf_arch.shift_alignment);
     let ph_end = ph_offset as usize + ph_num as usize * ph_ent_size as usize;
     let physical_shift_start = ph_end as u64;
 
@@ -1587,6 +1734,7 @@ fn gen_elf_le(
 
     let mut rel_sections: Vec<(u64, u64)> = vec![];
     let mut rela_sections: Vec<(u64, u64)> = vec![];
+    let mut dynsym_section: Option<(u64, u64)> = None;
     for sh in section_headers.iter_mut() {
         let sh_offset = sh.sh_offset.get(NativeEndian);
         let sh_addr = sh.sh_addr.get(NativeEndian);
@@ -1603,6 +1751,8 @@ fn gen_elf_le(
             rel_sections.push((sh_offset, sh.sh_size.get(NativeEndian)));
         } else if sh_type == elf::SHT_RELA {
             rela_sections.push((sh_offset, sh.sh_size.get(NativeEndian)));
+        } else if sh_type == elf::SHT_DYNSYM {
+            dynsym_section = Some((sh_offset, sh.sh_size.get(NativeEndian)));
         }
     }
 
@@ -1644,7 +1794,7 @@ fn gen_elf_le(
                 rel.r_offset = endian::U64::new(LittleEndian, r_offset + md.added_byte_count);
                 // Deal with potential adjusts to absolute jumps.
                 // TODO: Verify other relocation types.
-                if rel.r_type(LittleEndian, false) == elf::R_X86_64_RELATIVE {
+                if rel.r_type(LittleEndian, false) == elf_arch.relative {
                     let r_addend = rel.r_addend.get(LittleEndian);
                     rel.r_addend
                         .set(LittleEndian, r_addend + md.added_byte_count as i64);
@@ -1652,11 +1802,11 @@ fn gen_elf_le(
             }
             // If the relocation goes to a roc function, we need to surgically link it and change it to relative.
             let r_type = rel.r_type(NativeEndian, false);
-            if r_type == elf::R_X86_64_GLOB_DAT {
+            if r_type == elf_arch.glob_dat {
                 let r_sym = rel.r_sym(NativeEndian, false);
                 for (name, index) in got_app_syms.iter() {
                     if *index as u32 == r_sym {
-                        rel.set_r_info(LittleEndian, false, 0, elf::R_X86_64_RELATIVE);
+                        rel.set_r_info(LittleEndian, false, 0, elf_arch.relative);
                         let addend_addr = sec_offset as usize
                             + i * mem::size_of::<elf::Rela64<LittleEndian>>()
                             // This 16 skips the first 2 fields and gets to the addend field.
@@ -1671,6 +1821,18 @@ fn gen_elf_le(
                             });
                     }
                 }
+            } else if r_type == elf_arch.jump_slot {
+                // The PLT entries of app functions get replaced with direct jumps, so their slots
+                // are never read. Pointing the relocation at the null symbol keeps the loader from
+                // looking up the app function, which fails when it binds eagerly.
+                let r_sym = rel.r_sym(NativeEndian, false);
+                if md
+                    .dynamic_symbol_indices
+                    .values()
+                    .any(|index| *index as u32 == r_sym)
+                {
+                    rel.set_r_info(LittleEndian, false, 0, elf_arch.jump_slot);
+                }
             }
         }
     }
@@ -1741,6 +1903,22 @@ fn gen_elf_le(
         }
     }
 
+    // Shared library hosts export their own functions through the dynamic symbol table as well.
+    if let Some((dynsym_offset, dynsym_size)) = dynsym_section {
+        let symbols = load_structs_inplace_mut::<elf::Sym64<LittleEndian>>(
+            &mut out_mmap,
+            (dynsym_offset + md.added_byte_count) as usize,
+            dynsym_size as usize / mem::size_of::<elf::Sym64<LittleEndian>>(),
+        );
+
+        for sym in symbols {
+            let addr = sym.st_value.get(NativeEndian);
+            if virtual_shift_start <= addr {
+                sym.st_value = endian::U64::new(LittleEndian, addr + md.added_byte_count);
+            }
+        }
+    }
+
     // Update all data in the global offset table.
     for (offset, size) in got_sections {
         let global_offsets = load_structs_inplace_mut::<endian::U64<LittleEndian>>(
@@ -1759,13 +1937,20 @@ fn gen_elf_le(
     // TODO: look into shifting all of the debug info and eh_frames.
 
     // Delete shared library from the dynamic table.
+    // A shared library host also loses its soname, since it's about to become the app.
+    let mut deleted_indices = vec![shared_lib_index];
+    deleted_indices.extend(soname_index);
+    deleted_indices.sort_unstable();
+
     let out_ptr = out_mmap.as_mut_ptr();
-    unsafe {
-        std::ptr::copy(
-            out_ptr.add(dyn_offset as usize + 16 * (shared_lib_index + 1)),
-            out_ptr.add(dyn_offset as usize + 16 * shared_lib_index),
-            16 * (dynamic_lib_count - shared_lib_index),
-        );
+    for index in deleted_indices.into_iter().rev() {
+        unsafe {
+            std::ptr::copy(
+                out_ptr.add(dyn_offset as usize + 16 * (index + 1)),
+                out_ptr.add(dyn_offset as usize + 16 * index),
+                16 * (dynamic_lib_count - index),
+            );
+        }
     }
 
     // Update main elf header for extra data.
@@ -1839,6 +2024,7 @@ fn scan_elf_dynamic_deps(
 
     let mut dyn_lib_index = 0;
     let mut shared_lib_index = None;
+    let mut soname_index = None;
     loop {
         let dyn_tag = u64::from_le_bytes(
             <[u8; 8]>::try_from(
@@ -1867,6 +2053,8 @@ fn scan_elf_dynamic_deps(
                     );
                 }
             }
+        } else if dyn_tag == elf::DT_SONAME as u64 {
+            soname_index = Some(dyn_lib_index);
         }
 
         dyn_lib_index += 1;
@@ -1935,6 +2123,7 @@ fn scan_elf_dynamic_deps(
         }
     }
 
+    let glob_dat = ElfArch::from_header(exec_data).glob_dat;
     let got_app_syms: Vec<(String, usize)> = (match exec_obj.dynamic_relocations() {
         Some(relocs) => relocs,
         None => {
@@ -1943,7 +2132,7 @@ fn scan_elf_dynamic_deps(
         }
     })
     .filter_map(|(_, reloc)| {
-        if let RelocationKind::Elf(6) = reloc.kind() {
+        if reloc.kind() == RelocationKind::Elf(glob_dat) {
             for symbol in app_syms.iter() {
                 if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
                     return Some((symbol.name().unwrap().to_string(), symbol.index().0));
@@ -1959,6 +2148,7 @@ fn scan_elf_dynamic_deps(
         got_sections,
         dynamic_lib_count,
         shared_lib_index,
+        soname_index,
     }
 }
 
@@ -2521,6 +2711,7 @@ pub fn surgery_elf(
         internal_error!("Only 64bit little endian elf currently supported for surgery");
     }
     let exec_header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(exec_mmap, 0);
+    let elf_arch = ElfArch::from_header(exec_mmap);
 
     let ph_offset = exec_header.e_phoff.get(NativeEndian);
     let ph_ent_size = exec_header.e_phentsize.get(NativeEndian);
@@ -2713,6 +2904,34 @@ pub fn surgery_elf(
                     if let Some(target_offset) = target_offset {
                         let virt_base = section_virtual_offset as usize + rel.0 as usize;
                         let base = section_offset as usize + rel.0 as usize;
+
+                        // AArch64 relocations patch an immediate inside the instruction instead.
+                        let aarch64_r_type = match (rel.1.kind(), rel.1.encoding()) {
+                            (RelocationKind::PltRelative, RelocationEncoding::AArch64Call) => {
+                                Some(elf::R_AARCH64_CALL26)
+                            }
+                            (RelocationKind::Elf(r_type), _)
+                                if elf_arch.machine == elf::EM_AARCH64 =>
+                            {
+                                Some(r_type)
+                            }
+                            _ => None,
+                        };
+                        if let Some(r_type) = aarch64_r_type {
+                            let target = (target_offset + rel.1.addend()) as u64;
+                            if verbose {
+                                println!(
+                                    "\t\tRelocation base location: {:+x} (virt: {:+x})",
+                                    base, virt_base
+                                );
+                                println!("\t\tFinal relocation target: {:+x}", target);
+                            }
+                            let inst = aarch64::read(exec_mmap, base);
+                            let inst = aarch64::relocate(r_type, inst, virt_base as u64, target);
+                            aarch64::write(exec_mmap, base, inst);
+                            continue;
+                        }
+
                         let target: i64 = match rel.1.kind() {
                             RelocationKind::Relative | RelocationKind::PltRelative => {
                                 target_offset - virt_base as i64 + rel.1.addend()
@@ -2882,6 +3101,19 @@ pub fn surgery_elf(
                 VirtualOffset::Absolute => 0,
             };
             match s.size {
+                4 if elf_arch.machine == elf::EM_AARCH64 => {
+                    let file_offset = (s.file_offset + md.added_byte_count) as usize;
+                    let inst = aarch64::read(exec_mmap, file_offset);
+                    let inst = aarch64::set_branch_target(
+                        inst,
+                        surgery_virt_offset as u64,
+                        func_virt_offset,
+                    );
+                    if verbose {
+                        println!("\tTarget Branch: {:+x}", func_virt_offset);
+                    }
+                    aarch64::write(exec_mmap, file_offset, inst);
+                }
                 4 => {
                     let target = (func_virt_offset as i64 - surgery_virt_offset) as i32;
                     if verbose {
@@ -2911,18 +3143,33 @@ pub fn surgery_elf(
         if let Some((plt_off, plt_vaddr)) = md.plt_addresses.get(func_name) {
             let plt_off = (*plt_off + md.added_byte_count) as usize;
             let plt_vaddr = *plt_vaddr + md.added_byte_count;
-            let jmp_inst_len = 5;
-            let target =
-                (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
-            if verbose {
-                println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
-                println!("\tTarget Jump: {:+x}", target);
-            }
-            let data = target.to_le_bytes();
-            exec_mmap[plt_off] = 0xE9;
-            exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
-            for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
-                exec_mmap[plt_off + i] = 0x90;
+            if elf_arch.machine == elf::EM_AARCH64 {
+                if verbose {
+                    println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
+                    println!("\tTarget Branch: {:+x}", func_virt_offset);
+                }
+                aarch64::write(
+                    exec_mmap,
+                    plt_off,
+                    aarch64::branch(plt_vaddr, func_virt_offset),
+                );
+                for i in (4..elf_arch.plt_entry_size as usize).step_by(4) {
+                    aarch64::write(exec_mmap, plt_off + i, aarch64::NOP);
+                }
+            } else {
+                let jmp_inst_len = 5;
+                let target =
+                    (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
+                if verbose {
+                    println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
+                    println!("\tTarget Jump: {:+x}", target);
+                }
+                let data = target.to_le_bytes();
+                exec_mmap[plt_off] = 0xE9;
+                exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
+                for i in jmp_inst_len..elf_arch.plt_entry_size as usize {
+                    exec_mmap[plt_off + i] = 0x90;
+                }
             }
         }
 
@@ -2998,6 +3245,7 @@ fn load_structs_inplace_mut<T>(bytes: &mut [u8], offset: usize, count: usize) ->
 #[cfg(test)]
 mod tests {
     use super::*;
+    use object::ObjectSegment;
 
     const ELF64_DYNHOST: &[u8] = include_bytes!("../dynhost_benchmarks_elf64") as &[_];
 
@@ -3051,4 +3299,292 @@ mod tests {
             keys.as_slice()
         )
     }
+
+    const AARCH64_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/aarch64");
+
+    fn aarch64_linux() -> Triple {
+        "aarch64-unknown-linux-gnu".parse().unwrap()
+    }
+
+    /// Preprocesses the aarch64 fixture host into `dir`,
+    /// returning the paths of the metadata and the preprocessed host.
+    fn preprocess_aarch64_host(dir: &Path) -> (String, String) {
+        let metadata = dir.join("metadata").to_str().unwrap().to_string();
+        let preprocessed = dir.join("preprocessedhost").to_str().unwrap().to_string();
+
+        preprocess(
+            &aarch64_linux(),
+            &format!("{}/dynhost", AARCH64_FIXTURES),
+            &metadata,
+            &preprocessed,
+            Path::new("libapp.so"),
+            false,
+            false,
+        );
+
+        (metadata, preprocessed)
+    }
+
+    fn symbol_address<'a>(
+        mut symbols: impl Iterator<Item = object::Symbol<'a, 'a>>,
+        name: &str,
+    ) -> u64 {
+        symbols
+            .find(|sym| sym.name() == Ok(name))
+            .unwrap_or_else(|| panic!("{} is not defined", name))
+            .address()
+    }
+
+    /// Reads the `size` bytes loaded at `address`
+    fn read_loaded<'a>(object: &object::File<'a>, address: u64, size: u64) -> &'a [u8] {
+        object
+            .segments()
+            .find_map(|segment| segment.data_range(address, size).unwrap())
+            .unwrap_or_else(|| panic!("{:+x} is not loaded from the file", address))
+    }
+
+    #[test]
+    fn supported_aarch64_linux() {
+        assert!(supported(LinkType::Executable, &aarch64_linux()));
+        assert!(supported(LinkType::Dylib, &aarch64_linux()));
+        assert!(!supported(LinkType::None, &aarch64_linux()));
+    }
+
+    #[test]
+    fn preprocess_elf_aarch64() {
+        let dir = tempfile::tempdir().unwrap();
+        let (metadata, preprocessed) = preprocess_aarch64_host(dir.path());
+
+        let md: metadata::Metadata =
+            deserialize_from(BufReader::new(File::open(metadata).unwrap())).unwrap();
+
+        let host_data = fs::read(format!("{}/dynhost", AARCH64_FIXTURES)).unwrap();
+        let host = object::File::parse(&*host_data).unwrap();
+        let run = symbol_address(host.symbols(), "run");
+        let text = host.section_by_name(".text").unwrap();
+        let text_vaddr_offset = text.address() - text.file_range().unwrap().0;
+        let plt = host.section_by_name(".plt").unwrap();
+
+        // ADRP is page relative, so everything has to move by whole pages.
+        assert_eq!(md.added_byte_count % 0x1000, 0);
+
+        // The app's only PLT entry comes right after the 32 byte PLT header.
+        assert_eq!(
+            md.plt_addresses["roc__mainForHost_1_exposed_generic"],
+            (plt.file_range().unwrap().0 + 0x20, plt.address() + 0x20)
+        );
+
+        // The `bl` and the tail call `b` in `run`.
+        let branch = |address| metadata::SurgeryEntry {
+            file_offset: address - text_vaddr_offset,
+            virtual_offset: VirtualOffset::Relative(address),
+            size: 4,
+        };
+        assert_eq!(
+            md.surgeries["roc__mainForHost_1_exposed_generic"],
+            [branch(run + 4), branch(run + 0x18)]
+        );
+
+        // The GOT entry gets filled in by a relative relocation, whose addend gets patched.
+        let got_surgeries = &md.surgeries["roc__mainForHost_size"];
+        assert_eq!(got_surgeries.len(), 1);
+        assert_eq!(got_surgeries[0].virtual_offset, VirtualOffset::Absolute);
+        assert_eq!(got_surgeries[0].size, 8);
+
+        let preprocessed_data = fs::read(preprocessed).unwrap();
+        let preprocessed = object::File::parse(&*preprocessed_data).unwrap();
+
+        let kinds: Vec<_> = preprocessed
+            .dynamic_relocations()
+            .unwrap()
+            .map(|(_, reloc)| reloc.kind())
+            .collect();
+        assert!(
+            !kinds.contains(&RelocationKind::Elf(elf::R_AARCH64_GLOB_DAT)),
+            "{:?}",
+            kinds
+        );
+        assert_eq!(
+            kinds
+                .iter()
+                .filter(|kind| **kind == RelocationKind::Elf(elf::R_AARCH64_RELATIVE))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn surgery_elf_aarch64() {
+        let dir = tempfile::tempdir().unwrap();
+        let (metadata, preprocessed) = preprocess_aarch64_host(dir.path());
+
+        surgery(
+            &format!("{}/app.o", AARCH64_FIXTURES),
+            &metadata,
+            &preprocessed,
+            false,
+            false,
+            &aarch64_linux(),
+        );
+
+        let md: metadata::Metadata =
+            deserialize_from(BufReader::new(File::open(metadata).unwrap())).unwrap();
+
+        let data = fs::read(preprocessed).unwrap();
+        let object = object::File::parse(&*data).unwrap();
+        let inst = |address| aarch64::read(read_loaded(&object, address, 4), 0);
+
+        let main = symbol_address(
+            object.dynamic_symbols(),
+            "roc__mainForHost_1_exposed_generic",
+        );
+        let size = symbol_address(object.dynamic_symbols(), "roc__mainForHost_size");
+        let run = symbol_address(object.symbols(), "run");
+        let roc_alloc = symbol_address(object.symbols(), "roc_alloc");
+        let roc_panic = symbol_address(object.symbols(), "roc_panic");
+
+        // The host calls straight into the app.
+        assert_eq!(aarch64::branch_target(inst(run + 4), run + 4), Some(main));
+        assert_eq!(
+            aarch64::branch_target(inst(run + 0x18), run + 0x18),
+            Some(main)
+        );
+
+        // Anything that still goes through the PLT jumps to the app too.
+        let (_, plt) = md.plt_addresses["roc__mainForHost_1_exposed_generic"];
+        let plt = plt + md.added_byte_count;
+        assert_eq!(aarch64::branch_target(inst(plt), plt), Some(main));
+        for i in (4..0x10).step_by(4) {
+            assert_eq!(inst(plt + i), aarch64::NOP);
+        }
+
+        // The GOT entry now points at the app's data.
+        let addends: Vec<_> = object
+            .dynamic_relocations()
+            .unwrap()
+            .map(|(_, reloc)| reloc.addend() as u64)
+            .collect();
+        assert!(addends.contains(&size), "{:x?}", addends);
+        assert!(addends.contains(&roc_alloc), "{:x?}", addends);
+
+        // The app's PLT slot is never read, so the loader isn't asked to look the app up for it.
+        let jump_slots: Vec<_> = object
+            .dynamic_relocations()
+            .unwrap()
+            .map(|(_, reloc)| reloc)
+            .filter(|reloc| reloc.kind() == RelocationKind::Elf(elf::R_AARCH64_JUMP_SLOT))
+            .collect();
+        assert_eq!(jump_slots.len(), 1, "{:x?}", jump_slots);
+        assert!(
+            !matches!(jump_slots[0].target(), RelocationTarget::Symbol(_)),
+            "{:x?}",
+            jump_slots
+        );
+
+        // adrp/add of the app's string.
+        let hello = aarch64::adrp_page(inst(main + 4), main + 4) + aarch64::imm12(inst(main + 8));
+        assert_eq!(read_loaded(&object, hello, 14), b"Hello, World!\0");
+
+        // adrp/ldr of the 8 byte number right after it.
+        let number = aarch64::adrp_page(inst(main + 0xc), main + 0xc)
+            + 8 * aarch64::imm12(inst(main + 0x10));
+        assert_eq!(read_loaded(&object, number, 8), 42u64.to_le_bytes());
+
+        // Calls from the app into the host.
+        assert_eq!(
+            aarch64::branch_target(inst(main + 0x14), main + 0x14),
+            Some(roc_alloc)
+        );
+
+        // The GOT load of roc_panic turns into computing its address directly.
+        let panic =
+            aarch64::adrp_page(inst(main + 0x18), main + 0x18) + aarch64::imm12(inst(main + 0x1c));
+        assert_eq!(panic, roc_panic);
+
+        // A tail call within the app.
+        assert_eq!(
+            aarch64::branch_target(inst(main + 0x24), main + 0x24),
+            Some(size)
+        );
+    }
+
+    #[test]
+    fn surgery_elf_dylib() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/dylib");
+        let target: Triple = "x86_64-unknown-linux-gnu".parse().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let metadata = dir.path().join("metadata").to_str().unwrap().to_string();
+        let preprocessed = dir.path().join("app.so").to_str().unwrap().to_string();
+
+        preprocess(
+            &target,
+            &format!("{}/dynhost", fixtures),
+            &metadata,
+            &preprocessed,
+            Path::new("libapp.so"),
+            false,
+            false,
+        );
+        surgery(
+            &format!("{}/app.o", fixtures),
+            &metadata,
+            &preprocessed,
+            false,
+            false,
+            &target,
+        );
+
+        let data = fs::read(preprocessed).unwrap();
+        let object = object::File::parse(&*data).unwrap();
+
+        assert_eq!(object.kind(), object::ObjectKind::Dynamic);
+
+        // The library no longer depends on the dummy app, and no longer goes by the host's name.
+        let dynamic = object.section_by_name(".dynamic").unwrap();
+        let tags: Vec<_> = dynamic
+            .data()
+            .unwrap()
+            .chunks(16)
+            .map(|entry| u64::from_le_bytes(entry[..8].try_into().unwrap()) as u32)
+            .take_while(|tag| *tag != elf::DT_NULL)
+            .collect();
+        assert!(!tags.contains(&elf::DT_NEEDED), "{:x?}", tags);
+        assert!(!tags.contains(&elf::DT_SONAME), "{:x?}", tags);
+
+        let host_main = symbol_address(object.dynamic_symbols(), "host_main");
+        let main = symbol_address(
+            object.dynamic_symbols(),
+            "roc__mainForHost_1_exposed_generic",
+        );
+        let size = symbol_address(object.dynamic_symbols(), "roc__mainForHost_size");
+
+        // `call` right after the `push` in host_main
+        let call = read_loaded(&object, host_main + 1, 5);
+        assert_eq!(call[0], 0xE8);
+        let offset = i32::from_le_bytes(call[1..].try_into().unwrap());
+        assert_eq!((host_main + 6).wrapping_add(offset as u64), main);
+
+        // The GOT entry gets filled in with the address of the app's function, and the loader
+        // is never asked to look up an app function, since those aren't in the hash table.
+        let relocations: Vec<_> = object
+            .dynamic_relocations()
+            .unwrap()
+            .map(|(_, reloc)| reloc)
+            .collect();
+        assert!(
+            relocations
+                .iter()
+                .any(|reloc| reloc.addend() as u64 == size),
+            "{:x?}",
+            relocations
+        );
+        assert!(
+            relocations
+                .iter()
+                .all(|reloc| !matches!(reloc.target(), RelocationTarget::Symbol(_))),
+            "{:x?}",
+            relocations
+        );
+    }
 }
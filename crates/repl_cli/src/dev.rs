@@ -0,0 +1,468 @@
+//! Evaluating inputs with the dev backend. It generates machine code much faster than LLVM,
+//! and instead of linking a dylib for every input, its output gets relocated into memory
+//! and called right away. This only works on x86_64 Linux so far.
+
+use bumpalo::Bump;
+use libloading::Library;
+use object::{Object, ObjectSection, ObjectSymbol, RelocationKind, RelocationTarget, SectionKind};
+use roc_build::link::dev_object_to_dylib;
+use roc_collections::all::{MutMap, MutSet};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_mono::ir::{Proc, ProcLayout};
+use roc_mono::layout::{LayoutIds, STLayoutInterner};
+use roc_parse::ast::Expr;
+use roc_repl_eval::ReplApp;
+use roc_target::TargetInfo;
+use std::cell::RefCell;
+use std::rc::Rc;
+use target_lexicon::{Architecture, BinaryFormat, Triple};
+
+use crate::CliMemory;
+
+const PAGE_SIZE: usize = 4096;
+
+/// A `jmp [rip + 0]` followed by the address to jump to
+const STUB_SIZE: usize = 16;
+
+thread_local! {
+    /// The builtins, along with the `roc_alloc` family of functions they need. They are linked
+    /// into a dylib the first time an input gets evaluated, and every input is relocated against it.
+    static BUILTINS: RefCell<Option<Rc<Library>>> = RefCell::new(None);
+}
+
+pub(crate) fn is_supported(target: &Triple) -> bool {
+    matches!(target.architecture, Architecture::X86_64)
+        && matches!(target.binary_format, BinaryFormat::Elf)
+}
+
+/// Generates code for the procedures with the dev backend, and loads it into memory.
+/// Returns `None` if the dev backend doesn't support something they use yet.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mono_module_to_jit<'a>(
+    arena: &'a Bump,
+    target: &Triple,
+    module_id: ModuleId,
+    procedures: MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
+    interns: &Interns,
+    layout_interner: &STLayoutInterner<'a>,
+    main_fn_symbol: Symbol,
+    main_fn_layout: ProcLayout<'a>,
+) -> Option<(DevApp, &'a str)> {
+    let mut exposed_to_host = MutSet::default();
+    exposed_to_host.insert(main_fn_symbol);
+
+    let env = roc_gen_dev::Env {
+        arena,
+        layout_interner,
+        module_id,
+        exposed_to_host,
+        lazy_literals: true,
+        generate_allocators: false, // they are in the builtins dylib
+        runs_expects: false,
+    };
+
+    if !roc_gen_dev::procedures_supported(&env, interns, &procedures) {
+        return None;
+    }
+
+    let object_bytes = {
+        let mut interns = interns.clone();
+
+        roc_gen_dev::build_module(&env, &mut interns, target, procedures)
+            .write()
+            .expect("failed to build output object")
+    };
+
+    let builtins = builtins_lib(&env, interns, target);
+    let module = JitModule::load(&object_bytes, |name| lookup(&builtins, name))
+        .unwrap_or_else(|err| panic!("failed to load the dev backend's output: {}", err));
+
+    let main_fn_name = LayoutIds::default()
+        .get_toplevel(main_fn_symbol, &main_fn_layout)
+        .to_exposed_symbol_string(main_fn_symbol, interns);
+
+    let ret_bytes = main_fn_layout
+        .result
+        .stack_size(layout_interner, TargetInfo::from(target));
+
+    Some((
+        DevApp {
+            module,
+            _builtins: builtins,
+            ret_bytes: ret_bytes as usize,
+        },
+        arena.alloc_str(&main_fn_name),
+    ))
+}
+
+fn builtins_lib(env: &roc_gen_dev::Env, interns: &Interns, target: &Triple) -> Rc<Library> {
+    BUILTINS.with(|builtins| {
+        builtins
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                // no procedures, just roc_alloc, roc_realloc, roc_dealloc and roc_panic
+                let env = roc_gen_dev::Env {
+                    arena: env.arena,
+                    layout_interner: env.layout_interner,
+                    module_id: env.module_id,
+                    exposed_to_host: MutSet::default(),
+                    lazy_literals: true,
+                    generate_allocators: true,
+                    runs_expects: false,
+                };
+                let mut interns = interns.clone();
+                let object_bytes =
+                    roc_gen_dev::build_module(&env, &mut interns, target, MutMap::default())
+                        .write()
+                        .expect("failed to build output object");
+
+                let lib = dev_object_to_dylib(&object_bytes, target)
+                    .expect("failed to link the builtins for the dev backend");
+
+                Rc::new(lib)
+            })
+            .clone()
+    })
+}
+
+fn lookup(builtins: &Library, name: &str) -> Option<usize> {
+    // anything the builtins don't define, like memcpy, comes from libc
+    let this = libloading::os::unix::Library::this();
+
+    unsafe {
+        match builtins.get::<*const u8>(name.as_bytes()) {
+            Ok(symbol) => Some(*symbol as usize),
+            Err(_) => this
+                .get::<*const u8>(name.as_bytes())
+                .ok()
+                .map(|symbol| *symbol as usize),
+        }
+    }
+}
+
+pub(crate) struct DevApp {
+    module: JitModule,
+    // the loaded code calls into it
+    _builtins: Rc<Library>,
+    /// The size of what main actually returns. Structs are passed to `call_function_dynamic_size`
+    /// with room for LLVM's `RocCallResult` flag, which would change how they get returned.
+    ret_bytes: usize,
+}
+
+/// The struct that values of up to 16 bytes are returned in, in rax and rdx
+#[repr(C)]
+struct TwoWords(u64, u64);
+
+impl<'a> ReplApp<'a> for DevApp {
+    type Memory = CliMemory;
+
+    /// Run user code that returns a type with a `Builtin` layout
+    /// Size of the return value is statically determined from its Rust type
+    fn call_function<Return, F>(&mut self, main_fn_name: &str, mut transform: F) -> Expr<'a>
+    where
+        F: FnMut(&'a Self::Memory, Return) -> Expr<'a>,
+        Self::Memory: 'a,
+    {
+        // the dev backend doesn't wrap the result in a RocCallResult like LLVM does
+        let result = unsafe {
+            let main: extern "C" fn() -> Return = std::mem::transmute(self.address(main_fn_name));
+
+            main()
+        };
+
+        transform(&CliMemory, result)
+    }
+
+    /// Run user code that returns a struct or union, whose size is provided as an argument
+    fn call_function_dynamic_size<T, F>(
+        &mut self,
+        main_fn_name: &str,
+        ret_bytes: usize,
+        mut transform: F,
+    ) -> T
+    where
+        F: FnMut(&'a Self::Memory, usize) -> T,
+        Self::Memory: 'a,
+    {
+        let address = self.address(main_fn_name);
+        let mut output = vec![0u64; (ret_bytes.max(self.ret_bytes) + 7) / 8];
+        let ret_bytes = self.ret_bytes;
+
+        // like `return_complex_symbol` in the dev backend's x86_64 calling convention
+        unsafe {
+            if ret_bytes == 0 {
+                let main: extern "C" fn() = std::mem::transmute(address);
+
+                main();
+            } else if ret_bytes <= 8 {
+                let main: extern "C" fn() -> u64 = std::mem::transmute(address);
+
+                output[0] = main();
+            } else if ret_bytes <= 16 {
+                let main: extern "C" fn() -> TwoWords = std::mem::transmute(address);
+                let TwoWords(first, second) = main();
+
+                output[0] = first;
+                output[1] = second;
+            } else {
+                let main: extern "C" fn(*mut u64) = std::mem::transmute(address);
+
+                main(output.as_mut_ptr());
+            }
+        }
+
+        transform(&CliMemory, output.as_ptr() as usize)
+    }
+}
+
+impl DevApp {
+    fn address(&self, name: &str) -> *const u8 {
+        match self.module.symbols.get(name) {
+            Some(address) => *address as *const u8,
+            None => panic!("Unable to JIT compile `{}`", name),
+        }
+    }
+}
+
+/// An x86_64 ELF object that has been relocated into memory, so its code can be called
+struct JitModule {
+    memory: *mut u8,
+    len: usize,
+    symbols: MutMap<String, usize>,
+}
+
+impl Drop for JitModule {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.memory as *mut libc::c_void, self.len);
+        }
+    }
+}
+
+impl JitModule {
+    /// `resolve` gives the addresses of the symbols the object uses but doesn't define.
+    fn load(object_bytes: &[u8], resolve: impl Fn(&str) -> Option<usize>) -> Result<Self, String> {
+        let file = object::File::parse(object_bytes).map_err(|err| err.to_string())?;
+
+        let sections: Vec<_> = file
+            .sections()
+            .filter(|section| {
+                matches!(
+                    section.kind(),
+                    SectionKind::Text
+                        | SectionKind::Data
+                        | SectionKind::ReadOnlyData
+                        | SectionKind::ReadOnlyString
+                        | SectionKind::UninitializedData
+                )
+            })
+            .collect();
+
+        // Calls to functions outside the object go through a stub, since they can be further
+        // away than a 32-bit offset reaches. Accesses through the GOT get an entry in a table.
+        let mut stubs = MutMap::default();
+        let mut got_entries = MutMap::default();
+
+        for section in sections.iter() {
+            for (_, reloc) in section.relocations() {
+                if let RelocationTarget::Symbol(index) = reloc.target() {
+                    let symbol = file.symbol_by_index(index).map_err(|err| err.to_string())?;
+
+                    match reloc.kind() {
+                        RelocationKind::PltRelative if symbol.is_undefined() => {
+                            let count = stubs.len();
+
+                            stubs.entry(index).or_insert(count);
+                        }
+                        RelocationKind::GotRelative => {
+                            let count = got_entries.len();
+
+                            got_entries.entry(index).or_insert(count);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // The code goes first, so it can be made executable without the data
+        let mut section_offsets = MutMap::default();
+        let mut len = 0;
+
+        for section in sections.iter().filter(|s| s.kind() == SectionKind::Text) {
+            len = align_to(len, section.align() as usize);
+            section_offsets.insert(section.index(), len);
+            len += section.size() as usize;
+        }
+
+        let stubs_offset = align_to(len, STUB_SIZE);
+        let code_len = align_to(stubs_offset + stubs.len() * STUB_SIZE, PAGE_SIZE);
+
+        len = code_len;
+
+        for section in sections.iter().filter(|s| s.kind() != SectionKind::Text) {
+            len = align_to(len, section.align() as usize);
+            section_offsets.insert(section.index(), len);
+            len += section.size() as usize;
+        }
+
+        let got_offset = align_to(len, 8);
+
+        len = align_to(got_offset + got_entries.len() * 8, PAGE_SIZE);
+
+        let memory = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+
+        if memory == libc::MAP_FAILED {
+            return Err("mmap failed".to_string());
+        }
+
+        // From here on, dropping the module unmaps the memory
+        let mut module = JitModule {
+            memory: memory as *mut u8,
+            len,
+            symbols: MutMap::default(),
+        };
+        let base = module.memory as usize;
+
+        for section in sections.iter() {
+            // uninitialized data has no bytes, and the mapping starts out zeroed
+            let data = section.data().map_err(|err| err.to_string())?;
+            let offset = section_offsets[&section.index()];
+
+            unsafe {
+                std::ptr::copy_nonoverlapping(data.as_ptr(), module.memory.add(offset), data.len());
+            }
+        }
+
+        let symbol_address = |index| -> Result<usize, String> {
+            let symbol = file.symbol_by_index(index).map_err(|err| err.to_string())?;
+
+            match symbol.section_index() {
+                Some(section_index) => match section_offsets.get(&section_index) {
+                    Some(offset) => Ok(base + offset + symbol.address() as usize),
+                    None => Err(format!(
+                        "symbol {:?} is in a section that isn't loaded",
+                        index
+                    )),
+                },
+                None => {
+                    let name = symbol.name().map_err(|err| err.to_string())?;
+
+                    resolve(name).ok_or(format!("undefined symbol {}", name))
+                }
+            }
+        };
+
+        for (index, stub) in stubs.iter() {
+            let stub_address = base + stubs_offset + stub * STUB_SIZE;
+            let mut bytes = [0u8; STUB_SIZE];
+
+            bytes[..6].copy_from_slice(&[0xff, 0x25, 0, 0, 0, 0]);
+            bytes[6..14].copy_from_slice(&(symbol_address(*index)? as u64).to_le_bytes());
+
+            unsafe {
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), stub_address as *mut u8, STUB_SIZE);
+            }
+        }
+
+        for (index, entry) in got_entries.iter() {
+            let entry_address = base + got_offset + entry * 8;
+
+            unsafe {
+                std::ptr::write_unaligned(
+                    entry_address as *mut u64,
+                    symbol_address(*index)? as u64,
+                );
+            }
+        }
+
+        for section in sections.iter() {
+            let section_address = base + section_offsets[&section.index()];
+
+            for (offset, reloc) in section.relocations() {
+                let place = section_address + offset as usize;
+                let target = match reloc.target() {
+                    RelocationTarget::Symbol(index) => match reloc.kind() {
+                        RelocationKind::PltRelative if stubs.contains_key(&index) => {
+                            base + stubs_offset + stubs[&index] * STUB_SIZE
+                        }
+                        RelocationKind::GotRelative => base + got_offset + got_entries[&index] * 8,
+                        _ => symbol_address(index)?,
+                    },
+                    RelocationTarget::Section(index) => match section_offsets.get(&index) {
+                        Some(offset) => base + offset,
+                        None => return Err(format!("relocation against {:?}", index)),
+                    },
+                    target => return Err(format!("unsupported relocation target {:?}", target)),
+                };
+                let value = (target as i64).wrapping_add(reloc.addend());
+
+                match (reloc.kind(), reloc.size()) {
+                    (RelocationKind::Absolute, 64) => unsafe {
+                        std::ptr::write_unaligned(place as *mut u64, value as u64);
+                    },
+                    (
+                        RelocationKind::Relative
+                        | RelocationKind::PltRelative
+                        | RelocationKind::GotRelative,
+                        32,
+                    ) => {
+                        let relative = value.wrapping_sub(place as i64);
+
+                        if relative != relative as i32 as i64 {
+                            return Err(format!("relocation at {:#x} is out of range", place));
+                        }
+
+                        unsafe {
+                            std::ptr::write_unaligned(place as *mut i32, relative as i32);
+                        }
+                    }
+                    (kind, size) => {
+                        return Err(format!(
+                            "unsupported relocation {:?} of size {}",
+                            kind, size
+                        ));
+                    }
+                }
+            }
+        }
+
+        if code_len > 0 {
+            let result =
+                unsafe { libc::mprotect(memory, code_len, libc::PROT_READ | libc::PROT_EXEC) };
+
+            if result != 0 {
+                return Err("mprotect failed".to_string());
+            }
+        }
+
+        for symbol in file.symbols() {
+            if let (Some(section_index), Ok(name)) = (symbol.section_index(), symbol.name()) {
+                if let Some(offset) = section_offsets.get(&section_index) {
+                    if !name.is_empty() {
+                        let address = base + offset + symbol.address() as usize;
+
+                        module.symbols.insert(name.to_string(), address);
+                    }
+                }
+            }
+        }
+
+        Ok(module)
+    }
+}
+
+fn align_to(offset: usize, align: usize) -> usize {
+    let align = align.max(1);
+
+    (offset + align - 1) / align * align
+}
@@ -11,6 +11,8 @@ use rustyline::validate::{self, ValidationContext, ValidationResult, Validator};
 use rustyline_derive::{Completer, Helper, Hinter};
 use std::borrow::Cow;
 use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 
 use roc_build::link::llvm_module_to_dylib;
@@ -20,15 +22,20 @@ use roc_gen_llvm::{run_jit_function, run_jit_function_dynamic_type};
 use roc_load::{EntryPoint, MonomorphizedModule};
 use roc_mono::ir::OptLevel;
 use roc_parse::ast::Expr;
-use roc_parse::parser::{EExpr, ELambda, SyntaxError};
+use roc_parse::parser::{EExpect, EExpr, EIf, EInParens, ELambda, EList, ERecord, EString, EWhen};
+use roc_region::all::Position;
 use roc_repl_eval::eval::jit_to_ast;
-use roc_repl_eval::gen::{compile_to_mono, format_answer, ReplOutput};
+use roc_repl_eval::gen::{
+    compile_to_mono, format_answer, is_only_defs, load_module, ReplOutput, ReplState,
+};
 use roc_repl_eval::{ReplApp, ReplAppMemory};
 use roc_reporting::report::DEFAULT_PALETTE;
 use roc_std::RocStr;
 use roc_target::TargetInfo;
 use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 
+mod dev;
+
 const BLUE: &str = "\u{001b}[36m";
 const PINK: &str = "\u{001b}[35m";
 const END_COL: &str = "\u{001b}[0m";
@@ -47,18 +54,69 @@ pub const WELCOME_MESSAGE: &str = concatcp!(
 pub const INSTRUCTIONS: &str = "Enter an expression, or :help, or :q to quit.\n";
 pub const PROMPT: &str = concatcp!("\n", BLUE, "»", END_COL, " ");
 pub const CONT_PROMPT: &str = concatcp!(BLUE, "…", END_COL, " ");
+pub const HELP: &str = r#"Commands:
+  :type <expr>              Show the type of an expression, without evaluating it
+  :time <expr>              Evaluate an expression, and show how long each phase took
+  :load <path/to/Mod.roc>   Bring an interface module and what it exposes into scope
+  import <Mod>              Load Mod.roc from the same directory as the loaded modules
+  :reset                    Forget all the defs and modules of this session
+  :exit or :quit or :q      Exit the REPL
+
+Defs like `x = 5` are kept for later inputs."#;
+
+/// A REPL command other than `:help`, `:reset` and the ones for exiting
+enum ReplCommand<'a> {
+    Type(&'a str),
+    Time(&'a str),
+    Load(&'a str),
+    Import(&'a str),
+}
+
+impl<'a> ReplCommand<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let (command, arg) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let arg = arg.trim();
+
+        match command {
+            ":type" | ":t" => Some(ReplCommand::Type(arg)),
+            ":time" => Some(ReplCommand::Time(arg)),
+            ":load" => Some(ReplCommand::Load(arg)),
+            "import" => Some(ReplCommand::Import(arg)),
+            _ => None,
+        }
+    }
+
+    fn arg(&self) -> &'a str {
+        match self {
+            ReplCommand::Type(arg)
+            | ReplCommand::Time(arg)
+            | ReplCommand::Load(arg)
+            | ReplCommand::Import(arg) => *arg,
+        }
+    }
+
+    /// How to use the command, for when its argument is missing
+    fn usage(&self) -> &'static str {
+        match self {
+            ReplCommand::Type(_) => "Use :type with an expression, like :type List.map",
+            ReplCommand::Time(_) => "Use :time with an expression, like :time List.range 0 1000",
+            ReplCommand::Load(_) => {
+                "Use :load with the path to a module, like :load path/to/Mod.roc"
+            }
+            ReplCommand::Import(_) => "Use import with the name of a module, like import Mod",
+        }
+    }
+}
 
 #[derive(Completer, Helper, Hinter)]
 struct ReplHelper {
     validator: InputValidator,
-    pending_src: String,
 }
 
 impl ReplHelper {
     pub(crate) fn new() -> ReplHelper {
         ReplHelper {
             validator: InputValidator::new(),
-            pending_src: String::new(),
         }
     }
 }
@@ -104,25 +162,130 @@ impl InputValidator {
 
 impl Validator for InputValidator {
     fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
-        if ctx.input().is_empty() {
+        let input = ctx.input();
+
+        if input.is_empty() || is_incomplete(input) {
             Ok(ValidationResult::Incomplete)
         } else {
-            let arena = bumpalo::Bump::new();
-            let state = roc_parse::state::State::new(ctx.input().trim().as_bytes());
-
-            match roc_parse::expr::parse_loc_expr(0, &arena, state) {
-                // Special case some syntax errors to allow for multi-line inputs
-                Err((_, EExpr::DefMissingFinalExpr(_), _))
-                | Err((_, EExpr::DefMissingFinalExpr2(_, _), _))
-                | Err((_, EExpr::Lambda(ELambda::Body(_, _), _), _)) => {
-                    Ok(ValidationResult::Incomplete)
-                }
-                _ => Ok(ValidationResult::Valid(None)),
-            }
+            Ok(ValidationResult::Valid(None))
         }
     }
 }
 
+/// Whether more lines could still be part of the input
+fn is_incomplete(input: &str) -> bool {
+    let src = input.trim();
+    let arena = Bump::new();
+    let state = roc_parse::state::State::new(src.as_bytes());
+
+    match roc_parse::expr::test_parse_expr(0, &arena, state) {
+        // Defs without a final expression get added to the session, so they are
+        // as complete as an expression is
+        Err(problem) if !is_only_defs(src) => ran_out_of_input(&problem, src.len()),
+        // An indented last line could be followed by more branches of a `when`,
+        // more defs in a lambda body, and so on
+        _ => match input.rsplit_once('\n') {
+            Some((_, last_line)) => last_line.starts_with(char::is_whitespace),
+            None => false,
+        },
+    }
+}
+
+/// Whether the parser got to the end of the input before finding the problem, like in an
+/// open `when` or an unclosed bracket
+fn ran_out_of_input(problem: &EExpr, len: usize) -> bool {
+    let at_end = |pos: &Position| pos.offset as usize >= len;
+
+    match problem {
+        EExpr::Str(EString::EndlessMulti(_), _) => true,
+
+        EExpr::DefMissingFinalExpr2(problem, _)
+        | EExpr::List(EList::Expr(problem, _), _)
+        | EExpr::Record(ERecord::Expr(problem, _), _)
+        | EExpr::InParens(EInParens::Expr(problem, _), _)
+        | EExpr::Lambda(ELambda::Body(problem, _), _)
+        | EExpr::When(
+            EWhen::Condition(problem, _) | EWhen::Branch(problem, _) | EWhen::IfGuard(problem, _),
+            _,
+        )
+        | EExpr::If(
+            EIf::Condition(problem, _) | EIf::ThenBranch(problem, _) | EIf::ElseBranch(problem, _),
+            _,
+        )
+        | EExpr::Expect(EExpect::Condition(problem, _) | EExpect::Continuation(problem, _), _)
+        | EExpr::Dbg(EExpect::Condition(problem, _) | EExpect::Continuation(problem, _), _) => {
+            ran_out_of_input(problem, len)
+        }
+
+        EExpr::List(
+            EList::Open(pos)
+            | EList::End(pos)
+            | EList::IndentOpen(pos)
+            | EList::IndentEnd(pos)
+            | EList::Space(_, pos),
+            _,
+        )
+        | EExpr::Record(
+            ERecord::End(pos)
+            | ERecord::Open(pos)
+            | ERecord::Field(pos)
+            | ERecord::Colon(pos)
+            | ERecord::IndentOpen(pos)
+            | ERecord::IndentColon(pos)
+            | ERecord::IndentEnd(pos)
+            | ERecord::Space(_, pos),
+            _,
+        )
+        | EExpr::InParens(
+            EInParens::End(pos)
+            | EInParens::Open(pos)
+            | EInParens::IndentOpen(pos)
+            | EInParens::IndentEnd(pos)
+            | EInParens::Space(_, pos),
+            _,
+        )
+        | EExpr::Lambda(
+            ELambda::Arrow(pos)
+            | ELambda::Arg(pos)
+            | ELambda::IndentArrow(pos)
+            | ELambda::IndentBody(pos)
+            | ELambda::IndentArg(pos),
+            _,
+        )
+        | EExpr::When(
+            EWhen::Is(pos)
+            | EWhen::Arrow(pos)
+            | EWhen::IndentIs(pos)
+            | EWhen::IndentCondition(pos)
+            | EWhen::IndentPattern(pos)
+            | EWhen::IndentArrow(pos)
+            | EWhen::IndentBranch(pos)
+            | EWhen::Pattern(_, pos),
+            _,
+        )
+        | EExpr::If(
+            EIf::Then(pos)
+            | EIf::Else(pos)
+            | EIf::IndentCondition(pos)
+            | EIf::IndentThenToken(pos)
+            | EIf::IndentElseToken(pos)
+            | EIf::IndentThenBranch(pos)
+            | EIf::IndentElseBranch(pos),
+            _,
+        )
+        | EExpr::Start(pos)
+        | EExpr::DefMissingFinalExpr(pos)
+        | EExpr::IndentDefBody(pos)
+        | EExpr::IndentEquals(pos)
+        | EExpr::IndentAnnotation(pos)
+        | EExpr::IndentStart(pos)
+        | EExpr::IndentEnd(pos)
+        | EExpr::BackpassArrow(pos) => at_end(pos),
+
+        _ => false,
+    }
+}
+
 struct CliApp {
     lib: Library,
 }
@@ -307,21 +470,46 @@ pub fn mono_module_to_dylib<'a>(
         .map(|lib| (lib, main_fn_name, subs, layout_interner))
 }
 
-fn gen_and_eval_llvm<'a>(
+/// How long each phase of compiling and evaluating an input took
+type Timings = Vec<(&'static str, Duration)>;
+
+fn gen_and_eval(
+    state: &mut ReplState,
     src: &str,
     target: Triple,
     opt_level: OptLevel,
-) -> Result<ReplOutput, SyntaxError<'a>> {
+    timings: &mut Timings,
+) -> ReplOutput {
     let arena = Bump::new();
     let target_info = TargetInfo::from(&target);
 
-    let mut loaded = match compile_to_mono(&arena, src, target_info, DEFAULT_PALETTE) {
-        Ok(x) => x,
+    let load_start = Instant::now();
+    let compiled = compile_to_mono(&arena, state, src, target_info, DEFAULT_PALETTE);
+
+    timings.push(("Load and monomorphize", load_start.elapsed()));
+
+    let mut loaded = match compiled {
+        Ok(Some(x)) => x,
+        Ok(None) => {
+            return ReplOutput::DefsAdded;
+        }
         Err(prob_strings) => {
-            return Ok(ReplOutput::Problems(prob_strings));
+            return ReplOutput::Problems(prob_strings);
         }
     };
 
+    // the phases of the input's own module, as opposed to the builtins
+    if let Some(module_timing) = loaded.timings.get(&loaded.module_id) {
+        let make_specializations: Duration = module_timing.make_specializations.iter().sum();
+
+        timings.push(("  Parse", module_timing.parse_body));
+        timings.push(("  Canonicalize", module_timing.canonicalize));
+        timings.push(("  Constrain", module_timing.constrain));
+        timings.push(("  Solve", module_timing.solve));
+        timings.push(("  Find specializations", module_timing.find_specializations));
+        timings.push(("  Make specializations", make_specializations));
+    }
+
     debug_assert_eq!(loaded.exposed_to_host.values.len(), 1);
     let (main_fn_symbol, main_fn_var) = loaded.exposed_to_host.values.iter().next().unwrap();
     let main_fn_symbol = *main_fn_symbol;
@@ -340,52 +528,204 @@ fn gen_and_eval_llvm<'a>(
     let (_, main_fn_layout) = match loaded.procedures.keys().find(|(s, _)| *s == main_fn_symbol) {
         Some(layout) => *layout,
         None => {
-            return Ok(ReplOutput::NoProblems {
+            return ReplOutput::NoProblems {
                 expr: "<function>".to_string(),
                 expr_type: expr_type_str,
-            });
+            };
         }
     };
 
     let interns = loaded.interns.clone();
 
-    let (lib, main_fn_name, subs, layout_interner) =
-        mono_module_to_dylib(&arena, target, loaded, opt_level).expect("we produce a valid Dylib");
-
-    let mut app = CliApp { lib };
+    let code_gen_start = Instant::now();
+
+    // Anything the dev backend can't generate code for yet goes through LLVM instead
+    let dev_app = match opt_level {
+        OptLevel::Development if dev::is_supported(&target) => dev::mono_module_to_jit(
+            &arena,
+            &target,
+            loaded.module_id,
+            loaded.procedures.clone(),
+            &loaded.interns,
+            &loaded.layout_interner,
+            main_fn_symbol,
+            main_fn_layout,
+        ),
+        _ => None,
+    };
 
-    let res_answer = jit_to_ast(
-        &arena,
-        &mut app,
-        main_fn_name,
-        main_fn_layout,
-        &content,
-        &subs,
-        &interns,
-        layout_interner.into_global().fork(),
-        target_info,
-    );
+    let res_answer = match dev_app {
+        Some((mut app, main_fn_name)) => {
+            timings.push(("Generate machine code and load", code_gen_start.elapsed()));
+
+            let eval_start = Instant::now();
+            let res_answer = jit_to_ast(
+                &arena,
+                &mut app,
+                main_fn_name,
+                main_fn_layout,
+                &content,
+                &loaded.subs,
+                &interns,
+                loaded.layout_interner.into_global().fork(),
+                target_info,
+            );
+
+            timings.push(("Evaluate", eval_start.elapsed()));
+
+            res_answer
+        }
+        None => {
+            let (lib, main_fn_name, subs, layout_interner) =
+                mono_module_to_dylib(&arena, target, loaded, opt_level)
+                    .expect("we produce a valid Dylib");
+
+            timings.push(("Generate LLVM and link", code_gen_start.elapsed()));
+
+            let mut app = CliApp { lib };
+
+            let eval_start = Instant::now();
+            let res_answer = jit_to_ast(
+                &arena,
+                &mut app,
+                main_fn_name,
+                main_fn_layout,
+                &content,
+                &subs,
+                &interns,
+                layout_interner.into_global().fork(),
+                target_info,
+            );
+
+            timings.push(("Evaluate", eval_start.elapsed()));
+
+            res_answer
+        }
+    };
 
-    let formatted = format_answer(&arena, res_answer, expr_type_str);
-    Ok(formatted)
+    format_answer(&arena, res_answer, expr_type_str)
 }
 
-fn eval_and_format<'a>(src: &str) -> Result<String, SyntaxError<'a>> {
-    let format_output = |output| match output {
+fn format_output(output: ReplOutput) -> String {
+    match output {
         ReplOutput::NoProblems { expr, expr_type } => {
             format!("\n{} {}:{} {}", expr, PINK, END_COL, expr_type)
         }
         ReplOutput::Problems(lines) => format!("\n{}\n", lines.join("\n\n")),
-    };
+        ReplOutput::DefsAdded => String::new(),
+    }
+}
+
+fn eval_and_format(state: &mut ReplState, opt_level: OptLevel, src: &str) -> String {
+    let mut timings = Timings::new();
 
-    gen_and_eval_llvm(src, Triple::host(), OptLevel::Normal).map(format_output)
+    format_output(gen_and_eval(
+        state,
+        src,
+        Triple::host(),
+        opt_level,
+        &mut timings,
+    ))
 }
 
-fn report_parse_error(fail: SyntaxError) {
-    println!("TODO Gracefully report parse error in repl: {:?}", fail);
+fn run_command(state: &mut ReplState, opt_level: OptLevel, command: ReplCommand) -> String {
+    if command.arg().is_empty() {
+        return format_output(ReplOutput::Problems(vec![command.usage().to_string()]));
+    }
+
+    match command {
+        ReplCommand::Type(src) => format_output(type_of(state, src, Triple::host())),
+        ReplCommand::Time(src) => {
+            let mut timings = Timings::new();
+            let start = Instant::now();
+
+            let output = gen_and_eval(state, src, Triple::host(), opt_level, &mut timings);
+            let mut buf = format_output(output);
+
+            buf.push_str("\n\n");
+
+            for (label, duration) in timings {
+                report_timing(&mut buf, label, duration);
+            }
+
+            report_timing(&mut buf, "Total", start.elapsed());
+
+            buf.pop(); // the output gets printed with its own newline
+
+            buf
+        }
+        ReplCommand::Load(path) => load(state, Path::new(path)),
+        ReplCommand::Import(module_name) => {
+            let path = state.module_path(module_name);
+
+            load(state, &path)
+        }
+    }
 }
 
-pub fn main() -> io::Result<()> {
+/// The type of the expression, without evaluating it
+fn type_of(state: &ReplState, src: &str, target: Triple) -> ReplOutput {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&target);
+
+    // If the input is a def, we don't want to add it to the session
+    let mut state = state.clone();
+
+    match compile_to_mono(&arena, &mut state, src, target_info, DEFAULT_PALETTE) {
+        Ok(Some(mut loaded)) => {
+            let (_, main_fn_var) = loaded.exposed_to_host.values.iter().next().unwrap();
+            let main_fn_var = *main_fn_var;
+
+            let expr_type = name_and_print_var(
+                main_fn_var,
+                &mut loaded.subs,
+                loaded.module_id,
+                &loaded.interns,
+                DebugPrint::NOTHING,
+            );
+
+            ReplOutput::NoProblems {
+                expr: src.to_string(),
+                expr_type,
+            }
+        }
+        Ok(None) => ReplOutput::Problems(vec![ReplCommand::Type(src).usage().to_string()]),
+        Err(problems) => ReplOutput::Problems(problems),
+    }
+}
+
+fn load(state: &mut ReplState, path: &Path) -> String {
+    let arena = Bump::new();
+    let target_info = TargetInfo::from(&Triple::host());
+
+    match load_module(&arena, state, path, target_info, DEFAULT_PALETTE) {
+        Ok(()) => String::new(),
+        Err(problems) => format_output(ReplOutput::Problems(problems)),
+    }
+}
+
+fn report_timing(buf: &mut String, label: &str, duration: Duration) {
+    use std::fmt::Write;
+
+    writeln!(
+        buf,
+        "{:9.3} ms   {}",
+        duration.as_secs_f64() * 1000.0,
+        label,
+    )
+    .unwrap()
+}
+
+fn print_output(output: &str) {
+    // Inputs that only added defs have nothing to show
+    if !output.is_empty() {
+        println!("{}", output);
+    }
+}
+
+/// Runs the REPL. With `OptLevel::Development`, inputs are evaluated with the dev backend
+/// where it supports them.
+pub fn main(opt_level: OptLevel) -> io::Result<()> {
     use rustyline::error::ReadlineError;
     use rustyline::Editor;
 
@@ -394,7 +734,7 @@ pub fn main() -> io::Result<()> {
     // <RUN WITH:> RUST_LOG=rustyline=debug cargo run repl 2> debug.log
     print!("{}{}", WELCOME_MESSAGE, INSTRUCTIONS);
 
-    let mut prev_line_blank = false;
+    let mut state = ReplState::new();
     let mut editor = Editor::<ReplHelper>::new();
     let repl_helper = ReplHelper::new();
     editor.set_helper(Some(repl_helper));
@@ -407,65 +747,26 @@ pub fn main() -> io::Result<()> {
                 let trim_line = line.trim();
                 editor.add_history_entry(trim_line);
 
-                let pending_src = &mut editor
-                    .helper_mut()
-                    .expect("Editor helper was not set")
-                    .pending_src;
-
                 match trim_line.to_lowercase().as_str() {
                     "" => {
-                        if pending_src.is_empty() {
-                            print!("\n{}", INSTRUCTIONS);
-                        } else if prev_line_blank {
-                            // After two blank lines in a row, give up and try parsing it
-                            // even though it's going to fail. This way you don't get stuck.
-                            match eval_and_format(pending_src.as_str()) {
-                                Ok(output) => {
-                                    println!("{}", output);
-                                }
-                                Err(fail) => {
-                                    report_parse_error(fail);
-                                }
-                            }
-
-                            pending_src.clear();
-                        } else {
-                            pending_src.push('\n');
-
-                            prev_line_blank = true;
-                            continue; // Skip the part where we reset prev_line_blank to false
-                        }
+                        print!("\n{}", INSTRUCTIONS);
                     }
                     ":help" => {
-                        println!("Use :exit or :quit or :q to exit.");
+                        println!("{}", HELP);
+                    }
+                    ":reset" => {
+                        state = ReplState::new();
                     }
                     ":exit" | ":quit" | ":q" => {
                         break;
                     }
                     _ => {
-                        let result = if pending_src.is_empty() {
-                            eval_and_format(trim_line)
-                        } else {
-                            pending_src.push('\n');
-                            pending_src.push_str(trim_line);
-
-                            eval_and_format(pending_src.as_str())
+                        let output = match ReplCommand::parse(trim_line) {
+                            Some(command) => run_command(&mut state, opt_level, command),
+                            None => eval_and_format(&mut state, opt_level, trim_line),
                         };
 
-                        match result {
-                            Ok(output) => {
-                                println!("{}", output);
-                                pending_src.clear();
-                            }
-                            //                            Err(Fail {
-                            //                                reason: FailReason::Eof(_),
-                            //                                ..
-                            //                            }) => {}
-                            Err(fail) => {
-                                report_parse_error(fail);
-                                pending_src.clear();
-                            }
-                        }
+                        print_output(&output);
                     }
                 }
             }
@@ -474,18 +775,6 @@ pub fn main() -> io::Result<()> {
                 break;
             }
             Err(ReadlineError::Eof) => {
-                // If we hit an eof, and we're allowed to keep going,
-                // append the str to the src we're building up and continue.
-                // (We only need to append it here if it was empty before;
-                // otherwise, we already appended it before calling eval_and_format.)
-                let pending_src = &mut editor
-                    .helper_mut()
-                    .expect("Editor helper was not set")
-                    .pending_src;
-
-                if pending_src.is_empty() {
-                    pending_src.push_str("");
-                }
                 break;
             }
             Err(err) => {
@@ -493,8 +782,6 @@ pub fn main() -> io::Result<()> {
                 break;
             }
         }
-
-        prev_line_blank = false;
     }
 
     Ok(())
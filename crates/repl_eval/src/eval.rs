@@ -10,14 +10,16 @@ use roc_module::ident::TagName;
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::ir::ProcLayout;
 use roc_mono::layout::{
-    self, union_sorted_tags_pub, Builtin, Layout, LayoutCache, LayoutInterner, UnionLayout,
-    UnionVariant, WrappedVariant,
+    self, cmp_fields, union_sorted_tags_pub, Builtin, Layout, LayoutCache, LayoutInterner,
+    UnionLayout, UnionVariant, WrappedVariant,
 };
 use roc_parse::ast::{AssignedField, Collection, Expr, StrLiteral};
 use roc_region::all::{Loc, Region};
 use roc_std::RocDec;
 use roc_target::TargetInfo;
-use roc_types::subs::{Content, FlatType, GetSubsSlice, RecordFields, Subs, UnionTags, Variable};
+use roc_types::subs::{
+    Content, FlatType, GetSubsSlice, RecordFields, Subs, TupleElems, UnionTags, Variable,
+};
 
 use crate::{ReplApp, ReplAppMemory};
 
@@ -379,6 +381,9 @@ fn jit_to_ast_help<'a, A: ReplApp<'a>>(
                 Content::Structure(FlatType::EmptyRecord) => {
                     Ok(struct_to_ast(env, mem, addr, RecordFields::empty()))
                 }
+                Content::Structure(FlatType::Tuple(elems, ext)) => {
+                    Ok(tuple_to_ast(env, mem, addr, *elems, *ext))
+                }
                 Content::Structure(FlatType::TagUnion(tags, _)) => {
                     let (tag_name, payload_vars) = unpack_single_element_tag_union(env.subs, *tags);
 
@@ -579,6 +584,9 @@ fn addr_to_ast<'a, M: ReplAppMemory>(
             Content::Structure(FlatType::EmptyRecord) => {
                 struct_to_ast(env, mem, addr, RecordFields::empty())
             }
+            Content::Structure(FlatType::Tuple(elems, ext)) => {
+                tuple_to_ast(env, mem, addr, *elems, *ext)
+            }
             other => {
                 unreachable!(
                     "Something had a Struct layout, but instead of a Record type, it had: {:?}",
@@ -1059,6 +1067,71 @@ fn struct_to_ast<'a, 'env, M: ReplAppMemory>(
     }
 }
 
+fn tuple_to_ast<'a, 'env, M: ReplAppMemory>(
+    env: &mut Env<'a, 'env>,
+    mem: &'a M,
+    addr: usize,
+    tuple_elems: TupleElems,
+    ext: Variable,
+) -> Expr<'a> {
+    let arena = env.arena;
+    let subs = env.subs;
+
+    let (it, _) = tuple_elems.sorted_iterator_and_ext(subs, ext);
+    let mut elems: std::vec::Vec<_> = it
+        .map(|(index, elem_var)| {
+            let elem_layout = env
+                .layout_cache
+                .from_var(arena, elem_var, env.subs)
+                .unwrap();
+
+            (index, elem_var, elem_layout)
+        })
+        .collect();
+
+    // The elements are stored in the order of their layouts, so visit them in that order to
+    // find their addresses, and then put them back in the order of the type.
+    elems.sort_by(|(index1, _, layout1), (index2, _, layout2)| {
+        cmp_fields(
+            &env.layout_cache.interner,
+            index1,
+            layout1,
+            index2,
+            layout2,
+            env.target_info,
+        )
+    });
+
+    let mut elem_addr = addr;
+    let mut output = std::vec::Vec::with_capacity(elems.len());
+
+    for (index, elem_var, elem_layout) in elems {
+        let content = subs.get_content_without_compacting(elem_var);
+
+        let loc_expr = &*arena.alloc(Loc {
+            value: addr_to_ast(
+                env,
+                mem,
+                elem_addr,
+                &elem_layout,
+                WhenRecursive::Unreachable,
+                content,
+            ),
+            region: Region::zero(),
+        });
+
+        output.push((index, loc_expr));
+
+        elem_addr += elem_layout.stack_size(&env.layout_cache.interner, env.target_info) as usize;
+    }
+
+    output.sort_by_key(|(index, _)| *index);
+
+    let output = Vec::from_iter_in(output.into_iter().map(|(_, loc_expr)| loc_expr), arena);
+
+    Expr::Tuple(Collection::with_items(output.into_bump_slice()))
+}
+
 fn unpack_single_element_tag_union(subs: &Subs, tags: UnionTags) -> (&TagName, &[Variable]) {
     let (tag_name_index, payload_vars_index) = tags.iter_all().next().unwrap();
 
@@ -1,21 +1,456 @@
 use bumpalo::Bump;
 use roc_load::{ExecutionMode, LoadConfig, Threading};
 use roc_reporting::report::Palette;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use roc_fmt::annotation::Formattable;
 use roc_fmt::annotation::{Newlines, Parens};
 use roc_load::{LoadingProblem, MonomorphizedModule};
-use roc_parse::ast::Expr;
-use roc_region::all::LineInfo;
-use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
+use roc_module::symbol::{Interns, ModuleIds};
+use roc_parse::ast::{
+    AssignedField, Expr, ExtractSpaces, Module, Pattern, StrLiteral, StrSegment, TypeDef, ValueDef,
+};
+use roc_parse::parser::{EExpr, FileError, Parser, SourceError, SyntaxError};
+use roc_problem::can::Problem;
+use roc_region::all::{LineInfo, Loc, Position, Region};
+use roc_reporting::report::{can_problem, parse_problem, type_problem, RocDocAllocator};
 use roc_target::TargetInfo;
 
 use crate::eval::ToAstProblem;
 
 pub enum ReplOutput {
     Problems(Vec<String>),
-    NoProblems { expr: String, expr_type: String },
+    NoProblems {
+        expr: String,
+        expr_type: String,
+    },
+    /// The input only had defs, which were added to the session
+    DefsAdded,
+}
+
+/// The defs entered in earlier inputs of a REPL session, which later inputs can use.
+/// Entering a value with the same name as an earlier one shadows the earlier one, while
+/// types get replaced.
+#[derive(Debug, Default, Clone)]
+pub struct ReplState {
+    past_defs: Vec<PastDef>,
+    imports: Vec<ReplImport>,
+    /// The directory the loaded modules are in. Imports are resolved relative to it.
+    src_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+struct ReplImport {
+    module_name: String,
+    exposed: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct PastDef {
+    /// The name of the value or type this def introduces
+    ident: String,
+    kind: PastDefKind,
+    src: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PastDefKind {
+    Type,
+    /// An annotation on its own, which a later def of the same name gets to use
+    Annotation,
+    Value {
+        annotated: bool,
+    },
+}
+
+impl ReplState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Where `import Foo.Bar` looks for the module: next to the modules loaded so far,
+    /// or else in the current directory
+    pub fn module_path(&self, module_name: &str) -> PathBuf {
+        let src_dir = self.src_dir.as_deref().unwrap_or_else(|| Path::new("."));
+        let mut path = src_dir.join(module_name.replace('.', "/"));
+
+        path.set_extension("roc");
+
+        path
+    }
+
+    /// The defs of the session once `new_defs` are added.
+    ///
+    /// An earlier value with the same name as a new def gets a fresh name, and so do the
+    /// references to it, so the defs that used it keep doing so rather than picking up the
+    /// new one. An earlier annotation is merged with the new def it is for.
+    fn past_defs_with(&self, new_defs: Vec<PastDef>) -> Vec<PastDef> {
+        let mut past_defs = self.past_defs.clone();
+        let mut added = Vec::with_capacity(new_defs.len());
+
+        for mut new_def in new_defs {
+            let index = past_defs
+                .iter()
+                .position(|past_def| past_def.ident == new_def.ident);
+
+            if let Some(index) = index {
+                match (past_defs[index].kind, new_def.kind) {
+                    (
+                        PastDefKind::Value { .. },
+                        PastDefKind::Annotation | PastDefKind::Value { .. },
+                    ) => {
+                        let renamed = fresh_ident(&past_defs, &new_def.ident);
+
+                        for past_def in past_defs.iter_mut() {
+                            past_def.rename(&new_def.ident, &renamed);
+                        }
+                    }
+                    (PastDefKind::Annotation, PastDefKind::Value { annotated: false }) => {
+                        let annotation = past_defs.remove(index);
+
+                        new_def.src = format!("{}\n{}", annotation.src, new_def.src);
+                        new_def.kind = PastDefKind::Value { annotated: true };
+                    }
+                    _ => {
+                        past_defs.remove(index);
+                    }
+                }
+            }
+
+            added.push(new_def);
+        }
+
+        past_defs.extend(added);
+
+        past_defs
+    }
+}
+
+impl PastDef {
+    /// Makes the def refer to the value `ident` as `renamed`, and be called that if it is
+    /// the def of `ident`
+    fn rename(&mut self, ident: &str, renamed: &str) {
+        if self.kind == PastDefKind::Type {
+            return;
+        }
+
+        if self.ident == ident {
+            self.ident = renamed.to_string();
+        }
+
+        self.src = rename_in_def(&self.src, ident, renamed);
+    }
+}
+
+/// A name for a shadowed `ident` that no def in the session uses
+fn fresh_ident(past_defs: &[PastDef], ident: &str) -> String {
+    (1..)
+        .map(|n| format!("{}Shadowed{}", ident, n))
+        .find(|candidate| {
+            past_defs
+                .iter()
+                .all(|past_def| &past_def.ident != candidate)
+        })
+        .unwrap()
+}
+
+fn rename_in_def(src: &str, ident: &str, renamed: &str) -> String {
+    let arena = Bump::new();
+    let state = roc_parse::state::State::new(src.as_bytes());
+
+    // past defs parsed fine when they were entered
+    let defs = match roc_parse::module::module_defs().parse(&arena, state) {
+        Ok((_, defs, _)) => defs,
+        Err(_) => return src.to_string(),
+    };
+
+    let mut renamer = Renamer {
+        src,
+        ident,
+        renamed,
+        edits: Vec::new(),
+    };
+
+    for def in defs.defs() {
+        if let Err(value_def) = def {
+            renamer.value_def(value_def);
+        }
+    }
+
+    renamer.apply()
+}
+
+/// Finds where a def mentions a value, to rename it. Roc doesn't allow shadowing, so every
+/// unqualified mention of the name in a def is either its own name or a reference to it.
+struct Renamer<'s> {
+    src: &'s str,
+    ident: &'s str,
+    renamed: &'s str,
+    /// The byte ranges to replace, and what to replace them with
+    edits: Vec<(usize, usize, String)>,
+}
+
+impl<'s> Renamer<'s> {
+    fn apply(mut self) -> String {
+        let mut src = self.src.to_string();
+
+        self.edits.sort_by_key(|(start, _, _)| *start);
+        self.edits.dedup_by_key(|(start, _, _)| *start);
+
+        for (start, end, replacement) in self.edits.into_iter().rev() {
+            src.replace_range(start..end, &replacement);
+        }
+
+        src
+    }
+
+    /// Renames the first mention of the name in `region`, which may also hold spaces,
+    /// comments and parentheses around it
+    fn rename_in(&mut self, region: Region, replacement: String) {
+        let start = region.start().offset as usize;
+        let end = region.end().offset as usize;
+        let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+
+        let found = self.src[start..end]
+            .match_indices(self.ident)
+            .find(|(index, _)| {
+                let before = self.src[..start + index].chars().next_back();
+                let after = self.src[start + index + self.ident.len()..].chars().next();
+
+                !matches!(before, Some(c) if is_ident_char(c) || c == '.')
+                    && !matches!(after, Some(c) if is_ident_char(c))
+            });
+
+        if let Some((index, _)) = found {
+            let ident_start = start + index;
+
+            self.edits
+                .push((ident_start, ident_start + self.ident.len(), replacement));
+        }
+    }
+
+    fn value_def(&mut self, def: &ValueDef) {
+        match def {
+            ValueDef::Annotation(pattern, _) => self.pattern(pattern),
+            ValueDef::Body(pattern, body) => {
+                self.pattern(pattern);
+                self.expr(&body.value, body.region);
+            }
+            ValueDef::AnnotatedBody {
+                ann_pattern,
+                body_pattern,
+                body_expr,
+                ..
+            } => {
+                self.pattern(ann_pattern);
+                self.pattern(body_pattern);
+                self.expr(&body_expr.value, body_expr.region);
+            }
+            ValueDef::Expect { condition, .. } | ValueDef::ExpectFx { condition, .. } => {
+                self.expr(&condition.value, condition.region)
+            }
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Loc<Pattern>) {
+        if pattern_ident(&pattern.value) == Some(self.ident) {
+            self.rename_in(pattern.region, self.renamed.to_string());
+        }
+    }
+
+    fn loc_expr(&mut self, expr: &Loc<Expr>) {
+        self.expr(&expr.value, expr.region)
+    }
+
+    /// `region` is where `expr` is, or where the closest expression around it with a
+    /// region is
+    fn expr(&mut self, expr: &Expr, region: Region) {
+        match expr {
+            Expr::Var {
+                module_name: "",
+                ident,
+            } if *ident == self.ident => self.rename_in(region, self.renamed.to_string()),
+            Expr::Access(inner, _)
+            | Expr::TupleAccess(inner, _)
+            | Expr::SpaceBefore(inner, _)
+            | Expr::SpaceAfter(inner, _)
+            | Expr::ParensAround(inner) => self.expr(inner, region),
+            Expr::Str(literal) => self.str_literal(literal),
+            Expr::List(items) | Expr::Tuple(items) => {
+                for item in items.iter() {
+                    self.loc_expr(item);
+                }
+            }
+            Expr::RecordUpdate { update, fields } => {
+                self.loc_expr(update);
+                self.fields(fields.items);
+            }
+            Expr::Record(fields) => self.fields(fields.items),
+            Expr::Closure(_, body) => self.loc_expr(body),
+            Expr::Defs(defs, body) => {
+                for def in defs.defs() {
+                    if let Err(value_def) = def {
+                        self.value_def(value_def);
+                    }
+                }
+
+                self.loc_expr(body);
+            }
+            Expr::Backpassing(_, first, second)
+            | Expr::Expect(first, second)
+            | Expr::Dbg(first, second) => {
+                self.loc_expr(first);
+                self.loc_expr(second);
+            }
+            Expr::Apply(function, args, _) => {
+                self.loc_expr(function);
+
+                for arg in args.iter() {
+                    self.loc_expr(arg);
+                }
+            }
+            Expr::BinOps(operands, last) => {
+                for (operand, _) in operands.iter() {
+                    self.loc_expr(operand);
+                }
+
+                self.loc_expr(last);
+            }
+            Expr::UnaryOp(operand, _) => self.loc_expr(operand),
+            Expr::If(branches, final_else) => {
+                for (condition, then) in branches.iter() {
+                    self.loc_expr(condition);
+                    self.loc_expr(then);
+                }
+
+                self.loc_expr(final_else);
+            }
+            Expr::When(condition, branches) => {
+                self.loc_expr(condition);
+
+                for branch in branches.iter() {
+                    if let Some(guard) = &branch.guard {
+                        self.loc_expr(guard);
+                    }
+
+                    self.loc_expr(&branch.value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn fields(&mut self, fields: &[Loc<AssignedField<Expr>>]) {
+        for field in fields {
+            self.field(&field.value);
+        }
+    }
+
+    fn field(&mut self, field: &AssignedField<Expr>) {
+        match field {
+            AssignedField::RequiredValue(_, _, value)
+            | AssignedField::OptionalValue(_, _, value) => self.loc_expr(value),
+            // `{ x }` is short for `{ x: x }`, and only the value gets renamed
+            AssignedField::LabelOnly(label) if label.value == self.ident => {
+                let replacement = format!("{}: {}", self.ident, self.renamed);
+
+                self.rename_in(label.region, replacement);
+            }
+            AssignedField::SpaceBefore(field, _) | AssignedField::SpaceAfter(field, _) => {
+                self.field(field)
+            }
+            _ => {}
+        }
+    }
+
+    fn str_literal(&mut self, literal: &StrLiteral) {
+        let lines = match literal {
+            StrLiteral::PlainLine(_) => return,
+            StrLiteral::Line(segments) => std::slice::from_ref(segments),
+            StrLiteral::Block(lines) => *lines,
+        };
+
+        for segment in lines.iter().flat_map(|segments| segments.iter()) {
+            if let StrSegment::Interpolated(expr) = segment {
+                self.expr(expr.value, expr.region);
+            }
+        }
+    }
+}
+
+/// Whether the input consists only of defs, like `x = 5` or `Age := U32`, rather than
+/// ending in an expression to evaluate.
+pub fn is_only_defs(src: &str) -> bool {
+    let arena = Bump::new();
+
+    parse_only_defs(&arena, src).is_some()
+}
+
+fn parse_only_defs(arena: &Bump, src: &str) -> Option<Vec<PastDef>> {
+    let src = src.trim();
+    let state = roc_parse::state::State::new(src.as_bytes());
+
+    let (_, defs, _) = roc_parse::module::module_defs().parse(arena, state).ok()?;
+
+    if defs.is_empty() {
+        return None;
+    }
+
+    let mut past_defs = Vec::with_capacity(defs.len());
+
+    for (def, region) in defs.defs().zip(defs.regions.iter()) {
+        let (ident, kind) = match def {
+            Ok(type_def) => {
+                let header = match type_def {
+                    TypeDef::Alias { header, .. }
+                    | TypeDef::Opaque { header, .. }
+                    | TypeDef::Ability { header, .. } => header,
+                };
+
+                (header.name.value, PastDefKind::Type)
+            }
+            // top-level destructures are not supported, so those stay local to the input
+            Err(ValueDef::Annotation(pattern, _)) => {
+                (pattern_ident(&pattern.value)?, PastDefKind::Annotation)
+            }
+            Err(ValueDef::Body(pattern, _)) => (
+                pattern_ident(&pattern.value)?,
+                PastDefKind::Value { annotated: false },
+            ),
+            Err(ValueDef::AnnotatedBody {
+                body_pattern: pattern,
+                ..
+            }) => (
+                pattern_ident(&pattern.value)?,
+                PastDefKind::Value { annotated: true },
+            ),
+            Err(ValueDef::Expect { .. } | ValueDef::ExpectFx { .. }) => {
+                // top-level expects don't define anything a later input could use
+                return None;
+            }
+        };
+
+        let start = region.start().offset as usize;
+        let end = region.end().offset as usize;
+
+        past_defs.push(PastDef {
+            ident: ident.to_string(),
+            kind,
+            src: src[start..end].to_string(),
+        });
+    }
+
+    Some(past_defs)
+}
+
+fn pattern_ident<'a>(pattern: &Pattern<'a>) -> Option<&'a str> {
+    match pattern {
+        Pattern::Identifier(ident) => Some(ident),
+        Pattern::SpaceBefore(pattern, _) | Pattern::SpaceAfter(pattern, _) => {
+            pattern_ident(pattern)
+        }
+        _ => None,
+    }
 }
 
 pub fn format_answer(
@@ -42,16 +477,39 @@ pub fn format_answer(
     }
 }
 
+/// Compiles the input along with the defs of earlier inputs. If the input only has defs, they
+/// are added to the session and there is nothing to evaluate, so this returns `None`.
 pub fn compile_to_mono<'a>(
     arena: &'a Bump,
+    state: &mut ReplState,
     src: &str,
     target_info: TargetInfo,
     palette: Palette,
-) -> Result<MonomorphizedModule<'a>, Vec<String>> {
+) -> Result<Option<MonomorphizedModule<'a>>, Vec<String>> {
     let filename = PathBuf::from("");
-    let src_dir = PathBuf::from("fake/test/path");
+    let src_dir = match &state.src_dir {
+        Some(src_dir) => src_dir.clone(),
+        None => PathBuf::from("fake/test/path"),
+    };
 
-    let module_src = arena.alloc(promote_expr_to_module(src));
+    let new_defs = parse_only_defs(arena, src);
+
+    // report parse errors against the input, rather than the module it gets promoted to
+    if new_defs.is_none() {
+        let parse_state = roc_parse::state::State::new(src.as_bytes());
+
+        if let Err(problem) = roc_parse::expr::test_parse_expr(0, arena, parse_state) {
+            return Err(vec![parse_problem_report(src, filename, problem, palette)]);
+        }
+    }
+
+    let session_defs = new_defs.map(|new_defs| state.past_defs_with(new_defs));
+
+    let (module_src, defs_start) = match &session_defs {
+        Some(session_defs) => promote_expr_to_module(&state.imports, session_defs, "{}"),
+        None => promote_expr_to_module(&state.imports, &state.past_defs, src),
+    };
+    let module_src = arena.alloc(module_src);
 
     let exposed_types = Default::default();
     let loaded = roc_load::load_and_monomorphize_from_str(
@@ -79,6 +537,7 @@ pub fn compile_to_mono<'a>(
     };
 
     let MonomorphizedModule {
+        module_id: repl_home,
         interns,
         sources,
         can_problems,
@@ -98,13 +557,28 @@ pub fn compile_to_mono<'a>(
             continue;
         }
 
-        let line_info = LineInfo::new(module_src);
+        let line_info = LineInfo::new(src);
         let src_lines: Vec<&str> = src.split('\n').collect();
 
         // Report parsing and canonicalization problems
         let alloc = RocDocAllocator::new(&src_lines, *home, interns);
 
         for problem in can_probs.into_iter() {
+            // Defs are never used outside of the REPL, so there is no point in warning about
+            // the ones that later inputs didn't use (yet)
+            if let Problem::UnusedDef(_, region) = problem {
+                if *home == *repl_home && region.start().offset as usize >= defs_start {
+                    continue;
+                }
+            }
+
+            // Same for the modules loaded into the session
+            if let Problem::UnusedImport(_, _) = problem {
+                if *home == *repl_home {
+                    continue;
+                }
+            }
+
             let report = can_problem(&alloc, &line_info, module_path.clone(), problem);
             let mut buf = String::new();
 
@@ -127,13 +601,146 @@ pub fn compile_to_mono<'a>(
     if !lines.is_empty() {
         Err(lines)
     } else {
-        Ok(loaded)
+        match session_defs {
+            Some(session_defs) => {
+                state.past_defs = session_defs;
+
+                Ok(None)
+            }
+            None => Ok(Some(loaded)),
+        }
     }
 }
 
-fn promote_expr_to_module(src: &str) -> String {
-    let mut buffer =
-        String::from("app \"app\" provides [replOutput] to \"./platform\"\n\nreplOutput =\n");
+fn parse_problem_report(src: &str, filename: PathBuf, problem: EExpr, palette: Palette) -> String {
+    let src_lines: Vec<&str> = src.lines().collect();
+    let line_info = LineInfo::new(src);
+
+    let mut module_ids = ModuleIds::default();
+    let home = module_ids.get_or_insert(&"REPL".into());
+    let interns = Interns {
+        module_ids,
+        ..Default::default()
+    };
+    let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+    let problem = match problem {
+        // like the loader does when there is something after the end of a module
+        EExpr::BadExprEnd(pos) => SyntaxError::NotEndOfFile(pos),
+        problem => SyntaxError::Expr(problem, Position::default()),
+    };
+    let problem = FileError {
+        problem: SourceError {
+            problem,
+            bytes: src.as_bytes(),
+        },
+        filename: filename.clone(),
+    };
+    let report = parse_problem(&alloc, &line_info, filename, 0, problem);
+    let mut buf = String::new();
+
+    report.render_color_terminal(&mut buf, &alloc, &palette);
+
+    buf
+}
+
+/// Brings an interface module, and the values and types it exposes, into scope for the rest
+/// of the session. All the modules of a session have to be in the same directory.
+pub fn load_module(
+    arena: &Bump,
+    state: &mut ReplState,
+    path: &Path,
+    target_info: TargetInfo,
+    palette: Palette,
+) -> Result<(), Vec<String>> {
+    let src = std::fs::read_to_string(path)
+        .map_err(|err| vec![format!("I could not read {}: {}", path.display(), err)])?;
+
+    let parse_state = roc_parse::state::State::new(src.as_bytes());
+    let import = match roc_parse::module::parse_header(arena, parse_state) {
+        Ok((Module::Interface { header }, _)) => ReplImport {
+            module_name: header.name.value.as_str().to_string(),
+            exposed: header
+                .exposes
+                .iter()
+                .map(|loc_exposed| {
+                    let name: &str = loc_exposed.value.extract_spaces().item.into();
+
+                    name.to_string()
+                })
+                .collect(),
+        },
+        _ => {
+            return Err(vec![format!(
+                "{} is not an interface module, so I cannot load it.",
+                path.display()
+            )]);
+        }
+    };
+
+    // `Foo.Bar` is in `Foo/Bar.roc`, so the directory that imports are resolved
+    // relative to is one level up for every dot in the module name
+    let mut src_dir = match path.parent() {
+        Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    for _ in import.module_name.matches('.') {
+        src_dir.pop();
+    }
+
+    if let Some(existing_src_dir) = &state.src_dir {
+        if existing_src_dir != &src_dir {
+            return Err(vec![format!(
+                "{} is not in {}, where the other loaded modules are. All the modules loaded into one session have to be in the same directory.",
+                path.display(),
+                existing_src_dir.display()
+            )]);
+        }
+    }
+
+    // Only keep the module if everything still compiles with it
+    let mut new_state = state.clone();
+
+    new_state
+        .imports
+        .retain(|existing| existing.module_name != import.module_name);
+    new_state.imports.push(import);
+    new_state.src_dir = Some(src_dir);
+
+    compile_to_mono(arena, &mut new_state, "{}", target_info, palette)?;
+
+    *state = new_state;
+
+    Ok(())
+}
+
+/// Returns the source of the module, and the offset where the defs of the session start.
+/// They go after `replOutput`, so the line numbers in reports about the input don't depend on
+/// how many defs came before it.
+fn promote_expr_to_module(
+    imports: &[ReplImport],
+    past_defs: &[PastDef],
+    src: &str,
+) -> (String, usize) {
+    let mut buffer = String::from("app \"app\" ");
+
+    if !imports.is_empty() {
+        let entries: Vec<String> = imports
+            .iter()
+            .map(|import| {
+                if import.exposed.is_empty() {
+                    import.module_name.clone()
+                } else {
+                    format!("{}.{{{}}}", import.module_name, import.exposed.join(", "))
+                }
+            })
+            .collect();
+
+        buffer.push_str(&format!("imports [{}] ", entries.join(", ")));
+    }
+
+    buffer.push_str("provides [replOutput] to \"./platform\"\n\nreplOutput =\n");
 
     for line in src.lines() {
         // indent the body!
@@ -142,5 +749,13 @@ fn promote_expr_to_module(src: &str) -> String {
         buffer.push('\n');
     }
 
-    buffer
+    let defs_start = buffer.len();
+
+    for past_def in past_defs {
+        buffer.push('\n');
+        buffer.push_str(&past_def.src);
+        buffer.push('\n');
+    }
+
+    (buffer, defs_start)
 }
@@ -92,21 +92,30 @@ mod test {
     use pretty_assertions::assert_eq;
     use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
     use roc_load::{ExecutionMode, LoadConfig, Threading};
+    use roc_mono::ir::OptLevel;
     use roc_reporting::report::RenderTarget;
     use target_lexicon::Triple;
 
-    use crate::run::expect_mono_module_to_dylib;
+    use crate::run::{expect_mono_module_to_dylib, expect_mono_module_to_dylib_dev};
 
     use super::*;
 
     fn run_expect_test(source: &str, expected: &str) {
+        run_expect_test_help(source, expected, OptLevel::Normal)
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+    fn run_expect_test_dev(source: &str, expected: &str) {
+        run_expect_test_help(source, expected, OptLevel::Development)
+    }
+
+    fn run_expect_test_help(source: &str, expected: &str, opt_level: OptLevel) {
         let arena = bumpalo::Bump::new();
         let arena = &arena;
 
         let triple = Triple::host();
         let target = &triple;
 
-        let opt_level = roc_mono::ir::OptLevel::Normal;
         let target_info = TargetInfo::from(target);
 
         // Step 1: compile the app and generate the .o file
@@ -137,13 +146,16 @@ mod test {
 
         let interns = loaded.interns.clone();
 
-        let (lib, expects, layout_interner) = expect_mono_module_to_dylib(
-            arena,
-            target.clone(),
-            loaded,
-            opt_level,
-            LlvmBackendMode::CliTest,
-        )
+        let (lib, expects, layout_interner) = match opt_level {
+            OptLevel::Development => expect_mono_module_to_dylib_dev(arena, target.clone(), loaded),
+            _ => expect_mono_module_to_dylib(
+                arena,
+                target.clone(),
+                loaded,
+                opt_level,
+                LlvmBackendMode::CliTest,
+            ),
+        }
         .unwrap();
 
         let arena = &bumpalo::Bump::new();
@@ -180,8 +192,8 @@ mod test {
         let bytes = strip_ansi_escapes::strip(writer).unwrap();
         let actual = String::from_utf8(bytes).unwrap();
 
-        if !actual.is_empty() {
-            // trim off the first line; it contains a path in a tempdir that
+        if actual.starts_with("──") {
+            // trim off the first line of the report; it contains a path in a tempdir that
             // changes between test runs
             let p = actual.bytes().position(|c| c == b'\n').unwrap();
             let (_, x) = actual.split_at(p);
@@ -934,4 +946,157 @@ mod test {
             ),
         );
     }
+
+    #[test]
+    fn dbg_values() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = 1
+                    dbg a + 1
+
+                    dbg ["one", "two"]
+
+                    a == 1
+                "#
+            ),
+            indoc!(
+                r#"
+                [Test.roc:7] a + 1 = 2
+                [Test.roc:9] ["one", "two"] = ["one", "two"]
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+    fn equals_fail_dev() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect 1 == 2
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│  expect 1 == 2
+                    ^^^^^^^^^^^^^
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+    fn lookup_integer_dev() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = 1
+                    b = 2
+
+                    a == b
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│>  expect
+                6│>      a = 1
+                7│>      b = 2
+                8│>
+                9│>      a == b
+
+                When it failed, these variables had these values:
+
+                a : Num a
+                a = 1
+
+                b : Num a
+                b = 2
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+    fn crash_dev() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = 1
+                    b = 0
+
+                    Num.divTrunc a b == 0
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation crashed while running:
+
+                5│>  expect
+                6│>      a = 1
+                7│>      b = 0
+                8│>
+                9│>      Num.divTrunc a b == 0
+
+                The crash reported this message:
+
+                Floating point exception
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", target_os = "linux"))]
+    fn dbg_values_dev() {
+        run_expect_test_dev(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    a = 1
+                    dbg a + 1
+
+                    dbg "hello"
+
+                    a == 1
+                "#
+            ),
+            indoc!(
+                r#"
+                [Test.roc:7] a + 1 = 2
+                [Test.roc:9] "hello" = "hello"
+                "#
+            ),
+        );
+    }
 }
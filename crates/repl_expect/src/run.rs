@@ -3,7 +3,7 @@ use std::{os::unix::process::parent_id, sync::Arc};
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
 use inkwell::context::Context;
-use roc_build::link::llvm_module_to_dylib;
+use roc_build::link::{dev_object_to_dylib, llvm_module_to_dylib};
 use roc_collections::{MutSet, VecMap};
 use roc_gen_llvm::{
     llvm::{build::LlvmBackendMode, externs::add_default_roc_externs},
@@ -13,7 +13,10 @@ use roc_gen_llvm::{
 use roc_intern::{GlobalInterner, SingleThreadedInterner};
 use roc_load::{EntryPoint, Expectations, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
-use roc_mono::{ir::OptLevel, layout::Layout};
+use roc_mono::{
+    ir::OptLevel,
+    layout::{Layout, LayoutIds},
+};
 use roc_region::all::Region;
 use roc_reporting::{error::expect::Renderer, report::RenderTarget};
 use roc_target::TargetInfo;
@@ -107,6 +110,41 @@ pub fn run_expects<'a, W: std::io::Write>(
     )
 }
 
+/// Runs the expects like [`run_expects`], but returns the outcome of each expect separately
+/// instead of writing all output to a single writer.
+#[allow(clippy::too_many_arguments)]
+pub fn run_expects_with_results<'a, 'e>(
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    expects: ExpectFunctions<'e>,
+) -> std::io::Result<Vec<ExpectResult<'e>>> {
+    let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
+    let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
+
+    let mut results = Vec::with_capacity(expects.pure.len() + expects.fx.len());
+
+    run_expects_help(
+        render_target,
+        arena,
+        interns,
+        layout_interner,
+        lib,
+        expectations,
+        expects,
+        &mut memory,
+        |result| {
+            results.push(result);
+            Ok(())
+        },
+    )?;
+
+    Ok(results)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     writer: &mut W,
@@ -122,9 +160,47 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     let mut failed = 0;
     let mut passed = 0;
 
+    run_expects_help(
+        render_target,
+        arena,
+        interns,
+        layout_interner,
+        lib,
+        expectations,
+        expects,
+        memory,
+        |result| {
+            writer.write_all(&result.output)?;
+
+            match result.passed {
+                true => passed += 1,
+                false => failed += 1,
+            }
+
+            Ok(())
+        },
+    )?;
+
+    Ok((failed, passed))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_expects_help<'a, 'e>(
+    render_target: RenderTarget,
+    arena: &'a Bump,
+    interns: &'a Interns,
+    layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
+    lib: &libloading::Library,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    expects: ExpectFunctions<'e>,
+    memory: &mut ExpectMemory,
+    mut on_result: impl FnMut(ExpectResult<'e>) -> std::io::Result<()>,
+) -> std::io::Result<()> {
     for expect in expects.fx {
-        let result = run_expect_fx(
-            writer,
+        let mut output = Vec::new();
+
+        let passed = run_expect_fx(
+            &mut output,
             render_target,
             arena,
             interns,
@@ -135,17 +211,20 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
             expect,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
+        on_result(ExpectResult {
+            expect,
+            passed,
+            output,
+        })?;
     }
 
     memory.set_shared_buffer(lib);
 
     for expect in expects.pure {
-        let result = run_expect_pure(
-            writer,
+        let mut output = Vec::new();
+
+        let passed = run_expect_pure(
+            &mut output,
             render_target,
             arena,
             interns,
@@ -154,15 +233,106 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
             expectations,
             memory,
             expect,
+            expects.catches_crashes,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
+        on_result(ExpectResult {
+            expect,
+            passed,
+            output,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Calls a toplevel expect. Functions built by the dev backend never write to the result,
+/// so it starts out as a success rather than uninitialized.
+fn run_expect_function(lib: &libloading::Library, name: &str) -> Result<(), String> {
+    type Main = unsafe extern "C" fn(*mut RocCallResult<()>);
+
+    let main: libloading::Symbol<Main> = unsafe { lib.get(name.as_bytes()) }
+        .unwrap_or_else(|_| panic!("Unable to find the expect `{}`", name));
+
+    let mut result = RocCallResult::default();
+    unsafe { main(&mut result) };
+
+    result.into()
+}
+
+/// Calls a toplevel expect in a child process, so that a crash only takes down the child.
+/// The child writes its frames to a shared copy of the buffer, which is copied back afterwards.
+fn run_expect_function_in_child(
+    lib: &libloading::Library,
+    name: &str,
+    memory: &mut ExpectMemory,
+) -> Result<(), String> {
+    let child_ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            memory.length,
+            libc::PROT_WRITE | libc::PROT_READ,
+            libc::MAP_SHARED | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+
+    if child_ptr == libc::MAP_FAILED {
+        return Err(format!(
+            "Unable to map memory for the expect: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+
+    let mut child_memory = ExpectMemory {
+        ptr: child_ptr.cast(),
+        length: memory.length,
+        shm_name: None,
+        _marker: std::marker::PhantomData,
+    };
+
+    unsafe { std::ptr::copy_nonoverlapping(memory.ptr, child_memory.ptr, memory.length) };
+
+    let result = match unsafe { libc::fork() } {
+        0 => {
+            // we are the child
+            child_memory.set_shared_buffer(lib);
+
+            match run_expect_function(lib, name) {
+                Ok(()) => std::process::exit(0),
+                Err(_) => std::process::exit(1),
+            }
         }
+        -1 => Err(format!(
+            "Unable to run the expect: {}",
+            std::io::Error::last_os_error()
+        )),
+        child_id => {
+            let mut status = 0;
+            unsafe { libc::waitpid(child_id, &mut status, 0) };
+
+            if libc::WIFSIGNALED(status) {
+                let signal =
+                    unsafe { std::ffi::CStr::from_ptr(libc::strsignal(libc::WTERMSIG(status))) };
+                Err(signal.to_string_lossy().into_owned())
+            } else if libc::WEXITSTATUS(status) != 0 {
+                Err(format!(
+                    "The expect exited with status {}",
+                    libc::WEXITSTATUS(status)
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    };
+
+    unsafe {
+        std::ptr::copy_nonoverlapping(child_memory.ptr, memory.ptr, memory.length);
+        libc::munmap(child_ptr, memory.length);
     }
 
-    Ok((failed, passed))
+    result
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -176,16 +346,19 @@ fn run_expect_pure<'a, W: std::io::Write>(
     expectations: &mut VecMap<ModuleId, Expectations>,
     shared_memory: &mut ExpectMemory,
     expect: ToplevelExpect<'_>,
+    catches_crashes: bool,
 ) -> std::io::Result<bool> {
-    use roc_gen_llvm::try_run_jit_function;
-
     let sequence = ExpectSequence::new(shared_memory.ptr.cast());
 
-    let result: Result<(), String> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+    let result = if catches_crashes {
+        run_expect_function(lib, expect.name)
+    } else {
+        run_expect_function_in_child(lib, expect.name, shared_memory)
+    };
 
     let shared_memory_ptr: *const u8 = shared_memory.ptr.cast();
 
-    if result.is_err() || sequence.count_failures() > 0 {
+    if result.is_err() || sequence.count_frames() > 0 {
         let module_id = expect.symbol.module_id();
         let data = expectations.get_mut(&module_id).unwrap();
 
@@ -195,29 +368,28 @@ fn run_expect_pure<'a, W: std::io::Write>(
 
         let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
+        let mut has_failed = render_frames(
+            writer,
+            &renderer,
+            arena,
+            Some(expect),
+            expectations,
+            interns,
+            layout_interner,
+            shared_memory_ptr,
+            sequence.count_frames(),
+        )?;
+
         if let Err(roc_panic_message) = result {
             renderer.render_panic(writer, &roc_panic_message, expect.region)?;
-        } else {
-            let mut offset = ExpectSequence::START_OFFSET;
-
-            for _ in 0..sequence.count_failures() {
-                offset += render_expect_failure(
-                    writer,
-                    &renderer,
-                    arena,
-                    Some(expect),
-                    expectations,
-                    interns,
-                    layout_interner,
-                    shared_memory_ptr,
-                    offset,
-                )?;
-            }
+            has_failed = true;
         }
 
-        writeln!(writer)?;
+        if has_failed {
+            writeln!(writer)?;
+        }
 
-        Ok(false)
+        Ok(!has_failed)
     } else {
         Ok(true)
     }
@@ -243,21 +415,19 @@ fn run_expect_fx<'a, W: std::io::Write>(
         0 => unsafe {
             // we are the child
 
-            use roc_gen_llvm::try_run_jit_function;
-
             let mut child_memory = parent_memory.reuse_mmap().unwrap();
 
             let sequence = ExpectSequence::new(child_memory.ptr);
 
             child_memory.set_shared_buffer(lib);
 
-            let result: Result<(), String> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+            let result = run_expect_function(lib, expect.name);
 
             if let Err(msg) = result {
                 panic!("roc panic {}", msg);
             }
 
-            if sequence.count_failures() > 0 {
+            if sequence.count_frames() > 0 {
                 libc::kill(parent_id() as _, SIGUSR1);
             }
 
@@ -281,8 +451,10 @@ fn run_expect_fx<'a, W: std::io::Write>(
                         return Ok(has_succeeded);
                     }
                     SIGUSR1 => {
-                        // this is the signal we use for an expect failure. Let's see what the child told us
-                        has_succeeded = false;
+                        // this is the signal we use for an expect failure or dbg. Let's see what the child told us
+                        let sequence = ExpectSequence {
+                            ptr: parent_memory.ptr,
+                        };
 
                         let frame =
                             ExpectFrame::at_offset(parent_memory.ptr, ExpectSequence::START_OFFSET);
@@ -301,7 +473,7 @@ fn run_expect_fx<'a, W: std::io::Write>(
                             &source,
                         );
 
-                        render_expect_failure(
+                        let has_failed = render_frames(
                             writer,
                             &renderer,
                             arena,
@@ -310,8 +482,12 @@ fn run_expect_fx<'a, W: std::io::Write>(
                             interns,
                             layout_interner,
                             parent_memory.ptr,
-                            ExpectSequence::START_OFFSET,
+                            sequence.count_frames(),
                         )?;
+
+                        if has_failed {
+                            has_succeeded = false;
+                        }
                     }
                     _ => println!("received signal {}", sig),
                 }
@@ -360,6 +536,101 @@ pub fn roc_dev_expect<'a>(
     )
 }
 
+/// Render the frames written to the shared memory, in the order they were written.
+/// Returns whether any of them was a failed expectation (and not just a `dbg`)
+#[allow(clippy::too_many_arguments)]
+fn render_frames<'a>(
+    writer: &mut impl std::io::Write,
+    renderer: &Renderer,
+    arena: &'a Bump,
+    expect: Option<ToplevelExpect>,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    interns: &'a Interns,
+    layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
+    start: *const u8,
+    frame_count: usize,
+) -> std::io::Result<bool> {
+    let mut has_failed = false;
+    let mut offset = ExpectSequence::START_OFFSET;
+
+    for _ in 0..frame_count {
+        let frame = ExpectFrame::at_offset(start, offset);
+
+        let is_dbg = match expectations.get(&frame.module_id) {
+            Some(data) => data.dbgs.contains_key(&frame.region),
+            None => false,
+        };
+
+        if is_dbg {
+            render_dbg(
+                writer,
+                renderer,
+                arena,
+                expectations,
+                interns,
+                layout_interner,
+                start,
+                &frame,
+            )?;
+        } else {
+            has_failed = true;
+
+            render_expect_failure(
+                writer,
+                renderer,
+                arena,
+                expect,
+                expectations,
+                interns,
+                layout_interner,
+                start,
+                offset,
+            )?;
+        }
+
+        offset = frame.end_offset;
+    }
+
+    Ok(has_failed)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_dbg<'a>(
+    writer: &mut impl std::io::Write,
+    renderer: &Renderer,
+    arena: &'a Bump,
+    expectations: &mut VecMap<ModuleId, Expectations>,
+    interns: &'a Interns,
+    layout_interner: &Arc<GlobalInterner<'a, Layout<'a>>>,
+    start: *const u8,
+    frame: &ExpectFrame,
+) -> std::io::Result<()> {
+    // we always run programs as the host
+    let target_info = (&target_lexicon::Triple::host()).into();
+
+    let data = expectations.get_mut(&frame.module_id).unwrap();
+
+    let variable = match data.dbgs.get(&frame.region) {
+        None => panic!("region {:?} not in list of dbgs", frame.region),
+        Some(variable) => *variable,
+    };
+    let subs = arena.alloc(&mut data.subs);
+
+    let (_, expressions) = crate::get_values(
+        target_info,
+        arena,
+        subs,
+        interns,
+        layout_interner,
+        start,
+        frame.start_offset,
+        &[variable],
+    )
+    .unwrap();
+
+    renderer.render_dbg(writer, &expressions[0], frame.region)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn render_expect_failure<'a>(
     writer: &mut impl std::io::Write,
@@ -438,7 +709,8 @@ impl ExpectSequence {
         }
     }
 
-    fn count_failures(&self) -> usize {
+    /// The number of frames (failed expectations and `dbg`s) written so far
+    fn count_frames(&self) -> usize {
         unsafe { *(self.ptr as *const usize).add(Self::COUNT_INDEX) }
     }
 }
@@ -447,6 +719,7 @@ struct ExpectFrame {
     region: Region,
     module_id: ModuleId,
     start_offset: usize,
+    end_offset: usize,
 }
 
 impl ExpectFrame {
@@ -457,13 +730,17 @@ impl ExpectFrame {
         let module_id_bytes: [u8; 4] = unsafe { *(start.add(offset + 8).cast()) };
         let module_id: ModuleId = unsafe { std::mem::transmute(module_id_bytes) };
 
-        // skip to frame, 8 bytes for region, 4 for module id
-        let start_offset = offset + 12;
+        let end_offset_bytes: [u8; 8] = unsafe { *(start.add(offset + 12).cast()) };
+        let end_offset = usize::from_ne_bytes(end_offset_bytes);
+
+        // skip to frame, 8 bytes for region, 4 for module id, 8 for the end offset
+        let start_offset = offset + 20;
 
         Self {
             region,
             module_id,
             start_offset,
+            end_offset,
         }
     }
 }
@@ -475,10 +752,22 @@ pub struct ToplevelExpect<'a> {
     pub region: Region,
 }
 
+/// The outcome of running a single toplevel expect
+#[derive(Debug)]
+pub struct ExpectResult<'a> {
+    pub expect: ToplevelExpect<'a>,
+    pub passed: bool,
+    /// The rendered failure report and `dbg` output of this expect, if any
+    pub output: Vec<u8>,
+}
+
 #[derive(Debug)]
 pub struct ExpectFunctions<'a> {
     pub pure: BumpVec<'a, ToplevelExpect<'a>>,
     pub fx: BumpVec<'a, ToplevelExpect<'a>>,
+    /// Whether a crash is reported through the result of the expect. When it isn't, pure
+    /// expects run in a child process so that a crash does not take the test runner down too.
+    pub catches_crashes: bool,
 }
 
 pub fn expect_mono_module_to_dylib<'a>(
@@ -588,6 +877,7 @@ pub fn expect_mono_module_to_dylib<'a>(
     let expects = ExpectFunctions {
         pure: expects_pure,
         fx: expects_fx,
+        catches_crashes: true,
     };
 
     env.dibuilder.finalize();
@@ -615,3 +905,102 @@ pub fn expect_mono_module_to_dylib<'a>(
 
     llvm_module_to_dylib(env.module, &target, opt_level).map(|lib| (lib, expects, layout_interner))
 }
+
+/// Whether [`expect_mono_module_to_dylib_dev`] can build these expects. The dev backend can't
+/// look up every kind of value yet, so otherwise they should go through LLVM.
+pub fn dev_backend_supports_expects(loaded: &MonomorphizedModule) -> bool {
+    roc_gen_dev::expects_supported(&loaded.layout_interner, &loaded.procedures)
+}
+
+pub fn expect_mono_module_to_dylib_dev<'a>(
+    arena: &'a Bump,
+    target: Triple,
+    loaded: MonomorphizedModule<'a>,
+) -> Result<
+    (
+        libloading::Library,
+        ExpectFunctions<'a>,
+        SingleThreadedInterner<'a, Layout<'a>>,
+    ),
+    libloading::Error,
+> {
+    let MonomorphizedModule {
+        module_id,
+        toplevel_expects,
+        procedures,
+        mut interns,
+        layout_interner,
+        ..
+    } = loaded;
+
+    // The dev backend exposes expects under the same name it uses for them internally
+    let mut layout_ids = LayoutIds::default();
+    let mut expect_name = |symbol: Symbol| -> &'a str {
+        let (_, proc_layout) = procedures
+            .keys()
+            .find(|(proc_symbol, _)| *proc_symbol == symbol)
+            .unwrap();
+
+        let name = layout_ids
+            .get_toplevel(symbol, proc_layout)
+            .to_symbol_string(symbol, &interns);
+
+        arena.alloc_str(&name)
+    };
+
+    let expects_pure = BumpVec::from_iter_in(
+        toplevel_expects
+            .pure
+            .into_iter()
+            .map(|(symbol, region)| ToplevelExpect {
+                symbol,
+                region,
+                name: expect_name(symbol),
+            }),
+        arena,
+    );
+
+    let expects_fx = BumpVec::from_iter_in(
+        toplevel_expects
+            .fx
+            .into_iter()
+            .map(|(symbol, region)| ToplevelExpect {
+                symbol,
+                region,
+                name: expect_name(symbol),
+            }),
+        arena,
+    );
+
+    let expects = ExpectFunctions {
+        pure: expects_pure,
+        fx: expects_fx,
+        // roc_panic can't unwind out of code built by the dev backend yet
+        catches_crashes: false,
+    };
+
+    let module_out = {
+        let env = roc_gen_dev::Env {
+            arena,
+            layout_interner: &layout_interner,
+            module_id,
+            exposed_to_host: expects
+                .pure
+                .iter()
+                .chain(expects.fx.iter())
+                .map(|expect| expect.symbol)
+                .collect(),
+            lazy_literals: true,
+            generate_allocators: true, // the test runner has no platform to provide them
+            runs_expects: true,
+        };
+
+        let module_object = roc_gen_dev::build_module(&env, &mut interns, &target, procedures);
+
+        module_object
+            .write()
+            .expect("failed to build output object")
+    };
+
+    dev_object_to_dylib(&module_out, &target).map(|lib| (lib, expects, layout_interner))
+}
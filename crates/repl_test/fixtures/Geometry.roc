@@ -0,0 +1,11 @@
+interface Geometry
+    exposes [Point, origin, shift]
+    imports []
+
+Point : { x : I64, y : I64 }
+
+origin : Point
+origin = { x: 0, y: 0 }
+
+shift : Point, I64 -> Point
+shift = \p, d -> { x: p.x + d, y: p.y + d }
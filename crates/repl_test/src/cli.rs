@@ -39,10 +39,10 @@ fn path_to_roc_binary() -> PathBuf {
     path
 }
 
-fn repl_eval(input: &str) -> Out {
+fn repl_eval(args: &[&str], input: &str) -> Out {
     let mut cmd = Command::new(path_to_roc_binary());
 
-    cmd.arg("repl");
+    cmd.arg("repl").args(args);
 
     let mut child = cmd
         .stdin(Stdio::piped())
@@ -124,15 +124,31 @@ fn repl_eval(input: &str) -> Out {
 }
 
 pub fn expect_success(input: &str, expected: &str) {
-    let out = repl_eval(input);
+    expect_success_with_args(&[], input, expected);
+}
+
+/// Like `expect_success`, but in a REPL that evaluates inputs with the dev backend if it can.
+pub fn expect_success_dev(input: &str, expected: &str) {
+    expect_success_with_args(&["--dev"], input, expected);
+}
+
+fn expect_success_with_args(args: &[&str], input: &str, expected: &str) {
+    let out = repl_eval(args, input);
 
     assert_multiline_str_eq!("", out.stderr.as_str());
     assert_multiline_str_eq!(expected, out.stdout.as_str());
     assert!(out.status.success());
 }
 
+/// Enter several inputs into the same REPL session. The last one has to be an expression.
+pub fn expect_success_session(inputs: &[&str], expected: &str) {
+    let src: String = inputs.iter().map(|input| format!("{}\n", input)).collect();
+
+    expect_success(src.trim_end_matches('\n'), expected);
+}
+
 pub fn expect_failure(input: &str, expected: &str) {
-    let out = repl_eval(input);
+    let out = repl_eval(&[], input);
 
     // there may be some other stuff printed (e.g. unification errors)
     // so skip till the header of the first error
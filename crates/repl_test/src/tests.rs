@@ -2,11 +2,11 @@
 use indoc::indoc;
 
 #[cfg(not(feature = "wasm"))]
-use crate::cli::{expect_failure, expect_success};
+use crate::cli::{expect_failure, expect_success, expect_success_dev, expect_success_session};
 
 #[cfg(feature = "wasm")]
 #[allow(unused_imports)]
-use crate::wasm::{expect_failure, expect_success};
+use crate::wasm::{expect_failure, expect_success, expect_success_session};
 
 #[test]
 fn literal_0() {
@@ -322,6 +322,15 @@ fn num_bitwise_and() {
     expect_success("Num.bitwiseAnd 200 0", "0 : Int *")
 }
 
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn num_bitwise_and_u128_dev() {
+    // The dev backend can't do 128-bit bitwise operations yet, so this goes through LLVM
+    expect_success_dev("Num.bitwiseAnd 1u128 3u128", "1 : U128");
+
+    expect_success_dev("Num.bitwiseAnd 1u128 2u128", "0 : U128")
+}
+
 #[test]
 fn num_bitwise_xor() {
     expect_success("Num.bitwiseXor 20 20", "0 : Int *");
@@ -482,6 +491,14 @@ fn basic_2_field_i64_record() {
     );
 }
 
+#[test]
+fn tuple() {
+    expect_success(
+        r#"("a", 42u8, 3.5f64)"#,
+        r#"("a", 42, 3.5) : ( Str, U8, F64 )"#,
+    );
+}
+
 #[test]
 fn basic_2_field_f64_record() {
     expect_success(
@@ -899,11 +916,8 @@ fn parse_problem() {
 
                 I am partway through parsing a definition, but I got stuck here:
 
-                1│  app "app" provides [replOutput] to "./platform"
-                2│
-                3│  replOutput =
-                4│      add m n = m + n
-                            ^^^
+                1│  add m n = m + n
+                        ^^^
 
                 Looks like you are trying to define a function. In roc, functions are
                 always written as a lambda, like increment = \n -> n + 1.
@@ -912,6 +926,37 @@ fn parse_problem() {
     );
 }
 
+#[cfg(not(feature = "wasm"))] // TODO: mismatch is due to terminal control codes!
+#[test]
+fn unfinished_list() {
+    // a list that can't be finished by more lines gets submitted
+    expect_failure(
+        "[1, 2 }",
+        indoc!(
+            r#"
+                ── UNFINISHED LIST ─────────────────────────────────────────────────────────────
+
+                I am partway through started parsing a list, but I got stuck here:
+
+                1│  [1, 2 }
+                          ^
+
+                I was expecting to see a closing square bracket before this, so try
+                adding a ] and see if that helps?
+
+                Note: When I get stuck like this, it usually means that there is a
+                missing parenthesis or bracket somewhere earlier. It could also be a
+                stray keyword or operator.
+                "#
+        ),
+    );
+}
+
+#[test]
+fn multiline_list() {
+    expect_success("[\n    1,\n    2,\n]", "[1, 2] : List (Num *)");
+}
+
 #[cfg(not(feature = "wasm"))] // TODO: mismatch is due to terminal control codes!
 #[test]
 fn exhaustiveness_problem() {
@@ -1239,3 +1284,81 @@ fn record_of_poly_function_and_string() {
         r#"{ a: <function>, b: "b" } : { a : * -> Str, b : Str }"#,
     );
 }
+
+#[test]
+fn session_value_def() {
+    expect_success_session(&["x = 5", "x + 1"], "6 : Num *")
+}
+
+#[test]
+fn session_function_def() {
+    expect_success_session(&["addOne = \\n -> n + 1", "addOne 41"], "42 : Num *")
+}
+
+#[test]
+fn session_type_alias() {
+    expect_success_session(
+        &[
+            "Point : { x : I64, y : I64 }",
+            "origin : Point\norigin = { x: 0, y: 0 }",
+            "origin",
+        ],
+        "{ x: 0, y: 0 } : Point",
+    )
+}
+
+#[test]
+fn session_redefinition_shadows() {
+    expect_success_session(&["x = 5", r#"x = "hello""#, "x"], r#""hello" : Str"#)
+}
+
+#[test]
+fn session_redefinition_keeps_earlier_uses() {
+    expect_success_session(&["x = 1", "y = x + 1", "x = 10", "y"], "2 : Num *")
+}
+
+#[test]
+fn session_annotation_then_body() {
+    expect_success_session(&["x : U8", "x = 5", "x"], "5 : U8")
+}
+
+#[test]
+fn session_several_outputs() {
+    expect_success_session(&["x = 1", "x + 1", "x + 2"], "2 : Num *\n\n3 : Num *")
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn type_command() {
+    expect_success(":type Str.concat", "Str.concat : Str, Str -> Str")
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn type_command_with_session_def() {
+    expect_success_session(&["x = 5", ":type x", "x"], "x : Num *\n\n5 : Num *")
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn load_command() {
+    expect_success_session(
+        &[":load fixtures/Geometry.roc", "shift origin 3"],
+        "{ x: 3, y: 3 } : Geometry.Point",
+    )
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn load_command_without_path() {
+    expect_success(
+        ":load",
+        "Use :load with the path to a module, like :load path/to/Mod.roc\n",
+    )
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn type_command_without_expr() {
+    expect_success(":t", "Use :type with an expression, like :type List.map\n")
+}
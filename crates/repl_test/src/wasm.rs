@@ -240,6 +240,28 @@ fn dummy_system_time_now() -> f64 {
 }
 
 fn run(src: &'static str) -> (bool, String) {
+    run_session(&[src]).pop().unwrap()
+}
+
+/// Run several inputs in a new REPL session
+fn run_session(inputs: &[&'static str]) -> Vec<(bool, String)> {
+    if let Ok(_guard) = TEST_MUTEX.lock() {
+        COMPILER
+            .exports
+            .get_function("reset_from_test")
+            .unwrap()
+            .call(&[])
+            .unwrap();
+
+        inputs.iter().map(|src| run_input(src)).collect()
+    } else {
+        panic!(
+            "Failed to acquire test mutex! A previous test must have panicked while holding it, running Wasm"
+        )
+    }
+}
+
+fn run_input(src: &'static str) -> (bool, String) {
     println!("run");
     REPL_STATE.with(|rs| {
         *rs.borrow_mut().deref_mut() = Some(ReplState {
@@ -250,20 +272,14 @@ fn run(src: &'static str) -> (bool, String) {
         });
     });
 
-    let ok = if let Ok(_guard) = TEST_MUTEX.lock() {
-        let entrypoint = COMPILER
-            .exports
-            .get_function("entrypoint_from_test")
-            .unwrap();
+    let entrypoint = COMPILER
+        .exports
+        .get_function("entrypoint_from_test")
+        .unwrap();
 
-        let src_len = Value::I32(src.len() as i32);
-        let wasm_ok: i32 = entrypoint.call(&[src_len]).unwrap().deref()[0].unwrap_i32();
-        wasm_ok != 0
-    } else {
-        panic!(
-            "Failed to acquire test mutex! A previous test must have panicked while holding it, running Wasm"
-        )
-    };
+    let src_len = Value::I32(src.len() as i32);
+    let wasm_ok: i32 = entrypoint.call(&[src_len]).unwrap().deref()[0].unwrap_i32();
+    let ok = wasm_ok != 0;
 
     let final_state: ReplState = REPL_STATE.with(|rs| rs.take()).unwrap();
     let output: String = final_state.output.unwrap();
@@ -286,3 +302,22 @@ pub fn expect_failure(input: &'static str, expected: &str) {
     assert_eq!(ok, false);
     assert_eq!(output, expected);
 }
+
+/// Enter several inputs into the same REPL session. The last one has to be an expression.
+#[allow(dead_code)]
+pub fn expect_success_session(inputs: &[&'static str], expected: &str) {
+    let mut outputs = Vec::new();
+
+    for (ok, output) in run_session(inputs) {
+        if !ok {
+            panic!("\n{}\n", output);
+        }
+
+        // inputs that only add defs have no output
+        if !output.is_empty() {
+            outputs.push(output);
+        }
+    }
+
+    assert_eq!(outputs.join("\n\n"), expected);
+}
@@ -47,3 +47,9 @@ pub extern "C" fn entrypoint_from_test(src_len: usize) -> bool {
 
     ok
 }
+
+/// Start a new REPL session, since all the tests share one compiler instance
+#[no_mangle]
+pub extern "C" fn reset_from_test() {
+    crate::repl::reset_repl_state();
+}
@@ -16,4 +16,6 @@ pub use externs_js::{entrypoint_from_js, js_create_app, js_get_result_and_memory
 #[cfg(feature = "wasmer")]
 mod externs_test;
 #[cfg(feature = "wasmer")]
-pub use externs_test::{entrypoint_from_test, js_create_app, js_get_result_and_memory, js_run_app};
+pub use externs_test::{
+    entrypoint_from_test, js_create_app, js_get_result_and_memory, js_run_app, reset_from_test,
+};
@@ -1,4 +1,5 @@
 use bumpalo::{collections::vec::Vec, Bump};
+use std::cell::RefCell;
 use std::mem::size_of;
 
 use roc_collections::all::MutSet;
@@ -7,7 +8,7 @@ use roc_load::MonomorphizedModule;
 use roc_parse::ast::Expr;
 use roc_repl_eval::{
     eval::jit_to_ast,
-    gen::{compile_to_mono, format_answer, ReplOutput},
+    gen::{compile_to_mono, format_answer, ReplOutput, ReplState},
     ReplApp, ReplAppMemory,
 };
 use roc_reporting::report::DEFAULT_PALETTE_HTML;
@@ -18,6 +19,11 @@ use crate::{js_create_app, js_get_result_and_memory, js_run_app};
 
 const WRAPPER_NAME: &str = "wrapper";
 
+thread_local! {
+    /// The defs entered so far in this session
+    static REPL_STATE: RefCell<ReplState> = RefCell::new(ReplState::new());
+}
+
 pub struct WasmReplApp<'a> {
     arena: &'a Bump,
 }
@@ -175,8 +181,19 @@ pub async fn entrypoint_from_js(src: String) -> Result<String, String> {
 
     // Compile the app
     let target_info = TargetInfo::default_wasm32();
-    let mono = match compile_to_mono(arena, &src, target_info, DEFAULT_PALETTE_HTML) {
-        Ok(m) => m,
+    let compiled = REPL_STATE.with(|state| {
+        compile_to_mono(
+            arena,
+            &mut state.borrow_mut(),
+            &src,
+            target_info,
+            DEFAULT_PALETTE_HTML,
+        )
+    });
+    let mono = match compiled {
+        Ok(Some(m)) => m,
+        // The input only had defs, so there is nothing to show
+        Ok(None) => return Ok(String::new()),
         Err(messages) => return Err(messages.join("\n\n")),
     };
 
@@ -276,5 +293,11 @@ pub async fn entrypoint_from_js(src: String) -> Result<String, String> {
     match format_answer(arena, res_answer, expr_type_str) {
         ReplOutput::NoProblems { expr, expr_type } => Ok(format!("{} : {}", expr, expr_type)),
         ReplOutput::Problems(lines) => Err(format!("\n{}\n", lines.join("\n\n"))),
+        ReplOutput::DefsAdded => Ok(String::new()),
     }
 }
+
+/// Forget the defs from earlier inputs
+pub fn reset_repl_state() {
+    REPL_STATE.with(|state| *state.borrow_mut() = ReplState::new());
+}
@@ -586,6 +586,7 @@ pub fn can_problem<'b>(
                 ExtensionTypeKind::TagUnion => {
                     ("tag union", "a type variable or another tag union")
                 }
+                ExtensionTypeKind::Tuple => ("tuple", "a type variable or another tuple"),
             };
 
             doc = alloc.stack([
@@ -957,6 +958,19 @@ pub fn can_problem<'b>(
             title = "UNNECESSARY DEFINITION".to_string();
             severity = Severity::Warning;
         }
+        Problem::MultipleListRestPattern { region } => {
+            doc = alloc.stack([
+                alloc.reflow("This list pattern match has multiple rest patterns:"),
+                alloc.region(lines.convert_region(region)),
+                alloc.concat([
+                    alloc.reflow("I only support compiling list patterns with one "),
+                    alloc.parser_suggestion(".."),
+                    alloc.reflow(" pattern! Can you remove this additional one?"),
+                ]),
+            ]);
+            title = "MULTIPLE LIST REST PATTERNS".to_string();
+            severity = Severity::RuntimeError;
+        }
         Problem::OverloadedSpecialization {
             ability_member,
             overload,
@@ -1453,15 +1467,18 @@ fn pretty_runtime_error<'b>(
                 QualifiedIdentifier => " qualified ",
                 EmptySingleQuote => " empty character literal ",
                 MultipleCharsInSingleQuote => " overfull literal ",
+                DuplicateListRestPattern => " list rest ",
             };
 
             let tip = match problem {
                 MalformedInt | MalformedFloat | MalformedBase(_) => alloc
                     .tip()
                     .append(alloc.reflow("Learn more about number literals at TODO")),
-                EmptySingleQuote | MultipleCharsInSingleQuote | Unknown | BadIdent(_) => {
-                    alloc.nil()
-                }
+                EmptySingleQuote
+                | MultipleCharsInSingleQuote
+                | DuplicateListRestPattern
+                | Unknown
+                | BadIdent(_) => alloc.nil(),
                 QualifiedIdentifier => alloc
                     .tip()
                     .append(alloc.reflow("In patterns, only tags can be qualified")),
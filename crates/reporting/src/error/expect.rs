@@ -15,6 +15,7 @@ pub struct Renderer<'a> {
     arena: &'a Bump,
     alloc: RocDocAllocator<'a>,
     filename: PathBuf,
+    source: &'a str,
     line_info: LineInfo,
     render_target: RenderTarget,
 }
@@ -38,6 +39,7 @@ impl<'a> Renderer<'a> {
             alloc,
             line_info,
             filename,
+            source,
             render_target,
         }
     }
@@ -162,6 +164,37 @@ impl<'a> Renderer<'a> {
         write!(writer, "{}", buf)
     }
 
+    /// Renders the value of a `dbg` on a single line, like `[Main.roc:12] x + 1 = 42`
+    pub fn render_dbg<W>(
+        &self,
+        writer: &mut W,
+        expression: &Expr<'_>,
+        dbg_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use roc_fmt::annotation::Formattable;
+
+        let line_col_region = self.line_info.convert_region(dbg_region);
+        let dbg_source =
+            &self.source[dbg_region.start().offset as usize..dbg_region.end().offset as usize];
+
+        let mut buf = roc_fmt::Buf::new_in(self.arena);
+        expression.format(&mut buf, 0);
+
+        let filename = self.filename.file_name().unwrap_or_default();
+
+        writeln!(
+            writer,
+            "[{}:{}] {} = {}",
+            filename.to_string_lossy(),
+            line_col_region.start.line + 1,
+            dbg_source,
+            buf.into_bump_str()
+        )
+    }
+
     pub fn render_panic<W>(
         &self,
         writer: &mut W,
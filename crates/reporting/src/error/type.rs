@@ -8,9 +8,7 @@ use roc_module::called_via::{BinOp, CalledVia};
 use roc_module::ident::{Ident, IdentStr, Lowercase, TagName};
 use roc_module::symbol::Symbol;
 use roc_region::all::{LineInfo, Loc, Region};
-use roc_solve_problem::{
-    NotDerivableContext, NotDerivableDecode, TypeError, UnderivableReason, Unfulfilled,
-};
+use roc_solve_problem::{NotDerivableContext, TypeError, UnderivableReason, Unfulfilled};
 use roc_std::RocDec;
 use roc_types::pretty_print::{Parens, WILDCARD};
 use roc_types::types::{
@@ -419,20 +417,6 @@ fn underivable_hint<'b>(
                 ])),
             ])))
         }
-        NotDerivableContext::Decode(reason) => match reason {
-            NotDerivableDecode::OptionalRecordField(field) => {
-                Some(alloc.note("").append(alloc.concat([
-                    alloc.reflow("I can't derive decoding for a record with an optional field, which in this case is "),
-                    alloc.record_field(field),
-                    alloc.reflow(". Optional record fields are polymorphic over records that may or may not contain them at compile time, "),
-                    alloc.reflow("but are not a concept that extends to runtime!"),
-                    alloc.hardline(),
-                    alloc.reflow("Maybe you wanted to use a "),
-                    alloc.symbol_unqualified(Symbol::RESULT_RESULT),
-                    alloc.reflow("?"),
-                ])))
-            }
-        },
     }
 }
 
@@ -1701,6 +1685,17 @@ fn format_category<'b>(
             ]),
             alloc.text(" is a:"),
         ),
+        Tuple => (
+            alloc.concat([this_is, alloc.text(" a tuple")]),
+            alloc.text(" of type:"),
+        ),
+        TupleAccess(index) => (
+            alloc.concat([
+                alloc.text(format!("{}he value at ", t)),
+                alloc.tuple_elem(*index),
+            ]),
+            alloc.text(" is a:"),
+        ),
         CallResult(
             Some(_),
             CalledVia::BinOp(
@@ -1926,6 +1921,32 @@ fn to_pattern_report<'b>(
                     severity: Severity::RuntimeError,
                 }
             }
+            PReason::ListElem => {
+                let doc = alloc.stack([
+                    alloc.reflow(
+                        "This list element doesn't match the types of other elements in the pattern:",
+                    ),
+                    alloc.region_with_subregion(
+                        lines.convert_region(region),
+                        lines.convert_region(expr_region),
+                    ),
+                    pattern_type_comparison(
+                        alloc,
+                        found,
+                        expected_type,
+                        add_pattern_category(alloc, alloc.text("It matches"), &category),
+                        alloc.reflow("But the other elements in this list pattern match:"),
+                        vec![],
+                    ),
+                ]);
+
+                Report {
+                    filename,
+                    title: "TYPE MISMATCH".to_string(),
+                    doc,
+                    severity: Severity::RuntimeError,
+                }
+            }
             PReason::TagArg { .. } | PReason::PatternGuard => {
                 unreachable!("I didn't think this could trigger. Please tell Folkert about it!")
             }
@@ -1970,6 +1991,8 @@ fn add_pattern_category<'b>(
     let rest = match category {
         Record => alloc.reflow(" record values of type:"),
         EmptyRecord => alloc.reflow(" an empty record:"),
+        Tuple => alloc.reflow(" tuple values of type:"),
+        List => alloc.reflow(" lists of type:"),
         PatternGuard => alloc.reflow(" a pattern guard of type:"),
         PatternDefault => alloc.reflow(" an optional field of type:"),
         Set => alloc.reflow(" sets of type:"),
@@ -2230,7 +2253,14 @@ fn to_doc_help<'b>(
         Error => alloc.text("?"),
 
         FlexVar(lowercase) | RigidVar(lowercase) => alloc.type_variable(lowercase),
-        FlexAbleVar(lowercase, ability) | RigidAbleVar(lowercase, ability) => {
+        FlexAbleVar(lowercase, abilities) => {
+            // TODO we should be putting able variables on the toplevel of the type, not here
+            for ability in abilities {
+                ctx.able_variables.push((lowercase.clone(), ability));
+            }
+            alloc.type_variable(lowercase)
+        }
+        RigidAbleVar(lowercase, ability) => {
             // TODO we should be putting able variables on the toplevel of the type, not here
             ctx.able_variables.push((lowercase.clone(), ability));
             alloc.type_variable(lowercase)
@@ -2317,6 +2347,19 @@ fn to_doc_help<'b>(
             )
         }
 
+        Tuple(elems, ext) => {
+            // Positions we know nothing about (because the tuple is open) are shown as wildcards.
+            let mut elem_docs = Vec::with_capacity(elems.len());
+            for (index, elem) in elems {
+                while elem_docs.len() < index {
+                    elem_docs.push(alloc.type_variable(WILDCARD.into()));
+                }
+                elem_docs.push(to_doc_help(ctx, alloc, Parens::Unnecessary, elem));
+            }
+
+            report_text::tuple(alloc, elem_docs, ext_to_doc(alloc, ext))
+        }
+
         TagUnion(tags_map, ext) => {
             let mut tags = tags_map
                 .into_iter()
@@ -2393,13 +2436,24 @@ fn same<'b>(
 fn type_with_able_vars<'b>(
     alloc: &'b RocDocAllocator<'b>,
     typ: RocDocBuilder<'b>,
-    able: AbleVariables,
+    mut able: AbleVariables,
 ) -> RocDocBuilder<'b> {
     if able.is_empty() {
         // fast path: taken the vast majority of the time
         return typ;
     }
 
+    // The same variable may be reached more than once while walking the type.
+    let mut seen = Vec::with_capacity(able.len());
+    able.retain(|entry| {
+        if seen.contains(entry) {
+            false
+        } else {
+            seen.push(entry.clone());
+            true
+        }
+    });
+
     let mut doc = Vec::with_capacity(1 + 6 * able.len());
     doc.push(typ);
 
@@ -3247,6 +3301,24 @@ mod report_text {
         }
     }
 
+    pub fn tuple<'b>(
+        alloc: &'b RocDocAllocator<'b>,
+        elems: Vec<RocDocBuilder<'b>>,
+        opt_ext: Option<RocDocBuilder<'b>>,
+    ) -> RocDocBuilder<'b> {
+        let ext_doc = if let Some(t) = opt_ext {
+            t
+        } else {
+            alloc.nil()
+        };
+
+        alloc
+            .reflow("( ")
+            .append(alloc.intersperse(elems, alloc.reflow(", ")))
+            .append(alloc.reflow(" )"))
+            .append(ext_doc)
+    }
+
     pub fn to_suggestion_record<'b>(
         alloc: &'b RocDocAllocator<'b>,
         f: (Lowercase, RecordField<ErrorType>),
@@ -3582,16 +3654,26 @@ fn type_problem_to_pretty<'b>(
 
             match tipe {
                 Infinite | Error | FlexVar(_) => alloc.nil(),
-                FlexAbleVar(_, ability) => bad_rigid_var(
+                FlexAbleVar(_, abilities) => bad_rigid_var(
                     x,
                     alloc.concat([
-                        alloc.reflow("an instance of the ability "),
-                        alloc.symbol_unqualified(ability),
+                        alloc.reflow(if abilities.len() == 1 {
+                            "an instance of the ability "
+                        } else {
+                            "an instance of the abilities "
+                        }),
+                        alloc.intersperse(
+                            abilities
+                                .into_iter()
+                                .map(|ability| alloc.symbol_unqualified(ability)),
+                            alloc.reflow(" and "),
+                        ),
                     ]),
                 ),
                 RigidVar(y) | RigidAbleVar(y, _) => bad_double_rigid(x, y),
                 Function(_, _, _) => bad_rigid_var(x, alloc.reflow("a function value")),
                 Record(_, _) => bad_rigid_var(x, alloc.reflow("a record value")),
+                Tuple(_, _) => bad_rigid_var(x, alloc.reflow("a tuple value")),
                 TagUnion(_, _) | RecursiveTagUnion(_, _, _) => {
                     bad_rigid_var(x, alloc.reflow("a tag value"))
                 }
@@ -3973,7 +4055,7 @@ fn pattern_to_doc_help<'b>(
     use roc_can::exhaustive::{GUARD_CTOR, NONEXHAUSIVE_CTOR};
     use roc_exhaustive::Literal::*;
     use roc_exhaustive::Pattern::*;
-    use roc_exhaustive::RenderAs;
+    use roc_exhaustive::{ListArity, RenderAs};
 
     match pattern {
         Anything => alloc.text("_"),
@@ -4012,7 +4094,7 @@ fn pattern_to_doc_help<'b>(
                             Anything => {
                                 arg_docs.push(alloc.text(label.to_string()));
                             }
-                            Literal(_) | Ctor(_, _, _) => {
+                            Literal(_) | Ctor(_, _, _) | List(_, _) => {
                                 arg_docs.push(
                                     alloc
                                         .text(label.to_string())
@@ -4028,6 +4110,16 @@ fn pattern_to_doc_help<'b>(
                         .append(alloc.intersperse(arg_docs, alloc.reflow(", ")))
                         .append(" }")
                 }
+                RenderAs::Tuple => {
+                    let arg_docs = args
+                        .into_iter()
+                        .map(|v| pattern_to_doc_help(alloc, v, false));
+
+                    alloc
+                        .text("( ")
+                        .append(alloc.intersperse(arg_docs, alloc.reflow(", ")))
+                        .append(" )")
+                }
                 RenderAs::Tag | RenderAs::Opaque => {
                     let ctor = &union.alternatives[tag_id.0 as usize];
                     match &ctor.name {
@@ -4070,5 +4162,20 @@ fn pattern_to_doc_help<'b>(
                 }
             }
         }
+        List(arity, args) => {
+            let mut arg_docs: Vec<_> = args
+                .into_iter()
+                .map(|v| pattern_to_doc_help(alloc, v, false))
+                .collect();
+
+            if let ListArity::Slice(before, _) = arity {
+                arg_docs.insert(before, alloc.text(".."));
+            }
+
+            alloc
+                .text("[")
+                .append(alloc.intersperse(arg_docs, alloc.reflow(", ")))
+                .append("]")
+        }
     }
 }
@@ -446,6 +446,11 @@ impl<'a> RocDocAllocator<'a> {
             .annotate(Annotation::RecordField)
     }
 
+    pub fn tuple_elem(&'a self, index: usize) -> DocBuilder<'a, Self, Annotation> {
+        self.text(format!(".{}", index))
+            .annotate(Annotation::RecordField)
+    }
+
     pub fn module(&'a self, module_id: ModuleId) -> DocBuilder<'a, Self, Annotation> {
         let name = self.interns.module_name(module_id);
         let name = if name.is_empty() {
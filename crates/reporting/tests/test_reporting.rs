@@ -2098,7 +2098,7 @@ mod test_reporting {
         Ok
         U8
         Box
-        f
+        Eq
     "###
     );
 
@@ -3616,7 +3616,7 @@ mod test_reporting {
         Set
         List
         Dict
-        Result
+        Hash
 
     ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
 
@@ -5423,7 +5423,7 @@ All branches in an `if` must have the same type!
         part_starts_with_number,
         indoc!(
             r#"
-            foo.100
+            foo.1x
             "#
         ),
         @r###"
@@ -5431,7 +5431,7 @@ All branches in an `if` must have the same type!
 
     I trying to parse a record field access here:
 
-    4│      foo.100
+    4│      foo.1x
                 ^
 
     So I expect to see a lowercase letter next, like .name or .height.
@@ -5912,10 +5912,12 @@ All branches in an `if` must have the same type!
     here:
 
     4│      \( a,
-                ^
+                 ^
 
     I was expecting to see a closing parenthesis before this, so try
     adding a ) and see if that helps?
+
+    Note: I may be confused by indentation
     "###
     );
 
@@ -6246,7 +6248,7 @@ All branches in an `if` must have the same type!
         inference_var_conflict_in_rigid_links,
         indoc!(
             r#"
-            f : a -> (_ -> b)
+            f : a -> (_ -> b) | a has Eq
             f = \x -> \y -> if x == y then x else y
             f
             "#
@@ -6257,19 +6259,19 @@ All branches in an `if` must have the same type!
 
     Something is off with the body of the `f` definition:
 
-    4│      f : a -> (_ -> b)
+    4│      f : a -> (_ -> b) | a has Eq
     5│      f = \x -> \y -> if x == y then x else y
                       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
 
     The body is an anonymous function of type:
 
-        a -> a
+        a -> a | a has Eq
 
     But the type annotation on `f` says it should be:
 
-        a -> b
+        a -> b | a has Eq
 
-    Tip: Your type annotation uses `a` and `b` as separate type variables.
+    Tip: Your type annotation uses `b` and `a` as separate type variables.
     Your code seems to be saying they are the same though. Maybe they
     should be the same in your type annotation? Maybe your code uses them
     in a weird way?
@@ -7966,8 +7968,8 @@ All branches in an `if` must have the same type!
         ability_first_demand_not_indented_enough,
         indoc!(
             r#"
-            Eq has
-            eq : a, a -> U64 | a has Eq
+            MEq has
+            eq : a, a -> U64 | a has MEq
 
             1
             "#
@@ -7978,8 +7980,8 @@ All branches in an `if` must have the same type!
     I was partway through parsing an ability definition, but I got stuck
     here:
 
-    4│      Eq has
-    5│      eq : a, a -> U64 | a has Eq
+    4│      MEq has
+    5│      eq : a, a -> U64 | a has MEq
             ^
 
     I suspect this line is not indented enough (by 1 spaces)
@@ -7990,9 +7992,9 @@ All branches in an `if` must have the same type!
         ability_demands_not_indented_with_first,
         indoc!(
             r#"
-            Eq has
-                eq : a, a -> U64 | a has Eq
-                    neq : a, a -> U64 | a has Eq
+            MEq has
+                eq : a, a -> U64 | a has MEq
+                    neq : a, a -> U64 | a has MEq
 
             1
             "#
@@ -8003,8 +8005,8 @@ All branches in an `if` must have the same type!
         I was partway through parsing an ability definition, but I got stuck
         here:
 
-        5│          eq : a, a -> U64 | a has Eq
-        6│              neq : a, a -> U64 | a has Eq
+        5│          eq : a, a -> U64 | a has MEq
+        6│              neq : a, a -> U64 | a has MEq
                         ^
 
         I suspect this line is indented too much (by 4 spaces)"#
@@ -8014,8 +8016,8 @@ All branches in an `if` must have the same type!
         ability_demand_value_has_args,
         indoc!(
             r#"
-                Eq has
-                    eq b c : a, a -> U64 | a has Eq
+                MEq has
+                    eq b c : a, a -> U64 | a has MEq
 
                 1
                 "#
@@ -8026,7 +8028,7 @@ All branches in an `if` must have the same type!
         I was partway through parsing an ability definition, but I got stuck
         here:
 
-        5│          eq b c : a, a -> U64 | a has Eq
+        5│          eq b c : a, a -> U64 | a has MEq
                        ^
 
         I was expecting to see a : annotating the signature of this value
@@ -8037,7 +8039,7 @@ All branches in an `if` must have the same type!
         ability_non_signature_expression,
         indoc!(
             r#"
-            Eq has
+            MEq has
                 123
 
             1
@@ -8049,7 +8051,7 @@ All branches in an `if` must have the same type!
     I was partway through parsing an ability definition, but I got stuck
     here:
 
-    4│      Eq has
+    4│      MEq has
     5│          123
                 ^
 
@@ -8175,30 +8177,30 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [] to "./platform"
 
-            Hash a b c has
-              hash : a -> U64 | a has Hash
+            MHash a b c has
+              hash : a -> U64 | a has MHash
             "#
         ),
         @r#"
         ── ABILITY HAS TYPE VARIABLES ──────────────────────────── /code/proj/Main.roc ─
 
-        The definition of the `Hash` ability includes type variables:
+        The definition of the `MHash` ability includes type variables:
 
-        3│  Hash a b c has
-                 ^^^^^
+        3│  MHash a b c has
+                  ^^^^^
 
         Abilities cannot depend on type variables, but their member values
         can!
 
         ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
 
-        `Hash` is not used anywhere in your code.
+        `MHash` is not used anywhere in your code.
 
-        3│  Hash a b c has
-            ^^^^
+        3│  MHash a b c has
+            ^^^^^
 
-        If you didn't intend on using `Hash` then remove it so future readers of
-        your code don't wonder why it is there.
+        If you didn't intend on using `MHash` then remove it so future readers
+        of your code don't wonder why it is there.
         "#
     );
 
@@ -8208,7 +8210,7 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash] to "./platform"
 
-            Hash has hash : a, b -> Num.U64 | a has Hash, b has Bool.Bool
+            MHash has hash : a, b -> Num.U64 | a has MHash, b has Bool.Bool
             "#
         ),
         @r#"
@@ -8216,8 +8218,8 @@ All branches in an `if` must have the same type!
 
         The type referenced in this "has" clause is not an ability:
 
-        3│  Hash has hash : a, b -> Num.U64 | a has Hash, b has Bool.Bool
-                                                                ^^^^^^^^^
+        3│  MHash has hash : a, b -> Num.U64 | a has MHash, b has Bool.Bool
+                                                                  ^^^^^^^^^
         "#
     );
 
@@ -8320,23 +8322,23 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [] to "./platform"
 
-            Eq has eq : a, b -> Bool.Bool | a has Eq, b has Eq
+            MEq has eq : a, b -> Bool.Bool | a has MEq, b has MEq
             "#
         ),
         @r#"
         ── ABILITY MEMBER BINDS MULTIPLE VARIABLES ─────────────── /code/proj/Main.roc ─
 
         The definition of the ability member `eq` includes multiple variables
-        bound to the `Eq`` ability:`
+        bound to the `MEq`` ability:`
 
-        3│  Eq has eq : a, b -> Bool.Bool | a has Eq, b has Eq
-                                            ^^^^^^^^^^^^^^^^^^
+        3│  MEq has eq : a, b -> Bool.Bool | a has MEq, b has MEq
+                                             ^^^^^^^^^^^^^^^^^^^^
 
         Ability members can only bind one type variable to their parent
         ability. Otherwise, I wouldn't know what type implements an ability by
         looking at specializations!
 
-        Hint: Did you mean to only bind `a` to `Eq`?
+        Hint: Did you mean to only bind `a` to `MEq`?
         "#
     );
 
@@ -8346,9 +8348,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [f] to "./platform"
 
-            Hash has hash : (a | a has Hash) -> Num.U64
+            MHash has hash : (a | a has MHash) -> Num.U64
 
-            f : a -> Num.U64 | a has Hash
+            f : a -> Num.U64 | a has MHash
             "#
         ),
         @r#"
@@ -8356,23 +8358,23 @@ All branches in an `if` must have the same type!
 
         A `has` clause is not allowed here:
 
-        3│  Hash has hash : (a | a has Hash) -> Num.U64
-                                 ^^^^^^^^^^
+        3│  MHash has hash : (a | a has MHash) -> Num.U64
+                                  ^^^^^^^^^^^
 
         `has` clauses can only be specified on the top-level type annotations.
 
         ── ABILITY MEMBER MISSING HAS CLAUSE ───────────────────── /code/proj/Main.roc ─
 
         The definition of the ability member `hash` does not include a `has`
-        clause binding a type variable to the ability `Hash`:
+        clause binding a type variable to the ability `MHash`:
 
-        3│  Hash has hash : (a | a has Hash) -> Num.U64
-                     ^^^^
+        3│  MHash has hash : (a | a has MHash) -> Num.U64
+                      ^^^^
 
         Ability members must include a `has` clause binding a type variable to
         an ability, like
 
-            a has Hash
+            a has MHash
 
         Otherwise, the function does not need to be part of the ability!
         "#
@@ -8384,9 +8386,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash] to "./platform"
 
-            Hash has hash : a -> U64 | a has Hash
+            MHash has hash : a -> U64 | a has MHash
 
-            Id := U32 has [Hash {hash}]
+            Id := U32 has [MHash {hash}]
 
             hash = \@Id n -> n
             "#
@@ -8415,11 +8417,11 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [eq, le] to "./platform"
 
-            Eq has
-                eq : a, a -> Bool | a has Eq
-                le : a, a -> Bool | a has Eq
+            MEq has
+                eq : a, a -> Bool | a has MEq
+                le : a, a -> Bool | a has MEq
 
-            Id := U64 has [Eq {eq}]
+            Id := U64 has [MEq {eq}]
 
             eq = \@Id m, @Id n -> m == n
             "#
@@ -8427,10 +8429,10 @@ All branches in an `if` must have the same type!
         @r###"
     ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    7│  Id := U64 has [Eq {eq}]
-                       ^^^^^^^
+    7│  Id := U64 has [MEq {eq}]
+                       ^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -8444,8 +8446,8 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
             hash = \_ -> 0u64
             "#
@@ -8469,11 +8471,11 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash, One, Two] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            One := {} has [Hash {hash}]
-            Two := {} has [Hash {hash}]
+            One := {} has [MHash {hash}]
+            Two := {} has [MHash {hash}]
 
             hash = \_ -> 0u64
             "#
@@ -8485,8 +8487,8 @@ All branches in an `if` must have the same type!
     This ability member specialization is already claimed to specialize
     another opaque type:
 
-    7│  Two := {} has [Hash {hash}]
-                             ^^^^
+    7│  Two := {} has [MHash {hash}]
+                              ^^^^
 
     Previously, we found it to specialize `hash` for `One`.
 
@@ -8506,7 +8508,7 @@ All branches in an `if` must have the same type!
 
     But the type annotation on `hash` says it must match:
 
-        a -> U64 | a has Hash
+        a -> U64 | a has MHash
 
     Note: The specialized type is too general, and does not provide a
     concrete type where a type variable is bound to an ability.
@@ -8523,11 +8525,11 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash, One, Two] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            One := {} has [Hash {hash}]
-            Two := {} has [Hash {hash}]
+            One := {} has [MHash {hash}]
+            Two := {} has [MHash {hash}]
 
             hash = \@One _ -> 0u64
             "#
@@ -8538,8 +8540,8 @@ All branches in an `if` must have the same type!
     This ability member specialization is already claimed to specialize
     another opaque type:
 
-    7│  Two := {} has [Hash {hash}]
-                             ^^^^
+    7│  Two := {} has [MHash {hash}]
+                              ^^^^
 
     Previously, we found it to specialize `hash` for `One`.
 
@@ -8554,10 +8556,10 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [eq] to "./platform"
 
-            Eq has
-                eq : a, a -> Bool | a has Eq
+            MEq has
+                eq : a, a -> Bool | a has MEq
 
-            You := {} has [Eq {eq}]
+            You := {} has [MEq {eq}]
             AndI := {}
 
             eq = \@You {}, @AndI {} -> False
@@ -8592,10 +8594,10 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            Id := U64 has [Hash {hash}]
+            Id := U64 has [MHash {hash}]
 
             hash : Id -> U32
             hash = \@Id n -> n
@@ -8641,10 +8643,10 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [noGoodVeryBadTerrible] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            Id := U64 has [Hash {hash}]
+            Id := U64 has [MHash {hash}]
 
             hash = \@Id n -> n
 
@@ -8665,7 +8667,7 @@ All branches in an `if` must have the same type!
     15│          notYet: hash (A 1),
                                ^^^
 
-    Roc can't generate an implementation of the `#UserApp.Hash` ability for
+    Roc can't generate an implementation of the `#UserApp.MHash` ability for
 
         [A (Num a)]b
 
@@ -8678,7 +8680,7 @@ All branches in an `if` must have the same type!
     14│          nope: hash (@User {}),
                              ^^^^^^^^
 
-    The type `User` does not fully implement the ability `Hash`.
+    The type `User` does not fully implement the ability `MHash`.
     "###
     );
 
@@ -8689,8 +8691,8 @@ All branches in an `if` must have the same type!
             app "test" provides [main] to "./platform"
 
             main =
-                Hash has
-                    hash : a -> U64 | a has Hash
+                MHash has
+                    hash : a -> U64 | a has MHash
 
                 123
             "#
@@ -8700,8 +8702,8 @@ All branches in an `if` must have the same type!
 
         This ability definition is not on the top-level of a module:
 
-        4│>      Hash has
-        5│>          hash : a -> U64 | a has Hash
+        4│>      MHash has
+        5│>          hash : a -> U64 | a has MHash
 
         Abilities can only be defined on the top-level of a Roc module.
         "#
@@ -8713,13 +8715,13 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash, hashable] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            Id := U64 has [Hash {hash}]
+            Id := U64 has [MHash {hash}]
             hash = \@Id n -> n
 
-            hashable : a | a has Hash
+            hashable : a | a has MHash
             hashable = @Id 15
             "#
         ),
@@ -8728,7 +8730,7 @@ All branches in an `if` must have the same type!
 
         Something is off with the body of the `hashable` definition:
 
-         9│  hashable : a | a has Hash
+         9│  hashable : a | a has MHash
         10│  hashable = @Id 15
                         ^^^^^^
 
@@ -8738,11 +8740,11 @@ All branches in an `if` must have the same type!
 
         But the type annotation on `hashable` says it should be:
 
-            a | a has Hash
+            a | a has MHash
 
         Tip: The type annotation uses the type variable `a` to say that this
-        definition can produce any value implementing the `Hash` ability. But in
-        the body I see that it will only produce a `Id` value of a single
+        definition can produce any value implementing the `MHash` ability. But
+        in the body I see that it will only produce a `Id` value of a single
         specific type. Maybe change the type annotation to be more specific?
         Maybe change the code to be more general?
         "#
@@ -8754,16 +8756,16 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [result] to "./platform"
 
-            Hash has
-                hash : a -> U64 | a has Hash
+            MHash has
+                hash : a -> U64 | a has MHash
 
-            mulHashes : Hash, Hash -> U64
+            mulHashes : MHash, MHash -> U64
             mulHashes = \x, y -> hash x * hash y
 
-            Id := U64 has [Hash {hash: hashId}]
+            Id := U64 has [MHash {hash: hashId}]
             hashId = \@Id n -> n
 
-            Three := {} has [Hash {hash: hashThree}]
+            Three := {} has [MHash {hash: hashThree}]
             hashThree = \@Three _ -> 3
 
             result = mulHashes (@Id 100) (@Three {})
@@ -8772,31 +8774,31 @@ All branches in an `if` must have the same type!
         @r#"
         ── ABILITY USED AS TYPE ────────────────────────────────── /code/proj/Main.roc ─
 
-        You are attempting to use the ability `Hash` as a type directly:
+        You are attempting to use the ability `MHash` as a type directly:
 
-        6│  mulHashes : Hash, Hash -> U64
-                        ^^^^
+        6│  mulHashes : MHash, MHash -> U64
+                        ^^^^^
 
         Abilities can only be used in type annotations to constrain type
         variables.
 
         Hint: Perhaps you meant to include a `has` annotation, like
 
-            a has Hash
+            a has MHash
 
         ── ABILITY USED AS TYPE ────────────────────────────────── /code/proj/Main.roc ─
 
-        You are attempting to use the ability `Hash` as a type directly:
+        You are attempting to use the ability `MHash` as a type directly:
 
-        6│  mulHashes : Hash, Hash -> U64
-                              ^^^^
+        6│  mulHashes : MHash, MHash -> U64
+                               ^^^^^
 
         Abilities can only be used in type annotations to constrain type
         variables.
 
         Hint: Perhaps you meant to include a `has` annotation, like
 
-            b has Hash
+            b has MHash
         "#
     );
 
@@ -9179,9 +9181,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> U64 | a has Eq
+            MEq has eq : a, a -> U64 | a has MEq
 
-            A := U8 has [Eq {eq}]
+            A := U8 has [MEq {eq}]
             "#
         ),
         @r###"
@@ -9189,8 +9191,8 @@ All branches in an `if` must have the same type!
 
     An implementation of `eq` could not be found in this scope:
 
-    5│  A := U8 has [Eq {eq}]
-                         ^^
+    5│  A := U8 has [MEq {eq}]
+                          ^^
 
     Tip: consider adding a value of name `eq` in this scope, or using
     another variable that implements this ability member, like
@@ -9198,10 +9200,10 @@ All branches in an `if` must have the same type!
 
     ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [Eq {eq}]
-                     ^^^^^^^
+    5│  A := U8 has [MEq {eq}]
+                     ^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9215,9 +9217,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A, myEq] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq: aEq} ]
+            A := U8 has [ MEq {eq: aEq} ]
 
             myEq = \m, n -> m == n
             "#
@@ -9227,22 +9229,22 @@ All branches in an `if` must have the same type!
 
     Nothing is named `aEq` in this scope.
 
-    5│  A := U8 has [ Eq {eq: aEq} ]
-                              ^^^
+    5│  A := U8 has [ MEq {eq: aEq} ]
+                               ^^^
 
     Did you mean one of these?
 
         Eq
-        myEq
+        MEq
         eq
-        U8
+        myEq
 
     ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq: aEq} ]
-                      ^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq: aEq} ]
+                      ^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9256,9 +9258,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A, myEq] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq ? aEq} ]
+            A := U8 has [ MEq {eq ? aEq} ]
 
             myEq = \m, n -> m == n
             "#
@@ -9268,8 +9270,8 @@ All branches in an `if` must have the same type!
 
     Ability implementations cannot be optional:
 
-    5│  A := U8 has [ Eq {eq ? aEq} ]
-                          ^^^^^^^^
+    5│  A := U8 has [ MEq {eq ? aEq} ]
+                           ^^^^^^^^
 
     Custom implementations must be supplied fully.
 
@@ -9277,10 +9279,10 @@ All branches in an `if` must have the same type!
 
     ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq ? aEq} ]
-                      ^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq ? aEq} ]
+                      ^^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9334,9 +9336,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq : Bool.eq} ]
+            A := U8 has [ MEq {eq : Bool.eq} ]
             "#
         ),
         @r###"
@@ -9344,18 +9346,18 @@ All branches in an `if` must have the same type!
 
     This ability implementation is qualified:
 
-    5│  A := U8 has [ Eq {eq : Bool.eq} ]
-                               ^^^^^^^
+    5│  A := U8 has [ MEq {eq : Bool.eq} ]
+                                ^^^^^^^
 
     Custom implementations must be defined in the local scope, and
     unqualified.
 
     ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq : Bool.eq} ]
-                      ^^^^^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq : Bool.eq} ]
+                      ^^^^^^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9369,9 +9371,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq : \m, n -> m == n} ]
+            A := U8 has [ MEq {eq : \m, n -> m == n} ]
             "#
         ),
         @r###"
@@ -9379,8 +9381,8 @@ All branches in an `if` must have the same type!
 
     This ability implementation is not an identifier:
 
-    5│  A := U8 has [ Eq {eq : \m, n -> m == n} ]
-                               ^^^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq : \m, n -> m == n} ]
+                                ^^^^^^^^^^^^^^^
 
     Custom ability implementations defined in this position can only be
     unqualified identifiers, not arbitrary expressions.
@@ -9389,10 +9391,10 @@ All branches in an `if` must have the same type!
 
     ── INCOMPLETE ABILITY IMPLEMENTATION ───────────────────── /code/proj/Main.roc ─
 
-    This type does not fully implement the `Eq` ability:
+    This type does not fully implement the `MEq` ability:
 
-    5│  A := U8 has [ Eq {eq : \m, n -> m == n} ]
-                      ^^^^^^^^^^^^^^^^^^^^^^^^^
+    5│  A := U8 has [ MEq {eq : \m, n -> m == n} ]
+                      ^^^^^^^^^^^^^^^^^^^^^^^^^^
 
     The following necessary members are missing implementations:
 
@@ -9406,9 +9408,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [A] to "./platform"
 
-            Eq has eq : a, a -> Bool | a has Eq
+            MEq has eq : a, a -> Bool | a has MEq
 
-            A := U8 has [ Eq {eq: eqA, eq: eqA} ]
+            A := U8 has [ MEq {eq: eqA, eq: eqA} ]
 
             eqA = \@A m, @A n -> m == n
             "#
@@ -9418,13 +9420,13 @@ All branches in an `if` must have the same type!
 
     This ability member implementation is duplicate:
 
-    5│  A := U8 has [ Eq {eq: eqA, eq: eqA} ]
-                                   ^^^^^^^
+    5│  A := U8 has [ MEq {eq: eqA, eq: eqA} ]
+                                    ^^^^^^^
 
     The first implementation was defined here:
 
-    5│  A := U8 has [ Eq {eq: eqA, eq: eqA} ]
-                          ^^^^^^^
+    5│  A := U8 has [ MEq {eq: eqA, eq: eqA} ]
+                           ^^^^^^^
 
     Only one custom implementation can be defined for an ability member.
     "###
@@ -10082,7 +10084,7 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash, Id] to "./platform"
 
-            Hash has hash : a -> U64 | a has Hash
+            MHash has hash : a -> U64 | a has MHash
 
             Id := {}
 
@@ -10108,9 +10110,9 @@ All branches in an `if` must have the same type!
             r#"
             app "test" provides [hash, Id, Id2] to "./platform"
 
-            Hash has hash : a -> U64 | a has Hash
+            MHash has hash : a -> U64 | a has MHash
 
-            Id := {} has [Hash {hash}]
+            Id := {} has [MHash {hash}]
             Id2 := {}
 
             hash = \@Id2 _ -> 0
@@ -10501,40 +10503,6 @@ All branches in an `if` must have the same type!
     "###
     );
 
-    test_report!(
-        record_with_optional_field_types_cannot_derive_decoding,
-        indoc!(
-            r#"
-             app "test" imports [Decode.{decoder}] provides [main] to "./platform"
-
-             main =
-                 myDecoder : Decoder {x : Str, y ? Str} fmt | fmt has DecoderFormatting
-                 myDecoder = decoder
-
-                 myDecoder
-             "#
-        ),
-        @r###"
-     ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
-
-     This expression has a type that does not implement the abilities it's expected to:
-
-     5│      myDecoder = decoder
-                         ^^^^^^^
-
-     Roc can't generate an implementation of the `Decode.Decoding` ability
-     for
-
-         { x : Str, y ? Str }
-
-     Note: I can't derive decoding for a record with an optional field,
-     which in this case is `.y`. Optional record fields are polymorphic over
-     records that may or may not contain them at compile time, but are not
-     a concept that extends to runtime!
-     Maybe you wanted to use a `Result`?
-     "###
-    );
-
     test_report!(
         uninhabited_type_is_trivially_exhaustive,
         indoc!(
@@ -10687,4 +10655,231 @@ All branches in an `if` must have the same type!
     be safely removed!
     "###
     );
+
+    test_report!(
+        function_cannot_derive_eq,
+        indoc!(
+            r#"
+            f = \x -> x + 1
+
+            f == f
+            "#
+        ),
+    @r###"
+    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+
+    This expression has a type that does not implement the abilities it's expected to:
+
+    6│      f == f
+            ^
+
+    Roc can't generate an implementation of the `Bool.Eq` ability for
+
+        Num a -> Num a
+
+    Note: `Eq` cannot be generated for functions.
+    "###
+    );
+
+    test_report!(
+        opaque_without_eq_compared,
+        indoc!(
+            r#"
+            Id := U64
+
+            @Id 1 == @Id 2
+            "#
+        ),
+    @r###"
+    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+
+    This expression has a type that does not implement the abilities it's expected to:
+
+    6│      @Id 1 == @Id 2
+            ^^^^^
+
+    The type `Id` does not fully implement the ability `Eq`.
+    "###
+    );
+
+    test_report!(
+        list_pattern_not_exhaustive,
+        indoc!(
+            r#"
+            when [] is
+                [] -> ""
+                [_] -> ""
+                [_, .., A] -> ""
+            "#
+        ),
+    @r###"
+    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+
+    This `when` does not cover all the possibilities:
+
+    4│>      when [] is
+    5│>          [] -> ""
+    6│>          [_] -> ""
+    7│>          [_, .., A] -> ""
+
+    Other possibilities include:
+
+        [_, .., _]
+
+    I would have to crash if I saw one of those! Add branches for them!
+    "###
+    );
+
+    test_report!(
+        list_pattern_redundant,
+        indoc!(
+            r#"
+            when [] is
+                [_, ..] -> ""
+                [_, _] -> ""
+                _ -> ""
+            "#
+        ),
+    @r###"
+    ── REDUNDANT PATTERN ───────────────────────────────────── /code/proj/Main.roc ─
+
+    The 2nd pattern is redundant:
+
+    4│       when [] is
+    5│           [_, ..] -> ""
+    6│>          [_, _] -> ""
+    7│           _ -> ""
+
+    Any value of this shape will be handled by a previous pattern, so this
+    one should be removed.
+    "###
+    );
+
+    test_report!(
+        list_pattern_element_type_mismatch,
+        indoc!(
+            r#"
+            when [] is
+                [1, "b"] -> ""
+                _ -> ""
+            "#
+        ),
+    @r###"
+    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+
+    This list element doesn't match the types of other elements in the
+    pattern:
+
+    5│          [1, "b"] -> ""
+                    ^^^
+
+    It matches strings:
+
+        Str
+
+    But the other elements in this list pattern match:
+
+        Num a
+    "###
+    );
+
+    test_report!(
+        list_pattern_multiple_rests,
+        indoc!(
+            r#"
+            when [] is
+                [.., 1, ..] -> ""
+                _ -> ""
+            "#
+        ),
+    @r###"
+    ── MULTIPLE LIST REST PATTERNS ─────────────────────────── /code/proj/Main.roc ─
+
+    This list pattern match has multiple rest patterns:
+
+    5│          [.., 1, ..] -> ""
+                        ^^
+
+    I only support compiling list patterns with one .. pattern! Can you
+    remove this additional one?
+    "###
+    );
+
+    test_report!(
+        tuple_access_out_of_bounds,
+        indoc!(
+            r#"
+            ("a", 1).2
+            "#
+        ),
+    @r###"
+    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+
+    This expression is used in an unexpected way:
+
+    4│      ("a", 1).2
+            ^^^^^^^^^^
+
+    It is a tuple of type:
+
+        ( Str, Num a )
+
+    But you are trying to use it as:
+
+        ( *, *, a )b
+    "###
+    );
+
+    test_report!(
+        tuple_pattern_not_exhaustive,
+        indoc!(
+            r#"
+            when (1, "a") is
+                (0, s) -> s
+            "#
+        ),
+    @r###"
+    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+
+    This `when` does not cover all the possibilities:
+
+    4│>      when (1, "a") is
+    5│>          (0, s) -> s
+
+    Other possibilities include:
+
+        ( _, _ )
+
+    I would have to crash if I saw one of those! Add branches for them!
+    "###
+    );
+
+    test_report!(
+        tuple_type_mismatch,
+        indoc!(
+            r#"
+            t : (Str, U8)
+            t = ("a", "b")
+
+            t
+            "#
+        ),
+    @r###"
+    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+
+    Something is off with the body of the `t` definition:
+
+    4│      t : (Str, U8)
+    5│      t = ("a", "b")
+                ^^^^^^^^^^
+
+    The body is a tuple of type:
+
+        ( Str, Str )
+
+    But the type annotation on `t` says it should be:
+
+        ( Str, U8 )
+    "###
+    );
 }
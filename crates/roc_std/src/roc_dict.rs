@@ -1,12 +1,23 @@
 use crate::roc_list::RocList;
 use core::{
+    cmp::Ordering,
     fmt::{self, Debug},
-    hash::Hash,
+    hash::{Hash, Hasher},
     mem::{align_of, ManuallyDrop},
 };
 
-/// At the moment, Roc's Dict is just an association list. Its lookups are O(n) but
-/// we haven't grown such big programs that it's a problem yet!
+/// Roc's Dict is a hash table. Its entries are stored contiguously in `data`, in
+/// insertion order, while `data_indices` and `metadata` make up the index that
+/// Roc uses to look up keys by their hash.
+///
+/// The index can only be built by Roc, because Rust can't compute the hashes Roc
+/// would give the keys. So dictionaries constructed here are left unindexed, with an
+/// empty `data_indices` and `metadata`; Roc treats those as a plain list of entries
+/// and builds the index the first time the dictionary is modified.
+///
+/// Comparisons and hashing only look at the entries, like `==` in Roc does, since the
+/// index depends on how the dictionary was built. The order of the entries is left out
+/// too, because removing an entry moves the last one into its place.
 ///
 /// We do some things in this data structure that only make sense because the
 /// memory is managed in Roc:
@@ -15,51 +26,84 @@ use core::{
 ///    since Roc owns the memory, not rust.
 /// 2. We use a union for [`RocDictItem`] instead of just a struct. See the
 ///    comment on that data structure for why.
-#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[repr(transparent)]
-pub struct RocDict<K, V>(RocList<RocDictItem<K, V>>);
+#[derive(Default, Clone)]
+#[repr(C)]
+pub struct RocDict<K, V> {
+    // NOTE: the fields must be in the order Roc lays out the record backing a Dict,
+    // which is by alignment first and then alphabetically.
+    data: RocList<RocDictItem<K, V>>,
+    data_indices: RocList<usize>,
+    deleted_slots: usize,
+    metadata: RocList<i8>,
+}
 
 impl<K, V> RocDict<K, V> {
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.data.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.data.is_empty()
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(RocList::with_capacity(capacity))
+        Self {
+            data: RocList::with_capacity(capacity),
+            data_indices: RocList::empty(),
+            deleted_slots: 0,
+            metadata: RocList::empty(),
+        }
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
-        self.0.iter().map(|item| (item.key(), item.value()))
+        self.data.iter().map(|item| (item.key(), item.value()))
     }
 
     pub fn iter_keys(&self) -> impl Iterator<Item = &K> {
-        self.0.iter().map(|item| item.key())
+        self.data.iter().map(|item| item.key())
     }
 
     pub fn iter_values(&self) -> impl Iterator<Item = &V> {
-        self.0.iter().map(|item| item.value())
+        self.data.iter().map(|item| item.value())
     }
-}
 
-impl<K: Hash, V> RocDict<K, V> {
-    pub fn from_iter<I: Iterator<Item = (K, V)>>(src: I) -> Self {
-        let mut ret = Self::with_capacity(src.size_hint().0);
+    /// Iterates over the entries ordered by key, without allocating. Keys are distinct,
+    /// so each step looks for the smallest key greater than the previous one.
+    fn iter_by_key(&self) -> impl Iterator<Item = (&K, &V)>
+    where
+        K: PartialOrd,
+    {
+        let first = self.next_by_key(None);
 
-        for (key, val) in src {
-            unsafe {
-                ret.insert_unchecked(key, val);
-            }
-        }
+        core::iter::successors(first, move |(prev, _)| self.next_by_key(Some(prev)))
+    }
 
-        ret
+    fn next_by_key(&self, prev: Option<&K>) -> Option<(&K, &V)>
+    where
+        K: PartialOrd,
+    {
+        self.iter()
+            .filter(|(key, _)| match prev {
+                Some(prev) => *key > prev,
+                None => true,
+            })
+            .fold(None, |next, (key, value)| match next {
+                Some((next_key, _)) if next_key <= key => next,
+                _ => Some((key, value)),
+            })
     }
+}
 
-    unsafe fn insert_unchecked(&mut self, _key: K, _val: V) {
-        todo!();
+impl<K: Hash, V> RocDict<K, V> {
+    /// Builds an unindexed dictionary from the given entries. The entries must have
+    /// distinct keys.
+    pub fn from_iter<I: Iterator<Item = (K, V)>>(src: I) -> Self {
+        Self {
+            data: src.map(|(key, val)| RocDictItem::new(key, val)).collect(),
+            data_indices: RocList::empty(),
+            deleted_slots: 0,
+            metadata: RocList::empty(),
+        }
     }
 }
 
@@ -76,7 +120,7 @@ impl<'a, K, V> IntoIterator for &'a RocDict<K, V> {
     fn into_iter(self) -> Self::IntoIter {
         IntoIter {
             index: 0,
-            items: self.0.as_slice(),
+            items: self.data.as_slice(),
         }
     }
 }
@@ -107,6 +151,69 @@ impl<'a, K, V> Iterator for IntoIter<'a, K, V> {
     }
 }
 
+impl<K: PartialEq, V: PartialEq> PartialEq for RocDict<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        // Keys are distinct, so finding each of our entries in `other` is enough.
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.iter().any(|(k, v)| k == key && v == value))
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for RocDict<K, V> {}
+
+impl<K: PartialOrd, V: PartialOrd> PartialOrd for RocDict<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter_by_key().partial_cmp(other.iter_by_key())
+    }
+}
+
+impl<K: Ord, V: Ord> Ord for RocDict<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter_by_key().cmp(other.iter_by_key())
+    }
+}
+
+impl<K: Hash, V: Hash> Hash for RocDict<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash every entry on its own and add the results up, so the order of the
+        // entries doesn't matter.
+        let entries = self.iter().fold(0u64, |sum, entry| {
+            let mut hasher = EntryHasher::default();
+            entry.hash(&mut hasher);
+
+            sum.wrapping_add(hasher.finish())
+        });
+
+        self.len().hash(state);
+        entries.hash(state);
+    }
+}
+
+/// FNV-1a, used to hash the entries of a [`RocDict`] one at a time, since `core` has no
+/// hasher we could create ourselves.
+struct EntryHasher(u64);
+
+impl Default for EntryHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for EntryHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 impl<K: Debug, V: Debug> Debug for RocDict<K, V> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("RocDict ")?;
@@ -152,6 +259,18 @@ struct ValueFirst<K, V> {
 }
 
 impl<K, V> RocDictItem<K, V> {
+    fn new(key: K, value: V) -> Self {
+        if align_of::<K>() >= align_of::<V>() {
+            Self {
+                key_first: ManuallyDrop::new(KeyFirst { key, value }),
+            }
+        } else {
+            Self {
+                value_first: ManuallyDrop::new(ValueFirst { value, key }),
+            }
+        }
+    }
+
     fn key(&self) -> &K {
         if align_of::<K>() >= align_of::<V>() {
             unsafe { &self.key_first.key }
@@ -59,7 +59,7 @@ pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut
 
 #[cfg(test)]
 mod test_roc_std {
-    use roc_std::{RocBox, RocDec, RocList, RocResult, RocStr};
+    use roc_std::{RocBox, RocDec, RocDict, RocList, RocResult, RocStr};
 
     fn roc_str_byte_representation(string: &RocStr) -> [u8; RocStr::SIZE] {
         unsafe { core::mem::transmute_copy(string) }
@@ -296,6 +296,37 @@ mod test_roc_std {
         let example = RocDec::from_str("1234.5678").unwrap();
         assert_eq!(format!("{}", example), "1234.5678");
     }
+
+    fn hash_of<T: core::hash::Hash>(value: &T) -> u64 {
+        use core::hash::Hasher;
+        use std::collections::hash_map::DefaultHasher;
+
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn roc_dict_eq_ignores_order() {
+        let dict: RocDict<u8, u16> = [(1, 10), (2, 20), (3, 30)].into_iter().collect();
+        let reordered: RocDict<u8, u16> = [(3, 30), (1, 10), (2, 20)].into_iter().collect();
+
+        assert_eq!(dict, reordered);
+        assert_eq!(hash_of(&dict), hash_of(&reordered));
+        assert_eq!(dict.cmp(&reordered), core::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn roc_dict_ne_on_different_entries() {
+        let dict: RocDict<u8, u16> = [(1, 10), (2, 20)].into_iter().collect();
+        let other_value: RocDict<u8, u16> = [(2, 21), (1, 10)].into_iter().collect();
+        let fewer: RocDict<u8, u16> = [(1, 10)].into_iter().collect();
+
+        assert_ne!(dict, other_value);
+        assert_ne!(dict, fewer);
+        assert!(dict < other_value);
+        assert!(fewer < dict);
+    }
 }
 
 #[cfg(test)]
@@ -29,23 +29,20 @@ echo 'Generating builtin docs...'
 cargo --version
 rustc --version
 
-# We set ROC_DOCS_ROOT_DIR=builtins so that links will be generated relative to
+# We set --base-url /builtins so that links will be generated relative to
 # "/builtins/" rather than "/" - which is what we want based on how the server
 # is set up to serve them.
-export ROC_DOCS_URL_ROOT=/builtins
-
-cargo run --bin roc-docs crates/compiler/builtins/roc/*.roc
+cargo run --bin roc-docs -- --base-url /builtins crates/compiler/builtins/roc/*.roc
 mv generated-docs/*.* www/build # move all the .js, .css, etc. files to build/
 mv generated-docs/ www/build/builtins # move all the folders to build/builtins/
 
 echo 'Generating CLI example platform docs...'
-# Change ROC_DOCS_ROOT_DIR=builtins so that links will be generated relative to
+# Change --base-url so that links will be generated relative to
 # "/examples/cli/" rather than "/builtins/"
-export ROC_DOCS_URL_ROOT=/examples/cli
 
 # Until https://github.com/roc-lang/roc/issues/3280 is done,
 # manually exclude the Internal* modules and `main.roc`.
-ls examples/interactive/cli-platform/*.roc | grep -v Internal | grep -v main.roc | grep -v Effect.roc | xargs cargo run --bin roc-docs
+ls examples/interactive/cli-platform/*.roc | grep -v Internal | grep -v main.roc | grep -v Effect.roc | xargs cargo run --bin roc-docs -- --base-url /examples/cli
 
 mkdir www/build/examples
 rm generated-docs/*.* # we already copied over the *.js and *.css files earlier, so just drop these.